@@ -118,6 +118,10 @@ where
     C::Api: BabeApi<Block>,
     C::Api: BlockBuilder<Block>,
     C::Api: node_rpc::nft::NFTRuntimeApi<Block>,
+    C::Api: node_rpc::settlement::SettlementRuntimeApi<Block>,
+    C::Api: node_rpc::statistics::StatisticsRuntimeApi<Block>,
+    C::Api: node_rpc::state_health::StateHealthRuntimeApi<Block>,
+    C::Api: node_rpc::bridge::BridgeRuntimeApi<Block, AccountId, BlockNumber>,
     P: TransactionPool + 'static,
     SC: SelectChain<Block> + 'static,
     B: sc_client_api::Backend<Block> + Send + Sync + 'static,
@@ -126,9 +130,13 @@ where
     use node_rpc::compliance_manager::{ComplianceManager, ComplianceManagerApiServer};
     use node_rpc::{
         asset::{Asset, AssetApiServer},
+        bridge::{Bridge, BridgeApiServer},
         identity::{Identity, IdentityApiServer},
         nft::{NFTApiServer, NFT},
         pips::{Pips, PipsApiServer},
+        settlement::{Settlement, SettlementApiServer},
+        state_health::{StateHealth, StateHealthApiServer},
+        statistics::{Statistics, StatisticsApiServer},
         transaction_payment::{TransactionPayment, TransactionPaymentApiServer},
     };
     use pallet_group_rpc::{Group, GroupApiServer};
@@ -222,7 +230,11 @@ where
     io.merge(Asset::new(client.clone()).into_rpc())?;
     io.merge(Group::from(client.clone()).into_rpc())?;
     io.merge(ComplianceManager::new(client.clone()).into_rpc())?;
-    io.merge(NFT::new(client).into_rpc())?;
+    io.merge(NFT::new(client.clone()).into_rpc())?;
+    io.merge(Settlement::new(client.clone()).into_rpc())?;
+    io.merge(Statistics::new(client.clone()).into_rpc())?;
+    io.merge(StateHealth::new(client.clone()).into_rpc())?;
+    io.merge(Bridge::new(client).into_rpc())?;
 
     Ok(io)
 }
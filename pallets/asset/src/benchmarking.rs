@@ -29,8 +29,8 @@ use polymesh_common_utilities::{
 use polymesh_primitives::{
     asset::{AssetName, NonFungibleType},
     asset_metadata::{
-        AssetMetadataDescription, AssetMetadataKey, AssetMetadataName, AssetMetadataSpec,
-        AssetMetadataValue, AssetMetadataValueDetail,
+        AssetMetadataDescription, AssetMetadataKey, AssetMetadataLocalKey, AssetMetadataName,
+        AssetMetadataSpec, AssetMetadataValue, AssetMetadataValueDetail,
     },
     ticker::TICKER_LEN,
     AuthorizationData, NFTCollectionKeys, PortfolioName, Signatory, Ticker, Url,
@@ -274,6 +274,13 @@ benchmarks! {
         assert_eq!(Module::<T>::frozen(&ticker), false);
     }
 
+    set_lifecycle_state {
+        let (owner, ticker) = owned_ticker::<T>();
+    }: _(owner.origin, ticker, AssetLifecycleState::Suspended)
+    verify {
+        assert_eq!(Module::<T>::lifecycle_state(&ticker), AssetLifecycleState::Suspended);
+    }
+
     rename_asset {
         // New token name length.
         let n in 1 .. T::AssetNameMaxLength::get() as u32;
@@ -340,6 +347,26 @@ benchmarks! {
         }
     }
 
+    update_document {
+        let (owner, ticker) = owned_ticker::<T>();
+        Module::<T>::add_documents(owner.origin().into(), vec![make_document()], ticker)
+            .expect("Documents cannot be added");
+        let doc = make_document();
+    }: _(owner.origin, ticker, DocumentId(0), doc)
+    verify {
+        assert_eq!(Module::<T>::asset_document_version(ticker, DocumentId(0)), 1);
+    }
+
+    acknowledge_document {
+        let (owner, ticker) = owned_ticker::<T>();
+        Module::<T>::add_documents(owner.origin().into(), vec![make_document()], ticker)
+            .expect("Documents cannot be added");
+        let investor = user::<T>("investor", 0);
+    }: _(investor.origin, ticker, DocumentId(0))
+    verify {
+        assert!(DocumentAcknowledgements::<T>::contains_key((ticker, DocumentId(0), 0), investor.did()));
+    }
+
     set_funding_round {
         let f in 1 .. T::FundingRoundNameMaxLength::get() as u32;
 
@@ -575,4 +602,267 @@ benchmarks! {
             None,
         ).unwrap();
     }: _(user.origin, ticker, AssetMetadataKey::Local(AssetMetadataLocalKey(1)))
+
+    propose_authorized_supply {
+        let (owner, ticker) = owned_ticker::<T>();
+        let supply = Some((2_000_000 * POLY).into());
+    }: _(owner.origin, ticker, supply)
+    verify {
+        assert_eq!(Module::<T>::pending_authorized_supply(ticker), Some((owner.did(), supply)));
+    }
+
+    approve_authorized_supply {
+        let (owner, ticker) = owned_ticker::<T>();
+        let agent = UserBuilder::<T>::default().generate_did().build("agent");
+        pallet_external_agents::Module::<T>::unchecked_add_agent(ticker, agent.did(), AgentGroup::Full)?;
+        let supply = Some((2_000_000 * POLY).into());
+        Module::<T>::propose_authorized_supply(owner.origin().into(), ticker, supply)?;
+    }: _(agent.origin, ticker)
+    verify {
+        assert_eq!(Module::<T>::authorized_supply(ticker), supply);
+    }
+
+    set_ticker_expiry_periods {
+        let notice_period: T::Moment = 1000u32.into();
+        let grace_period: T::Moment = 2000u32.into();
+    }: _(RawOrigin::Root, notice_period, grace_period)
+    verify {
+        assert_eq!(Module::<T>::ticker_expiry_config(), TickerExpiryConfig { notice_period, grace_period });
+    }
+
+    set_large_issuance_threshold {
+        let threshold = Some(Permill::from_percent(10));
+        let timelock: T::Moment = 1000u32.into();
+    }: _(RawOrigin::Root, threshold, timelock)
+    verify {
+        assert_eq!(
+            Module::<T>::large_issuance_config(),
+            Some(LargeIssuanceConfig { threshold: Permill::from_percent(10), timelock })
+        );
+    }
+
+    propose_issuance {
+        let (owner, ticker) = owned_ticker::<T>();
+        <LargeIssuance<T>>::put(LargeIssuanceConfig {
+            threshold: Permill::from_percent(10),
+            timelock: 1000u32.into(),
+        });
+    }: _(owner.origin, ticker, (1_000_000 * POLY).into())
+    verify {
+        assert!(Module::<T>::pending_issuance(ticker).is_some());
+    }
+
+    approve_issuance {
+        let (owner, ticker) = owned_ticker::<T>();
+        let agent = UserBuilder::<T>::default().generate_did().build("agent");
+        pallet_external_agents::Module::<T>::unchecked_add_agent(ticker, agent.did(), AgentGroup::Full)?;
+        Module::<T>::propose_issuance(owner.origin().into(), ticker, (1_000_000 * POLY).into())?;
+    }: _(agent.origin, ticker)
+    verify {
+        assert_eq!(Module::<T>::token_details(ticker).total_supply, (2_000_000 * POLY).into());
+    }
+
+    cancel_issuance {
+        let (owner, ticker) = owned_ticker::<T>();
+        Module::<T>::propose_issuance(owner.origin().into(), ticker, (1_000_000 * POLY).into())?;
+    }: _(owner.origin, ticker)
+    verify {
+        assert!(Module::<T>::pending_issuance(ticker).is_none());
+    }
+
+    issuer_batch_transfer {
+        let t in 0 .. MAX_ISSUER_BATCH_TRANSFERS;
+
+        let (owner, ticker) = owned_ticker::<T>();
+        let from_portfolio = PortfolioId::default_portfolio(owner.did());
+        let transfers = (0..t)
+            .map(|i| {
+                let investor = UserBuilder::<T>::default().generate_did().seed(i).build("investor");
+                (PortfolioId::default_portfolio(investor.did()), 100u32.into())
+            })
+            .collect();
+    }: _(owner.origin, ticker, from_portfolio, transfers)
+
+    controller_transfer_batch {
+        let t in 0 .. MAX_CONTROLLER_BATCH_TRANSFERS;
+
+        let (owner, ticker) = owned_ticker::<T>();
+        let from_portfolio = PortfolioId::default_portfolio(owner.did());
+        // Tokens can only be forced into portfolios in the custody of the agent.
+        let portfolio_num = NextPortfolioNumber::get(&owner.did());
+        Portfolio::<T>::create_portfolio(owner.origin().into(), PortfolioName(vec![65u8; 5])).unwrap();
+        let to_portfolio = PortfolioId::user_portfolio(owner.did(), portfolio_num);
+        let transfers = (0..t)
+            .map(|_| (from_portfolio, to_portfolio, 100u32.into()))
+            .collect();
+    }: _(owner.origin, ticker, transfers)
+
+    remove_document_acknowledgements {
+        let l in 0 .. 1_000;
+
+        // A removed document with `l` acknowledgements of its only version.
+        let (owner, ticker) = owned_ticker::<T>();
+        Module::<T>::add_documents(owner.origin().into(), vec![make_document()], ticker)
+            .expect("Documents cannot be added");
+        let now = <pallet_timestamp::Pallet<T>>::get();
+        let key = (ticker, DocumentId(0), 0);
+        for i in 0..l {
+            DocumentAcknowledgements::<T>::insert(key, IdentityId::from(i as u128), now);
+        }
+        Module::<T>::remove_documents(owner.origin().into(), vec![DocumentId(0)], ticker)
+            .expect("Documents cannot be removed");
+    }: _(owner.origin, ticker, DocumentId(0), l)
+    verify {
+        assert_eq!(DocumentAcknowledgements::<T>::iter_prefix(key).count(), 0);
+    }
+
+    set_ticker_auto_renewal {
+        set_config::<T>();
+        let owner = owner::<T>();
+        let ticker = make_ticker::<T>(owner.origin().into(), None);
+    }: _(owner.origin, ticker, true)
+    verify {
+        assert!(Module::<T>::ticker_auto_renewal(ticker));
+    }
+
+    process_expiring_tickers {
+        let t in 0 .. MAX_EXPIRING_TICKERS_PER_BLOCK;
+
+        // All registrations are within the notice period and renewed automatically.
+        set_config::<T>();
+        <TickerExpiry<T>>::put(TickerExpiryConfig {
+            notice_period: (60u32 * 24 * 60 * 60).into(),
+            grace_period: 0u32.into(),
+        });
+        let owner = owner::<T>();
+        let mut registrations = Vec::new();
+        for i in 0..t {
+            let ticker = make_ticker::<T>(owner.origin().into(), Some(&[b'E', b'X', b'P', b'A' + i as u8]));
+            TickerAutoRenewal::insert(ticker, true);
+            registrations.push((ticker, Module::<T>::ticker_registration(ticker).expiry));
+        }
+    }: {
+        Module::<T>::process_expiring_tickers();
+    }
+    verify {
+        for (ticker, expiry) in registrations {
+            assert!(Module::<T>::ticker_registration(ticker).expiry > expiry);
+        }
+    }
+
+    accept_ticker_renewal_agent {
+        set_config::<T>();
+        let owner = owner::<T>();
+        let agent = UserBuilder::<T>::default().generate_did().build("agent");
+        let ticker = make_ticker::<T>(owner.origin().into(), None);
+        let auth_id = identity::Module::<T>::add_auth(
+            owner.did(),
+            Signatory::from(agent.did()),
+            AuthorizationData::BecomeTickerRenewalAgent(ticker),
+            None
+        );
+    }: _(agent.origin, auth_id)
+    verify {
+        assert_eq!(Module::<T>::ticker_renewal_agent(ticker), Some(agent.did()));
+    }
+
+    remove_ticker_renewal_agent {
+        set_config::<T>();
+        let owner = owner::<T>();
+        let agent = UserBuilder::<T>::default().generate_did().build("agent");
+        let ticker = make_ticker::<T>(owner.origin().into(), None);
+        TickerRenewalAgents::insert(ticker, agent.did());
+    }: _(owner.origin, ticker)
+    verify {
+        assert_eq!(Module::<T>::ticker_renewal_agent(ticker), None);
+    }
+
+    renew_ticker_registration {
+        set_config::<T>();
+        let owner = owner::<T>();
+        let agent = UserBuilder::<T>::default().generate_did().build("agent");
+        let ticker = make_ticker::<T>(owner.origin().into(), None);
+        TickerRenewalAgents::insert(ticker, agent.did());
+        let expiry = Module::<T>::ticker_registration(ticker).expiry;
+    }: _(agent.origin, ticker)
+    verify {
+        assert!(Module::<T>::ticker_registration(ticker).expiry > expiry);
+    }
+
+    schedule_issuance {
+        let (owner, ticker) = owned_ticker::<T>();
+        let at = frame_system::Pallet::<T>::block_number() + 10u32.into();
+        // All the blocks searched but the last one are full.
+        let full: Vec<_> = (0..MAX_SCHEDULED_ISSUANCES_PER_BLOCK as u64)
+            .map(ScheduledIssuanceId)
+            .collect();
+        for i in 0..MAX_SCHEDULED_ISSUANCE_BLOCKS - 1 {
+            ScheduledIssuancesAt::<T>::insert(at + i.into(), full.clone());
+        }
+        let last = at + (MAX_SCHEDULED_ISSUANCE_BLOCKS - 1).into();
+    }: _(owner.origin, ticker, (1_000 * POLY).into(), PortfolioKind::Default, at)
+    verify {
+        assert_eq!(Module::<T>::scheduled_issuances_at(last).len(), 1);
+    }
+
+    cancel_scheduled_issuance {
+        let (owner, ticker) = owned_ticker::<T>();
+        let at = frame_system::Pallet::<T>::block_number() + 10u32.into();
+        Module::<T>::schedule_issuance(
+            owner.origin().into(),
+            ticker,
+            (1_000 * POLY).into(),
+            PortfolioKind::Default,
+            at,
+        ).unwrap();
+        let id = Module::<T>::scheduled_issuance_counter();
+    }: _(owner.origin, ticker, id)
+    verify {
+        assert!(Module::<T>::scheduled_issuance(id).is_none());
+    }
+
+    execute_scheduled_issuances {
+        let i in 0 .. MAX_SCHEDULED_ISSUANCES_PER_BLOCK;
+
+        // Issuances into a user portfolio, which are moved from the default portfolio.
+        let (owner, ticker) = owned_ticker::<T>();
+        let portfolio_num = NextPortfolioNumber::get(&owner.did());
+        Portfolio::<T>::create_portfolio(owner.origin().into(), PortfolioName(vec![65u8; 5])).unwrap();
+        let at = frame_system::Pallet::<T>::block_number() + 10u32.into();
+        for _ in 0..i {
+            Module::<T>::schedule_issuance(
+                owner.origin().into(),
+                ticker,
+                (1_000 * POLY).into(),
+                PortfolioKind::User(portfolio_num),
+                at,
+            ).unwrap();
+        }
+    }: {
+        Module::<T>::process_scheduled_issuances(at);
+    }
+    verify {
+        let portfolio = PortfolioId::user_portfolio(owner.did(), portfolio_num);
+        assert_eq!(PortfolioAssetBalances::get(&portfolio, &ticker), 1_000 * POLY * i as u128);
+    }
+
+    migrate_ticker {
+        let h in 1 .. 1_000;
+        // The redirect of `ticker` uses up one of the entries.
+        let e in 1 .. 1_000;
+
+        let (owner, ticker) = owned_ticker::<T>();
+        for i in 1..h {
+            BalanceOf::insert(ticker, IdentityId::from(i as u128), 1);
+        }
+        for i in 1..e {
+            let key = AssetMetadataKey::Local(AssetMetadataLocalKey(i.into()));
+            AssetMetadataValues::insert(ticker, key, make_metadata_value::<T>());
+        }
+        let new = Ticker::from_slice_truncated(b"NEWTICKER".as_ref());
+    }: _(RawOrigin::Root, ticker, new, h, e)
+    verify {
+        assert_eq!(Module::<T>::ticker_migration(ticker), Some(new));
+        assert_eq!(Module::<T>::token_details(new).owner_did, owner.did());
+    }
 }
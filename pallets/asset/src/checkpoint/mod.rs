@@ -51,7 +51,7 @@ use frame_support::{
     traits::UnixTime,
 };
 use frame_system::ensure_root;
-use pallet_base::{try_next_pre, Error::CounterOverflow};
+use pallet_base::{try_next_pre, try_use_entry, Error::CounterOverflow};
 pub use polymesh_common_utilities::traits::checkpoint::{Event, WeightInfo};
 use polymesh_common_utilities::traits::checkpoint::{ScheduleId, StoredSchedule};
 use polymesh_common_utilities::{
@@ -305,6 +305,41 @@ decl_error! {
 }
 
 impl<T: Config> Module<T> {
+    /// Moves the checkpoints and checkpoint schedules of `old` to `new`.
+    /// Each checkpoint balance, checkpoint, balance update and schedule moved
+    /// uses up one of the `entries`.
+    pub(crate) fn migrate_ticker(old: Ticker, new: Ticker, entries: &mut u32) -> DispatchResult {
+        // Every checkpoint has a total supply.
+        for (id, supply) in TotalSupply::drain_prefix(old) {
+            try_use_entry::<T>(entries)?;
+            TotalSupply::insert(new, id, supply);
+            for (did, balance) in Balance::drain_prefix((old, id)) {
+                try_use_entry::<T>(entries)?;
+                Balance::insert((new, id), did, balance);
+            }
+        }
+        for (id, timestamp) in Timestamps::drain_prefix(old) {
+            try_use_entry::<T>(entries)?;
+            Timestamps::insert(new, id, timestamp);
+        }
+        for (did, updates) in BalanceUpdates::drain_prefix(old) {
+            try_use_entry::<T>(entries)?;
+            BalanceUpdates::insert(new, did, updates);
+        }
+        for (id, count) in ScheduleRefCount::drain_prefix(old) {
+            try_use_entry::<T>(entries)?;
+            ScheduleRefCount::insert(new, id, count);
+        }
+        for (id, points) in SchedulePoints::drain_prefix(old) {
+            try_use_entry::<T>(entries)?;
+            SchedulePoints::insert(new, id, points);
+        }
+        CheckpointIdSequence::swap(old, new);
+        ScheduleIdSequence::swap(old, new);
+        Schedules::swap(old, new);
+        Ok(())
+    }
+
     /// Does checkpoint with ID `cp_id` exist for `ticker`?
     pub fn checkpoint_exists(ticker: &Ticker, cp: CheckpointId) -> bool {
         (CheckpointId(1)..=CheckpointIdSequence::get(ticker)).contains(&cp)
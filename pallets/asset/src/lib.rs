@@ -42,12 +42,14 @@
 //! - `unfreeze` - Unfreezes transfers and minting of a given token.
 //! - `rename_asset` - Renames a given asset.
 //! - `controller_transfer` - Forces a transfer between two DID.
+//! - `controller_transfer_batch` - Forces several transfers between portfolios at once.
 //! - `issue` - Function is used to issue(or mint) new tokens to the caller.
 //! - `redeem` - Redeems tokens from the caller's default portfolio.
 //! - `make_divisible` - Change the divisibility of the token to divisible.
 //! - `can_transfer` - Checks whether a transaction with given parameters can take place or not.
 //! - `add_documents` - Add documents for a given token.
 //! - `remove_documents` - Remove documents for a given token.
+//! - `remove_document_acknowledgements` - Removes the acknowledgements and versions of a removed document.
 //! - `set_funding_round` - Sets the name of the current funding round.
 //! - `update_identifiers` - Updates the asset identifiers.
 //! - `set_asset_metadata` - Set asset metadata value.
@@ -55,11 +57,28 @@
 //! - `register_asset_metadata_local_type` - Register asset metadata local type.
 //! - `register_asset_metadata_global_type` - Register asset metadata global type.
 //! - `redeem_from_portfolio` - Redeems tokens from the caller's portfolio.
+//! - `propose_authorized_supply` - Proposes a new authorized supply for a given token.
+//! - `approve_authorized_supply` - Approves the authorized supply proposed by another agent.
+//! - `set_ticker_expiry_periods` - Sets the notice and grace periods of ticker registrations.
+//! - `set_ticker_auto_renewal` - Enables or disables the automatic renewal of a ticker registration.
+//! - `accept_ticker_renewal_agent` - Accepts an authorization to renew a ticker registration.
+//! - `remove_ticker_renewal_agent` - Removes the identity allowed to renew a ticker registration.
+//! - `renew_ticker_registration` - Renews a ticker registration at the cost of the caller.
+//! - `set_lifecycle_state` - Moves a token to another lifecycle state.
+//! - `set_large_issuance_threshold` - Sets the share of the supply above which issuances must be approved.
+//! - `propose_issuance` - Proposes a large issuance of a given token.
+//! - `approve_issuance` - Approves and executes a large issuance proposed by another agent.
+//! - `cancel_issuance` - Cancels a pending large issuance.
+//! - `schedule_issuance` - Schedules an issuance into a portfolio of the caller at a future block.
+//! - `cancel_scheduled_issuance` - Cancels an issuance scheduled by `schedule_issuance`.
+//! - `update_document` - Replaces a document with a new version, keeping the previous one.
+//! - `acknowledge_document` - Records the acknowledgement of the current version of a document.
 //!
 //! ### Public Functions
 //!
 //! - `ticker_registration` - Provide ticker registration details.
 //! - `ticker_registration_config` - Provide the ticker registration configuration details.
+//! - `ticker_expiry_config` - Provide the notice and grace periods of ticker registrations.
 //! - `token_details` - Returns details of the token.
 //! - `balance_of` - Returns the balance of the DID corresponds to the ticker.
 //! - `identifiers` - It provides the identifiers for a given ticker.
@@ -68,6 +87,9 @@
 //! - `extension_details` - It provides the list of Smart extension added for the given tokens.
 //! - `extensions` - It provides the list of Smart extension added for the given tokens and for the given type.
 //! - `frozen` - It tells whether the given ticker is frozen or not.
+//! - `lifecycle_state` - It provides the lifecycle state of a given ticker.
+//! - `verify_document_hashes` - It checks a list of document hashes against the documents of a given ticker.
+//! - `describe_asset` - It provides the descriptive metadata of a given ticker in a single structure.
 //! - `is_ticker_available` - It checks whether the given ticker is available or not.
 //! - `is_ticker_registry_valid` - It checks whether the ticker is owned by a given IdentityId or not.
 //! - `is_ticker_available_or_registered_to` - It provides the status of a given ticker.
@@ -88,43 +110,89 @@ use core::result::Result as StdResult;
 use currency::*;
 use frame_support::{
     decl_error, decl_module, decl_storage,
-    dispatch::{DispatchError, DispatchResult},
+    dispatch::{CallMetadata, DispatchError, DispatchResult, Weight},
     ensure, fail,
     traits::Get,
+    IterableStorageDoubleMap,
 };
 use frame_system::ensure_root;
 use pallet_base::{
-    ensure_opt_string_limited, ensure_string_limited, try_next_pre, Error::CounterOverflow,
+    ensure_opt_string_limited, ensure_string_limited, try_next_pre, try_use_entry,
+    Error::CounterOverflow,
 };
 use pallet_identity::{self as identity, PermissionedCallOriginData};
-pub use polymesh_common_utilities::traits::asset::{Config, Event, RawEvent, WeightInfo};
+use pallet_permissions::with_call_metadata;
+pub use polymesh_common_utilities::traits::asset::{
+    Config, Event, RawEvent, ScheduledIssuanceId, WeightInfo,
+};
 use polymesh_common_utilities::{
     asset::{AssetFnTrait, AssetSubTrait},
     compliance_manager::Config as ComplianceManagerConfig,
     constants::*,
     protocol_fee::{ChargeProtocolFee, ProtocolOp},
     traits::nft::NFTTrait,
-    with_transaction, SystematicIssuers,
+    transaction_payment::CddAndFeeDetails,
+    with_transaction, SystematicIssuers, GC_DID,
 };
 use polymesh_primitives::{
     agent::AgentGroup,
-    asset::{AssetName, AssetType, CustomAssetTypeId, FundingRoundName, GranularCanTransferResult},
+    asset::{
+        AssetDescription, AssetHolders, AssetLifecycleState, AssetName, AssetType,
+        AuthorizedCapital, CustomAssetTypeId, FundingRoundName, GranularCanTransferResult,
+        NonFungibleType,
+    },
     asset_metadata::{
         AssetMetadataGlobalKey, AssetMetadataKey, AssetMetadataLocalKey, AssetMetadataName,
         AssetMetadataSpec, AssetMetadataValue, AssetMetadataValueDetail,
     },
     calendar::CheckpointId,
     ethereum::{self, EcdsaSignature, EthereumAddress},
-    extract_auth, storage_migration_ver,
+    extract_auth,
+    statistics::AssetScope,
+    storage_migration_ver,
     transfer_compliance::TransferConditionResult,
-    AssetIdentifier, Balance, Document, DocumentId, IdentityId, PortfolioId, PortfolioKind,
-    ScopeId, SecondaryKey, Ticker,
+    AssetIdentifier, Balance, ClaimType, Document, DocumentHash, DocumentHashCheck, DocumentId,
+    IdentityId, Moment, PortfolioId, PortfolioKind, Scope, ScopeId, SecondaryKey, Ticker,
 };
 use scale_info::TypeInfo;
-use sp_runtime::traits::Zero;
+use sp_runtime::traits::{One, Saturating, Zero};
+use sp_runtime::{Permill, SaturatedConversion};
 #[cfg(feature = "std")]
 use sp_runtime::{Deserialize, Serialize};
-use sp_std::{convert::TryFrom, prelude::*};
+use sp_std::{collections::btree_set::BTreeSet, convert::TryFrom, prelude::*};
+
+/// Maximum number of `ExpiringTickers` entries and empty buckets processed in a block.
+pub const MAX_EXPIRING_TICKERS_PER_BLOCK: u32 = 16;
+
+/// Length, in milliseconds, of the expiry buckets of `ExpiringTickers`.
+pub const EXPIRING_TICKERS_BUCKET_LENGTH: u64 = 60 * 1000;
+
+/// Maximum number of transfers in an `issuer_batch_transfer` call.
+pub const MAX_ISSUER_BATCH_TRANSFERS: u32 = 100;
+
+/// Maximum number of transfers in a `controller_transfer_batch` call.
+pub const MAX_CONTROLLER_BATCH_TRANSFERS: u32 = 100;
+
+/// Claim types that are scoped to a ticker, rescoped when the asset is migrated.
+const TICKER_SCOPED_CLAIM_TYPES: [ClaimType; 11] = [
+    ClaimType::Accredited,
+    ClaimType::Affiliate,
+    ClaimType::BuyLockup,
+    ClaimType::SellLockup,
+    ClaimType::KnowYourCustomer,
+    ClaimType::Jurisdiction,
+    ClaimType::Exempted,
+    ClaimType::Blocked,
+    ClaimType::InvestorUniqueness,
+    ClaimType::InvestorUniquenessV2,
+    ClaimType::RiskScore,
+];
+
+/// Maximum number of scheduled issuances executed in a block.
+pub const MAX_SCHEDULED_ISSUANCES_PER_BLOCK: u32 = 16;
+
+/// Maximum number of blocks, starting at the requested one, searched for room for a scheduled issuance.
+pub const MAX_SCHEDULED_ISSUANCE_BLOCKS: u32 = 16;
 
 type Checkpoint<T> = checkpoint::Module<T>;
 type ExternalAgents<T> = pallet_external_agents::Module<T>;
@@ -169,6 +237,53 @@ pub struct TickerRegistrationConfig<U> {
     pub registration_length: Option<U>,
 }
 
+/// Periods around the expiry of ticker registrations.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, TypeInfo, Clone, Default, PartialEq, Debug)]
+pub struct TickerExpiryConfig<U> {
+    /// How long before its expiry a registration is announced as expiring soon,
+    /// and renewed if auto-renewal is enabled.
+    pub notice_period: U,
+    /// How long after its expiry only the previous owner can register the ticker again.
+    pub grace_period: U,
+}
+
+/// Issuances that must be proposed and approved rather than issued directly.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, TypeInfo, Clone, Default, PartialEq, Debug)]
+pub struct LargeIssuanceConfig<U> {
+    /// Share of the current total supply above which an issuance is large.
+    pub threshold: Permill,
+    /// How long after its proposal a large issuance can be approved.
+    pub timelock: U,
+}
+
+/// A large issuance proposed by an agent, awaiting approval.
+#[derive(Encode, Decode, TypeInfo, Clone, PartialEq, Debug)]
+pub struct PendingIssuance<U> {
+    /// The agent who proposed the issuance, and to whose default portfolio it is issued.
+    pub proposer: IdentityId,
+    /// Amount of tokens to issue.
+    pub amount: Balance,
+    /// When the issuance can be approved.
+    pub executable_at: U,
+}
+
+/// An issuance scheduled by an agent, executed at a future block.
+#[derive(Encode, Decode, TypeInfo, Clone, PartialEq, Debug)]
+pub struct ScheduledIssuance<BlockNumber> {
+    /// The agent who scheduled the issuance.
+    pub agent: IdentityId,
+    /// The ticker of the token to issue.
+    pub ticker: Ticker,
+    /// The portfolio of the agent the tokens are issued into.
+    pub portfolio: PortfolioId,
+    /// Amount of tokens to issue.
+    pub amount: Balance,
+    /// Block in which the issuance is executed.
+    pub at: BlockNumber,
+}
+
 /// Enum that represents the current status of a ticker.
 #[derive(Encode, Decode, Clone, Eq, PartialEq, Debug)]
 pub enum TickerRegistrationStatus {
@@ -216,7 +331,7 @@ pub struct ClassicTickerRegistration {
     pub is_created: bool,
 }
 
-storage_migration_ver!(1);
+storage_migration_ver!(3);
 
 decl_storage! {
     trait Store for Module<T: Config> as Asset {
@@ -226,6 +341,22 @@ decl_storage! {
         /// Ticker registration config.
         /// (ticker) -> TickerRegistrationConfig
         pub TickerConfig get(fn ticker_registration_config) config(): TickerRegistrationConfig<T::Moment>;
+        /// Notice and grace periods of ticker registrations.
+        pub TickerExpiry get(fn ticker_expiry_config): TickerExpiryConfig<T::Moment>;
+        /// Tickers whose registration is renewed before it expires, at the cost of the owner.
+        /// ticker -> auto-renewal enabled
+        pub TickerAutoRenewal get(fn ticker_auto_renewal): map hasher(blake2_128_concat) Ticker => bool;
+        /// Identity renewing the registration of a ticker and paying its fees on behalf of the owner.
+        /// ticker -> renewal agent
+        pub TickerRenewalAgents get(fn ticker_renewal_agent):
+            map hasher(blake2_128_concat) Ticker => Option<IdentityId>;
+        /// Ticker registrations to announce before they expire, bucketed by the minute of their expiry.
+        /// expiry bucket -> ticker -> expiry
+        pub ExpiringTickers get(fn expiring_tickers):
+            double_map hasher(twox_64_concat) u64, hasher(blake2_128_concat) Ticker => Option<T::Moment>;
+        /// First bucket of `ExpiringTickers` which may still hold registrations to announce.
+        /// `None` until the first registration is queued.
+        pub NextExpiringTickersBucket get(fn next_expiring_tickers_bucket): Option<u64>;
         /// Details of the token corresponding to the token ticker.
         /// (ticker) -> SecurityToken details [returns SecurityToken struct]
         pub Tokens get(fn token_details): map hasher(blake2_128_concat) Ticker => SecurityToken;
@@ -268,6 +399,18 @@ decl_storage! {
         /// Per-ticker document ID counter.
         /// (ticker) -> doc_id
         pub AssetDocumentsIdSequence get(fn asset_documents_id_sequence): map hasher(blake2_128_concat) Ticker => DocumentId;
+        /// Current version of each document, incremented each time the document is updated.
+        /// (ticker, doc_id) -> version
+        pub AssetDocumentVersions get(fn asset_document_version):
+            double_map hasher(blake2_128_concat) Ticker, hasher(twox_64_concat) DocumentId => u32;
+        /// Previous versions of the documents attached to an Asset.
+        /// ticker -> (doc_id, version) -> document
+        pub AssetDocumentHistory get(fn asset_document_history):
+            double_map hasher(blake2_128_concat) Ticker, hasher(twox_64_concat) (DocumentId, u32) => Option<Document>;
+        /// When identities acknowledged each version of the documents attached to an Asset.
+        /// (ticker, doc_id, version) -> DID -> moment
+        pub DocumentAcknowledgements get(fn document_acknowledgement):
+            double_map hasher(blake2_128_concat) (Ticker, DocumentId, u32), hasher(identity) IdentityId => Option<T::Moment>;
         /// Ticker registration details on Polymath Classic / Ethereum.
         pub ClassicTickers get(fn classic_ticker_registration): map hasher(blake2_128_concat) Ticker => Option<ClassicTickerRegistration>;
         /// Balances get stored on the basis of the `ScopeId`.
@@ -326,8 +469,62 @@ decl_storage! {
         /// Next Asset Metadata Global Key.
         pub AssetMetadataNextGlobalKey get(fn asset_metadata_next_global_key): AssetMetadataGlobalKey;
 
+        /// Maximum supply authorized for a ticker, i.e. its authorized share capital.
+        /// Issuance beyond it is rejected.
+        /// ticker -> authorized supply
+        pub AuthorizedSupply get(fn authorized_supply):
+            map hasher(blake2_128_concat) Ticker => Option<Balance>;
+        /// A change of the authorized supply proposed by an agent, awaiting the approval of another agent.
+        /// ticker -> (proposer DID, authorized supply)
+        pub PendingAuthorizedSupply get(fn pending_authorized_supply):
+            map hasher(blake2_128_concat) Ticker => Option<(IdentityId, Option<Balance>)>;
+
+        /// Issuances above a share of the current total supply must be proposed, then approved
+        /// by another agent or by governance once a timelock is over. Disabled if unset.
+        pub LargeIssuance get(fn large_issuance_config): Option<LargeIssuanceConfig<T::Moment>>;
+        /// Large issuances proposed by an agent, awaiting approval.
+        /// ticker -> pending issuance
+        pub PendingIssuances get(fn pending_issuance):
+            map hasher(blake2_128_concat) Ticker => Option<PendingIssuance<T::Moment>>;
+        /// The last scheduled issuance ID.
+        pub ScheduledIssuanceCounter get(fn scheduled_issuance_counter): ScheduledIssuanceId;
+        /// Issuances scheduled by agents, awaiting execution.
+        /// scheduled issuance ID -> scheduled issuance
+        pub ScheduledIssuances get(fn scheduled_issuance):
+            map hasher(twox_64_concat) ScheduledIssuanceId => Option<ScheduledIssuance<T::BlockNumber>>;
+        /// Scheduled issuances executed in a block, at most `MAX_SCHEDULED_ISSUANCES_PER_BLOCK`.
+        /// block number -> scheduled issuance IDs
+        pub ScheduledIssuancesAt get(fn scheduled_issuances_at):
+            map hasher(twox_64_concat) T::BlockNumber => Vec<ScheduledIssuanceId>;
+
+        /// Lifecycle state of a ticker, defining whether its tokens can be issued, transferred
+        /// or redeemed. Tickers without an entry are active.
+        /// ticker -> lifecycle state
+        pub LifecycleStates get(fn lifecycle_state):
+            map hasher(blake2_128_concat) Ticker => AssetLifecycleState;
+
+        /// When a holder opened their current position in a ticker, i.e. acquired tokens,
+        /// by transfer or issuance, while holding none. Topping up a position doesn't move it.
+        /// (ticker, DID) -> moment
+        pub AcquiredAt get(fn acquired_at):
+            double_map hasher(blake2_128_concat) Ticker, hasher(identity) IdentityId => Option<T::Moment>;
+
+        /// When acquisitions started being recorded. Holders without a recorded acquisition
+        /// are taken to have opened their position at that moment.
+        pub AcquisitionsTrackedSince get(fn acquisitions_tracked_since): T::Moment;
+
+        /// Tickers of migrated assets, redirecting to the ticker each asset has been migrated to.
+        /// old ticker -> new ticker
+        pub TickerMigrations get(fn ticker_migration):
+            map hasher(blake2_128_concat) Ticker => Option<Ticker>;
+
+        /// Tickers redirecting to a ticker, i.e. the previous tickers of the asset.
+        /// current ticker -> previous tickers
+        pub TickerMigrationSources get(fn ticker_migration_sources):
+            map hasher(blake2_128_concat) Ticker => Vec<Ticker>;
+
         /// Storage version.
-        StorageVersion get(fn storage_version) build(|_| Version::new(1)): Version;
+        StorageVersion get(fn storage_version) build(|_| Version::new(3)): Version;
     }
     add_extra_genesis {
         config(classic_migration_tickers): Vec<ClassicTickerImport>;
@@ -373,6 +570,46 @@ decl_module! {
         const AssetMetadataValueMaxLength: u32 = T::AssetMetadataValueMaxLength::get();
         const AssetMetadataTypeDefMaxLength: u32 = T::AssetMetadataTypeDefMaxLength::get();
 
+        fn on_initialize(now: T::BlockNumber) -> Weight {
+            Self::process_expiring_tickers()
+                .saturating_add(Self::process_scheduled_issuances(now))
+        }
+
+        fn on_runtime_upgrade() -> Weight {
+            use frame_support::IterableStorageMap;
+            use polymesh_primitives::storage_migrate_on;
+
+            // Frozen assets are suspended.
+            storage_migrate_on!(StorageVersion, 2, {
+                Frozen::iter()
+                    .filter(|(_, frozen)| *frozen)
+                    .for_each(|(ticker, _)| LifecycleStates::insert(ticker, AssetLifecycleState::Suspended));
+            });
+
+            // Registrations made before the queue existed are queued by expiry.
+            let mut weight = Weight::zero();
+            storage_migrate_on!(StorageVersion, 3, {
+                let now = <pallet_timestamp::Pallet<T>>::get();
+                let mut queued = 0u64;
+                let mut read = 0u64;
+                for (ticker, registration) in <Tickers<T>>::iter() {
+                    read += 1;
+                    match registration.expiry {
+                        Some(expiry) if expiry > now => {
+                            Self::queue_expiring_ticker(ticker, expiry);
+                            queued += 1;
+                        }
+                        _ => {}
+                    }
+                }
+                // Positions opened before acquisitions were recorded are held since the upgrade.
+                <AcquisitionsTrackedSince<T>>::put(now);
+                weight = T::DbWeight::get().reads_writes(read.saturating_add(1), queued.saturating_add(2));
+            });
+
+            weight
+        }
+
         /// Registers a new ticker or extends validity of an existing ticker.
         /// NB: Ticker validity does not get carry forward when renewing ticker.
         ///
@@ -518,6 +755,9 @@ decl_module! {
         /// * `ticker` of the token.
         /// * `amount` of tokens that get issued.
         ///
+        /// # Errors
+        /// - `IssuanceRequiresApproval` if `amount` is a large issuance, which must be proposed instead.
+        ///
         /// # Permissions
         /// * Asset
         /// * Portfolio
@@ -525,6 +765,7 @@ decl_module! {
         pub fn issue(origin, ticker: Ticker, amount: Balance) -> DispatchResult {
             // Ensure origin is agent with custody and permissions for default portfolio.
             let portfolio = Self::ensure_agent_with_custody_and_perms(origin, ticker, PortfolioKind::Default)?;
+            ensure!(!Self::is_large_issuance(&ticker, amount), Error::<T>::IssuanceRequiresApproval);
             Self::_mint(&ticker, portfolio.did, amount, Some(ProtocolOp::AssetIssue))
         }
 
@@ -579,6 +820,7 @@ decl_module! {
         }
 
         /// Remove documents for a given token.
+        /// Their previous versions and acknowledgements are removed with `remove_document_acknowledgements`.
         ///
         /// # Arguments
         /// * `origin` is a signer that has permissions to act as an agent of `ticker`.
@@ -919,6 +1161,476 @@ decl_module! {
         pub fn remove_metadata_value(origin, ticker: Ticker, metadata_key: AssetMetadataKey) -> DispatchResult {
             Self::base_remove_metadata_value(origin, ticker, metadata_key)
         }
+
+        /// Proposes a new authorized supply for `ticker`, which caps the total supply that can be issued.
+        /// The proposal only takes effect once approved by another agent of the asset,
+        /// and replaces any earlier pending proposal.
+        ///
+        /// # Arguments
+        /// * `origin` - the secondary key of the sender.
+        /// * `ticker` - the ticker of the token.
+        /// * `supply` - the new authorized supply, or `None` to remove the cap.
+        ///
+        /// # Errors
+        /// - `AuthorizedSupplyBelowIssued` if `supply` is below the current total supply.
+        /// - `TotalSupplyAboveLimit` if `supply` is above `MAX_SUPPLY`.
+        ///
+        /// # Permissions
+        /// * Asset
+        #[weight = <T as Config>::WeightInfo::propose_authorized_supply()]
+        pub fn propose_authorized_supply(origin, ticker: Ticker, supply: Option<Balance>) -> DispatchResult {
+            Self::base_propose_authorized_supply(origin, ticker, supply)
+        }
+
+        /// Approves the authorized supply of `ticker` proposed by another agent.
+        ///
+        /// # Arguments
+        /// * `origin` - the secondary key of the sender.
+        /// * `ticker` - the ticker of the token.
+        ///
+        /// # Errors
+        /// - `NoPendingAuthorizedSupply` if no authorized supply has been proposed.
+        /// - `AuthorizedSupplyApproverIsProposer` if the caller proposed the authorized supply.
+        /// - `AuthorizedSupplyBelowIssued` if tokens issued since the proposal exceed it.
+        ///
+        /// # Permissions
+        /// * Asset
+        #[weight = <T as Config>::WeightInfo::approve_authorized_supply()]
+        pub fn approve_authorized_supply(origin, ticker: Ticker) -> DispatchResult {
+            Self::base_approve_authorized_supply(origin, ticker)
+        }
+
+        /// Sets the notice and grace periods of ticker registrations.
+        ///
+        /// Registrations are announced by a `TickerExpiringSoon` event `notice_period` before they expire,
+        /// and only their previous owner can register them again during `grace_period` after they expire.
+        ///
+        /// # Arguments
+        /// * `origin` - must be root.
+        /// * `notice_period` - how long before its expiry a registration is announced and auto-renewed.
+        /// * `grace_period` - how long after its expiry a ticker is kept for its previous owner.
+        #[weight = <T as Config>::WeightInfo::set_ticker_expiry_periods()]
+        pub fn set_ticker_expiry_periods(origin, notice_period: T::Moment, grace_period: T::Moment) -> DispatchResult {
+            ensure_root(origin)?;
+            TickerExpiry::<T>::put(TickerExpiryConfig { notice_period, grace_period });
+            Self::deposit_event(RawEvent::TickerExpiryPeriodsSet(GC_DID, notice_period, grace_period));
+            Ok(())
+        }
+
+        /// Enables or disables the automatic renewal of the registration of `ticker`.
+        ///
+        /// A registration with auto-renewal enabled is renewed when it is announced as expiring soon.
+        /// The ticker registration fee is charged to the primary key of the owner for each renewal.
+        /// Auto-renewal is disabled when the ticker is transferred or its asset is created.
+        ///
+        /// # Arguments
+        /// * `origin` - the secondary key of the sender.
+        /// * `ticker` - the ticker registered to the caller.
+        /// * `enabled` - whether the registration is renewed automatically.
+        ///
+        /// # Errors
+        /// - `TickerRegistrationExpired` if the ticker isn't registered to the caller or its registration has expired.
+        ///
+        /// # Permissions
+        /// * Asset
+        #[weight = <T as Config>::WeightInfo::set_ticker_auto_renewal()]
+        pub fn set_ticker_auto_renewal(origin, ticker: Ticker, enabled: bool) -> DispatchResult {
+            let did = Identity::<T>::ensure_perms(origin)?;
+            ensure!(Self::is_ticker_registry_valid(&ticker, did), Error::<T>::TickerRegistrationExpired);
+            TickerAutoRenewal::insert(ticker, enabled);
+            Self::deposit_event(RawEvent::TickerAutoRenewalSet(did, ticker, enabled));
+            Ok(())
+        }
+
+        /// Accepts the `BecomeTickerRenewalAgent` authorization `auth_id`, making the caller
+        /// the renewal agent of the ticker.
+        ///
+        /// The renewal agent pays the ticker registration fee of the renewals of the registration,
+        /// including the automatic ones, without being granted any other permission over the ticker.
+        /// The renewal agent is removed when the ticker is transferred or its asset is created.
+        ///
+        /// # Arguments
+        /// * `origin` - the secondary key of the renewal agent.
+        /// * `auth_id` - the id of the authorization, issued by the owner of the ticker.
+        ///
+        /// # Errors
+        /// - `AssetAlreadyCreated` if the asset of the ticker has been created.
+        /// - `TickerRegistrationExpired` if the authorizer no longer owns the ticker or its registration has expired.
+        #[weight = <T as Config>::WeightInfo::accept_ticker_renewal_agent()]
+        pub fn accept_ticker_renewal_agent(origin, auth_id: u64) -> DispatchResult {
+            Self::base_accept_ticker_renewal_agent(origin, auth_id)
+        }
+
+        /// Removes the renewal agent of `ticker`.
+        /// Either the owner of the ticker or the renewal agent itself can remove it.
+        ///
+        /// # Arguments
+        /// * `origin` - the secondary key of the owner or of the renewal agent.
+        /// * `ticker` - the ticker to remove the renewal agent of.
+        ///
+        /// # Errors
+        /// - `NoTickerRenewalAgent` if the ticker has no renewal agent.
+        /// - `UnauthorizedTickerRenewal` if the caller is neither the owner nor the renewal agent.
+        #[weight = <T as Config>::WeightInfo::remove_ticker_renewal_agent()]
+        pub fn remove_ticker_renewal_agent(origin, ticker: Ticker) -> DispatchResult {
+            let did = Identity::<T>::ensure_perms(origin)?;
+            let agent = Self::ticker_renewal_agent(ticker).ok_or(Error::<T>::NoTickerRenewalAgent)?;
+            ensure!(
+                did == agent || Self::maybe_ticker(&ticker).map_or(false, |r| r.owner == did),
+                Error::<T>::UnauthorizedTickerRenewal
+            );
+            TickerRenewalAgents::remove(ticker);
+            Self::deposit_event(RawEvent::TickerRenewalAgentSet(did, ticker, None));
+            Ok(())
+        }
+
+        /// Renews the registration of `ticker` by the registration length.
+        ///
+        /// The ticker registration fee is charged to the caller, who is either the owner of the ticker
+        /// or its renewal agent.
+        ///
+        /// # Arguments
+        /// * `origin` - the secondary key of the owner or of the renewal agent.
+        /// * `ticker` - the ticker to renew the registration of.
+        ///
+        /// # Errors
+        /// - `TickerRegistrationExpired` if the ticker isn't registered or its registration has expired.
+        /// - `TickerRegistrationHasNoExpiry` if the registration of the ticker doesn't expire.
+        /// - `UnauthorizedTickerRenewal` if the caller is neither the owner nor the renewal agent.
+        ///
+        /// # Permissions
+        /// * Asset
+        #[weight = <T as Config>::WeightInfo::renew_ticker_registration()]
+        pub fn renew_ticker_registration(origin, ticker: Ticker) -> DispatchResult {
+            let did = Identity::<T>::ensure_perms(origin)?;
+            let registration = Self::maybe_ticker(&ticker).ok_or(Error::<T>::TickerRegistrationExpired)?;
+            ensure!(
+                Self::is_ticker_registry_valid(&ticker, registration.owner),
+                Error::<T>::TickerRegistrationExpired
+            );
+            let expiry = registration.expiry.ok_or(Error::<T>::TickerRegistrationHasNoExpiry)?;
+            ensure!(
+                did == registration.owner || Self::ticker_renewal_agent(ticker) == Some(did),
+                Error::<T>::UnauthorizedTickerRenewal
+            );
+
+            T::ProtocolFee::charge_fee(ProtocolOp::AssetRegisterTicker)?;
+            Self::extend_ticker_registration(ticker, registration.owner, expiry);
+            Ok(())
+        }
+
+        /// Moves a token to another lifecycle state, e.g. once it has matured or to retire it.
+        ///
+        /// Fungible tokens start in `PreIssuance` and become `Active` when first issued.
+        /// `Suspended` is the state of frozen tokens.
+        ///
+        /// # Arguments
+        /// * `origin` - the secondary key of the sender.
+        /// * `ticker` - the ticker of the token.
+        /// * `state` - the new lifecycle state of the token.
+        ///
+        /// # Errors
+        /// - `InvalidLifecycleTransition` if the token can't be moved from its current state to `state`.
+        ///
+        /// # Permissions
+        /// * Asset
+        #[weight = <T as Config>::WeightInfo::set_lifecycle_state()]
+        pub fn set_lifecycle_state(origin, ticker: Ticker, state: AssetLifecycleState) -> DispatchResult {
+            let did = <ExternalAgents<T>>::ensure_perms(origin, ticker)?;
+            Self::ensure_asset_exists(&ticker)?;
+            Self::transition_lifecycle_state(did, ticker, state)
+        }
+
+        /// Sets the share of the current total supply above which issuances must be proposed
+        /// and approved, rather than issued directly.
+        ///
+        /// # Arguments
+        /// * `origin` - must be root.
+        /// * `threshold` - the share of the total supply, or `None` to disable the approval workflow.
+        /// * `timelock` - how long after its proposal a large issuance can be approved.
+        #[weight = <T as Config>::WeightInfo::set_large_issuance_threshold()]
+        pub fn set_large_issuance_threshold(origin, threshold: Option<Permill>, timelock: T::Moment) -> DispatchResult {
+            ensure_root(origin)?;
+            match threshold {
+                Some(threshold) => LargeIssuance::<T>::put(LargeIssuanceConfig { threshold, timelock }),
+                None => LargeIssuance::<T>::kill(),
+            }
+            Self::deposit_event(RawEvent::LargeIssuanceThresholdSet(GC_DID, threshold, timelock));
+            Ok(())
+        }
+
+        /// Proposes to issue `amount` tokens of `ticker` to the caller's default portfolio.
+        /// The issuance is executed once approved, and replaces any earlier pending proposal.
+        ///
+        /// # Arguments
+        /// * `origin` - a signer that has permissions to act as an agent of `ticker`.
+        /// * `ticker` - the ticker of the token.
+        /// * `amount` - the amount of tokens to issue.
+        ///
+        /// # Permissions
+        /// * Asset
+        /// * Portfolio
+        #[weight = <T as Config>::WeightInfo::propose_issuance()]
+        pub fn propose_issuance(origin, ticker: Ticker, amount: Balance) -> DispatchResult {
+            Self::base_propose_issuance(origin, ticker, amount)
+        }
+
+        /// Approves the issuance of `ticker` proposed by another agent, and issues its tokens.
+        ///
+        /// # Arguments
+        /// * `origin` - root, or a signer that has permissions to act as an agent of `ticker`.
+        /// * `ticker` - the ticker of the token.
+        ///
+        /// # Errors
+        /// - `NoPendingIssuance` if no issuance has been proposed.
+        /// - `IssuanceApproverIsProposer` if the caller proposed the issuance.
+        /// - `IssuanceTimelockNotExpired` if the timelock of the issuance is not over.
+        ///
+        /// # Permissions
+        /// * Asset
+        #[weight = <T as Config>::WeightInfo::approve_issuance()]
+        pub fn approve_issuance(origin, ticker: Ticker) -> DispatchResult {
+            Self::base_approve_issuance(origin, ticker)
+        }
+
+        /// Cancels the pending issuance of `ticker`.
+        ///
+        /// # Arguments
+        /// * `origin` - a signer that has permissions to act as an agent of `ticker`.
+        /// * `ticker` - the ticker of the token.
+        ///
+        /// # Errors
+        /// - `NoPendingIssuance` if no issuance has been proposed.
+        ///
+        /// # Permissions
+        /// * Asset
+        #[weight = <T as Config>::WeightInfo::cancel_issuance()]
+        pub fn cancel_issuance(origin, ticker: Ticker) -> DispatchResult {
+            let did = <ExternalAgents<T>>::ensure_perms(origin, ticker)?;
+            ensure!(PendingIssuances::<T>::contains_key(ticker), Error::<T>::NoPendingIssuance);
+            PendingIssuances::<T>::remove(ticker);
+            Self::deposit_event(RawEvent::IssuanceCancelled(did, ticker));
+            Ok(())
+        }
+
+        /// Transfers tokens from `from_portfolio` to each of the given portfolios.
+        /// Each transfer is checked against the compliance rules of the asset like any other transfer,
+        /// and is skipped if it can't be made. The status code of every transfer is reported
+        /// in the `IssuerBatchTransfer` event.
+        ///
+        /// # Arguments
+        /// * `origin` - a signer that has permissions to act as an agent of `ticker`.
+        /// * `ticker` - the ticker of the token.
+        /// * `from_portfolio` - portfolio, in custody of the caller, the tokens are transferred from.
+        /// * `transfers` - receiving portfolios and amounts, at most `MAX_ISSUER_BATCH_TRANSFERS`.
+        ///
+        /// # Errors
+        /// - `TooManyBatchTransfers` if there are more than `MAX_ISSUER_BATCH_TRANSFERS` transfers.
+        ///
+        /// # Permissions
+        /// * Asset
+        /// * Portfolio
+        #[weight = <T as Config>::WeightInfo::issuer_batch_transfer(transfers.len() as u32)]
+        pub fn issuer_batch_transfer(
+            origin,
+            ticker: Ticker,
+            from_portfolio: PortfolioId,
+            transfers: Vec<(PortfolioId, Balance)>,
+        ) -> DispatchResult {
+            Self::base_issuer_batch_transfer(origin, ticker, from_portfolio, transfers)
+        }
+
+        /// Schedules the issuance of `amount` tokens of `ticker` into a portfolio of the caller at block `at`.
+        /// Several issuances can be scheduled to mint the tranches of a vesting program.
+        ///
+        /// The issuance is executed in the first block, out of the `MAX_SCHEDULED_ISSUANCE_BLOCKS`
+        /// starting at `at`, with room for it.
+        /// It is dropped if it can't be executed, e.g. if the caller is no longer permissioned to
+        /// issue tokens of `ticker`, or if it has become a large issuance.
+        /// The protocol fee of the issuance is charged when it is scheduled.
+        ///
+        /// # Arguments
+        /// * `origin` - a signer that has permissions to act as an agent of `ticker`.
+        /// * `ticker` - the ticker of the token.
+        /// * `amount` - the amount of tokens to issue.
+        /// * `portfolio` - the kind of the portfolio of the caller the tokens are issued into.
+        /// * `at` - the block in which the tokens are issued.
+        ///
+        /// # Errors
+        /// - `InvalidIssuanceBlock` if `at` is not a future block.
+        /// - `IssuanceRequiresApproval` if the issuance is above the large issuance threshold.
+        /// - `ScheduledIssuanceBlocksFull` if none of the blocks searched has room for the issuance.
+        ///
+        /// # Permissions
+        /// * Asset
+        /// * Portfolio
+        #[weight = <T as Config>::WeightInfo::schedule_issuance()]
+        pub fn schedule_issuance(
+            origin,
+            ticker: Ticker,
+            amount: Balance,
+            portfolio: PortfolioKind,
+            at: T::BlockNumber,
+        ) -> DispatchResult {
+            Self::base_schedule_issuance(origin, ticker, amount, portfolio, at)
+        }
+
+        /// Cancels the scheduled issuance `id` of `ticker`.
+        ///
+        /// # Arguments
+        /// * `origin` - a signer that has permissions to act as an agent of `ticker`.
+        /// * `ticker` - the ticker of the token.
+        /// * `id` - the ID of the scheduled issuance.
+        ///
+        /// # Errors
+        /// - `NoSuchScheduledIssuance` if there is no scheduled issuance `id` of `ticker`,
+        ///   or of a ticker migrated to `ticker`.
+        ///
+        /// # Permissions
+        /// * Asset
+        #[weight = <T as Config>::WeightInfo::cancel_scheduled_issuance()]
+        pub fn cancel_scheduled_issuance(origin, ticker: Ticker, id: ScheduledIssuanceId) -> DispatchResult {
+            let did = <ExternalAgents<T>>::ensure_perms(origin, ticker)?;
+            let issuance = Self::scheduled_issuance(id)
+                .filter(|issuance| {
+                    issuance.ticker == ticker
+                        || Self::ticker_migration(issuance.ticker) == Some(ticker)
+                })
+                .ok_or(Error::<T>::NoSuchScheduledIssuance)?;
+            ScheduledIssuancesAt::<T>::mutate(issuance.at, |ids| ids.retain(|i| *i != id));
+            ScheduledIssuances::<T>::remove(id);
+            Self::deposit_event(RawEvent::ScheduledIssuanceCancelled(did, ticker, id));
+            Ok(())
+        }
+
+        /// Moves the asset `old` to the ticker `new`, leaving a redirect from `old` to `new`.
+        /// Tickers previously redirecting to `old` are redirected to `new`.
+        ///
+        /// Registration, balances, portfolio balances, checkpoints, agents, documents, metadata,
+        /// compliance, statistics, transfer restrictions, venue filtering, corporate actions
+        /// and offerings are moved to `new`, and the claims of the holders scoped to `old` are
+        /// rescoped to `new`. Issuances scheduled for `old` are executed on `new`.
+        ///
+        /// # Arguments
+        /// * `origin` - must be root.
+        /// * `old` - the ticker of the asset to migrate.
+        /// * `new` - the ticker to migrate the asset to, which must be available or registered to the asset owner.
+        /// * `holders` - the number of identities holding a balance of `old`.
+        /// * `entries` - the number of other storage entries to move, e.g. checkpoint balances,
+        ///   documents, claims or corporate actions.
+        ///
+        /// # Errors
+        /// - `NoSuchAsset` if `old` is not an asset.
+        /// - `UnexpectedNonFungibleToken` if `old` is a non-fungible asset.
+        /// - `TickerAlreadyRegistered` if `new` is registered to another identity than the asset owner.
+        /// - `TooManyTickerHolders` if `old` has more than `holders` holders.
+        /// - `BalanceNotMigratable` if some balance of `old` is locked or labelled.
+        /// - `TooLong` if there are more than `entries` storage entries to move.
+        /// - `DistributionNotMigratable` if a capital distribution of `old` may still pay holders.
+        #[weight = <T as Config>::WeightInfo::migrate_ticker(*holders, *entries)]
+        pub fn migrate_ticker(origin, old: Ticker, new: Ticker, holders: u32, entries: u32) -> DispatchResult {
+            ensure_root(origin)?;
+            Self::base_migrate_ticker(old, new, holders, entries)
+        }
+
+        /// Replaces the document `id` of `ticker` with a new version.
+        /// The previous version is kept in the document history, along with its acknowledgements.
+        ///
+        /// # Arguments
+        /// * `origin` - a signer that has permissions to act as an agent of `ticker`.
+        /// * `ticker` - the ticker of the token.
+        /// * `id` - the id of the document.
+        /// * `doc` - the new version of the document.
+        ///
+        /// # Errors
+        /// - `NoSuchDoc` if `ticker` has no document `id`.
+        ///
+        /// # Permissions
+        /// * Asset
+        #[weight = <T as Config>::WeightInfo::update_document()]
+        pub fn update_document(origin, ticker: Ticker, id: DocumentId, doc: Document) -> DispatchResult {
+            Self::base_update_document(origin, ticker, id, doc)
+        }
+
+        /// Records that the caller's identity acknowledged the current version of the document `id` of `ticker`.
+        ///
+        /// # Arguments
+        /// * `origin` - the secondary key of the caller.
+        /// * `ticker` - the ticker of the token.
+        /// * `id` - the id of the document.
+        ///
+        /// # Errors
+        /// - `NoSuchDoc` if `ticker` has no document `id`.
+        /// - `DocumentAlreadyAcknowledged` if the caller already acknowledged the current version of the document.
+        #[weight = <T as Config>::WeightInfo::acknowledge_document()]
+        pub fn acknowledge_document(origin, ticker: Ticker, id: DocumentId) -> DispatchResult {
+            let did = Identity::<T>::ensure_perms(origin)?;
+            Self::ensure_doc_exists(&ticker, &id)?;
+            let version = Self::asset_document_version(ticker, id);
+            let key = (ticker, id, version);
+            ensure!(
+                !DocumentAcknowledgements::<T>::contains_key(key, did),
+                Error::<T>::DocumentAlreadyAcknowledged
+            );
+            DocumentAcknowledgements::<T>::insert(key, did, <pallet_timestamp::Pallet<T>>::get());
+            Self::deposit_event(RawEvent::DocumentAcknowledged(did, ticker, id, version));
+            Ok(())
+        }
+
+        /// Forces a batch of transfers of `ticker` between portfolios, bypassing compliance rules.
+        /// Either all the transfers are made or, if any of them fails, none is.
+        /// A `ControllerBatchTransfer` event is emitted for each transfer.
+        ///
+        /// # Arguments
+        /// * `origin` - a signer that has permissions to act as an agent of `ticker`.
+        /// * `ticker` - the ticker of the token.
+        /// * `transfers` - sending portfolio, receiving portfolio and amount of each transfer,
+        ///   at most `MAX_CONTROLLER_BATCH_TRANSFERS`. Every receiving portfolio must be
+        ///   in the custody of the caller.
+        ///
+        /// # Errors
+        /// - `TooManyBatchTransfers` if there are more than `MAX_CONTROLLER_BATCH_TRANSFERS` transfers.
+        /// - `UnauthorizedCustodian` if the caller isn't the custodian of a receiving portfolio.
+        /// - `SecondaryKeyNotAuthorizedForPortfolio` if the caller's secondary key doesn't have
+        ///   permission over a receiving portfolio.
+        /// - `PortfolioDoesNotExist` if a sending or receiving portfolio doesn't exist.
+        /// - `DestinationIsSamePortfolio` if a transfer is made to its sending portfolio.
+        /// - `InsufficientPortfolioBalance` if a sending portfolio doesn't have enough free,
+        ///   unlocked balance.
+        /// - any error of a single transfer, e.g. `InvalidGranularity`.
+        ///
+        /// # Permissions
+        /// * Asset
+        #[weight = <T as Config>::WeightInfo::controller_transfer_batch(transfers.len() as u32)]
+        pub fn controller_transfer_batch(
+            origin,
+            ticker: Ticker,
+            transfers: Vec<(PortfolioId, PortfolioId, Balance)>,
+        ) -> DispatchResult {
+            Self::base_controller_transfer_batch(origin, ticker, transfers)
+        }
+
+        /// Removes up to `limit` acknowledgements and previous versions of the removed document `id`
+        /// of `ticker`, from its latest version. Removing a version once all its acknowledgements
+        /// are removed counts towards `limit`, so everything is removed when the
+        /// `DocumentAcknowledgementsRemoved` event reports fewer than `limit` removals.
+        ///
+        /// # Arguments
+        /// * `origin` - a signer that has permissions to act as an agent of `ticker`.
+        /// * `ticker` - the ticker of the token.
+        /// * `id` - the id of the removed document.
+        /// * `limit` - the maximum number of acknowledgements and versions to remove.
+        ///
+        /// # Errors
+        /// - `NoSuchDoc` if `ticker` never had a document `id`.
+        /// - `DocumentNotRemoved` if the document `id` is still attached to `ticker`.
+        ///
+        /// # Permissions
+        /// * Asset
+        #[weight = <T as Config>::WeightInfo::remove_document_acknowledgements(*limit)]
+        pub fn remove_document_acknowledgements(origin, ticker: Ticker, id: DocumentId, limit: u32) -> DispatchResult {
+            Self::base_remove_document_acknowledgements(origin, ticker, id, limit)
+        }
     }
 }
 
@@ -1002,6 +1714,50 @@ decl_error! {
         AssetMetadataValueIsEmpty,
         /// Investor Uniqueness not allowed.
         InvestorUniquenessNotAllowed,
+        /// Issuing the amount would exceed the authorized supply of the asset.
+        AuthorizedSupplyExceeded,
+        /// The authorized supply is below the total supply of the asset.
+        AuthorizedSupplyBelowIssued,
+        /// No authorized supply has been proposed for the asset.
+        NoPendingAuthorizedSupply,
+        /// The authorized supply must be approved by an agent other than its proposer.
+        AuthorizedSupplyApproverIsProposer,
+        /// The token can't be moved from its current lifecycle state to the requested one.
+        InvalidLifecycleTransition,
+        /// Tokens can't be issued in the current lifecycle state of the asset.
+        IssuanceNotAllowed,
+        /// Tokens can't be redeemed in the current lifecycle state of the asset.
+        RedemptionNotAllowed,
+        /// The issuance is above the large issuance threshold and must be proposed instead.
+        IssuanceRequiresApproval,
+        /// No issuance has been proposed for the asset.
+        NoPendingIssuance,
+        /// The issuance must be approved by an agent other than its proposer.
+        IssuanceApproverIsProposer,
+        /// The timelock of the proposed issuance is not over.
+        IssuanceTimelockNotExpired,
+        /// More than `MAX_ISSUER_BATCH_TRANSFERS` or `MAX_CONTROLLER_BATCH_TRANSFERS` transfers were given.
+        TooManyBatchTransfers,
+        /// The block of a scheduled issuance must be in the future.
+        InvalidIssuanceBlock,
+        /// The scheduled issuance doesn't exist.
+        NoSuchScheduledIssuance,
+        /// The ticker registration doesn't expire and can't be renewed.
+        TickerRegistrationHasNoExpiry,
+        /// Only the owner of the ticker or its renewal agent can renew its registration.
+        UnauthorizedTickerRenewal,
+        /// The asset has more holders than given.
+        TooManyTickerHolders,
+        /// The ticker belongs to an asset that has been migrated to another ticker.
+        MigratedTicker,
+        /// The identity already acknowledged the current version of the document.
+        DocumentAlreadyAcknowledged,
+        /// The ticker has no renewal agent.
+        NoTickerRenewalAgent,
+        /// None of the `MAX_SCHEDULED_ISSUANCE_BLOCKS` blocks searched has room for the scheduled issuance.
+        ScheduledIssuanceBlocksFull,
+        /// The document is still attached to the asset.
+        DocumentNotRemoved,
     }
 }
 
@@ -1015,6 +1771,26 @@ impl<T: Config> AssetFnTrait<T::AccountId, T::RuntimeOrigin> for Module<T> {
         Self::balance_of(ticker, &who)
     }
 
+    fn acquired_at(ticker: &Ticker, did: IdentityId) -> Moment {
+        Self::acquired_at(ticker, did)
+            .unwrap_or_else(Self::acquisitions_tracked_since)
+            .saturated_into()
+    }
+
+    fn holders(ticker: &Ticker, start_after: Option<IdentityId>, limit: u32) -> Vec<IdentityId> {
+        let iter = match start_after {
+            Some(did) => {
+                let raw_key = BalanceOf::hashed_key_for(ticker, did);
+                BalanceOf::iter_prefix_from(ticker, raw_key)
+            }
+            None => BalanceOf::iter_prefix(ticker),
+        };
+        iter.filter(|(_, balance)| *balance > 0)
+            .map(|(did, _)| did)
+            .take(limit as usize)
+            .collect()
+    }
+
     fn create_asset(
         origin: T::RuntimeOrigin,
         name: AssetName,
@@ -1133,6 +1909,14 @@ impl<T: Config> AssetSubTrait for Module<T> {
         );
         Ok(())
     }
+
+    fn accept_ticker_transfer(to: IdentityId, auth_id: u64) -> DispatchResult {
+        Self::unsafe_accept_ticker_transfer(to, auth_id)
+    }
+
+    fn accept_asset_ownership_transfer(to: IdentityId, auth_id: u64) -> DispatchResult {
+        Self::unsafe_accept_token_ownership_transfer(to, auth_id)
+    }
 }
 
 /// All functions in the decl_module macro become part of the public interface of the module
@@ -1235,9 +2019,10 @@ impl<T: Config> Module<T> {
     pub fn is_ticker_available(ticker: &Ticker) -> bool {
         // Assumes uppercase ticker
         if let Some(ticker) = Self::maybe_ticker(ticker) {
+            let grace_period = Self::ticker_expiry_config().grace_period;
             ticker
                 .expiry
-                .filter(|&e| <pallet_timestamp::Pallet<T>>::get() > e)
+                .filter(|&e| <pallet_timestamp::Pallet<T>>::get() > e.saturating_add(grace_period))
                 .is_some()
         } else {
             true
@@ -1259,6 +2044,8 @@ impl<T: Config> Module<T> {
     /// - `RegisteredByOther` if ticker is registered to someone else.
     /// - `Available` if ticker is available for registry.
     /// - `RegisteredByDid` if ticker is already registered to provided did.
+    ///
+    /// A ticker in the grace period after its expiry is still registered to its previous owner.
     pub fn is_ticker_available_or_registered_to(
         ticker: &Ticker,
         did: IdentityId,
@@ -1267,7 +2054,10 @@ impl<T: Config> Module<T> {
         match Self::maybe_ticker(ticker) {
             Some(TickerRegistration { expiry, owner }) => match expiry {
                 // Ticker registered to someone but expired and can be registered again.
-                Some(expiry) if <pallet_timestamp::Pallet<T>>::get() > expiry => {
+                Some(expiry)
+                    if <pallet_timestamp::Pallet<T>>::get()
+                        > expiry.saturating_add(Self::ticker_expiry_config().grace_period) =>
+                {
                     TickerRegistrationStatus::Available
                 }
                 // Ticker is already registered to provided did (may or may not expire in future).
@@ -1304,6 +2094,10 @@ impl<T: Config> Module<T> {
     ) -> Result<Option<T::Moment>, DispatchError> {
         Self::ensure_ticker_ascii(&ticker)?;
         Self::ensure_asset_fresh(&ticker)?;
+        ensure!(
+            !TickerMigrations::contains_key(ticker),
+            Error::<T>::MigratedTicker
+        );
 
         let config = config();
 
@@ -1333,6 +2127,13 @@ impl<T: Config> Module<T> {
     fn unverified_register_ticker(ticker: &Ticker, owner: IdentityId, expiry: Option<T::Moment>) {
         if let Some(ticker_details) = Self::maybe_ticker(ticker) {
             AssetOwnershipRelations::remove(ticker_details.owner, ticker);
+            if ticker_details.owner != owner {
+                TickerAutoRenewal::remove(ticker);
+                TickerRenewalAgents::remove(ticker);
+            }
+        }
+        if let Some(expiry) = expiry {
+            Self::queue_expiring_ticker(*ticker, expiry);
         }
 
         let ticker_registration = TickerRegistration { owner, expiry };
@@ -1363,7 +2164,7 @@ impl<T: Config> Module<T> {
         to_portfolio: PortfolioId,
         value: Balance,
     ) -> StdResult<u8, DispatchError> {
-        if Self::frozen(ticker) {
+        if !Self::lifecycle_state(ticker).allows_transfers() {
             return Ok(ERC1400_TRANSFERS_HALTED);
         }
 
@@ -1435,6 +2236,7 @@ impl<T: Config> Module<T> {
         BalanceOf::insert(ticker, &from_portfolio.did, updated_from_total_balance);
         // increase receiver's balance
         BalanceOf::insert(ticker, &to_portfolio.did, updated_to_total_balance);
+        Self::record_acquisition(ticker, to_portfolio.did, to_total_balance);
         // transfer portfolio balances
         Portfolio::<T>::unchecked_transfer_portfolio_balance(
             &from_portfolio,
@@ -1484,6 +2286,15 @@ impl<T: Config> Module<T> {
         Ok(())
     }
 
+    /// Records that `did` acquired tokens of `ticker` just now, holding `previous_balance` before.
+    /// Only the opening of a position is recorded, so that receiving more tokens,
+    /// e.g. dust sent by anyone, doesn't restart the holding period of the whole balance.
+    fn record_acquisition(ticker: &Ticker, did: IdentityId, previous_balance: Balance) {
+        if previous_balance.is_zero() {
+            AcquiredAt::<T>::insert(ticker, did, <pallet_timestamp::Pallet<T>>::get());
+        }
+    }
+
     /// Updates scope balances after a transfer
     pub fn update_scope_balance(
         ticker: &Ticker,
@@ -1520,6 +2331,11 @@ impl<T: Config> Module<T> {
             token.asset_type.is_fungible(),
             Error::<T>::UnexpectedNonFungibleToken
         );
+        let lifecycle_state = Self::lifecycle_state(ticker);
+        ensure!(
+            lifecycle_state.allows_issuance(),
+            Error::<T>::IssuanceNotAllowed
+        );
 
         // Prepare the updated total supply.
         let updated_total_supply = token
@@ -1527,6 +2343,7 @@ impl<T: Config> Module<T> {
             .checked_add(value)
             .ok_or(Error::<T>::TotalSupplyOverflow)?;
         Self::ensure_within_max_supply(updated_total_supply)?;
+        Self::ensure_within_authorized_supply(ticker, updated_total_supply)?;
         // Increase receiver balance.
         let current_to_balance = Self::balance_of(ticker, to_did);
         // No check since the total balance is always <= the total supply. The
@@ -1550,9 +2367,21 @@ impl<T: Config> Module<T> {
             <Checkpoint<T>>::advance_update_balances(ticker, &[(to_did, current_to_balance)])
         })?;
 
+        // The first issuance activates the asset.
+        if lifecycle_state == AssetLifecycleState::PreIssuance {
+            LifecycleStates::insert(ticker, AssetLifecycleState::Active);
+            Self::deposit_event(RawEvent::AssetLifecycleStateChanged(
+                to_did,
+                *ticker,
+                lifecycle_state,
+                AssetLifecycleState::Active,
+            ));
+        }
+
         // Increase total supply.
         token.total_supply = updated_total_supply;
         BalanceOf::insert(ticker, &to_did, updated_to_balance);
+        Self::record_acquisition(ticker, to_did, current_to_balance);
         Portfolio::<T>::set_default_portfolio_balance(to_did, ticker, updated_to_def_balance);
         Tokens::insert(ticker, token);
 
@@ -1628,6 +2457,11 @@ impl<T: Config> Module<T> {
     /// Accepts and executes the ticker transfer.
     fn base_accept_ticker_transfer(origin: T::RuntimeOrigin, auth_id: u64) -> DispatchResult {
         let to = Identity::<T>::ensure_perms(origin)?;
+        Self::unsafe_accept_ticker_transfer(to, auth_id)
+    }
+
+    /// Accepts and executes the ticker transfer on behalf of `to`.
+    fn unsafe_accept_ticker_transfer(to: IdentityId, auth_id: u64) -> DispatchResult {
         <Identity<T>>::accept_auth_with(&to.into(), auth_id, |data, auth_by| {
             let ticker = extract_auth!(data, TransferTicker(t));
 
@@ -1642,17 +2476,143 @@ impl<T: Config> Module<T> {
         })
     }
 
-    /// Transfer the given `ticker`'s registration from `from` to `to`.
-    fn transfer_ticker(ticker: Ticker, to: IdentityId, from: IdentityId) {
-        AssetOwnershipRelations::remove(from, ticker);
-        AssetOwnershipRelations::insert(to, ticker, AssetOwnershipRelation::TickerOwned);
-        <Tickers<T>>::mutate(&ticker, |tr| tr.owner = to);
-        Self::deposit_event(RawEvent::TickerTransferred(to, ticker, from));
+    /// Accepts the renewal agent authorization `auth_id` on behalf of the caller.
+    fn base_accept_ticker_renewal_agent(origin: T::RuntimeOrigin, auth_id: u64) -> DispatchResult {
+        let did = Identity::<T>::ensure_perms(origin)?;
+        <Identity<T>>::accept_auth_with(&did.into(), auth_id, |data, auth_by| {
+            let ticker = extract_auth!(data, BecomeTickerRenewalAgent(t));
+            Self::ensure_asset_fresh(&ticker)?;
+
+            // The authorization lapses once its issuer no longer owns the ticker.
+            ensure!(
+                Self::is_ticker_registry_valid(&ticker, auth_by),
+                Error::<T>::TickerRegistrationExpired
+            );
+
+            TickerRenewalAgents::insert(ticker, did);
+            Self::deposit_event(RawEvent::TickerRenewalAgentSet(did, ticker, Some(did)));
+            Ok(())
+        })
+    }
+
+    /// Transfer the given `ticker`'s registration from `from` to `to`.
+    fn transfer_ticker(ticker: Ticker, to: IdentityId, from: IdentityId) {
+        AssetOwnershipRelations::remove(from, ticker);
+        AssetOwnershipRelations::insert(to, ticker, AssetOwnershipRelation::TickerOwned);
+        <Tickers<T>>::mutate(&ticker, |tr| tr.owner = to);
+        TickerAutoRenewal::remove(ticker);
+        TickerRenewalAgents::remove(ticker);
+        Self::deposit_event(RawEvent::TickerTransferred(to, ticker, from));
+    }
+
+    /// Returns the `ExpiringTickers` bucket of registrations expiring at `expiry`.
+    fn expiring_tickers_bucket(expiry: T::Moment) -> u64 {
+        expiry.saturated_into::<u64>() / EXPIRING_TICKERS_BUCKET_LENGTH
+    }
+
+    /// Adds the registration of `ticker` expiring at `expiry` to the `ExpiringTickers` queue.
+    /// Registrations expiring in a bucket already processed are added to the next bucket to process.
+    fn queue_expiring_ticker(ticker: Ticker, expiry: T::Moment) {
+        let bucket = Self::expiring_tickers_bucket(expiry);
+        let bucket = match Self::next_expiring_tickers_bucket() {
+            Some(next) => bucket.max(next),
+            None => {
+                NextExpiringTickersBucket::put(bucket);
+                bucket
+            }
+        };
+        ExpiringTickers::<T>::insert(bucket, ticker, expiry);
+    }
+
+    /// Announces the ticker registrations expiring within the notice period, and renews those
+    /// with auto-renewal enabled. Buckets are processed in order of expiry, and at most
+    /// `MAX_EXPIRING_TICKERS_PER_BLOCK` entries and empty buckets are read.
+    ///
+    /// The bucket containing the end of the notice period is only partially due, so a registration
+    /// can be announced up to one bucket length after entering its notice period.
+    fn process_expiring_tickers() -> Weight {
+        let mut bucket = match Self::next_expiring_tickers_bucket() {
+            Some(bucket) => bucket,
+            None => return <T as Config>::WeightInfo::process_expiring_tickers(0),
+        };
+        let deadline = <pallet_timestamp::Pallet<T>>::get()
+            .saturating_add(Self::ticker_expiry_config().notice_period);
+        let end_bucket = Self::expiring_tickers_bucket(deadline);
+        let mut processed = 0;
+        while bucket <= end_bucket && processed < MAX_EXPIRING_TICKERS_PER_BLOCK {
+            let entries: Vec<(Ticker, T::Moment)> = ExpiringTickers::<T>::iter_prefix(bucket)
+                .take((MAX_EXPIRING_TICKERS_PER_BLOCK - processed) as usize)
+                .collect();
+            processed += (entries.len() as u32).max(1);
+            for (ticker, expiry) in entries {
+                // Only the entries of the last bucket can expire after the deadline.
+                if expiry <= deadline {
+                    ExpiringTickers::<T>::remove(bucket, ticker);
+                    Self::process_expiring_ticker(ticker, expiry);
+                }
+            }
+            // A bucket with more entries than the limit is finished in the next blocks.
+            if bucket == end_bucket || ExpiringTickers::<T>::iter_prefix(bucket).next().is_some() {
+                break;
+            }
+            bucket += 1;
+        }
+        NextExpiringTickersBucket::put(bucket);
+        <T as Config>::WeightInfo::process_expiring_tickers(processed)
+    }
+
+    /// Announces the registration of `ticker` expiring at `expiry`, and renews it if auto-renewal is enabled.
+    /// Registrations that were renewed, transferred or completed by the creation of their asset
+    /// since they were queued are skipped.
+    fn process_expiring_ticker(ticker: Ticker, expiry: T::Moment) {
+        let owner = match Self::maybe_ticker(&ticker) {
+            Some(registration) if registration.expiry == Some(expiry) => registration.owner,
+            _ => return,
+        };
+        Self::deposit_event(RawEvent::TickerExpiringSoon(owner, ticker, expiry));
+        if Self::ticker_auto_renewal(ticker) {
+            if let Err(error) = Self::renew_ticker(ticker, owner, expiry) {
+                Self::deposit_event(RawEvent::TickerRenewalFailed(owner, ticker, error));
+            }
+        }
+    }
+
+    /// Extends the registration of `ticker` by the registration length, charging the ticker
+    /// registration fee to the primary key of its renewal agent, or of `owner` if there is none.
+    fn renew_ticker(ticker: Ticker, owner: IdentityId, expiry: T::Moment) -> DispatchResult {
+        let payer_did = Self::ticker_renewal_agent(ticker).unwrap_or(owner);
+        let payer = Identity::<T>::get_primary_key(payer_did)
+            .ok_or(identity::Error::<T>::DidDoesNotExist)?;
+        let previous_payer = T::CddHandler::get_payer_from_context();
+        T::CddHandler::set_payer_context(Some(payer));
+        let charged = T::ProtocolFee::charge_fee(ProtocolOp::AssetRegisterTicker);
+        T::CddHandler::set_payer_context(previous_payer);
+        charged?;
+
+        Self::extend_ticker_registration(ticker, owner, expiry);
+        Ok(())
+    }
+
+    /// Extends the registration of `ticker` expiring at `expiry` by the registration length.
+    fn extend_ticker_registration(ticker: Ticker, owner: IdentityId, expiry: T::Moment) {
+        let new_expiry = Self::ticker_registration_config()
+            .registration_length
+            .map(|length| expiry.saturating_add(length));
+        <Tickers<T>>::mutate(&ticker, |tr| tr.expiry = new_expiry);
+        if let Some(new_expiry) = new_expiry {
+            Self::queue_expiring_ticker(ticker, new_expiry);
+        }
+        Self::deposit_event(RawEvent::TickerRenewed(owner, ticker, new_expiry));
     }
 
     /// Accept and process a token ownership transfer.
     fn base_accept_token_ownership_transfer(origin: T::RuntimeOrigin, id: u64) -> DispatchResult {
         let to = Identity::<T>::ensure_perms(origin)?;
+        Self::unsafe_accept_token_ownership_transfer(to, id)
+    }
+
+    /// Accept and process a token ownership transfer on behalf of `to`.
+    fn unsafe_accept_token_ownership_transfer(to: IdentityId, id: u64) -> DispatchResult {
         <Identity<T>>::accept_auth_with(&to.into(), id, |data, auth_by| {
             let ticker = extract_auth!(data, TransferAssetOwnership(t));
 
@@ -1755,6 +2715,156 @@ impl<T: Config> Module<T> {
         Ok(())
     }
 
+    /// Ensure `supply` does not exceed the authorized supply of `ticker`, if any.
+    fn ensure_within_authorized_supply(ticker: &Ticker, supply: Balance) -> DispatchResult {
+        if let Some(authorized) = Self::authorized_supply(ticker) {
+            ensure!(supply <= authorized, Error::<T>::AuthorizedSupplyExceeded);
+        }
+        Ok(())
+    }
+
+    /// Ensure the authorized `supply` covers the total supply of `ticker`.
+    fn ensure_valid_authorized_supply(ticker: &Ticker, supply: Option<Balance>) -> DispatchResult {
+        if let Some(supply) = supply {
+            Self::ensure_within_max_supply(supply)?;
+            ensure!(
+                supply >= Self::token_details(ticker).total_supply,
+                Error::<T>::AuthorizedSupplyBelowIssued
+            );
+        }
+        Ok(())
+    }
+
+    /// Returns the authorized and issued supply of `ticker`.
+    pub fn authorized_capital(ticker: &Ticker) -> AuthorizedCapital {
+        AuthorizedCapital {
+            authorized_supply: Self::authorized_supply(ticker),
+            issued_supply: Self::token_details(ticker).total_supply,
+        }
+    }
+
+    /// Returns the descriptive metadata of `ticker`, or `None` if the asset doesn't exist.
+    pub fn describe_asset(ticker: &Ticker) -> Option<AssetDescription> {
+        let token = Tokens::try_get(ticker).ok()?;
+        let asset_type = match token.asset_type.label() {
+            Some(label) => label.to_vec(),
+            None => match token.asset_type {
+                AssetType::Custom(id) | AssetType::NonFungible(NonFungibleType::Custom(id)) => {
+                    CustomTypes::get(id)
+                }
+                _ => Vec::new(),
+            },
+        };
+        let metadata = AssetMetadataValues::iter_prefix(ticker)
+            .filter_map(|(key, value)| {
+                let name = match key {
+                    AssetMetadataKey::Global(key) => AssetMetadataGlobalKeyToName::get(key),
+                    AssetMetadataKey::Local(key) => AssetMetadataLocalKeyToName::get(ticker, key),
+                }?;
+                Some((name.0, value.0))
+            })
+            .collect();
+
+        Some(AssetDescription {
+            ticker: *ticker,
+            name: AssetNames::get(ticker).0,
+            asset_type,
+            divisible: token.divisible,
+            owner: token.owner_did,
+            total_supply: token.total_supply,
+            funding_round: FundingRound::get(ticker).0,
+            identifiers: Identifiers::get(ticker)
+                .iter()
+                .map(|identifier| (identifier.scheme().to_vec(), identifier.code().to_vec()))
+                .collect(),
+            documents: AssetDocuments::iter_prefix(ticker).collect(),
+            metadata,
+            compliance: T::ComplianceManager::compliance_summary(ticker),
+        })
+    }
+
+    /// Returns at most `limit` holders of `ticker` after `start_after`, with their balances at
+    /// `checkpoint`, or their current balances if `checkpoint` is `None`.
+    /// Returns `None` if `checkpoint` doesn't exist.
+    pub fn holder_balances(
+        ticker: &Ticker,
+        checkpoint: Option<CheckpointId>,
+        start_after: Option<IdentityId>,
+        limit: u32,
+    ) -> Option<AssetHolders> {
+        if let Some(cp) = checkpoint {
+            if !<Checkpoint<T>>::checkpoint_exists(ticker, cp) {
+                return None;
+            }
+        }
+        let balance_of = |did: IdentityId, balance: Balance| match checkpoint {
+            Some(cp) => <Checkpoint<T>>::balance_at(*ticker, did, cp).unwrap_or(balance),
+            None => balance,
+        };
+        let iter = match start_after {
+            Some(did) => {
+                let raw_key = BalanceOf::hashed_key_for(ticker, did);
+                BalanceOf::iter_prefix_from(ticker, raw_key)
+            }
+            None => BalanceOf::iter_prefix(ticker),
+        };
+        let mut holders: Vec<_> = iter
+            .map(|(did, balance)| (did, balance_of(did, balance)))
+            .filter(|(_, balance)| *balance > 0)
+            .take(limit as usize + 1)
+            .collect();
+        let next_holder = if holders.len() > limit as usize {
+            holders.truncate(limit as usize);
+            holders.last().map(|(did, _)| *did)
+        } else {
+            None
+        };
+        Some(AssetHolders {
+            holders,
+            next_holder,
+        })
+    }
+
+    /// Checks each `(id, hash)` of `documents` against the hash of the document `id` of `ticker`.
+    /// The results are in the same order as `documents`.
+    pub fn verify_document_hashes(
+        ticker: &Ticker,
+        documents: Vec<(DocumentId, DocumentHash)>,
+    ) -> Vec<DocumentHashCheck> {
+        documents
+            .into_iter()
+            .map(|(id, hash)| {
+                if !AssetDocuments::contains_key(ticker, id) {
+                    DocumentHashCheck::NotFound
+                } else if AssetDocuments::get(ticker, id).content_hash == hash {
+                    DocumentHashCheck::Matches
+                } else {
+                    DocumentHashCheck::Differs
+                }
+            })
+            .collect()
+    }
+
+    /// Returns at most `limit` identities, after `start_after` in increasing order of DID, that
+    /// acknowledged the `version` of the document `id` of `ticker`, with the time of their acknowledgement.
+    pub fn document_acknowledgements(
+        ticker: Ticker,
+        id: DocumentId,
+        version: u32,
+        start_after: Option<IdentityId>,
+        limit: u32,
+    ) -> Vec<(IdentityId, T::Moment)> {
+        let key = (ticker, id, version);
+        let iter = match start_after {
+            Some(did) => {
+                let raw_key = DocumentAcknowledgements::<T>::hashed_key_for(key, did);
+                DocumentAcknowledgements::<T>::iter_prefix_from(key, raw_key)
+            }
+            None => DocumentAcknowledgements::<T>::iter_prefix(key),
+        };
+        iter.take(limit as usize).collect()
+    }
+
     /// Ensure ticker length is within limit per `config`.
     fn ensure_ticker_length<U>(
         ticker: &Ticker,
@@ -1875,6 +2985,8 @@ impl<T: Config> Module<T> {
         } else {
             // Ticker already registered by the user.
             <Tickers<T>>::mutate(&ticker, |tr| tr.expiry = None);
+            TickerAutoRenewal::remove(&ticker);
+            TickerRenewalAgents::remove(&ticker);
         }
 
         let token = SecurityToken {
@@ -1884,6 +2996,10 @@ impl<T: Config> Module<T> {
             asset_type,
         };
         Tokens::insert(&ticker, token);
+        // Non-fungible tokens are issued by the NFT pallet, so they are active right away.
+        if asset_type.is_fungible() {
+            LifecycleStates::insert(&ticker, AssetLifecycleState::PreIssuance);
+        }
         AssetNames::insert(&ticker, &name);
         DisableInvestorUniqueness::insert(&ticker, disable_iu);
         // NB - At the time of asset creation it is obvious that the asset issuer will not have an
@@ -1911,6 +3027,9 @@ impl<T: Config> Module<T> {
         // Grant owner full agent permissions.
         <ExternalAgents<T>>::unchecked_add_agent(ticker, did, AgentGroup::Full).unwrap();
 
+        // Attach the issuer's or the default compliance requirements for `asset_type`.
+        T::ComplianceManager::apply_requirements_template(&ticker, did, asset_type);
+
         Ok(did)
     }
 
@@ -1918,18 +3037,44 @@ impl<T: Config> Module<T> {
         let did = <ExternalAgents<T>>::ensure_perms(origin, ticker)?;
         Self::ensure_asset_exists(&ticker)?;
 
-        let (event, error) = match freeze {
-            true => (
-                RawEvent::AssetFrozen(did, ticker),
-                Error::<T>::AlreadyFrozen,
-            ),
-            false => (RawEvent::AssetUnfrozen(did, ticker), Error::<T>::NotFrozen),
+        let error = match freeze {
+            true => Error::<T>::AlreadyFrozen,
+            false => Error::<T>::NotFrozen,
         };
-
         ensure!(Self::frozen(&ticker) != freeze, error);
-        Frozen::insert(&ticker, freeze);
 
-        Self::deposit_event(event);
+        // Freezing suspends the asset, and unfreezing activates it again.
+        let state = match freeze {
+            true => AssetLifecycleState::Suspended,
+            false => AssetLifecycleState::Active,
+        };
+        Self::transition_lifecycle_state(did, ticker, state)
+    }
+
+    /// Moves `ticker` to the lifecycle `state`, keeping its frozen flag in sync.
+    fn transition_lifecycle_state(
+        did: IdentityId,
+        ticker: Ticker,
+        state: AssetLifecycleState,
+    ) -> DispatchResult {
+        let current = Self::lifecycle_state(ticker);
+        ensure!(
+            current.can_transition_to(&state),
+            Error::<T>::InvalidLifecycleTransition
+        );
+
+        LifecycleStates::insert(ticker, state);
+        let frozen = state == AssetLifecycleState::Suspended;
+        if Self::frozen(&ticker) != frozen {
+            Frozen::insert(&ticker, frozen);
+            Self::deposit_event(match frozen {
+                true => RawEvent::AssetFrozen(did, ticker),
+                false => RawEvent::AssetUnfrozen(did, ticker),
+            });
+        }
+        Self::deposit_event(RawEvent::AssetLifecycleStateChanged(
+            did, ticker, current, state,
+        ));
         Ok(())
     }
 
@@ -1972,6 +3117,10 @@ impl<T: Config> Module<T> {
             Tokens::get(&ticker).asset_type.is_fungible(),
             Error::<T>::UnexpectedNonFungibleToken
         );
+        ensure!(
+            Self::lifecycle_state(ticker).allows_redemption(),
+            Error::<T>::RedemptionNotAllowed
+        );
 
         // Reduce caller's portfolio balance. This makes sure that the caller has enough unlocked tokens.
         // If `advance_update_balances` fails, `reduce_portfolio_balance` shouldn't modify storage.
@@ -2081,6 +3230,31 @@ impl<T: Config> Module<T> {
         Ok(())
     }
 
+    fn base_update_document(
+        origin: T::RuntimeOrigin,
+        ticker: Ticker,
+        id: DocumentId,
+        doc: Document,
+    ) -> DispatchResult {
+        let did = <ExternalAgents<T>>::ensure_perms(origin, ticker)?;
+        Self::ensure_doc_exists(&ticker, &id)?;
+        ensure_string_limited::<T>(&doc.uri)?;
+        ensure_string_limited::<T>(&doc.name)?;
+        ensure_opt_string_limited::<T>(doc.doc_type.as_deref())?;
+
+        let version = Self::asset_document_version(ticker, id);
+        let new_version = version.checked_add(1).ok_or(CounterOverflow::<T>)?;
+        T::ProtocolFee::charge_fee(ProtocolOp::AssetAddDocuments)?;
+
+        // Archive the current version and replace it.
+        let previous = AssetDocuments::get(ticker, id);
+        AssetDocumentHistory::insert(ticker, (id, version), previous);
+        AssetDocumentVersions::insert(ticker, id, new_version);
+        AssetDocuments::insert(ticker, id, doc.clone());
+        Self::deposit_event(RawEvent::DocumentUpdated(did, ticker, id, new_version, doc));
+        Ok(())
+    }
+
     fn base_remove_documents(
         origin: T::RuntimeOrigin,
         ids: Vec<DocumentId>,
@@ -2094,6 +3268,58 @@ impl<T: Config> Module<T> {
         Ok(())
     }
 
+    fn base_remove_document_acknowledgements(
+        origin: T::RuntimeOrigin,
+        ticker: Ticker,
+        id: DocumentId,
+        limit: u32,
+    ) -> DispatchResult {
+        let did = <ExternalAgents<T>>::ensure_perms(origin, ticker)?;
+        ensure!(
+            id < Self::asset_documents_id_sequence(ticker),
+            Error::<T>::NoSuchDoc
+        );
+        ensure!(
+            !AssetDocuments::contains_key(ticker, id),
+            Error::<T>::DocumentNotRemoved
+        );
+
+        // The versions are removed from the latest one, which keeps track of the progress.
+        let mut removed = 0;
+        let mut version = Self::asset_document_version(ticker, id);
+        while removed < limit {
+            let key = (ticker, id, version);
+            let acks = DocumentAcknowledgements::<T>::iter_key_prefix(key)
+                .take((limit - removed) as usize)
+                .collect::<Vec<_>>();
+            for ack in &acks {
+                DocumentAcknowledgements::<T>::remove(key, ack);
+            }
+            removed += acks.len() as u32;
+            if removed == limit {
+                break;
+            }
+
+            // All the acknowledgements of `version` are removed, so the version is removed too.
+            AssetDocumentHistory::remove(ticker, (id, version));
+            removed += 1;
+            match version.checked_sub(1) {
+                Some(previous) => {
+                    AssetDocumentVersions::insert(ticker, id, previous);
+                    version = previous;
+                }
+                None => {
+                    AssetDocumentVersions::remove(ticker, id);
+                    break;
+                }
+            }
+        }
+        Self::deposit_event(RawEvent::DocumentAcknowledgementsRemoved(
+            did, ticker, id, removed,
+        ));
+        Ok(())
+    }
+
     fn base_set_funding_round(
         origin: T::RuntimeOrigin,
         ticker: Ticker,
@@ -2150,7 +3376,7 @@ impl<T: Config> Module<T> {
     }
 
     /// Ensure asset metadata `name` is within the global limit.
-    fn ensure_asset_metadata_name_limited(name: &AssetMetadataName) -> DispatchResult {
+    pub fn ensure_asset_metadata_name_limited(name: &AssetMetadataName) -> DispatchResult {
         ensure!(
             name.len() <= T::AssetMetadataNameMaxLength::get() as usize,
             Error::<T>::AssetMetadataNameMaxLengthExceeded
@@ -2159,7 +3385,7 @@ impl<T: Config> Module<T> {
     }
 
     /// Ensure asset metadata `spec` is within the global limit.
-    fn ensure_asset_metadata_spec_limited(spec: &AssetMetadataSpec) -> DispatchResult {
+    pub fn ensure_asset_metadata_spec_limited(spec: &AssetMetadataSpec) -> DispatchResult {
         ensure_opt_string_limited::<T>(spec.url.as_deref())?;
         ensure_opt_string_limited::<T>(spec.description.as_deref())?;
         if let Some(ref type_def) = spec.type_def {
@@ -2281,7 +3507,7 @@ impl<T: Config> Module<T> {
         Self::unverified_register_asset_metadata_local_type(did, ticker, name, spec).map(drop)
     }
 
-    fn unverified_register_asset_metadata_local_type(
+    pub fn unverified_register_asset_metadata_local_type(
         did: IdentityId,
         ticker: Ticker,
         name: AssetMetadataName,
@@ -2439,6 +3665,88 @@ impl<T: Config> Module<T> {
         Ok(())
     }
 
+    fn base_issuer_batch_transfer(
+        origin: T::RuntimeOrigin,
+        ticker: Ticker,
+        from_portfolio: PortfolioId,
+        transfers: Vec<(PortfolioId, Balance)>,
+    ) -> DispatchResult {
+        let data = <ExternalAgents<T>>::ensure_agent_asset_perms(origin, ticker)?;
+        Portfolio::<T>::ensure_portfolio_custody_and_permission(
+            from_portfolio,
+            data.primary_did,
+            data.secondary_key.as_ref(),
+        )?;
+        ensure!(
+            transfers.len() <= MAX_ISSUER_BATCH_TRANSFERS as usize,
+            Error::<T>::TooManyBatchTransfers
+        );
+
+        let results = transfers
+            .into_iter()
+            .map(|(to_portfolio, value)| {
+                let status =
+                    match Self::_is_valid_transfer(&ticker, from_portfolio, to_portfolio, value) {
+                        Ok(ERC1400_TRANSFER_SUCCESS) => with_transaction(|| {
+                            Self::unsafe_transfer(from_portfolio, to_portfolio, &ticker, value)
+                        })
+                        .map_or(ERC1400_TRANSFER_FAILURE, |_| ERC1400_TRANSFER_SUCCESS),
+                        Ok(status) => status,
+                        Err(_) => ERC1400_TRANSFER_FAILURE,
+                    };
+                (to_portfolio, value, status)
+            })
+            .collect();
+        Self::deposit_event(RawEvent::IssuerBatchTransfer(
+            data.primary_did,
+            ticker,
+            from_portfolio,
+            results,
+        ));
+        Ok(())
+    }
+
+    fn base_controller_transfer_batch(
+        origin: T::RuntimeOrigin,
+        ticker: Ticker,
+        transfers: Vec<(PortfolioId, PortfolioId, Balance)>,
+    ) -> DispatchResult {
+        let data = <ExternalAgents<T>>::ensure_agent_asset_perms(origin, ticker)?;
+        let did = data.primary_did;
+        ensure!(
+            transfers.len() <= MAX_CONTROLLER_BATCH_TRANSFERS as usize,
+            Error::<T>::TooManyBatchTransfers
+        );
+
+        // All the transfers are reverted if any of them fails.
+        with_transaction(|| {
+            for (from_portfolio, to_portfolio, value) in transfers {
+                // As with a single controller transfer, tokens may only be forced
+                // into portfolios the caller has custody of.
+                Portfolio::<T>::ensure_portfolio_custody_and_permission(
+                    to_portfolio,
+                    did,
+                    data.secondary_key.as_ref(),
+                )?;
+                Portfolio::<T>::ensure_portfolio_transfer_validity(
+                    &from_portfolio,
+                    &to_portfolio,
+                    &ticker,
+                    value,
+                )?;
+                Self::unsafe_transfer(from_portfolio, to_portfolio, &ticker, value)?;
+                Self::deposit_event(RawEvent::ControllerBatchTransfer(
+                    did,
+                    ticker,
+                    from_portfolio,
+                    to_portfolio,
+                    value,
+                ));
+            }
+            Ok(())
+        })
+    }
+
     pub fn unsafe_can_transfer_granular(
         from_custodian: Option<IdentityId>,
         from_portfolio: PortfolioId,
@@ -2464,17 +3772,19 @@ impl<T: Config> Module<T> {
             ticker,
             value,
         );
-        let asset_frozen = Self::frozen(ticker);
+        // Transfers are halted for frozen assets and in other lifecycle states.
+        let asset_frozen = !Self::lifecycle_state(ticker).allows_transfers();
         let transfer_condition_result = Self::transfer_condition_failures_granular(
             &from_portfolio.did,
             &to_portfolio.did,
             ticker,
             value,
         );
-        let compliance_result = T::ComplianceManager::verify_restriction_granular(
+        let compliance_result = T::ComplianceManager::verify_restriction_granular_for_amount(
             ticker,
             Some(from_portfolio.did),
             Some(to_portfolio.did),
+            value,
         );
 
         GranularCanTransferResult {
@@ -2732,4 +4042,362 @@ impl<T: Config> Module<T> {
         ));
         Ok(())
     }
+
+    fn base_propose_authorized_supply(
+        origin: T::RuntimeOrigin,
+        ticker: Ticker,
+        supply: Option<Balance>,
+    ) -> DispatchResult {
+        let did = <ExternalAgents<T>>::ensure_perms(origin, ticker)?;
+        Self::ensure_valid_authorized_supply(&ticker, supply)?;
+
+        PendingAuthorizedSupply::insert(ticker, (did, supply));
+        Self::deposit_event(RawEvent::AuthorizedSupplyProposed(did, ticker, supply));
+        Ok(())
+    }
+
+    fn base_approve_authorized_supply(origin: T::RuntimeOrigin, ticker: Ticker) -> DispatchResult {
+        let did = <ExternalAgents<T>>::ensure_perms(origin, ticker)?;
+        let (proposer, supply) =
+            Self::pending_authorized_supply(ticker).ok_or(Error::<T>::NoPendingAuthorizedSupply)?;
+        ensure!(
+            proposer != did,
+            Error::<T>::AuthorizedSupplyApproverIsProposer
+        );
+        // Tokens may have been issued since the proposal.
+        Self::ensure_valid_authorized_supply(&ticker, supply)?;
+
+        PendingAuthorizedSupply::remove(ticker);
+        AuthorizedSupply::mutate(ticker, |authorized| *authorized = supply);
+        Self::deposit_event(RawEvent::AuthorizedSupplySet(did, ticker, supply));
+        Ok(())
+    }
+
+    /// Returns `true` if issuing `amount` tokens of `ticker` requires an approval.
+    /// The first issuance of an asset is never large.
+    pub fn is_large_issuance(ticker: &Ticker, amount: Balance) -> bool {
+        Self::large_issuance_config().map_or(false, |config| {
+            let total_supply = Self::token_details(ticker).total_supply;
+            !total_supply.is_zero() && amount > config.threshold.mul_floor(total_supply)
+        })
+    }
+
+    fn base_propose_issuance(
+        origin: T::RuntimeOrigin,
+        ticker: Ticker,
+        amount: Balance,
+    ) -> DispatchResult {
+        let portfolio =
+            Self::ensure_agent_with_custody_and_perms(origin, ticker, PortfolioKind::Default)?;
+        Self::ensure_granular(&ticker, amount)?;
+
+        let timelock = Self::large_issuance_config()
+            .map(|config| config.timelock)
+            .unwrap_or_default();
+        let executable_at = <pallet_timestamp::Pallet<T>>::get().saturating_add(timelock);
+        PendingIssuances::<T>::insert(
+            ticker,
+            PendingIssuance {
+                proposer: portfolio.did,
+                amount,
+                executable_at,
+            },
+        );
+        Self::deposit_event(RawEvent::IssuanceProposed(
+            portfolio.did,
+            ticker,
+            amount,
+            executable_at,
+        ));
+        Ok(())
+    }
+
+    fn base_approve_issuance(origin: T::RuntimeOrigin, ticker: Ticker) -> DispatchResult {
+        // Governance approvals are not charged any protocol fee.
+        let (did, protocol_fee_data) = match ensure_root(origin.clone()) {
+            Ok(()) => (GC_DID, None),
+            Err(_) => (
+                <ExternalAgents<T>>::ensure_perms(origin, ticker)?,
+                Some(ProtocolOp::AssetIssue),
+            ),
+        };
+        let issuance = Self::pending_issuance(ticker).ok_or(Error::<T>::NoPendingIssuance)?;
+        ensure!(
+            issuance.proposer != did,
+            Error::<T>::IssuanceApproverIsProposer
+        );
+        ensure!(
+            <pallet_timestamp::Pallet<T>>::get() >= issuance.executable_at,
+            Error::<T>::IssuanceTimelockNotExpired
+        );
+
+        PendingIssuances::<T>::remove(ticker);
+        Self::_mint(
+            &ticker,
+            issuance.proposer,
+            issuance.amount,
+            protocol_fee_data,
+        )?;
+        Self::deposit_event(RawEvent::IssuanceApproved(did, ticker, issuance.amount));
+        Ok(())
+    }
+
+    fn base_schedule_issuance(
+        origin: T::RuntimeOrigin,
+        ticker: Ticker,
+        amount: Balance,
+        portfolio_kind: PortfolioKind,
+        at: T::BlockNumber,
+    ) -> DispatchResult {
+        let portfolio = Self::ensure_agent_with_custody_and_perms(origin, ticker, portfolio_kind)?;
+        Self::ensure_asset_exists(&ticker)?;
+        Self::ensure_granular(&ticker, amount)?;
+        ensure!(
+            at > frame_system::Pallet::<T>::block_number(),
+            Error::<T>::InvalidIssuanceBlock
+        );
+        ensure!(
+            !Self::is_large_issuance(&ticker, amount),
+            Error::<T>::IssuanceRequiresApproval
+        );
+
+        let at = Self::scheduled_issuance_block(at)?;
+        let id = ScheduledIssuanceCounter::try_mutate(try_next_pre::<T, _>)?;
+        T::ProtocolFee::charge_fee(ProtocolOp::AssetIssue)?;
+        ScheduledIssuancesAt::<T>::append(at, id);
+        ScheduledIssuances::<T>::insert(
+            id,
+            ScheduledIssuance {
+                agent: portfolio.did,
+                ticker,
+                portfolio,
+                amount,
+                at,
+            },
+        );
+        Self::deposit_event(RawEvent::IssuanceScheduled(
+            portfolio.did,
+            ticker,
+            id,
+            portfolio,
+            amount,
+            at,
+        ));
+        Ok(())
+    }
+
+    /// Returns the first block, out of the `MAX_SCHEDULED_ISSUANCE_BLOCKS` starting at `at`,
+    /// that has room for another scheduled issuance.
+    fn scheduled_issuance_block(mut at: T::BlockNumber) -> Result<T::BlockNumber, DispatchError> {
+        for _ in 0..MAX_SCHEDULED_ISSUANCE_BLOCKS {
+            if ScheduledIssuancesAt::<T>::decode_len(at).unwrap_or_default()
+                < MAX_SCHEDULED_ISSUANCES_PER_BLOCK as usize
+            {
+                return Ok(at);
+            }
+            at = at.saturating_add(One::one());
+        }
+        Err(Error::<T>::ScheduledIssuanceBlocksFull.into())
+    }
+
+    /// Moves the asset `old` and the storage keyed by its ticker to `new`.
+    fn base_migrate_ticker(
+        old: Ticker,
+        new: Ticker,
+        max_holders: u32,
+        mut entries: u32,
+    ) -> DispatchResult {
+        Self::ensure_asset_exists(&old)?;
+        let token = Tokens::get(&old);
+        ensure!(
+            token.asset_type.is_fungible(),
+            Error::<T>::UnexpectedNonFungibleToken
+        );
+        let owner = token.owner_did;
+        Self::ticker_registration_checks(&new, owner, false, || {
+            Self::ticker_registration_config()
+        })?;
+        let holders: Vec<IdentityId> = BalanceOf::iter_key_prefix(old)
+            .take(max_holders as usize + 1)
+            .collect();
+        ensure!(
+            holders.len() <= max_holders as usize,
+            Error::<T>::TooManyTickerHolders
+        );
+
+        // The claims of the holders checked by the compliance rules are rescoped along with them.
+        let claim_types = T::ComplianceManager::claim_types(&old)
+            .into_iter()
+            .chain(TICKER_SCOPED_CLAIM_TYPES.iter().copied())
+            .collect::<BTreeSet<_>>();
+        let (old_scope, new_scope) = (Scope::Ticker(old), Scope::Ticker(new));
+        for did in holders {
+            Portfolio::<T>::migrate_ticker_balances(did, &old, &new)?;
+            BalanceOf::swap(old, did, new, did);
+            ScopeIdOf::swap(old, did, new, did);
+            <AcquiredAt<T>>::swap(old, did, new, did);
+            for claim_type in &claim_types {
+                <Identity<T>>::migrate_claims_scope(
+                    did,
+                    *claim_type,
+                    &old_scope,
+                    &new_scope,
+                    &mut entries,
+                )?;
+            }
+        }
+        for (scope_id, balance) in AggregateBalance::drain_prefix(old) {
+            try_use_entry::<T>(&mut entries)?;
+            AggregateBalance::insert(new, scope_id, balance);
+        }
+
+        // The registration of `new`, if any, is replaced by the one of the asset.
+        <Tickers<T>>::insert(new, <Tickers<T>>::take(old));
+        for ticker in [old, new] {
+            TickerAutoRenewal::remove(ticker);
+            TickerRenewalAgents::remove(ticker);
+        }
+        AssetOwnershipRelations::remove(owner, old);
+        AssetOwnershipRelations::insert(owner, new, AssetOwnershipRelation::AssetOwned);
+
+        let funding_round = FundingRound::get(old);
+        IssuedInFundingRound::swap((old, funding_round.clone()), (new, funding_round));
+        Tokens::swap(old, new);
+        AssetNames::swap(old, new);
+        Identifiers::swap(old, new);
+        FundingRound::swap(old, new);
+        Frozen::swap(old, new);
+        DisableInvestorUniqueness::swap(old, new);
+        AuthorizedSupply::swap(old, new);
+        PendingAuthorizedSupply::swap(old, new);
+        <PendingIssuances<T>>::swap(old, new);
+        LifecycleStates::swap(old, new);
+
+        // Removed documents may still have versions and acknowledgements, so every id is moved.
+        let next_id = AssetDocumentsIdSequence::take(old);
+        AssetDocumentsIdSequence::insert(new, next_id);
+        for id in (0..next_id.0).map(DocumentId) {
+            try_use_entry::<T>(&mut entries)?;
+            if let Ok(doc) = AssetDocuments::try_get(old, id) {
+                AssetDocuments::remove(old, id);
+                AssetDocuments::insert(new, id, doc);
+            }
+            let current = AssetDocumentVersions::take(old, id);
+            for version in 0..=current {
+                try_use_entry::<T>(&mut entries)?;
+                for (did, at) in DocumentAcknowledgements::<T>::drain_prefix((old, id, version)) {
+                    try_use_entry::<T>(&mut entries)?;
+                    DocumentAcknowledgements::<T>::insert((new, id, version), did, at);
+                }
+                if let Some(doc) = AssetDocumentHistory::take(old, (id, version)) {
+                    AssetDocumentHistory::insert(new, (id, version), doc);
+                }
+            }
+            if current > 0 {
+                AssetDocumentVersions::insert(new, id, current);
+            }
+        }
+        AssetMetadataNextLocalKey::swap(old, new);
+        for (key, value) in AssetMetadataValues::drain_prefix(old) {
+            try_use_entry::<T>(&mut entries)?;
+            AssetMetadataValues::insert(new, key, value);
+        }
+        for (key, detail) in <AssetMetadataValueDetails<T>>::drain_prefix(old) {
+            try_use_entry::<T>(&mut entries)?;
+            <AssetMetadataValueDetails<T>>::insert(new, key, detail);
+        }
+        for (name, key) in AssetMetadataLocalNameToKey::drain_prefix(old) {
+            try_use_entry::<T>(&mut entries)?;
+            AssetMetadataLocalNameToKey::insert(new, name, key);
+        }
+        for (key, name) in AssetMetadataLocalKeyToName::drain_prefix(old) {
+            try_use_entry::<T>(&mut entries)?;
+            AssetMetadataLocalKeyToName::insert(new, key, name);
+        }
+        for (key, spec) in AssetMetadataLocalSpecs::drain_prefix(old) {
+            try_use_entry::<T>(&mut entries)?;
+            AssetMetadataLocalSpecs::insert(new, key, spec);
+        }
+
+        <Checkpoint<T>>::migrate_ticker(old, new, &mut entries)?;
+        <ExternalAgents<T>>::migrate_ticker(old, new, &mut entries)?;
+        T::ComplianceManager::migrate_ticker(&old, &new, &mut entries)?;
+        <Statistics<T>>::migrate_asset_scope(
+            AssetScope::Ticker(old),
+            AssetScope::Ticker(new),
+            &mut entries,
+        )?;
+        T::TickerMigration::migrate_ticker(&old, &new, &mut entries)?;
+
+        // Redirect `old`, and the tickers redirecting to it, straight to `new`.
+        let mut sources = TickerMigrationSources::take(old);
+        sources.push(old);
+        for source in &sources {
+            try_use_entry::<T>(&mut entries)?;
+            TickerMigrations::insert(source, new);
+        }
+        TickerMigrationSources::insert(new, sources);
+        Self::deposit_event(RawEvent::TickerMigrated(GC_DID, old, new));
+        Ok(())
+    }
+
+    /// Executes the issuances scheduled in block `now`.
+    fn process_scheduled_issuances(now: T::BlockNumber) -> Weight {
+        let ids = ScheduledIssuancesAt::<T>::take(now);
+        let weight = <T as Config>::WeightInfo::execute_scheduled_issuances(ids.len() as u32);
+        for id in ids {
+            if let Some(issuance) = ScheduledIssuances::<T>::take(id) {
+                Self::execute_scheduled_issuance(id, issuance);
+            }
+        }
+        weight
+    }
+
+    /// Issues the tokens of a scheduled issuance, or drops it if it can't be executed.
+    fn execute_scheduled_issuance(
+        id: ScheduledIssuanceId,
+        issuance: ScheduledIssuance<T::BlockNumber>,
+    ) {
+        let ScheduledIssuance {
+            agent,
+            ticker,
+            portfolio,
+            amount,
+            ..
+        } = issuance;
+        // Issuances scheduled before the asset was migrated are executed on its current ticker.
+        let ticker = Self::ticker_migration(ticker).unwrap_or(ticker);
+        let result = with_transaction(|| -> DispatchResult {
+            // The agent must still be permissioned to issue tokens of `ticker`.
+            let issue = CallMetadata {
+                pallet_name: "Asset",
+                function_name: "issue",
+            };
+            with_call_metadata(issue, || {
+                <ExternalAgents<T>>::ensure_agent_permissioned(ticker, agent)
+            })?;
+            Portfolio::<T>::ensure_portfolio_validity(&portfolio)?;
+            Portfolio::<T>::ensure_portfolio_custody(portfolio, agent)?;
+            ensure!(
+                !Self::is_large_issuance(&ticker, amount),
+                Error::<T>::IssuanceRequiresApproval
+            );
+            Self::_mint(&ticker, agent, amount, None)?;
+            if portfolio.kind != PortfolioKind::Default {
+                Portfolio::<T>::unchecked_transfer_portfolio_balance(
+                    &PortfolioId::default_portfolio(agent),
+                    &portfolio,
+                    &ticker,
+                    amount,
+                );
+            }
+            Ok(())
+        });
+        match result {
+            Ok(()) => Self::deposit_event(RawEvent::ScheduledIssuanceExecuted(agent, ticker, id)),
+            Err(error) => {
+                Self::deposit_event(RawEvent::ScheduledIssuanceFailed(agent, ticker, id, error))
+            }
+        }
+    }
 }
@@ -57,6 +57,25 @@ benchmarks! {
         assert_eq!(Balances::<T>::free_balance(&recipient.account), amount);
     }
 
+    batch_transfer_with_memos {
+        let t in 1..100;
+
+        let caller = UserBuilder::<T>::default().balance(100_000u32).generate_did().build("caller");
+        let recipients: Vec<_> = (0..t)
+            .map(|i| UserBuilder::<T>::default().balance(0u32).seed(i).generate_did().build("recipient"))
+            .collect();
+        let transfers = recipients
+            .iter()
+            .map(|recipient| (recipient.lookup(), 42u32.into(), make_worst_memo()))
+            .collect();
+    }: _(caller.origin(), transfers)
+    verify {
+        assert_eq!(Balances::<T>::free_balance(&caller.account), (100_000u32 - 42 * t).into());
+        for recipient in &recipients {
+            assert_eq!(Balances::<T>::free_balance(&recipient.account), 42u32.into());
+        }
+    }
+
     deposit_block_reward_reserve_balance {
         let caller = UserBuilder::<T>::default().balance(1000u32).generate_did().build("caller");
         let amount = 500u32.into();
@@ -90,6 +90,7 @@
 //!
 //! - `transfer` - Transfer some liquid free balance to another account.
 //! - `transfer_with_memo` - Transfer some liquid free balance to another account alon with a memo.
+//! - `batch_transfer_with_memos` - Transfer some liquid free balance to many accounts, each one with its own memo.
 //! - `set_balance` - Set the balances of a given account. The origin of this call must be root.
 //! - `deposit_block_reward_reserve_balance` - Transfer some liquid free balance to block rewards reserve.
 //! - `force_transfer` - Force transfer some balance from one account to another. The origin of this call must be root.
@@ -320,6 +321,31 @@ decl_module! {
             Self::safe_transfer_core(&transactor, &dest, value, memo, ExistenceRequirement::AllowDeath)?;
         }
 
+        // Polymesh modified code. New function to pay out many accounts at once.
+        /// Transfers the native currency to each of the given accounts, along with their memos.
+        ///
+        /// All the transfers succeed or none does. Besides the `Transfer` event of each one,
+        /// a single `BatchTransfer` event sums up the batch.
+        #[weight = <T as Config>::WeightInfo::batch_transfer_with_memos(transfers.len() as u32)]
+        pub fn batch_transfer_with_memos(
+            origin,
+            transfers: Vec<(<T::Lookup as StaticLookup>::Source, Balance, Option<Memo>)>
+        ) {
+            let transactor = ensure_signed(origin)?;
+            let mut total: Balance = Zero::zero();
+            for (dest, value, memo) in transfers.iter().cloned() {
+                let dest = T::Lookup::lookup(dest)?;
+                Self::safe_transfer_core(&transactor, &dest, value, memo, ExistenceRequirement::AllowDeath)?;
+                total = total.checked_add(value).ok_or(Error::<T>::Overflow)?;
+            }
+            Self::deposit_event(RawEvent::BatchTransfer(
+                T::IdentityFn::get_identity(&transactor),
+                transactor,
+                transfers.len() as u32,
+                total,
+            ));
+        }
+
         // Polymesh specific change. New function to transfer balance to BRR.
         /// Move some POLYX from balance of self to balance of BRR.
         #[weight = <T as Config>::WeightInfo::deposit_block_reward_reserve_balance()]
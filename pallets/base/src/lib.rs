@@ -91,6 +91,13 @@ pub fn ensure_opt_string_limited<T: Config>(s: Option<&[u8]>) -> DispatchResult
     }
 }
 
+/// Uses up one of the `remaining` entries of a bounded operation,
+/// failing with `TooLong` if there are none left.
+pub fn try_use_entry<T: Config>(remaining: &mut u32) -> DispatchResult {
+    *remaining = remaining.checked_sub(1).ok_or(Error::<T>::TooLong)?;
+    Ok(())
+}
+
 /// Try to pre-increment the counter `seq` and return the next number/ID to use.
 pub fn try_next_pre<T: Config, I: CheckedInc + Clone>(seq: &mut I) -> Result<I, DispatchError> {
     let id = seq.checked_inc().ok_or(Error::<T>::CounterOverflow)?;
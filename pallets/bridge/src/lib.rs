@@ -47,6 +47,11 @@
 //!
 //! - **bridge limit exempted**: Identities not constrained by the bridge limit.
 //!
+//! - **withdrawal queue**: outbound transfers of POLYX from Polymesh back to Ethereum. Withdrawals
+//! above the withdrawal threshold are delayed by a challenge period during which a freeze admin
+//! can halt them. Halted withdrawals can be resumed or cancelled (refunding the sender) by the
+//! admin.
+//!
 //! ### Transaction State Transitions
 //!
 //! Although the bridge is not implemented as a state machine in the strict sense, the status of a
@@ -96,6 +101,13 @@
 //! - `unfreeze_txs`: Unfreezes given bridge transactions.
 //! - `add_freeze_admin`: Add a freeze admin.
 //! - `remove_freeze_admin`: Remove a freeze admin.
+//! - `remove_txs`: Remove given frozen bridge transactions.
+//! - `withdraw`: Burns POLYX to be released to an Ethereum address, possibly after a challenge
+//! period.
+//! - `halt_withdrawals`: Halts given queued withdrawals.
+//! - `resume_withdrawals`: Resumes given halted withdrawals, restarting their challenge period.
+//! - `cancel_withdrawals`: Cancels given halted withdrawals and refunds their senders.
+//! - `change_withdrawal_queue_params`: Changes the withdrawal threshold and challenge period.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -110,20 +122,23 @@ use frame_support::{
     storage::StorageDoubleMap,
     traits::{
         schedule::{Anon as ScheduleAnon, DispatchTime, LOWEST_PRIORITY},
-        Currency,
+        Currency, ExistenceRequirement, WithdrawReasons,
     },
 };
 use frame_system::{ensure_root, ensure_signed, RawOrigin};
 use pallet_balances as balances;
+use pallet_base::try_next_post;
 use pallet_multisig as multisig;
 use polymesh_common_utilities::traits::balances::Config as BalancesConfig;
 use polymesh_common_utilities::{
     traits::{balances::CheckCdd, identity::Config as IdentityConfig},
     Context, GC_DID,
 };
-use polymesh_primitives::{storage_migration_ver, Balance, IdentityId, Signatory};
+use polymesh_primitives::{
+    impl_checked_inc, storage_migration_ver, Balance, IdentityId, Signatory,
+};
 use scale_info::TypeInfo;
-use sp_core::H256;
+use sp_core::{H160, H256};
 use sp_runtime::traits::{Saturating, Zero};
 #[cfg(feature = "std")]
 use sp_runtime::{Deserialize, Serialize};
@@ -132,6 +147,9 @@ use sp_std::{convert::TryFrom, fmt::Debug, prelude::*, vec};
 type Identity<T> = pallet_identity::Module<T>;
 type System<T> = frame_system::Pallet<T>;
 
+/// The default withdrawal challenge period, in blocks: one day of 6 second blocks.
+pub const DEFAULT_WITHDRAWAL_CHALLENGE_PERIOD: u32 = 14_400;
+
 pub trait Config: multisig::Config + BalancesConfig + pallet_base::Config {
     type RuntimeEvent: From<Event<Self>> + Into<<Self as frame_system::Config>::RuntimeEvent>;
     type Proposal: From<Call<Self>> + Into<<Self as IdentityConfig>::Proposal>;
@@ -218,6 +236,47 @@ impl<T, E: Encode> From<Result<T, E>> for HandledTxStatus {
     }
 }
 
+/// A global and unique outbound withdrawal ID.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, TypeInfo)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub struct WithdrawalId(pub u64);
+impl_checked_inc!(WithdrawalId);
+
+/// The status of an outbound withdrawal.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, TypeInfo)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum WithdrawalStatus {
+    /// The withdrawal is waiting for its challenge period to end.
+    Queued,
+    /// The withdrawal has been halted by a freeze admin. It will not be released automatically.
+    Halted,
+}
+
+impl Default for WithdrawalStatus {
+    fn default() -> Self {
+        WithdrawalStatus::Queued
+    }
+}
+
+/// An outbound withdrawal of POLYX from Polymesh to Ethereum.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, TypeInfo)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub struct Withdrawal<Account, BlockNumber> {
+    /// The account whose POLYX has been burnt.
+    pub sender: Account,
+    /// The recipient address on Ethereum.
+    pub recipient: H160,
+    /// Amount of POLYX tokens withdrawn.
+    pub amount: Balance,
+    /// Status of the withdrawal.
+    pub status: WithdrawalStatus,
+    /// Block number at which this withdrawal is planned to be released.
+    pub release_block: BlockNumber,
+}
+
 decl_error! {
     pub enum Error for Module<T: Config> {
         /// The bridge controller address is not set.
@@ -246,6 +305,16 @@ decl_error! {
         DivisionByZero,
         /// The transaction is timelocked.
         TimelockedTx,
+        /// The withdrawal amount is zero.
+        ZeroWithdrawal,
+        /// The withdrawal does not exist.
+        NoSuchWithdrawal,
+        /// The withdrawal is not queued.
+        WithdrawalNotQueued,
+        /// The withdrawal is not halted.
+        WithdrawalNotHalted,
+        /// The withdrawal challenge period is zero.
+        ZeroChallengePeriod,
     }
 }
 
@@ -290,6 +359,22 @@ decl_storage! {
         /// Identities not constrained by the bridge limit.
         BridgeLimitExempted get(fn bridge_exempted): map hasher(twox_64_concat) IdentityId => bool;
 
+        /// Outbound withdrawals above this amount of POLYX are delayed by the withdrawal challenge
+        /// period.
+        WithdrawalThreshold get(fn withdrawal_threshold): Balance;
+
+        /// The period, in blocks, during which a freeze admin can halt a queued withdrawal.
+        /// Never zero, `DEFAULT_WITHDRAWAL_CHALLENGE_PERIOD` until changed by the admin.
+        WithdrawalChallengePeriod get(fn withdrawal_challenge_period):
+            T::BlockNumber = DEFAULT_WITHDRAWAL_CHALLENGE_PERIOD.into();
+
+        /// The next outbound withdrawal ID.
+        WithdrawalCounter get(fn withdrawal_counter): WithdrawalId;
+
+        /// Outbound withdrawals, identified by their ID, that are neither released nor cancelled.
+        pub Withdrawals get(fn withdrawals):
+            map hasher(twox_64_concat) WithdrawalId => Option<Withdrawal<T::AccountId, T::BlockNumber>>;
+
         /// Storage version.
         StorageVersion get(fn storage_version) build(|_| Version::new(0)): Version;
     }
@@ -346,6 +431,20 @@ decl_event! {
         TxRemoved(IdentityId, BridgeTx<AccountId>),
         /// Bridge Tx failed.  Recipient missing CDD or limit reached.
         BridgeTxFailed(IdentityId, BridgeTx<AccountId>, DispatchError),
+        /// An outbound withdrawal has been queued until the end of its challenge period.
+        /// (caller DID, withdrawal ID, sender, recipient, amount, release block)
+        WithdrawalQueued(IdentityId, WithdrawalId, AccountId, H160, Balance, BlockNumber),
+        /// An outbound withdrawal has been released to the recipient on Ethereum.
+        /// (withdrawal ID, sender, recipient, amount)
+        WithdrawalReleased(WithdrawalId, AccountId, H160, Balance),
+        /// A queued withdrawal has been halted by a freeze admin.
+        WithdrawalHalted(IdentityId, WithdrawalId),
+        /// A halted withdrawal has been resumed by the admin, with its new release block.
+        WithdrawalResumed(IdentityId, WithdrawalId, BlockNumber),
+        /// A halted withdrawal has been cancelled by the admin and the sender refunded.
+        WithdrawalCancelled(IdentityId, WithdrawalId),
+        /// The withdrawal threshold and challenge period have been updated.
+        WithdrawalQueueParamsChanged(IdentityId, Balance, BlockNumber),
     }
 }
 
@@ -560,6 +659,91 @@ decl_module! {
         pub fn remove_txs(origin, bridge_txs: Vec<BridgeTx<T::AccountId>>) -> DispatchResult {
             Self::base_remove_txs(origin, bridge_txs)
         }
+
+        /// Burns `amount` POLYX from the caller to be released to `recipient` on Ethereum.
+        /// Withdrawals above `Self::withdrawal_threshold()` are queued for the withdrawal challenge
+        /// period, during which a freeze admin can halt them.
+        ///
+        /// ## Errors
+        /// - `Frozen` if the bridge is frozen.
+        /// - `ZeroWithdrawal` if `amount` is zero.
+        #[weight = (800_000_000, DispatchClass::Normal, Pays::Yes)]
+        pub fn withdraw(origin, recipient: H160, amount: Balance) -> DispatchResult {
+            Self::base_withdraw(origin, recipient, amount)
+        }
+
+        /// Halts given queued withdrawals.
+        ///
+        /// ## Errors
+        /// - `BadAdmin` if `origin` is not a freeze admin or `Self::admin()` account.
+        /// - `NoSuchWithdrawal` if a withdrawal in `withdrawals` does not exist.
+        /// - `WithdrawalNotQueued` if a withdrawal in `withdrawals` is not queued.
+        #[weight = (
+            400_000_000 + 2_000_000 * u64::try_from(withdrawals.len()).unwrap_or_default(),
+            DispatchClass::Operational,
+            Pays::Yes
+        )]
+        pub fn halt_withdrawals(origin, withdrawals: Vec<WithdrawalId>) -> DispatchResult {
+            Self::base_halt_withdrawals(origin, withdrawals)
+        }
+
+        /// Resumes given halted withdrawals. Their challenge period starts again.
+        ///
+        /// ## Errors
+        /// - `BadAdmin` if `origin` is not `Self::admin()` account.
+        /// - `NoSuchWithdrawal` if a withdrawal in `withdrawals` does not exist.
+        /// - `WithdrawalNotHalted` if a withdrawal in `withdrawals` is not halted.
+        #[weight = (
+            400_000_000 + 7_000_000 * u64::try_from(withdrawals.len()).unwrap_or_default(),
+            DispatchClass::Operational,
+            Pays::Yes
+        )]
+        pub fn resume_withdrawals(origin, withdrawals: Vec<WithdrawalId>) -> DispatchResult {
+            Self::base_resume_withdrawals(origin, withdrawals)
+        }
+
+        /// Cancels given halted withdrawals and refunds their senders.
+        ///
+        /// ## Errors
+        /// - `BadAdmin` if `origin` is not `Self::admin()` account.
+        /// - `NoSuchWithdrawal` if a withdrawal in `withdrawals` does not exist.
+        /// - `WithdrawalNotHalted` if a withdrawal in `withdrawals` is not halted.
+        #[weight = (
+            400_000_000 + 7_000_000 * u64::try_from(withdrawals.len()).unwrap_or_default(),
+            DispatchClass::Operational,
+            Pays::Yes
+        )]
+        pub fn cancel_withdrawals(origin, withdrawals: Vec<WithdrawalId>) -> DispatchResult {
+            Self::base_cancel_withdrawals(origin, withdrawals)
+        }
+
+        /// Changes the withdrawal threshold and the withdrawal challenge period.
+        ///
+        /// ## Errors
+        /// - `BadAdmin` if `origin` is not `Self::admin()` account.
+        /// - `ZeroChallengePeriod` if `challenge_period` is zero.
+        #[weight = (300_000_000, DispatchClass::Operational, Pays::Yes)]
+        pub fn change_withdrawal_queue_params(
+            origin,
+            threshold: Balance,
+            challenge_period: T::BlockNumber
+        ) -> DispatchResult {
+            Self::base_change_withdrawal_queue_params(origin, threshold, challenge_period)
+        }
+
+        /// Root callable extrinsic, used as an internal call to release a queued withdrawal at the
+        /// end of its challenge period.
+        ///
+        /// # Errors
+        /// - `BadOrigin` if `origin` is not root.
+        #[weight = (
+            500_000_000,
+            DispatchClass::Operational,
+            Pays::Yes
+        )]
+        fn handle_scheduled_withdrawal(origin, id: WithdrawalId) {
+            Self::base_handle_scheduled_withdrawal(origin, id)?;
+        }
     }
 }
 
@@ -1038,4 +1222,180 @@ impl<T: Config> Module<T> {
         }
         Ok(())
     }
+
+    fn base_withdraw(origin: T::RuntimeOrigin, recipient: H160, amount: Balance) -> DispatchResult {
+        let sender = ensure_signed(origin)?;
+        let did = Context::current_identity_or::<Identity<T>>(&sender)?;
+        ensure!(!Self::frozen(), Error::<T>::Frozen);
+        ensure!(!amount.is_zero(), Error::<T>::ZeroWithdrawal);
+
+        let id = WithdrawalCounter::try_mutate(try_next_post::<T, _>)?;
+        let _neg_imbalance = <balances::Pallet<T>>::withdraw(
+            &sender,
+            amount,
+            WithdrawReasons::TRANSFER,
+            ExistenceRequirement::KeepAlive,
+        )?;
+
+        let mut withdrawal = Withdrawal {
+            sender,
+            recipient,
+            amount,
+            status: WithdrawalStatus::Queued,
+            release_block: System::<T>::block_number(),
+        };
+        if amount <= Self::withdrawal_threshold() {
+            // Small withdrawals are released immediately.
+            return Ok(Self::release_withdrawal(id, withdrawal));
+        }
+
+        withdrawal.release_block = withdrawal
+            .release_block
+            .saturating_add(Self::withdrawal_challenge_period());
+        Self::schedule_withdrawal(id, withdrawal.release_block)?;
+        Self::deposit_event(RawEvent::WithdrawalQueued(
+            did,
+            id,
+            withdrawal.sender.clone(),
+            recipient,
+            amount,
+            withdrawal.release_block,
+        ));
+        <Withdrawals<T>>::insert(id, withdrawal);
+        Ok(())
+    }
+
+    /// Removes the withdrawal and emits the event picked up by the bridge relayers.
+    fn release_withdrawal(id: WithdrawalId, withdrawal: Withdrawal<T::AccountId, T::BlockNumber>) {
+        <Withdrawals<T>>::remove(id);
+        Self::deposit_event(RawEvent::WithdrawalReleased(
+            id,
+            withdrawal.sender,
+            withdrawal.recipient,
+            withdrawal.amount,
+        ));
+    }
+
+    /// Schedules the release of a queued withdrawal at `block_number`.
+    fn schedule_withdrawal(id: WithdrawalId, block_number: T::BlockNumber) -> DispatchResult {
+        let call = Call::<T>::handle_scheduled_withdrawal { id }.into();
+        <T as Config>::Scheduler::schedule(
+            DispatchTime::At(block_number),
+            None,
+            LOWEST_PRIORITY,
+            RawOrigin::Root.into(),
+            call,
+        )?;
+        Ok(())
+    }
+
+    fn base_handle_scheduled_withdrawal(
+        origin: T::RuntimeOrigin,
+        id: WithdrawalId,
+    ) -> DispatchResult {
+        ensure_root(origin)?;
+        let mut withdrawal = match Self::withdrawals(id) {
+            // Halted or cancelled withdrawals are not released automatically.
+            Some(w) if w.status == WithdrawalStatus::Queued => w,
+            _ => return Ok(()),
+        };
+        if Self::frozen() {
+            // Bridge module frozen. Retry this withdrawal again later.
+            withdrawal.release_block =
+                System::<T>::block_number().saturating_add(Self::withdrawal_challenge_period());
+            Self::schedule_withdrawal(id, withdrawal.release_block)?;
+            <Withdrawals<T>>::insert(id, withdrawal);
+            return Ok(());
+        }
+        Self::release_withdrawal(id, withdrawal);
+        Ok(())
+    }
+
+    /// Ensures that all `ids` are withdrawals with the status `status`, returning them.
+    fn ensure_withdrawals_status(
+        ids: Vec<WithdrawalId>,
+        status: WithdrawalStatus,
+        error: Error<T>,
+    ) -> Result<Vec<(WithdrawalId, Withdrawal<T::AccountId, T::BlockNumber>)>, DispatchError> {
+        let error: DispatchError = error.into();
+        ids.into_iter()
+            .map(|id| -> Result<_, DispatchError> {
+                let withdrawal = Self::withdrawals(id).ok_or(Error::<T>::NoSuchWithdrawal)?;
+                ensure!(withdrawal.status == status, error);
+                Ok((id, withdrawal))
+            })
+            .collect()
+    }
+
+    fn base_halt_withdrawals(origin: T::RuntimeOrigin, ids: Vec<WithdrawalId>) -> DispatchResult {
+        let did = Self::ensure_freeze_admin_did(origin)?;
+        let withdrawals = Self::ensure_withdrawals_status(
+            ids,
+            WithdrawalStatus::Queued,
+            Error::<T>::WithdrawalNotQueued,
+        )?;
+        for (id, mut withdrawal) in withdrawals {
+            withdrawal.status = WithdrawalStatus::Halted;
+            <Withdrawals<T>>::insert(id, withdrawal);
+            Self::deposit_event(RawEvent::WithdrawalHalted(did, id));
+        }
+        Ok(())
+    }
+
+    fn base_resume_withdrawals(origin: T::RuntimeOrigin, ids: Vec<WithdrawalId>) -> DispatchResult {
+        let did = Self::ensure_admin_did(origin)?;
+        let withdrawals = Self::ensure_withdrawals_status(
+            ids,
+            WithdrawalStatus::Halted,
+            Error::<T>::WithdrawalNotHalted,
+        )?;
+        let release_block =
+            System::<T>::block_number().saturating_add(Self::withdrawal_challenge_period());
+        for (id, mut withdrawal) in withdrawals {
+            Self::schedule_withdrawal(id, release_block)?;
+            withdrawal.status = WithdrawalStatus::Queued;
+            withdrawal.release_block = release_block;
+            <Withdrawals<T>>::insert(id, withdrawal);
+            Self::deposit_event(RawEvent::WithdrawalResumed(did, id, release_block));
+        }
+        Ok(())
+    }
+
+    fn base_cancel_withdrawals(origin: T::RuntimeOrigin, ids: Vec<WithdrawalId>) -> DispatchResult {
+        let did = Self::ensure_admin_did(origin)?;
+        let withdrawals = Self::ensure_withdrawals_status(
+            ids,
+            WithdrawalStatus::Halted,
+            Error::<T>::WithdrawalNotHalted,
+        )?;
+        for (id, withdrawal) in withdrawals {
+            let _pos_imbalance =
+                <balances::Pallet<T>>::deposit_creating(&withdrawal.sender, withdrawal.amount);
+            <Withdrawals<T>>::remove(id);
+            Self::deposit_event(RawEvent::WithdrawalCancelled(did, id));
+        }
+        Ok(())
+    }
+
+    fn base_change_withdrawal_queue_params(
+        origin: T::RuntimeOrigin,
+        threshold: Balance,
+        challenge_period: T::BlockNumber,
+    ) -> DispatchResult {
+        let did = Self::ensure_admin_did(origin)?;
+        ensure!(!challenge_period.is_zero(), Error::<T>::ZeroChallengePeriod);
+        WithdrawalThreshold::put(threshold);
+        <WithdrawalChallengePeriod<T>>::put(challenge_period);
+        Self::deposit_event(RawEvent::WithdrawalQueueParamsChanged(
+            did,
+            threshold,
+            challenge_period,
+        ));
+        Ok(())
+    }
+
+    /// Returns the withdrawals that have neither been released nor cancelled yet.
+    pub fn withdrawal_queue() -> Vec<(WithdrawalId, Withdrawal<T::AccountId, T::BlockNumber>)> {
+        <Withdrawals<T>>::iter().collect()
+    }
 }
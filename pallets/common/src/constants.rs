@@ -50,9 +50,16 @@ pub mod queue_priority {
 /// Prefixes for scheduled actions
 pub mod schedule_name_prefix {
     pub const SETTLEMENT_INSTRUCTION_EXECUTION: [u8; 27] = *b"SETTLEMENT_INSTRUCTION_EXEC";
+    pub const SETTLEMENT_INSTRUCTION_EXPIRY: [u8; 29] = *b"SETTLEMENT_INSTRUCTION_EXPIRY";
+    pub const SETTLEMENT_RECURRING_INSTRUCTION: [u8; 32] = *b"SETTLEMENT_RECURRING_INSTRUCTION";
     pub const MULTISIG_PROPOSAL_EXECUTION: [u8; 22] = *b"MULTISIG_PROPOSAL_EXEC";
     pub const PIP_EXECUTION: [u8; 8] = *b"PIP_EXEC";
     pub const PIP_EXPIRY: [u8; 10] = *b"PIP_EXPIRY";
+    pub const PORTFOLIO_SWEEP_RULE: [u8; 20] = *b"PORTFOLIO_SWEEP_RULE";
+    pub const IDENTITY_CLAIM_REVOCATION: [u8; 25] = *b"IDENTITY_CLAIM_REVOCATION";
+    pub const IDENTITY_PERMISSION_REVERT: [u8; 26] = *b"IDENTITY_PERMISSION_REVERT";
+    pub const IDENTITY_PROBATION_END: [u8; 22] = *b"IDENTITY_PROBATION_END";
+    pub const COMPLIANCE_REQUIREMENT_CHANGE: [u8; 29] = *b"COMPLIANCE_REQUIREMENT_CHANGE";
 }
 
 // ERC1400 transfer status codes
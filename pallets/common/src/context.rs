@@ -70,7 +70,7 @@ impl<AccountId> Context<AccountId> {
 #[cfg(test)]
 mod test {
     use super::*;
-    use polymesh_primitives::{AccountId, IdentityId};
+    use polymesh_primitives::{AccountId, ClaimType, IdentityId};
 
     use lazy_static::lazy_static;
     use std::{collections::BTreeMap, convert::From, sync::RwLock, thread};
@@ -118,6 +118,10 @@ mod test {
         fn has_valid_cdd(_target_did: IdentityId) -> bool {
             true
         }
+
+        fn has_valid_claim(_: IdentityId, _: ClaimType, _: IdentityId) -> bool {
+            false
+        }
     }
 
     #[test]
@@ -13,24 +13,34 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <http://www.gnu.org/licenses/>.
 
+use codec::{Decode, Encode};
 use frame_support::decl_event;
-use frame_support::dispatch::DispatchResult;
+use frame_support::dispatch::{DispatchError, DispatchResult};
 use frame_support::traits::{Currency, Get, UnixTime};
 use frame_support::weights::Weight;
 use polymesh_primitives::{
-    asset::{AssetName, AssetType, CustomAssetTypeId, FundingRoundName},
+    asset::{AssetLifecycleState, AssetName, AssetType, CustomAssetTypeId, FundingRoundName},
     asset_metadata::{
         AssetMetadataGlobalKey, AssetMetadataKey, AssetMetadataLocalKey, AssetMetadataName,
         AssetMetadataSpec, AssetMetadataValue, AssetMetadataValueDetail,
     },
     ethereum::EthereumAddress,
-    AssetIdentifier, Balance, Document, DocumentId, IdentityId, PortfolioId, ScopeId, Ticker,
+    impl_checked_inc, AssetIdentifier, Balance, Document, DocumentId, IdentityId, Moment,
+    PortfolioId, ScopeId, Ticker,
 };
+use scale_info::TypeInfo;
+use sp_runtime::Permill;
 use sp_std::prelude::Vec;
 
 use crate::traits::nft::NFTTrait;
 use crate::traits::{checkpoint, compliance_manager, external_agents, portfolio, statistics};
 
+/// The ID of a scheduled issuance.
+#[derive(Encode, Decode, TypeInfo)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub struct ScheduledIssuanceId(pub u64);
+impl_checked_inc!(ScheduledIssuanceId);
+
 /// This trait is used by the `identity` pallet to interact with the `pallet-asset`.
 pub trait AssetSubTrait {
     /// Update the `ticker` balance of `target_did` under `scope_id`. Clean up the balances related
@@ -54,6 +64,27 @@ pub trait AssetSubTrait {
 
     /// Ensure that Investor Uniqueness is allowed for the ticker.
     fn ensure_investor_uniqueness_claims_allowed(ticker: &Ticker) -> DispatchResult;
+
+    /// Accepts the ticker transfer authorization `auth_id` on behalf of `to`.
+    fn accept_ticker_transfer(to: IdentityId, auth_id: u64) -> DispatchResult;
+
+    /// Accepts the asset ownership transfer authorization `auth_id` on behalf of `to`.
+    fn accept_asset_ownership_transfer(to: IdentityId, auth_id: u64) -> DispatchResult;
+}
+
+/// Handles the ticker migrations of assets in pallets depending on `pallet-asset`.
+pub trait TickerMigrationHandler {
+    /// Called once the asset `old` has been migrated to `new`, to move the storage keyed by `old`.
+    /// Each storage entry moved uses up one of the `entries`.
+    fn migrate_ticker(old: &Ticker, new: &Ticker, entries: &mut u32) -> DispatchResult;
+}
+
+#[impl_trait_for_tuples::impl_for_tuples(5)]
+impl TickerMigrationHandler for Tuple {
+    fn migrate_ticker(old: &Ticker, new: &Ticker, entries: &mut u32) -> DispatchResult {
+        for_tuples!( #( Tuple::migrate_ticker(old, new, entries)?; )* );
+        Ok(())
+    }
 }
 
 pub trait AssetFnTrait<Account, Origin> {
@@ -62,6 +93,14 @@ pub trait AssetFnTrait<Account, Origin> {
 
     fn balance(ticker: &Ticker, did: IdentityId) -> Balance;
 
+    /// Returns when `did` opened their current position in `ticker`.
+    /// Positions opened before acquisitions were recorded are taken to be opened when recording began.
+    fn acquired_at(ticker: &Ticker, did: IdentityId) -> Moment;
+
+    /// Returns up to `limit` identities holding a balance of `ticker`, in ascending order,
+    /// starting after `start_after` if given.
+    fn holders(ticker: &Ticker, start_after: Option<IdentityId>, limit: u32) -> Vec<IdentityId>;
+
     fn create_asset(
         origin: Origin,
         name: AssetName,
@@ -119,6 +158,28 @@ pub trait WeightInfo {
     fn update_asset_type() -> Weight;
     fn remove_local_metadata_key() -> Weight;
     fn remove_metadata_value() -> Weight;
+    fn propose_authorized_supply() -> Weight;
+    fn approve_authorized_supply() -> Weight;
+    fn set_ticker_expiry_periods() -> Weight;
+    fn set_ticker_auto_renewal() -> Weight;
+    fn process_expiring_tickers(t: u32) -> Weight;
+    fn accept_ticker_renewal_agent() -> Weight;
+    fn remove_ticker_renewal_agent() -> Weight;
+    fn renew_ticker_registration() -> Weight;
+    fn schedule_issuance() -> Weight;
+    fn cancel_scheduled_issuance() -> Weight;
+    fn execute_scheduled_issuances(i: u32) -> Weight;
+    fn migrate_ticker(h: u32, e: u32) -> Weight;
+    fn set_lifecycle_state() -> Weight;
+    fn set_large_issuance_threshold() -> Weight;
+    fn propose_issuance() -> Weight;
+    fn approve_issuance() -> Weight;
+    fn cancel_issuance() -> Weight;
+    fn issuer_batch_transfer(t: u32) -> Weight;
+    fn update_document() -> Weight;
+    fn acknowledge_document() -> Weight;
+    fn controller_transfer_batch(t: u32) -> Weight;
+    fn remove_document_acknowledgements(l: u32) -> Weight;
 }
 
 /// The module's configuration trait.
@@ -162,6 +223,9 @@ pub trait Config:
     type CPWeightInfo: crate::traits::checkpoint::WeightInfo;
 
     type NFTFn: NFTTrait<Self::RuntimeOrigin>;
+
+    /// Moves the storage of other pallets when the ticker of an asset is migrated.
+    type TickerMigration: TickerMigrationHandler;
 }
 
 decl_event! {
@@ -169,6 +233,7 @@ decl_event! {
     where
         Moment = <T as pallet_timestamp::Config>::Moment,
         AccountId = <T as frame_system::Config>::AccountId,
+        BlockNumber = <T as frame_system::Config>::BlockNumber,
     {
         /// Event for transfer of tokens.
         /// caller DID, ticker, from portfolio, to portfolio, value
@@ -254,5 +319,74 @@ decl_event! {
         /// An event emitted when a local metadata value has been removed.
         /// Parameters: caller ticker, Local type name
         MetadataValueDeleted(IdentityId, Ticker, AssetMetadataKey),
+        /// An agent proposed a new authorized supply for an asset.
+        /// Parameters: caller DID, ticker, proposed authorized supply.
+        AuthorizedSupplyProposed(IdentityId, Ticker, Option<Balance>),
+        /// Another agent approved the proposed authorized supply of an asset.
+        /// Parameters: caller DID, ticker, authorized supply.
+        AuthorizedSupplySet(IdentityId, Ticker, Option<Balance>),
+        /// The notice and grace periods of ticker registrations have been set.
+        /// Parameters: GC DID, notice period, grace period.
+        TickerExpiryPeriodsSet(IdentityId, Moment, Moment),
+        /// The owner of a ticker enabled or disabled its auto-renewal.
+        /// Parameters: caller DID, ticker, auto-renewal enabled.
+        TickerAutoRenewalSet(IdentityId, Ticker, bool),
+        /// A ticker registration expires within the notice period.
+        /// Parameters: owner DID, ticker, expiry.
+        TickerExpiringSoon(IdentityId, Ticker, Moment),
+        /// A ticker registration has been renewed.
+        /// Parameters: owner DID, ticker, new expiry.
+        TickerRenewed(IdentityId, Ticker, Option<Moment>),
+        /// The automatic renewal of a ticker registration failed.
+        /// Parameters: owner DID, ticker, error.
+        TickerRenewalFailed(IdentityId, Ticker, DispatchError),
+        /// The renewal agent of a ticker accepted its authorization, or was removed.
+        /// Parameters: caller DID, ticker, renewal agent.
+        TickerRenewalAgentSet(IdentityId, Ticker, Option<IdentityId>),
+        /// An asset and its storage have been moved to a new ticker.
+        /// Parameters: GC DID, old ticker, new ticker.
+        TickerMigrated(IdentityId, Ticker, Ticker),
+        /// The lifecycle state of an asset has changed.
+        /// Parameters: caller DID, ticker, previous state, new state.
+        AssetLifecycleStateChanged(IdentityId, Ticker, AssetLifecycleState, AssetLifecycleState),
+        /// The large issuance threshold has been set.
+        /// Parameters: GC DID, share of the total supply (`None` if disabled), timelock.
+        LargeIssuanceThresholdSet(IdentityId, Option<Permill>, Moment),
+        /// An agent proposed a large issuance.
+        /// Parameters: caller DID, ticker, amount, time from which it can be approved.
+        IssuanceProposed(IdentityId, Ticker, Balance, Moment),
+        /// A proposed issuance has been approved and its tokens issued.
+        /// Parameters: caller DID (GC DID if approved by governance), ticker, amount.
+        IssuanceApproved(IdentityId, Ticker, Balance),
+        /// A proposed issuance has been cancelled.
+        /// Parameters: caller DID, ticker.
+        IssuanceCancelled(IdentityId, Ticker),
+        /// An agent has made a batch of transfers, the failed ones being skipped.
+        /// Parameters: caller DID, ticker, sender portfolio, (receiver portfolio, value, status code) of each transfer.
+        IssuerBatchTransfer(IdentityId, Ticker, PortfolioId, Vec<(PortfolioId, Balance, u8)>),
+        /// An agent scheduled an issuance into one of its portfolios.
+        /// Parameters: caller DID, ticker, scheduled issuance ID, portfolio, amount, block of the issuance.
+        IssuanceScheduled(IdentityId, Ticker, ScheduledIssuanceId, PortfolioId, Balance, BlockNumber),
+        /// A scheduled issuance has been executed.
+        /// Parameters: DID of the agent who scheduled it, ticker, scheduled issuance ID.
+        ScheduledIssuanceExecuted(IdentityId, Ticker, ScheduledIssuanceId),
+        /// A scheduled issuance could not be executed and has been dropped.
+        /// Parameters: DID of the agent who scheduled it, ticker, scheduled issuance ID, error.
+        ScheduledIssuanceFailed(IdentityId, Ticker, ScheduledIssuanceId, DispatchError),
+        /// A scheduled issuance has been cancelled.
+        /// Parameters: caller DID, ticker, scheduled issuance ID.
+        ScheduledIssuanceCancelled(IdentityId, Ticker, ScheduledIssuanceId),
+        /// A document has been replaced with a new version.
+        /// Parameters: caller DID, ticker, document ID, new version, new document.
+        DocumentUpdated(IdentityId, Ticker, DocumentId, u32, Document),
+        /// An identity acknowledged a version of a document.
+        /// Parameters: caller DID, ticker, document ID, version.
+        DocumentAcknowledged(IdentityId, Ticker, DocumentId, u32),
+        /// A transfer of a controller batch has been forced.
+        /// Parameters: caller DID, ticker, sender portfolio, receiver portfolio, value.
+        ControllerBatchTransfer(IdentityId, Ticker, PortfolioId, PortfolioId, Balance),
+        /// Acknowledgements and previous versions of a removed document have been removed.
+        /// Parameters: caller DID, ticker, document ID, number of acknowledgements and versions removed.
+        DocumentAcknowledgementsRemoved(IdentityId, Ticker, DocumentId, u32),
     }
 }
@@ -144,6 +144,9 @@ decl_event!(
         /// Final argument indicates the destination balance type.
         /// \[from, to, balance, destination_status]
         ReserveRepatriated(AccountId, AccountId, Balance, Status),
+        /// A batch of transfers succeeded, each one also emitting a `Transfer` event.
+        /// (from_did, from, number_of_transfers, total_value)
+        BatchTransfer(Option<IdentityId>, AccountId, u32, Balance),
     }
 );
 
@@ -154,6 +157,7 @@ pub trait WeightInfo {
     fn set_balance() -> Weight;
     fn force_transfer() -> Weight;
     fn burn_account_balance() -> Weight;
+    fn batch_transfer_with_memos(t: u32) -> Weight;
 }
 
 pub trait Config: IdentityConfig {
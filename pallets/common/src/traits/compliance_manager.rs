@@ -14,12 +14,20 @@
 // along with this program. If not, see <http://www.gnu.org/licenses/>.
 
 use core::result::Result;
-use frame_support::{dispatch::DispatchError, weights::Weight};
+use frame_support::{
+    dispatch::{DispatchError, DispatchResult},
+    weights::Weight,
+};
 use polymesh_primitives::{
-    compliance_manager::{AssetComplianceResult, ComplianceRequirement},
+    asset::AssetType,
+    compliance_manager::{
+        AssetComplianceResult, ComplianceRequirement, ComplianceRequirementChange,
+        ComplianceSummary,
+    },
     condition::{conditions_total_counts, Condition},
-    Balance, IdentityId, Ticker,
+    Balance, ClaimType, IdentityId, Ticker,
 };
+use sp_std::collections::btree_set::BTreeSet;
 
 pub trait Config {
     fn verify_restriction(
@@ -34,6 +42,27 @@ pub trait Config {
         from_did_opt: Option<IdentityId>,
         to_did_opt: Option<IdentityId>,
     ) -> AssetComplianceResult;
+
+    /// Same as `verify_restriction_granular`, evaluating the transfer limits for a transfer of `value`.
+    fn verify_restriction_granular_for_amount(
+        ticker: &Ticker,
+        from_did_opt: Option<IdentityId>,
+        to_did_opt: Option<IdentityId>,
+        value: Balance,
+    ) -> AssetComplianceResult;
+
+    /// Attaches the requirements template for `asset_type` to the newly created asset `ticker`.
+    fn apply_requirements_template(ticker: &Ticker, did: IdentityId, asset_type: AssetType);
+
+    /// Returns an overview of the compliance rules of `ticker`.
+    fn compliance_summary(ticker: &Ticker) -> ComplianceSummary;
+
+    /// Returns the types of the claims that the compliance requirements of `ticker` check.
+    fn claim_types(ticker: &Ticker) -> BTreeSet<ClaimType>;
+
+    /// Moves the compliance requirements, trusted issuers and disabled requirements of `old` to `new`.
+    /// Each scheduled re-enabling and requirement change moved uses up one of the `entries`.
+    fn migrate_ticker(old: &Ticker, new: &Ticker, entries: &mut u32) -> DispatchResult;
 }
 
 pub trait WeightInfo {
@@ -46,6 +75,17 @@ pub trait WeightInfo {
     fn change_compliance_requirement(c: u32) -> Weight;
     fn replace_asset_compliance(c: u32) -> Weight;
     fn reset_asset_compliance() -> Weight;
+    fn set_default_requirements_template(c: u32) -> Weight;
+    fn set_issuer_requirements_template(c: u32) -> Weight;
+    fn disable_compliance_requirements(r: u32) -> Weight;
+    fn enable_compliance_requirements(r: u32) -> Weight;
+    fn reenable_compliance_requirements(r: u32) -> Weight;
+    fn schedule_compliance_requirement_change(c: u32) -> Weight;
+    fn cancel_compliance_requirement_change() -> Weight;
+    fn apply_compliance_requirement_change() -> Weight;
+    fn register_compliance_template(c: u32) -> Weight;
+    fn remove_compliance_template() -> Weight;
+    fn apply_compliance_template() -> Weight;
 
     fn condition_costs(conditions: u32, claims: u32, issuers: u32, claim_types: u32) -> Weight;
 
@@ -60,6 +100,17 @@ pub trait WeightInfo {
         ))
     }
 
+    fn schedule_compliance_requirement_change_full(change: &ComplianceRequirementChange) -> Weight {
+        let (conditions, claims, issuers, claim_types) = match change {
+            ComplianceRequirementChange::Add(req) | ComplianceRequirementChange::Replace(req) => {
+                req.counts()
+            }
+            ComplianceRequirementChange::Remove(_) => (0, 0, 0, 0),
+        };
+        Self::schedule_compliance_requirement_change(conditions)
+            .saturating_add(Self::condition_costs(0, claims, issuers, claim_types))
+    }
+
     fn change_compliance_requirement_full(req: &ComplianceRequirement) -> Weight {
         let (conditions, claims, issuers, claim_types) = req.counts();
         Self::change_compliance_requirement(conditions).saturating_add(Self::condition_costs(
@@ -80,4 +131,31 @@ pub trait WeightInfo {
             claim_types,
         ))
     }
+
+    fn set_default_requirements_template_full(reqs: &[ComplianceRequirement]) -> Weight {
+        let (conditions, claims, issuers, claim_types) =
+            conditions_total_counts(reqs.iter().flat_map(|req| req.conditions()));
+        Self::set_default_requirements_template(reqs.len() as u32).saturating_add(
+            Self::condition_costs(conditions, claims, issuers, claim_types),
+        )
+    }
+
+    fn set_issuer_requirements_template_full(reqs: &[ComplianceRequirement]) -> Weight {
+        let (conditions, claims, issuers, claim_types) =
+            conditions_total_counts(reqs.iter().flat_map(|req| req.conditions()));
+        Self::set_issuer_requirements_template(reqs.len() as u32).saturating_add(
+            Self::condition_costs(conditions, claims, issuers, claim_types),
+        )
+    }
+
+    fn register_compliance_template_full(reqs: &[ComplianceRequirement]) -> Weight {
+        let (conditions, claims, issuers, claim_types) =
+            conditions_total_counts(reqs.iter().flat_map(|req| req.conditions()));
+        Self::register_compliance_template(reqs.len() as u32).saturating_add(Self::condition_costs(
+            conditions,
+            claims,
+            issuers,
+            claim_types,
+        ))
+    }
 }
@@ -1,6 +1,7 @@
-use frame_support::{decl_event, weights::Weight};
+use frame_support::{decl_event, traits::Get, weights::Weight};
 use polymesh_primitives::agent::{AGId, AgentGroup};
 use polymesh_primitives::{EventDid, ExtrinsicPermissions, IdentityId, Ticker};
+use sp_std::vec::Vec;
 
 pub trait WeightInfo {
     fn create_group(p: u32) -> Weight;
@@ -12,17 +13,29 @@ pub trait WeightInfo {
     fn change_group_builtin() -> Weight;
     fn change_group_custom() -> Weight;
     fn accept_become_agent() -> Weight;
+    fn register_session_keys(k: u32) -> Weight;
+    fn expire_session_keys(k: u32) -> Weight;
+    fn schedule_agent_replacement() -> Weight;
+    fn cancel_agent_replacement() -> Weight;
+    fn replace_agent(a: u32, g: u32) -> Weight;
 }
 
 pub trait Config: frame_system::Config + crate::balances::Config {
     /// The overarching event type.
-    type RuntimeEvent: From<Event> + Into<<Self as frame_system::Config>::RuntimeEvent>;
+    type RuntimeEvent: From<Event<Self>> + Into<<Self as frame_system::Config>::RuntimeEvent>;
 
     type WeightInfo: WeightInfo;
+
+    /// Notice given to the sole full agent of an asset before governance can replace it.
+    type AgentReplacementDelay: Get<<Self as pallet_timestamp::Config>::Moment>;
 }
 
 decl_event! {
-    pub enum Event {
+    pub enum Event<T>
+    where
+        AccountId = <T as frame_system::Config>::AccountId,
+        Moment = <T as pallet_timestamp::Config>::Moment,
+    {
         /// An Agent Group was created.
         ///
         /// (Caller DID, AG's ticker, AG's ID, AG's permissions)
@@ -47,5 +60,30 @@ decl_event! {
         ///
         /// (Caller DID, Agent's ticker, Agent's DID, The new group of the agent)
         GroupChanged(EventDid, Ticker, IdentityId, AgentGroup),
+
+        /// Session keys were registered by an agent.
+        ///
+        /// (Agent DID, Session keys' ticker, Session keys, Session keys' permissions, Expiry)
+        SessionKeysRegistered(EventDid, Ticker, Vec<AccountId>, ExtrinsicPermissions, Moment),
+
+        /// Session keys were expired by an agent.
+        ///
+        /// (Agent DID, Session keys' ticker, Session keys)
+        SessionKeysExpired(EventDid, Ticker, Vec<AccountId>),
+
+        /// Governance scheduled the replacement of the sole full agent of an asset.
+        ///
+        /// (Governance DID, Agent's ticker, New agent's DID, Moment from which it can be carried out)
+        AgentReplacementScheduled(IdentityId, Ticker, IdentityId, Moment),
+
+        /// A scheduled agent replacement was cancelled.
+        ///
+        /// (Caller DID, Agent's ticker)
+        AgentReplacementCancelled(IdentityId, Ticker),
+
+        /// Governance replaced all the agents of an asset by a single full agent.
+        ///
+        /// (Governance DID, Agent's ticker, New agent's DID)
+        AgentReplaced(IdentityId, Ticker, IdentityId),
     }
 }
@@ -28,16 +28,17 @@ use codec::{Decode, Encode};
 use frame_support::{
     decl_event,
     dispatch::{GetDispatchInfo, PostDispatchInfo, Weight},
-    traits::{Currency, EnsureOrigin, Get, GetCallMetadata},
+    traits::{schedule::Named as ScheduleNamed, Currency, EnsureOrigin, Get, GetCallMetadata},
     Parameter,
 };
 use polymesh_primitives::{
     secondary_key::{v1, SecondaryKey},
-    AuthorizationData, Balance, CustomClaimTypeId, IdentityClaim, IdentityId, Permissions,
-    Signatory, Ticker,
+    Agreement, AgreementConsent, AgreementId, AuthorizationData, Balance, CallFilter, Claim,
+    ClaimType, CustomClaimSchema, CustomClaimTypeId, DispatchableName, IdentityClaim, IdentityId,
+    PalletName, Permissions, RecoveryGuardians, Scope, Signatory, Ticker,
 };
 use scale_info::TypeInfo;
-use sp_core::H512;
+use sp_core::{H256, H512};
 use sp_runtime::traits::{Dispatchable, IdentifyAccount, Member, Verify};
 use sp_std::convert::TryFrom;
 use sp_std::vec::Vec;
@@ -113,6 +114,9 @@ pub trait WeightInfo {
     fn join_identity_as_key() -> Weight;
     fn leave_identity_as_key() -> Weight;
     fn add_claim() -> Weight;
+    fn add_claims_batch(c: u32) -> Weight;
+    fn schedule_claim_revocation() -> Weight;
+    fn revoke_scheduled_claim() -> Weight;
     fn revoke_claim() -> Weight;
     fn set_secondary_key_permissions() -> Weight;
     /// Complexity Parameters:
@@ -129,6 +133,13 @@ pub trait WeightInfo {
 
     fn freeze_secondary_keys() -> Weight;
     fn unfreeze_secondary_keys() -> Weight;
+    fn set_secondary_key_expiry() -> Weight;
+    fn grant_temporary_permissions() -> Weight;
+    fn revert_temporary_permissions() -> Weight;
+    fn set_key_usage_audit() -> Weight;
+    fn set_key_probation_period() -> Weight;
+    fn confirm_secondary_key() -> Weight;
+    fn end_key_probation() -> Weight;
     fn add_authorization() -> Weight;
     fn remove_authorization() -> Weight;
     fn add_secondary_keys_with_authorization(n: u32) -> Weight;
@@ -136,6 +147,38 @@ pub trait WeightInfo {
     fn add_investor_uniqueness_claim_v2() -> Weight;
     fn revoke_claim_by_index() -> Weight;
     fn register_custom_claim_type(n: u32) -> Weight;
+    fn set_key_recovery_delegate() -> Weight;
+    fn initiate_key_recovery() -> Weight;
+    fn cancel_key_recovery() -> Weight;
+    fn execute_key_recovery() -> Weight;
+    fn set_recovery_guardians(n: u32) -> Weight;
+    fn approve_social_recovery() -> Weight;
+    fn cancel_social_recovery() -> Weight;
+    fn execute_social_recovery() -> Weight;
+    fn set_claim_issuance_limit() -> Weight;
+    fn set_issuer_claim_issuance_limit() -> Weight;
+    fn add_call_filter() -> Weight;
+    fn request_call_filter_removal() -> Weight;
+    fn remove_call_filter() -> Weight;
+    fn publish_agreement() -> Weight;
+    fn update_agreement() -> Weight;
+    fn give_consent() -> Weight;
+    fn revoke_consent() -> Weight;
+    fn approve_parent_identity() -> Weight;
+    fn link_child_identity() -> Weight;
+    fn create_child_identity() -> Weight;
+    fn unlink_child_identity() -> Weight;
+    fn set_child_secondary_key_permissions() -> Weight;
+    fn remove_child_secondary_keys(i: u32) -> Weight;
+    fn set_child_secondary_keys_frozen() -> Weight;
+    fn register_custom_claim_type_with_schema(n: u32, f: u32) -> Weight;
+    fn delegate_claim_issuance(c: u32) -> Weight;
+    fn remove_expired_authorizations(a: u32) -> Weight;
+    fn consume_authorizations_batch(a: u32) -> Weight;
+    fn start_claim_import() -> Weight;
+    fn import_claim_batch(c: u32) -> Weight;
+    fn finalize_claim_import() -> Weight;
+    fn abort_claim_import() -> Weight;
 
     /// Add complexity cost of Permissions to `add_secondary_keys_with_authorization` extrinsic.
     fn add_secondary_keys_full_v1<AccountId>(
@@ -182,6 +225,17 @@ pub trait WeightInfo {
     fn set_secondary_key_permissions_full(perms: &Permissions) -> Weight {
         Self::permissions_cost_perms(perms).saturating_add(Self::set_secondary_key_permissions())
     }
+
+    /// Add complexity cost of Permissions to `grant_temporary_permissions` extrinsic.
+    fn grant_temporary_permissions_full(perms: &Permissions) -> Weight {
+        Self::permissions_cost_perms(perms).saturating_add(Self::grant_temporary_permissions())
+    }
+
+    /// Add complexity cost of Permissions to `set_child_secondary_key_permissions` extrinsic.
+    fn set_child_secondary_key_permissions_full(perms: &Permissions) -> Weight {
+        Self::permissions_cost_perms(perms)
+            .saturating_add(Self::set_child_secondary_key_permissions())
+    }
 }
 
 /// The module's configuration trait.
@@ -231,6 +285,39 @@ pub trait Config: CommonConfig + pallet_timestamp::Config + crate::traits::base:
     /// Only allow MultiSig primary/secondary keys to be removed from an identity
     /// if its POLYX balance is below this limit.
     type MultiSigBalanceLimit: Get<<Self::Balances as Currency<Self::AccountId>>::Balance>;
+
+    /// Time during which a primary key recovery, initiated by a CDD provider
+    /// or approved by recovery guardians, can be cancelled.
+    type KeyRecoveryChallengePeriod: Get<Self::Moment>;
+
+    /// Time an identity has to wait, after requesting it, before removing one of its call filters.
+    type CallFilterRemovalDelay: Get<Self::Moment>;
+
+    /// Scheduler of the removals of revoked claims and of the ends of temporary permission grants.
+    /// Named apart from the `Scheduler` of the pallets built on this one.
+    type IdentityScheduler: ScheduleNamed<Self::BlockNumber, Self::Proposal, Self::SchedulerOrigin>;
+
+    /// Builds the calls dispatched by `IdentityScheduler`.
+    type IdentityScheduledCalls: IdentitySchedulerCalls<Self>;
+}
+
+/// Builds the calls of the identity pallet that are dispatched by the scheduler.
+/// It is implemented by the runtime, which knows the `Call` type of the pallet.
+pub trait IdentitySchedulerCalls<T: Config> {
+    /// Returns the call removing the claim whose revocation was scheduled at `at`.
+    fn revoke_scheduled_claim(
+        target: IdentityId,
+        claim_type: ClaimType,
+        issuer: IdentityId,
+        scope: Option<Scope>,
+        at: T::Moment,
+    ) -> T::Proposal;
+
+    /// Returns the call restoring the permissions of `key` when its temporary grant ends at `revert_at`.
+    fn revert_temporary_permissions(key: T::AccountId, revert_at: T::BlockNumber) -> T::Proposal;
+
+    /// Returns the call removing `key` when its probation ends at `ends_at` without confirmation.
+    fn end_key_probation(key: T::AccountId, ends_at: T::BlockNumber) -> T::Proposal;
 }
 
 decl_event!(
@@ -238,6 +325,7 @@ decl_event!(
     where
         AccountId = <T as frame_system::Config>::AccountId,
         Moment = <T as pallet_timestamp::Config>::Moment,
+        BlockNumber = <T as frame_system::Config>::BlockNumber,
     {
         /// Identity created.
         ///
@@ -279,6 +367,11 @@ decl_event!(
         /// (DID, claim)
         ClaimRevoked(IdentityId, IdentityClaim),
 
+        /// Revocation of a claim scheduled, the claim now expiring at the revocation time.
+        ///
+        /// (DID, claim, revocation time)
+        ClaimRevocationScheduled(IdentityId, IdentityClaim, Moment),
+
         /// Asset's identity registered.
         ///
         /// (Asset DID, ticker)
@@ -337,10 +430,199 @@ decl_event!(
         /// (DID)
         SecondaryKeysUnfrozen(IdentityId),
 
+        /// The expiry of a secondary key has been set or cleared.
+        ///
+        /// (DID, secondary key, moment from which the key loses its permissions)
+        SecondaryKeyExpirySet(IdentityId, AccountId, Option<Moment>),
+
+        /// Permissions have been temporarily granted to a secondary key.
+        ///
+        /// (DID, secondary key, granted permissions, block at which the previous permissions are restored)
+        TemporaryPermissionsGranted(IdentityId, AccountId, Permissions, BlockNumber),
+
+        /// The audit trail of the calls made by secondary keys has been enabled or disabled.
+        ///
+        /// (DID, enabled)
+        KeyUsageAuditSet(IdentityId, bool),
+
+        /// A secondary key of an identity with an audit trail has been permitted to make a call.
+        /// Only kept for calls that succeed, as the event is rolled back when the call fails.
+        ///
+        /// (DID, secondary key, pallet name, dispatchable name)
+        SecondaryKeyUsed(IdentityId, AccountId, PalletName, DispatchableName),
+
+        /// The probation period of new secondary keys has been set or cleared.
+        ///
+        /// (DID, probation period in blocks)
+        KeyProbationPeriodSet(IdentityId, Option<BlockNumber>),
+
+        /// A new secondary key has been put on probation.
+        ///
+        /// (DID, secondary key, block at which the key is removed unless confirmed)
+        SecondaryKeyOnProbation(IdentityId, AccountId, BlockNumber),
+
+        /// A secondary key on probation has been confirmed.
+        ///
+        /// (DID, secondary key)
+        SecondaryKeyConfirmed(IdentityId, AccountId),
+
         /// A new CustomClaimType was added.
         ///
         /// (DID, id, Type)
         CustomClaimTypeAdded(IdentityId, CustomClaimTypeId, Vec<u8>),
+
+        /// The CDD provider allowed to recover the primary key of an identity has been set or removed.
+        ///
+        /// (DID, CDD provider DID)
+        KeyRecoveryDelegateSet(IdentityId, Option<IdentityId>),
+
+        /// A CDD provider initiated the recovery of the primary key of an identity.
+        ///
+        /// (DID, CDD provider DID, new primary key, executable at)
+        KeyRecoveryInitiated(IdentityId, IdentityId, AccountId, Moment),
+
+        /// A pending primary key recovery was cancelled.
+        ///
+        /// (DID, caller DID)
+        KeyRecoveryCancelled(IdentityId, IdentityId),
+
+        /// A primary key recovery was executed.
+        ///
+        /// (DID, CDD provider DID, new primary key)
+        KeyRecoveryExecuted(IdentityId, IdentityId, AccountId),
+
+        /// The guardians allowed to recover the primary key of an identity have been set or removed.
+        ///
+        /// (DID, guardians)
+        RecoveryGuardiansSet(IdentityId, Option<RecoveryGuardians>),
+
+        /// A guardian approved the recovery of the primary key of an identity.
+        ///
+        /// (DID, guardian DID, new primary key, number of approvals of that key)
+        SocialRecoveryApproved(IdentityId, IdentityId, AccountId, u32),
+
+        /// Enough guardians approved a primary key recovery, which is now pending its challenge period.
+        ///
+        /// (DID, new primary key, executable at)
+        SocialRecoveryInitiated(IdentityId, AccountId, Moment),
+
+        /// A pending social recovery was cancelled.
+        ///
+        /// (DID, caller DID)
+        SocialRecoveryCancelled(IdentityId, IdentityId),
+
+        /// A social recovery was executed.
+        ///
+        /// (DID, new primary key)
+        SocialRecoveryExecuted(IdentityId, AccountId),
+
+        /// The default claim issuance limit has been set.
+        ///
+        /// (claims per era, era length)
+        ClaimIssuanceLimitSet(Option<u32>, Moment),
+
+        /// The claim issuance limit of an issuer has been overridden or reset to the default.
+        ///
+        /// (issuer DID, claims per era)
+        IssuerClaimIssuanceLimitSet(IdentityId, Option<u32>),
+
+        /// An identity restricted itself from making the calls matched by a call filter.
+        ///
+        /// (DID, call filter)
+        CallFilterAdded(IdentityId, CallFilter),
+
+        /// An identity requested the removal of one of its call filters.
+        ///
+        /// (DID, call filter, removable at)
+        CallFilterRemovalRequested(IdentityId, CallFilter, Moment),
+
+        /// A call filter was removed after its removal delay.
+        ///
+        /// (DID, call filter)
+        CallFilterRemoved(IdentityId, CallFilter),
+
+        /// An identity published an agreement, or a new version of one of its agreements.
+        ///
+        /// (publisher DID, agreement id, agreement)
+        AgreementPublished(IdentityId, AgreementId, Agreement),
+
+        /// An identity consented to a version of an agreement.
+        ///
+        /// (DID, publisher DID, agreement id, consent)
+        ConsentGiven(
+            IdentityId,
+            IdentityId,
+            AgreementId,
+            AgreementConsent<Moment>,
+        ),
+
+        /// An identity revoked its consent to an agreement.
+        ///
+        /// (DID, publisher DID, agreement id)
+        ConsentRevoked(IdentityId, IdentityId, AgreementId),
+
+        /// An identity allowed another identity to link it as its child, or revoked the approval.
+        ///
+        /// (child DID, parent DID)
+        ParentIdentityApproved(IdentityId, Option<IdentityId>),
+
+        /// An identity has been linked as the child of another identity.
+        ///
+        /// (parent DID, child DID, inherits claims)
+        ChildIdentityLinked(IdentityId, IdentityId, bool),
+
+        /// A child identity has been unlinked from its parent.
+        ///
+        /// (caller DID, parent DID, child DID)
+        ChildIdentityUnlinked(IdentityId, IdentityId, IdentityId),
+
+        /// A custom claim type has been registered with the schema of its payloads.
+        ///
+        /// (DID, id, schema)
+        CustomClaimSchemaRegistered(IdentityId, CustomClaimTypeId, CustomClaimSchema),
+
+        /// An identity has delegated the issuance of claims on its behalf to another identity.
+        /// An empty list of claim types revokes the delegation.
+        ///
+        /// (delegator DID, delegate DID, claim types)
+        ClaimIssuanceDelegated(IdentityId, IdentityId, Vec<ClaimType>),
+
+        /// Expired authorizations have been removed.
+        /// `last_scanned` is the authorization to resume the removal after, if the scan stopped early.
+        ///
+        /// (caller DID, authorized_identity, authorized_key, auth_ids, last_scanned)
+        ExpiredAuthorizationsRemoved(
+            IdentityId,
+            Option<IdentityId>,
+            Option<AccountId>,
+            Vec<u64>,
+            Option<u64>,
+        ),
+
+        /// A guardian approved the cancellation of a pending social recovery.
+        ///
+        /// (DID, guardian DID, number of cancellation approvals)
+        SocialRecoveryCancellationApproved(IdentityId, IdentityId, u32),
+
+        /// An import of a claim to a list of targets has been started.
+        ///
+        /// (issuer DID, claim, total entries, expected hash)
+        ClaimImportStarted(IdentityId, Claim, u32, H256),
+
+        /// A batch of targets has been given the claim of an import.
+        ///
+        /// (issuer DID, claim, imported entries so far, total entries)
+        ClaimBatchImported(IdentityId, Claim, u32, u32),
+
+        /// An import of a claim has been completed and its integrity hash verified.
+        ///
+        /// (issuer DID, claim, total entries, final hash)
+        ClaimImportFinalized(IdentityId, Claim, u32, H256),
+
+        /// An import of a claim has been aborted.
+        ///
+        /// (issuer DID, claim, imported entries)
+        ClaimImportAborted(IdentityId, Claim, u32),
     }
 );
 
@@ -353,4 +635,7 @@ pub trait IdentityFnTrait<AccountId> {
 
     /// Provides the DID status for the given DID
     fn has_valid_cdd(target_did: IdentityId) -> bool;
+
+    /// Returns `true` if `target_did` holds a non-expired, unscoped `claim_type` claim issued by `issuer`.
+    fn has_valid_claim(target_did: IdentityId, claim_type: ClaimType, issuer: IdentityId) -> bool;
 }
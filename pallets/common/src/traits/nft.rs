@@ -9,9 +9,12 @@ use frame_support::decl_event;
 use frame_support::traits::Get;
 use frame_support::weights::Weight;
 use polymesh_primitives::asset_metadata::AssetMetadataKey;
-use polymesh_primitives::nft::{NFTCollectionId, NFTId};
+use polymesh_primitives::nft::{
+    NFTCollectionId, NFTId, NFTLeaseId, NFTMetadataKeySetId, NFTMetadataKeySpec,
+};
 use polymesh_primitives::ticker::Ticker;
-use polymesh_primitives::IdentityId;
+use polymesh_primitives::{IdentityId, PortfolioId};
+use sp_std::vec::Vec;
 
 use crate::compliance_manager::Config as ComplianceManagerConfig;
 use crate::{asset, base, identity, portfolio};
@@ -38,6 +41,31 @@ decl_event!(
         IssuedNFT(IdentityId, NFTCollectionId, NFTId),
         /// Emitted when an NFT is redeemed.
         RedeemedNFT(IdentityId, Ticker, NFTId),
+        /// Emitted when the NFTs of a collection can be linked, or no longer, to identities as credentials.
+        CredentialCollectionSet(IdentityId, Ticker, bool),
+        /// Emitted when an NFT is linked to its holder's identity as a credential.
+        NFTCredentialLinked(IdentityId, Ticker, NFTId),
+        /// Emitted when an NFT is unlinked from an identity, either by its holder or because it left the identity.
+        NFTCredentialUnlinked(IdentityId, Ticker, NFTId),
+        /// Emitted when a reusable metadata key-set is registered.
+        MetadataKeySetRegistered(IdentityId, NFTMetadataKeySetId, Vec<NFTMetadataKeySpec>),
+        /// Emitted when an nft collection is created with the metadata keys of a key-set.
+        NftCollectionKeySetUsed(IdentityId, NFTCollectionId, NFTMetadataKeySetId),
+        /// Emitted when an NFT lease is offered. Parameters: caller DID, lease id, authorization id.
+        NFTLeaseOffered(IdentityId, NFTLeaseId, u64),
+        /// Emitted when an NFT is leased. Parameters: caller DID, lease id, lessor portfolio, lessee portfolio, ticker, NFT id.
+        NFTLeased(
+            IdentityId,
+            NFTLeaseId,
+            PortfolioId,
+            PortfolioId,
+            Ticker,
+            NFTId,
+        ),
+        /// Emitted when a party of a lease agrees to end it before its end block.
+        NFTLeaseEndApproved(IdentityId, NFTLeaseId),
+        /// Emitted when a lease ends and its NFT is returned to the lessor.
+        NFTLeaseEnded(NFTLeaseId, Ticker, NFTId),
     }
 );
 
@@ -45,6 +73,15 @@ pub trait WeightInfo {
     fn create_nft_collection(n: u32) -> Weight;
     fn issue_nft(n: u32) -> Weight;
     fn redeem_nft(n: u32) -> Weight;
+    fn set_credential_collection() -> Weight;
+    fn link_nft_credential() -> Weight;
+    fn unlink_nft_credential() -> Weight;
+    fn register_metadata_key_set(n: u32) -> Weight;
+    fn create_nft_collection_from_key_set(n: u32) -> Weight;
+    fn lease_nft() -> Weight;
+    fn approve_nft_lease_end() -> Weight;
+    fn end_nft_leases(n: u32) -> Weight;
+    fn accept_nft_lease() -> Weight;
 }
 
 pub trait NFTTrait<Origin> {
@@ -46,4 +46,12 @@ pub trait CheckAccountCallPermissions<AccountId> {
         pallet_name: impl FnOnce() -> PalletName,
         function_name: impl FnOnce() -> DispatchableName,
     ) -> Option<AccountCallPermissionsData<AccountId>>;
+
+    /// Returns `true` if the identity of `who` has restricted itself from calling the extrinsic
+    /// represented by `pallet_name` and `function_name`.
+    fn is_account_call_filtered(
+        who: &AccountId,
+        pallet_name: &PalletName,
+        function_name: &DispatchableName,
+    ) -> bool;
 }
@@ -18,16 +18,44 @@
 //! The interface allows to accept portfolio custody
 
 use crate::{asset::AssetFnTrait, balances::Memo, base, identity, CommonConfig};
+use codec::{Decode, Encode};
 use frame_support::decl_event;
 use frame_support::dispatch::DispatchResult;
 use frame_support::pallet_prelude::Get;
+use frame_support::traits::schedule::Named as ScheduleNamed;
 use frame_support::weights::Weight;
 use polymesh_primitives::{
-    Balance, Fund, FundDescription, IdentityId, Memo as PortfolioMemo, NFTId, NFTs, PortfolioId,
-    PortfolioName, PortfolioNumber, SecondaryKey, Ticker,
+    impl_checked_inc, Balance, Fund, FundDescription, IdentityId, Memo as PortfolioMemo, NFTId,
+    NFTs, PortfolioBalanceLabel, PortfolioId, PortfolioName, PortfolioNumber, SecondaryKey, Ticker,
 };
+use scale_info::TypeInfo;
 use sp_std::vec::Vec;
 
+/// The ID of a portfolio sweep rule.
+#[derive(Encode, Decode, TypeInfo)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub struct SweepRuleId(pub u64);
+impl_checked_inc!(SweepRuleId);
+
+impl SweepRuleId {
+    /// Converts a sweep rule id and the block of its next run into a scheduler name.
+    pub fn schedule_name<BlockNumber: Encode>(&self, next_run: &BlockNumber) -> Vec<u8> {
+        (
+            crate::constants::schedule_name_prefix::PORTFOLIO_SWEEP_RULE,
+            self.0,
+            next_run,
+        )
+            .encode()
+    }
+}
+
+/// Builds the calls of the portfolio pallet that are dispatched by the scheduler.
+/// It is implemented by the runtime, which knows the `Call` type of the pallet.
+pub trait PortfolioSchedulerCalls<Call> {
+    /// Returns the call executing the sweep rule `id`.
+    fn execute_sweep_rule(id: SweepRuleId) -> Call;
+}
+
 /// This trait is used to accept custody of a portfolio
 pub trait PortfolioSubTrait<AccountId> {
     /// Checks that the custodian is authorized for the portfolio
@@ -82,10 +110,37 @@ pub trait PortfolioSubTrait<AccountId> {
     /// * `ticker` - the ticker of the NFT.
     /// * `nft_id` - the id of the nft to be unlocked.
     fn unlock_nft(portfolio_id: &PortfolioId, ticker: &Ticker, nft_id: &NFTId) -> DispatchResult;
+
+    /// Accepts the portfolio custody authorization `auth_id` on behalf of `to`.
+    ///
+    /// # Arguments
+    /// * `to` - Identity of the new custodian.
+    /// * `auth_id` - Id of the portfolio custody authorization.
+    fn accept_portfolio_custody(to: IdentityId, auth_id: u64) -> DispatchResult;
+}
+
+/// Handles the custody changes of portfolios.
+pub trait PortfolioCustodyHandler {
+    /// Called once the custody of `portfolio` has been given to `custodian`. Returns the consumed weight.
+    fn on_custody_changed(portfolio: &PortfolioId, custodian: IdentityId) -> Weight;
+
+    /// Returns the maximum weight consumed by `on_custody_changed`.
+    fn on_custody_changed_max_weight() -> Weight;
+}
+
+impl PortfolioCustodyHandler for () {
+    fn on_custody_changed(_: &PortfolioId, _: IdentityId) -> Weight {
+        Weight::zero()
+    }
+
+    fn on_custody_changed_max_weight() -> Weight {
+        Weight::zero()
+    }
 }
 
 pub trait WeightInfo {
     fn create_portfolio() -> Weight;
+    fn create_portfolio_with_salt() -> Weight;
     fn delete_portfolio() -> Weight;
     fn move_portfolio_funds(i: u32) -> Weight;
     fn rename_portfolio(i: u32) -> Weight;
@@ -94,8 +149,17 @@ pub trait WeightInfo {
     fn move_portfolio_v2(funds: &[Fund]) -> Weight {
         let (f, n) = count_token_moves(funds);
         Self::move_portfolio_funds_v2(f, n)
+            .saturating_add(Self::fund_memos(count_memo_bytes(funds)))
     }
     fn move_portfolio_funds_v2(f: u32, u: u32) -> Weight;
+    fn fund_memos(m: u32) -> Weight;
+    fn add_sweep_rule() -> Weight;
+    fn remove_sweep_rule() -> Weight;
+    fn execute_sweep_rule() -> Weight;
+    fn move_labelled_balance() -> Weight;
+    fn set_settlement_lock_label() -> Weight;
+    fn add_portfolio_observer() -> Weight;
+    fn remove_portfolio_observer() -> Weight;
 }
 
 pub trait Config: CommonConfig + identity::Config + base::Config {
@@ -107,6 +171,18 @@ pub trait Config: CommonConfig + identity::Config + base::Config {
     type MaxNumberOfFungibleMoves: Get<u32>;
     /// Maximum number of NFTs that can be moved in a single transfer call.
     type MaxNumberOfNFTsMoves: Get<u32>;
+    /// Handles the custody changes of portfolios, e.g. to withdraw the affirmations made by
+    /// their previous custodian.
+    type CustodyHandler: PortfolioCustodyHandler;
+    /// Scheduler of sweep rules.
+    /// Named apart from the `Scheduler` of the pallets built on this one.
+    type PortfolioScheduler: ScheduleNamed<
+        Self::BlockNumber,
+        <Self as identity::Config>::Proposal,
+        <Self as identity::Config>::SchedulerOrigin,
+    >;
+    /// Builds the calls dispatched by `PortfolioScheduler`.
+    type PortfolioScheduledCalls: PortfolioSchedulerCalls<<Self as identity::Config>::Proposal>;
 }
 
 decl_event! {
@@ -190,6 +266,82 @@ decl_event! {
             Balance,
             Option<PortfolioMemo>,
         ),
+        /// A sweep rule has been added by the custodian of the source portfolio.
+        ///
+        /// # Parameters
+        /// * custodian DID
+        /// * sweep rule id
+        /// * source portfolio
+        /// * destination portfolio
+        /// * asset ticker
+        /// * balance kept in the source portfolio
+        SweepRuleAdded(
+            IdentityId,
+            SweepRuleId,
+            PortfolioId,
+            PortfolioId,
+            Ticker,
+            Balance,
+        ),
+        /// A sweep rule has been removed, either by its custodian or because
+        /// the custodian lost custody of the source portfolio.
+        ///
+        /// # Parameters
+        /// * custodian DID
+        /// * sweep rule id
+        SweepRuleRemoved(IdentityId, SweepRuleId),
+        /// A sweep rule has been executed.
+        ///
+        /// # Parameters
+        /// * custodian DID
+        /// * sweep rule id
+        /// * asset balance that was moved
+        SweepRuleExecuted(IdentityId, SweepRuleId, Balance),
+        /// An asset balance has been moved between two labels of a portfolio.
+        /// `None` stands for the unlabelled free balance.
+        ///
+        /// # Parameters
+        /// * custodian DID
+        /// * portfolio id
+        /// * asset ticker
+        /// * source label
+        /// * destination label
+        /// * asset balance that was moved
+        LabelledBalanceMoved(
+            IdentityId,
+            PortfolioId,
+            Ticker,
+            Option<PortfolioBalanceLabel>,
+            Option<PortfolioBalanceLabel>,
+            Balance,
+        ),
+        /// The label that settlement locks take from has been set or cleared.
+        ///
+        /// # Parameters
+        /// * custodian DID
+        /// * portfolio id
+        /// * asset ticker
+        /// * the new label, `None` meaning the unlabelled free balance
+        SettlementLockLabelSet(
+            IdentityId,
+            PortfolioId,
+            Ticker,
+            Option<PortfolioBalanceLabel>,
+        ),
+        /// An identity has been designated as an observer of a portfolio.
+        ///
+        /// # Parameters
+        /// * owner DID
+        /// * portfolio id
+        /// * observer DID
+        PortfolioObserverAdded(IdentityId, PortfolioId, IdentityId),
+        /// An observer of a portfolio has been removed.
+        ///
+        /// # Parameters
+        /// * owner DID
+        /// * portfolio id
+        /// * observer DID
+        PortfolioObserverRemoved(IdentityId, PortfolioId, IdentityId),
     }
 }
 
@@ -208,3 +360,10 @@ fn count_token_moves(funds: &[Fund]) -> (u32, u32) {
     }
     (fungible_moves, nfts_moves as u32)
 }
+
+fn count_memo_bytes(funds: &[Fund]) -> u32 {
+    funds
+        .iter()
+        .filter_map(|fund| fund.memo.as_ref())
+        .fold(0usize, |bytes, memo| bytes.saturating_add(memo.len())) as u32
+}
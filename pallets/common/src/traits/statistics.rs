@@ -7,6 +7,7 @@ use polymesh_primitives::{
     transfer_compliance::{TransferCondition, TransferConditionExemptKey},
     IdentityId, ScopeId,
 };
+use sp_core::H256;
 use sp_std::vec::Vec;
 
 /// The main trait for statistics module
@@ -21,6 +22,10 @@ pub trait Config:
     type MaxStatsPerAsset: Get<u32>;
     /// Maximum transfer conditions that can be enabled for an Asset.
     type MaxTransferConditionsPerAsset: Get<u32>;
+    /// Maximum number of exempt entities that can be imported in a single batch.
+    type MaxExemptionsPerBatch: Get<u32>;
+    /// Number of blocks in an era of transfer volume stats.
+    type TransferVolumeEraLength: Get<Self::BlockNumber>;
     /// Weights for extrinsics.
     type WeightInfo: WeightInfo;
 }
@@ -31,6 +36,11 @@ pub trait WeightInfo {
     fn batch_update_asset_stats(i: u32) -> Weight;
     fn set_asset_transfer_compliance(i: u32) -> Weight;
     fn set_entities_exempt(i: u32) -> Weight;
+    fn start_exemption_import() -> Weight;
+    fn import_exemption_batch(i: u32) -> Weight;
+    fn finalize_exemption_import() -> Weight;
+    fn abort_exemption_import() -> Weight;
+    fn set_transfer_volume_tracking() -> Weight;
 }
 
 decl_event!(
@@ -59,5 +69,25 @@ decl_event!(
         ///
         /// (Caller DID, Exempt key, Entities)
         TransferConditionExemptionsRemoved(IdentityId, TransferConditionExemptKey, Vec<ScopeId>),
+        /// An import of exempt entities has been started.
+        ///
+        /// (Caller DID, Exempt key, Total entries, Expected hash)
+        ExemptionImportStarted(IdentityId, TransferConditionExemptKey, u32, H256),
+        /// A batch of exempt entities has been imported.
+        ///
+        /// (Caller DID, Exempt key, Imported entries so far, Total entries)
+        ExemptionBatchImported(IdentityId, TransferConditionExemptKey, u32, u32),
+        /// An import of exempt entities has been completed and its integrity hash verified.
+        ///
+        /// (Caller DID, Exempt key, Total entries, Final hash)
+        ExemptionImportFinalized(IdentityId, TransferConditionExemptKey, u32, H256),
+        /// An import of exempt entities has been aborted.
+        ///
+        /// (Caller DID, Exempt key, Imported entries)
+        ExemptionImportAborted(IdentityId, TransferConditionExemptKey, u32),
+        /// Transfer volume tracking enabled/disabled for an asset.
+        ///
+        /// (Caller DID, Asset, Enabled)
+        TransferVolumeTrackingSet(IdentityId, AssetScope, bool),
     }
 );
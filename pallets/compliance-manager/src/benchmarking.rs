@@ -16,6 +16,7 @@
 use crate::*;
 
 use frame_benchmarking::benchmarks;
+use frame_system::RawOrigin;
 use pallet_asset::SecurityToken;
 use polymesh_common_utilities::{
     benchs::{AccountIdOf, User, UserBuilder},
@@ -31,6 +32,7 @@ const MAX_RECEIVER_CONDITIONS_PER_COMPLIANCE: u32 = 3;
 const MAX_CONDITIONS_PER_COMPLIANCE: u32 =
     MAX_SENDER_CONDITIONS_PER_COMPLIANCE + MAX_RECEIVER_CONDITIONS_PER_COMPLIANCE;
 const MAX_COMPLIANCE_REQUIREMENTS: u32 = 2;
+const MAX_DISABLED_REQUIREMENTS: u32 = 50;
 
 const MAX_CONDITIONS: u32 = 10;
 const MAX_CONDITION_TYPE_CLAIMS: u32 = 10;
@@ -50,6 +52,7 @@ const CLAIM_TYPES: &[ClaimType] = &[
     ClaimType::InvestorUniqueness,
     ClaimType::NoType,
     ClaimType::InvestorUniquenessV2,
+    ClaimType::RiskScore,
 ];
 
 /// Create a token issuer trusted for `Any`.
@@ -128,7 +131,7 @@ pub fn make_token<T: Config>(owner: &User<T>, name: Vec<u8>) -> Ticker {
     };
     let ticker = Ticker::from_slice_truncated(&*name);
 
-    T::Asset::create_asset(
+    <T as Config>::Asset::create_asset(
         owner.origin.clone().into(),
         name.into(),
         ticker,
@@ -140,7 +143,7 @@ pub fn make_token<T: Config>(owner: &User<T>, name: Vec<u8>) -> Ticker {
     )
     .expect("Cannot create an asset");
 
-    T::Asset::issue(
+    <T as Config>::Asset::issue(
         owner.origin.clone().into(),
         ticker,
         u128::try_from(token.total_supply).unwrap().into(),
@@ -223,6 +226,49 @@ impl<T: Config> ComplianceRequirementBuilder<T> {
     }
 }
 
+/// Creates a template of `c` compliance requirements with the maximum number of conditions.
+fn make_template<T: Config + TestUtilsFn<AccountIdOf<T>>>(c: u32) -> Vec<ComplianceRequirement> {
+    let issuers = make_issuers::<T>(MAX_TRUSTED_ISSUER_PER_CONDITION, None);
+    (1..=c)
+        .map(|id| ComplianceRequirement {
+            sender_conditions: make_conditions(
+                MAX_SENDER_CONDITIONS_PER_COMPLIANCE,
+                None,
+                &issuers,
+            ),
+            receiver_conditions: make_conditions(
+                MAX_RECEIVER_CONDITIONS_PER_COMPLIANCE,
+                None,
+                &issuers,
+            ),
+            id,
+        })
+        .collect()
+}
+
+/// Creates an asset with `r` compliance requirements without conditions.
+fn disabled_requirements_setup<T: Config + TestUtilsFn<AccountIdOf<T>>>(
+    r: u32,
+) -> (User<T>, Ticker, Vec<u32>) {
+    let owner = UserBuilder::<T>::default().generate_did().build("OWNER");
+    let ticker = make_token::<T>(&owner, b"1".to_vec());
+    let requirements = (1..=r)
+        .map(|id| ComplianceRequirement {
+            sender_conditions: vec![],
+            receiver_conditions: vec![],
+            id,
+        })
+        .collect();
+    AssetCompliances::insert(
+        ticker,
+        AssetCompliance {
+            paused: false,
+            requirements,
+        },
+    );
+    (owner, ticker, (1..=r).collect())
+}
+
 fn setup_conditions_bench<T: Config + TestUtilsFn<AccountIdOf<T>>>(
     conditions: u32,
     claims: u32,
@@ -270,6 +316,83 @@ benchmarks! {
         assert_eq!( req.receiver_conditions, d.receiver_conditions, "Sender conditions not expected");
     }
 
+    schedule_compliance_requirement_change {
+        let c in 1..MAX_CONDITIONS_PER_COMPLIANCE;
+
+        // Add maximum size compliance requirements.
+        let d = ComplianceRequirementBuilder::<T>::new(
+            MAX_TRUSTED_ISSUER_PER_CONDITION,
+            MAX_CONDITIONS_PER_COMPLIANCE)
+            .add_compliance_requirement().build();
+
+        // Fill the pending changes of the asset, but one.
+        let did = d.owner.did();
+        let effective_from = frame_system::Pallet::<T>::block_number() + 10u32.into();
+        let pending = (0..MAX_SCHEDULED_REQUIREMENT_CHANGES as u32 - 1)
+            .map(|id| (id + 100, (effective_from, did, ComplianceRequirementChange::Remove(id + 100))))
+            .collect::<BTreeMap<_, _>>();
+        ScheduledRequirementChanges::<T>::insert(d.ticker, pending);
+
+        let (sender_count, receiver_count) = split_conditions(c);
+        let issuers = make_issuers::<T>(MAX_TRUSTED_ISSUER_PER_CONDITION, None);
+        let change = ComplianceRequirementChange::Add(ComplianceRequirement {
+            sender_conditions: make_conditions(sender_count, None, &issuers),
+            receiver_conditions: make_conditions(receiver_count, None, &issuers),
+            id: 0,
+        });
+    }: _(d.owner.origin, d.ticker, change, effective_from)
+    verify {
+        assert_eq!(
+            Module::<T>::scheduled_requirement_changes(d.ticker).len(),
+            MAX_SCHEDULED_REQUIREMENT_CHANGES
+        );
+    }
+
+    cancel_compliance_requirement_change {
+        let d = ComplianceRequirementBuilder::<T>::new(
+            MAX_TRUSTED_ISSUER_PER_CONDITION,
+            MAX_CONDITIONS_PER_COMPLIANCE)
+            .add_compliance_requirement().build();
+        let id = Module::<T>::get_latest_requirement_id(d.ticker);
+        let effective_from = frame_system::Pallet::<T>::block_number() + 10u32.into();
+        Module::<T>::schedule_compliance_requirement_change(
+            d.owner.origin.clone().into(),
+            d.ticker,
+            ComplianceRequirementChange::Remove(id),
+            effective_from,
+        ).unwrap();
+    }: _(d.owner.origin, d.ticker, id)
+    verify {
+        assert!(Module::<T>::scheduled_requirement_changes(d.ticker).is_empty());
+    }
+
+    apply_compliance_requirement_change {
+        // Add maximum size compliance requirements, with default trusted issuers.
+        let d = ComplianceRequirementBuilder::<T>::new(
+            MAX_TRUSTED_ISSUER_PER_CONDITION,
+            MAX_CONDITIONS_PER_COMPLIANCE)
+            .add_compliance_requirement().build();
+        d.add_default_trusted_claim_issuer(MAX_DEFAULT_TRUSTED_CLAIM_ISSUERS);
+
+        // Schedule the largest requirement to be added.
+        let effective_from = frame_system::Pallet::<T>::block_number() + 10u32.into();
+        let new_req = ComplianceRequirement {
+            sender_conditions: d.sender_conditions.clone(),
+            receiver_conditions: d.receiver_conditions.clone(),
+            id: 0,
+        };
+        Module::<T>::schedule_compliance_requirement_change(
+            d.owner.origin.clone().into(),
+            d.ticker,
+            ComplianceRequirementChange::Add(new_req),
+            effective_from,
+        ).unwrap();
+        let id = Module::<T>::next_requirement_id(d.ticker) - 1;
+    }: _(RawOrigin::Root, d.ticker, id)
+    verify {
+        assert_eq!(Module::<T>::asset_compliance(d.ticker).requirements.len(), 2);
+    }
+
     remove_compliance_requirement {
         // Add the compliance requirement.
         let d = ComplianceRequirementBuilder::<T>::new(
@@ -426,4 +549,110 @@ benchmarks! {
             Module::<T>::asset_compliance(d.ticker).requirements.is_empty(),
             "Compliance Requeriment was not reset");
     }
+
+    set_default_requirements_template {
+        let c in 0..MAX_COMPLIANCE_REQUIREMENTS;
+
+        let issuers = make_issuers::<T>(MAX_TRUSTED_ISSUER_PER_CONDITION, None);
+        let template = (1..=c).map(|id| {
+            ComplianceRequirement {
+                sender_conditions: make_conditions(MAX_SENDER_CONDITIONS_PER_COMPLIANCE, None, &issuers),
+                receiver_conditions: make_conditions(MAX_RECEIVER_CONDITIONS_PER_COMPLIANCE, None, &issuers),
+                id,
+            }}).collect::<Vec<_>>();
+    }: _(RawOrigin::Root, AssetType::default(), template.clone())
+    verify {
+        assert_eq!(Module::<T>::default_requirements_template(AssetType::default()), template);
+    }
+
+    disable_compliance_requirements {
+        let r in 1..MAX_DISABLED_REQUIREMENTS;
+
+        let (owner, ticker, ids) = disabled_requirements_setup::<T>(r);
+        let reenable_at = frame_system::Pallet::<T>::block_number() + 10u32.into();
+    }: _(owner.origin, ticker, ids, Some(reenable_at))
+    verify {
+        assert_eq!(Module::<T>::disabled_requirements(ticker).len(), r as usize);
+    }
+
+    enable_compliance_requirements {
+        let r in 1..MAX_DISABLED_REQUIREMENTS;
+
+        let (owner, ticker, ids) = disabled_requirements_setup::<T>(r);
+        Module::<T>::disable_compliance_requirements(owner.origin.clone().into(), ticker, ids.clone(), None).unwrap();
+    }: _(owner.origin, ticker, ids)
+    verify {
+        assert!(Module::<T>::disabled_requirements(ticker).is_empty());
+    }
+
+    reenable_compliance_requirements {
+        let r in 1..MAX_SCHEDULED_REENABLES_PER_BLOCK;
+
+        let (owner, ticker, ids) = disabled_requirements_setup::<T>(r);
+        let reenable_at = frame_system::Pallet::<T>::block_number() + 10u32.into();
+        Module::<T>::disable_compliance_requirements(owner.origin.clone().into(), ticker, ids, Some(reenable_at)).unwrap();
+        let scheduled = ScheduledReenables::<T>::take(reenable_at);
+    }: {
+        Module::<T>::reenable_requirements(reenable_at, scheduled);
+    }
+    verify {
+        assert!(Module::<T>::disabled_requirements(ticker).is_empty());
+    }
+
+    set_issuer_requirements_template {
+        let c in 0..MAX_COMPLIANCE_REQUIREMENTS;
+
+        let owner = UserBuilder::<T>::default().generate_did().build("OWNER");
+        let issuers = make_issuers::<T>(MAX_TRUSTED_ISSUER_PER_CONDITION, None);
+        let template = (1..=c).map(|id| {
+            ComplianceRequirement {
+                sender_conditions: make_conditions(MAX_SENDER_CONDITIONS_PER_COMPLIANCE, None, &issuers),
+                receiver_conditions: make_conditions(MAX_RECEIVER_CONDITIONS_PER_COMPLIANCE, None, &issuers),
+                id,
+            }}).collect::<Vec<_>>();
+    }: _(owner.origin, AssetType::default(), template.clone())
+    verify {
+        let did = owner.did.unwrap();
+        assert_eq!(Module::<T>::issuer_requirements_template(did, AssetType::default()), template);
+    }
+
+    register_compliance_template {
+        let c in 0..MAX_COMPLIANCE_REQUIREMENTS;
+
+        let owner = UserBuilder::<T>::default().generate_did().build("OWNER");
+        let template = make_template::<T>(c);
+    }: _(owner.origin, ComplianceTemplateName::from("TEMPLATE"), template.clone())
+    verify {
+        let id = ComplianceTemplateId(1);
+        assert_eq!(Module::<T>::compliance_template(id).unwrap().requirements, template);
+    }
+
+    remove_compliance_template {
+        let owner = UserBuilder::<T>::default().generate_did().build("OWNER");
+        let id = ComplianceTemplateId(1);
+        Module::<T>::register_compliance_template(
+            owner.origin.clone().into(),
+            ComplianceTemplateName::from("TEMPLATE"),
+            make_template::<T>(MAX_COMPLIANCE_REQUIREMENTS),
+        ).unwrap();
+    }: _(owner.origin, id)
+    verify {
+        assert!(Module::<T>::compliance_template(id).is_none());
+    }
+
+    apply_compliance_template {
+        let d = ComplianceRequirementBuilder::<T>::new(MAX_TRUSTED_ISSUER_PER_CONDITION, MAX_CONDITIONS_PER_COMPLIANCE)
+            .add_compliance_requirement().build();
+        d.add_default_trusted_claim_issuer(MAX_DEFAULT_TRUSTED_CLAIM_ISSUERS);
+        let template = make_template::<T>(MAX_COMPLIANCE_REQUIREMENTS);
+        let id = ComplianceTemplateId(1);
+        Module::<T>::register_compliance_template(
+            RawOrigin::Root.into(),
+            ComplianceTemplateName::from("TEMPLATE"),
+            template.clone(),
+        ).unwrap();
+    }: _(d.owner.origin, d.ticker, id)
+    verify {
+        assert_eq!(Module::<T>::asset_compliance(d.ticker).requirements, template);
+    }
 }
@@ -64,6 +64,23 @@
 //!  the default claim issuer.
 //! - [change_compliance_requirement](Module::change_compliance_requirement) - Updates a compliance requirement, based on its id.
 //! based on its id for a given asset.
+//! - [set_default_requirements_template](Module::set_default_requirements_template) - Sets the compliance
+//!  requirements attached to every new asset of a given type.
+//! - [set_issuer_requirements_template](Module::set_issuer_requirements_template) - Sets the compliance
+//!  requirements attached to the caller's new assets of a given type, overriding the default template.
+//! - [disable_compliance_requirements](Module::disable_compliance_requirements) - Stops evaluating some
+//!  compliance requirements of an asset, optionally until a given block.
+//! - [enable_compliance_requirements](Module::enable_compliance_requirements) - Evaluates disabled
+//!  compliance requirements again.
+//! - [schedule_compliance_requirement_change](Module::schedule_compliance_requirement_change) - Adds,
+//!  replaces or removes a compliance requirement of an asset in a given block.
+//! - [cancel_compliance_requirement_change](Module::cancel_compliance_requirement_change) - Cancels a
+//!  scheduled change to a compliance requirement.
+//! - [register_compliance_template](Module::register_compliance_template) - Registers a named set of
+//!  compliance requirements, either chain-level or private to the caller.
+//! - [remove_compliance_template](Module::remove_compliance_template) - Removes a compliance template.
+//! - [apply_compliance_template](Module::apply_compliance_template) - Replaces an asset's compliance
+//!  requirements with those of a template.
 //!
 //! ### Public Functions
 //!
@@ -81,10 +98,14 @@ use frame_support::{
     decl_error, decl_event, decl_module, decl_storage,
     dispatch::{DispatchError, DispatchResult},
     ensure,
-    traits::Get,
+    traits::{
+        schedule::{DispatchTime, Named as ScheduleNamed, LOWEST_PRIORITY},
+        Get,
+    },
     weights::Weight,
 };
-use pallet_base::ensure_length_ok;
+use frame_system::{ensure_root, RawOrigin};
+use pallet_base::{ensure_length_ok, try_next_pre, try_use_entry};
 use pallet_external_agents::Config as EAConfig;
 pub use polymesh_common_utilities::traits::compliance_manager::WeightInfo;
 use polymesh_common_utilities::{
@@ -94,22 +115,59 @@ use polymesh_common_utilities::{
     constants::*,
     identity::Config as IdentityConfig,
     protocol_fee::{ChargeProtocolFee, ProtocolOp},
+    statistics::Config as StatisticsConfig,
+    GC_DID,
 };
 use polymesh_primitives::{
+    asset::AssetType,
     compliance_manager::{
-        AssetCompliance, AssetComplianceResult, ComplianceRequirement, ConditionResult,
+        AssetCompliance, AssetComplianceReport, AssetComplianceResult, ComplianceRequirement,
+        ComplianceRequirementChange, ComplianceRequirementReport, ComplianceSummary,
+        ComplianceTemplate, ComplianceTemplateId, ComplianceTemplateName, ConditionReport,
+        ConditionResult, RequirementsImpact,
     },
-    proposition, storage_migration_ver, Balance, Claim, Condition, ConditionType, Context,
-    IdentityId, Ticker, TrustedFor, TrustedIssuer,
+    proposition,
+    statistics::AssetScope,
+    storage_migration_ver, Balance, Claim, ClaimType, Condition, ConditionType, Context,
+    IdentityId, Moment, Scope, Ticker, TrustedFor, TrustedIssuer,
+};
+use sp_runtime::{
+    traits::{One, Saturating},
+    SaturatedConversion,
 };
-use sp_std::{convert::From, prelude::*};
+use sp_std::{
+    collections::{btree_map::BTreeMap, btree_set::BTreeSet},
+    convert::From,
+    prelude::*,
+};
+
+/// Number of milliseconds in a day, the unit of `ConditionType::IsPresentWithin`.
+const MILLISECONDS_PER_DAY: u64 = 24 * 60 * 60 * 1000;
+
+/// Maximum number of holders evaluated by a single `simulate_requirements` call.
+pub const MAX_SIMULATED_HOLDERS: u32 = 500;
+
+/// Maximum number of pending scheduled changes to the compliance requirements of an asset.
+pub const MAX_SCHEDULED_REQUIREMENT_CHANGES: usize = 16;
+
+/// Maximum number of disabled requirements enabled again in a block.
+pub const MAX_SCHEDULED_REENABLES_PER_BLOCK: u32 = 50;
+
+/// Maximum number of blocks, starting at the requested one, searched for room for scheduled re-enables.
+pub const MAX_SCHEDULED_REENABLE_BLOCKS: u32 = 16;
 
 type ExternalAgents<T> = pallet_external_agents::Module<T>;
 type Identity<T> = pallet_identity::Module<T>;
+type Statistics<T> = pallet_statistics::Module<T>;
 
 /// The module's configuration trait.
 pub trait Config:
-    pallet_timestamp::Config + frame_system::Config + BalancesConfig + IdentityConfig + EAConfig
+    pallet_timestamp::Config
+    + frame_system::Config
+    + BalancesConfig
+    + IdentityConfig
+    + EAConfig
+    + StatisticsConfig
 {
     /// The overarching event type.
     type RuntimeEvent: From<Event> + Into<<Self as frame_system::Config>::RuntimeEvent>;
@@ -122,6 +180,16 @@ pub trait Config:
 
     /// The maximum claim reads that are allowed to happen in worst case of a condition resolution
     type MaxConditionComplexity: Get<u32>;
+
+    /// Scheduler of compliance requirement changes.
+    type ComplianceScheduler: ScheduleNamed<
+        Self::BlockNumber,
+        Self::ComplianceSchedulerCall,
+        Self::SchedulerOrigin,
+    >;
+
+    /// A call type for compliance requirement changes scheduled by this module.
+    type ComplianceSchedulerCall: From<Call<Self>> + Into<<Self as IdentityConfig>::Proposal>;
 }
 
 pub mod weight_for {
@@ -144,6 +212,37 @@ decl_storage! {
         pub AssetCompliances get(fn asset_compliance): map hasher(blake2_128_concat) Ticker => AssetCompliance;
         /// List of trusted claim issuer Ticker -> Issuer Identity
         pub TrustedClaimIssuer get(fn trusted_claim_issuer): map hasher(blake2_128_concat) Ticker => Vec<TrustedIssuer>;
+        /// Compliance requirements attached to every new asset of a given type, set by governance.
+        /// (AssetType -> template)
+        pub DefaultRequirementsTemplates get(fn default_requirements_template):
+            map hasher(blake2_128_concat) AssetType => Vec<ComplianceRequirement>;
+        /// Compliance requirements attached to an issuer's new assets of a given type.
+        /// Takes precedence over `DefaultRequirementsTemplates`.
+        /// (issuer DID, AssetType) -> template
+        pub IssuerRequirementsTemplates get(fn issuer_requirements_template):
+            double_map hasher(identity) IdentityId, hasher(blake2_128_concat) AssetType => Vec<ComplianceRequirement>;
+        /// Compliance requirements of an asset that are not evaluated, with the block
+        /// in which they are automatically enabled again, if any.
+        /// (Ticker -> requirement id -> re-enable block)
+        pub DisabledRequirements get(fn disabled_requirements):
+            map hasher(blake2_128_concat) Ticker => BTreeMap<u32, Option<T::BlockNumber>>;
+        /// Disabled compliance requirements to enable again in a given block,
+        /// at most `MAX_SCHEDULED_REENABLES_PER_BLOCK`.
+        /// (block -> [(Ticker, requirement id)])
+        pub ScheduledReenables get(fn scheduled_reenables):
+            map hasher(twox_64_concat) T::BlockNumber => Vec<(Ticker, u32)>;
+        /// Pending changes to the compliance requirements of an asset, with the block in which
+        /// they are applied and the identity that scheduled them.
+        /// (Ticker -> requirement id -> (block, caller DID, change))
+        pub ScheduledRequirementChanges get(fn scheduled_requirement_changes):
+            map hasher(blake2_128_concat) Ticker
+                => BTreeMap<u32, (T::BlockNumber, IdentityId, ComplianceRequirementChange)>;
+        /// Number of compliance templates ever registered, used to assign template ids.
+        pub ComplianceTemplateCounter get(fn compliance_template_counter): ComplianceTemplateId;
+        /// Registered compliance templates.
+        /// (template id -> template)
+        pub ComplianceTemplates get(fn compliance_template):
+            map hasher(twox_64_concat) ComplianceTemplateId => Option<ComplianceTemplate>;
         /// Storage version.
         StorageVersion get(fn storage_version) build(|_| Version::new(0)): Version;
     }
@@ -163,6 +262,25 @@ decl_error! {
         DuplicateComplianceRequirements,
         /// The worst case scenario of the compliance requirement is too complex
         ComplianceRequirementTooComplex,
+        /// The compliance requirement is not disabled.
+        ComplianceRequirementNotDisabled,
+        /// The block in which disabled requirements are enabled again must be in the future.
+        InvalidReenableBlock,
+        /// The compliance template doesn't exist.
+        ComplianceTemplateNotFound,
+        /// The block in which a scheduled requirement change is applied must be in the future.
+        InvalidEffectiveBlock,
+        /// The asset already has `MAX_SCHEDULED_REQUIREMENT_CHANGES` scheduled requirement changes.
+        TooManyScheduledRequirements,
+        /// The scheduler rejected the requirement change.
+        FailedToScheduleRequirementChange,
+        /// A change to the compliance requirement is already scheduled.
+        RequirementChangeAlreadyScheduled,
+        /// No change to the compliance requirement is scheduled.
+        NoScheduledRequirementChange,
+        /// None of the `MAX_SCHEDULED_REENABLE_BLOCKS` blocks starting at the requested one
+        /// has room for the re-enabled requirements.
+        ReenableBlocksFull,
     }
 }
 
@@ -175,6 +293,13 @@ decl_module! {
 
         const MaxConditionComplexity: u32 = T::MaxConditionComplexity::get();
 
+        fn on_initialize(now: T::BlockNumber) -> Weight {
+            let scheduled = ScheduledReenables::<T>::take(now);
+            let weight = <T as Config>::WeightInfo::reenable_compliance_requirements(scheduled.len() as u32);
+            Self::reenable_requirements(now, scheduled);
+            weight
+        }
+
         /// Adds a compliance requirement to an asset's compliance by ticker.
         /// If there are duplicate ClaimTypes for a particular trusted issuer, duplicates are removed.
         ///
@@ -189,28 +314,13 @@ decl_module! {
         #[weight = <T as Config>::WeightInfo::add_compliance_requirement_full(&sender_conditions, &receiver_conditions)]
         pub fn add_compliance_requirement(origin, ticker: Ticker, sender_conditions: Vec<Condition>, receiver_conditions: Vec<Condition>) {
             let did = <ExternalAgents<T>>::ensure_perms(origin, ticker)?;
-
-            // Ensure `Scope::Custom(..)`s are limited.
-            Self::ensure_custom_scopes_limited(sender_conditions.iter())?;
-            Self::ensure_custom_scopes_limited(receiver_conditions.iter())?;
-
-            // Bundle as a requirement.
-            let id = Self::get_latest_requirement_id(ticker) + 1u32;
-            let mut new_req = ComplianceRequirement { sender_conditions, receiver_conditions, id };
-
-            // Dedup `ClaimType`s and ensure issuers are limited in length.
-            Self::dedup_and_ensure_requirement_limited(&mut new_req)?;
-
-            // Add to existing requirements, and place a limit on the total complexity.
-            let mut asset_compliance = AssetCompliances::get(ticker);
-            let reqs = &mut asset_compliance.requirements;
-            reqs.push(new_req.clone());
-            Self::verify_compliance_complexity(&reqs, ticker, 0)?;
+            let (asset_compliance, new_req) = Self::base_add_requirement(ticker, sender_conditions, receiver_conditions)?;
 
             // Last storage change, now we can charge the fee.
             T::ProtocolFee::charge_fee(ProtocolOp::ComplianceManagerAddComplianceRequirement)?;
 
             // Commit new compliance to storage & emit event.
+            Self::update_investor_volume_tracking(&ticker, &asset_compliance.requirements);
             AssetCompliances::insert(&ticker, asset_compliance);
             Self::deposit_event(Event::ComplianceRequirementCreated(did, ticker, new_req));
         }
@@ -232,8 +342,10 @@ decl_module! {
                 let before = requirements.len();
                 requirements.retain(|requirement| requirement.id != id);
                 ensure!(before != requirements.len(), Error::<T>::InvalidComplianceRequirementId);
+                Self::update_investor_volume_tracking(&ticker, requirements);
                 Ok(()) as DispatchResult
             })?;
+            DisabledRequirements::<T>::mutate(ticker, |disabled| disabled.remove(&id));
 
             Self::deposit_event(Event::ComplianceRequirementRemoved(did, ticker, id));
         }
@@ -274,7 +386,11 @@ decl_module! {
             Self::verify_compliance_complexity(&asset_compliance, ticker, 0)?;
 
             // Commit changes to storage + emit event.
+            Self::update_investor_volume_tracking(&ticker, &asset_compliance);
             AssetCompliances::mutate(&ticker, |old| old.requirements = asset_compliance.clone());
+            DisabledRequirements::<T>::mutate(ticker, |disabled| {
+                disabled.retain(|id, _| asset_compliance.binary_search_by_key(id, |r| r.id).is_ok())
+            });
             Self::deposit_event(Event::AssetComplianceReplaced(did, ticker, asset_compliance));
         }
 
@@ -290,6 +406,8 @@ decl_module! {
         pub fn reset_asset_compliance(origin, ticker: Ticker) {
             let did = <ExternalAgents<T>>::ensure_perms(origin, ticker)?;
             AssetCompliances::remove(ticker);
+            DisabledRequirements::<T>::remove(ticker);
+            Self::update_investor_volume_tracking(&ticker, &[]);
             Self::deposit_event(Event::AssetComplianceReset(did, ticker));
         }
 
@@ -411,9 +529,314 @@ decl_module! {
             Self::verify_compliance_complexity(&reqs, ticker, 0)?;
 
             // Store updated asset compliance.
+            Self::update_investor_volume_tracking(&ticker, &asset_compliance.requirements);
             AssetCompliances::insert(&ticker, asset_compliance);
             Self::deposit_event(Event::ComplianceRequirementChanged(did, ticker, new_req));
         }
+
+        /// Sets the compliance requirements template attached to every new asset of `asset_type`.
+        /// An empty `template` removes the template.
+        ///
+        /// Requirement ids are reassigned sequentially, starting from `1`.
+        ///
+        /// # Arguments
+        /// * `origin` - must be root.
+        /// * `asset_type` - the type of assets the template applies to.
+        /// * `template` - the compliance requirements of the template.
+        ///
+        /// # Errors
+        /// * `BadOrigin` if `origin` is not root.
+        /// * `ComplianceRequirementTooComplex` if the template exceeds the maximum complexity.
+        #[weight = <T as Config>::WeightInfo::set_default_requirements_template_full(&template)]
+        pub fn set_default_requirements_template(origin, asset_type: AssetType, template: Vec<ComplianceRequirement>) {
+            ensure_root(origin)?;
+            let template = Self::ensure_valid_template(template)?;
+
+            DefaultRequirementsTemplates::mutate(asset_type, |t| *t = template.clone());
+            Self::deposit_event(Event::DefaultRequirementsTemplateSet(GC_DID, asset_type, template));
+        }
+
+        /// Sets the compliance requirements template attached to the caller's new assets of `asset_type`.
+        /// This template takes precedence over the default one set by governance.
+        /// An empty `template` removes the template.
+        ///
+        /// Requirement ids are reassigned sequentially, starting from `1`.
+        ///
+        /// # Arguments
+        /// * `origin` - the issuer setting the template.
+        /// * `asset_type` - the type of assets the template applies to.
+        /// * `template` - the compliance requirements of the template.
+        ///
+        /// # Errors
+        /// * `ComplianceRequirementTooComplex` if the template exceeds the maximum complexity.
+        #[weight = <T as Config>::WeightInfo::set_issuer_requirements_template_full(&template)]
+        pub fn set_issuer_requirements_template(origin, asset_type: AssetType, template: Vec<ComplianceRequirement>) {
+            let did = Identity::<T>::ensure_perms(origin)?;
+            let template = Self::ensure_valid_template(template)?;
+
+            IssuerRequirementsTemplates::mutate(did, asset_type, |t| *t = template.clone());
+            Self::deposit_event(Event::IssuerRequirementsTemplateSet(did, asset_type, template));
+        }
+
+        /// Disables the compliance requirements `ids` of `ticker`, until they are enabled again or,
+        /// if `reenable_at` is given, until that block. Disabled requirements are not evaluated,
+        /// so they can't make a transfer compliant.
+        ///
+        /// Disabling an already disabled requirement replaces the block in which it is enabled again.
+        /// At most `MAX_SCHEDULED_REENABLES_PER_BLOCK` requirements are enabled again in a block,
+        /// so requirements that don't fit in `reenable_at` are enabled again in the next blocks
+        /// with room, recorded in `DisabledRequirements`.
+        ///
+        /// # Arguments
+        /// * origin - Signer of the dispatchable. It should be the owner of the ticker.
+        /// * ticker - Symbol of the asset.
+        /// * ids - Ids of the compliance requirements to disable.
+        /// * reenable_at - Block in which the requirements are automatically enabled again.
+        ///
+        /// # Errors
+        /// * `InvalidComplianceRequirementId` if one of `ids` is not a requirement of `ticker`.
+        /// * `InvalidReenableBlock` if `reenable_at` is not in the future.
+        /// * `ReenableBlocksFull` if the requirements don't fit in the `MAX_SCHEDULED_REENABLE_BLOCKS`
+        ///   blocks starting at `reenable_at`.
+        ///
+        /// # Permissions
+        /// * Asset
+        #[weight = <T as Config>::WeightInfo::disable_compliance_requirements(ids.len() as u32)]
+        pub fn disable_compliance_requirements(origin, ticker: Ticker, ids: Vec<u32>, reenable_at: Option<T::BlockNumber>) {
+            let did = <ExternalAgents<T>>::ensure_perms(origin, ticker)?;
+            if let Some(reenable_at) = reenable_at {
+                ensure!(reenable_at > frame_system::Pallet::<T>::block_number(), Error::<T>::InvalidReenableBlock);
+            }
+
+            let mut ids = ids;
+            ids.sort_unstable();
+            ids.dedup();
+            let requirements = AssetCompliances::get(ticker).requirements;
+            for id in &ids {
+                ensure!(
+                    requirements.binary_search_by_key(id, |r| r.id).is_ok(),
+                    Error::<T>::InvalidComplianceRequirementId
+                );
+            }
+
+            DisabledRequirements::<T>::try_mutate(ticker, |disabled| -> DispatchResult {
+                let mut reenable_at = reenable_at;
+                let end = reenable_at.map(|at| at.saturating_add(MAX_SCHEDULED_REENABLE_BLOCKS.into()));
+                for id in &ids {
+                    if let (Some(at), Some(end)) = (&mut reenable_at, end) {
+                        *at = Self::reenable_block(*at, end)?;
+                        ScheduledReenables::<T>::append(*at, (ticker, *id));
+                    }
+                    disabled.insert(*id, reenable_at);
+                }
+                Ok(())
+            })?;
+            Self::deposit_event(Event::ComplianceRequirementsDisabled(did, ticker, ids));
+        }
+
+        /// Enables the disabled compliance requirements `ids` of `ticker`.
+        ///
+        /// # Arguments
+        /// * origin - Signer of the dispatchable. It should be the owner of the ticker.
+        /// * ticker - Symbol of the asset.
+        /// * ids - Ids of the compliance requirements to enable.
+        ///
+        /// # Errors
+        /// * `ComplianceRequirementNotDisabled` if one of `ids` is not a disabled requirement of `ticker`.
+        ///
+        /// # Permissions
+        /// * Asset
+        #[weight = <T as Config>::WeightInfo::enable_compliance_requirements(ids.len() as u32)]
+        pub fn enable_compliance_requirements(origin, ticker: Ticker, ids: Vec<u32>) {
+            let did = <ExternalAgents<T>>::ensure_perms(origin, ticker)?;
+            DisabledRequirements::<T>::try_mutate(ticker, |disabled| {
+                for id in &ids {
+                    ensure!(disabled.remove(id).is_some(), Error::<T>::ComplianceRequirementNotDisabled);
+                }
+                Ok(()) as DispatchResult
+            })?;
+            Self::deposit_event(Event::ComplianceRequirementsEnabled(did, ticker, ids));
+        }
+
+        /// Schedules `change` to the compliance requirements of `ticker` for block `effective_from`.
+        /// The change is validated now, and a requirement being added gets its id assigned now.
+        /// If the change has become invalid by then, for instance because it would make the asset's
+        /// compliance too complex, it's dropped.
+        ///
+        /// Each requirement can have a single pending change.
+        ///
+        /// # Arguments
+        /// * origin - Signer of the dispatchable. It should be the owner of the ticker.
+        /// * ticker - Symbol of the asset.
+        /// * change - The requirement to add or replace, or the id of the requirement to remove.
+        /// * effective_from - Block in which the change is applied.
+        ///
+        /// # Errors
+        /// * `InvalidEffectiveBlock` if `effective_from` is not in the future.
+        /// * `InvalidComplianceRequirementId` if the requirement to replace or remove doesn't exist.
+        /// * `ComplianceRequirementTooComplex` if the change would exceed the maximum complexity.
+        /// * `RequirementChangeAlreadyScheduled` if the requirement already has a pending change.
+        /// * `TooManyScheduledRequirements` if `ticker` has too many pending changes.
+        /// * `FailedToScheduleRequirementChange` if the scheduler rejects the change.
+        ///
+        /// # Permissions
+        /// * Asset
+        #[weight = <T as Config>::WeightInfo::schedule_compliance_requirement_change_full(&change)]
+        pub fn schedule_compliance_requirement_change(
+            origin,
+            ticker: Ticker,
+            change: ComplianceRequirementChange,
+            effective_from: T::BlockNumber
+        ) {
+            let did = <ExternalAgents<T>>::ensure_perms(origin, ticker)?;
+            ensure!(effective_from > frame_system::Pallet::<T>::block_number(), Error::<T>::InvalidEffectiveBlock);
+            let change = Self::ensure_valid_requirement_change(ticker, change)?;
+            let id = change.requirement_id();
+
+            let mut scheduled = ScheduledRequirementChanges::<T>::get(ticker);
+            ensure!(!scheduled.contains_key(&id), Error::<T>::RequirementChangeAlreadyScheduled);
+            ensure!(scheduled.len() < MAX_SCHEDULED_REQUIREMENT_CHANGES, Error::<T>::TooManyScheduledRequirements);
+
+            let call = Call::<T>::apply_compliance_requirement_change { ticker, id }.into();
+            T::ComplianceScheduler::schedule_named(
+                Self::requirement_change_name(&ticker, id),
+                DispatchTime::At(effective_from),
+                None,
+                LOWEST_PRIORITY,
+                RawOrigin::Root.into(),
+                call,
+            )
+            .map_err(|_| Error::<T>::FailedToScheduleRequirementChange)?;
+            scheduled.insert(id, (effective_from, did, change.clone()));
+            ScheduledRequirementChanges::<T>::insert(ticker, scheduled);
+
+            if let ComplianceRequirementChange::Add(_) = change {
+                T::ProtocolFee::charge_fee(ProtocolOp::ComplianceManagerAddComplianceRequirement)?;
+            }
+            Self::deposit_event(Event::ComplianceRequirementChangeScheduled(did, ticker, change));
+        }
+
+        /// Cancels the scheduled change to the compliance requirement `id` of `ticker`.
+        ///
+        /// # Arguments
+        /// * origin - Signer of the dispatchable. It should be the owner of the ticker.
+        /// * ticker - Symbol of the asset.
+        /// * id - Id of the requirement whose change is cancelled.
+        ///
+        /// # Errors
+        /// * `NoScheduledRequirementChange` if no change to the requirement `id` is scheduled.
+        ///
+        /// # Permissions
+        /// * Asset
+        #[weight = <T as Config>::WeightInfo::cancel_compliance_requirement_change()]
+        pub fn cancel_compliance_requirement_change(origin, ticker: Ticker, id: u32) {
+            let did = <ExternalAgents<T>>::ensure_perms(origin, ticker)?;
+            ScheduledRequirementChanges::<T>::try_mutate(ticker, |scheduled| {
+                scheduled.remove(&id).ok_or(Error::<T>::NoScheduledRequirementChange)
+            })?;
+            let _ = T::ComplianceScheduler::cancel_named(Self::requirement_change_name(&ticker, id));
+            Self::deposit_event(Event::ComplianceRequirementChangeCancelled(did, ticker, id));
+        }
+
+        /// Applies the scheduled change to the compliance requirement `id` of `ticker`,
+        /// dropping it if it's no longer valid.
+        ///
+        /// Dispatched by the scheduler in the block the change was scheduled for.
+        ///
+        /// # Errors
+        /// * `BadOrigin` if `origin` is not root.
+        #[weight = <T as Config>::WeightInfo::apply_compliance_requirement_change()]
+        pub fn apply_compliance_requirement_change(origin, ticker: Ticker, id: u32) {
+            ensure_root(origin)?;
+            Self::base_apply_requirement_change(ticker, id);
+        }
+
+        /// Registers a named compliance template, which can then be applied to assets with
+        /// `apply_compliance_template`. Templates registered by root are chain-level and can be
+        /// applied by anyone, while those registered by an identity can only be applied by it.
+        ///
+        /// Requirement ids are reassigned sequentially, starting from `1`.
+        ///
+        /// # Arguments
+        /// * `origin` - root, or the identity registering the template.
+        /// * `name` - the name of the template.
+        /// * `requirements` - the compliance requirements of the template.
+        ///
+        /// # Errors
+        /// * `TooLong` if `name` is too long.
+        /// * `ComplianceRequirementTooComplex` if the template exceeds the maximum complexity.
+        /// * `CounterOverflow` if no template id is left.
+        #[weight = <T as Config>::WeightInfo::register_compliance_template_full(&requirements)]
+        pub fn register_compliance_template(origin, name: ComplianceTemplateName, requirements: Vec<ComplianceRequirement>) {
+            let owner = match ensure_root(origin.clone()) {
+                Ok(()) => GC_DID,
+                Err(_) => Identity::<T>::ensure_perms(origin)?,
+            };
+            pallet_base::ensure_string_limited::<T>(&name)?;
+            let requirements = Self::ensure_valid_template(requirements)?;
+
+            let id = ComplianceTemplateCounter::try_mutate(try_next_pre::<T, _>)?;
+            let template = ComplianceTemplate { owner, name: name.clone(), requirements: requirements.clone() };
+            ComplianceTemplates::insert(id, template);
+            Self::deposit_event(Event::ComplianceTemplateRegistered(owner, id, name, requirements));
+        }
+
+        /// Removes the compliance template `id`.
+        /// Assets the template was applied to keep their compliance requirements.
+        ///
+        /// # Arguments
+        /// * `origin` - root for chain-level templates, or the identity that registered the template.
+        /// * `id` - the id of the template to remove.
+        ///
+        /// # Errors
+        /// * `ComplianceTemplateNotFound` if there's no template `id`.
+        /// * `Unauthorized` if `origin` is not the owner of the template.
+        #[weight = <T as Config>::WeightInfo::remove_compliance_template()]
+        pub fn remove_compliance_template(origin, id: ComplianceTemplateId) {
+            let did = match ensure_root(origin.clone()) {
+                Ok(()) => GC_DID,
+                Err(_) => Identity::<T>::ensure_perms(origin)?,
+            };
+            let template = ComplianceTemplates::get(id).ok_or(Error::<T>::ComplianceTemplateNotFound)?;
+            ensure!(template.owner == did, Error::<T>::Unauthorized);
+
+            ComplianceTemplates::remove(id);
+            Self::deposit_event(Event::ComplianceTemplateRemoved(did, id));
+        }
+
+        /// Replaces the compliance requirements of `ticker` with those of the template `id`,
+        /// which must be chain-level or registered by the caller's identity.
+        ///
+        /// # Arguments
+        /// * origin - Signer of the dispatchable. It should be the owner of the ticker.
+        /// * ticker - Symbol of the asset.
+        /// * id - Id of the template to apply.
+        ///
+        /// # Errors
+        /// * `ComplianceTemplateNotFound` if there's no template `id`.
+        /// * `Unauthorized` if the template is registered by another identity.
+        /// * `ComplianceRequirementTooComplex` if the template exceeds the maximum complexity,
+        ///   given the default trusted issuers of `ticker`.
+        ///
+        /// # Permissions
+        /// * Asset
+        #[weight = <T as Config>::WeightInfo::apply_compliance_template()]
+        pub fn apply_compliance_template(origin, ticker: Ticker, id: ComplianceTemplateId) {
+            let did = <ExternalAgents<T>>::ensure_perms(origin, ticker)?;
+            let template = ComplianceTemplates::get(id).ok_or(Error::<T>::ComplianceTemplateNotFound)?;
+            ensure!(template.owner == GC_DID || template.owner == did, Error::<T>::Unauthorized);
+
+            // Ensure the complexity is limited, given the default trusted issuers of the ticker.
+            let requirements = template.requirements;
+            Self::verify_compliance_complexity(&requirements, ticker, 0)?;
+
+            // Commit changes to storage + emit events.
+            Self::update_investor_volume_tracking(&ticker, &requirements);
+            AssetCompliances::mutate(&ticker, |old| old.requirements = requirements.clone());
+            DisabledRequirements::<T>::remove(ticker);
+            Self::deposit_event(Event::AssetComplianceReplaced(did, ticker, requirements));
+            Self::deposit_event(Event::ComplianceTemplateApplied(did, ticker, id));
+        }
     }
 }
 
@@ -446,27 +869,99 @@ decl_event!(
         /// Emitted when default claim issuer list for a given ticker get removed.
         /// (caller DID, Ticker, Removed TrustedIssuer).
         TrustedDefaultClaimIssuerRemoved(IdentityId, Ticker, IdentityId),
+        /// Emitted when the default requirements template for an asset type is set.
+        /// (GC DID, AssetType, template).
+        DefaultRequirementsTemplateSet(IdentityId, AssetType, Vec<ComplianceRequirement>),
+        /// Emitted when an issuer sets its requirements template for an asset type.
+        /// (caller DID, AssetType, template).
+        IssuerRequirementsTemplateSet(IdentityId, AssetType, Vec<ComplianceRequirement>),
+        /// Emitted when compliance requirements of an asset are disabled.
+        /// (caller DID, Ticker, requirement ids).
+        ComplianceRequirementsDisabled(IdentityId, Ticker, Vec<u32>),
+        /// Emitted when disabled compliance requirements of an asset are enabled.
+        /// (caller DID, Ticker, requirement ids).
+        ComplianceRequirementsEnabled(IdentityId, Ticker, Vec<u32>),
+        /// Emitted when disabled compliance requirements of an asset reach their re-enable block.
+        /// (Ticker, requirement ids).
+        ComplianceRequirementsReenabled(Ticker, Vec<u32>),
+        /// Emitted when a change to the compliance requirements of an asset is scheduled.
+        /// (caller DID, Ticker, change).
+        ComplianceRequirementChangeScheduled(IdentityId, Ticker, ComplianceRequirementChange),
+        /// Emitted when a scheduled change to the compliance requirements of an asset is dropped,
+        /// as it's no longer valid.
+        /// (Ticker, change).
+        ScheduledComplianceRequirementChangeDropped(Ticker, ComplianceRequirementChange),
+        /// Emitted when a compliance template is registered.
+        /// (owner DID, template id, template name, requirements).
+        ComplianceTemplateRegistered(
+            IdentityId,
+            ComplianceTemplateId,
+            ComplianceTemplateName,
+            Vec<ComplianceRequirement>,
+        ),
+        /// Emitted when a compliance template is removed.
+        /// (caller DID, template id).
+        ComplianceTemplateRemoved(IdentityId, ComplianceTemplateId),
+        /// Emitted when a compliance template is applied to an asset.
+        /// (caller DID, Ticker, template id).
+        ComplianceTemplateApplied(IdentityId, Ticker, ComplianceTemplateId),
+        /// Emitted when a scheduled change to a compliance requirement is cancelled.
+        /// (caller DID, Ticker, requirement id).
+        ComplianceRequirementChangeCancelled(IdentityId, Ticker, u32),
     }
 );
 
 impl<T: Config> Module<T> {
     /// Fetches all claims of `target` identity with type
-    /// and scope from `claim` and generated by any of `issuers`.
+    /// and scope from `claim` and generated by any of `issuers`,
+    /// or by the identities they delegated the issuance of such claims to.
+    /// With `fresh_since`, only the claims last updated from that moment are fetched.
     fn fetch_claims<'a>(
         target: IdentityId,
         claim: &'a Claim,
         issuers: &'a [TrustedIssuer],
+        fresh_since: Option<u64>,
     ) -> impl 'a + Iterator<Item = Claim> {
-        let claim_type = claim.claim_type();
-        let scope = claim.as_scope();
+        Self::fetch_claims_of_type(
+            target,
+            claim.claim_type(),
+            claim.as_scope(),
+            issuers,
+            fresh_since,
+        )
+    }
 
+    /// Fetches all claims of `target` identity with `claim_type` and `scope`,
+    /// as `fetch_claims` does.
+    fn fetch_claims_of_type<'a>(
+        target: IdentityId,
+        claim_type: ClaimType,
+        scope: Option<&'a Scope>,
+        issuers: &'a [TrustedIssuer],
+        fresh_since: Option<u64>,
+    ) -> impl 'a + Iterator<Item = Claim> {
         issuers
             .iter()
             .filter(move |issuer| issuer.is_trusted_for(claim_type))
             .filter_map(move |issuer| {
-                Identity::<T>::fetch_claim(target, claim_type, issuer.issuer, scope.cloned())
-                    .map(|id_claim| id_claim.claim)
+                Identity::<T>::fetch_claim_issued_under(
+                    target,
+                    claim_type,
+                    issuer.issuer,
+                    scope.cloned(),
+                )
             })
+            .filter(move |id_claim| {
+                fresh_since.map_or(true, |since| id_claim.last_update_date >= since)
+            })
+            .map(|id_claim| id_claim.claim)
+    }
+
+    /// Returns the moment from which claims are at most `days` old.
+    fn freshness_cutoff(days: u32) -> u64 {
+        <pallet_timestamp::Pallet<T>>::get()
+            .saturated_into::<u64>()
+            .saturating_sub(u64::from(days).saturating_mul(MILLISECONDS_PER_DAY))
     }
 
     /// Returns trusted issuers specified in `condition` if any,
@@ -500,54 +995,97 @@ impl<T: Config> Module<T> {
 
         let claims = match &condition.condition_type {
             ConditionType::IsPresent(claim) | ConditionType::IsAbsent(claim) => Left(Left(
-                Self::fetch_claims(id, claim, Self::issuers_for(ticker, condition, slot)),
+                Self::fetch_claims(id, claim, Self::issuers_for(ticker, condition, slot), None),
             )),
+            ConditionType::IsPresentWithin(claim, days) => Left(Left(Self::fetch_claims(
+                id,
+                claim,
+                Self::issuers_for(ticker, condition, slot),
+                Some(Self::freshness_cutoff(*days)),
+            ))),
             ConditionType::IsAnyOf(claims) | ConditionType::IsNoneOf(claims) => {
                 let issuers = Self::issuers_for(ticker, condition, slot);
                 Left(Right(claims.iter().flat_map(move |claim| {
-                    Self::fetch_claims(id, claim, issuers)
+                    Self::fetch_claims(id, claim, issuers, None)
                 })))
             }
-            ConditionType::IsIdentity(_) => Right(core::iter::empty()),
+            ConditionType::RiskScoreAtMost(scope, _)
+            | ConditionType::RiskScoreAtLeast(scope, _) => Left(Left(Self::fetch_claims_of_type(
+                id,
+                ClaimType::RiskScore,
+                Some(scope),
+                Self::issuers_for(ticker, condition, slot),
+                None,
+            ))),
+            ConditionType::IsIdentity(_)
+            | ConditionType::HoldsNFTCredential(_)
+            | ConditionType::MinimumHoldingPeriod(_)
+            | ConditionType::MaxTransferAmount(_)
+            | ConditionType::MaxTransferVolume(..) => Right(core::iter::empty()),
         };
 
         proposition::Context { claims, id }
     }
 
-    /// Loads the context for each condition in `conditions` and verifies that all of them evaluate to `true`.
+    /// Loads the context for each condition in `conditions` and verifies that all of them evaluate to `true`
+    /// for a transfer of `value`.
     fn are_all_conditions_satisfied(
         ticker: &Ticker,
         did: IdentityId,
         conditions: &[Condition],
+        value: Balance,
     ) -> bool {
         let slot = &mut None;
         conditions
             .iter()
-            .all(|condition| Self::is_condition_satisfied(ticker, did, condition, slot))
+            .all(|condition| Self::is_condition_satisfied(ticker, did, condition, slot, value))
     }
 
-    /// Checks whether the given condition is satisfied or not.
+    /// Checks whether the given condition is satisfied or not for a transfer of `value`.
     fn is_condition_satisfied(
         ticker: &Ticker,
         did: IdentityId,
         condition: &Condition,
         slot: &mut Option<Vec<TrustedIssuer>>,
+        value: Balance,
     ) -> bool {
         let context = Self::fetch_context(did, ticker, slot, &condition);
         let any_ea = |ctx: Context<_>| ExternalAgents::<T>::agents(ticker, ctx.id).is_some();
-        proposition::run(&condition, context, any_ea)
+        let has_nft_credential = |collection: &Ticker, ctx: Context<_>| {
+            Identity::<T>::nft_credential(ctx.id, collection).is_some()
+        };
+        let has_held_for = |period: Moment, ctx: Context<_>| {
+            let now = <pallet_timestamp::Pallet<T>>::get().saturated_into::<Moment>();
+            <T as Config>::Asset::acquired_at(ticker, ctx.id).saturating_add(period) <= now
+        };
+        let within_transfer_limit = |max: Balance, days: Option<u32>, ctx: Context<_>| {
+            let past_volume = days.map_or(0, |days| {
+                Statistics::<T>::investor_transfer_volume(AssetScope::Ticker(*ticker), ctx.id, days)
+            });
+            past_volume.saturating_add(value) <= max
+        };
+        proposition::run(
+            &condition,
+            context,
+            any_ea,
+            has_nft_credential,
+            has_held_for,
+            within_transfer_limit,
+        )
     }
 
-    /// Returns whether all conditions, in their proper context, hold when evaluated.
+    /// Returns whether all conditions, in their proper context, hold when evaluated for a transfer of `value`.
     /// As a side-effect, each condition will be updated with its result,
     /// implying strict (non-lazy) evaluation of the conditions.
     fn evaluate_conditions(
         ticker: &Ticker,
         did: IdentityId,
         conditions: &mut [ConditionResult],
+        value: Balance,
     ) -> bool {
         conditions.iter_mut().fold(true, |overall, res| {
-            res.result = Self::is_condition_satisfied(ticker, did, &res.condition, &mut None);
+            res.result =
+                Self::is_condition_satisfied(ticker, did, &res.condition, &mut None, value);
             overall & res.result
         })
     }
@@ -563,6 +1101,193 @@ impl<T: Config> Module<T> {
         Ok(did)
     }
 
+    /// Enables the requirements `scheduled` to be enabled again in block `now`.
+    /// Requirements enabled since, or disabled again until another block, are skipped.
+    fn reenable_requirements(now: T::BlockNumber, scheduled: Vec<(Ticker, u32)>) {
+        let mut reenabled = BTreeMap::<Ticker, Vec<u32>>::new();
+        for (ticker, id) in scheduled {
+            DisabledRequirements::<T>::mutate(ticker, |disabled| {
+                if disabled.get(&id) == Some(&Some(now)) {
+                    disabled.remove(&id);
+                    reenabled.entry(ticker).or_default().push(id);
+                }
+            });
+        }
+        for (ticker, ids) in reenabled {
+            Self::deposit_event(Event::ComplianceRequirementsReenabled(ticker, ids));
+        }
+    }
+
+    /// Returns the first block, from `at` and before `end`, that has room for another
+    /// scheduled re-enable.
+    fn reenable_block(
+        mut at: T::BlockNumber,
+        end: T::BlockNumber,
+    ) -> Result<T::BlockNumber, DispatchError> {
+        while at < end {
+            if ScheduledReenables::<T>::decode_len(at).unwrap_or_default()
+                < MAX_SCHEDULED_REENABLES_PER_BLOCK as usize
+            {
+                return Ok(at);
+            }
+            at = at.saturating_add(One::one());
+        }
+        Err(Error::<T>::ReenableBlocksFull.into())
+    }
+
+    /// Returns the scheduler task name of the change to the requirement `id` of `ticker`.
+    fn requirement_change_name(ticker: &Ticker, id: u32) -> Vec<u8> {
+        let prefix = schedule_name_prefix::COMPLIANCE_REQUIREMENT_CHANGE;
+        (prefix, ticker, id).encode()
+    }
+
+    /// Replaces the scope of the asset `old` by that of `new` in the conditions of `req`.
+    fn migrate_requirement_scope(req: &mut ComplianceRequirement, old: &Ticker, new: &Ticker) {
+        let conditions = req
+            .sender_conditions
+            .iter_mut()
+            .chain(req.receiver_conditions.iter_mut());
+        for scope in conditions.flat_map(Condition::scopes_mut) {
+            if *scope == Scope::Ticker(*old) {
+                *scope = Scope::Ticker(*new);
+            }
+        }
+    }
+
+    /// Validates `change` to the requirements of `ticker`,
+    /// returning it with the id of a requirement being added assigned.
+    fn ensure_valid_requirement_change(
+        ticker: Ticker,
+        change: ComplianceRequirementChange,
+    ) -> Result<ComplianceRequirementChange, DispatchError> {
+        match change {
+            ComplianceRequirementChange::Add(req) => {
+                let (_, new_req) = Self::base_add_requirement(
+                    ticker,
+                    req.sender_conditions,
+                    req.receiver_conditions,
+                )?;
+                Ok(ComplianceRequirementChange::Add(new_req))
+            }
+            ComplianceRequirementChange::Replace(mut new_req) => {
+                // Ensure `Scope::Custom(..)`s are limited.
+                Self::ensure_custom_scopes_limited(new_req.conditions())?;
+
+                // Dedup `ClaimType`s and ensure issuers are limited in length.
+                Self::dedup_and_ensure_requirement_limited(&mut new_req)?;
+
+                // Ensure the requirement exists and the complexity is limited.
+                let mut reqs = AssetCompliances::get(ticker).requirements;
+                let pos = reqs
+                    .binary_search_by_key(&new_req.id, |req| req.id)
+                    .map_err(|_| Error::<T>::InvalidComplianceRequirementId)?;
+                reqs[pos] = new_req.clone();
+                Self::verify_compliance_complexity(&reqs, ticker, 0)?;
+                Ok(ComplianceRequirementChange::Replace(new_req))
+            }
+            ComplianceRequirementChange::Remove(id) => {
+                AssetCompliances::get(ticker)
+                    .requirements
+                    .binary_search_by_key(&id, |req| req.id)
+                    .map_err(|_| Error::<T>::InvalidComplianceRequirementId)?;
+                Ok(ComplianceRequirementChange::Remove(id))
+            }
+        }
+    }
+
+    /// Applies the scheduled change to the requirement `id` of `ticker`, if still pending,
+    /// dropping it if it's no longer valid.
+    fn base_apply_requirement_change(ticker: Ticker, id: u32) {
+        let scheduled =
+            ScheduledRequirementChanges::<T>::mutate(ticker, |scheduled| scheduled.remove(&id));
+        if let Some((_, did, change)) = scheduled {
+            if Self::apply_requirement_change(ticker, did, change.clone()).is_err() {
+                Self::deposit_event(Event::ScheduledComplianceRequirementChangeDropped(
+                    ticker, change,
+                ));
+            }
+        }
+    }
+
+    /// Applies `change` to the requirements of `ticker`, on behalf of `did`.
+    fn apply_requirement_change(
+        ticker: Ticker,
+        did: IdentityId,
+        change: ComplianceRequirementChange,
+    ) -> DispatchResult {
+        let mut asset_compliance = AssetCompliances::get(ticker);
+        let reqs = &mut asset_compliance.requirements;
+        let pos = reqs.binary_search_by_key(&change.requirement_id(), |req| req.id);
+        let event = match change {
+            ComplianceRequirementChange::Add(new_req) => {
+                let pos = pos
+                    .err()
+                    .ok_or(Error::<T>::DuplicateComplianceRequirements)?;
+                reqs.insert(pos, new_req.clone());
+                Event::ComplianceRequirementCreated(did, ticker, new_req)
+            }
+            ComplianceRequirementChange::Replace(new_req) => {
+                let pos = pos.map_err(|_| Error::<T>::InvalidComplianceRequirementId)?;
+                reqs[pos] = new_req.clone();
+                Event::ComplianceRequirementChanged(did, ticker, new_req)
+            }
+            ComplianceRequirementChange::Remove(id) => {
+                let pos = pos.map_err(|_| Error::<T>::InvalidComplianceRequirementId)?;
+                reqs.remove(pos);
+                Event::ComplianceRequirementRemoved(did, ticker, id)
+            }
+        };
+        Self::verify_compliance_complexity(&reqs, ticker, 0)?;
+
+        if let Event::ComplianceRequirementRemoved(_, _, id) = event {
+            DisabledRequirements::<T>::mutate(ticker, |disabled| disabled.remove(&id));
+        }
+        Self::update_investor_volume_tracking(&ticker, &asset_compliance.requirements);
+        AssetCompliances::insert(&ticker, asset_compliance);
+        Self::deposit_event(event);
+        Ok(())
+    }
+
+    /// Validates a new requirement of `ticker` with the given conditions,
+    /// returning the asset's compliance with the requirement added, along with the requirement.
+    fn base_add_requirement(
+        ticker: Ticker,
+        sender_conditions: Vec<Condition>,
+        receiver_conditions: Vec<Condition>,
+    ) -> Result<(AssetCompliance, ComplianceRequirement), DispatchError> {
+        // Ensure `Scope::Custom(..)`s are limited.
+        Self::ensure_custom_scopes_limited(sender_conditions.iter())?;
+        Self::ensure_custom_scopes_limited(receiver_conditions.iter())?;
+
+        // Bundle as a requirement.
+        let id = Self::next_requirement_id(ticker);
+        let mut new_req = ComplianceRequirement {
+            sender_conditions,
+            receiver_conditions,
+            id,
+        };
+
+        // Dedup `ClaimType`s and ensure issuers are limited in length.
+        Self::dedup_and_ensure_requirement_limited(&mut new_req)?;
+
+        // Add to existing requirements, and place a limit on the total complexity.
+        let mut asset_compliance = AssetCompliances::get(ticker);
+        let reqs = &mut asset_compliance.requirements;
+        reqs.push(new_req.clone());
+        Self::verify_compliance_complexity(&reqs, ticker, 0)?;
+        Ok((asset_compliance, new_req))
+    }
+
+    /// Records the transfer volumes of the investors of `ticker` only while one of its
+    /// `requirements` has a `MaxTransferVolume` condition.
+    fn update_investor_volume_tracking(ticker: &Ticker, requirements: &[ComplianceRequirement]) {
+        let enabled = requirements
+            .iter()
+            .flat_map(|req| req.conditions())
+            .any(|cond| matches!(cond.condition_type, ConditionType::MaxTransferVolume(..)));
+        Statistics::<T>::set_investor_volume_tracking(AssetScope::Ticker(*ticker), enabled);
+    }
+
     /// Compute the id of the last requirement in a `ticker`'s compliance rules.
     fn get_latest_requirement_id(ticker: Ticker) -> u32 {
         Self::asset_compliance(ticker)
@@ -572,6 +1297,17 @@ impl<T: Config> Module<T> {
             .unwrap_or(0)
     }
 
+    /// Compute the id of the next requirement of `ticker`,
+    /// skipping the ids of the requirements scheduled to be added.
+    fn next_requirement_id(ticker: Ticker) -> u32 {
+        let last_scheduled = ScheduledRequirementChanges::<T>::get(ticker)
+            .keys()
+            .next_back()
+            .copied()
+            .unwrap_or(0);
+        Self::get_latest_requirement_id(ticker).max(last_scheduled) + 1u32
+    }
+
     /// Verify that `asset_compliance`, with `base` complexity,
     /// is within the maximum condition complexity allowed.
     pub fn verify_compliance_complexity(
@@ -607,12 +1343,39 @@ impl<T: Config> Module<T> {
         Err(Error::<T>::ComplianceRequirementTooComplex.into())
     }
 
+    /// Ensures `template` is a valid set of compliance requirements for a new asset,
+    /// returning it with its requirement ids reassigned sequentially.
+    fn ensure_valid_template(
+        mut template: Vec<ComplianceRequirement>,
+    ) -> Result<Vec<ComplianceRequirement>, DispatchError> {
+        // Ensure `Scope::Custom(..)`s are limited.
+        Self::ensure_custom_scopes_limited(template.iter().flat_map(|c| c.conditions()))?;
+
+        for (id, req) in (1u32..).zip(template.iter_mut()) {
+            req.id = id;
+            // Dedup `ClaimType`s and ensure issuers are limited in length.
+            Self::dedup_and_ensure_requirement_limited(req)?;
+        }
+
+        // A new asset has no default trusted issuers.
+        Self::base_verify_compliance_complexity(&template, 0)?;
+        Ok(template)
+    }
+
     fn ensure_custom_scopes_limited<'a>(
         condition: impl Iterator<Item = &'a Condition>,
     ) -> DispatchResult {
-        condition
-            .flat_map(|c| c.claims())
-            .try_for_each(Identity::<T>::ensure_custom_scopes_limited)
+        condition.try_for_each(|c| {
+            c.claims()
+                .try_for_each(Identity::<T>::ensure_custom_scopes_limited)?;
+            match c.risk_score_scope() {
+                Some(scope) => Identity::<T>::ensure_custom_scopes_limited(&Claim::RiskScore(
+                    Default::default(),
+                    scope.clone(),
+                )),
+                None => Ok(()),
+            }
+        })
     }
 
     fn dedup_and_ensure_requirement_limited(req: &mut ComplianceRequirement) -> DispatchResult {
@@ -638,6 +1401,121 @@ impl<T: Config> Module<T> {
             TrustedFor::Specific(cts) => ensure_length_ok::<T>(cts.len()),
         }
     }
+
+    /// Evaluates the compliance rules of `ticker` for a transfer of `amount` from `from_did` to `to_did`,
+    /// reporting the result of each condition along with the claims that would satisfy failing ones.
+    /// Like `verify_restriction_granular`, pausing the rules doesn't skip their evaluation.
+    pub fn compliance_report(
+        ticker: &Ticker,
+        from_did: Option<IdentityId>,
+        to_did: Option<IdentityId>,
+        amount: Balance,
+    ) -> AssetComplianceReport {
+        let results =
+            Self::verify_restriction_granular_for_amount(ticker, from_did, to_did, amount);
+        let disabled = Self::disabled_requirements(ticker);
+        let default_issuers = Self::trusted_claim_issuer(ticker);
+        let report = |res: ConditionResult| {
+            let missing_claims = if res.result {
+                Vec::new()
+            } else {
+                let issuers = if res.condition.issuers.is_empty() {
+                    &default_issuers
+                } else {
+                    &res.condition.issuers
+                };
+                Self::missing_claims(&res.condition.condition_type, issuers)
+            };
+            ConditionReport {
+                condition: res.condition,
+                result: res.result,
+                missing_claims,
+            }
+        };
+        let requirements = results
+            .requirements
+            .into_iter()
+            .map(|req| ComplianceRequirementReport {
+                id: req.id,
+                disabled: disabled.contains_key(&req.id),
+                sender_conditions: req.sender_conditions.into_iter().map(&report).collect(),
+                receiver_conditions: req.receiver_conditions.into_iter().map(&report).collect(),
+                result: req.result,
+            })
+            .collect();
+        AssetComplianceReport {
+            paused: results.paused,
+            requirements,
+            result: results.result,
+        }
+    }
+
+    /// Evaluates the proposed `requirements` for `ticker` against up to `limit` of its holders,
+    /// starting after `start_after` if given, reporting those that could no longer send or receive it.
+    /// At most `MAX_SIMULATED_HOLDERS` holders are evaluated.
+    pub fn simulate_requirements(
+        ticker: &Ticker,
+        requirements: &[ComplianceRequirement],
+        start_after: Option<IdentityId>,
+        limit: u32,
+    ) -> RequirementsImpact {
+        let limit = limit.min(MAX_SIMULATED_HOLDERS);
+        let mut holders = <T as Config>::Asset::holders(ticker, start_after, limit + 1);
+        let next_holder = if holders.len() > limit as usize {
+            holders.truncate(limit as usize);
+            holders.last().copied()
+        } else {
+            None
+        };
+
+        let satisfies_any = |did, conditions: fn(&ComplianceRequirement) -> &[Condition]| {
+            requirements
+                .iter()
+                .any(|req| Self::are_all_conditions_satisfied(ticker, did, conditions(req), 0))
+        };
+        let mut impact = RequirementsImpact {
+            evaluated: holders.len() as u32,
+            cannot_send: Vec::new(),
+            cannot_receive: Vec::new(),
+            next_holder,
+        };
+        for did in holders {
+            if !satisfies_any(did, |req| &req.sender_conditions) {
+                impact.cannot_send.push(did);
+            }
+            if !satisfies_any(did, |req| &req.receiver_conditions) {
+                impact.cannot_receive.push(did);
+            }
+        }
+        impact
+    }
+
+    /// Returns the claims that would satisfy a failing condition of `condition_type`,
+    /// each with those of `issuers` trusted to issue it.
+    fn missing_claims(
+        condition_type: &ConditionType,
+        issuers: &[TrustedIssuer],
+    ) -> Vec<(Claim, Vec<TrustedIssuer>)> {
+        let claims = match condition_type {
+            ConditionType::IsPresent(claim) | ConditionType::IsPresentWithin(claim, _) => {
+                vec![claim.clone()]
+            }
+            ConditionType::IsAnyOf(claims) => claims.clone(),
+            _ => Vec::new(),
+        };
+        claims
+            .into_iter()
+            .map(|claim| {
+                let claim_type = claim.claim_type();
+                let trusted = issuers
+                    .iter()
+                    .filter(|issuer| issuer.is_trusted_for(claim_type))
+                    .cloned()
+                    .collect();
+                (claim, trusted)
+            })
+            .collect()
+    }
 }
 
 impl<T: Config> ComplianceManagerConfig for Module<T> {
@@ -646,7 +1524,7 @@ impl<T: Config> ComplianceManagerConfig for Module<T> {
         ticker: &Ticker,
         from_did_opt: Option<IdentityId>,
         to_did_opt: Option<IdentityId>,
-        _: Balance,
+        value: Balance,
     ) -> Result<u8, DispatchError> {
         // Transfer is valid if ALL receiver AND sender conditions of ANY asset conditions are valid.
         let asset_compliance = Self::asset_compliance(ticker);
@@ -654,16 +1532,32 @@ impl<T: Config> ComplianceManagerConfig for Module<T> {
             return Ok(ERC1400_TRANSFER_SUCCESS);
         }
 
-        for req in asset_compliance.requirements {
+        // Disabled requirements can't make the transfer valid.
+        let disabled = Self::disabled_requirements(ticker);
+        let requirements = asset_compliance
+            .requirements
+            .into_iter()
+            .filter(|req| !disabled.contains_key(&req.id));
+        for req in requirements {
             if let Some(from_did) = from_did_opt {
-                if !Self::are_all_conditions_satisfied(ticker, from_did, &req.sender_conditions) {
+                if !Self::are_all_conditions_satisfied(
+                    ticker,
+                    from_did,
+                    &req.sender_conditions,
+                    value,
+                ) {
                     // Skips checking receiver conditions because sender conditions are not satisfied.
                     continue;
                 }
             }
 
             if let Some(to_did) = to_did_opt {
-                if Self::are_all_conditions_satisfied(ticker, to_did, &req.receiver_conditions) {
+                if Self::are_all_conditions_satisfied(
+                    ticker,
+                    to_did,
+                    &req.receiver_conditions,
+                    value,
+                ) {
                     // All conditions satisfied, return early
                     return Ok(ERC1400_TRANSFER_SUCCESS);
                 }
@@ -674,11 +1568,24 @@ impl<T: Config> ComplianceManagerConfig for Module<T> {
 
     /// verifies all requirements and returns the result in an array of booleans.
     /// this does not care if the requirements are paused or not. It is meant to be
-    /// called only in failure conditions
+    /// called only in failure conditions. Disabled requirements are not evaluated
+    /// and always fail.
+    ///
+    /// Transfer amount and volume limits are evaluated for an empty transfer.
     fn verify_restriction_granular(
         ticker: &Ticker,
         from_did_opt: Option<IdentityId>,
         to_did_opt: Option<IdentityId>,
+    ) -> AssetComplianceResult {
+        Self::verify_restriction_granular_for_amount(ticker, from_did_opt, to_did_opt, 0)
+    }
+
+    /// Same as `verify_restriction_granular`, for a transfer of `value`.
+    fn verify_restriction_granular_for_amount(
+        ticker: &Ticker,
+        from_did_opt: Option<IdentityId>,
+        to_did_opt: Option<IdentityId>,
+        value: Balance,
     ) -> AssetComplianceResult {
         let mut compliance_with_results =
             AssetComplianceResult::from(Self::asset_compliance(ticker));
@@ -686,10 +1593,15 @@ impl<T: Config> ComplianceManagerConfig for Module<T> {
         // Evaluates all conditions.
         // False result in any of the conditions => False requirement result.
         let eval = |did: Option<_>, conds| {
-            did.filter(|did| !Self::evaluate_conditions(ticker, *did, conds))
+            did.filter(|did| !Self::evaluate_conditions(ticker, *did, conds, value))
                 .is_some()
         };
+        let disabled = Self::disabled_requirements(ticker);
         for req in &mut compliance_with_results.requirements {
+            if disabled.contains_key(&req.id) {
+                req.result = false;
+                continue;
+            }
             if eval(from_did_opt, &mut req.sender_conditions) {
                 req.result = false;
             }
@@ -700,4 +1612,101 @@ impl<T: Config> ComplianceManagerConfig for Module<T> {
         }
         compliance_with_results
     }
+
+    /// Attaches the requirements template of `did`, or otherwise the default one,
+    /// for `asset_type` to the newly created asset `ticker`.
+    fn apply_requirements_template(ticker: &Ticker, did: IdentityId, asset_type: AssetType) {
+        let mut template = IssuerRequirementsTemplates::get(did, asset_type);
+        if template.is_empty() {
+            template = DefaultRequirementsTemplates::get(asset_type);
+        }
+        if template.is_empty() {
+            return;
+        }
+
+        Self::update_investor_volume_tracking(ticker, &template);
+        AssetCompliances::mutate(ticker, |old| old.requirements = template.clone());
+        Self::deposit_event(Event::AssetComplianceReplaced(did, *ticker, template));
+    }
+
+    fn compliance_summary(ticker: &Ticker) -> ComplianceSummary {
+        let asset_compliance = AssetCompliances::get(ticker);
+        ComplianceSummary {
+            paused: asset_compliance.paused,
+            requirements: asset_compliance.requirements.len() as u32,
+            disabled_requirements: DisabledRequirements::<T>::get(ticker).len() as u32,
+            trusted_issuers: TrustedClaimIssuer::get(ticker)
+                .into_iter()
+                .map(|trusted| trusted.issuer)
+                .collect(),
+        }
+    }
+
+    fn claim_types(ticker: &Ticker) -> BTreeSet<ClaimType> {
+        let mut claim_types = BTreeSet::new();
+        for condition in AssetCompliances::get(ticker)
+            .requirements
+            .iter()
+            .flat_map(|req| req.conditions())
+        {
+            claim_types.extend(condition.claims().map(Claim::claim_type));
+            if condition.risk_score_scope().is_some() {
+                claim_types.insert(ClaimType::RiskScore);
+            }
+        }
+        claim_types
+    }
+
+    fn migrate_ticker(old: &Ticker, new: &Ticker, entries: &mut u32) -> DispatchResult {
+        // The conditions on claims scoped to `old` are moved to `new`, along with the claims.
+        let mut asset_compliance = AssetCompliances::take(old);
+        for req in &mut asset_compliance.requirements {
+            Self::migrate_requirement_scope(req, old, new);
+        }
+        AssetCompliances::insert(new, asset_compliance);
+        TrustedClaimIssuer::swap(old, new);
+
+        // The disabled requirements are re-enabled under the new ticker.
+        DisabledRequirements::<T>::swap(old, new);
+        for (id, reenable_at) in DisabledRequirements::<T>::get(new) {
+            if let Some(reenable_at) = reenable_at {
+                try_use_entry::<T>(entries)?;
+                ScheduledReenables::<T>::mutate(reenable_at, |scheduled| {
+                    for (ticker, _) in scheduled.iter_mut().filter(|entry| **entry == (*old, id)) {
+                        *ticker = *new;
+                    }
+                });
+            }
+        }
+
+        // Reschedule the pending requirement changes under the new ticker.
+        ScheduledRequirementChanges::<T>::swap(old, new);
+        ScheduledRequirementChanges::<T>::mutate(new, |scheduled| {
+            for (.., change) in scheduled.values_mut() {
+                if let ComplianceRequirementChange::Add(req)
+                | ComplianceRequirementChange::Replace(req) = change
+                {
+                    Self::migrate_requirement_scope(req, old, new);
+                }
+            }
+        });
+        for (id, (effective_from, ..)) in ScheduledRequirementChanges::<T>::get(new) {
+            try_use_entry::<T>(entries)?;
+            let _ = T::ComplianceScheduler::cancel_named(Self::requirement_change_name(old, id));
+            let call = Call::<T>::apply_compliance_requirement_change { ticker: *new, id }.into();
+            if T::ComplianceScheduler::schedule_named(
+                Self::requirement_change_name(new, id),
+                DispatchTime::At(effective_from),
+                None,
+                LOWEST_PRIORITY,
+                RawOrigin::Root.into(),
+                call,
+            )
+            .is_err()
+            {
+                ScheduledRequirementChanges::<T>::mutate(new, |scheduled| scheduled.remove(&id));
+            }
+        }
+        Ok(())
+    }
 }
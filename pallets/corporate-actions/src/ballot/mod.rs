@@ -90,7 +90,7 @@ use frame_support::{
     weights::Weight,
 };
 use pallet_asset::checkpoint;
-use pallet_base::ensure_string_limited;
+use pallet_base::{ensure_string_limited, try_use_entry};
 use pallet_identity as identity;
 use polymesh_common_utilities::protocol_fee::{ChargeProtocolFee, ProtocolOp};
 use polymesh_primitives::{Balance, EventDid, IdentityId, Moment};
@@ -648,6 +648,21 @@ impl<T: Config> Module<T> {
         Ok(())
     }
 
+    /// Moves the ballot of the corporate action `old` to `new`.
+    /// Each vote moved uses up one of the `entries`.
+    pub(crate) fn migrate_ca(old: CAId, new: CAId, entries: &mut u32) -> DispatchResult {
+        TimeRanges::swap(old, new);
+        Metas::swap(old, new);
+        MotionNumChoices::swap(old, new);
+        RCV::swap(old, new);
+        Results::swap(old, new);
+        for (did, votes) in Votes::drain_prefix(old) {
+            try_use_entry::<T>(entries)?;
+            Votes::insert(new, did, votes);
+        }
+        Ok(())
+    }
+
     /// Ensure that no string embedded within `meta` is too long.
     fn ensure_meta_lengths_limited(meta: &BallotMeta) -> DispatchResult {
         ensure_string_limited::<T>(&meta.title)?;
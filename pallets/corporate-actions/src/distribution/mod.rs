@@ -381,6 +381,8 @@ decl_error! {
         DistributionAmountIsZero,
         /// Distribution `per_share` cannot be zero.
         DistributionPerShareIsZero,
+        /// A distribution whose holders may still be paid can't be moved to another ticker.
+        DistributionNotMigratable,
     }
 }
 
@@ -584,6 +586,27 @@ impl<T: Config> Module<T> {
             .ok_or_else(|| Error::<T>::BalancePerShareProductOverflowed.into())
     }
 
+    /// Moves the distribution of the corporate action `old` to `new`.
+    /// Fails if holders may still be paid, as the payments already made are not moved.
+    pub(crate) fn migrate_ca(old: CAId, new: CAId) -> DispatchResult {
+        if let Some(mut dist) = Distributions::take(old) {
+            let now = <Checkpoint<T>>::now_unix();
+            ensure!(
+                now < dist.payment_at
+                    || expired(dist.expires_at, now)
+                    || dist.reclaimed
+                    || dist.remaining.is_zero(),
+                Error::<T>::DistributionNotMigratable
+            );
+            // A distribution paid in the migrated asset itself is paid in `new`.
+            if dist.currency == old.ticker {
+                dist.currency = new.ticker;
+            }
+            Distributions::insert(new, dist);
+        }
+        Ok(())
+    }
+
     /// Ensure `ca_id` has some distribution and return it.
     fn ensure_distribution_exists(ca_id: CAId) -> Result<Distribution, DispatchError> {
         Distributions::get(ca_id).ok_or_else(|| Error::<T>::NoSuchDistribution.into())
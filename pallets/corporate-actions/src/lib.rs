@@ -102,11 +102,14 @@ use frame_support::{
 };
 use frame_system::ensure_root;
 use pallet_asset::checkpoint::{self, SchedulePoints, ScheduleRefCount};
-use pallet_base::try_next_post;
+use pallet_base::{try_next_post, try_use_entry};
 use pallet_identity::PermissionedCallOriginData;
 use polymesh_common_utilities::{
-    balances::Config as BalancesConfig, identity::Config as IdentityConfig, traits::asset,
-    traits::checkpoint::ScheduleId, with_transaction, GC_DID,
+    balances::Config as BalancesConfig,
+    identity::Config as IdentityConfig,
+    traits::asset::{self, TickerMigrationHandler},
+    traits::checkpoint::ScheduleId,
+    with_transaction, GC_DID,
 };
 use polymesh_primitives::{
     calendar::CheckpointId, impl_checked_inc, storage_migration_ver, Balance, DocumentId, EventDid,
@@ -837,6 +840,32 @@ decl_error! {
     }
 }
 
+impl<T: Config> TickerMigrationHandler for Module<T> {
+    fn migrate_ticker(old: &Ticker, new: &Ticker, entries: &mut u32) -> DispatchResult {
+        DefaultTargetIdentities::swap(old, new);
+        DefaultWithholdingTax::swap(old, new);
+        DidWithholdingTax::swap(old, new);
+        CAIdSequence::swap(old, new);
+        for (local_id, ca) in CorporateActions::drain_prefix(old) {
+            try_use_entry::<T>(entries)?;
+            let old_id = CAId {
+                ticker: *old,
+                local_id,
+            };
+            let new_id = CAId {
+                ticker: *new,
+                local_id,
+            };
+            CADocLink::swap(old_id, new_id);
+            Details::swap(old_id, new_id);
+            <Ballot<T>>::migrate_ca(old_id, new_id, entries)?;
+            <Distribution<T>>::migrate_ca(old_id, new_id)?;
+            CorporateActions::insert(new, local_id, ca);
+        }
+        Ok(())
+    }
+}
+
 impl<T: Config> Module<T> {
     fn unsafe_initiate_corporate_action(
         agent: EventDid,
@@ -14,15 +14,20 @@
 // along with this program. If not, see <http://www.gnu.org/licenses/>.
 
 use crate::*;
+use codec::Encode;
 use frame_benchmarking::benchmarks;
-use polymesh_common_utilities::benchs::{make_asset, user, AccountIdOf, User};
+use frame_system::RawOrigin;
+use polymesh_common_utilities::benchs::{make_asset, user, user_without_did, AccountIdOf, User};
 use polymesh_common_utilities::traits::asset::Config as Asset;
+use polymesh_common_utilities::traits::identity::TargetIdAuthorization;
 use polymesh_common_utilities::TestUtilsFn;
 use polymesh_primitives::{AuthorizationData, ExtrinsicPermissions, PalletPermissions, Ticker};
+use sp_core::H512;
 use sp_std::prelude::*;
 
 pub(crate) const SEED: u32 = 0;
 const MAX_PALLETS: u32 = 19;
+const MAX_SESSION_KEYS: u32 = 10;
 
 fn setup<T: Asset + TestUtilsFn<AccountIdOf<T>>>() -> (User<T>, Ticker) {
     let owner = user("owner", SEED);
@@ -61,6 +66,28 @@ fn custom_group<T: Config>(owner: User<T>, ticker: Ticker) {
     Module::<T>::create_group(owner.origin().into(), ticker, <_>::default()).unwrap();
 }
 
+fn session_keys<T: Asset + TestUtilsFn<AccountIdOf<T>>>(
+    owner: &User<T>,
+    k: u32,
+    expires_at: T::Moment,
+) -> Vec<SessionKeyWithAuth<T::AccountId>> {
+    let auth_encoded = TargetIdAuthorization::<T::Moment> {
+        target_id: owner.did(),
+        nonce: Identity::<T>::offchain_authorization_nonce(owner.did()),
+        expires_at,
+    }
+    .encode();
+    (0..k)
+        .map(|x| {
+            let key = user_without_did::<T>("key", x);
+            SessionKeyWithAuth {
+                key: key.account(),
+                auth_signature: H512::from(key.sign(&auth_encoded).unwrap()),
+            }
+        })
+        .collect()
+}
+
 benchmarks! {
     where_clause { where T: Asset, T: TestUtilsFn<AccountIdOf<T>> }
 
@@ -147,4 +174,81 @@ benchmarks! {
         assert_eq!(AGId(1), AGIdSequence::get(ticker));
     }
 
+    register_session_keys {
+        let k in 1..MAX_SESSION_KEYS;
+
+        let (owner, ticker) = setup::<T>();
+        let expires_at: T::Moment = 600u32.into();
+        let keys = session_keys::<T>(&owner, k, expires_at);
+        let key = keys[0].key.clone();
+    }: _(owner.origin, ticker, perms(MAX_PALLETS), keys, expires_at, expires_at)
+    verify {
+        assert_eq!(pallet_identity::SecondaryKeyExpiries::<T>::get(key), Some(expires_at));
+    }
+
+    expire_session_keys {
+        let k in 1..MAX_SESSION_KEYS;
+
+        let (owner, ticker) = setup::<T>();
+        let expires_at: T::Moment = 600u32.into();
+        let keys = session_keys::<T>(&owner, k, expires_at);
+        let accounts: Vec<_> = keys.iter().map(|sk| sk.key.clone()).collect();
+        Module::<T>::register_session_keys(
+            owner.origin().into(),
+            ticker,
+            perms(MAX_PALLETS),
+            keys,
+            expires_at,
+            expires_at,
+        )
+        .unwrap();
+    }: _(owner.origin, ticker, accounts.clone())
+    verify {
+        assert!(pallet_identity::KeyRecords::<T>::get(&accounts[0]).is_none());
+    }
+
+    schedule_agent_replacement {
+        let (_, ticker) = setup::<T>();
+        let new_agent = user::<T>("new_agent", SEED);
+    }: _(RawOrigin::Root, ticker, new_agent.did())
+    verify {
+        assert!(AgentReplacements::<T>::get(ticker).is_some());
+    }
+
+    cancel_agent_replacement {
+        let (owner, ticker) = setup::<T>();
+        let new_agent = user::<T>("new_agent", SEED);
+        Module::<T>::schedule_agent_replacement(RawOrigin::Root.into(), ticker, new_agent.did()).unwrap();
+    }: _(owner.origin, ticker)
+    verify {
+        assert!(AgentReplacements::<T>::get(ticker).is_none());
+    }
+
+    replace_agent {
+        let a in 1..100;
+        let g in 0..100;
+
+        let (owner, ticker) = setup::<T>();
+        let new_agent = user::<T>("new_agent", SEED);
+        for i in 1..a {
+            let agent = user::<T>("agent", i);
+            Module::<T>::unchecked_add_agent(ticker, agent.did(), AgentGroup::ExceptMeta).unwrap();
+        }
+        // Authorizations given by the agents for other assets are scanned too.
+        for i in 0..g {
+            pallet_identity::Module::<T>::add_auth(
+                owner.did(),
+                new_agent.did().into(),
+                AuthorizationData::BecomeAgent(Ticker::generate(i as u64), AgentGroup::Full),
+                None,
+            );
+        }
+        Module::<T>::schedule_agent_replacement(RawOrigin::Root.into(), ticker, new_agent.did()).unwrap();
+        let effective_at = AgentReplacements::<T>::get(ticker).unwrap().effective_at;
+        pallet_timestamp::Pallet::<T>::set_timestamp(effective_at);
+    }: _(RawOrigin::Root, ticker, a, g)
+    verify {
+        assert_eq!(None, GroupOfAgent::get(ticker, owner.did()));
+        assert_eq!(Some(AgentGroup::Full), GroupOfAgent::get(ticker, new_agent.did()));
+    }
 }
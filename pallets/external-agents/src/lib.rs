@@ -46,6 +46,13 @@
 //! - `remove_agent` removes an agent from an asset.
 //! - `abdicate` removes the caller as an agent from an asset.
 //! - `change_group` changes the agent group an asset belongs to.
+//! - `register_session_keys` links short-lived secondary keys, restricted to an asset
+//!   and a subset of extrinsics, to the caller's identity.
+//! - `expire_session_keys` unlinks session keys from the caller's identity.
+//! - `schedule_agent_replacement` lets governance give notice that it will replace
+//!   the sole full agent of an asset, e.g. when its keys are lost.
+//! - `cancel_agent_replacement` cancels a scheduled replacement.
+//! - `replace_agent` carries out a scheduled replacement once its notice is over.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -56,15 +63,19 @@ use frame_support::{
     decl_error, decl_module, decl_storage,
     dispatch::{DispatchError, DispatchResult},
     ensure,
+    traits::Get,
+    IterableStorageDoubleMap,
 };
-use pallet_base::{try_next_post, try_next_pre};
+use frame_system::ensure_root;
+use pallet_base::{try_next_post, try_next_pre, try_use_entry};
 use pallet_identity::PermissionedCallOriginData;
-pub use polymesh_common_utilities::traits::external_agents::{Config, Event, WeightInfo};
-use polymesh_common_utilities::with_transaction;
-use polymesh_primitives::agent::{AGId, AgentGroup};
+pub use polymesh_common_utilities::traits::external_agents::{Config, Event, RawEvent, WeightInfo};
+use polymesh_common_utilities::traits::identity::SecondaryKeyWithAuth;
+use polymesh_common_utilities::{with_transaction, GC_DID};
+use polymesh_primitives::agent::{AGId, AgentGroup, AgentReplacement, SessionKeyWithAuth};
 use polymesh_primitives::{
-    extract_auth, AuthorizationData, EventDid, ExtrinsicPermissions, IdentityId, PalletPermissions,
-    Signatory, SubsetRestriction, Ticker,
+    extract_auth, AuthorizationData, EventDid, ExtrinsicPermissions, IdentityId, KeyRecord,
+    PalletPermissions, SecondaryKey, Signatory, SubsetRestriction, Ticker,
 };
 use sp_std::prelude::*;
 
@@ -106,6 +117,11 @@ decl_storage! {
                 hasher(blake2_128_concat) Ticker,
                 hasher(twox_64_concat) AGId
                 => Option<ExtrinsicPermissions>;
+
+        /// Replacements of the sole full agent of a `Ticker` scheduled by governance.
+        pub AgentReplacements get(fn agent_replacement):
+            map hasher(blake2_128_concat) Ticker
+                => Option<AgentReplacement<T::Moment>>;
     }
 }
 
@@ -257,6 +273,123 @@ decl_module! {
         pub fn create_and_change_custom_group(origin, ticker: Ticker, perms: ExtrinsicPermissions, agent: IdentityId) -> DispatchResult {
             with_transaction(|| Self::base_create_and_change_custom_group(origin, ticker, perms, agent))
         }
+
+        /// Registers short-lived session `keys` for the caller's identity,
+        /// restricted to `ticker` and to the extrinsics in `perms`.
+        ///
+        /// The keys are linked as secondary keys, as in `Identity::add_secondary_keys_with_authorization`,
+        /// so each one must have signed the off-chain authorization expiring at `auth_expires_at`.
+        /// Their asset permissions are limited to `ticker`, and their secondary key expiry is set
+        /// to `expires_at`, after which they can't be used until they are unlinked by `expire_session_keys`.
+        ///
+        /// # Arguments
+        /// - `ticker` the session keys can act on.
+        /// - `perms` the extrinsics the session keys can call.
+        /// - `keys` to register, along with their off-chain authorization signatures.
+        /// - `auth_expires_at` expiry of the off-chain authorization signed by the keys.
+        /// - `expires_at` moment after which the session keys can no longer act as agents.
+        ///
+        /// # Errors
+        /// - `UnauthorizedAgent` if the caller's identity is not authorized as an agent to call this.
+        /// - `SessionKeyExpiryInPast` if `expires_at` is not in the future.
+        /// - `TooLong` if `perms` had some string or list length that was too long.
+        ///
+        /// # Permissions
+        /// * Agent
+        /// * Primary key
+        #[weight = <T as Config>::WeightInfo::register_session_keys(keys.len() as u32)]
+        pub fn register_session_keys(
+            origin,
+            ticker: Ticker,
+            perms: ExtrinsicPermissions,
+            keys: Vec<SessionKeyWithAuth<T::AccountId>>,
+            auth_expires_at: T::Moment,
+            expires_at: T::Moment,
+        ) -> DispatchResult {
+            Self::base_register_session_keys(origin, ticker, perms, keys, auth_expires_at, expires_at)
+        }
+
+        /// Expires the session `keys` that the caller's identity registered for `ticker`,
+        /// unlinking them from the identity.
+        ///
+        /// # Arguments
+        /// - `ticker` the session keys were registered for.
+        /// - `keys` to expire.
+        ///
+        /// # Errors
+        /// - `NoSuchSessionKey` if one of `keys` is not a session key of the caller for `ticker`.
+        ///
+        /// # Permissions
+        /// * Primary key
+        #[weight = <T as Config>::WeightInfo::expire_session_keys(keys.len() as u32)]
+        pub fn expire_session_keys(origin, ticker: Ticker, keys: Vec<T::AccountId>) -> DispatchResult {
+            Self::base_expire_session_keys(origin, ticker, keys)
+        }
+
+        /// Gives notice that `new_agent` will replace the sole full agent of `ticker`,
+        /// once `AgentReplacementDelay` has passed.
+        ///
+        /// # Arguments
+        /// - `ticker` whose agent is unresponsive.
+        /// - `new_agent` that will become the only agent of `ticker`.
+        ///
+        /// # Errors
+        /// - `BadOrigin` if the caller is not root.
+        /// - `DidDoesNotExist` if `new_agent` has no identity.
+        /// - `NotSoleFullAgent` if `ticker` does not have exactly one full agent.
+        /// - `AgentReplacementAlreadyScheduled` if a replacement is already scheduled for `ticker`.
+        #[weight = <T as Config>::WeightInfo::schedule_agent_replacement()]
+        pub fn schedule_agent_replacement(origin, ticker: Ticker, new_agent: IdentityId) -> DispatchResult {
+            ensure_root(origin)?;
+            Self::base_schedule_agent_replacement(ticker, new_agent)
+        }
+
+        /// Cancels the replacement scheduled for `ticker`.
+        /// This can be done by governance, or by an agent of `ticker` to show it is still responsive.
+        ///
+        /// # Arguments
+        /// - `ticker` whose replacement is cancelled.
+        ///
+        /// # Errors
+        /// - `UnauthorizedAgent` if `origin` is neither root nor an agent authorized to call this.
+        /// - `NoAgentReplacementScheduled` if no replacement is scheduled for `ticker`.
+        ///
+        /// # Permissions
+        /// * Asset
+        /// * Agent
+        #[weight = <T as Config>::WeightInfo::cancel_agent_replacement()]
+        pub fn cancel_agent_replacement(origin, ticker: Ticker) -> DispatchResult {
+            let did = match ensure_root(origin.clone()) {
+                Ok(()) => GC_DID,
+                Err(_) => Self::ensure_perms(origin, ticker)?,
+            };
+            ensure!(AgentReplacements::<T>::contains_key(ticker), Error::<T>::NoAgentReplacementScheduled);
+            AgentReplacements::<T>::remove(ticker);
+            Self::deposit_event(RawEvent::AgentReplacementCancelled(did, ticker));
+            Ok(())
+        }
+
+        /// Carries out the replacement scheduled for `ticker`,
+        /// removing all its agents and adding the new one as its only full agent.
+        ///
+        /// # Arguments
+        /// - `ticker` whose agent is replaced.
+        /// - `agents_count` the number of agents of `ticker`.
+        /// - `auths_count` the total number of authorizations given by the agents of `ticker`.
+        ///
+        /// # Errors
+        /// - `BadOrigin` if the caller is not root.
+        /// - `NoAgentReplacementScheduled` if no replacement is scheduled for `ticker`.
+        /// - `AgentReplacementNoticeNotOver` if the notice given to the agent has not passed yet.
+        /// - `NotSoleFullAgent` if `ticker` no longer has exactly one full agent.
+        /// - `TooManyAgents` if `ticker` has more than `agents_count` agents.
+        /// - `TooManyAgentAuthorizations` if its agents have given more than `auths_count` authorizations.
+        /// - `PendingAgentAuthorization` if an agent of `ticker` has an outstanding `BecomeAgent` authorization.
+        #[weight = <T as Config>::WeightInfo::replace_agent(*agents_count, *auths_count)]
+        pub fn replace_agent(origin, ticker: Ticker, agents_count: u32, auths_count: u32) -> DispatchResult {
+            ensure_root(origin)?;
+            Self::base_replace_agent(ticker, agents_count, auths_count)
+        }
     }
 }
 
@@ -275,6 +408,24 @@ decl_error! {
         RemovingLastFullAgent,
         /// The caller's secondary key does not have the required asset permission.
         SecondaryKeyNotAuthorizedForAsset,
+        /// The expiry of the session keys is not in the future.
+        SessionKeyExpiryInPast,
+        /// The key is not a session key of the caller for the `Ticker`.
+        NoSuchSessionKey,
+        /// The asset does not have exactly one full agent.
+        NotSoleFullAgent,
+        /// A replacement of the agent is already scheduled for the `Ticker`.
+        AgentReplacementAlreadyScheduled,
+        /// No replacement of the agent is scheduled for the `Ticker`.
+        NoAgentReplacementScheduled,
+        /// The notice given to the agent before its replacement has not passed yet.
+        AgentReplacementNoticeNotOver,
+        /// An agent of the `Ticker` has issued a `BecomeAgent` authorization that is still pending.
+        PendingAgentAuthorization,
+        /// The `Ticker` has more agents than the given count.
+        TooManyAgents,
+        /// The agents of the `Ticker` have given more authorizations than the given count.
+        TooManyAgentAuthorizations,
     }
 }
 
@@ -307,7 +458,7 @@ impl<T: Config> Module<T> {
         let id = AGIdSequence::try_mutate(ticker, try_next_pre::<T, _>)?;
         // Commit & emit.
         GroupPermissions::insert(ticker, id, perms.clone());
-        Self::deposit_event(Event::GroupCreated(did.for_event(), ticker, id, perms));
+        Self::deposit_event(RawEvent::GroupCreated(did.for_event(), ticker, id, perms));
         Ok((did, id))
     }
 
@@ -340,7 +491,7 @@ impl<T: Config> Module<T> {
 
         // Commit & emit.
         GroupPermissions::insert(ticker, id, perms.clone());
-        Self::deposit_event(Event::GroupPermissionsUpdated(did, ticker, id, perms));
+        Self::deposit_event(RawEvent::GroupPermissionsUpdated(did, ticker, id, perms));
         Ok(())
     }
 
@@ -351,14 +502,14 @@ impl<T: Config> Module<T> {
     ) -> DispatchResult {
         let did = Self::ensure_perms(origin, ticker)?.for_event();
         Self::try_mutate_agents_group(ticker, agent, None)?;
-        Self::deposit_event(Event::AgentRemoved(did, ticker, agent));
+        Self::deposit_event(RawEvent::AgentRemoved(did, ticker, agent));
         Ok(())
     }
 
     fn base_abdicate(origin: T::RuntimeOrigin, ticker: Ticker) -> DispatchResult {
         let did = Self::ensure_asset_perms(origin, &ticker)?.primary_did;
         Self::try_mutate_agents_group(ticker, did, None)?;
-        Self::deposit_event(Event::AgentRemoved(did.for_event(), ticker, did));
+        Self::deposit_event(RawEvent::AgentRemoved(did.for_event(), ticker, did));
         Ok(())
     }
 
@@ -390,10 +541,200 @@ impl<T: Config> Module<T> {
     ) -> DispatchResult {
         Self::ensure_agent_group_valid(ticker, group)?;
         Self::try_mutate_agents_group(ticker, agent, Some(group))?;
-        Self::deposit_event(Event::GroupChanged(did, ticker, agent, group));
+        Self::deposit_event(RawEvent::GroupChanged(did, ticker, agent, group));
+        Ok(())
+    }
+
+    fn base_register_session_keys(
+        origin: T::RuntimeOrigin,
+        ticker: Ticker,
+        perms: ExtrinsicPermissions,
+        keys: Vec<SessionKeyWithAuth<T::AccountId>>,
+        auth_expires_at: T::Moment,
+        expires_at: T::Moment,
+    ) -> DispatchResult {
+        let (_, did) = <Identity<T>>::ensure_primary_key(origin)?;
+        Self::ensure_agent_permissioned(ticker, did)?;
+        ensure!(
+            <pallet_timestamp::Pallet<T>>::get() < expires_at,
+            Error::<T>::SessionKeyExpiryInPast
+        );
+
+        let permissions = polymesh_primitives::Permissions {
+            asset: SubsetRestriction::elem(ticker),
+            extrinsic: perms.clone(),
+            portfolio: SubsetRestriction::Whole,
+        };
+        let session_keys: Vec<T::AccountId> = keys.iter().map(|sk| sk.key.clone()).collect();
+        let keys = keys
+            .into_iter()
+            .map(|sk| SecondaryKeyWithAuth {
+                secondary_key: SecondaryKey::new(sk.key, permissions.clone()),
+                auth_signature: sk.auth_signature,
+            })
+            .collect();
+        <Identity<T>>::unsafe_add_secondary_keys_with_authorization(did, keys, auth_expires_at)?;
+
+        // Commit & emit.
+        for key in &session_keys {
+            <Identity<T>>::unsafe_set_secondary_key_expiry(did, key.clone(), Some(expires_at));
+        }
+        Self::deposit_event(RawEvent::SessionKeysRegistered(
+            did.for_event(),
+            ticker,
+            session_keys,
+            perms,
+            expires_at,
+        ));
         Ok(())
     }
 
+    fn base_expire_session_keys(
+        origin: T::RuntimeOrigin,
+        ticker: Ticker,
+        keys: Vec<T::AccountId>,
+    ) -> DispatchResult {
+        let (_, did) = <Identity<T>>::ensure_primary_key(origin)?;
+        for key in &keys {
+            ensure!(
+                Self::is_session_key(did, ticker, key),
+                Error::<T>::NoSuchSessionKey
+            );
+        }
+
+        // Commit & emit.
+        <Identity<T>>::unsafe_remove_secondary_keys(did, keys.clone())?;
+        Self::deposit_event(RawEvent::SessionKeysExpired(did.for_event(), ticker, keys));
+        Ok(())
+    }
+
+    fn base_schedule_agent_replacement(ticker: Ticker, new_agent: IdentityId) -> DispatchResult {
+        <Identity<T>>::ensure_id_record_exists(new_agent)?;
+        ensure!(
+            NumFullAgents::get(ticker) == 1,
+            Error::<T>::NotSoleFullAgent
+        );
+        ensure!(
+            !AgentReplacements::<T>::contains_key(ticker),
+            Error::<T>::AgentReplacementAlreadyScheduled
+        );
+
+        let effective_at = <pallet_timestamp::Pallet<T>>::get() + T::AgentReplacementDelay::get();
+        AgentReplacements::<T>::insert(
+            ticker,
+            AgentReplacement {
+                new_agent,
+                effective_at,
+            },
+        );
+        Self::deposit_event(RawEvent::AgentReplacementScheduled(
+            GC_DID,
+            ticker,
+            new_agent,
+            effective_at,
+        ));
+        Ok(())
+    }
+
+    fn base_replace_agent(ticker: Ticker, agents_count: u32, auths_count: u32) -> DispatchResult {
+        let replacement =
+            AgentReplacements::<T>::get(ticker).ok_or(Error::<T>::NoAgentReplacementScheduled)?;
+        ensure!(
+            <pallet_timestamp::Pallet<T>>::get() >= replacement.effective_at,
+            Error::<T>::AgentReplacementNoticeNotOver
+        );
+        ensure!(
+            NumFullAgents::get(ticker) == 1,
+            Error::<T>::NotSoleFullAgent
+        );
+        let agents: Vec<IdentityId> = GroupOfAgent::iter_prefix(ticker)
+            .map(|(agent, _)| agent)
+            .take(agents_count as usize + 1)
+            .collect();
+        ensure!(
+            agents.len() <= agents_count as usize,
+            Error::<T>::TooManyAgents
+        );
+        let mut auths_left = auths_count;
+        for agent in &agents {
+            ensure!(
+                !Self::has_pending_agent_auth(*agent, ticker, &mut auths_left)?,
+                Error::<T>::PendingAgentAuthorization
+            );
+        }
+
+        // Commit & emit.
+        AgentReplacements::<T>::remove(ticker);
+        for agent in agents {
+            GroupOfAgent::remove(ticker, agent);
+            AgentOf::remove(agent, ticker);
+            Self::deposit_event(RawEvent::AgentRemoved(GC_DID.for_event(), ticker, agent));
+        }
+        NumFullAgents::insert(ticker, 0);
+        Self::unchecked_add_agent(ticker, replacement.new_agent, AgentGroup::Full)?;
+        Self::deposit_event(RawEvent::AgentReplaced(
+            GC_DID,
+            ticker,
+            replacement.new_agent,
+        ));
+        Ok(())
+    }
+
+    /// Moves the agents, agent groups and scheduled agent replacement of `old` to `new`.
+    /// Each agent and agent group moved uses up one of the `entries`.
+    pub fn migrate_ticker(old: Ticker, new: Ticker, entries: &mut u32) -> DispatchResult {
+        for (agent, group) in GroupOfAgent::drain_prefix(old) {
+            try_use_entry::<T>(entries)?;
+            GroupOfAgent::insert(new, agent, group);
+            AgentOf::remove(agent, old);
+            AgentOf::insert(agent, new, ());
+        }
+        for (ag_id, permissions) in GroupPermissions::drain_prefix(old) {
+            try_use_entry::<T>(entries)?;
+            GroupPermissions::insert(new, ag_id, permissions);
+        }
+        AGIdSequence::swap(old, new);
+        NumFullAgents::swap(old, new);
+        AgentReplacements::<T>::swap(old, new);
+        Ok(())
+    }
+
+    /// Returns `true` if `agent` has issued a `BecomeAgent` authorization for `ticker`
+    /// that can still be accepted.
+    ///
+    /// At most `auths_left` authorizations are scanned, the count being decreased by the scanned ones.
+    fn has_pending_agent_auth(
+        agent: IdentityId,
+        ticker: Ticker,
+        auths_left: &mut u32,
+    ) -> Result<bool, DispatchError> {
+        let now = <pallet_timestamp::Pallet<T>>::get();
+        for (auth_id, target) in pallet_identity::AuthorizationsGiven::<T>::iter_prefix(agent) {
+            *auths_left = auths_left
+                .checked_sub(1)
+                .ok_or(Error::<T>::TooManyAgentAuthorizations)?;
+            let pending = <Identity<T>>::authorizations(target, auth_id).map_or(false, |auth| {
+                matches!(auth.authorization_data, AuthorizationData::BecomeAgent(t, _) if t == ticker)
+                    && auth.expiry.map_or(true, |expiry| expiry > now)
+            });
+            if pending {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    /// Returns `true` if `key` is a session key of `did` for `ticker`,
+    /// i.e. an expiring secondary key of `did` whose asset permissions are limited to `ticker`.
+    fn is_session_key(did: IdentityId, ticker: Ticker, key: &T::AccountId) -> bool {
+        <Identity<T>>::secondary_key_expiry(key).is_some()
+            && matches!(
+                <Identity<T>>::key_records(key),
+                Some(KeyRecord::SecondaryKey(key_did, perms))
+                    if key_did == did && perms.asset == SubsetRestriction::elem(ticker)
+            )
+    }
+
     /// Ensure that `group` is a valid agent group for `ticker`.
     fn ensure_agent_group_valid(ticker: Ticker, group: AgentGroup) -> DispatchResult {
         if let AgentGroup::Custom(id) = group {
@@ -451,7 +792,7 @@ impl<T: Config> Module<T> {
         }
         GroupOfAgent::insert(ticker, did, group);
         AgentOf::insert(did, ticker, ());
-        Self::deposit_event(Event::AgentAdded(did.for_event(), ticker, group));
+        Self::deposit_event(RawEvent::AgentAdded(did.for_event(), ticker, group));
         Ok(())
     }
 
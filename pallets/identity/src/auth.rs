@@ -14,19 +14,26 @@
 // along with this program. If not, see <http://www.gnu.org/licenses/>.
 
 use crate::{
-    AuthorizationType, Authorizations, AuthorizationsGiven, Config, Error, KeyRecords, Module,
-    MultiPurposeNonce, RawEvent,
+    AuthorizationCount, AuthorizationType, Authorizations, AuthorizationsGiven, Config, Error,
+    KeyRecords, Module, MultiPurposeNonce, RawEvent, WeightInfo,
+};
+use frame_support::dispatch::{DispatchResult, DispatchResultWithPostInfo, Pays};
+use frame_support::{
+    ensure, fail, IterableStorageDoubleMap, StorageDoubleMap, StorageMap, StorageValue,
 };
-use frame_support::dispatch::DispatchResult;
-use frame_support::{ensure, StorageDoubleMap, StorageMap, StorageValue};
 use frame_system::ensure_signed;
-use polymesh_common_utilities::Context;
+use pallet_base::ensure_custom_length_ok;
+use polymesh_common_utilities::traits::{asset::AssetSubTrait, portfolio::PortfolioSubTrait};
+use polymesh_common_utilities::{with_transaction, Context};
 use polymesh_primitives::{
     Authorization, AuthorizationData, AuthorizationError, IdentityId, Signatory,
 };
 use sp_runtime::DispatchError;
 use sp_std::vec::Vec;
 
+/// Maximum number of authorizations that can be accepted by a single `consume_authorizations_batch` call.
+pub const MAX_AUTHORIZATIONS_PER_BATCH: u32 = 50;
+
 impl<T: Config> Module<T> {
     /// Adds an authorization.
     pub(crate) fn base_add_authorization(
@@ -63,6 +70,7 @@ impl<T: Config> Module<T> {
         };
 
         <Authorizations<T>>::insert(target.clone(), new_nonce, auth);
+        AuthorizationCount::mutate(|count| *count = count.saturating_add(1));
         <AuthorizationsGiven<T>>::insert(from, new_nonce, target.clone());
 
         // This event is split in order to help the event harvesters.
@@ -110,8 +118,7 @@ impl<T: Config> Module<T> {
         authorizer: &IdentityId,
         revoked: bool,
     ) {
-        <Authorizations<T>>::remove(target, auth_id);
-        <AuthorizationsGiven<T>>::remove(authorizer, auth_id);
+        Self::remove_auth_entries(target, auth_id, authorizer);
         let id = target.as_identity().cloned();
         let acc = target.as_account().cloned();
         let event = if revoked {
@@ -122,6 +129,102 @@ impl<T: Config> Module<T> {
         Self::deposit_event(event(id, acc, auth_id))
     }
 
+    fn remove_auth_entries(
+        target: &Signatory<T::AccountId>,
+        auth_id: u64,
+        authorizer: &IdentityId,
+    ) {
+        <Authorizations<T>>::remove(target, auth_id);
+        <AuthorizationsGiven<T>>::remove(authorizer, auth_id);
+        AuthorizationCount::mutate(|count| *count = count.saturating_sub(1));
+    }
+
+    /// Removes the expired authorizations of `target` among at most `max_scanned`, capped to
+    /// `MAX_AUTHORIZATIONS_PER_BATCH`, of its authorizations after `start_after`.
+    ///
+    /// Freeing storage is refunded: the caller pays no fee if every scanned authorization was removed.
+    pub(crate) fn base_remove_expired_authorizations(
+        origin: T::RuntimeOrigin,
+        target: Signatory<T::AccountId>,
+        start_after: Option<u64>,
+        max_scanned: u32,
+    ) -> DispatchResultWithPostInfo {
+        let did = Self::ensure_perms(origin)?;
+        let now = <pallet_timestamp::Pallet<T>>::get();
+        let max_scanned = max_scanned.min(MAX_AUTHORIZATIONS_PER_BATCH) as usize;
+        let iter = match start_after {
+            Some(auth_id) => {
+                let raw_key = <Authorizations<T>>::hashed_key_for(&target, auth_id);
+                <Authorizations<T>>::iter_prefix_from(&target, raw_key)
+            }
+            None => <Authorizations<T>>::iter_prefix(&target),
+        };
+        let scanned: Vec<_> = iter.take(max_scanned).map(|(_, auth)| auth).collect();
+        let last_scanned = (scanned.len() == max_scanned)
+            .then(|| scanned.last().map(|auth| auth.auth_id))
+            .flatten();
+        let scanned_count = scanned.len() as u32;
+        let expired: Vec<_> = scanned
+            .into_iter()
+            .filter(|auth| auth.expiry.map_or(false, |expiry| expiry <= now))
+            .map(|auth| (auth.auth_id, auth.authorized_by))
+            .collect();
+
+        let auth_ids: Vec<_> = expired
+            .into_iter()
+            .map(|(auth_id, authorizer)| {
+                Self::remove_auth_entries(&target, auth_id, &authorizer);
+                auth_id
+            })
+            .collect();
+        let removed = auth_ids.len() as u32;
+        Self::deposit_event(RawEvent::ExpiredAuthorizationsRemoved(
+            did,
+            target.as_identity().cloned(),
+            target.as_account().cloned(),
+            auth_ids,
+            last_scanned,
+        ));
+
+        // Only a scan that removed every authorization it went through is free.
+        let pays_fee = if removed > 0 && removed == scanned_count {
+            Pays::No
+        } else {
+            Pays::Yes
+        };
+        let actual_weight = <T as Config>::WeightInfo::remove_expired_authorizations(scanned_count);
+        Ok((Some(actual_weight), pays_fee).into())
+    }
+
+    /// Accepts, all or none, the ticker transfer, asset ownership transfer and portfolio custody
+    /// authorizations `auth_ids` of the caller's identity.
+    pub(crate) fn base_consume_authorizations_batch(
+        origin: T::RuntimeOrigin,
+        auth_ids: Vec<u64>,
+    ) -> DispatchResult {
+        let did = Self::ensure_perms(origin)?;
+        ensure_custom_length_ok::<T>(auth_ids.len(), MAX_AUTHORIZATIONS_PER_BATCH)?;
+        let target = Signatory::Identity(did);
+
+        with_transaction(|| {
+            for auth_id in auth_ids {
+                match Self::ensure_authorization(&target, auth_id)?.authorization_data {
+                    AuthorizationData::TransferTicker(_) => {
+                        T::AssetSubTraitTarget::accept_ticker_transfer(did, auth_id)?
+                    }
+                    AuthorizationData::TransferAssetOwnership(_) => {
+                        T::AssetSubTraitTarget::accept_asset_ownership_transfer(did, auth_id)?
+                    }
+                    AuthorizationData::PortfolioCustody(_) => {
+                        T::Portfolio::accept_portfolio_custody(did, auth_id)?
+                    }
+                    _ => fail!(Error::<T>::UnsupportedBatchAuthorization),
+                }
+            }
+            Ok(())
+        })
+    }
+
     /// Use to get the filtered authorization data for a given signatory
     /// - if auth_type is None then return authorizations data on the basis of the `allow_expired` boolean
     /// - if auth_type is Some(value) then return filtered authorizations on the value basis type in conjunction
@@ -189,6 +292,7 @@ impl<T: Config> Module<T> {
             if auth.count == 0 {
                 <Authorizations<T>>::remove(&target, auth_id);
                 <AuthorizationsGiven<T>>::remove(auth.authorized_by, auth_id);
+                AuthorizationCount::mutate(|count| *count = count.saturating_sub(1));
                 Self::deposit_event(RawEvent::AuthorizationRetryLimitReached(
                     target.as_identity().cloned(),
                     target.as_account().cloned(),
@@ -206,6 +310,7 @@ impl<T: Config> Module<T> {
         // Remove authorization from storage and emit event.
         <Authorizations<T>>::remove(&target, auth_id);
         <AuthorizationsGiven<T>>::remove(auth.authorized_by, auth_id);
+        AuthorizationCount::mutate(|count| *count = count.saturating_sub(1));
         Self::deposit_event(RawEvent::AuthorizationConsumed(
             target.as_identity().cloned(),
             target.as_account().cloned(),
@@ -22,12 +22,14 @@ use polymesh_common_utilities::{
     benchs::{cdd_provider, user, user_without_did, AccountIdOf, User, UserBuilder},
     traits::{identity::TargetIdAuthorization, TestUtilsFn},
 };
+use polymesh_primitives::identity_claim::MAX_CUSTOM_CLAIM_SCHEMA_FIELDS;
 use polymesh_primitives::{
     investor_zkproof_data::{v1, v2},
     secondary_key::DispatchableNames,
-    AssetPermissions, AuthorizationData, Claim, CountryCode, DispatchableName,
-    ExtrinsicPermissions, IdentityId, InvestorUid, PalletName, PalletPermissions, Permissions,
-    PortfolioId, PortfolioNumber, PortfolioPermissions, Scope, ScopeId, SecondaryKey, Signatory,
+    AssetPermissions, AuthorizationData, Claim, ClaimType, CountryCode, CustomClaimField,
+    CustomClaimFieldType, DispatchableName, ExtrinsicPermissions, IdentityId, InvestorUid,
+    PalletName, PalletPermissions, Permissions, PortfolioId, PortfolioNumber, PortfolioPermissions,
+    RecoveryGuardians, Scope, ScopeId, SecondaryKey, Signatory,
 };
 use sp_core::H512;
 use sp_std::prelude::*;
@@ -130,6 +132,22 @@ pub fn generate_secondary_keys<T: Config>(n: usize) -> Vec<SecondaryKey<T::Accou
     secondary_keys
 }
 
+/// Links the identity of a new `child` user as a child of a new `parent` user.
+fn setup_child_identity<T: Config + TestUtilsFn<AccountIdOf<T>>>() -> (User<T>, User<T>) {
+    let parent = user::<T>("parent", 0);
+    let child = user::<T>("child", 0);
+    Module::<T>::approve_parent_identity(child.origin().into(), Some(parent.did())).unwrap();
+    Module::<T>::link_child_identity(parent.origin().into(), child.did(), true).unwrap();
+    (parent, child)
+}
+
+fn bridge_withdraw_filter() -> CallFilter {
+    CallFilter {
+        pallet_name: PalletName::from("Bridge"),
+        dispatchable_name: Some(DispatchableName::from("handle_bridge_tx")),
+    }
+}
+
 #[cfg(feature = "running-ci")]
 mod limits {
     pub const MAX_SECONDARY_KEYS: u32 = 2;
@@ -285,6 +303,43 @@ benchmarks! {
         let claim = Claim::Jurisdiction(CountryCode::BB, scope);
     }: _(caller.origin, target.did(), claim, Some(666u32.into()))
 
+    add_claims_batch {
+        let c in 0 .. MAX_CLAIMS_PER_BATCH as u32;
+
+        let caller = user::<T>("caller", 0);
+        let claims = (0..c)
+            .map(|i| {
+                let target = user::<T>("target", i);
+                let scope = Scope::Identity(caller.did());
+                (target.did(), Claim::Jurisdiction(CountryCode::BB, scope), Some(666u32.into()))
+            })
+            .collect::<Vec<_>>();
+    }: _(caller.origin, claims)
+
+    schedule_claim_revocation {
+        let caller = user::<T>("caller", 0);
+        let target = user::<T>("target", 0);
+        let scope = Scope::Identity(caller.did());
+        let claim = Claim::Jurisdiction(CountryCode::BB, scope);
+        Module::<T>::add_claim(caller.origin.clone().into(), target.did(), claim.clone(), None).unwrap();
+        let at: T::Moment = 1_000u32.into();
+    }: _(caller.origin, target.did(), claim, at)
+
+    revoke_scheduled_claim {
+        let caller = user::<T>("caller", 0);
+        let target = user::<T>("target", 0);
+        let scope = Scope::Identity(caller.did());
+        let claim = Claim::Jurisdiction(CountryCode::BB, scope.clone());
+        Module::<T>::add_claim(caller.origin.clone().into(), target.did(), claim.clone(), None).unwrap();
+        let at: T::Moment = 1_000u32.into();
+        Module::<T>::schedule_claim_revocation(caller.origin.clone().into(), target.did(), claim, at).unwrap();
+        pallet_timestamp::Pallet::<T>::set_timestamp(at);
+        let (pk, sk) = Module::<T>::get_claim_keys(target.did(), ClaimType::Jurisdiction, caller.did(), Some(scope.clone()));
+    }: _(RawOrigin::Root, target.did(), ClaimType::Jurisdiction, caller.did(), Some(scope), at)
+    verify {
+        assert!(!Claims::contains_key(&pk, &sk));
+    }
+
     revoke_claim {
         let (caller, scope, claim, proof) = setup_investor_uniqueness_claim_v1::<T>("caller");
         Module::<T>::add_investor_uniqueness_claim(caller.origin.clone().into(), caller.did(), claim.clone(), proof, Some(666u32.into())).unwrap();
@@ -372,6 +427,75 @@ benchmarks! {
         Module::<T>::freeze_secondary_keys(caller.origin.clone().into()).unwrap();
     }: _(caller.origin)
 
+    set_secondary_key_expiry {
+        let target = user::<T>("target", 0);
+        let key = UserBuilder::<T>::default().build("key");
+        let account_id = key.account();
+
+        Module::<T>::unsafe_join_identity(target.did(), Permissions::default(), account_id.clone());
+        let expiry: T::Moment = 600u32.into();
+    }: _(target.origin, account_id, Some(expiry))
+
+    grant_temporary_permissions {
+        let target = user::<T>("target", 0);
+        let key = UserBuilder::<T>::default().build("key");
+        let account_id = key.account();
+
+        Module::<T>::unsafe_join_identity(target.did(), Permissions::empty(), account_id.clone());
+    }: _(target.origin, account_id, Permissions::default(), 10u32.into())
+
+    set_key_usage_audit {
+        let caller = user::<T>("caller", 0);
+    }: _(caller.origin, true)
+    verify {
+        assert!(KeyUsageAudit::get(caller.did()));
+    }
+
+    set_key_probation_period {
+        let caller = user::<T>("caller", 0);
+    }: _(caller.origin, Some(10u32.into()))
+    verify {
+        assert_eq!(KeyProbationPeriods::<T>::get(caller.did()), Some(10u32.into()));
+    }
+
+    confirm_secondary_key {
+        let target = user::<T>("target", 0);
+        let key = UserBuilder::<T>::default().build("key");
+        let account_id = key.account();
+
+        KeyProbationPeriods::<T>::insert(target.did(), T::BlockNumber::from(10u32));
+        Module::<T>::unsafe_join_identity(target.did(), Permissions::default(), account_id.clone());
+    }: _(target.origin, account_id.clone())
+    verify {
+        assert!(ProbationaryKeys::<T>::get(account_id).is_none());
+    }
+
+    end_key_probation {
+        let target = user::<T>("target", 0);
+        let key = UserBuilder::<T>::default().build("key");
+        let account_id = key.account();
+
+        KeyProbationPeriods::<T>::insert(target.did(), T::BlockNumber::from(1u32));
+        Module::<T>::unsafe_join_identity(target.did(), Permissions::default(), account_id.clone());
+        let ends_at = frame_system::Pallet::<T>::block_number() + 1u32.into();
+    }: _(RawOrigin::Root, account_id.clone(), ends_at)
+    verify {
+        assert!(KeyRecords::<T>::get(account_id).is_none());
+    }
+
+    revert_temporary_permissions {
+        let target = user::<T>("target", 0);
+        let key = UserBuilder::<T>::default().build("key");
+        let account_id = key.account();
+
+        Module::<T>::unsafe_join_identity(target.did(), Permissions::empty(), account_id.clone());
+        Module::<T>::grant_temporary_permissions(target.origin.into(), account_id.clone(), Permissions::default(), 1u32.into()).unwrap();
+        let revert_at = frame_system::Pallet::<T>::block_number() + 1u32.into();
+    }: _(RawOrigin::Root, account_id.clone(), revert_at)
+    verify {
+        assert!(TemporaryPermissionGrants::<T>::get(account_id).is_none());
+    }
+
     add_authorization {
         let caller = user::<T>("caller", 0);
         let signatory = Signatory::Identity(caller.did());
@@ -420,6 +544,218 @@ benchmarks! {
         let (caller, scope, claim, proof) = setup_investor_uniqueness_claim_v2::<T>("caller");
     }: _(caller.origin, caller.did(), scope, claim, proof.0, Some(666u32.into()))
 
+    set_key_recovery_delegate {
+        let cdd = cdd_provider::<T>("cdd", 0);
+        let target = user::<T>("target", 0);
+    }: _(target.origin, Some(cdd.did()))
+    verify {
+        assert_eq!(Module::<T>::key_recovery_delegate(target.did()), Some(cdd.did()));
+    }
+
+    initiate_key_recovery {
+        let cdd = cdd_provider::<T>("cdd", 0);
+        let target = user::<T>("target", 0);
+        let new_key: T::AccountId = account("key", SEED, SEED);
+        Module::<T>::set_key_recovery_delegate(target.origin().into(), Some(cdd.did())).unwrap();
+    }: _(cdd.origin, target.did(), new_key)
+
+    cancel_key_recovery {
+        let cdd = cdd_provider::<T>("cdd", 0);
+        let target = user::<T>("target", 0);
+        let new_key: T::AccountId = account("key", SEED, SEED);
+        Module::<T>::set_key_recovery_delegate(target.origin().into(), Some(cdd.did())).unwrap();
+        Module::<T>::initiate_key_recovery(cdd.origin().into(), target.did(), new_key).unwrap();
+    }: _(target.origin, target.did())
+
+    execute_key_recovery {
+        let cdd = cdd_provider::<T>("cdd", 0);
+        let target = user::<T>("target", 0);
+        let new_key: T::AccountId = account("key", SEED, SEED);
+        Module::<T>::set_key_recovery_delegate(target.origin().into(), Some(cdd.did())).unwrap();
+        Module::<T>::initiate_key_recovery(cdd.origin().into(), target.did(), new_key.clone()).unwrap();
+        PendingKeyRecoveries::<T>::mutate(target.did(), |recovery| {
+            if let Some(recovery) = recovery {
+                recovery.executable_at = 0u32.into();
+            }
+        });
+    }: _(cdd.origin, target.did())
+    verify {
+        assert_eq!(Module::<T>::get_primary_key(target.did()), Some(new_key));
+    }
+
+    set_recovery_guardians {
+        let n in 0 .. MAX_RECOVERY_GUARDIANS;
+
+        let target = user::<T>("target", 0);
+        let guardians = (0..n).map(|i| user::<T>("guardian", i).did()).collect::<Vec<_>>();
+        let guardians = (n > 0).then(|| RecoveryGuardians { guardians, threshold: n });
+    }: _(target.origin, guardians.clone())
+    verify {
+        assert_eq!(Module::<T>::recovery_guardians(target.did()), guardians);
+    }
+
+    approve_social_recovery {
+        let target = user::<T>("target", 0);
+        let guardian = user::<T>("guardian", 0);
+        let new_key: T::AccountId = account("key", SEED, SEED);
+        let guardians = RecoveryGuardians { guardians: vec![guardian.did()], threshold: 1 };
+        Module::<T>::set_recovery_guardians(target.origin().into(), Some(guardians)).unwrap();
+    }: _(guardian.origin, target.did(), new_key)
+    verify {
+        assert!(Module::<T>::pending_social_recovery(target.did()).unwrap().executable.is_some());
+    }
+
+    cancel_social_recovery {
+        let target = user::<T>("target", 0);
+        let guardian = user::<T>("guardian", 0);
+        let new_key: T::AccountId = account("key", SEED, SEED);
+        let guardians = RecoveryGuardians { guardians: vec![guardian.did()], threshold: 1 };
+        Module::<T>::set_recovery_guardians(target.origin().into(), Some(guardians)).unwrap();
+        Module::<T>::approve_social_recovery(guardian.origin().into(), target.did(), new_key).unwrap();
+    }: _(guardian.origin, target.did())
+    verify {
+        assert!(Module::<T>::pending_social_recovery(target.did()).is_none());
+    }
+
+    execute_social_recovery {
+        let target = user::<T>("target", 0);
+        let guardian = user::<T>("guardian", 0);
+        let new_key: T::AccountId = account("key", SEED, SEED);
+        let guardians = RecoveryGuardians { guardians: vec![guardian.did()], threshold: 1 };
+        Module::<T>::set_recovery_guardians(target.origin().into(), Some(guardians)).unwrap();
+        Module::<T>::approve_social_recovery(guardian.origin().into(), target.did(), new_key.clone()).unwrap();
+        PendingSocialRecoveries::<T>::mutate(target.did(), |recovery| {
+            if let Some(recovery) = recovery {
+                recovery.executable = Some((new_key.clone(), 0u32.into()));
+            }
+        });
+    }: _(RawOrigin::Signed(new_key.clone()), target.did())
+    verify {
+        assert_eq!(Module::<T>::get_primary_key(target.did()), Some(new_key));
+    }
+
+    set_claim_issuance_limit {
+    }: _(RawOrigin::Root, Some(100), 1000u32.into())
+
+    set_issuer_claim_issuance_limit {
+        let issuer = user::<T>("issuer", 0);
+    }: _(RawOrigin::Root, issuer.did(), Some(100))
+
+    add_call_filter {
+        let caller = user::<T>("caller", 0);
+        let filter = bridge_withdraw_filter();
+    }: _(caller.origin, filter.clone())
+    verify {
+        assert!(Module::<T>::has_call_filter(caller.did(), filter));
+    }
+
+    request_call_filter_removal {
+        let caller = user::<T>("caller", 0);
+        let filter = bridge_withdraw_filter();
+        Module::<T>::add_call_filter(caller.origin().into(), filter.clone()).unwrap();
+    }: _(caller.origin, filter)
+
+    remove_call_filter {
+        let caller = user::<T>("caller", 0);
+        let filter = bridge_withdraw_filter();
+        Module::<T>::add_call_filter(caller.origin().into(), filter.clone()).unwrap();
+        Module::<T>::request_call_filter_removal(caller.origin().into(), filter.clone()).unwrap();
+        CallFilterRemovals::<T>::insert(caller.did(), &filter, T::Moment::from(0u32));
+    }: _(caller.origin, filter.clone())
+    verify {
+        assert!(!Module::<T>::has_call_filter(caller.did(), filter));
+    }
+
+    publish_agreement {
+        let caller = user::<T>("caller", 0);
+    }: _(caller.origin, H256::repeat_byte(1))
+    verify {
+        assert!(Module::<T>::agreement(caller.did(), AgreementId(1)).is_some());
+    }
+
+    update_agreement {
+        let caller = user::<T>("caller", 0);
+        Module::<T>::publish_agreement(caller.origin().into(), H256::repeat_byte(1)).unwrap();
+    }: _(caller.origin, AgreementId(1), H256::repeat_byte(2))
+
+    give_consent {
+        let publisher = user::<T>("publisher", 0);
+        let caller = user::<T>("caller", 0);
+        Module::<T>::publish_agreement(publisher.origin().into(), H256::repeat_byte(1)).unwrap();
+    }: _(caller.origin, publisher.did(), AgreementId(1), 1)
+    verify {
+        assert!(Module::<T>::has_consented(caller.did(), publisher.did(), AgreementId(1)));
+    }
+
+    revoke_consent {
+        let publisher = user::<T>("publisher", 0);
+        let caller = user::<T>("caller", 0);
+        Module::<T>::publish_agreement(publisher.origin().into(), H256::repeat_byte(1)).unwrap();
+        Module::<T>::give_consent(caller.origin().into(), publisher.did(), AgreementId(1), 1).unwrap();
+    }: _(caller.origin, publisher.did(), AgreementId(1))
+    verify {
+        assert!(!Module::<T>::has_consented(caller.did(), publisher.did(), AgreementId(1)));
+    }
+
+    approve_parent_identity {
+        let parent = user::<T>("parent", 0);
+        let child = user::<T>("child", 0);
+    }: _(child.origin, Some(parent.did()))
+    verify {
+        assert_eq!(Module::<T>::parent_identity_approval(child.did()), Some(parent.did()));
+    }
+
+    link_child_identity {
+        let parent = user::<T>("parent", 0);
+        let child = user::<T>("child", 0);
+        Module::<T>::approve_parent_identity(child.origin().into(), Some(parent.did())).unwrap();
+    }: _(parent.origin, child.did(), true)
+    verify {
+        assert_eq!(Module::<T>::parent_did(child.did()), Some(parent.did()));
+    }
+
+    create_child_identity {
+        let parent = user::<T>("parent", 0);
+        let key: T::AccountId = account("key", SEED, SEED);
+        Module::<T>::unsafe_join_identity(parent.did(), Permissions::default(), key.clone());
+    }: _(parent.origin, key.clone(), true)
+    verify {
+        let child = Module::<T>::get_identity(&key).unwrap();
+        assert_eq!(Module::<T>::parent_did(child), Some(parent.did()));
+    }
+
+    unlink_child_identity {
+        let (parent, child) = setup_child_identity::<T>();
+    }: _(parent.origin, child.did())
+    verify {
+        assert_eq!(Module::<T>::parent_did(child.did()), None);
+    }
+
+    set_child_secondary_key_permissions {
+        let (parent, child) = setup_child_identity::<T>();
+        let key: T::AccountId = account("key", SEED, SEED);
+        Module::<T>::unsafe_join_identity(child.did(), Permissions::empty(), key.clone());
+    }: _(parent.origin, child.did(), key, Permissions::default())
+
+    remove_child_secondary_keys {
+        let i in 0 .. MAX_SECONDARY_KEYS;
+
+        let (parent, child) = setup_child_identity::<T>();
+        let mut signatories = Vec::with_capacity(i as usize);
+        for x in 0..i {
+            let key: T::AccountId = account("key", x, SEED);
+            signatories.push(key.clone());
+            Module::<T>::unsafe_join_identity(child.did(), Permissions::default(), key);
+        }
+    }: _(parent.origin, child.did(), signatories)
+
+    set_child_secondary_keys_frozen {
+        let (parent, child) = setup_child_identity::<T>();
+    }: _(parent.origin, child.did(), true)
+    verify {
+        assert!(Module::<T>::is_did_frozen(child.did()));
+    }
+
     register_custom_claim_type {
         let n in 1 .. T::MaxLen::get() as u32;
 
@@ -431,4 +767,112 @@ benchmarks! {
         assert_ne!(id, Module::<T>::custom_claim_id_seq());
     }
 
+    register_custom_claim_type_with_schema {
+        let n in 1 .. T::MaxLen::get() as u32;
+        let f in 0 .. MAX_CUSTOM_CLAIM_SCHEMA_FIELDS as u32;
+
+        let caller = user::<T>("caller", 0);
+        let ty = vec![b'X'; n as usize];
+        let fields = (0..f)
+            .map(|i| CustomClaimField {
+                name: i.to_le_bytes().to_vec(),
+                field_type: CustomClaimFieldType::Text(T::MaxLen::get()),
+            })
+            .collect();
+    }: _(caller.origin, ty, CustomClaimSchema { fields })
+    verify {
+        let id = Module::<T>::custom_claim_id_seq();
+        assert_eq!(Module::<T>::custom_claim_schema(id).unwrap().fields.len(), f as usize);
+    }
+
+    remove_expired_authorizations {
+        let a in 0 .. MAX_AUTHORIZATIONS_PER_BATCH;
+
+        let caller = user::<T>("caller", 0);
+        let target = user::<T>("target", 0);
+        let signatory = Signatory::Identity(target.did());
+        for _ in 0..a {
+            Module::<T>::add_auth(
+                caller.did(),
+                signatory.clone(),
+                AuthorizationData::JoinIdentity(Permissions::default()),
+                Some(0u32.into()),
+            );
+        }
+    }: _(caller.origin, signatory.clone(), None, a)
+    verify {
+        assert_eq!(<Authorizations<T>>::iter_prefix_values(signatory).count(), 0);
+    }
+
+    consume_authorizations_batch {
+        let a in 0 .. MAX_AUTHORIZATIONS_PER_BATCH;
+
+        let caller = user::<T>("caller", 0);
+        let auth_ids: Vec<_> = (0..a)
+            .map(|i| {
+                let owner = user::<T>("owner", i);
+                Module::<T>::add_auth(
+                    owner.did(),
+                    Signatory::Identity(caller.did()),
+                    AuthorizationData::PortfolioCustody(PortfolioId::default_portfolio(owner.did())),
+                    None,
+                )
+            })
+            .collect();
+    }: _(caller.origin, auth_ids)
+    verify {
+        assert_eq!(<Authorizations<T>>::iter_prefix_values(Signatory::Identity(caller.did())).count(), 0);
+    }
+
+    delegate_claim_issuance {
+        let c in 0 .. MAX_DELEGATED_CLAIM_TYPES;
+
+        let caller = user::<T>("caller", 0);
+        let delegate = user::<T>("delegate", 0);
+        let claim_types: Vec<_> = (0..c)
+            .map(|i| ClaimType::Custom(CustomClaimTypeId(i)))
+            .collect();
+    }: _(caller.origin, delegate.did(), claim_types)
+    verify {
+        assert_eq!(
+            Module::<T>::claim_issuance_delegation(caller.did(), delegate.did()).len(),
+            c as usize
+        );
+    }
+
+    start_claim_import {
+        let caller = user::<T>("caller", 0);
+        let claim = Claim::Jurisdiction(CountryCode::BB, Scope::Identity(caller.did()));
+    }: _(caller.origin, claim, Some(666u32.into()), MAX_CLAIMS_PER_BATCH as u32, H256::zero())
+
+    import_claim_batch {
+        let c in 0 .. MAX_CLAIMS_PER_BATCH as u32;
+
+        let caller = user::<T>("caller", 0);
+        let claim = Claim::Jurisdiction(CountryCode::BB, Scope::Identity(caller.did()));
+        let targets: Vec<_> = (0..c).map(|i| user::<T>("target", i).did()).collect();
+        let mut import = BatchImport::new(c, H256::zero());
+        targets.iter().for_each(|target| import.add_entry(target));
+        Module::<T>::start_claim_import(caller.origin.clone().into(), claim.clone(), None, c, import.running_hash)?;
+    }: _(caller.origin, claim, targets, import.running_hash)
+
+    finalize_claim_import {
+        let caller = user::<T>("caller", 0);
+        let claim = Claim::Jurisdiction(CountryCode::BB, Scope::Identity(caller.did()));
+        let targets: Vec<_> = (0..MAX_CLAIMS_PER_BATCH as u32).map(|i| user::<T>("target", i).did()).collect();
+        let mut import = BatchImport::new(targets.len() as u32, H256::zero());
+        targets.iter().for_each(|target| import.add_entry(target));
+        Module::<T>::start_claim_import(caller.origin.clone().into(), claim.clone(), None, import.total_entries, import.running_hash)?;
+        Module::<T>::import_claim_batch(caller.origin.clone().into(), claim.clone(), targets, import.running_hash)?;
+    }: _(caller.origin, claim.clone())
+    verify {
+        assert!(Module::<T>::claim_import(caller.did(), claim).is_none());
+    }
+
+    abort_claim_import {
+        let caller = user::<T>("caller", 0);
+        let claim = Claim::Jurisdiction(CountryCode::BB, Scope::Identity(caller.did()));
+        Module::<T>::start_claim_import(caller.origin.clone().into(), claim.clone(), None, 1, H256::zero())?;
+    }: _(caller.origin, claim)
+
 }
@@ -0,0 +1,149 @@
+// This file is part of the Polymesh distribution (https://github.com/PolymeshAssociation/Polymesh).
+// Copyright (c) 2020 Polymath
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, version 3.
+
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+// General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+use crate::keys::MAX_NAME_LEN;
+use crate::{CallFilterRemovals, CallFilters, Config, Error, Module, RawEvent};
+use core::marker::PhantomData;
+use frame_support::dispatch::DispatchResult;
+use frame_support::traits::{Contains, Get, GetCallMetadata, PalletInfo};
+use frame_support::{ensure, IterableStorageDoubleMap, StorageDoubleMap};
+use pallet_base::ensure_custom_string_limited;
+use polymesh_common_utilities::Context;
+use polymesh_primitives::{CallFilter, DispatchableName, IdentityId, PalletName};
+use sp_runtime::traits::Saturating;
+
+/// Calls of this pallet that can't be filtered, so that an identity can always lift its filters.
+const UNFILTERABLE_CALLS: &[&[u8]] = &[b"request_call_filter_removal", b"remove_call_filter"];
+
+impl<T: Config> Module<T> {
+    /// Restricts the caller's identity from making the calls matched by `filter`.
+    pub(crate) fn base_add_call_filter(
+        origin: T::RuntimeOrigin,
+        filter: CallFilter,
+    ) -> DispatchResult {
+        let (_, did) = Self::ensure_primary_key(origin)?;
+        ensure_custom_string_limited::<T>(&filter.pallet_name, MAX_NAME_LEN)?;
+        if let Some(dispatchable_name) = &filter.dispatchable_name {
+            ensure_custom_string_limited::<T>(dispatchable_name, MAX_NAME_LEN)?;
+        }
+        ensure!(
+            !CallFilters::contains_key(did, &filter),
+            Error::<T>::CallFilterAlreadyAdded
+        );
+
+        CallFilters::insert(did, &filter, true);
+        Self::deposit_event(RawEvent::CallFilterAdded(did, filter));
+        Ok(())
+    }
+
+    /// Starts the delay after which `filter` can be removed from the caller's identity.
+    pub(crate) fn base_request_call_filter_removal(
+        origin: T::RuntimeOrigin,
+        filter: CallFilter,
+    ) -> DispatchResult {
+        let (_, did) = Self::ensure_primary_key(origin)?;
+        ensure!(
+            CallFilters::contains_key(did, &filter),
+            Error::<T>::CallFilterNotFound
+        );
+        ensure!(
+            !CallFilterRemovals::<T>::contains_key(did, &filter),
+            Error::<T>::CallFilterRemovalAlreadyRequested
+        );
+
+        let now = <pallet_timestamp::Pallet<T>>::get();
+        let removable_at = now.saturating_add(T::CallFilterRemovalDelay::get());
+        CallFilterRemovals::<T>::insert(did, &filter, removable_at);
+        Self::deposit_event(RawEvent::CallFilterRemovalRequested(
+            did,
+            filter,
+            removable_at,
+        ));
+        Ok(())
+    }
+
+    /// Removes `filter` from the caller's identity, once its removal delay is over.
+    pub(crate) fn base_remove_call_filter(
+        origin: T::RuntimeOrigin,
+        filter: CallFilter,
+    ) -> DispatchResult {
+        let (_, did) = Self::ensure_primary_key(origin)?;
+        let removable_at = Self::call_filter_removal(did, &filter)
+            .ok_or(Error::<T>::CallFilterRemovalNotRequested)?;
+        let now = <pallet_timestamp::Pallet<T>>::get();
+        ensure!(
+            now >= removable_at,
+            Error::<T>::CallFilterRemovalDelayNotOver
+        );
+
+        CallFilters::remove(did, &filter);
+        CallFilterRemovals::<T>::remove(did, &filter);
+        Self::deposit_event(RawEvent::CallFilterRemoved(did, filter));
+        Ok(())
+    }
+
+    /// Returns `true` if `did` has restricted itself from calling the extrinsic represented by
+    /// `pallet_name` and `function_name`.
+    pub fn is_call_filtered(
+        did: IdentityId,
+        pallet_name: &PalletName,
+        function_name: &DispatchableName,
+    ) -> bool {
+        if !Self::has_call_filters(did) || Self::is_unfilterable_call(pallet_name, function_name) {
+            return false;
+        }
+        let filter = |dispatchable_name| CallFilter {
+            pallet_name: pallet_name.clone(),
+            dispatchable_name,
+        };
+        CallFilters::contains_key(did, filter(None))
+            || CallFilters::contains_key(did, filter(Some(function_name.clone())))
+    }
+
+    /// Returns `true` if `did` has any call filter.
+    pub(crate) fn has_call_filters(did: IdentityId) -> bool {
+        CallFilters::iter_prefix(did).next().is_some()
+    }
+
+    fn is_unfilterable_call(pallet_name: &PalletName, function_name: &DispatchableName) -> bool {
+        T::PalletInfo::name::<Self>().map_or(false, |name| name.as_bytes() == &**pallet_name)
+            && UNFILTERABLE_CALLS.contains(&&**function_name)
+    }
+}
+
+/// Rejects the calls matched by the call filters of the identity in the current context.
+///
+/// Meant to be the `BaseCallFilter` of the runtime, so that the calls nested in batches
+/// and in multisig proposals are filtered as well as the calls of transactions.
+/// Its storage read is included in the base weight of extrinsics.
+pub struct IdentityCallFilter<T>(PhantomData<T>);
+
+impl<T: Config> Contains<<T as frame_system::Config>::RuntimeCall> for IdentityCallFilter<T>
+where
+    <T as frame_system::Config>::RuntimeCall: GetCallMetadata,
+{
+    fn contains(call: &<T as frame_system::Config>::RuntimeCall) -> bool {
+        let did = match Context::current_identity::<Module<T>>() {
+            Some(did) if Module::<T>::has_call_filters(did) => did,
+            _ => return true,
+        };
+        let metadata = call.get_call_metadata();
+        !Module::<T>::is_call_filtered(
+            did,
+            &metadata.pallet_name.as_bytes().into(),
+            &metadata.function_name.as_bytes().into(),
+        )
+    }
+}
@@ -0,0 +1,169 @@
+// This file is part of the Polymesh distribution (https://github.com/PolymeshAssociation/Polymesh).
+// Copyright (c) 2020 Polymath
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, version 3.
+
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+// General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+use crate::{ChildIdentities, Config, Error, Module, ParentDid, ParentIdentityApprovals, RawEvent};
+use frame_support::dispatch::DispatchResult;
+use frame_support::{ensure, IterableStorageDoubleMap, StorageDoubleMap, StorageMap};
+use polymesh_primitives::{ClaimType, IdentityId, Permissions};
+use sp_runtime::DispatchError;
+use sp_std::{vec, vec::Vec};
+
+impl<T: Config> Module<T> {
+    /// Sets or removes the identity allowed to link the caller's identity as its child.
+    pub(crate) fn base_approve_parent_identity(
+        origin: T::RuntimeOrigin,
+        parent: Option<IdentityId>,
+    ) -> DispatchResult {
+        let (_, did) = Self::ensure_primary_key(origin)?;
+        if let Some(parent) = parent {
+            ensure!(parent != did, Error::<T>::InvalidParentIdentity);
+            Self::ensure_id_record_exists(parent)?;
+        }
+        ParentIdentityApprovals::mutate(did, |approval| *approval = parent);
+        Self::deposit_event(RawEvent::ParentIdentityApproved(did, parent));
+        Ok(())
+    }
+
+    /// Links `child`, which approved the caller's identity as its parent, as a child of the caller's identity.
+    pub(crate) fn base_link_child_identity(
+        origin: T::RuntimeOrigin,
+        child: IdentityId,
+        inherit_claims: bool,
+    ) -> DispatchResult {
+        let (_, parent) = Self::ensure_primary_key(origin)?;
+        ensure!(
+            Self::parent_identity_approval(child) == Some(parent),
+            Error::<T>::ParentIdentityNotApproved
+        );
+        ensure!(
+            !ParentDid::contains_key(child),
+            Error::<T>::ChildIdentityAlreadyLinked
+        );
+        ensure!(
+            ChildIdentities::iter_prefix(child).next().is_none(),
+            Error::<T>::NestedChildIdentity
+        );
+        Self::ensure_not_child_identity(parent)?;
+
+        ParentIdentityApprovals::remove(child);
+        Self::unsafe_link_child_identity(parent, child, inherit_claims);
+        Ok(())
+    }
+
+    /// Creates a child identity of the caller's identity, whose primary key is `key`,
+    /// a secondary key of the caller's identity.
+    pub(crate) fn base_create_child_identity(
+        origin: T::RuntimeOrigin,
+        key: T::AccountId,
+        inherit_claims: bool,
+    ) -> DispatchResult {
+        let (_, parent) = Self::ensure_primary_key(origin)?;
+        Self::ensure_not_child_identity(parent)?;
+        Self::ensure_secondary_key(parent, &key)?;
+        Self::ensure_key_unlinkable_from_did(&key)?;
+
+        // The secondary key leaves the parent to become the primary key of the child.
+        Self::remove_key_record(&key, Some(parent));
+        Self::deposit_event(RawEvent::SecondaryKeysRemoved(parent, vec![key.clone()]));
+        let child = Self::_register_did(key, Vec::new(), None)?;
+        Self::unsafe_link_child_identity(parent, child, inherit_claims);
+        Ok(())
+    }
+
+    /// Unlinks `child` from its parent, on behalf of either of them.
+    pub(crate) fn base_unlink_child_identity(
+        origin: T::RuntimeOrigin,
+        child: IdentityId,
+    ) -> DispatchResult {
+        let (_, did) = Self::ensure_primary_key(origin)?;
+        let parent = Self::parent_did(child).ok_or(Error::<T>::NotParentIdentity)?;
+        ensure!(did == parent || did == child, Error::<T>::NotParentIdentity);
+
+        ParentDid::remove(child);
+        ChildIdentities::remove(parent, child);
+        Self::deposit_event(RawEvent::ChildIdentityUnlinked(did, parent, child));
+        Ok(())
+    }
+
+    /// Sets the permissions of `key`, a secondary key of `child`, on behalf of its parent.
+    pub(crate) fn base_set_child_secondary_key_permissions(
+        origin: T::RuntimeOrigin,
+        child: IdentityId,
+        key: T::AccountId,
+        permissions: Permissions,
+    ) -> DispatchResult {
+        Self::ensure_parent_of(origin, child)?;
+        Self::set_secondary_key_permissions_of(child, key, permissions)
+    }
+
+    /// Removes secondary keys of `child` on behalf of its parent.
+    pub(crate) fn base_remove_child_secondary_keys(
+        origin: T::RuntimeOrigin,
+        child: IdentityId,
+        keys: Vec<T::AccountId>,
+    ) -> DispatchResult {
+        Self::ensure_parent_of(origin, child)?;
+        Self::unsafe_remove_secondary_keys(child, keys)
+    }
+
+    /// Freezes/unfreezes the secondary keys of `child` on behalf of its parent.
+    pub(crate) fn base_set_child_secondary_keys_frozen(
+        origin: T::RuntimeOrigin,
+        child: IdentityId,
+        freeze: bool,
+    ) -> DispatchResult {
+        Self::ensure_parent_of(origin, child)?;
+        Self::unsafe_set_frozen_secondary_keys(child, freeze);
+        Ok(())
+    }
+
+    /// Returns the parent of `did` if `did` inherits its `claim_type` claims.
+    ///
+    /// CDD claims are never inherited: each child identity must be verified by a CDD provider itself.
+    pub(crate) fn claims_parent(did: IdentityId, claim_type: ClaimType) -> Option<IdentityId> {
+        if claim_type == ClaimType::CustomerDueDiligence {
+            return None;
+        }
+        Self::parent_did(did).filter(|parent| ChildIdentities::get(parent, did))
+    }
+
+    fn unsafe_link_child_identity(parent: IdentityId, child: IdentityId, inherit_claims: bool) {
+        ParentDid::insert(child, parent);
+        ChildIdentities::insert(parent, child, inherit_claims);
+        Self::deposit_event(RawEvent::ChildIdentityLinked(parent, child, inherit_claims));
+    }
+
+    /// Ensures that `origin` is the primary key of the parent of `child`, and returns the parent.
+    fn ensure_parent_of(
+        origin: T::RuntimeOrigin,
+        child: IdentityId,
+    ) -> Result<IdentityId, DispatchError> {
+        let (_, did) = Self::ensure_primary_key(origin)?;
+        ensure!(
+            Self::parent_did(child) == Some(did),
+            Error::<T>::NotParentIdentity
+        );
+        Ok(did)
+    }
+
+    /// Child identities can't have children themselves.
+    fn ensure_not_child_identity(did: IdentityId) -> DispatchResult {
+        ensure!(
+            !ParentDid::contains_key(did),
+            Error::<T>::NestedChildIdentity
+        );
+        Ok(())
+    }
+}
@@ -14,33 +14,61 @@
 // along with this program. If not, see <http://www.gnu.org/licenses/>.
 
 use crate::{
-    Claim1stKey, Claim2ndKey, Claims, CustomClaimIdSequence, CustomClaims, CustomClaimsInverse,
-    DidRecords, Error, Event, Module,
+    Claim1stKey, Claim2ndKey, ClaimCount, ClaimImports, ClaimIssuanceDelegateCount,
+    ClaimIssuanceDelegations, Claims, ClaimsIssued, CustomClaimIdSequence, CustomClaimSchemas,
+    CustomClaims, CustomClaimsInverse, DefaultClaimIssuanceLimit, DidRecords, Error, Event,
+    IssuerClaimIssuanceLimits, Module,
 };
+use codec::Encode;
 use core::convert::From;
 use frame_support::{
     dispatch::{DispatchError, DispatchResult},
-    ensure, fail, StorageDoubleMap, StorageMap, StorageValue,
+    ensure, fail,
+    traits::{
+        schedule::{DispatchTime, Named as ScheduleNamed, LOWEST_PRIORITY},
+        Get,
+    },
+    IterableStorageDoubleMap, StorageDoubleMap, StorageMap, StorageValue,
+};
+use frame_system::{ensure_root, RawOrigin};
+use pallet_base::{
+    ensure_custom_length_ok, ensure_length_ok, ensure_string_limited, try_next_pre, try_use_entry,
 };
-use frame_system::ensure_root;
-use pallet_base::{ensure_string_limited, try_next_pre};
 pub use polymesh_common_utilities::traits::identity::WeightInfo;
 use polymesh_common_utilities::{
-    protocol_fee::ProtocolOp,
+    constants::schedule_name_prefix::IDENTITY_CLAIM_REVOCATION,
+    protocol_fee::{ChargeProtocolFee as _, ProtocolOp},
     traits::{
         asset::AssetSubTrait,
         group::{GroupTrait, InactiveMember},
-        identity::{Config, RawEvent},
+        identity::{Config, IdentitySchedulerCalls, RawEvent},
     },
     SystematicIssuers, SYSTEMATIC_ISSUERS,
 };
-use polymesh_primitives::identity_claim::CustomClaimTypeId;
+use polymesh_primitives::identity_claim::{
+    CustomClaimSchema, CustomClaimTypeId, MAX_CUSTOM_CLAIM_SCHEMA_FIELDS,
+};
 use polymesh_primitives::{
-    investor_zkproof_data::InvestorZKProofData as InvestorZKProof, valid_proof_of_investor, CddId,
-    Claim, ClaimType, IdentityClaim, IdentityId, InvestorUid, Scope, ScopeId, SecondaryKey, Ticker,
+    investor_zkproof_data::InvestorZKProofData as InvestorZKProof, valid_proof_of_investor,
+    BatchImport, CddId, Claim, ClaimType, IdentityClaim, IdentityId, InvestorUid, Scope, ScopeId,
+    SecondaryKey, Ticker,
 };
+use sp_core::H256;
 use sp_runtime::traits::{CheckedAdd, SaturatedConversion, Zero};
-use sp_std::prelude::*;
+use sp_std::{
+    collections::{btree_map::BTreeMap, btree_set::BTreeSet},
+    prelude::*,
+};
+
+/// Maximum number of claims that can be added by a single `add_claims_batch` call.
+pub const MAX_CLAIMS_PER_BATCH: usize = 100;
+
+/// Maximum number of claim types whose issuance can be delegated to a single identity.
+pub const MAX_DELEGATED_CLAIM_TYPES: u32 = 32;
+
+/// Maximum number of identities an identity can delegate the issuance of claims to.
+/// It bounds the delegations read by `fetch_claim_issued_under` for each trusted issuer.
+pub const MAX_CLAIM_ISSUANCE_DELEGATES: u32 = 4;
 
 impl<T: Config> Module<T> {
     /// Ensure that any `Scope::Custom(data)` is limited to 32 characters.
@@ -64,11 +92,43 @@ impl<T: Config> Module<T> {
     /// It fetches an specific `claim_type` claim type for target identity `id`, which was issued
     /// by `issuer`.
     /// It only returns non-expired claims.
+    /// Child identities inheriting claims fall back on the claims of their parent, except for CDD claims.
     pub fn fetch_claim(
         id: IdentityId,
         claim_type: ClaimType,
         issuer: IdentityId,
         scope: Option<Scope>,
+    ) -> Option<IdentityClaim> {
+        Self::fetch_own_claim(id, claim_type, issuer, scope.clone()).or_else(|| {
+            Self::claims_parent(id, claim_type)
+                .and_then(|parent| Self::fetch_own_claim(parent, claim_type, issuer, scope))
+        })
+    }
+
+    /// Same as `fetch_claim`, but if `issuer` hasn't issued such a claim, falls back on the claims
+    /// issued by the identities that `issuer` delegated the issuance of `claim_type` claims to.
+    /// At most `MAX_CLAIM_ISSUANCE_DELEGATES` delegations are read.
+    pub fn fetch_claim_issued_under(
+        id: IdentityId,
+        claim_type: ClaimType,
+        issuer: IdentityId,
+        scope: Option<Scope>,
+    ) -> Option<IdentityClaim> {
+        Self::fetch_claim(id, claim_type, issuer, scope.clone()).or_else(|| {
+            ClaimIssuanceDelegations::iter_prefix(issuer)
+                .filter(|(_, claim_types)| claim_types.contains(&claim_type))
+                .find_map(|(delegate, _)| {
+                    Self::fetch_claim(id, claim_type, delegate, scope.clone())
+                })
+        })
+    }
+
+    /// Same as `fetch_claim`, ignoring the claims inherited from a parent identity.
+    fn fetch_own_claim(
+        id: IdentityId,
+        claim_type: ClaimType,
+        issuer: IdentityId,
+        scope: Option<Scope>,
     ) -> Option<IdentityClaim> {
         let now = <pallet_timestamp::Pallet<T>>::get();
 
@@ -180,13 +240,39 @@ impl<T: Config> Module<T> {
                     }))
     }
 
-    /// It iterates over all claims of type `claim_type` for target `id` identity.
+    /// Returns the latest non-expired `RiskScore` claim of `target` issued by each scoring provider,
+    /// ordered by provider.
+    pub fn latest_risk_scores(target: IdentityId) -> Vec<IdentityClaim> {
+        let now = <pallet_timestamp::Pallet<T>>::get();
+        let mut latest: BTreeMap<IdentityId, IdentityClaim> = BTreeMap::new();
+        for id_claim in Self::fetch_base_claims(target, ClaimType::RiskScore)
+            .filter(|c| Self::is_identity_claim_not_expired_at(c, now))
+        {
+            match latest.get(&id_claim.claim_issuer) {
+                Some(prev) if prev.last_update_date >= id_claim.last_update_date => {}
+                _ => {
+                    latest.insert(id_claim.claim_issuer, id_claim);
+                }
+            }
+        }
+        latest.into_values().collect()
+    }
+
+    /// It iterates over all claims of type `claim_type` for target `id` identity,
+    /// followed by the claims inherited from its parent identity, if any.
     /// Please note that it could return expired claims.
     fn fetch_base_claims<'a>(
         target: IdentityId,
         claim_type: ClaimType,
     ) -> impl Iterator<Item = IdentityClaim> + 'a {
+        let inherited = Self::claims_parent(target, claim_type).map(|parent| {
+            Claims::iter_prefix_values(Claim1stKey {
+                target: parent,
+                claim_type,
+            })
+        });
         Claims::iter_prefix_values(Claim1stKey { target, claim_type })
+            .chain(inherited.into_iter().flatten())
     }
 
     /// It fetches an specific `claim_type` claim type for target identity `id`, which was issued
@@ -210,11 +296,15 @@ impl<T: Config> Module<T> {
         expiry: Option<T::Moment>,
     ) -> DispatchResult {
         let inner_scope = claim.as_scope().cloned();
-        if let ClaimType::Custom(id) = claim.claim_type() {
-            ensure!(
-                CustomClaims::contains_key(id),
-                Error::<T>::CustomClaimTypeDoesNotExist
-            );
+        match &claim {
+            Claim::Custom(id, _) => Self::ensure_custom_claim_payload(id, None)?,
+            Claim::CustomWithPayload(id, _, payload) => {
+                Self::ensure_custom_claim_payload(id, Some(payload.as_slice()))?
+            }
+            Claim::RiskScore(risk_score, _) => {
+                ensure!(risk_score.is_valid(), Error::<T>::InvalidRiskScore)
+            }
+            _ => {}
         }
         Self::unverified_add_claim_with_scope(target, claim, inner_scope, issuer, expiry);
         Ok(())
@@ -230,7 +320,7 @@ impl<T: Config> Module<T> {
     ) {
         let claim_type = claim.claim_type();
         let last_update_date = <pallet_timestamp::Pallet<T>>::get().saturated_into::<u64>();
-        let issuance_date = Self::fetch_claim(target, claim_type, issuer, scope.clone())
+        let issuance_date = Self::fetch_own_claim(target, claim_type, issuer, scope.clone())
             .map_or(last_update_date, |id_claim| id_claim.issuance_date);
 
         let expiry = expiry.map(|m| m.saturated_into::<u64>());
@@ -243,6 +333,9 @@ impl<T: Config> Module<T> {
             claim,
         };
 
+        if !Claims::contains_key(&pk, &sk) {
+            ClaimCount::mutate(|count| *count = count.saturating_add(1));
+        }
         Claims::insert(&pk, &sk, id_claim.clone());
         Self::deposit_event(RawEvent::ClaimAdded(target, id_claim));
     }
@@ -276,6 +369,186 @@ impl<T: Config> Module<T> {
         Self::base_add_claim(target, claim, issuer, expiry)
     }
 
+    /// Adds all `claims` issued by the caller's identity, charging the protocol fee
+    /// of the non-CDD claims at once.
+    pub(crate) fn base_add_claims_batch(
+        origin: T::RuntimeOrigin,
+        claims: Vec<(IdentityId, Claim, Option<T::Moment>)>,
+    ) -> DispatchResult {
+        ensure!(
+            claims.len() <= MAX_CLAIMS_PER_BATCH,
+            Error::<T>::TooManyClaims
+        );
+        let issuer = Self::ensure_perms(origin)?;
+
+        // Validate all claims before charging the fee.
+        let mut fee_count = 0;
+        for (target, claim, _) in &claims {
+            ensure!(
+                DidRecords::<T>::contains_key(target),
+                Error::<T>::DidMustAlreadyExist
+            );
+            match claim {
+                Claim::CustomerDueDiligence(..) => {}
+                Claim::InvestorUniqueness(..) | Claim::InvestorUniquenessV2(..) => {
+                    fail!(Error::<T>::ClaimVariantNotAllowed)
+                }
+                _ => {
+                    Self::ensure_custom_scopes_limited(claim)?;
+                    fee_count += 1;
+                }
+            }
+            Self::ensure_claim_issuance_allowed(issuer)?;
+        }
+        T::ProtocolFee::batch_charge_fee(ProtocolOp::IdentityAddClaim, fee_count)?;
+
+        for (target, claim, expiry) in claims {
+            match claim {
+                Claim::CustomerDueDiligence(..) => {
+                    Self::base_add_cdd_claim(target, claim, issuer, expiry)?
+                }
+                _ => Self::base_add_claim(target, claim, issuer, expiry)?,
+            }
+        }
+        Ok(())
+    }
+
+    /// Starts the import of `claim`, issued by the caller's identity, to `total_entries` targets.
+    pub(crate) fn base_start_claim_import(
+        origin: T::RuntimeOrigin,
+        claim: Claim,
+        expiry: Option<T::Moment>,
+        total_entries: u32,
+        expected_hash: H256,
+    ) -> DispatchResult {
+        let issuer = Self::ensure_perms(origin)?;
+        ensure!(
+            !ClaimImports::<T>::contains_key(issuer, &claim),
+            Error::<T>::ClaimImportInProgress
+        );
+        match &claim {
+            Claim::CustomerDueDiligence(..) => Self::ensure_authorized_cdd_provider(issuer)?,
+            Claim::InvestorUniqueness(..) | Claim::InvestorUniquenessV2(..) => {
+                fail!(Error::<T>::ClaimVariantNotAllowed)
+            }
+            _ => Self::ensure_custom_scopes_limited(&claim)?,
+        }
+
+        ClaimImports::<T>::insert(
+            issuer,
+            &claim,
+            (expiry, BatchImport::new(total_entries, expected_hash)),
+        );
+        Self::deposit_event(RawEvent::ClaimImportStarted(
+            issuer,
+            claim,
+            total_entries,
+            expected_hash,
+        ));
+        Ok(())
+    }
+
+    /// Adds the claim of the caller's import of `claim` to `targets`, charging the protocol fee
+    /// of the non-CDD claims at once.
+    pub(crate) fn base_import_claim_batch(
+        origin: T::RuntimeOrigin,
+        claim: Claim,
+        targets: Vec<IdentityId>,
+        batch_hash: H256,
+    ) -> DispatchResult {
+        ensure!(
+            targets.len() <= MAX_CLAIMS_PER_BATCH,
+            Error::<T>::TooManyClaims
+        );
+        let issuer = Self::ensure_perms(origin)?;
+        let (expiry, mut import) =
+            ClaimImports::<T>::get(issuer, &claim).ok_or(Error::<T>::ClaimImportNotFound)?;
+        ensure!(
+            (import.imported_entries as usize).saturating_add(targets.len())
+                <= import.total_entries as usize,
+            Error::<T>::ClaimImportOverflow
+        );
+
+        // Verify the batch before adding any of its claims.
+        targets.iter().for_each(|target| import.add_entry(target));
+        ensure!(
+            import.running_hash == batch_hash,
+            Error::<T>::ClaimBatchHashMismatch
+        );
+        for target in &targets {
+            ensure!(
+                DidRecords::<T>::contains_key(target),
+                Error::<T>::DidMustAlreadyExist
+            );
+            Self::ensure_claim_issuance_allowed(issuer)?;
+        }
+
+        // Commit changes to storage.
+        let is_cdd = matches!(claim, Claim::CustomerDueDiligence(..));
+        if !is_cdd {
+            T::ProtocolFee::batch_charge_fee(ProtocolOp::IdentityAddClaim, targets.len())?;
+        }
+        for target in targets {
+            if is_cdd {
+                Self::base_add_cdd_claim(target, claim.clone(), issuer, expiry)?;
+            } else {
+                Self::base_add_claim(target, claim.clone(), issuer, expiry)?;
+            }
+        }
+        let imported_entries = import.imported_entries;
+        let total_entries = import.total_entries;
+        ClaimImports::<T>::insert(issuer, &claim, (expiry, import));
+
+        Self::deposit_event(RawEvent::ClaimBatchImported(
+            issuer,
+            claim,
+            imported_entries,
+            total_entries,
+        ));
+        Ok(())
+    }
+
+    /// Completes the caller's import of `claim`, after verifying its integrity.
+    pub(crate) fn base_finalize_claim_import(
+        origin: T::RuntimeOrigin,
+        claim: Claim,
+    ) -> DispatchResult {
+        let issuer = Self::ensure_perms(origin)?;
+        let (_, import) =
+            ClaimImports::<T>::get(issuer, &claim).ok_or(Error::<T>::ClaimImportNotFound)?;
+        ensure!(
+            import.imported_entries == import.total_entries,
+            Error::<T>::ClaimImportIncomplete
+        );
+        ensure!(import.is_complete(), Error::<T>::ClaimImportHashMismatch);
+
+        ClaimImports::<T>::remove(issuer, &claim);
+        Self::deposit_event(RawEvent::ClaimImportFinalized(
+            issuer,
+            claim,
+            import.total_entries,
+            import.running_hash,
+        ));
+        Ok(())
+    }
+
+    /// Aborts the caller's import of `claim`. Claims added by previous batches are kept.
+    pub(crate) fn base_abort_claim_import(
+        origin: T::RuntimeOrigin,
+        claim: Claim,
+    ) -> DispatchResult {
+        let issuer = Self::ensure_perms(origin)?;
+        let (_, import) =
+            ClaimImports::<T>::take(issuer, &claim).ok_or(Error::<T>::ClaimImportNotFound)?;
+
+        Self::deposit_event(RawEvent::ClaimImportAborted(
+            issuer,
+            claim,
+            import.imported_entries,
+        ));
+        Ok(())
+    }
+
     /// Enforce CDD_ID uniqueness for a given target DID.
     ///
     /// # Errors
@@ -420,6 +693,7 @@ impl<T: Config> Module<T> {
         }
 
         let claim = Claims::take(&pk, &sk);
+        ClaimCount::mutate(|count| *count = count.saturating_sub(1));
         Self::deposit_event(RawEvent::ClaimRevoked(target, claim));
         Ok(())
     }
@@ -437,6 +711,138 @@ impl<T: Config> Module<T> {
         Ok(primary_did)
     }
 
+    /// Makes the claim issued by the caller's identity to `target` expire at `at`, and schedules its removal.
+    pub(crate) fn base_schedule_claim_revocation(
+        origin: T::RuntimeOrigin,
+        target: IdentityId,
+        claim: Claim,
+        at: T::Moment,
+    ) -> DispatchResult {
+        let issuer = Self::ensure_perms(origin)?;
+        ensure!(
+            !matches!(
+                claim,
+                Claim::InvestorUniqueness(..) | Claim::InvestorUniquenessV2(..)
+            ),
+            Error::<T>::ClaimVariantNotAllowed
+        );
+        let (pk, sk) = Self::get_claim_keys(
+            target,
+            claim.claim_type(),
+            issuer,
+            claim.as_scope().cloned(),
+        );
+        let mut id_claim = Claims::try_get(&pk, &sk).map_err(|_| Error::<T>::ClaimDoesNotExist)?;
+
+        let revoked_at = at.saturated_into::<u64>();
+        let now = <pallet_timestamp::Pallet<T>>::get().saturated_into::<u64>();
+        ensure!(
+            revoked_at > now && id_claim.expiry.map_or(true, |expiry| revoked_at < expiry),
+            Error::<T>::InvalidClaimRevocationTime
+        );
+
+        // The claim is no longer valid from `at`, even before it is removed.
+        id_claim.expiry = Some(revoked_at);
+        Claims::insert(&pk, &sk, id_claim.clone());
+        Self::schedule_claim_removal(pk, sk, at)?;
+        Self::deposit_event(RawEvent::ClaimRevocationScheduled(target, id_claim, at));
+        Ok(())
+    }
+
+    /// Schedules the removal of the claim of keys `pk` and `sk`, revoked at `at`,
+    /// in the block in which `at` is expected to be reached.
+    fn schedule_claim_removal(pk: Claim1stKey, sk: Claim2ndKey, at: T::Moment) -> DispatchResult {
+        let now = <pallet_timestamp::Pallet<T>>::get().saturated_into::<u64>();
+        let block_time = T::MinimumPeriod::get()
+            .saturated_into::<u64>()
+            .saturating_mul(2)
+            .max(1);
+        let blocks = at.saturated_into::<u64>().saturating_sub(now) / block_time + 1;
+        let block =
+            <frame_system::Pallet<T>>::block_number().saturating_add(blocks.saturated_into());
+
+        // The block is part of the name, as a removal can be rescheduled by its own execution.
+        let name = (IDENTITY_CLAIM_REVOCATION, &pk, &sk, at, block).encode();
+        let call = T::IdentityScheduledCalls::revoke_scheduled_claim(
+            pk.target,
+            pk.claim_type,
+            sk.issuer,
+            sk.scope,
+            at,
+        );
+        T::IdentityScheduler::schedule_named(
+            name,
+            DispatchTime::At(block),
+            None,
+            LOWEST_PRIORITY,
+            RawOrigin::Root.into(),
+            call,
+        )
+        .map_err(|_| Error::<T>::FailedToScheduleClaimRevocation)?;
+        Ok(())
+    }
+
+    /// Removes the claim whose revocation was scheduled at `at`, unless it was re-issued since.
+    /// The removal is rescheduled if `at` isn't reached yet.
+    pub(crate) fn base_revoke_scheduled_claim(
+        target: IdentityId,
+        claim_type: ClaimType,
+        issuer: IdentityId,
+        scope: Option<Scope>,
+        at: T::Moment,
+    ) {
+        let (pk, sk) = Self::get_claim_keys(target, claim_type, issuer, scope);
+        let id_claim = match Claims::try_get(&pk, &sk) {
+            Ok(id_claim) if id_claim.expiry == Some(at.saturated_into::<u64>()) => id_claim,
+            _ => return,
+        };
+        if <pallet_timestamp::Pallet<T>>::get() < at {
+            // Should the rescheduling fail, the claim still expires at `at`.
+            let _ = Self::schedule_claim_removal(pk, sk, at);
+            return;
+        }
+        Claims::remove(&pk, &sk);
+        ClaimCount::mutate(|count| *count = count.saturating_sub(1));
+        Self::deposit_event(RawEvent::ClaimRevoked(pk.target, id_claim));
+    }
+
+    /// Moves the `claim_type` claims of `target` scoped to `old` to the scope `new`,
+    /// e.g. once the asset `old` refers to has been migrated to another ticker.
+    /// Each claim of `target` of that type uses up one of the `entries`.
+    pub fn migrate_claims_scope(
+        target: IdentityId,
+        claim_type: ClaimType,
+        old: &Scope,
+        new: &Scope,
+        entries: &mut u32,
+    ) -> DispatchResult {
+        let pk = Claim1stKey { target, claim_type };
+        let mut issuers = Vec::new();
+        for sk in Claims::iter_key_prefix(&pk) {
+            try_use_entry::<T>(entries)?;
+            if sk.scope.as_ref() == Some(old) {
+                issuers.push(sk.issuer);
+            }
+        }
+        for issuer in issuers {
+            let scope = Some(old.clone());
+            let mut id_claim = Claims::take(&pk, Claim2ndKey { issuer, scope });
+            if let Some(scope) = id_claim.claim.as_scope_mut() {
+                *scope = new.clone();
+            }
+            let sk = Claim2ndKey {
+                issuer,
+                scope: Some(new.clone()),
+            };
+            // A claim already scoped to `new` is replaced.
+            if Claims::contains_key(&pk, &sk) {
+                ClaimCount::mutate(|count| *count = count.saturating_sub(1));
+            }
+            Claims::insert(&pk, sk, id_claim);
+        }
+        Ok(())
+    }
+
     /// Checks whether the sender and the receiver of a transfer have valid investor uniqueness claims for a given ticker
     pub fn verify_iu_claims_for_transfer(
         ticker: Ticker,
@@ -468,7 +874,7 @@ impl<T: Config> Module<T> {
     }
 
     /// Ensures that the did is an active CDD Provider.
-    fn ensure_authorized_cdd_provider(did: IdentityId) -> DispatchResult {
+    pub(crate) fn ensure_authorized_cdd_provider(did: IdentityId) -> DispatchResult {
         ensure!(
             T::CddServiceProviders::get_members().contains(&did),
             Error::<T>::UnAuthorizedCddProvider
@@ -561,6 +967,113 @@ impl<T: Config> Module<T> {
         Ok(())
     }
 
+    pub fn base_register_custom_claim_type_with_schema(
+        origin: T::RuntimeOrigin,
+        ty: Vec<u8>,
+        schema: CustomClaimSchema,
+    ) -> DispatchResult {
+        let did = Self::ensure_perms(origin)?;
+        ensure_custom_length_ok::<T>(schema.fields.len(), MAX_CUSTOM_CLAIM_SCHEMA_FIELDS)?;
+        for field in &schema.fields {
+            ensure_string_limited::<T>(&field.name)?;
+            if let Some(max_len) = field.field_type.max_len() {
+                ensure_length_ok::<T>(max_len as usize)?;
+            }
+        }
+        let names: BTreeSet<_> = schema.fields.iter().map(|field| &field.name).collect();
+        ensure!(
+            names.len() == schema.fields.len(),
+            Error::<T>::InvalidCustomClaimSchema
+        );
+
+        let id = Self::unsafe_register_custom_claim_type(ty.clone())?;
+        CustomClaimSchemas::insert(id, &schema);
+        Self::deposit_event(Event::<T>::CustomClaimTypeAdded(did, id, ty));
+        Self::deposit_event(Event::<T>::CustomClaimSchemaRegistered(did, id, schema));
+        Ok(())
+    }
+
+    /// Delegates the issuance of `claim_types` claims on behalf of the caller's identity to `delegate`,
+    /// replacing any previous delegation to it. An empty `claim_types` revokes the delegation.
+    pub(crate) fn base_delegate_claim_issuance(
+        origin: T::RuntimeOrigin,
+        delegate: IdentityId,
+        claim_types: Vec<ClaimType>,
+    ) -> DispatchResult {
+        let did = Self::ensure_perms(origin)?;
+        ensure!(did != delegate, Error::<T>::InvalidClaimIssuanceDelegate);
+        Self::ensure_id_record_exists(delegate)?;
+        ensure_custom_length_ok::<T>(claim_types.len(), MAX_DELEGATED_CLAIM_TYPES)?;
+        let claim_types: Vec<_> = claim_types
+            .into_iter()
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+
+        let delegated = ClaimIssuanceDelegations::contains_key(did, delegate);
+        if claim_types.is_empty() {
+            if delegated {
+                ClaimIssuanceDelegations::remove(did, delegate);
+                ClaimIssuanceDelegateCount::mutate(did, |count| *count = count.saturating_sub(1));
+            }
+        } else {
+            if !delegated {
+                let count = ClaimIssuanceDelegateCount::get(did);
+                ensure!(
+                    count < MAX_CLAIM_ISSUANCE_DELEGATES,
+                    Error::<T>::TooManyClaimIssuanceDelegates
+                );
+                ClaimIssuanceDelegateCount::insert(did, count + 1);
+            }
+            ClaimIssuanceDelegations::insert(did, delegate, &claim_types);
+        }
+        Self::deposit_event(RawEvent::ClaimIssuanceDelegated(did, delegate, claim_types));
+        Ok(())
+    }
+
+    /// Ensures that the custom claim type `id` exists, and that `payload` matches its schema.
+    /// Custom claims without a payload match schemas without fields only.
+    fn ensure_custom_claim_payload(
+        id: &CustomClaimTypeId,
+        payload: Option<&[u8]>,
+    ) -> DispatchResult {
+        ensure!(
+            CustomClaims::contains_key(id),
+            Error::<T>::CustomClaimTypeDoesNotExist
+        );
+        match (Self::custom_claim_schema(id), payload) {
+            (Some(schema), payload) => ensure!(
+                schema.accepts(payload.unwrap_or_default()),
+                Error::<T>::CustomClaimPayloadMismatch
+            ),
+            (None, Some(_)) => fail!(Error::<T>::CustomClaimSchemaNotFound),
+            (None, None) => {}
+        }
+        Ok(())
+    }
+
+    /// Counts a claim added by `issuer`, ensuring that the issuer has not reached its claim
+    /// issuance limit for the current era.
+    pub(crate) fn ensure_claim_issuance_allowed(issuer: IdentityId) -> DispatchResult {
+        let era_length = Self::claim_issuance_era_length();
+        let limit =
+            match IssuerClaimIssuanceLimits::get(issuer).or_else(DefaultClaimIssuanceLimit::get) {
+                Some(limit) if !era_length.is_zero() => limit,
+                _ => return Ok(()),
+            };
+        let era = <pallet_timestamp::Pallet<T>>::get() / era_length;
+
+        ClaimsIssued::<T>::try_mutate(issuer, |(last_era, count)| {
+            if *last_era != era {
+                *last_era = era;
+                *count = 0;
+            }
+            ensure!(*count < limit, Error::<T>::ClaimIssuanceLimitReached);
+            *count += 1;
+            Ok(())
+        })
+    }
+
     fn unsafe_register_custom_claim_type(ty: Vec<u8>) -> Result<CustomClaimTypeId, DispatchError> {
         ensure_string_limited::<T>(&ty)?;
         ensure!(
@@ -0,0 +1,104 @@
+// This file is part of the Polymesh distribution (https://github.com/PolymeshAssociation/Polymesh).
+// Copyright (c) 2020 Polymath
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, version 3.
+
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+// General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+use crate::{AgreementConsents, AgreementCounter, Agreements, Config, Error, Module, RawEvent};
+use frame_support::dispatch::DispatchResult;
+use frame_support::{ensure, StorageDoubleMap, StorageMap};
+use pallet_base::try_next_pre;
+use polymesh_primitives::{Agreement, AgreementConsent, AgreementId, IdentityId};
+use sp_core::H256;
+
+impl<T: Config> Module<T> {
+    /// Publishes a new agreement, with the document hash `hash`, on behalf of the caller's identity.
+    pub(crate) fn base_publish_agreement(origin: T::RuntimeOrigin, hash: H256) -> DispatchResult {
+        let publisher = Self::ensure_perms(origin)?;
+        let id = AgreementCounter::try_mutate(publisher, try_next_pre::<T, _>)?;
+
+        let agreement = Agreement { hash, version: 1 };
+        Agreements::insert(publisher, id, agreement);
+        Self::deposit_event(RawEvent::AgreementPublished(publisher, id, agreement));
+        Ok(())
+    }
+
+    /// Publishes a new version, with the document hash `hash`, of the caller's agreement `id`.
+    /// Consents given to previous versions no longer cover the agreement.
+    pub(crate) fn base_update_agreement(
+        origin: T::RuntimeOrigin,
+        id: AgreementId,
+        hash: H256,
+    ) -> DispatchResult {
+        let publisher = Self::ensure_perms(origin)?;
+        let mut agreement = Self::agreement(publisher, id).ok_or(Error::<T>::AgreementNotFound)?;
+
+        agreement.hash = hash;
+        try_next_pre::<T, _>(&mut agreement.version)?;
+        Agreements::insert(publisher, id, agreement);
+        Self::deposit_event(RawEvent::AgreementPublished(publisher, id, agreement));
+        Ok(())
+    }
+
+    /// Records the consent of the caller's identity to `version` of the agreement `id` of `publisher`.
+    pub(crate) fn base_give_consent(
+        origin: T::RuntimeOrigin,
+        publisher: IdentityId,
+        id: AgreementId,
+        version: u32,
+    ) -> DispatchResult {
+        let did = Self::ensure_perms(origin)?;
+        let agreement = Self::agreement(publisher, id).ok_or(Error::<T>::AgreementNotFound)?;
+        // The version makes sure the identity consents to the document it has reviewed.
+        ensure!(
+            agreement.version == version,
+            Error::<T>::AgreementVersionMismatch
+        );
+
+        let consent = AgreementConsent {
+            hash: agreement.hash,
+            version,
+            given_at: <pallet_timestamp::Pallet<T>>::get(),
+        };
+        <AgreementConsents<T>>::insert(did, (publisher, id), consent);
+        Self::deposit_event(RawEvent::ConsentGiven(did, publisher, id, consent));
+        Ok(())
+    }
+
+    /// Revokes the consent of the caller's identity to the agreement `id` of `publisher`.
+    pub(crate) fn base_revoke_consent(
+        origin: T::RuntimeOrigin,
+        publisher: IdentityId,
+        id: AgreementId,
+    ) -> DispatchResult {
+        let did = Self::ensure_perms(origin)?;
+        ensure!(
+            <AgreementConsents<T>>::contains_key(did, (publisher, id)),
+            Error::<T>::ConsentNotFound
+        );
+
+        <AgreementConsents<T>>::remove(did, (publisher, id));
+        Self::deposit_event(RawEvent::ConsentRevoked(did, publisher, id));
+        Ok(())
+    }
+
+    /// Returns `true` if `did` has consented to the current version of the agreement `id` of `publisher`.
+    pub fn has_consented(did: IdentityId, publisher: IdentityId, id: AgreementId) -> bool {
+        match (
+            Self::agreement(publisher, id),
+            Self::agreement_consent(did, (publisher, id)),
+        ) {
+            (Some(agreement), Some(consent)) => consent.covers(&agreement),
+            _ => false,
+        }
+    }
+}
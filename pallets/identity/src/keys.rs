@@ -14,25 +14,33 @@
 // along with this program. If not, see <http://www.gnu.org/licenses/>.
 
 use crate::{
-    types, AccountKeyRefCount, Config, DidKeys, DidRecords, Error, IsDidFrozen, KeyRecords, Module,
-    MultiPurposeNonce, OffChainAuthorizationNonce, PermissionedCallOriginData, RawEvent,
-    RpcDidRecords,
+    types, AccountKeyRefCount, CallFilters, Config, DidKeys, DidRecords, Error, IsDidFrozen,
+    KeyProbationPeriods, KeyRecords, KeyUsageAudit, Module, MultiPurposeNonce,
+    OffChainAuthorizationNonce, PermissionedCallOriginData, ProbationaryKeys, RawEvent,
+    RpcDidRecords, SecondaryKeyExpiries, TemporaryPermissionGrants,
 };
 use codec::{Decode, Encode as _};
 use core::mem;
 use frame_support::dispatch::DispatchResult;
-use frame_support::traits::{Currency as _, Get as _};
+use frame_support::traits::{
+    schedule::{DispatchTime, Named as ScheduleNamed, LOWEST_PRIORITY},
+    Currency as _, Get as _,
+};
 use frame_support::{
     ensure, IterableStorageDoubleMap, StorageDoubleMap, StorageMap as _, StorageValue as _,
 };
-use frame_system::ensure_signed;
+use frame_system::{ensure_signed, RawOrigin};
 use pallet_base::{ensure_custom_length_ok, ensure_custom_string_limited};
-use polymesh_common_utilities::constants::did::USER;
+use polymesh_common_utilities::constants::{
+    did::USER,
+    schedule_name_prefix::{IDENTITY_PERMISSION_REVERT, IDENTITY_PROBATION_END},
+};
 use polymesh_common_utilities::group::GroupTrait;
 use polymesh_common_utilities::identity::{SecondaryKeyWithAuth, TargetIdAuthorization};
 use polymesh_common_utilities::multisig::MultiSigSubTrait as _;
 use polymesh_common_utilities::protocol_fee::{ChargeProtocolFee as _, ProtocolOp};
 use polymesh_common_utilities::traits::{
+    identity::{IdentitySchedulerCalls, WeightInfo as _},
     AccountCallPermissionsData, CddAndFeeDetails, CheckAccountCallPermissions,
 };
 use polymesh_common_utilities::{Context, SystematicIssuers};
@@ -42,7 +50,9 @@ use polymesh_primitives::{
 };
 use sp_core::sr25519::Signature;
 use sp_io::hashing::blake2_256;
-use sp_runtime::traits::{AccountIdConversion as _, IdentifyAccount, Verify};
+use sp_runtime::traits::{
+    AccountIdConversion as _, IdentifyAccount, Saturating as _, Verify, Zero as _,
+};
 use sp_runtime::{AnySignature, DispatchError};
 use sp_std::{vec, vec::Vec};
 
@@ -53,7 +63,7 @@ const MAX_ASSETS: usize = 2000;
 const MAX_PORTFOLIOS: usize = 2000;
 const MAX_PALLETS: usize = 80;
 const MAX_EXTRINSICS: usize = 80;
-const MAX_NAME_LEN: usize = 60;
+pub(crate) const MAX_NAME_LEN: usize = 60;
 
 // Limit the maximum memory/cpu cost of a key's permissions.
 const MAX_PERMISSION_COMPLEXITY: usize = 1_000_000;
@@ -72,7 +82,7 @@ impl<T: Config> Module<T> {
     }
 
     /// Returns `Err(DidDoesNotExist)` unless `id` has an associated record.
-    pub(crate) fn ensure_id_record_exists(id: IdentityId) -> DispatchResult {
+    pub fn ensure_id_record_exists(id: IdentityId) -> DispatchResult {
         ensure!(Self::is_identity_exists(&id), Error::<T>::DidDoesNotExist);
         Ok(())
     }
@@ -82,8 +92,12 @@ impl<T: Config> Module<T> {
     pub fn get_identity(key: &T::AccountId) -> Option<IdentityId> {
         match KeyRecords::<T>::get(key)? {
             KeyRecord::PrimaryKey(did) => Some(did),
-            KeyRecord::SecondaryKey(did, _) if !Self::is_did_frozen(did) => Some(did),
-            // Is a multisig signer, or frozen secondary key.
+            KeyRecord::SecondaryKey(did, _)
+                if !Self::is_did_frozen(did) && !Self::is_key_expired(key) =>
+            {
+                Some(did)
+            }
+            // Is a multisig signer, or frozen or expired secondary key.
             _ => None,
         }
     }
@@ -130,6 +144,52 @@ impl<T: Config> Module<T> {
         })
     }
 
+    /// RPC call to fetch the permissions of `acc` and the restrictions applying to them.
+    pub fn get_key_permissions(acc: T::AccountId) -> Option<types::KeyPermissionsData<IdentityId>> {
+        let (identity, permissions) = match KeyRecords::<T>::get(&acc)? {
+            KeyRecord::PrimaryKey(did) => Some((did, None)),
+            KeyRecord::SecondaryKey(did, perms) => Some((did, Some(perms))),
+            // Is a multisig signer.
+            _ => None,
+        }?;
+        let is_secondary_key = permissions.is_some();
+        Some(types::KeyPermissionsData {
+            identity,
+            permissions,
+            frozen: is_secondary_key && Self::is_did_frozen(identity),
+            expired: is_secondary_key && Self::is_key_expired(&acc),
+            call_filters: CallFilters::iter_key_prefix(identity).collect(),
+        })
+    }
+
+    /// Returns `true` if `acc` is currently allowed to call `dispatchable_name` of `pallet_name`.
+    ///
+    /// Unlike `check_account_call_permissions`, this has no side effects.
+    pub fn can_call(
+        acc: &T::AccountId,
+        pallet_name: &PalletName,
+        dispatchable_name: &DispatchableName,
+    ) -> bool {
+        match KeyRecords::<T>::get(acc) {
+            Some(KeyRecord::PrimaryKey(did)) => {
+                !Self::is_call_filtered(did, pallet_name, dispatchable_name)
+            }
+            Some(KeyRecord::SecondaryKey(did, permissions)) => {
+                Self::is_secondary_key_usable(did, acc)
+                    && permissions
+                        .extrinsic
+                        .sufficient_for(pallet_name, dispatchable_name)
+                    && !Self::is_call_filtered(did, pallet_name, dispatchable_name)
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns `true` if the secondary key `key` of `did` is neither frozen nor expired.
+    fn is_secondary_key_usable(did: IdentityId, key: &T::AccountId) -> bool {
+        !Self::is_did_frozen(&did) && !Self::is_key_expired(key)
+    }
+
     /// Check if the key is linked to an identity or MultiSig.
     /// (linked_to_did, linked_to_multsig)
     pub fn is_key_linked(acc: &T::AccountId) -> (bool, bool) {
@@ -174,7 +234,7 @@ impl<T: Config> Module<T> {
     }
 
     /// Ensure that the account key is safe to unlink from it's identity.
-    fn ensure_key_unlinkable_from_did(key: &T::AccountId) -> DispatchResult {
+    pub(crate) fn ensure_key_unlinkable_from_did(key: &T::AccountId) -> DispatchResult {
         ensure!(
             <AccountKeyRefCount<T>>::get(key) == 0,
             Error::<T>::AccountKeyIsBeingUsed
@@ -256,6 +316,9 @@ impl<T: Config> Module<T> {
         };
         if remove_key {
             KeyRecords::<T>::remove(key);
+            SecondaryKeyExpiries::<T>::remove(key);
+            TemporaryPermissionGrants::<T>::remove(key);
+            ProbationaryKeys::<T>::remove(key);
         }
     }
 
@@ -334,6 +397,27 @@ impl<T: Config> Module<T> {
             })?;
         }
 
+        Self::unsafe_rotate_primary_key(
+            target_did,
+            old_primary_key,
+            new_primary_key,
+            is_secondary_key,
+            new_permissions,
+        );
+        Ok(())
+    }
+
+    /// Sets `new_primary_key` as the primary key of `target_did`, without any checks.
+    ///
+    /// The old primary key becomes a secondary key if `new_permissions` is not `None`,
+    /// otherwise it is unlinked from `target_did`.
+    pub(crate) fn unsafe_rotate_primary_key(
+        target_did: IdentityId,
+        old_primary_key: T::AccountId,
+        new_primary_key: T::AccountId,
+        is_secondary_key: bool,
+        new_permissions: Option<Permissions>,
+    ) {
         // Replace primary key of the owner that initiated key rotation.
         let key_record = KeyRecord::PrimaryKey(target_did);
         if is_secondary_key {
@@ -364,7 +448,6 @@ impl<T: Config> Module<T> {
         } else {
             Self::remove_key_record(&old_primary_key, Some(target_did));
         }
-        Ok(())
     }
 
     /// Accepts a primary key rotation.
@@ -402,34 +485,121 @@ impl<T: Config> Module<T> {
         permissions: Permissions,
     ) -> DispatchResult {
         let (_, did) = Self::ensure_primary_key(origin)?;
+        Self::set_secondary_key_permissions_of(did, key, permissions)
+    }
 
-        // Ensure that the `key` is a secondary key of the caller's Identity
+    /// Sets the permissions of `key`, which must be a secondary key of `did`.
+    pub(crate) fn set_secondary_key_permissions_of(
+        did: IdentityId,
+        key: T::AccountId,
+        permissions: Permissions,
+    ) -> DispatchResult {
+        // Ensure that the `key` is a secondary key of the Identity
         Self::ensure_secondary_key(did, &key)?;
 
         Self::ensure_perms_length_limited(&permissions)?;
 
-        // Update secondary key's permissions.
-        KeyRecords::<T>::mutate(&key, |record| {
-            if let Some(KeyRecord::SecondaryKey(_, perms)) = record {
+        // Explicitly set permissions replace any temporary grant, so they aren't reverted.
+        TemporaryPermissionGrants::<T>::remove(&key);
+        Self::unsafe_set_secondary_key_permissions(did, &key, permissions);
+        Ok(())
+    }
+
+    /// Updates the permissions of the secondary `key` of `did`, without any checks.
+    /// Returns the previous permissions of the key, if it is a secondary key.
+    fn unsafe_set_secondary_key_permissions(
+        did: IdentityId,
+        key: &T::AccountId,
+        permissions: Permissions,
+    ) -> Option<Permissions> {
+        KeyRecords::<T>::mutate(key, |record| match record {
+            Some(KeyRecord::SecondaryKey(_, perms)) => {
                 let old_perms = mem::replace(perms, permissions.clone());
                 Self::deposit_event(RawEvent::SecondaryKeyPermissionsUpdated(
                     did,
                     key.clone(),
-                    old_perms,
+                    old_perms.clone(),
                     permissions,
                 ));
+                Some(old_perms)
             }
-        });
+            _ => None,
+        })
+    }
+
+    /// Temporarily sets the permissions of `key`, a secondary key of the caller's identity,
+    /// and schedules the restoration of its current permissions in `duration` blocks.
+    pub(crate) fn base_grant_temporary_permissions(
+        origin: T::RuntimeOrigin,
+        key: T::AccountId,
+        permissions: Permissions,
+        duration: T::BlockNumber,
+    ) -> DispatchResult {
+        let (_, did) = Self::ensure_primary_key(origin)?;
+        Self::ensure_secondary_key(did, &key)?;
+        Self::ensure_perms_length_limited(&permissions)?;
+        ensure!(
+            !duration.is_zero(),
+            Error::<T>::InvalidPermissionGrantDuration
+        );
+        ensure!(
+            !TemporaryPermissionGrants::<T>::contains_key(&key),
+            Error::<T>::TemporaryPermissionsAlreadyGranted
+        );
+
+        let revert_at = <frame_system::Pallet<T>>::block_number().saturating_add(duration);
+        T::IdentityScheduler::schedule_named(
+            (IDENTITY_PERMISSION_REVERT, &key, revert_at).encode(),
+            DispatchTime::At(revert_at),
+            None,
+            LOWEST_PRIORITY,
+            RawOrigin::Root.into(),
+            T::IdentityScheduledCalls::revert_temporary_permissions(key.clone(), revert_at),
+        )
+        .map_err(|_| Error::<T>::FailedToSchedulePermissionRevert)?;
+
+        if let Some(old_perms) =
+            Self::unsafe_set_secondary_key_permissions(did, &key, permissions.clone())
+        {
+            TemporaryPermissionGrants::<T>::insert(&key, (old_perms, revert_at));
+        }
+        Self::deposit_event(RawEvent::TemporaryPermissionsGranted(
+            did,
+            key,
+            permissions,
+            revert_at,
+        ));
         Ok(())
     }
 
+    /// Restores the permissions of `key` if its temporary grant ends at `revert_at`.
+    pub(crate) fn base_revert_temporary_permissions(key: T::AccountId, revert_at: T::BlockNumber) {
+        // Grants that were replaced, or whose key was removed, are no longer pending.
+        match TemporaryPermissionGrants::<T>::get(&key) {
+            Some((old_perms, grant_revert_at)) if grant_revert_at == revert_at => {
+                TemporaryPermissionGrants::<T>::remove(&key);
+                if let Some(KeyRecord::SecondaryKey(did, _)) = KeyRecords::<T>::get(&key) {
+                    Self::unsafe_set_secondary_key_permissions(did, &key, old_perms);
+                }
+            }
+            _ => {}
+        }
+    }
+
     /// Removes specified secondary keys of a DID if present.
     pub(crate) fn base_remove_secondary_keys(
         origin: T::RuntimeOrigin,
         keys: Vec<T::AccountId>,
     ) -> DispatchResult {
         let (_, did) = Self::ensure_primary_key(origin)?;
+        Self::unsafe_remove_secondary_keys(did, keys)
+    }
 
+    /// Removes the secondary `keys` of `did`, without checking who requested it.
+    pub fn unsafe_remove_secondary_keys(
+        did: IdentityId,
+        keys: Vec<T::AccountId>,
+    ) -> DispatchResult {
         // Ensure that it is safe to unlink the secondary keys from the did.
         for key in &keys {
             // Ensure that the key is a secondary key.
@@ -456,7 +626,16 @@ impl<T: Config> Module<T> {
         expires_at: T::Moment,
     ) -> DispatchResult {
         let (_, did) = Self::ensure_primary_key(origin)?;
+        Self::unsafe_add_secondary_keys_with_authorization(did, keys, expires_at)
+    }
 
+    /// Adds secondary keys to the identity `did`, without checking who requested it.
+    /// Each key must have signed the off-chain authorization to join `did`.
+    pub fn unsafe_add_secondary_keys_with_authorization(
+        did: IdentityId,
+        keys: Vec<SecondaryKeyWithAuth<T::AccountId>>,
+        expires_at: T::Moment,
+    ) -> DispatchResult {
         // 0. Check expiration
         let now = <pallet_timestamp::Pallet<T>>::get();
         ensure!(now < expires_at, Error::<T>::AuthorizationExpired);
@@ -502,6 +681,7 @@ impl<T: Config> Module<T> {
                 &sk.key,
                 KeyRecord::SecondaryKey(did, sk.permissions.clone()),
             );
+            Self::start_key_probation(did, &sk.key);
         });
         // 2.2. Update that identity's offchain authorization nonce.
         OffChainAuthorizationNonce::mutate(did, |nonce| *nonce = authorization.nonce + 1);
@@ -547,6 +727,7 @@ impl<T: Config> Module<T> {
             &key,
             KeyRecord::SecondaryKey(target_did, permissions.clone()),
         );
+        Self::start_key_probation(target_did, &key);
 
         let sk = SecondaryKey { key, permissions };
         Self::deposit_event(RawEvent::SecondaryKeysAdded(target_did, vec![sk]));
@@ -568,6 +749,43 @@ impl<T: Config> Module<T> {
         Ok(())
     }
 
+    /// Sets the moment from which `key`, a secondary key of the caller's identity, loses its permissions.
+    pub(crate) fn base_set_secondary_key_expiry(
+        origin: T::RuntimeOrigin,
+        key: T::AccountId,
+        expiry: Option<T::Moment>,
+    ) -> DispatchResult {
+        let (_, did) = Self::ensure_primary_key(origin)?;
+        Self::ensure_secondary_key(did, &key)?;
+        if let Some(expiry) = expiry {
+            ensure!(
+                expiry > <pallet_timestamp::Pallet<T>>::get(),
+                Error::<T>::SecondaryKeyExpiryInPast
+            );
+        }
+
+        Self::unsafe_set_secondary_key_expiry(did, key, expiry);
+        Ok(())
+    }
+
+    /// Sets the moment from which `key`, a secondary key of `did`, loses its permissions,
+    /// without checking who requested it.
+    pub fn unsafe_set_secondary_key_expiry(
+        did: IdentityId,
+        key: T::AccountId,
+        expiry: Option<T::Moment>,
+    ) {
+        SecondaryKeyExpiries::<T>::set(&key, expiry);
+        Self::deposit_event(RawEvent::SecondaryKeyExpirySet(did, key, expiry));
+    }
+
+    /// Returns `true` if `key` has an expiry that has been reached.
+    fn is_key_expired(key: &T::AccountId) -> bool {
+        SecondaryKeyExpiries::<T>::get(key).map_or(false, |expiry| {
+            expiry <= <pallet_timestamp::Pallet<T>>::get()
+        })
+    }
+
     /// Freezes/unfreezes the target `did` identity.
     ///
     /// # Errors
@@ -577,6 +795,12 @@ impl<T: Config> Module<T> {
         freeze: bool,
     ) -> DispatchResult {
         let (_, did) = Self::ensure_primary_key(origin)?;
+        Self::unsafe_set_frozen_secondary_keys(did, freeze);
+        Ok(())
+    }
+
+    /// Freezes/unfreezes the secondary keys of `did`, without checking who requested it.
+    pub(crate) fn unsafe_set_frozen_secondary_keys(did: IdentityId, freeze: bool) {
         if freeze {
             IsDidFrozen::insert(&did, true);
             Self::deposit_event(RawEvent::SecondaryKeysFrozen(did))
@@ -584,6 +808,81 @@ impl<T: Config> Module<T> {
             IsDidFrozen::remove(&did);
             Self::deposit_event(RawEvent::SecondaryKeysUnfrozen(did));
         }
+    }
+
+    /// Sets the number of blocks during which the new secondary keys of the caller's identity are on probation.
+    pub(crate) fn base_set_key_probation_period(
+        origin: T::RuntimeOrigin,
+        period: Option<T::BlockNumber>,
+    ) -> DispatchResult {
+        let (_, did) = Self::ensure_primary_key(origin)?;
+        ensure!(
+            period.map_or(true, |period| !period.is_zero()),
+            Error::<T>::InvalidProbationPeriod
+        );
+        KeyProbationPeriods::<T>::set(did, period);
+        Self::deposit_event(RawEvent::KeyProbationPeriodSet(did, period));
+        Ok(())
+    }
+
+    /// Ends the probation of `key`, a secondary key of the caller's identity, which is then kept.
+    pub(crate) fn base_confirm_secondary_key(
+        origin: T::RuntimeOrigin,
+        key: T::AccountId,
+    ) -> DispatchResult {
+        let (_, did) = Self::ensure_primary_key(origin)?;
+        Self::ensure_secondary_key(did, &key)?;
+        ensure!(
+            ProbationaryKeys::<T>::take(&key).is_some(),
+            Error::<T>::KeyNotOnProbation
+        );
+        Self::deposit_event(RawEvent::SecondaryKeyConfirmed(did, key));
+        Ok(())
+    }
+
+    /// Puts the new secondary `key` of `did` on probation, if `did` has a probation period.
+    fn start_key_probation(did: IdentityId, key: &T::AccountId) {
+        let period = match KeyProbationPeriods::<T>::get(did) {
+            Some(period) => period,
+            None => return,
+        };
+        let ends_at = <frame_system::Pallet<T>>::block_number().saturating_add(period);
+        // Scheduling only fails if the key already has a probation ending at `ends_at`,
+        // e.g. when it is removed and added again in the same block,
+        // in which case that task also ends this probation.
+        let _ = T::IdentityScheduler::schedule_named(
+            (IDENTITY_PROBATION_END, key, ends_at).encode(),
+            DispatchTime::At(ends_at),
+            None,
+            LOWEST_PRIORITY,
+            RawOrigin::Root.into(),
+            T::IdentityScheduledCalls::end_key_probation(key.clone(), ends_at),
+        );
+        ProbationaryKeys::<T>::insert(key, ends_at);
+        Self::deposit_event(RawEvent::SecondaryKeyOnProbation(did, key.clone(), ends_at));
+    }
+
+    /// Removes `key` if it is still on the probation that ends at `ends_at`.
+    pub(crate) fn base_end_key_probation(key: T::AccountId, ends_at: T::BlockNumber) {
+        // Confirmed keys, or keys removed meanwhile, are no longer on probation.
+        if ProbationaryKeys::<T>::get(&key) != Some(ends_at) {
+            return;
+        }
+        ProbationaryKeys::<T>::remove(&key);
+        if let Some(KeyRecord::SecondaryKey(did, _)) = KeyRecords::<T>::get(&key) {
+            // Keys that can't be unlinked, e.g. still in use, are kept.
+            let _ = Self::unsafe_remove_secondary_keys(did, vec![key]);
+        }
+    }
+
+    /// Enables or disables the recording of the calls made by the secondary keys of the caller's identity.
+    pub(crate) fn base_set_key_usage_audit(
+        origin: T::RuntimeOrigin,
+        enabled: bool,
+    ) -> DispatchResult {
+        let (_, did) = Self::ensure_primary_key(origin)?;
+        KeyUsageAudit::insert(did, enabled);
+        Self::deposit_event(RawEvent::KeyUsageAuditSet(did, enabled));
         Ok(())
     }
 
@@ -683,14 +982,14 @@ impl<T: Config> Module<T> {
     }
 
     /// Ensure the `key` is a secondary key of the identity `did`.
-    fn ensure_secondary_key(did: IdentityId, key: &T::AccountId) -> DispatchResult {
+    pub(crate) fn ensure_secondary_key(did: IdentityId, key: &T::AccountId) -> DispatchResult {
         let key_did = Self::key_records(key).and_then(|rec| rec.is_secondary_key());
         ensure!(key_did == Some(did), Error::<T>::NotASigner);
         Ok(())
     }
 
     /// Ensures that `origin`'s key is the primary key of a DID.
-    fn ensure_primary_key(
+    pub fn ensure_primary_key(
         origin: T::RuntimeOrigin,
     ) -> Result<(T::AccountId, IdentityId), DispatchError> {
         let sender = ensure_signed(origin)?;
@@ -757,7 +1056,7 @@ impl<T: Config> Module<T> {
 }
 
 impl<T: Config> CheckAccountCallPermissions<T::AccountId> for Module<T> {
-    // For weighting purposes, the function reads 4 storage values.
+    // For weighting purposes, the function reads 7 storage values.
     fn check_account_call_permissions(
         who: &T::AccountId,
         pallet_name: impl FnOnce() -> PalletName,
@@ -769,19 +1068,48 @@ impl<T: Config> CheckAccountCallPermissions<T::AccountId> for Module<T> {
         };
 
         match KeyRecords::<T>::get(who)? {
-            // Primary keys do not have / require further permission checks.
-            KeyRecord::PrimaryKey(did) => Some(data(did, None)),
-            // Secondary Key. Ensure DID isn't frozen + key has sufficient permissions.
-            KeyRecord::SecondaryKey(did, permissions) if !Self::is_did_frozen(&did) => {
+            // Primary keys do not have / require further permission checks,
+            // besides the call filters of their identity.
+            // The call metadata is only read for identities that have call filters.
+            KeyRecord::PrimaryKey(did) => (!Self::has_call_filters(did)
+                || !Self::is_call_filtered(did, &pallet_name(), &function_name()))
+            .then(|| data(did, None)),
+            // Secondary Key. Ensure DID isn't frozen, key hasn't expired + key has sufficient permissions.
+            KeyRecord::SecondaryKey(did, permissions)
+                if Self::is_secondary_key_usable(did, who) =>
+            {
+                let (pallet_name, function_name) = (pallet_name(), function_name());
                 let sk = SecondaryKey {
                     key: who.clone(),
                     permissions,
                 };
-                sk.has_extrinsic_permission(&pallet_name(), &function_name())
-                    .then(|| data(did, Some(sk)))
+                let permitted = sk.has_extrinsic_permission(&pallet_name, &function_name)
+                    && !Self::is_call_filtered(did, &pallet_name, &function_name);
+                if permitted && KeyUsageAudit::get(did) {
+                    Self::deposit_event(RawEvent::SecondaryKeyUsed(
+                        did,
+                        who.clone(),
+                        pallet_name,
+                        function_name,
+                    ));
+                }
+                permitted.then(|| data(did, Some(sk)))
             }
-            // DIDs with frozen secondary keys, AKA frozen DIDs, are not permitted to call extrinsics.
+            // DIDs with frozen secondary keys, AKA frozen DIDs, and expired secondary keys
+            // are not permitted to call extrinsics.
             _ => None,
         }
     }
+
+    fn is_account_call_filtered(
+        who: &T::AccountId,
+        pallet_name: &PalletName,
+        function_name: &DispatchableName,
+    ) -> bool {
+        KeyRecords::<T>::get(who)
+            .and_then(|record| record.as_did())
+            .map_or(false, |did| {
+                Self::is_call_filtered(did, pallet_name, function_name)
+            })
+    }
 }
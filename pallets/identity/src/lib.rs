@@ -66,26 +66,71 @@
 //! - `change_cdd_requirement_for_mk_rotation` - Sets if CDD authorization is required for updating primary key of an identity.
 //! - `join_identity_as_key` - Join an identity as a secondary key.
 //! - `add_claim` - Adds a new claim record or edits an existing one.
+//! - `add_claims_batch` - Adds several claim records, or edits existing ones, in a single call.
+//! - `start_claim_import` - Starts an import of a claim to a hash-checked list of targets.
+//! - `import_claim_batch` - Adds the claim of an ongoing import to the next batch of its targets.
+//! - `finalize_claim_import` - Completes an import of a claim, after verifying its integrity.
+//! - `abort_claim_import` - Aborts an ongoing import of a claim.
 //! - `revoke_claim` - Marks the specified claim as revoked.
 //! - `revoke_claim_by_index` - Revoke a claim identified by its index.
+//! - `schedule_claim_revocation` - Schedules the revocation of a claim at a future moment.
 //! - `set_secondary_key_permissions` - Sets permissions for a secondary key.
 //! - `freeze_secondary_keys` - Disables all secondary keys at `did` identity.
 //! - `unfreeze_secondary_keys` - Re-enables all secondary keys of the caller's identity.
+//! - `set_secondary_key_expiry` - Sets the moment from which a secondary key loses all its permissions.
+//! - `grant_temporary_permissions` - Sets the permissions of a secondary key for a number of blocks.
+//! - `set_key_usage_audit` - Enables or disables the audit trail of the successful calls made by secondary keys.
+//! - `set_key_probation_period` - Sets the number of blocks during which new secondary keys are on probation.
+//! - `confirm_secondary_key` - Ends the probation of a secondary key, which is then kept.
 //! - `add_authorization` - Adds an authorization.
 //! - `remove_authorization` - Removes an authorization.
 //! - `add_secondary_keys_with_authorization` - Adds secondary keys to target identity `id`.
 //! - `add_investor_uniqueness_claim` - Adds InvestorUniqueness claim for a given target identity.
 //! - `add_investor_uniqueness_claim_v2` - Adds InvestorUniqueness claim V2 for a given target identity.
+//! - `set_key_recovery_delegate` - Pre-authorizes a CDD provider to recover the caller's primary key.
+//! - `initiate_key_recovery` - Starts the recovery of an identity's primary key by a CDD provider.
+//! - `cancel_key_recovery` - Cancels a pending primary key recovery.
+//! - `execute_key_recovery` - Rotates the primary key once the recovery's challenge period is over.
+//! - `set_recovery_guardians` - Sets the identities that can jointly recover the caller's primary key.
+//! - `approve_social_recovery` - Approves, as a guardian, the recovery of an identity's primary key.
+//! - `cancel_social_recovery` - Cancels a pending social recovery.
+//! - `execute_social_recovery` - Rotates the primary key once the social recovery's challenge period is over.
+//! - `add_call_filter` - Restricts the caller's identity from making some calls.
+//! - `request_call_filter_removal` - Starts the delay after which a call filter can be removed.
+//! - `remove_call_filter` - Removes a call filter once its removal delay is over.
+//! - `publish_agreement` - Publishes an agreement identities can consent to.
+//! - `update_agreement` - Publishes a new version of an agreement.
+//! - `give_consent` - Records the consent of the caller's identity to a version of an agreement.
+//! - `revoke_consent` - Revokes the consent of the caller's identity to an agreement.
+//! - `approve_parent_identity` - Allows an identity to link the caller's identity as its child.
+//! - `link_child_identity` - Links an identity, which approved the caller's identity, as its child.
+//! - `create_child_identity` - Turns a secondary key into the primary key of a new child identity.
+//! - `unlink_child_identity` - Unlinks a child identity from its parent.
+//! - `set_child_secondary_key_permissions` - Sets the permissions of a secondary key of a child identity.
+//! - `remove_child_secondary_keys` - Removes secondary keys of a child identity.
+//! - `set_child_secondary_keys_frozen` - Freezes or unfreezes the secondary keys of a child identity.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![recursion_limit = "256"]
 
 mod auth;
+mod call_filters;
+mod children;
 mod claims;
+mod consents;
 mod keys;
+mod recovery;
+
+pub use auth::MAX_AUTHORIZATIONS_PER_BATCH;
+pub use call_filters::IdentityCallFilter;
+pub use claims::{MAX_CLAIMS_PER_BATCH, MAX_CLAIM_ISSUANCE_DELEGATES, MAX_DELEGATED_CLAIM_TYPES};
+pub use recovery::MAX_RECOVERY_GUARDIANS;
 
 pub mod types;
-pub use types::{Claim1stKey, Claim2ndKey, DidStatus, PermissionedCallOriginData, RpcDidRecords};
+pub use types::{
+    CddClaimDetails, Claim1stKey, Claim2ndKey, DidStatus, DidStatusDetails, KeyRecovery,
+    PermissionedCallOriginData, RpcDidRecords, SocialKeyRecovery,
+};
 
 #[cfg(feature = "runtime-benchmarks")]
 pub mod benchmarking;
@@ -97,7 +142,7 @@ use frame_support::{
     decl_error, decl_module, decl_storage,
     dispatch::{
         DispatchClass::{Normal, Operational},
-        DispatchResult, Pays,
+        DispatchResult, DispatchResultWithPostInfo, Pays, Weight,
     },
     traits::{ChangeMembers, Currency, EnsureOrigin, Get, InitializeMembers},
 };
@@ -113,16 +158,19 @@ use polymesh_common_utilities::{
     SystematicIssuers, GC_DID,
 };
 use polymesh_primitives::{
-    investor_zkproof_data::v1::InvestorZKProofData, storage_migration_ver, Authorization,
-    AuthorizationData, AuthorizationType, CddId, Claim, ClaimType, CustomClaimTypeId, DidRecord,
-    IdentityClaim, IdentityId, KeyRecord, Permissions, Scope, SecondaryKey, Signatory, Ticker,
+    investor_zkproof_data::v1::InvestorZKProofData, storage_migrate_on, storage_migration_ver,
+    Agreement, AgreementConsent, AgreementId, Authorization, AuthorizationData, AuthorizationType,
+    BatchImport, CallFilter, CddId, Claim, ClaimType, CustomClaimSchema, CustomClaimTypeId,
+    DidRecord, IdentityClaim, IdentityId, KeyRecord, Moment, NFTId, Permissions, RecoveryGuardians,
+    Scope, SecondaryKey, Signatory, Ticker,
 };
-use sp_runtime::traits::Hash;
+use sp_core::H256;
+use sp_runtime::traits::{Hash, Zero};
 use sp_std::{convert::TryFrom, prelude::*};
 
 pub type Event<T> = polymesh_common_utilities::traits::identity::Event<T>;
 
-storage_migration_ver!(2);
+storage_migration_ver!(3);
 
 decl_storage! {
     trait Store for Module<T: Config> as Identity {
@@ -134,6 +182,17 @@ decl_storage! {
         /// DID -> bool that indicates if secondary keys are frozen.
         pub IsDidFrozen get(fn is_did_frozen): map hasher(identity) IdentityId => bool;
 
+        /// DID -> bool that indicates if the calls made by its secondary keys are recorded in events.
+        pub KeyUsageAudit get(fn key_usage_audit): map hasher(identity) IdentityId => bool;
+
+        /// DID -> number of blocks during which its new secondary keys are on probation.
+        pub KeyProbationPeriods get(fn key_probation_period):
+            map hasher(identity) IdentityId => Option<T::BlockNumber>;
+
+        /// Secondary key on probation -> block at which it is removed unless confirmed.
+        pub ProbationaryKeys get(fn probationary_key):
+            map hasher(twox_64_concat) T::AccountId => Option<T::BlockNumber>;
+
         /// It stores the current identity for current transaction.
         pub CurrentDid: Option<IdentityId>;
 
@@ -142,17 +201,40 @@ decl_storage! {
 
         /// (Target ID, claim type) (issuer,scope) -> Associated claims
         pub Claims: double_map hasher(twox_64_concat) Claim1stKey, hasher(blake2_128_concat) Claim2ndKey => IdentityClaim;
+        /// The number of entries of `Claims`.
+        pub ClaimCount get(fn claim_count): u64;
+        /// Claim issuance delegations. (delegator DID, delegate DID) -> claim types the delegate
+        /// issues on behalf of the delegator.
+        pub ClaimIssuanceDelegations get(fn claim_issuance_delegation):
+            double_map hasher(identity) IdentityId, hasher(identity) IdentityId => Vec<ClaimType>;
+        /// Delegator DID -> number of identities it delegated the issuance of claims to.
+        pub ClaimIssuanceDelegateCount get(fn claim_issuance_delegate_count):
+            map hasher(identity) IdentityId => u32;
+        /// (issuer DID, claim) -> expiry of the claim and progress of its import to a list of targets.
+        pub ClaimImports get(fn claim_import):
+            double_map hasher(identity) IdentityId, hasher(blake2_128_concat) Claim => Option<(Option<T::Moment>, BatchImport)>;
         /// CustomClaimTypeId -> String constant
         pub CustomClaims: map hasher(twox_64_concat) CustomClaimTypeId => Vec<u8>;
         /// String constant -> CustomClaimTypeId
         pub CustomClaimsInverse: map hasher(blake2_128_concat) Vec<u8> => CustomClaimTypeId;
         /// The next `CustomClaimTypeId`.
         pub CustomClaimIdSequence get(fn custom_claim_id_seq): CustomClaimTypeId;
+        /// CustomClaimTypeId -> schema that the payloads of its claims must match.
+        pub CustomClaimSchemas get(fn custom_claim_schema):
+            map hasher(twox_64_concat) CustomClaimTypeId => Option<CustomClaimSchema>;
 
         /// Map from AccountId to `KeyRecord` that holds the key's identity and permissions.
         pub KeyRecords get(fn key_records):
             map hasher(twox_64_concat) T::AccountId => Option<KeyRecord<T::AccountId>>;
 
+        /// Secondary key -> moment from which the key loses all its permissions.
+        pub SecondaryKeyExpiries get(fn secondary_key_expiry):
+            map hasher(twox_64_concat) T::AccountId => Option<T::Moment>;
+
+        /// Secondary key -> (its permissions before a temporary grant, block at which they are restored).
+        pub TemporaryPermissionGrants get(fn temporary_permission_grant):
+            map hasher(twox_64_concat) T::AccountId => Option<(Permissions, T::BlockNumber)>;
+
         /// A reverse double map to allow finding all keys for an identity.
         pub DidKeys get(fn did_keys):
             double_map hasher(identity) IdentityId, hasher(twox_64_concat) T::AccountId => bool;
@@ -166,6 +248,8 @@ decl_storage! {
         /// All authorizations that an identity/key has
         pub Authorizations get(fn authorizations): double_map hasher(blake2_128_concat)
             Signatory<T::AccountId>, hasher(twox_64_concat) u64 => Option<Authorization<T::AccountId, T::Moment>>;
+        /// The number of entries of `Authorizations`.
+        pub AuthorizationCount get(fn authorization_count): u64;
 
         /// All authorizations that an identity has given. (Authorizer, auth_id -> authorized)
         pub AuthorizationsGiven: double_map hasher(identity)
@@ -180,7 +264,7 @@ decl_storage! {
         pub CddAuthForPrimaryKeyRotation get(fn cdd_auth_for_primary_key_rotation): bool;
 
         /// Storage version.
-        StorageVersion get(fn storage_version) build(|_| Version::new(2)): Version;
+        StorageVersion get(fn storage_version) build(|_| Version::new(3)): Version;
 
         /// How many "strong" references to the account key.
         ///
@@ -191,6 +275,72 @@ decl_storage! {
         ///
         pub AccountKeyRefCount get(fn account_key_ref_count):
             map hasher(blake2_128_concat) T::AccountId => u64;
+
+        /// DID -> CDD provider pre-authorized by the identity to recover its primary key.
+        pub KeyRecoveryDelegates get(fn key_recovery_delegate):
+            map hasher(identity) IdentityId => Option<IdentityId>;
+
+        /// DID -> primary key recovery pending its challenge period.
+        pub PendingKeyRecoveries get(fn pending_key_recovery):
+            map hasher(identity) IdentityId => Option<KeyRecovery<T::AccountId, T::Moment>>;
+
+        /// DID -> guardians allowed to jointly recover its primary key.
+        pub RecoveryGuardianSets get(fn recovery_guardians):
+            map hasher(identity) IdentityId => Option<RecoveryGuardians>;
+
+        /// DID -> primary key recovery being approved by its guardians, or pending its challenge period.
+        pub PendingSocialRecoveries get(fn pending_social_recovery):
+            map hasher(identity) IdentityId => Option<SocialKeyRecovery<T::AccountId, T::Moment>>;
+
+        /// Length of the eras over which claim issuance is rate limited.
+        /// Claim issuance is not limited while it is zero.
+        pub ClaimIssuanceEraLength get(fn claim_issuance_era_length): T::Moment;
+
+        /// Maximum number of claims an issuer can add per era, unless overridden for the issuer.
+        pub DefaultClaimIssuanceLimit get(fn default_claim_issuance_limit): Option<u32>;
+
+        /// Issuer DID -> maximum number of claims it can add per era, overriding the default limit.
+        pub IssuerClaimIssuanceLimits get(fn issuer_claim_issuance_limit):
+            map hasher(identity) IdentityId => Option<u32>;
+
+        /// Issuer DID -> (era, number of claims added by the issuer in that era).
+        pub ClaimsIssued get(fn claims_issued):
+            map hasher(identity) IdentityId => (T::Moment, u32);
+
+        /// (DID, call filter) -> bool that indicates if the identity restricted itself from the filtered calls.
+        pub CallFilters get(fn has_call_filter):
+            double_map hasher(identity) IdentityId, hasher(blake2_128_concat) CallFilter => bool;
+
+        /// (DID, call filter) -> moment from which the filter, whose removal was requested, can be removed.
+        pub CallFilterRemovals get(fn call_filter_removal):
+            double_map hasher(identity) IdentityId, hasher(blake2_128_concat) CallFilter => Option<T::Moment>;
+
+        /// Publisher DID -> id of the last agreement published by the identity.
+        pub AgreementCounter get(fn agreement_counter):
+            map hasher(identity) IdentityId => AgreementId;
+
+        /// (publisher DID, agreement id) -> current version of the agreement.
+        pub Agreements get(fn agreement):
+            double_map hasher(identity) IdentityId, hasher(twox_64_concat) AgreementId => Option<Agreement>;
+
+        /// (DID, (publisher DID, agreement id)) -> consent of the identity to a version of the agreement.
+        pub AgreementConsents get(fn agreement_consent):
+            double_map hasher(identity) IdentityId, hasher(blake2_128_concat) (IdentityId, AgreementId) => Option<AgreementConsent<T::Moment>>;
+
+        /// (DID, ticker of an NFT collection) -> NFT of the collection linked to the identity as a credential.
+        pub NFTCredentials get(fn nft_credential):
+            double_map hasher(identity) IdentityId, hasher(blake2_128_concat) Ticker => Option<NFTId>;
+
+        /// Child DID -> DID of its parent.
+        pub ParentDid get(fn parent_did): map hasher(identity) IdentityId => Option<IdentityId>;
+
+        /// (parent DID, child DID) -> whether the child inherits the claims of its parent, except for CDD claims.
+        pub ChildIdentities get(fn child_identities):
+            double_map hasher(identity) IdentityId, hasher(identity) IdentityId => bool;
+
+        /// DID -> identity allowed to link it as its child.
+        pub ParentIdentityApprovals get(fn parent_identity_approval):
+            map hasher(identity) IdentityId => Option<IdentityId>;
     }
     add_extra_genesis {
         // Identities at genesis.
@@ -251,6 +401,16 @@ decl_module! {
         fn deposit_event() = default;
 
         const InitialPOLYX: <T::Balances as Currency<T::AccountId>>::Balance = T::InitialPOLYX::get();
+        const KeyRecoveryChallengePeriod: T::Moment = T::KeyRecoveryChallengePeriod::get();
+        const CallFilterRemovalDelay: T::Moment = T::CallFilterRemovalDelay::get();
+
+        fn on_runtime_upgrade() -> Weight {
+            storage_migrate_on!(StorageVersion, 3, {
+                migration::migrate_v3::<T>();
+            });
+
+            Weight::zero()
+        }
 
         /// Register `target_account` with a new Identity.
         ///
@@ -348,6 +508,7 @@ decl_module! {
             expiry: Option<T::Moment>,
         ) -> DispatchResult {
             let issuer = Self::ensure_signed_and_validate_claim_target(origin, target)?;
+            Self::ensure_claim_issuance_allowed(issuer)?;
 
             match &claim {
                 Claim::CustomerDueDiligence(..) => Self::base_add_cdd_claim(target, claim, issuer, expiry),
@@ -360,6 +521,26 @@ decl_module! {
             }
         }
 
+        /// Adds several claim records, or edits existing ones, in a single call.
+        ///
+        /// Each claim is validated as in `add_claim` and emits its own `ClaimAdded` event,
+        /// while the protocol fee of all the non-CDD claims is charged at once.
+        ///
+        /// # Arguments
+        /// * `claims` The `(target, claim, expiry)` of each claim to add.
+        ///
+        /// # Errors
+        /// * `TooManyClaims` if more than `MAX_CLAIMS_PER_BATCH` claims are given.
+        /// * `DidMustAlreadyExist` if any of the targets doesn't exist.
+        /// * `ClaimVariantNotAllowed` if any of the claims is an `InvestorUniqueness` claim.
+        #[weight = <T as Config>::WeightInfo::add_claims_batch(claims.len() as u32)]
+        pub fn add_claims_batch(
+            origin,
+            claims: Vec<(IdentityId, Claim, Option<T::Moment>)>,
+        ) -> DispatchResult {
+            Self::base_add_claims_batch(origin, claims)
+        }
+
         /// Marks the specified claim as revoked.
         #[weight = (<T as Config>::WeightInfo::revoke_claim(), revoke_claim_class(claim.claim_type()))]
         pub fn revoke_claim(origin, target: IdentityId, claim: Claim) -> DispatchResult {
@@ -369,6 +550,26 @@ decl_module! {
             Self::base_revoke_claim(target, claim_type, issuer, scope)
         }
 
+        /// Schedules the revocation of a claim issued by the caller's identity at a future moment.
+        ///
+        /// The claim expires at `at`, and its removal from `target` is scheduled in the block
+        /// in which `at` is expected to be reached.
+        ///
+        /// # Arguments
+        /// * `target` The identity holding the claim.
+        /// * `claim` The claim to revoke.
+        /// * `at` The moment at which the claim is revoked.
+        ///
+        /// # Errors
+        /// * `ClaimDoesNotExist` if the caller's identity hasn't issued `claim` to `target`.
+        /// * `ClaimVariantNotAllowed` if `claim` is an `InvestorUniqueness` claim.
+        /// * `InvalidClaimRevocationTime` if `at` isn't in the future or is after the claim's expiry.
+        /// * `FailedToScheduleClaimRevocation` if the removal of the claim can't be scheduled.
+        #[weight = <T as Config>::WeightInfo::schedule_claim_revocation()]
+        pub fn schedule_claim_revocation(origin, target: IdentityId, claim: Claim, at: T::Moment) {
+            Self::base_schedule_claim_revocation(origin, target, claim, at)?;
+        }
+
         /// Deprecated. Use `set_secondary_key_permissions` instead.
         #[weight = <T as Config>::WeightInfo::set_secondary_key_permissions_full(&perms)]
         pub fn set_permission_to_signer(origin, key: Signatory<T::AccountId>, perms: Permissions) {
@@ -398,6 +599,84 @@ decl_module! {
             Self::set_frozen_secondary_key_flags(origin, false)
         }
 
+        /// Sets the moment from which a secondary key of the caller's identity loses all its permissions.
+        /// With `None`, the key no longer expires.
+        ///
+        /// # Arguments
+        /// * `key` The secondary key.
+        /// * `expiry` The moment at which the key expires.
+        ///
+        /// # Errors
+        /// * `KeyNotAllowed` if the caller isn't a primary key.
+        /// * `NotASigner` if `key` isn't a secondary key of the caller's identity.
+        /// * `SecondaryKeyExpiryInPast` if `expiry` isn't in the future.
+        #[weight = <T as Config>::WeightInfo::set_secondary_key_expiry()]
+        pub fn set_secondary_key_expiry(origin, key: T::AccountId, expiry: Option<T::Moment>) -> DispatchResult {
+            Self::base_set_secondary_key_expiry(origin, key, expiry)
+        }
+
+        /// Sets the permissions of a secondary key of the caller's identity for `duration` blocks,
+        /// after which its current permissions are restored.
+        ///
+        /// Setting the permissions of the key with `set_secondary_key_permissions` ends the grant,
+        /// and the permissions are then no longer restored.
+        ///
+        /// # Arguments
+        /// * `key` The secondary key.
+        /// * `perms` The permissions granted to the key.
+        /// * `duration` The number of blocks for which the permissions are granted.
+        ///
+        /// # Errors
+        /// * `KeyNotAllowed` if the caller isn't a primary key.
+        /// * `NotASigner` if `key` isn't a secondary key of the caller's identity.
+        /// * `InvalidPermissionGrantDuration` if `duration` is zero.
+        /// * `TemporaryPermissionsAlreadyGranted` if `key` already has a temporary grant.
+        /// * `FailedToSchedulePermissionRevert` if the end of the grant can't be scheduled.
+        #[weight = <T as Config>::WeightInfo::grant_temporary_permissions_full(&perms)]
+        pub fn grant_temporary_permissions(origin, key: T::AccountId, perms: Permissions, duration: T::BlockNumber) -> DispatchResult {
+            Self::base_grant_temporary_permissions(origin, key, perms, duration)
+        }
+
+        /// Enables or disables the audit trail of the caller's identity.
+        ///
+        /// When enabled, a `SecondaryKeyUsed` event is emitted each time a secondary key
+        /// of the identity is permitted to make a call.
+        /// The event is emitted during the dispatch of the call, so it is rolled back
+        /// with the rest of the call's changes when the call fails: the audit trail only
+        /// records the calls that succeeded, not the failed attempts.
+        ///
+        /// # Errors
+        /// * `KeyNotAllowed` if the caller isn't a primary key.
+        #[weight = <T as Config>::WeightInfo::set_key_usage_audit()]
+        pub fn set_key_usage_audit(origin, enabled: bool) -> DispatchResult {
+            Self::base_set_key_usage_audit(origin, enabled)
+        }
+
+        /// Sets the number of blocks during which the secondary keys joining the caller's identity are on probation.
+        ///
+        /// A key on probation is removed from the identity at the end of its probation,
+        /// unless the primary key confirms it with `confirm_secondary_key`.
+        /// With `None`, new keys are no longer put on probation.
+        ///
+        /// # Errors
+        /// * `KeyNotAllowed` if the caller isn't a primary key.
+        /// * `InvalidProbationPeriod` if `period` is zero.
+        #[weight = <T as Config>::WeightInfo::set_key_probation_period()]
+        pub fn set_key_probation_period(origin, period: Option<T::BlockNumber>) -> DispatchResult {
+            Self::base_set_key_probation_period(origin, period)
+        }
+
+        /// Ends the probation of a secondary key of the caller's identity, which is then kept.
+        ///
+        /// # Errors
+        /// * `KeyNotAllowed` if the caller isn't a primary key.
+        /// * `NotASigner` if `key` isn't a secondary key of the caller's identity.
+        /// * `KeyNotOnProbation` if `key` isn't on probation.
+        #[weight = <T as Config>::WeightInfo::confirm_secondary_key()]
+        pub fn confirm_secondary_key(origin, key: T::AccountId) -> DispatchResult {
+            Self::base_confirm_secondary_key(origin, key)
+        }
+
         // Manage generic authorizations
         /// Adds an authorization.
         #[weight = <T as Config>::WeightInfo::add_authorization_full::<T::AccountId>(&data)]
@@ -587,6 +866,506 @@ decl_module! {
             Self::base_add_claim(target_did, cdd_claim, cdd_did, expiry)?;
         }
 
+        /// Pre-authorizes a CDD provider to recover the primary key of the caller's identity,
+        /// or removes the authorization when `cdd_provider` is `None`.
+        ///
+        /// Removing or changing the CDD provider also cancels any pending recovery.
+        ///
+        /// # Arguments
+        /// * `cdd_provider` The CDD provider allowed to recover the primary key.
+        ///
+        /// # Errors
+        /// * `KeyNotAllowed` if the caller isn't the primary key of its identity.
+        /// * `UnAuthorizedCddProvider` if `cdd_provider` isn't a CDD provider.
+        #[weight = <T as Config>::WeightInfo::set_key_recovery_delegate()]
+        pub fn set_key_recovery_delegate(origin, cdd_provider: Option<IdentityId>) {
+            Self::base_set_key_recovery_delegate(origin, cdd_provider)?;
+        }
+
+        /// Initiates the recovery of the primary key of `target`, after an off-chain identity verification.
+        ///
+        /// The caller must be the CDD provider pre-authorized by `target`.
+        /// The recovery can be executed, using `execute_key_recovery`, once the challenge period is over.
+        /// Until then, either `target` or the CDD provider can cancel it.
+        ///
+        /// # Arguments
+        /// * `target` The identity whose primary key is recovered.
+        /// * `new_primary_key` The key that will become the new primary key of `target`.
+        ///
+        /// # Errors
+        /// * `UnAuthorizedCddProvider` if the caller isn't a CDD provider.
+        /// * `KeyRecoveryNotAuthorized` if `target` hasn't pre-authorized the caller.
+        /// * `KeyRecoveryAlreadyPending` if there is already a pending recovery for `target`.
+        /// * `AlreadyLinked` if `new_primary_key` is linked to an identity.
+        #[weight = <T as Config>::WeightInfo::initiate_key_recovery()]
+        pub fn initiate_key_recovery(origin, target: IdentityId, new_primary_key: T::AccountId) {
+            Self::base_initiate_key_recovery(origin, target, new_primary_key)?;
+        }
+
+        /// Cancels the pending recovery of the primary key of `target`.
+        ///
+        /// # Arguments
+        /// * `target` The identity whose primary key recovery is cancelled.
+        ///
+        /// # Errors
+        /// * `NoPendingKeyRecovery` if there is no pending recovery for `target`.
+        /// * `Unauthorized` if the caller is neither `target` nor the CDD provider that initiated the recovery.
+        #[weight = <T as Config>::WeightInfo::cancel_key_recovery()]
+        pub fn cancel_key_recovery(origin, target: IdentityId) {
+            Self::base_cancel_key_recovery(origin, target)?;
+        }
+
+        /// Executes the pending recovery of the primary key of `target`, once its challenge period is over.
+        ///
+        /// The previous primary key is unlinked from `target`.
+        ///
+        /// # Arguments
+        /// * `target` The identity whose primary key is recovered.
+        ///
+        /// # Errors
+        /// * `NoPendingKeyRecovery` if there is no pending recovery for `target`.
+        /// * `Unauthorized` if the caller isn't the CDD provider that initiated the recovery.
+        /// * `KeyRecoveryChallengePeriodNotOver` if the challenge period is not over.
+        #[weight = <T as Config>::WeightInfo::execute_key_recovery()]
+        pub fn execute_key_recovery(origin, target: IdentityId) {
+            Self::base_execute_key_recovery(origin, target)?;
+        }
+
+        /// Sets the guardians allowed to jointly recover the primary key of the caller's identity,
+        /// or removes them with `None`.
+        ///
+        /// Changing the guardians also cancels any pending social recovery.
+        ///
+        /// # Arguments
+        /// * `guardians` The guardian identities and the number of them that must approve a recovery.
+        ///
+        /// # Errors
+        /// * `KeyNotAllowed` if the caller isn't a primary key.
+        /// * `InvalidRecoveryGuardians` if the guardians aren't distinct identities other than the caller's,
+        ///   if there are more than `MAX_RECOVERY_GUARDIANS` of them, or if the threshold can't be reached.
+        /// * `DidDoesNotExist` if a guardian doesn't exist.
+        #[weight = <T as Config>::WeightInfo::set_recovery_guardians(
+            guardians.as_ref().map_or(0, |g| g.guardians.len() as u32)
+        )]
+        pub fn set_recovery_guardians(origin, guardians: Option<RecoveryGuardians>) {
+            Self::base_set_recovery_guardians(origin, guardians)?;
+        }
+
+        /// Approves, as one of its guardians, the recovery of the primary key of `target` to `new_primary_key`.
+        ///
+        /// Each guardian approves a single key: approving another key replaces the caller's approval.
+        /// Once enough guardians have approved the same key, the recovery can be executed
+        /// by that key, using `execute_social_recovery`, after the challenge period.
+        ///
+        /// # Arguments
+        /// * `target` The identity whose primary key is recovered.
+        /// * `new_primary_key` The key that will become the new primary key.
+        ///
+        /// # Errors
+        /// * `NotARecoveryGuardian` if the caller isn't a guardian of `target`.
+        /// * `SocialRecoveryAlreadyInitiated` if a key already reached the guardians' threshold.
+        /// * `SocialRecoveryAlreadyApproved` if the caller already approved `new_primary_key`.
+        /// * `AlreadyLinked` if `new_primary_key` is linked to an identity.
+        #[weight = <T as Config>::WeightInfo::approve_social_recovery()]
+        pub fn approve_social_recovery(origin, target: IdentityId, new_primary_key: T::AccountId) {
+            Self::base_approve_social_recovery(origin, target, new_primary_key)?;
+        }
+
+        /// Cancels the pending social recovery of the primary key of `target`.
+        ///
+        /// `target` cancels the recovery at once, while its guardians approve the cancellation,
+        /// which happens once the guardians' threshold is reached.
+        ///
+        /// # Arguments
+        /// * `target` The identity whose primary key recovery is cancelled.
+        ///
+        /// # Errors
+        /// * `NoPendingKeyRecovery` if there is no pending social recovery for `target`.
+        /// * `Unauthorized` if the caller is neither `target` nor one of its guardians.
+        /// * `SocialRecoveryCancellationAlreadyApproved` if the caller already approved the cancellation.
+        #[weight = <T as Config>::WeightInfo::cancel_social_recovery()]
+        pub fn cancel_social_recovery(origin, target: IdentityId) {
+            Self::base_cancel_social_recovery(origin, target)?;
+        }
+
+        /// Executes the pending social recovery of the primary key of `target`,
+        /// once its challenge period is over.
+        ///
+        /// The caller's key becomes the primary key of `target`, and the old primary key is unlinked.
+        ///
+        /// # Arguments
+        /// * `target` The identity whose primary key is recovered.
+        ///
+        /// # Errors
+        /// * `NoPendingKeyRecovery` if there is no pending social recovery for `target`.
+        /// * `Unauthorized` if the caller isn't the new primary key of the recovery.
+        /// * `SocialRecoveryThresholdNotReached` if not enough guardians approved the recovery.
+        /// * `KeyRecoveryChallengePeriodNotOver` if the challenge period is not over.
+        #[weight = <T as Config>::WeightInfo::execute_social_recovery()]
+        pub fn execute_social_recovery(origin, target: IdentityId) {
+            Self::base_execute_social_recovery(origin, target)?;
+        }
+
+        /// Sets the default maximum number of claims an issuer can add per era.
+        ///
+        /// # Arguments
+        /// * `limit` The maximum number of claims per era, or `None` to remove the default limit.
+        /// * `era_length` The length of an era. Claim issuance is not limited while it is zero.
+        ///
+        /// # Errors
+        /// * `BadOrigin` Only root allowed.
+        #[weight = <T as Config>::WeightInfo::set_claim_issuance_limit()]
+        pub fn set_claim_issuance_limit(origin, limit: Option<u32>, era_length: T::Moment) {
+            ensure_root(origin)?;
+            DefaultClaimIssuanceLimit::set(limit);
+            <ClaimIssuanceEraLength<T>>::put(era_length);
+            Self::deposit_event(RawEvent::ClaimIssuanceLimitSet(limit, era_length));
+        }
+
+        /// Overrides the maximum number of claims `issuer` can add per era, e.g. to allow a large
+        /// onboarding or to throttle a suspicious issuer.
+        ///
+        /// # Arguments
+        /// * `issuer` The claim issuer.
+        /// * `limit` The maximum number of claims per era, or `None` to use the default limit.
+        ///
+        /// # Errors
+        /// * `BadOrigin` Only root allowed.
+        #[weight = <T as Config>::WeightInfo::set_issuer_claim_issuance_limit()]
+        pub fn set_issuer_claim_issuance_limit(origin, issuer: IdentityId, limit: Option<u32>) {
+            ensure_root(origin)?;
+            IssuerClaimIssuanceLimits::set(issuer, limit);
+            Self::deposit_event(RawEvent::IssuerClaimIssuanceLimitSet(issuer, limit));
+        }
+
+        /// Restricts the caller's identity, including its primary key, from making the calls
+        /// matched by `filter`, e.g. all the calls of a pallet.
+        ///
+        /// The filter can only be removed, using `remove_call_filter`, once the removal delay
+        /// started by `request_call_filter_removal` is over.
+        ///
+        /// # Arguments
+        /// * `filter` The call, or all the calls of a pallet, to filter.
+        ///
+        /// # Errors
+        /// * `KeyNotAllowed` if the caller isn't the primary key of its identity.
+        /// * `TooLong` if the pallet or dispatchable name of `filter` is too long.
+        /// * `CallFilterAlreadyAdded` if the identity already has `filter`.
+        #[weight = <T as Config>::WeightInfo::add_call_filter()]
+        pub fn add_call_filter(origin, filter: CallFilter) {
+            Self::base_add_call_filter(origin, filter)?;
+        }
+
+        /// Requests the removal of one of the call filters of the caller's identity.
+        /// The filter can be removed once `CallFilterRemovalDelay` has passed.
+        ///
+        /// # Arguments
+        /// * `filter` The call filter to remove.
+        ///
+        /// # Errors
+        /// * `KeyNotAllowed` if the caller isn't the primary key of its identity.
+        /// * `CallFilterNotFound` if the identity doesn't have `filter`.
+        /// * `CallFilterRemovalAlreadyRequested` if the removal of `filter` was already requested.
+        #[weight = <T as Config>::WeightInfo::request_call_filter_removal()]
+        pub fn request_call_filter_removal(origin, filter: CallFilter) {
+            Self::base_request_call_filter_removal(origin, filter)?;
+        }
+
+        /// Removes one of the call filters of the caller's identity, once its removal delay is over.
+        ///
+        /// # Arguments
+        /// * `filter` The call filter to remove.
+        ///
+        /// # Errors
+        /// * `KeyNotAllowed` if the caller isn't the primary key of its identity.
+        /// * `CallFilterRemovalNotRequested` if the removal of `filter` wasn't requested.
+        /// * `CallFilterRemovalDelayNotOver` if the removal delay of `filter` is not over.
+        #[weight = <T as Config>::WeightInfo::remove_call_filter()]
+        pub fn remove_call_filter(origin, filter: CallFilter) {
+            Self::base_remove_call_filter(origin, filter)?;
+        }
+
+        /// Publishes an agreement, e.g. the terms of a CDD provider or of a distribution, that
+        /// identities can consent to. The agreement gets the next id of the caller's identity.
+        ///
+        /// # Arguments
+        /// * `hash` The hash of the agreement's document.
+        #[weight = <T as Config>::WeightInfo::publish_agreement()]
+        pub fn publish_agreement(origin, hash: H256) {
+            Self::base_publish_agreement(origin, hash)?;
+        }
+
+        /// Publishes a new version of one of the caller's agreements.
+        /// Consents given to the previous versions no longer cover the agreement.
+        ///
+        /// # Arguments
+        /// * `id` The id of the agreement.
+        /// * `hash` The hash of the new version of the agreement's document.
+        ///
+        /// # Errors
+        /// * `AgreementNotFound` if the caller's identity hasn't published the agreement `id`.
+        #[weight = <T as Config>::WeightInfo::update_agreement()]
+        pub fn update_agreement(origin, id: AgreementId, hash: H256) {
+            Self::base_update_agreement(origin, id, hash)?;
+        }
+
+        /// Records the consent of the caller's identity to a version of an agreement.
+        /// Consenting again replaces the previous consent.
+        ///
+        /// # Arguments
+        /// * `publisher` The identity that published the agreement.
+        /// * `id` The id of the agreement.
+        /// * `version` The version of the agreement, which must be its current one.
+        ///
+        /// # Errors
+        /// * `AgreementNotFound` if `publisher` hasn't published the agreement `id`.
+        /// * `AgreementVersionMismatch` if `version` isn't the current version of the agreement.
+        #[weight = <T as Config>::WeightInfo::give_consent()]
+        pub fn give_consent(origin, publisher: IdentityId, id: AgreementId, version: u32) {
+            Self::base_give_consent(origin, publisher, id, version)?;
+        }
+
+        /// Revokes the consent of the caller's identity to an agreement.
+        ///
+        /// # Arguments
+        /// * `publisher` The identity that published the agreement.
+        /// * `id` The id of the agreement.
+        ///
+        /// # Errors
+        /// * `ConsentNotFound` if the caller's identity hasn't consented to the agreement.
+        #[weight = <T as Config>::WeightInfo::revoke_consent()]
+        pub fn revoke_consent(origin, publisher: IdentityId, id: AgreementId) {
+            Self::base_revoke_consent(origin, publisher, id)?;
+        }
+
+        /// Allows `parent` to link the caller's identity as its child, or revokes the approval with `None`.
+        ///
+        /// # Errors
+        /// * `KeyNotAllowed` if the caller isn't a primary key.
+        /// * `InvalidParentIdentity` if `parent` is the caller's identity.
+        /// * `DidDoesNotExist` if `parent` doesn't exist.
+        #[weight = <T as Config>::WeightInfo::approve_parent_identity()]
+        pub fn approve_parent_identity(origin, parent: Option<IdentityId>) {
+            Self::base_approve_parent_identity(origin, parent)?;
+        }
+
+        /// Links `child` as a child of the caller's identity.
+        ///
+        /// The parent identity can then administer the secondary keys of the child.
+        ///
+        /// # Arguments
+        /// * `child` The child identity, which must have approved the caller's identity as its parent.
+        /// * `inherit_claims` Whether the claims of the caller's identity also apply to the child.
+        ///   CDD claims are never inherited, so the child must hold its own CDD claim.
+        ///
+        /// # Errors
+        /// * `KeyNotAllowed` if the caller isn't a primary key.
+        /// * `ParentIdentityNotApproved` if `child` hasn't approved the caller's identity.
+        /// * `ChildIdentityAlreadyLinked` if `child` already has a parent.
+        /// * `NestedChildIdentity` if `child` has children, or if the caller's identity has a parent.
+        #[weight = <T as Config>::WeightInfo::link_child_identity()]
+        pub fn link_child_identity(origin, child: IdentityId, inherit_claims: bool) {
+            Self::base_link_child_identity(origin, child, inherit_claims)?;
+        }
+
+        /// Creates a child identity of the caller's identity, whose primary key is `key`.
+        ///
+        /// # Arguments
+        /// * `key` A secondary key of the caller's identity, which becomes the primary key of the child.
+        /// * `inherit_claims` Whether the claims of the caller's identity also apply to the child.
+        ///   CDD claims are never inherited, so the child must get its own CDD claim before it can transact.
+        ///
+        /// # Errors
+        /// * `KeyNotAllowed` if the caller isn't a primary key.
+        /// * `NestedChildIdentity` if the caller's identity has a parent.
+        /// * `NotASigner` if `key` isn't a secondary key of the caller's identity.
+        #[weight = <T as Config>::WeightInfo::create_child_identity()]
+        pub fn create_child_identity(origin, key: T::AccountId, inherit_claims: bool) {
+            Self::base_create_child_identity(origin, key, inherit_claims)?;
+        }
+
+        /// Unlinks `child` from its parent. Either the parent or the child can unlink it.
+        ///
+        /// # Errors
+        /// * `KeyNotAllowed` if the caller isn't a primary key.
+        /// * `NotParentIdentity` if the caller's identity is neither `child` nor its parent.
+        #[weight = <T as Config>::WeightInfo::unlink_child_identity()]
+        pub fn unlink_child_identity(origin, child: IdentityId) {
+            Self::base_unlink_child_identity(origin, child)?;
+        }
+
+        /// Sets the permissions of `key`, a secondary key of `child`, a child of the caller's identity.
+        ///
+        /// # Errors
+        /// * `NotParentIdentity` if the caller's identity isn't the parent of `child`.
+        /// * `NotASigner` if `key` isn't a secondary key of `child`.
+        #[weight = <T as Config>::WeightInfo::set_child_secondary_key_permissions_full(&perms)]
+        pub fn set_child_secondary_key_permissions(origin, child: IdentityId, key: T::AccountId, perms: Permissions) {
+            Self::base_set_child_secondary_key_permissions(origin, child, key, perms)?;
+        }
+
+        /// Removes secondary keys of `child`, a child of the caller's identity.
+        ///
+        /// # Errors
+        /// * `NotParentIdentity` if the caller's identity isn't the parent of `child`.
+        /// * `NotASigner` if a key isn't a secondary key of `child`.
+        #[weight = <T as Config>::WeightInfo::remove_child_secondary_keys(keys.len() as u32)]
+        pub fn remove_child_secondary_keys(origin, child: IdentityId, keys: Vec<T::AccountId>) {
+            Self::base_remove_child_secondary_keys(origin, child, keys)?;
+        }
+
+        /// Freezes or unfreezes the secondary keys of `child`, a child of the caller's identity.
+        ///
+        /// # Errors
+        /// * `NotParentIdentity` if the caller's identity isn't the parent of `child`.
+        #[weight = <T as Config>::WeightInfo::set_child_secondary_keys_frozen()]
+        pub fn set_child_secondary_keys_frozen(origin, child: IdentityId, freeze: bool) {
+            Self::base_set_child_secondary_keys_frozen(origin, child, freeze)?;
+        }
+
+        /// Register custom claim type with the schema that the payloads of its claims must match.
+        ///
+        /// # Errors
+        /// * `CustomClaimTypeAlreadyExists` The type that is being registered already exists.
+        /// * `CounterOverflow` CustomClaimTypeId has overflowed.
+        /// * `TooLong` The type, a field name or a field length is too long, or there are too many fields.
+        /// * `InvalidCustomClaimSchema` Two fields of the schema have the same name.
+        #[weight = <T as Config>::WeightInfo::register_custom_claim_type_with_schema(ty.len() as u32, schema.fields.len() as u32)]
+        pub fn register_custom_claim_type_with_schema(origin, ty: Vec<u8>, schema: CustomClaimSchema) {
+            Self::base_register_custom_claim_type_with_schema(origin, ty, schema)?;
+        }
+
+        /// Delegates the issuance of `claim_types` claims on behalf of the caller's identity to `delegate`.
+        /// Compliance conditions trusting the caller's identity also accept such claims issued by `delegate`.
+        /// The delegation to `delegate` is replaced, or revoked when `claim_types` is empty.
+        ///
+        /// # Errors
+        /// * `InvalidClaimIssuanceDelegate` The caller's identity is `delegate`.
+        /// * `DidDoesNotExist` `delegate` doesn't exist.
+        /// * `TooLong` Too many claim types.
+        /// * `TooManyClaimIssuanceDelegates` The caller's identity already has `MAX_CLAIM_ISSUANCE_DELEGATES` delegates.
+        #[weight = <T as Config>::WeightInfo::delegate_claim_issuance(claim_types.len() as u32)]
+        pub fn delegate_claim_issuance(origin, delegate: IdentityId, claim_types: Vec<ClaimType>) {
+            Self::base_delegate_claim_issuance(origin, delegate, claim_types)?;
+        }
+
+        /// Removes the expired authorizations of `target` found by scanning at most `max_scanned`
+        /// of its authorizations after `start_after`, at most `MAX_AUTHORIZATIONS_PER_BATCH` per call.
+        /// Any identity can clean up expired authorizations, for free if every scanned one was removed.
+        ///
+        /// The emitted event reports the last scanned authorization, to resume the removal after it.
+        #[weight = <T as Config>::WeightInfo::remove_expired_authorizations((*max_scanned).min(MAX_AUTHORIZATIONS_PER_BATCH))]
+        pub fn remove_expired_authorizations(
+            origin,
+            target: Signatory<T::AccountId>,
+            start_after: Option<u64>,
+            max_scanned: u32
+        ) -> DispatchResultWithPostInfo {
+            Self::base_remove_expired_authorizations(origin, target, start_after, max_scanned)
+        }
+
+        /// Accepts the authorizations `auth_ids` of the caller's identity in one call.
+        /// Either all of them are accepted, or none is.
+        ///
+        /// Only ticker transfer, asset ownership transfer and portfolio custody authorizations are supported.
+        ///
+        /// # Errors
+        /// * `TooLong` More than `MAX_AUTHORIZATIONS_PER_BATCH` authorizations.
+        /// * `UnsupportedBatchAuthorization` An authorization of another type.
+        #[weight = <T as Config>::WeightInfo::consume_authorizations_batch(auth_ids.len() as u32)]
+        pub fn consume_authorizations_batch(origin, auth_ids: Vec<u64>) {
+            Self::base_consume_authorizations_batch(origin, auth_ids)?;
+        }
+
+        /// Removes the claim whose revocation was scheduled at `at` by `schedule_claim_revocation`.
+        /// The claim is kept if it was re-issued since, and its removal is rescheduled if `at` isn't reached yet.
+        ///
+        /// Only called by the scheduler.
+        #[weight = <T as Config>::WeightInfo::revoke_scheduled_claim()]
+        pub fn revoke_scheduled_claim(
+            origin,
+            target: IdentityId,
+            claim_type: ClaimType,
+            issuer: IdentityId,
+            scope: Option<Scope>,
+            at: T::Moment
+        ) {
+            ensure_root(origin)?;
+            Self::base_revoke_scheduled_claim(target, claim_type, issuer, scope, at);
+        }
+
+        /// Restores the permissions of `key` when its temporary grant, made by `grant_temporary_permissions`,
+        /// ends at `revert_at`. Grants that were replaced since are left unchanged.
+        ///
+        /// Only called by the scheduler.
+        #[weight = <T as Config>::WeightInfo::revert_temporary_permissions()]
+        pub fn revert_temporary_permissions(origin, key: T::AccountId, revert_at: T::BlockNumber) {
+            ensure_root(origin)?;
+            Self::base_revert_temporary_permissions(key, revert_at);
+        }
+
+        /// Removes `key` from its identity when its probation ends at `ends_at` without confirmation.
+        /// Keys that were confirmed or removed since are left unchanged.
+        ///
+        /// Only called by the scheduler.
+        #[weight = <T as Config>::WeightInfo::end_key_probation()]
+        pub fn end_key_probation(origin, key: T::AccountId, ends_at: T::BlockNumber) {
+            ensure_root(origin)?;
+            Self::base_end_key_probation(key, ends_at);
+        }
+
+        /// Starts an import of `claim`, issued by the caller's identity, to `total_entries` targets.
+        /// The targets are then given the claim in batches by `import_claim_batch`, and the import
+        /// is completed by `finalize_claim_import` once the ordered list of all targets hashes to
+        /// `expected_hash`, as computed by `BatchImport::add_entry`.
+        ///
+        /// # Errors
+        /// * `ClaimImportInProgress` The caller's identity already has an import of `claim`.
+        /// * `ClaimVariantNotAllowed` `claim` is an `InvestorUniqueness` claim.
+        /// * `UnAuthorizedCddProvider` `claim` is a CDD claim and the caller's identity isn't a CDD provider.
+        /// * `CustomScopeTooLong` The custom scope of `claim` is too long.
+        #[weight = <T as Config>::WeightInfo::start_claim_import()]
+        pub fn start_claim_import(origin, claim: Claim, expiry: Option<T::Moment>, total_entries: u32, expected_hash: H256) {
+            Self::base_start_claim_import(origin, claim, expiry, total_entries, expected_hash)?;
+        }
+
+        /// Adds the claim of an ongoing import to the next batch of its `targets`, as in `add_claims_batch`.
+        ///
+        /// The batch is rejected, and none of its targets given the claim, unless the running hash
+        /// of the import once the batch is folded in is `batch_hash`.
+        ///
+        /// # Errors
+        /// * `TooManyClaims` More than `MAX_CLAIMS_PER_BATCH` targets are given.
+        /// * `ClaimImportNotFound` The caller's identity has no import of `claim`.
+        /// * `ClaimImportOverflow` More targets are imported than announced by `start_claim_import`.
+        /// * `ClaimBatchHashMismatch` The running hash of the import after the batch isn't `batch_hash`.
+        /// * `DidMustAlreadyExist` Any of the targets doesn't exist.
+        /// * `ClaimIssuanceLimitReached` The caller's identity reached its claim issuance limit.
+        #[weight = <T as Config>::WeightInfo::import_claim_batch(targets.len() as u32)]
+        pub fn import_claim_batch(origin, claim: Claim, targets: Vec<IdentityId>, batch_hash: H256) {
+            Self::base_import_claim_batch(origin, claim, targets, batch_hash)?;
+        }
+
+        /// Completes an import of `claim`, after verifying its integrity.
+        ///
+        /// # Errors
+        /// * `ClaimImportNotFound` The caller's identity has no import of `claim`.
+        /// * `ClaimImportIncomplete` Not all the targets of the import were imported.
+        /// * `ClaimImportHashMismatch` The imported targets don't match the expected hash.
+        #[weight = <T as Config>::WeightInfo::finalize_claim_import()]
+        pub fn finalize_claim_import(origin, claim: Claim) {
+            Self::base_finalize_claim_import(origin, claim)?;
+        }
+
+        /// Aborts an ongoing import of `claim`.
+        /// Targets given the claim by previous batches keep it, it can be revoked with `revoke_claim`.
+        ///
+        /// # Errors
+        /// * `ClaimImportNotFound` The caller's identity has no import of `claim`.
+        #[weight = <T as Config>::WeightInfo::abort_claim_import()]
+        pub fn abort_claim_import(origin, claim: Claim) {
+            Self::base_abort_claim_import(origin, claim)?;
+        }
     }
 }
 
@@ -665,6 +1444,99 @@ decl_error! {
         CustomClaimTypeDoesNotExist,
         /// Claim does not exist.
         ClaimDoesNotExist,
+        /// The identity hasn't authorized the CDD provider to recover its primary key.
+        KeyRecoveryNotAuthorized,
+        /// There is already a pending primary key recovery for the identity.
+        KeyRecoveryAlreadyPending,
+        /// There is no pending primary key recovery for the identity.
+        NoPendingKeyRecovery,
+        /// The challenge period of the primary key recovery is not over.
+        KeyRecoveryChallengePeriodNotOver,
+        /// The recovery guardians are duplicated, too many, include the identity itself,
+        /// or their threshold can't be reached.
+        InvalidRecoveryGuardians,
+        /// The caller isn't a recovery guardian of the identity.
+        NotARecoveryGuardian,
+        /// A key already reached the guardians' threshold and the social recovery is pending its challenge period.
+        SocialRecoveryAlreadyInitiated,
+        /// The guardian already approved the pending social recovery.
+        SocialRecoveryAlreadyApproved,
+        /// Not enough guardians approved the social recovery.
+        SocialRecoveryThresholdNotReached,
+        /// The issuer has reached its claim issuance limit for the current era.
+        ClaimIssuanceLimitReached,
+        /// The score of a `RiskScore` claim is above `MAX_RISK_SCORE`.
+        InvalidRiskScore,
+        /// The identity already has the call filter.
+        CallFilterAlreadyAdded,
+        /// The identity doesn't have the call filter.
+        CallFilterNotFound,
+        /// The removal of the call filter was already requested.
+        CallFilterRemovalAlreadyRequested,
+        /// The removal of the call filter wasn't requested.
+        CallFilterRemovalNotRequested,
+        /// The removal delay of the call filter is not over.
+        CallFilterRemovalDelayNotOver,
+        /// The agreement doesn't exist.
+        AgreementNotFound,
+        /// The version isn't the current version of the agreement.
+        AgreementVersionMismatch,
+        /// The identity hasn't consented to the agreement.
+        ConsentNotFound,
+        /// Too many claims were given to `add_claims_batch`.
+        TooManyClaims,
+        /// The revocation time isn't in the future or is after the claim's expiry.
+        InvalidClaimRevocationTime,
+        /// The expiry of a secondary key isn't in the future.
+        SecondaryKeyExpiryInPast,
+        /// The duration of a temporary permission grant is zero.
+        InvalidPermissionGrantDuration,
+        /// The secondary key already has temporary permissions.
+        TemporaryPermissionsAlreadyGranted,
+        /// The end of a temporary permission grant couldn't be scheduled.
+        FailedToSchedulePermissionRevert,
+        /// The probation period of secondary keys is zero.
+        InvalidProbationPeriod,
+        /// The secondary key isn't on probation.
+        KeyNotOnProbation,
+        /// An identity can't be its own parent.
+        InvalidParentIdentity,
+        /// The child identity hasn't approved the caller's identity as its parent.
+        ParentIdentityNotApproved,
+        /// The child identity already has a parent.
+        ChildIdentityAlreadyLinked,
+        /// Child identities can't have children themselves.
+        NestedChildIdentity,
+        /// The caller's identity isn't the parent of the child identity.
+        NotParentIdentity,
+        /// Two fields of a custom claim schema have the same name.
+        InvalidCustomClaimSchema,
+        /// The payload of a custom claim doesn't match the schema of its type.
+        CustomClaimPayloadMismatch,
+        /// A custom claim with a payload has a type without a schema.
+        CustomClaimSchemaNotFound,
+        /// An identity can't delegate claim issuance to itself.
+        InvalidClaimIssuanceDelegate,
+        /// The authorization can't be accepted along with others.
+        UnsupportedBatchAuthorization,
+        /// The removal of a claim couldn't be scheduled.
+        FailedToScheduleClaimRevocation,
+        /// The guardian already approved the cancellation of the pending social recovery.
+        SocialRecoveryCancellationAlreadyApproved,
+        /// The identity already delegated the issuance of claims to `MAX_CLAIM_ISSUANCE_DELEGATES` identities.
+        TooManyClaimIssuanceDelegates,
+        /// The identity already has an import of the claim.
+        ClaimImportInProgress,
+        /// The identity has no import of the claim.
+        ClaimImportNotFound,
+        /// More targets were imported than announced when the import was started.
+        ClaimImportOverflow,
+        /// The running hash of the claim import after the batch doesn't match the given hash.
+        ClaimBatchHashMismatch,
+        /// Not all the targets of the claim import were imported.
+        ClaimImportIncomplete,
+        /// The imported targets don't match the expected hash of the claim import.
+        ClaimImportHashMismatch,
     }
 }
 
@@ -701,6 +1573,30 @@ impl<T: Config> Module<T> {
             .collect()
     }
 
+    /// Returns the status of each of `dids`, with the details of its valid CDD claim expiring last.
+    pub fn get_did_status_details(dids: Vec<IdentityId>) -> Vec<DidStatusDetails> {
+        dids.into_iter()
+            .map(|did| {
+                let cdd = Self::base_fetch_valid_cdd_claims(did, T::Moment::zero(), None)
+                    // Claims that never expire come last.
+                    .max_by_key(|claim| claim.expiry.unwrap_or(Moment::MAX))
+                    .map(|claim| CddClaimDetails {
+                        issuer: claim.claim_issuer,
+                        issuance_date: claim.issuance_date,
+                        expiry: claim.expiry,
+                    });
+                let status = if !DidRecords::<T>::contains_key(did) {
+                    DidStatus::Unknown
+                } else if cdd.is_some() || Self::has_valid_cdd(did) {
+                    DidStatus::CddVerified
+                } else {
+                    DidStatus::Exists
+                };
+                DidStatusDetails { status, cdd }
+            })
+            .collect()
+    }
+
     #[cfg(feature = "runtime-benchmarks")]
     /// Links a did with an identity
     pub fn link_did(account: T::AccountId, did: IdentityId) {
@@ -752,6 +1648,11 @@ impl<T: Config> IdentityFnTrait<T::AccountId> for Module<T> {
     fn has_valid_cdd(target_did: IdentityId) -> bool {
         Self::has_valid_cdd(target_did)
     }
+
+    /// Checks that `target_did` holds an unscoped `claim_type` claim issued by `issuer`.
+    fn has_valid_claim(target_did: IdentityId, claim_type: ClaimType, issuer: IdentityId) -> bool {
+        Self::fetch_claim(target_did, claim_type, issuer, None).is_some()
+    }
 }
 
 impl<T: Config> ChangeMembers<IdentityId> for Module<T> {
@@ -783,3 +1684,24 @@ fn revoke_claim_class(claim_type: ClaimType) -> frame_support::dispatch::Dispatc
         _ => Normal,
     }
 }
+
+pub mod migration {
+    use super::*;
+    use frame_support::{IterableStorageDoubleMap, StorageValue};
+
+    pub fn migrate_v3<T: Config>() {
+        sp_runtime::runtime_logger::RuntimeLogger::init();
+
+        log::info!(" >>> Updating Identity storage. Counting claims and authorizations...");
+        let claims = Claims::iter_keys().count() as u64;
+        ClaimCount::put(claims);
+        let authorizations = <Authorizations<T>>::iter_keys().count() as u64;
+        AuthorizationCount::put(authorizations);
+
+        log::info!(
+            " >>> Counted {} claims and {} authorizations.",
+            claims,
+            authorizations
+        );
+    }
+}
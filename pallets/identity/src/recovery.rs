@@ -0,0 +1,313 @@
+// This file is part of the Polymesh distribution (https://github.com/PolymeshAssociation/Polymesh).
+// Copyright (c) 2020 Polymath
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, version 3.
+
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+// General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+use crate::{
+    Config, Error, KeyRecovery, KeyRecoveryDelegates, Module, PendingKeyRecoveries,
+    PendingSocialRecoveries, RawEvent, RecoveryGuardianSets,
+};
+use frame_support::dispatch::DispatchResult;
+use frame_support::{ensure, traits::Get, StorageMap};
+use frame_system::ensure_signed;
+use polymesh_primitives::{IdentityId, RecoveryGuardians};
+use sp_runtime::traits::Saturating;
+
+/// The maximum number of recovery guardians of an identity.
+pub const MAX_RECOVERY_GUARDIANS: u32 = 10;
+
+impl<T: Config> Module<T> {
+    /// Sets or removes the CDD provider allowed to recover the caller's primary key.
+    pub(crate) fn base_set_key_recovery_delegate(
+        origin: T::RuntimeOrigin,
+        cdd_provider: Option<IdentityId>,
+    ) -> DispatchResult {
+        let (_, did) = Self::ensure_primary_key(origin)?;
+        if let Some(cdd_provider) = cdd_provider {
+            Self::ensure_authorized_cdd_provider(cdd_provider)?;
+        }
+
+        // A pending recovery is only valid for the CDD provider that initiated it.
+        if let Some(recovery) = PendingKeyRecoveries::<T>::get(did) {
+            if Some(recovery.cdd_provider) != cdd_provider {
+                PendingKeyRecoveries::<T>::remove(did);
+                Self::deposit_event(RawEvent::KeyRecoveryCancelled(did, did));
+            }
+        }
+        KeyRecoveryDelegates::mutate(did, |delegate| *delegate = cdd_provider);
+        Self::deposit_event(RawEvent::KeyRecoveryDelegateSet(did, cdd_provider));
+        Ok(())
+    }
+
+    /// Starts the challenge period of the recovery of `target`'s primary key.
+    pub(crate) fn base_initiate_key_recovery(
+        origin: T::RuntimeOrigin,
+        target: IdentityId,
+        new_primary_key: T::AccountId,
+    ) -> DispatchResult {
+        let cdd_provider = Self::ensure_perms(origin)?;
+        Self::ensure_authorized_cdd_provider(cdd_provider)?;
+        ensure!(
+            Self::key_recovery_delegate(target) == Some(cdd_provider),
+            Error::<T>::KeyRecoveryNotAuthorized
+        );
+        ensure!(
+            !PendingKeyRecoveries::<T>::contains_key(target),
+            Error::<T>::KeyRecoveryAlreadyPending
+        );
+        Self::ensure_key_did_unlinked(&new_primary_key)?;
+
+        let now = <pallet_timestamp::Pallet<T>>::get();
+        let executable_at = now.saturating_add(T::KeyRecoveryChallengePeriod::get());
+        PendingKeyRecoveries::<T>::insert(
+            target,
+            KeyRecovery {
+                cdd_provider,
+                new_primary_key: new_primary_key.clone(),
+                executable_at,
+            },
+        );
+        Self::deposit_event(RawEvent::KeyRecoveryInitiated(
+            target,
+            cdd_provider,
+            new_primary_key,
+            executable_at,
+        ));
+        Ok(())
+    }
+
+    /// Cancels the pending recovery of `target`'s primary key.
+    pub(crate) fn base_cancel_key_recovery(
+        origin: T::RuntimeOrigin,
+        target: IdentityId,
+    ) -> DispatchResult {
+        let did = Self::ensure_perms(origin)?;
+        let recovery =
+            Self::pending_key_recovery(target).ok_or(Error::<T>::NoPendingKeyRecovery)?;
+        ensure!(
+            did == target || did == recovery.cdd_provider,
+            Error::<T>::Unauthorized
+        );
+
+        PendingKeyRecoveries::<T>::remove(target);
+        Self::deposit_event(RawEvent::KeyRecoveryCancelled(target, did));
+        Ok(())
+    }
+
+    /// Rotates `target`'s primary key once the recovery's challenge period is over.
+    pub(crate) fn base_execute_key_recovery(
+        origin: T::RuntimeOrigin,
+        target: IdentityId,
+    ) -> DispatchResult {
+        let cdd_provider = Self::ensure_perms(origin)?;
+        let recovery =
+            Self::pending_key_recovery(target).ok_or(Error::<T>::NoPendingKeyRecovery)?;
+        ensure!(
+            cdd_provider == recovery.cdd_provider,
+            Error::<T>::Unauthorized
+        );
+        Self::ensure_authorized_cdd_provider(cdd_provider)?;
+        let now = <pallet_timestamp::Pallet<T>>::get();
+        ensure!(
+            now >= recovery.executable_at,
+            Error::<T>::KeyRecoveryChallengePeriodNotOver
+        );
+
+        let new_primary_key = recovery.new_primary_key;
+        Self::ensure_key_did_unlinked(&new_primary_key)?;
+        let old_primary_key = Self::get_primary_key(target).unwrap_or_default();
+        Self::ensure_key_unlinkable_from_did(&old_primary_key)?;
+
+        PendingKeyRecoveries::<T>::remove(target);
+        Self::unsafe_rotate_primary_key(
+            target,
+            old_primary_key,
+            new_primary_key.clone(),
+            false,
+            None,
+        );
+        Self::deposit_event(RawEvent::KeyRecoveryExecuted(
+            target,
+            cdd_provider,
+            new_primary_key,
+        ));
+        Ok(())
+    }
+
+    /// Sets or removes the guardians allowed to jointly recover the caller's primary key.
+    pub(crate) fn base_set_recovery_guardians(
+        origin: T::RuntimeOrigin,
+        guardians: Option<RecoveryGuardians>,
+    ) -> DispatchResult {
+        let (_, did) = Self::ensure_primary_key(origin)?;
+        if let Some(guardians) = &guardians {
+            Self::ensure_valid_recovery_guardians(did, guardians)?;
+        }
+
+        // Approvals are only valid for the guardians that gave them.
+        if PendingSocialRecoveries::<T>::take(did).is_some() {
+            Self::deposit_event(RawEvent::SocialRecoveryCancelled(did, did));
+        }
+        RecoveryGuardianSets::mutate(did, |set| *set = guardians.clone());
+        Self::deposit_event(RawEvent::RecoveryGuardiansSet(did, guardians));
+        Ok(())
+    }
+
+    /// Ensures that `guardians` are distinct existing identities other than `did`,
+    /// with a threshold that can be reached.
+    fn ensure_valid_recovery_guardians(
+        did: IdentityId,
+        guardians: &RecoveryGuardians,
+    ) -> DispatchResult {
+        let count = guardians.guardians.len();
+        let mut distinct = guardians.guardians.clone();
+        distinct.sort();
+        distinct.dedup();
+        ensure!(
+            count <= MAX_RECOVERY_GUARDIANS as usize
+                && distinct.len() == count
+                && guardians.threshold > 0
+                && guardians.threshold as usize <= count
+                && !distinct.contains(&did),
+            Error::<T>::InvalidRecoveryGuardians
+        );
+        for guardian in distinct {
+            Self::ensure_id_record_exists(guardian)?;
+        }
+        Ok(())
+    }
+
+    /// Approves the recovery of `target`'s primary key by one of its guardians.
+    ///
+    /// The challenge period starts once the guardians' threshold is reached.
+    pub(crate) fn base_approve_social_recovery(
+        origin: T::RuntimeOrigin,
+        target: IdentityId,
+        new_primary_key: T::AccountId,
+    ) -> DispatchResult {
+        let guardian = Self::ensure_perms(origin)?;
+        let guardians = Self::recovery_guardians(target).ok_or(Error::<T>::NotARecoveryGuardian)?;
+        ensure!(
+            guardians.guardians.contains(&guardian),
+            Error::<T>::NotARecoveryGuardian
+        );
+
+        let mut recovery = Self::pending_social_recovery(target).unwrap_or_default();
+        ensure!(
+            recovery.executable.is_none(),
+            Error::<T>::SocialRecoveryAlreadyInitiated
+        );
+        ensure!(
+            !recovery
+                .approvals
+                .contains(&(guardian, new_primary_key.clone())),
+            Error::<T>::SocialRecoveryAlreadyApproved
+        );
+        Self::ensure_key_did_unlinked(&new_primary_key)?;
+
+        // A guardian approves a single key, so approving another key replaces its approval.
+        recovery.approvals.retain(|(did, _)| *did != guardian);
+        recovery.approvals.push((guardian, new_primary_key.clone()));
+        let approvals = recovery
+            .approvals
+            .iter()
+            .filter(|(_, key)| *key == new_primary_key)
+            .count() as u32;
+        Self::deposit_event(RawEvent::SocialRecoveryApproved(
+            target,
+            guardian,
+            new_primary_key.clone(),
+            approvals,
+        ));
+
+        if approvals >= guardians.threshold {
+            let now = <pallet_timestamp::Pallet<T>>::get();
+            let executable_at = now.saturating_add(T::KeyRecoveryChallengePeriod::get());
+            recovery.executable = Some((new_primary_key.clone(), executable_at));
+            Self::deposit_event(RawEvent::SocialRecoveryInitiated(
+                target,
+                new_primary_key,
+                executable_at,
+            ));
+        }
+        PendingSocialRecoveries::<T>::insert(target, recovery);
+        Ok(())
+    }
+
+    /// Cancels the pending social recovery of `target`'s primary key,
+    /// or approves its cancellation by one of `target`'s guardians.
+    ///
+    /// A recovery is cancelled by `target`, or once the guardians' threshold approved its cancellation.
+    pub(crate) fn base_cancel_social_recovery(
+        origin: T::RuntimeOrigin,
+        target: IdentityId,
+    ) -> DispatchResult {
+        let did = Self::ensure_perms(origin)?;
+        let mut recovery =
+            Self::pending_social_recovery(target).ok_or(Error::<T>::NoPendingKeyRecovery)?;
+
+        if did != target {
+            let guardians = Self::recovery_guardians(target)
+                .filter(|guardians| guardians.guardians.contains(&did))
+                .ok_or(Error::<T>::Unauthorized)?;
+            ensure!(
+                !recovery.cancellations.contains(&did),
+                Error::<T>::SocialRecoveryCancellationAlreadyApproved
+            );
+            recovery.cancellations.push(did);
+            let cancellations = recovery.cancellations.len() as u32;
+            Self::deposit_event(RawEvent::SocialRecoveryCancellationApproved(
+                target,
+                did,
+                cancellations,
+            ));
+            if cancellations < guardians.threshold {
+                PendingSocialRecoveries::<T>::insert(target, recovery);
+                return Ok(());
+            }
+        }
+
+        PendingSocialRecoveries::<T>::remove(target);
+        Self::deposit_event(RawEvent::SocialRecoveryCancelled(target, did));
+        Ok(())
+    }
+
+    /// Rotates `target`'s primary key to the caller's key once the social recovery's
+    /// challenge period is over.
+    pub(crate) fn base_execute_social_recovery(
+        origin: T::RuntimeOrigin,
+        target: IdentityId,
+    ) -> DispatchResult {
+        let sender = ensure_signed(origin)?;
+        let recovery =
+            Self::pending_social_recovery(target).ok_or(Error::<T>::NoPendingKeyRecovery)?;
+        let (new_primary_key, executable_at) = recovery
+            .executable
+            .ok_or(Error::<T>::SocialRecoveryThresholdNotReached)?;
+        ensure!(sender == new_primary_key, Error::<T>::Unauthorized);
+        let now = <pallet_timestamp::Pallet<T>>::get();
+        ensure!(
+            now >= executable_at,
+            Error::<T>::KeyRecoveryChallengePeriodNotOver
+        );
+
+        Self::ensure_key_did_unlinked(&sender)?;
+        let old_primary_key = Self::get_primary_key(target).unwrap_or_default();
+        Self::ensure_key_unlinkable_from_did(&old_primary_key)?;
+
+        PendingSocialRecoveries::<T>::remove(target);
+        Self::unsafe_rotate_primary_key(target, old_primary_key, sender.clone(), false, None);
+        Self::deposit_event(RawEvent::SocialRecoveryExecuted(target, sender));
+        Ok(())
+    }
+}
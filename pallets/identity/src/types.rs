@@ -1,7 +1,9 @@
 //! Runtime API definition for Identity module.
 
 use codec::{Decode, Encode};
-use polymesh_primitives::{ClaimType, IdentityId, Permissions, Scope, SecondaryKey};
+use polymesh_primitives::{
+    Balance, CallFilter, ClaimType, IdentityId, Moment, Permissions, Scope, SecondaryKey,
+};
 use scale_info::TypeInfo;
 use sp_std::{prelude::*, vec::Vec};
 
@@ -71,9 +73,33 @@ pub enum DidStatus {
     CddVerified,
 }
 
+/// The status of a DID, with the details of its valid CDD claim, if any.
+#[derive(Encode, Decode, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct DidStatusDetails {
+    /// Whether the DID exists and has a valid CDD claim.
+    pub status: DidStatus,
+    /// The valid CDD claim of the DID expiring last, if any.
+    pub cdd: Option<CddClaimDetails>,
+}
+
+/// The details of a CDD claim.
+#[derive(Encode, Decode, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct CddClaimDetails {
+    /// The CDD provider who issued the claim.
+    pub issuer: IdentityId,
+    /// When the claim was issued.
+    pub issuance_date: Moment,
+    /// When the claim expires, if ever.
+    pub expiry: Option<Moment>,
+}
+
 /// Aggregate information about an `AccountId` in relation to an `IdentityId`.
 #[derive(Eq, PartialEq, Encode, Decode)]
-#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
 pub struct KeyIdentityData<IdentityId> {
     /// The identity of the provided `AccountId`.
     pub identity: IdentityId,
@@ -82,6 +108,43 @@ pub struct KeyIdentityData<IdentityId> {
     pub permissions: Option<Permissions>,
 }
 
+/// The permissions of an `AccountId` linked to an identity, and the restrictions applying to them.
+#[derive(Eq, PartialEq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct KeyPermissionsData<IdentityId> {
+    /// The identity of the provided `AccountId`.
+    pub identity: IdentityId,
+    /// The permissions of the `AccountId` within the `identity`.
+    /// If `None`, then this is a primary key.
+    pub permissions: Option<Permissions>,
+    /// Whether the `AccountId` is a secondary key of an identity whose secondary keys are frozen.
+    pub frozen: bool,
+    /// Whether the `AccountId` is a secondary key that has expired.
+    pub expired: bool,
+    /// The calls that the `identity` has restricted itself from making, whatever the key.
+    pub call_filters: Vec<CallFilter>,
+}
+
+/// Aggregate information about an `AccountId`, as shown on a wallet dashboard.
+#[derive(Eq, PartialEq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct AccountOverview<IdentityId, AccountId> {
+    /// The identity linked to the `AccountId`, and its permissions if it is a secondary key.
+    pub key_identity_data: Option<KeyIdentityData<IdentityId>>,
+    /// Free POLYX balance.
+    pub free_balance: Balance,
+    /// Reserved POLYX balance.
+    pub reserved_balance: Balance,
+    /// The amount that the free balance may not drop below, except for fee payment.
+    pub misc_frozen_balance: Balance,
+    /// The amount that the free balance may not drop below when paying fees.
+    pub fee_frozen_balance: Balance,
+    /// The paying key subsidising the `AccountId` and the remaining subsidy, if any.
+    pub subsidy: Option<(AccountId, Balance)>,
+}
+
 /// Result of a successful call permission check.
 #[derive(Clone, Eq, PartialEq)]
 pub struct PermissionedCallOriginData<AccountId> {
@@ -107,3 +170,36 @@ pub struct Claim2ndKey {
     pub issuer: IdentityId,
     pub scope: Option<Scope>,
 }
+
+/// A primary key recovery initiated by a CDD provider, pending its challenge period.
+#[derive(Encode, Decode, TypeInfo, Clone, PartialEq, Eq, Debug)]
+pub struct KeyRecovery<AccountId, Moment> {
+    /// The CDD provider that initiated the recovery.
+    pub cdd_provider: IdentityId,
+    /// The key that will become the new primary key.
+    pub new_primary_key: AccountId,
+    /// The recovery can be executed from this moment on.
+    pub executable_at: Moment,
+}
+
+/// A primary key recovery approved by the recovery guardians of an identity.
+#[derive(Encode, Decode, TypeInfo, Clone, PartialEq, Eq, Debug)]
+pub struct SocialKeyRecovery<AccountId, Moment> {
+    /// The guardians that approved the recovery, with the new primary key each of them approved.
+    pub approvals: Vec<(IdentityId, AccountId)>,
+    /// The guardians that approved the cancellation of the recovery.
+    pub cancellations: Vec<IdentityId>,
+    /// The key approved by the guardians' threshold, which can execute the recovery from the given moment on,
+    /// or `None` while no key reached the threshold.
+    pub executable: Option<(AccountId, Moment)>,
+}
+
+impl<AccountId, Moment> Default for SocialKeyRecovery<AccountId, Moment> {
+    fn default() -> Self {
+        Self {
+            approvals: Vec::new(),
+            cancellations: Vec::new(),
+            executable: None,
+        }
+    }
+}
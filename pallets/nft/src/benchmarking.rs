@@ -1,6 +1,6 @@
 use frame_benchmarking::benchmarks;
 use frame_system::RawOrigin;
-use polymesh_common_utilities::benchs::{user, AccountIdOf};
+use polymesh_common_utilities::benchs::{user, AccountIdOf, User};
 use polymesh_common_utilities::traits::asset::AssetFnTrait;
 use polymesh_common_utilities::TestUtilsFn;
 use polymesh_primitives::asset::NonFungibleType;
@@ -8,7 +8,7 @@ use polymesh_primitives::asset_metadata::{
     AssetMetadataGlobalKey, AssetMetadataKey, AssetMetadataSpec, AssetMetadataValue,
 };
 use polymesh_primitives::nft::{NFTCollectionId, NFTCollectionKeys, NFTId};
-use polymesh_primitives::PortfolioKind;
+use polymesh_primitives::{PortfolioId, PortfolioKind};
 use scale_info::prelude::format;
 use sp_std::prelude::*;
 use sp_std::vec::Vec;
@@ -49,6 +49,52 @@ fn creates_keys_register_metadata_types<T: Config>(n: u32) -> NFTCollectionKeys
     collection_keys
 }
 
+/// Offers the NFT `id` of `ticker` from the default portfolio of `lessor` to the one of `lessee` until block `end`.
+/// Returns the id of the authorization that has to be accepted by `lessee`.
+fn offer_nft_lease<T: Config>(
+    lessor: T::RuntimeOrigin,
+    lessee: IdentityId,
+    ticker: Ticker,
+    id: u32,
+    end: T::BlockNumber,
+) -> u64 {
+    Module::<T>::lease_nft(
+        lessor,
+        ticker,
+        NFTId(id.into()),
+        PortfolioKind::Default,
+        PortfolioId::default_portfolio(lessee),
+        end,
+    )
+    .expect("failed to offer nft lease");
+    Identity::<T>::multi_purpose_nonce()
+}
+
+/// Leases the NFTs `1..=n` of `ticker` from the default portfolio of `lessor` to the one of `lessee` until block `end`.
+fn lease_nfts<T: Config>(
+    lessor: &User<T>,
+    lessee: &User<T>,
+    ticker: Ticker,
+    n: u32,
+    end: T::BlockNumber,
+) {
+    for id in 1..n + 1 {
+        let auth_id = offer_nft_lease::<T>(lessor.origin().into(), lessee.did(), ticker, id, end);
+        Module::<T>::accept_nft_lease(lessee.origin().into(), auth_id)
+            .expect("failed to accept nft lease");
+    }
+}
+
+/// Creates `n` metadata keys with distinct names for a key-set.
+fn metadata_key_specs(n: u32) -> Vec<NFTMetadataKeySpec> {
+    (1..n + 1)
+        .map(|i| NFTMetadataKeySpec {
+            name: format!("key{}", i).as_bytes().to_vec().into(),
+            spec: AssetMetadataSpec::default(),
+        })
+        .collect()
+}
+
 /// Creates an NFT collection with `n_keys` global metadata keys and issues `n_nfts`.
 pub fn create_collection_issue_nfts<T: Config>(
     origin: T::RuntimeOrigin,
@@ -79,7 +125,7 @@ pub fn create_collection_issue_nfts<T: Config>(
 }
 
 benchmarks! {
-    where_clause { where T: TestUtilsFn<AccountIdOf<T>> }
+    where_clause { where T: TestUtilsFn<AccountIdOf<T>> + pallet_compliance_manager::Config }
 
     create_nft_collection {
         let n in 1..MAX_COLLECTION_KEYS;
@@ -149,4 +195,125 @@ benchmarks! {
             );
         }
     }
+
+    set_credential_collection {
+        let user = user::<T>("target", 0);
+        let ticker: Ticker = Ticker::from_slice_truncated(b"TICKER".as_ref());
+        let nft_type: Option<NonFungibleType> = Some(NonFungibleType::Derivative);
+        create_collection::<T>(user.origin().into(), ticker, nft_type, 0);
+    }: _(user.origin, ticker, true)
+    verify {
+        assert!(Module::<T>::is_credential_collection(&ticker));
+    }
+
+    link_nft_credential {
+        let user = user::<T>("target", 0);
+        let ticker: Ticker = Ticker::from_slice_truncated(b"TICKER".as_ref());
+        let nft_type: Option<NonFungibleType> = Some(NonFungibleType::Derivative);
+        create_collection_issue_nfts::<T>(user.origin().into(), ticker, nft_type, 0, 1, PortfolioKind::Default);
+        Module::<T>::set_credential_collection(user.origin().into(), ticker, true).expect("failed to designate collection");
+    }: _(user.origin, ticker, NFTId(1), PortfolioKind::Default)
+    verify {
+        assert_eq!(NFTCredentials::get(user.did(), ticker), Some(NFTId(1)));
+    }
+
+    unlink_nft_credential {
+        let user = user::<T>("target", 0);
+        let ticker: Ticker = Ticker::from_slice_truncated(b"TICKER".as_ref());
+        let nft_type: Option<NonFungibleType> = Some(NonFungibleType::Derivative);
+        create_collection_issue_nfts::<T>(user.origin().into(), ticker, nft_type, 0, 1, PortfolioKind::Default);
+        Module::<T>::set_credential_collection(user.origin().into(), ticker, true).expect("failed to designate collection");
+        Module::<T>::link_nft_credential(user.origin().into(), ticker, NFTId(1), PortfolioKind::Default).expect("failed to link credential");
+    }: _(user.origin, ticker)
+    verify {
+        assert_eq!(NFTCredentials::get(user.did(), ticker), None);
+    }
+
+    register_metadata_key_set {
+        let n in 1..MAX_COLLECTION_KEYS;
+
+        let user = user::<T>("target", 0);
+        let keys = metadata_key_specs(n);
+    }: _(user.origin, keys)
+    verify {
+        let key_set = MetadataKeySets::get(NFTMetadataKeySetId(1)).unwrap();
+        assert_eq!(key_set.keys.len(), n as usize);
+    }
+
+    create_nft_collection_from_key_set {
+        let n in 1..MAX_COLLECTION_KEYS;
+
+        let user = user::<T>("target", 0);
+        let ticker: Ticker = Ticker::from_slice_truncated(b"TICKER".as_ref());
+        let nft_type: Option<NonFungibleType> = Some(NonFungibleType::Derivative);
+        Module::<T>::register_metadata_key_set(user.origin().into(), metadata_key_specs(n)).expect("failed to register key-set");
+    }: _(user.origin, ticker, nft_type, NFTMetadataKeySetId(1))
+    verify {
+        assert_eq!(CollectionKeySet::get(NFTCollectionId(1)), Some(NFTMetadataKeySetId(1)));
+        assert_eq!(CollectionKeys::get(NFTCollectionId(1)).len(), n as usize);
+    }
+
+    lease_nft {
+        let alice = user::<T>("alice", 0);
+        let bob = user::<T>("bob", 0);
+        let ticker: Ticker = Ticker::from_slice_truncated(b"TICKER".as_ref());
+        let nft_type: Option<NonFungibleType> = Some(NonFungibleType::Derivative);
+        create_collection_issue_nfts::<T>(alice.origin().into(), ticker, nft_type, 0, 1, PortfolioKind::Default);
+        let lessee = PortfolioId::default_portfolio(bob.did());
+        let end = frame_system::Pallet::<T>::block_number() + 10u32.into();
+    }: _(alice.origin, ticker, NFTId(1), PortfolioKind::Default, lessee, end)
+    verify {
+        assert!(LeaseOffers::<T>::get(NFTLeaseId(1)).is_some());
+    }
+
+    accept_nft_lease {
+        let alice = user::<T>("alice", 0);
+        let bob = user::<T>("bob", 0);
+        let ticker: Ticker = Ticker::from_slice_truncated(b"TICKER".as_ref());
+        let nft_type: Option<NonFungibleType> = Some(NonFungibleType::Derivative);
+        create_collection_issue_nfts::<T>(alice.origin().into(), ticker, nft_type, 0, 1, PortfolioKind::Default);
+        pallet_compliance_manager::Module::<T>::pause_asset_compliance(alice.origin().into(), ticker)
+            .expect("failed to pause compliance");
+        let lessee = PortfolioId::default_portfolio(bob.did());
+        let end = frame_system::Pallet::<T>::block_number() + 10u32.into();
+        let auth_id = offer_nft_lease::<T>(alice.origin().into(), bob.did(), ticker, 1, end);
+    }: _(bob.origin, auth_id)
+    verify {
+        assert!(PortfolioLockedNFT::get(&lessee, (&ticker, NFTId(1))));
+    }
+
+    approve_nft_lease_end {
+        let alice = user::<T>("alice", 0);
+        let bob = user::<T>("bob", 0);
+        let ticker: Ticker = Ticker::from_slice_truncated(b"TICKER".as_ref());
+        let nft_type: Option<NonFungibleType> = Some(NonFungibleType::Derivative);
+        create_collection_issue_nfts::<T>(alice.origin().into(), ticker, nft_type, 0, 1, PortfolioKind::Default);
+        pallet_compliance_manager::Module::<T>::pause_asset_compliance(alice.origin().into(), ticker)
+            .expect("failed to pause compliance");
+        let end = frame_system::Pallet::<T>::block_number() + 10u32.into();
+        lease_nfts::<T>(&alice, &bob, ticker, 1, end);
+        Module::<T>::approve_nft_lease_end(bob.origin().into(), NFTLeaseId(1)).expect("failed to approve lease end");
+    }: _(alice.origin, NFTLeaseId(1))
+    verify {
+        assert!(Leases::<T>::get(NFTLeaseId(1)).is_none());
+    }
+
+    end_nft_leases {
+        let n in 0..MAX_LEASES_ENDING_PER_BLOCK;
+
+        let alice = user::<T>("alice", 0);
+        let bob = user::<T>("bob", 0);
+        let ticker: Ticker = Ticker::from_slice_truncated(b"TICKER".as_ref());
+        let nft_type: Option<NonFungibleType> = Some(NonFungibleType::Derivative);
+        create_collection_issue_nfts::<T>(alice.origin().into(), ticker, nft_type, 0, n, PortfolioKind::Default);
+        pallet_compliance_manager::Module::<T>::pause_asset_compliance(alice.origin().into(), ticker)
+            .expect("failed to pause compliance");
+        let end = frame_system::Pallet::<T>::block_number() + 10u32.into();
+        lease_nfts::<T>(&alice, &bob, ticker, n, end);
+    }: {
+        Module::<T>::end_expired_leases(end);
+    }
+    verify {
+        assert_eq!(NumberOfNFTs::get(&ticker, alice.did()), n as u64);
+    }
 }
@@ -1,21 +1,26 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use frame_support::dispatch::DispatchResult;
+use frame_support::dispatch::{DispatchError, DispatchResult};
 use frame_support::traits::Get;
+use frame_support::weights::Weight;
 use frame_support::{decl_error, decl_module, decl_storage};
 use frame_support::{ensure, require_transactional};
-use pallet_asset::Frozen;
+use pallet_asset::{AssetMetadataLocalNameToKey, AssetMetadataLocalSpecs, LifecycleStates};
 use pallet_base::try_next_pre;
-use pallet_portfolio::PortfolioNFT;
+use pallet_identity::{NFTCredentials, PermissionedCallOriginData};
+use pallet_portfolio::{PortfolioLockedNFT, PortfolioNFT};
 use polymesh_common_utilities::compliance_manager::Config as ComplianceManagerConfig;
 use polymesh_common_utilities::constants::ERC1400_TRANSFER_SUCCESS;
 pub use polymesh_common_utilities::traits::nft::{Config, Event, NFTTrait, WeightInfo};
 use polymesh_primitives::asset::{AssetName, AssetType, NonFungibleType};
 use polymesh_primitives::asset_metadata::{AssetMetadataKey, AssetMetadataValue};
 use polymesh_primitives::nft::{
-    NFTCollection, NFTCollectionId, NFTCollectionKeys, NFTCount, NFTId, NFTMetadataAttribute, NFTs,
+    NFTCollection, NFTCollectionId, NFTCollectionKeys, NFTCount, NFTId, NFTLease, NFTLeaseId,
+    NFTMetadataAttribute, NFTMetadataKeySet, NFTMetadataKeySetId, NFTMetadataKeySpec, NFTs,
+};
+use polymesh_primitives::{
+    extract_auth, AuthorizationData, IdentityId, PortfolioId, PortfolioKind, Signatory, Ticker,
 };
-use polymesh_primitives::{IdentityId, PortfolioId, PortfolioKind, Ticker};
 use sp_std::collections::btree_map::BTreeMap;
 use sp_std::collections::btree_set::BTreeSet;
 use sp_std::{vec, vec::Vec};
@@ -28,6 +33,9 @@ type Portfolio<T> = pallet_portfolio::Module<T>;
 #[cfg(feature = "runtime-benchmarks")]
 pub mod benchmarking;
 
+/// Maximum number of NFT leases ending in a block.
+pub const MAX_LEASES_ENDING_PER_BLOCK: u32 = 16;
+
 decl_storage!(
     trait Store for Module<T: Config> as NFT {
         /// The total number of NFTs per identity.
@@ -50,6 +58,33 @@ decl_storage!(
 
         /// The next available id for an NFT within a collection.
         pub NextNFTId get(fn nft_id): map hasher(blake2_128_concat) NFTCollectionId => NFTId;
+
+        /// Collections whose NFTs can be linked to the identity of their holder as a credential.
+        pub CredentialCollections get(fn is_credential_collection): map hasher(blake2_128_concat) Ticker => bool;
+
+        /// The next available id for a metadata key-set.
+        pub NextMetadataKeySetId get(fn metadata_key_set_id): NFTMetadataKeySetId;
+
+        /// All reusable metadata key-sets, and their owner, for a given key-set id.
+        pub MetadataKeySets get(fn metadata_key_set): map hasher(blake2_128_concat) NFTMetadataKeySetId => Option<NFTMetadataKeySet>;
+
+        /// The metadata key-set whose keys a collection was created with, if any.
+        pub CollectionKeySet get(fn collection_key_set): map hasher(blake2_128_concat) NFTCollectionId => Option<NFTMetadataKeySetId>;
+
+        /// The next available id for an NFT lease.
+        pub NextLeaseId get(fn lease_id): NFTLeaseId;
+
+        /// All ongoing leases for a given lease id.
+        pub Leases get(fn lease): map hasher(twox_64_concat) NFTLeaseId => Option<NFTLease<T::BlockNumber>>;
+
+        /// The leases offered by lessors and not yet accepted by the lessees.
+        pub LeaseOffers get(fn lease_offer): map hasher(twox_64_concat) NFTLeaseId => Option<NFTLease<T::BlockNumber>>;
+
+        /// The leases ending in a block, at most `MAX_LEASES_ENDING_PER_BLOCK`.
+        pub LeasesEndingAt get(fn leases_ending_at): map hasher(twox_64_concat) T::BlockNumber => Vec<NFTLeaseId>;
+
+        /// The party of a lease, if any, that agreed to end it before its end block.
+        pub LeaseEndApprovals get(fn lease_end_approval): map hasher(twox_64_concat) NFTLeaseId => Option<PortfolioId>;
     }
 );
 
@@ -64,6 +99,10 @@ decl_module! {
         /// Initializes the default event for this module.
         fn deposit_event() = default;
 
+        fn on_initialize(now: T::BlockNumber) -> Weight {
+            Self::end_expired_leases(now)
+        }
+
         /// Cretes a new `NFTCollection`.
         ///
         /// # Arguments
@@ -119,6 +158,7 @@ decl_module! {
         /// ## Errors
         /// - `CollectionNotFound` - if the collection associated to the given ticker has not been created.
         /// - `NFTNotFound` - if the given NFT does not exist in the portfolio.
+        /// - `NFTIsLocked` - if the given NFT is locked in the portfolio.
         ///
         /// # Permissions
         /// * Asset
@@ -127,6 +167,180 @@ decl_module! {
         pub fn redeem_nft(origin, ticker: Ticker, nft_id: NFTId, portfolio_kind: PortfolioKind) -> DispatchResult {
             Self::base_redeem_nft(origin, ticker, nft_id, portfolio_kind)
         }
+
+        /// Sets whether the NFTs of the collection can be linked to the identity of their holder
+        /// as a credential, which compliance conditions can then require.
+        /// Credentials already linked are kept when the collection is no longer designated.
+        ///
+        /// # Arguments
+        /// * `origin` - is a signer that has permissions to act as an agent of `ticker`.
+        /// * `ticker` - the ticker of the NFT collection.
+        /// * `enabled` - whether the NFTs of the collection can be linked as credentials.
+        ///
+        /// ## Errors
+        /// - `CollectionNotFound` - if the collection associated to the given ticker has not been created.
+        ///
+        /// # Permissions
+        /// * Asset
+        #[weight = <T as Config>::WeightInfo::set_credential_collection()]
+        pub fn set_credential_collection(origin, ticker: Ticker, enabled: bool) -> DispatchResult {
+            Self::base_set_credential_collection(origin, ticker, enabled)
+        }
+
+        /// Links an NFT held by the caller to its identity as a credential, replacing the NFT
+        /// of the same collection previously linked, if any.
+        /// The NFT is unlinked automatically when it leaves the caller's identity.
+        ///
+        /// # Arguments
+        /// * `origin` - the secondary key of the caller.
+        /// * `ticker` - the ticker of the NFT collection.
+        /// * `nft_id` - the id of the NFT to link.
+        /// * `portfolio_kind` - the portfolio that contains the nft.
+        ///
+        /// ## Errors
+        /// - `NotCredentialCollection` - if the NFTs of the collection can't be linked as credentials.
+        /// - `NFTNotFound` - if the given NFT does not exist in the portfolio.
+        ///
+        /// # Permissions
+        /// * Portfolio
+        #[weight = <T as Config>::WeightInfo::link_nft_credential()]
+        pub fn link_nft_credential(origin, ticker: Ticker, nft_id: NFTId, portfolio_kind: PortfolioKind) -> DispatchResult {
+            Self::base_link_nft_credential(origin, ticker, nft_id, portfolio_kind)
+        }
+
+        /// Unlinks the NFT of the collection linked to the caller's identity as a credential.
+        ///
+        /// # Arguments
+        /// * `origin` - the secondary key of the caller.
+        /// * `ticker` - the ticker of the NFT collection.
+        ///
+        /// ## Errors
+        /// - `NFTCredentialNotLinked` - if no NFT of the collection is linked to the caller's identity.
+        #[weight = <T as Config>::WeightInfo::unlink_nft_credential()]
+        pub fn unlink_nft_credential(origin, ticker: Ticker) -> DispatchResult {
+            let caller_did = Identity::<T>::ensure_perms(origin)?;
+            let nft_id = NFTCredentials::take(&caller_did, &ticker)
+                .ok_or(Error::<T>::NFTCredentialNotLinked)?;
+            Self::deposit_event(Event::NFTCredentialUnlinked(caller_did, ticker, nft_id));
+            Ok(())
+        }
+
+        /// Registers a reusable set of metadata keys, with their specs, that the caller's
+        /// NFT collections can then be created with.
+        ///
+        /// # Arguments
+        /// * `origin` - the secondary key of the caller.
+        /// * `keys` - the names and specs of the metadata keys of the key-set.
+        ///
+        /// ## Errors
+        /// - `MaxNumberOfKeysExceeded` - if the number of metadata keys is greater than the maximum allowed for a collection.
+        /// - `DuplicateMetadataKey` - if two metadata keys have the same name.
+        /// - `AssetMetadataNameMaxLengthExceeded` - if a name is longer than the maximum allowed.
+        #[weight = <T as Config>::WeightInfo::register_metadata_key_set(keys.len() as u32)]
+        pub fn register_metadata_key_set(origin, keys: Vec<NFTMetadataKeySpec>) -> DispatchResult {
+            Self::base_register_metadata_key_set(origin, keys)
+        }
+
+        /// Creates a new `NFTCollection` whose mandatory metadata keys are those of a key-set owned by the caller.
+        /// The keys are registered as local metadata keys of `ticker`, unless a local key with
+        /// the same name and specs already exists, in which case it is reused.
+        ///
+        /// # Arguments
+        /// * `origin` - contains the secondary key of the caller (i.e. who signed the transaction to execute this function).
+        /// * `ticker` - the ticker associated to the new collection.
+        /// * `nft_type` - in case the asset hasn't been created yet, one will be created with the given type.
+        /// * `key_set_id` - the id of the metadata key-set.
+        ///
+        /// ## Errors
+        /// - `CollectionAlredyRegistered` - if the ticker is already associated to an NFT collection.
+        /// - `InvalidAssetType` - if the associated asset is not of type NFT.
+        /// - `MetadataKeySetNotFound` - if the key-set does not exist.
+        /// - `NotMetadataKeySetOwner` - if the key-set is not owned by the caller's identity.
+        /// - `MetadataKeySpecMismatch` - if a local metadata key of `ticker` has the name of a key of the set, but different specs.
+        ///
+        /// # Permissions
+        /// * Asset
+        #[weight = <T as Config>::WeightInfo::create_nft_collection_from_key_set(T::MaxNumberOfCollectionKeys::get() as u32)]
+        pub fn create_nft_collection_from_key_set(origin, ticker: Ticker, nft_type: Option<NonFungibleType>, key_set_id: NFTMetadataKeySetId) -> DispatchResult {
+            Self::base_create_nft_collection_from_key_set(origin, ticker, nft_type, key_set_id)
+        }
+
+        /// Offers to lease an NFT of the caller to the `lessee` portfolio until block `end`.
+        /// A `LeaseNFT` authorization is added for the custodian of the lessee's portfolio,
+        /// the lease starts when it is accepted with `accept_nft_lease`.
+        /// The NFT is locked in the lessee's portfolio during the lease, and returned to the
+        /// lessor's portfolio at the start of block `end`, regardless of the compliance rules
+        /// and lifecycle state of the asset.
+        /// If the lessor's portfolio no longer exists, the NFT is returned to its default portfolio.
+        ///
+        /// # Arguments
+        /// * `origin` - the secondary key of the custodian of the lessor's portfolio.
+        /// * `ticker` - the ticker of the NFT collection.
+        /// * `nft_id` - the id of the NFT to lease.
+        /// * `portfolio_kind` - the portfolio that contains the nft.
+        /// * `lessee` - the portfolio that will hold the nft during the lease.
+        /// * `end` - the block in which the lease ends.
+        ///
+        /// ## Errors
+        /// - `InvalidLeaseEnd` - if `end` is not a future block.
+        /// - `LeaseEndBlockFull` - if `MAX_LEASES_ENDING_PER_BLOCK` leases already end in block `end`.
+        /// - `NFTNotFound` - if the NFT is not in the caller's portfolio.
+        /// - `NFTIsLocked` - if the NFT is locked in the caller's portfolio.
+        ///
+        /// # Permissions
+        /// * Portfolio
+        #[weight = <T as Config>::WeightInfo::lease_nft()]
+        pub fn lease_nft(
+            origin,
+            ticker: Ticker,
+            nft_id: NFTId,
+            portfolio_kind: PortfolioKind,
+            lessee: PortfolioId,
+            end: T::BlockNumber,
+        ) -> DispatchResult {
+            Self::base_lease_nft(origin, ticker, nft_id, portfolio_kind, lessee, end)
+        }
+
+        /// Agrees to end the lease `lease_id` before its end block, on behalf of the lessor or
+        /// lessee portfolio under the caller's custody.
+        /// Once both parties have agreed, the NFT is returned to the lessor.
+        /// A lease that has not been accepted yet is withdrawn by the lessor alone.
+        ///
+        /// # Arguments
+        /// * `origin` - the secondary key of the custodian of the lessor's or lessee's portfolio.
+        /// * `lease_id` - the id of the lease.
+        ///
+        /// ## Errors
+        /// - `LeaseNotFound` - if the lease does not exist.
+        /// - `NotLeaseParty` - if the caller has custody of neither the lessor's nor the lessee's portfolio.
+        ///
+        /// # Permissions
+        /// * Portfolio
+        #[weight = <T as Config>::WeightInfo::approve_nft_lease_end()]
+        pub fn approve_nft_lease_end(origin, lease_id: NFTLeaseId) -> DispatchResult {
+            Self::base_approve_nft_lease_end(origin, lease_id)
+        }
+
+        /// Accepts the `LeaseNFT` authorization `auth_id`, moving the NFT to the lessee's
+        /// portfolio, where it stays locked until the end of the lease.
+        ///
+        /// # Arguments
+        /// * `origin` - the secondary key of the custodian of the lessee's portfolio.
+        /// * `auth_id` - the id of the authorization.
+        ///
+        /// ## Errors
+        /// - `LeaseNotFound` - if the lease offer does not exist.
+        /// - `InvalidLeaseEnd` - if the end of the lease is no longer a future block.
+        /// - `LeaseEndBlockFull` - if `MAX_LEASES_ENDING_PER_BLOCK` leases already end in the block of the lease end.
+        /// - `NFTIsLocked` - if the NFT is locked in the lessor's portfolio.
+        /// - All errors of a regular NFT transfer, e.g. if the transfer is not compliant.
+        ///
+        /// # Permissions
+        /// * Portfolio
+        #[weight = <T as Config>::WeightInfo::accept_nft_lease()]
+        pub fn accept_nft_lease(origin, auth_id: u64) -> DispatchResult {
+            Self::base_accept_nft_lease(origin, auth_id)
+        }
     }
 }
 
@@ -172,6 +386,26 @@ decl_error! {
         UnregisteredMetadataKey,
         /// It is not possible to transferr zero nft.
         ZeroCount,
+        /// The NFTs of the collection can't be linked to identities as credentials.
+        NotCredentialCollection,
+        /// No NFT of the collection is linked to the identity as a credential.
+        NFTCredentialNotLinked,
+        /// The metadata key-set does not exist.
+        MetadataKeySetNotFound,
+        /// The metadata key-set is not owned by the caller's identity.
+        NotMetadataKeySetOwner,
+        /// A local metadata key with the same name but different specs is already registered for the ticker.
+        MetadataKeySpecMismatch,
+        /// The NFT is locked in the portfolio, e.g. because it is leased or in a pending settlement.
+        NFTIsLocked,
+        /// The end of a lease must be a future block.
+        InvalidLeaseEnd,
+        /// The lease does not exist.
+        LeaseNotFound,
+        /// The caller has custody of neither the lessor's nor the lessee's portfolio.
+        NotLeaseParty,
+        /// `MAX_LEASES_ENDING_PER_BLOCK` leases already end in the block.
+        LeaseEndBlockFull,
     }
 }
 
@@ -182,28 +416,8 @@ impl<T: Config> Module<T> {
         nft_type: Option<NonFungibleType>,
         collection_keys: NFTCollectionKeys,
     ) -> DispatchResult {
-        // Verifies if the asset has already been created and the caller's permission to create the collection
-        let (create_asset, caller_did) = {
-            match Asset::<T>::nft_asset(&ticker) {
-                Some(is_nft_asset) => {
-                    ensure!(is_nft_asset, Error::<T>::InvalidAssetType);
-                    let caller_did =
-                        <ExternalAgents<T>>::ensure_agent_asset_perms(origin.clone(), ticker)?
-                            .primary_did;
-                    (false, caller_did)
-                }
-                None => {
-                    let caller_did = Identity::<T>::ensure_perms(origin.clone())?;
-                    (true, caller_did)
-                }
-            }
-        };
-
-        // Verifies if the ticker is already associated to an NFT collection
-        ensure!(
-            !CollectionTicker::contains_key(&ticker),
-            Error::<T>::CollectionAlredyRegistered
-        );
+        let (create_asset, caller_did) =
+            Self::ensure_collection_creation_allowed(origin.clone(), &ticker)?;
 
         // Verifies if the maximum number of keys is respected
         ensure!(
@@ -229,20 +443,152 @@ impl<T: Config> Module<T> {
 
         // Creates an nft asset if it hasn't been created yet
         if create_asset {
-            let nft_type = nft_type.ok_or(Error::<T>::InvalidAssetType)?;
-            Asset::<T>::create_asset(
-                origin,
-                AssetName(ticker.as_slice().to_vec()),
-                ticker.clone(),
-                false,
-                AssetType::NonFungible(nft_type),
-                Vec::new(),
-                None,
-                true,
-            )?;
+            Self::create_nft_asset(origin, ticker, nft_type)?;
+        }
+
+        Self::unverified_create_nft_collection(caller_did, ticker, collection_keys).map(drop)
+    }
+
+    fn base_register_metadata_key_set(
+        origin: T::RuntimeOrigin,
+        keys: Vec<NFTMetadataKeySpec>,
+    ) -> DispatchResult {
+        let caller_did = Identity::<T>::ensure_perms(origin)?;
+
+        // Verifies if the maximum number of keys is respected
+        ensure!(
+            keys.len() <= (T::MaxNumberOfCollectionKeys::get() as usize),
+            Error::<T>::MaxNumberOfKeysExceeded
+        );
+
+        // Verifies that there are no duplicated names and that all keys are within the limits
+        let names: BTreeSet<&[u8]> = keys.iter().map(|key| key.name.0.as_slice()).collect();
+        ensure!(names.len() == keys.len(), Error::<T>::DuplicateMetadataKey);
+        for key in &keys {
+            Asset::<T>::ensure_asset_metadata_name_limited(&key.name)?;
+            Asset::<T>::ensure_asset_metadata_spec_limited(&key.spec)?;
+        }
+
+        let key_set_id = NextMetadataKeySetId::try_mutate(try_next_pre::<T, _>)?;
+        MetadataKeySets::insert(
+            &key_set_id,
+            NFTMetadataKeySet {
+                owner: caller_did,
+                keys: keys.clone(),
+            },
+        );
+        Self::deposit_event(Event::MetadataKeySetRegistered(
+            caller_did, key_set_id, keys,
+        ));
+        Ok(())
+    }
+
+    fn base_create_nft_collection_from_key_set(
+        origin: T::RuntimeOrigin,
+        ticker: Ticker,
+        nft_type: Option<NonFungibleType>,
+        key_set_id: NFTMetadataKeySetId,
+    ) -> DispatchResult {
+        let (create_asset, caller_did) =
+            Self::ensure_collection_creation_allowed(origin.clone(), &ticker)?;
+
+        // Verifies that the key-set exists and is owned by the caller
+        let key_set =
+            Self::metadata_key_set(&key_set_id).ok_or(Error::<T>::MetadataKeySetNotFound)?;
+        ensure!(
+            key_set.owner == caller_did,
+            Error::<T>::NotMetadataKeySetOwner
+        );
+
+        // Creates an nft asset if it hasn't been created yet
+        if create_asset {
+            Self::create_nft_asset(origin, ticker, nft_type)?;
         }
 
-        // Creates the nft collection
+        // Registers the keys of the set as local keys of the ticker, reusing the ones already registered
+        let mut collection_keys = BTreeSet::new();
+        for NFTMetadataKeySpec { name, spec } in key_set.keys {
+            let key = match AssetMetadataLocalNameToKey::get(&ticker, &name) {
+                Some(local_key) => {
+                    ensure!(
+                        AssetMetadataLocalSpecs::get(&ticker, &local_key).as_ref() == Some(&spec),
+                        Error::<T>::MetadataKeySpecMismatch
+                    );
+                    local_key.into()
+                }
+                None => Asset::<T>::unverified_register_asset_metadata_local_type(
+                    caller_did, ticker, name, spec,
+                )?,
+            };
+            collection_keys.insert(key);
+        }
+
+        let collection_id =
+            Self::unverified_create_nft_collection(caller_did, ticker, collection_keys)?;
+        CollectionKeySet::insert(&collection_id, key_set_id);
+        Self::deposit_event(Event::NftCollectionKeySetUsed(
+            caller_did,
+            collection_id,
+            key_set_id,
+        ));
+        Ok(())
+    }
+
+    /// Verifies the caller's permission to create a collection for `ticker`, and that `ticker` is not already
+    /// associated to a collection. Returns whether the asset must be created and the caller's identity.
+    fn ensure_collection_creation_allowed(
+        origin: T::RuntimeOrigin,
+        ticker: &Ticker,
+    ) -> Result<(bool, IdentityId), DispatchError> {
+        // Verifies if the asset has already been created and the caller's permission to create the collection
+        let (create_asset, caller_did) = {
+            match Asset::<T>::nft_asset(ticker) {
+                Some(is_nft_asset) => {
+                    ensure!(is_nft_asset, Error::<T>::InvalidAssetType);
+                    let caller_did =
+                        <ExternalAgents<T>>::ensure_agent_asset_perms(origin, *ticker)?.primary_did;
+                    (false, caller_did)
+                }
+                None => {
+                    let caller_did = Identity::<T>::ensure_perms(origin)?;
+                    (true, caller_did)
+                }
+            }
+        };
+
+        // Verifies if the ticker is already associated to an NFT collection
+        ensure!(
+            !CollectionTicker::contains_key(ticker),
+            Error::<T>::CollectionAlredyRegistered
+        );
+        Ok((create_asset, caller_did))
+    }
+
+    /// Creates the nft asset of `ticker`.
+    fn create_nft_asset(
+        origin: T::RuntimeOrigin,
+        ticker: Ticker,
+        nft_type: Option<NonFungibleType>,
+    ) -> DispatchResult {
+        let nft_type = nft_type.ok_or(Error::<T>::InvalidAssetType)?;
+        Asset::<T>::create_asset(
+            origin,
+            AssetName(ticker.as_slice().to_vec()),
+            ticker.clone(),
+            false,
+            AssetType::NonFungible(nft_type),
+            Vec::new(),
+            None,
+            true,
+        )
+    }
+
+    /// Creates the nft collection of `ticker` with the given mandatory keys.
+    fn unverified_create_nft_collection(
+        caller_did: IdentityId,
+        ticker: Ticker,
+        collection_keys: BTreeSet<AssetMetadataKey>,
+    ) -> Result<NFTCollectionId, DispatchError> {
         let collection_id = NextCollectionId::try_mutate(try_next_pre::<T, _>)?;
         let nft_collection = NFTCollection::new(collection_id, ticker.clone());
         Collection::insert(&collection_id, nft_collection);
@@ -254,7 +600,7 @@ impl<T: Config> Module<T> {
             ticker,
             collection_id,
         ));
-        Ok(())
+        Ok(collection_id)
     }
 
     fn base_issue_nft(
@@ -333,11 +679,15 @@ impl<T: Config> Module<T> {
         let caller_portfolio =
             Asset::<T>::ensure_agent_with_custody_and_perms(origin, ticker, portfolio_kind)?;
 
-        // Verifies if the NFT exists
+        // Verifies if the NFT exists and is not locked
         ensure!(
             PortfolioNFT::contains_key(&caller_portfolio, (&ticker, &nft_id)),
             Error::<T>::NFTNotFound
         );
+        ensure!(
+            !PortfolioLockedNFT::contains_key(&caller_portfolio, (&ticker, &nft_id)),
+            Error::<T>::NFTIsLocked
+        );
 
         // Burns the NFT
         let new_balance = NumberOfNFTs::get(&ticker, &caller_portfolio.did)
@@ -347,11 +697,264 @@ impl<T: Config> Module<T> {
         PortfolioNFT::remove(&caller_portfolio, (&ticker, &nft_id));
         #[allow(deprecated)]
         MetadataValue::remove_prefix((&collection_id, &nft_id), None);
+        Self::remove_nft_credential(caller_portfolio.did, &ticker, &nft_id);
 
         Self::deposit_event(Event::RedeemedNFT(caller_portfolio.did, ticker, nft_id));
         Ok(())
     }
 
+    fn base_set_credential_collection(
+        origin: T::RuntimeOrigin,
+        ticker: Ticker,
+        enabled: bool,
+    ) -> DispatchResult {
+        // Verifies if the collection exists
+        ensure!(
+            CollectionTicker::contains_key(&ticker),
+            Error::<T>::CollectionNotFound
+        );
+        let caller_did = <ExternalAgents<T>>::ensure_perms(origin, ticker)?;
+
+        CredentialCollections::insert(&ticker, enabled);
+        Self::deposit_event(Event::CredentialCollectionSet(caller_did, ticker, enabled));
+        Ok(())
+    }
+
+    fn base_link_nft_credential(
+        origin: T::RuntimeOrigin,
+        ticker: Ticker,
+        nft_id: NFTId,
+        portfolio_kind: PortfolioKind,
+    ) -> DispatchResult {
+        let caller_did = Identity::<T>::ensure_perms(origin)?;
+        ensure!(
+            Self::is_credential_collection(&ticker),
+            Error::<T>::NotCredentialCollection
+        );
+
+        // Verifies if the caller holds the NFT
+        let caller_portfolio = PortfolioId {
+            did: caller_did,
+            kind: portfolio_kind,
+        };
+        ensure!(
+            PortfolioNFT::contains_key(&caller_portfolio, (&ticker, &nft_id)),
+            Error::<T>::NFTNotFound
+        );
+
+        NFTCredentials::insert(&caller_did, &ticker, nft_id);
+        Self::deposit_event(Event::NFTCredentialLinked(caller_did, ticker, nft_id));
+        Ok(())
+    }
+
+    /// Unlinks `nft_id` from the identity `did`, if it is linked to it as a credential.
+    fn remove_nft_credential(did: IdentityId, ticker: &Ticker, nft_id: &NFTId) {
+        if NFTCredentials::get(&did, ticker).as_ref() == Some(nft_id) {
+            NFTCredentials::remove(&did, ticker);
+            Self::deposit_event(Event::NFTCredentialUnlinked(did, *ticker, *nft_id));
+        }
+    }
+
+    fn base_lease_nft(
+        origin: T::RuntimeOrigin,
+        ticker: Ticker,
+        nft_id: NFTId,
+        portfolio_kind: PortfolioKind,
+        lessee: PortfolioId,
+        end: T::BlockNumber,
+    ) -> DispatchResult {
+        let PermissionedCallOriginData {
+            primary_did,
+            secondary_key,
+            ..
+        } = Identity::<T>::ensure_origin_call_permissions(origin)?;
+        let lessor = PortfolioId {
+            did: primary_did,
+            kind: portfolio_kind,
+        };
+        Portfolio::<T>::ensure_portfolio_custody_and_permission(
+            lessor,
+            primary_did,
+            secondary_key.as_ref(),
+        )?;
+        Portfolio::<T>::ensure_portfolio_validity(&lessee)?;
+        Self::ensure_lease_end_valid(end)?;
+        ensure!(
+            PortfolioNFT::contains_key(&lessor, (&ticker, &nft_id)),
+            Error::<T>::NFTNotFound
+        );
+        ensure!(
+            !PortfolioLockedNFT::contains_key(&lessor, (&ticker, &nft_id)),
+            Error::<T>::NFTIsLocked
+        );
+
+        // The lease starts once the custodian of the lessee's portfolio accepts it
+        let lease_id = NextLeaseId::try_mutate(try_next_pre::<T, _>)?;
+        let lessee_custodian = Portfolio::<T>::portfolio_custodian(&lessee).unwrap_or(lessee.did);
+        let auth_id = Identity::<T>::add_auth(
+            primary_did,
+            Signatory::Identity(lessee_custodian),
+            AuthorizationData::LeaseNFT(lease_id),
+            None,
+        );
+        LeaseOffers::<T>::insert(
+            lease_id,
+            NFTLease {
+                lessor,
+                lessee,
+                ticker,
+                nft_id,
+                end,
+            },
+        );
+        Self::deposit_event(Event::NFTLeaseOffered(primary_did, lease_id, auth_id));
+        Ok(())
+    }
+
+    fn base_accept_nft_lease(origin: T::RuntimeOrigin, auth_id: u64) -> DispatchResult {
+        let PermissionedCallOriginData {
+            primary_did,
+            secondary_key,
+            ..
+        } = Identity::<T>::ensure_origin_call_permissions(origin)?;
+        Identity::<T>::accept_auth_with(&primary_did.into(), auth_id, |data, from| {
+            let lease_id = extract_auth!(data, LeaseNFT(l));
+            let lease = LeaseOffers::<T>::take(lease_id).ok_or(Error::<T>::LeaseNotFound)?;
+            Identity::<T>::ensure_auth_by(from, lease.lessor.did)?;
+            Portfolio::<T>::ensure_portfolio_custody_and_permission(
+                lease.lessee,
+                primary_did,
+                secondary_key.as_ref(),
+            )?;
+            Self::ensure_lease_end_valid(lease.end)?;
+            ensure!(
+                !PortfolioLockedNFT::contains_key(&lease.lessor, (&lease.ticker, &lease.nft_id)),
+                Error::<T>::NFTIsLocked
+            );
+
+            // Moves the NFT to the lessee's portfolio, where it stays locked until the end of the lease
+            let nfts = NFTs::new_unverified(lease.ticker, vec![lease.nft_id]);
+            Self::base_nft_transfer(&lease.lessor, &lease.lessee, &nfts)?;
+            PortfolioLockedNFT::insert(&lease.lessee, (&lease.ticker, &lease.nft_id), true);
+            LeasesEndingAt::<T>::append(lease.end, lease_id);
+            Self::deposit_event(Event::NFTLeased(
+                primary_did,
+                lease_id,
+                lease.lessor,
+                lease.lessee,
+                lease.ticker,
+                lease.nft_id,
+            ));
+            Leases::<T>::insert(lease_id, lease);
+            Ok(())
+        })
+    }
+
+    fn base_approve_nft_lease_end(
+        origin: T::RuntimeOrigin,
+        lease_id: NFTLeaseId,
+    ) -> DispatchResult {
+        let PermissionedCallOriginData {
+            primary_did,
+            secondary_key,
+            ..
+        } = Identity::<T>::ensure_origin_call_permissions(origin)?;
+
+        // The lessor alone can withdraw a lease that has not been accepted yet
+        if let Some(offer) = Self::lease_offer(lease_id) {
+            Portfolio::<T>::ensure_portfolio_custody_and_permission(
+                offer.lessor,
+                primary_did,
+                secondary_key.as_ref(),
+            )
+            .map_err(|_| Error::<T>::NotLeaseParty)?;
+            LeaseOffers::<T>::remove(lease_id);
+            Self::deposit_event(Event::NFTLeaseEndApproved(primary_did, lease_id));
+            return Ok(());
+        }
+        let lease = Self::lease(lease_id).ok_or(Error::<T>::LeaseNotFound)?;
+
+        // Verifies on behalf of which parties the caller is acting
+        let is_custodian = |portfolio: PortfolioId| {
+            Portfolio::<T>::ensure_portfolio_custody_and_permission(
+                portfolio,
+                primary_did,
+                secondary_key.as_ref(),
+            )
+            .is_ok()
+        };
+        let by_lessor = is_custodian(lease.lessor);
+        let by_lessee = is_custodian(lease.lessee);
+        ensure!(by_lessor || by_lessee, Error::<T>::NotLeaseParty);
+        Self::deposit_event(Event::NFTLeaseEndApproved(primary_did, lease_id));
+
+        // Ends the lease if the other party has already agreed
+        let approval = LeaseEndApprovals::get(lease_id);
+        if (by_lessor || approval == Some(lease.lessor))
+            && (by_lessee || approval == Some(lease.lessee))
+        {
+            LeasesEndingAt::<T>::mutate(lease.end, |ids| ids.retain(|id| *id != lease_id));
+            Self::end_lease(lease_id, lease);
+        } else {
+            let party = if by_lessor {
+                lease.lessor
+            } else {
+                lease.lessee
+            };
+            LeaseEndApprovals::insert(lease_id, party);
+        }
+        Ok(())
+    }
+
+    /// Ensures that a lease can end in block `end`, which must be a future block
+    /// in which less than `MAX_LEASES_ENDING_PER_BLOCK` leases end.
+    fn ensure_lease_end_valid(end: T::BlockNumber) -> DispatchResult {
+        ensure!(
+            end > frame_system::Pallet::<T>::block_number(),
+            Error::<T>::InvalidLeaseEnd
+        );
+        ensure!(
+            LeasesEndingAt::<T>::decode_len(end).unwrap_or_default()
+                < MAX_LEASES_ENDING_PER_BLOCK as usize,
+            Error::<T>::LeaseEndBlockFull
+        );
+        Ok(())
+    }
+
+    /// Ends the leases scheduled to end in block `now`.
+    fn end_expired_leases(now: T::BlockNumber) -> Weight {
+        let lease_ids = LeasesEndingAt::<T>::take(now);
+        let weight = <T as Config>::WeightInfo::end_nft_leases(lease_ids.len() as u32);
+        for lease_id in lease_ids {
+            if let Some(lease) = Self::lease(lease_id) {
+                Self::end_lease(lease_id, lease);
+            }
+        }
+        weight
+    }
+
+    /// Unlocks the NFT of the lease and returns it to the lessor's portfolio, or to the lessor's
+    /// default portfolio if the former no longer exists.
+    fn end_lease(lease_id: NFTLeaseId, lease: NFTLease<T::BlockNumber>) {
+        let NFTLease {
+            lessor,
+            lessee,
+            ticker,
+            nft_id,
+            ..
+        } = lease;
+        let receiver = match Portfolio::<T>::ensure_portfolio_validity(&lessor) {
+            Ok(()) => lessor,
+            Err(_) => PortfolioId::default_portfolio(lessor.did),
+        };
+        PortfolioLockedNFT::remove(&lessee, (&ticker, &nft_id));
+        let nfts = NFTs::new_unverified(ticker, vec![nft_id]);
+        Self::unverified_nft_transfer(&lessee, &receiver, &nfts);
+        Leases::<T>::remove(lease_id);
+        LeaseEndApprovals::remove(lease_id);
+        Self::deposit_event(Event::NFTLeaseEnded(lease_id, ticker, nft_id));
+    }
+
     /// Tranfer ownership of all NFTs.
     #[require_transactional]
     pub fn base_nft_transfer(
@@ -365,6 +968,16 @@ impl<T: Config> Module<T> {
         // Verifies if all rules for transfering the NFTs are being respected
         Self::validate_nft_transfer(sender_portfolio, receiver_portfolio, &nfts)?;
 
+        Self::unverified_nft_transfer(sender_portfolio, receiver_portfolio, nfts);
+        Ok(())
+    }
+
+    /// Transfers ownership of all NFTs without verifying any transfer rule.
+    fn unverified_nft_transfer(
+        sender_portfolio: &PortfolioId,
+        receiver_portfolio: &PortfolioId,
+        nfts: &NFTs,
+    ) {
         // Transfer ownership of the NFT
         // Update the balance of the sender and the receiver
         let transferred_amount = nfts.len() as u64;
@@ -378,8 +991,11 @@ impl<T: Config> Module<T> {
         for nft_id in nfts.ids() {
             PortfolioNFT::remove(sender_portfolio, (nfts.ticker(), nft_id));
             PortfolioNFT::insert(receiver_portfolio, (nfts.ticker(), nft_id), true);
+            // Credentials are only kept while the NFT stays with the same identity
+            if sender_portfolio.did != receiver_portfolio.did {
+                Self::remove_nft_credential(sender_portfolio.did, nfts.ticker(), nft_id);
+            }
         }
-        Ok(())
     }
 
     /// Verifies if and the sender and receiver are not the same, if both have valid balances,
@@ -395,9 +1011,9 @@ impl<T: Config> Module<T> {
             sender_portfolio != receiver_portfolio,
             Error::<T>::InvalidNFTTransferSamePortfolio
         );
-        // Verifies that the asset is not frozen
+        // Verifies that the asset is not frozen, nor in another lifecycle state halting transfers
         ensure!(
-            !Frozen::get(nfts.ticker()),
+            LifecycleStates::get(nfts.ticker()).allows_transfers(),
             Error::<T>::InvalidNFTTransferFrozenAsset
         );
         // Verifies that the sender has the required nft count
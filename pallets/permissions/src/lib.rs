@@ -17,6 +17,9 @@
 //!
 //! This module implements the functionality allowing to check permissions for an account to call
 //! the current extrinsic.
+//!
+//! The `StoreCallMetadata` signed extension also rejects the transactions of accounts whose
+//! identity has restricted itself from making the call.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -35,7 +38,9 @@ use polymesh_primitives::{DispatchableName, PalletName};
 use scale_info::TypeInfo;
 use sp_runtime::{
     traits::{DispatchInfoOf, PostDispatchInfoOf, SignedExtension},
-    transaction_validity::{TransactionValidity, TransactionValidityError, ValidTransaction},
+    transaction_validity::{
+        InvalidTransaction, TransactionValidity, TransactionValidityError, ValidTransaction,
+    },
 };
 use sp_std::{fmt, marker::PhantomData, result::Result, vec};
 
@@ -115,6 +120,21 @@ impl<T: Config> StoreCallMetadata<T> {
         CurrentPalletName::kill();
         CurrentDispatchableName::kill();
     }
+
+    /// Ensures that the identity of `who` hasn't restricted itself from making a call with `metadata`.
+    fn ensure_call_not_filtered(
+        who: &T::AccountId,
+        metadata: &CallMetadata,
+    ) -> Result<(), TransactionValidityError> {
+        if T::Checker::is_account_call_filtered(
+            who,
+            &metadata.pallet_name.as_bytes().into(),
+            &metadata.function_name.as_bytes().into(),
+        ) {
+            return Err(InvalidTransaction::Call.into());
+        }
+        Ok(())
+    }
 }
 
 impl<T> SignedExtension for StoreCallMetadata<T>
@@ -134,22 +154,24 @@ where
 
     fn validate(
         &self,
-        _: &Self::AccountId,
-        _: &Self::Call,
+        who: &Self::AccountId,
+        call: &Self::Call,
         _: &DispatchInfoOf<Self::Call>,
         _: usize,
     ) -> TransactionValidity {
+        Self::ensure_call_not_filtered(who, &call.get_call_metadata())?;
         Ok(ValidTransaction::default())
     }
 
     fn pre_dispatch(
         self,
-        _: &Self::AccountId,
+        who: &Self::AccountId,
         call: &Self::Call,
         _: &DispatchInfoOf<Self::Call>,
         _: usize,
     ) -> Result<Self::Pre, TransactionValidityError> {
         let metadata = call.get_call_metadata();
+        Self::ensure_call_not_filtered(who, &metadata)?;
         Self::set_call_metadata(
             metadata.pallet_name.as_bytes().into(),
             metadata.function_name.as_bytes().into(),
@@ -15,13 +15,14 @@
 
 use core::convert::TryInto;
 use frame_benchmarking::benchmarks;
+use frame_system::RawOrigin;
 use polymesh_common_utilities::{
     asset::Config as AssetConfig,
     benchs::{make_asset, user, AccountIdOf, User, UserBuilder},
     constants::currency::ONE_UNIT,
     TestUtilsFn,
 };
-use polymesh_primitives::{AuthorizationData, NFTs, PortfolioName, Signatory};
+use polymesh_primitives::{AuthorizationData, NFTs, PortfolioName, Signatory, MAX_MEMO_LEN};
 use scale_info::prelude::format;
 use sp_api_hidden_includes_decl_storage::hidden_include::traits::Get;
 use sp_std::prelude::*;
@@ -76,6 +77,17 @@ benchmarks! {
         assert_eq!(Portfolios::get(&did, &next_portfolio_num), portfolio_name);
     }
 
+    create_portfolio_with_salt {
+        let target = user::<T>("target", 0);
+        let did = target.did();
+        let portfolio_name = PortfolioName(vec![65u8; PORTFOLIO_NAME_LEN]);
+        let salt = [7u8; 32];
+        let num = Module::<T>::salted_portfolio_number(&salt);
+    }: _(target.origin, portfolio_name.clone(), salt)
+    verify {
+        assert_eq!(Portfolios::get(&did, &num), portfolio_name);
+    }
+
     delete_portfolio {
         let target = user::<T>("target", 0);
         let did = target.did();
@@ -187,4 +199,98 @@ benchmarks! {
             assert_eq!(PortfolioNFT::get(&alice_custom_portfolio, (&nft_ticker, NFTId(i as u64))), true);
         }
     }
+
+    fund_memos {
+        let m in 0..MAX_MEMO_LEN as u32;
+
+        let alice = UserBuilder::<T>::default().generate_did().build("Alice");
+        let alice_default_portfolio = PortfolioId { did: alice.did(), kind: PortfolioKind::Default };
+        let alice_custom_portfolio = PortfolioId { did: alice.did(), kind: PortfolioKind::User(PortfolioNumber(1)) };
+        Module::<T>::create_portfolio(alice.clone().origin().into(), PortfolioName(b"MyOwnPortfolio".to_vec())).unwrap();
+        let ticker = make_asset(&alice, None);
+        let memo = Some(polymesh_primitives::Memo(vec![7u8; m as usize]));
+        let funds = vec![Fund { description: FundDescription::Fungible { ticker, amount: ONE_UNIT }, memo }];
+    }: move_portfolio_funds_v2(alice.origin, alice_default_portfolio, alice_custom_portfolio, funds)
+    verify {
+        assert_eq!(PortfolioAssetBalances::get(&alice_custom_portfolio, &ticker), ONE_UNIT);
+    }
+
+    add_sweep_rule {
+        let (owner, user_portfolio) = owner_portfolio::<T>();
+        let default_portfolio = PortfolioId::default_portfolio(owner.did());
+        let ticker = make_asset::<T>(&owner, None);
+        let id = SweepRuleCounter::get();
+    }: _(owner.origin, default_portfolio, user_portfolio, ticker, ONE_UNIT, 10u32.into())
+    verify {
+        assert!(SweepRules::<T>::contains_key(id));
+    }
+
+    remove_sweep_rule {
+        let (owner, user_portfolio) = owner_portfolio::<T>();
+        let default_portfolio = PortfolioId::default_portfolio(owner.did());
+        let ticker = make_asset::<T>(&owner, None);
+        let id = SweepRuleCounter::get();
+        Module::<T>::add_sweep_rule(owner.origin.clone().into(), default_portfolio, user_portfolio, ticker, ONE_UNIT, 10u32.into())?;
+    }: _(owner.origin, id)
+    verify {
+        assert!(!SweepRules::<T>::contains_key(id));
+    }
+
+    execute_sweep_rule {
+        let (owner, user_portfolio) = owner_portfolio::<T>();
+        let default_portfolio = PortfolioId::default_portfolio(owner.did());
+        let ticker = make_asset::<T>(&owner, None);
+        PortfolioAssetBalances::insert(&default_portfolio, &ticker, 10 * ONE_UNIT);
+        PortfolioLockedAssets::insert(&default_portfolio, &ticker, ONE_UNIT);
+        let id = SweepRuleCounter::get();
+        Module::<T>::add_sweep_rule(owner.origin.clone().into(), default_portfolio, user_portfolio, ticker, ONE_UNIT, 10u32.into())?;
+    }: _(RawOrigin::Root, id)
+    verify {
+        assert_eq!(PortfolioAssetBalances::get(&default_portfolio, &ticker), 2 * ONE_UNIT);
+        assert_eq!(PortfolioAssetBalances::get(&user_portfolio, &ticker), 8 * ONE_UNIT);
+    }
+
+    move_labelled_balance {
+        let (owner, user_portfolio) = owner_portfolio::<T>();
+        let ticker = make_asset::<T>(&owner, None);
+        let from = PortfolioBalanceLabel(b"available".to_vec());
+        let to = PortfolioBalanceLabel(b"margin".to_vec());
+        PortfolioAssetBalances::insert(&user_portfolio, &ticker, 10 * ONE_UNIT);
+        LabelledBalances::insert(&user_portfolio, (&ticker, &from), 10 * ONE_UNIT);
+        LabelledTotals::insert(&user_portfolio, &ticker, 10 * ONE_UNIT);
+    }: _(owner.origin, user_portfolio, ticker, Some(from.clone()), Some(to.clone()), ONE_UNIT)
+    verify {
+        assert_eq!(LabelledBalances::get(&user_portfolio, (&ticker, &from)), 9 * ONE_UNIT);
+        assert_eq!(LabelledBalances::get(&user_portfolio, (&ticker, &to)), ONE_UNIT);
+    }
+
+    set_settlement_lock_label {
+        let (owner, user_portfolio) = owner_portfolio::<T>();
+        let ticker = make_asset::<T>(&owner, None);
+        let label = PortfolioBalanceLabel(b"margin".to_vec());
+    }: _(owner.origin, user_portfolio, ticker, Some(label.clone()))
+    verify {
+        assert_eq!(SettlementLockLabel::get(&user_portfolio, &ticker), Some(label));
+    }
+
+    add_portfolio_observer {
+        let (owner, user_portfolio) = owner_portfolio::<T>();
+        let observer = user::<T>("observer", 0);
+        for i in 0..(MAX_PORTFOLIO_OBSERVERS as u32 - 1) {
+            let other = user::<T>("other", i);
+            Module::<T>::add_portfolio_observer(owner.origin.clone().into(), user_portfolio, other.did()).unwrap();
+        }
+    }: _(owner.origin, user_portfolio, observer.did())
+    verify {
+        assert!(PortfolioObservers::get(&user_portfolio, observer.did()));
+    }
+
+    remove_portfolio_observer {
+        let (owner, user_portfolio) = owner_portfolio::<T>();
+        let observer = user::<T>("observer", 0);
+        Module::<T>::add_portfolio_observer(owner.origin.clone().into(), user_portfolio, observer.did()).unwrap();
+    }: _(owner.origin, user_portfolio, observer.did())
+    verify {
+        assert!(!PortfolioObservers::get(&user_portfolio, observer.did()));
+    }
 }
@@ -29,6 +29,14 @@
 //! - `move_portfolio_funds`: Moves specified amounts of assets from one portfolio to another portfolio
 //!   of the same DID.
 //! - `rename_portfolio`: Renames a user portfolio.
+//! - `add_sweep_rule`: Periodically moves the balance of an asset above a threshold from one
+//!   portfolio to another of the same DID.
+//! - `remove_sweep_rule`: Removes a sweep rule.
+//! - `execute_sweep_rule`: Executes a sweep rule, dispatched by the scheduler.
+//! - `move_labelled_balance`: Moves an asset balance between labelled sub-balances of a portfolio.
+//! - `set_settlement_lock_label`: Sets the labelled sub-balance that settlement locks take from.
+//! - `add_portfolio_observer`: Designates an identity as an observer of a portfolio, without custody rights.
+//! - `remove_portfolio_observer`: Removes an observer of a portfolio.
 //!
 //! ### Public Functions
 //!
@@ -39,6 +47,7 @@
 //! - `ensure_portfolio_custody`: Makes sure that the given identity has custodian access over the portfolio.
 //! - `ensure_portfolio_transfer_validity`: Makes sure that a transfer between two portfolios is valid.
 //! - `quit_portfolio_custody`: Returns the custody of the portfolio to the owner unilaterally.
+//! - `salted_portfolio_number`: Returns the number of a portfolio created with a given salt.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -49,28 +58,44 @@ use codec::{Decode, Encode};
 use core::{iter, mem};
 use frame_support::{
     decl_error, decl_module, decl_storage,
-    dispatch::{DispatchError, DispatchResult, Weight},
+    dispatch::{DispatchError, DispatchResult, DispatchResultWithPostInfo, Weight},
     ensure,
+    traits::{
+        schedule::{DispatchTime, Named as ScheduleNamed, LOWEST_PRIORITY},
+        Get,
+    },
 };
+use frame_system::{ensure_root, RawOrigin};
 use pallet_identity::{self as identity, PermissionedCallOriginData};
+use polymesh_common_utilities::constants::currency::ONE_UNIT;
 use polymesh_common_utilities::traits::balances::Memo;
-use polymesh_common_utilities::traits::portfolio::PortfolioSubTrait;
+use polymesh_common_utilities::traits::portfolio::{
+    PortfolioCustodyHandler, PortfolioSchedulerCalls, PortfolioSubTrait,
+};
 pub use polymesh_common_utilities::traits::{
     asset::AssetFnTrait,
-    portfolio::{Config, Event, WeightInfo},
+    portfolio::{Config, Event, SweepRuleId, WeightInfo},
 };
 use polymesh_primitives::{
     extract_auth, identity_id::PortfolioValidityResult, storage_migration_ver, Balance, Fund,
-    FundDescription, IdentityId, NFTId, PortfolioId, PortfolioKind, PortfolioName, PortfolioNumber,
-    SecondaryKey, Ticker,
+    FundDescription, IdentityId, NFTId, PortfolioBalanceLabel, PortfolioId, PortfolioKind,
+    PortfolioName, PortfolioNumber, SecondaryKey, Ticker,
 };
 use scale_info::TypeInfo;
-use sp_arithmetic::traits::Zero;
+use sp_arithmetic::traits::{Saturating, Zero};
+use sp_io::hashing::blake2_256;
 use sp_std::collections::btree_set::BTreeSet;
 use sp_std::prelude::*;
 
 type Identity<T> = identity::Module<T>;
 
+/// Maximum number of observers of a portfolio.
+pub const MAX_PORTFOLIO_OBSERVERS: usize = 16;
+
+/// The bit set in the numbers of portfolios created with a salt,
+/// keeping them apart from sequentially assigned numbers.
+pub const SALTED_PORTFOLIO_NUMBER_BIT: u64 = 1 << 63;
+
 /// The ticker and balance of an asset to be moved from one portfolio to another.
 #[derive(Encode, Decode, TypeInfo)]
 #[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
@@ -84,6 +109,27 @@ pub struct MovePortfolioItem {
     pub memo: Option<Memo>,
 }
 
+/// A rule that periodically moves the balance of `ticker` above `threshold`
+/// from portfolio `from` to portfolio `to`.
+#[derive(Encode, Decode, TypeInfo)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SweepRule<BlockNumber> {
+    /// The custodian of `from` that added the rule.
+    pub custodian: IdentityId,
+    /// The portfolio that is swept.
+    pub from: PortfolioId,
+    /// The portfolio receiving the swept balance.
+    pub to: PortfolioId,
+    /// The ticker of the asset to be swept.
+    pub ticker: Ticker,
+    /// The free balance that is kept in `from`.
+    pub threshold: Balance,
+    /// The number of blocks between two executions of the rule.
+    pub period: BlockNumber,
+    /// The block in which the rule is next executed.
+    pub next_run: BlockNumber,
+}
+
 decl_storage! {
     trait Store for Module<T: Config> as Portfolio {
         /// The next portfolio sequence number of an identity.
@@ -134,6 +180,38 @@ decl_storage! {
         pub PortfolioLockedNFT get(fn portfolio_locked_nft):
             double_map hasher(twox_64_concat) PortfolioId, hasher(blake2_128_concat) (Ticker, NFTId) => bool;
 
+        /// The next sweep rule ID.
+        pub SweepRuleCounter get(fn sweep_rule_counter): SweepRuleId;
+
+        /// Sweep rules identified by their ID.
+        pub SweepRules get(fn sweep_rules):
+            map hasher(twox_64_concat) SweepRuleId => Option<SweepRule<T::BlockNumber>>;
+
+        /// Labelled sub-balances of the free balance of portfolios.
+        /// These assets show up in portfolio balance but can only be moved between labels,
+        /// or locked by a settlement if the label is the portfolio's settlement lock label.
+        pub LabelledBalances get(fn labelled_balance):
+            double_map hasher(twox_64_concat) PortfolioId, hasher(blake2_128_concat) (Ticker, PortfolioBalanceLabel) => Balance;
+
+        /// The sum of all labelled sub-balances of an asset in a portfolio.
+        pub LabelledTotals get(fn labelled_total):
+            double_map hasher(twox_64_concat) PortfolioId, hasher(blake2_128_concat) Ticker => Balance;
+
+        /// The labelled sub-balance that settlement locks of an asset in a portfolio take from.
+        /// `None` implies that locks take from the unlabelled free balance.
+        pub SettlementLockLabel get(fn settlement_lock_label):
+            double_map hasher(twox_64_concat) PortfolioId, hasher(blake2_128_concat) Ticker => Option<PortfolioBalanceLabel>;
+
+        /// Identities designated as observers of a portfolio by its owner, e.g. auditors or fund administrators.
+        /// The chain state is public, so this grants no access: it only lets UIs and off-chain services
+        /// list the observers of a portfolio. `false` values are never explicitly stored in the map.
+        pub PortfolioObservers get(fn portfolio_observer):
+            double_map hasher(twox_64_concat) PortfolioId, hasher(identity) IdentityId => bool;
+
+        /// Tracks all the portfolios observed by a particular identity. Only used by the UIs.
+        pub ObservedPortfolios get(fn observed_portfolio):
+            double_map hasher(identity) IdentityId, hasher(twox_64_concat) PortfolioId => bool;
+
         /// Storage version.
         StorageVersion get(fn storage_version) build(|_| Version::new(2)): Version;
     }
@@ -172,7 +250,29 @@ decl_error! {
         /// Only owned NFTs can be moved between portfolios.
         InvalidTransferNFTNotOwned,
         /// Locked NFTs can not be moved between portfolios.
-        InvalidTransferNFTIsLocked
+        InvalidTransferNFTIsLocked,
+        /// The period of a sweep rule must be at least one block.
+        InvalidSweepPeriod,
+        /// The sweep rule doesn't exist.
+        NoSuchSweepRule,
+        /// The source and destination labels should be different.
+        SameBalanceLabel,
+        /// The labelled sub-balance is too low.
+        InsufficientLabelledBalance,
+        /// The memo of a fund is longer than `MAX_MEMO_LEN` bytes.
+        MemoTooLong,
+        /// The identity is already an observer of the portfolio.
+        ObserverAlreadyAdded,
+        /// The identity is not an observer of the portfolio.
+        ObserverNotFound,
+        /// The portfolio already has `MAX_PORTFOLIO_OBSERVERS` observers.
+        TooManyObservers,
+        /// A portfolio with the number derived from the salt already exists.
+        PortfolioNumberAlreadyInUse,
+        /// Locked and labelled balances can't be moved to another ticker.
+        BalanceNotMigratable,
+        /// The scheduler has no room for the next run of the sweep rule.
+        FailedToScheduleSweep
     }
 }
 
@@ -231,8 +331,17 @@ decl_module! {
             PortfolioAssetBalances::remove_prefix(&pid, None);
             #[allow(deprecated)]
             PortfolioLockedAssets::remove_prefix(&pid, None);
+            #[allow(deprecated)]
+            LabelledBalances::remove_prefix(&pid, None);
+            #[allow(deprecated)]
+            LabelledTotals::remove_prefix(&pid, None);
+            #[allow(deprecated)]
+            SettlementLockLabel::remove_prefix(&pid, None);
             PortfoliosInCustody::remove(&Self::custodian(&pid), &pid);
             PortfolioCustodian::remove(&pid);
+            for (observer, _) in PortfolioObservers::drain_prefix(&pid) {
+                ObservedPortfolios::remove(&observer, &pid);
+            }
 
             // Emit event.
             Self::deposit_event(Event::PortfolioDeleted(primary_did, num));
@@ -324,14 +433,16 @@ decl_module! {
 
         /// When called by the custodian of `portfolio_id`,
         /// allows returning the custody of the portfolio to the portfolio owner unilaterally.
+        /// The affirmations made by the custodian are withdrawn by the `CustodyHandler`.
         ///
         /// # Errors
         /// * `UnauthorizedCustodian` if the caller is not the current custodian of `portfolio_id`.
         ///
         /// # Permissions
         /// * Portfolio
-        #[weight = <T as Config>::WeightInfo::quit_portfolio_custody()]
-        pub fn quit_portfolio_custody(origin, pid: PortfolioId) {
+        #[weight = <T as Config>::WeightInfo::quit_portfolio_custody()
+            .saturating_add(T::CustodyHandler::on_custody_changed_max_weight())]
+        pub fn quit_portfolio_custody(origin, pid: PortfolioId) -> DispatchResultWithPostInfo {
             let did = Identity::<T>::ensure_perms(origin)?;
             let custodian = Self::custodian(&pid);
             ensure!(did == custodian, Error::<T>::UnauthorizedCustodian);
@@ -343,10 +454,15 @@ decl_module! {
                 pid,
                 pid.did,
             ));
+            let handler_weight = T::CustodyHandler::on_custody_changed(&pid, pid.did);
+            Ok(Some(<T as Config>::WeightInfo::quit_portfolio_custody().saturating_add(handler_weight)).into())
         }
 
-        #[weight = <T as Config>::WeightInfo::accept_portfolio_custody()]
-        pub fn accept_portfolio_custody(origin, auth_id: u64) -> DispatchResult {
+        /// Accepts the custody of a portfolio.
+        /// The affirmations made by the previous custodian are withdrawn by the `CustodyHandler`.
+        #[weight = <T as Config>::WeightInfo::accept_portfolio_custody()
+            .saturating_add(T::CustodyHandler::on_custody_changed_max_weight())]
+        pub fn accept_portfolio_custody(origin, auth_id: u64) -> DispatchResultWithPostInfo {
             Self::base_accept_portfolio_custody(origin, auth_id)
         }
 
@@ -388,6 +504,206 @@ decl_module! {
             Ok(())
         }
 
+        /// Adds a rule that, every `period` blocks, moves the free balance of `ticker`
+        /// above `threshold` from portfolio `from` to portfolio `to` of the same identity.
+        /// Must be called by the custodian of `from`. The first sweep happens `period` blocks from now.
+        /// Each sweep is dispatched by the scheduler, which then schedules the next one.
+        ///
+        /// # Errors
+        /// * `PortfolioDoesNotExist` if `to` references an invalid portfolio.
+        /// * `DestinationIsSamePortfolio` if both portfolios are the same.
+        /// * `DifferentIdentityPortfolios` if the portfolios belong to different identities.
+        /// * `UnauthorizedCustodian` if the caller is not the custodian of `from`.
+        /// * `InvalidSweepPeriod` if `period` is zero.
+        /// * `FailedToScheduleSweep` if the scheduler has no room for the first sweep.
+        ///
+        /// # Permissions
+        /// * Portfolio
+        #[weight = <T as Config>::WeightInfo::add_sweep_rule()]
+        pub fn add_sweep_rule(
+            origin,
+            from: PortfolioId,
+            to: PortfolioId,
+            ticker: Ticker,
+            threshold: Balance,
+            period: T::BlockNumber,
+        ) -> DispatchResult {
+            let custodian = Self::ensure_portfolios_validity_and_permissions(origin, from, to)?;
+            Self::ensure_portfolio_validity(&from)?;
+            ensure!(!period.is_zero(), Error::<T>::InvalidSweepPeriod);
+
+            let id = SweepRuleCounter::try_mutate(pallet_base::try_next_post::<T, _>)?;
+            let now = frame_system::Pallet::<T>::block_number();
+            let next_run = now.saturating_add(period);
+            Self::schedule_sweep(id, next_run)?;
+            SweepRules::<T>::insert(id, SweepRule {
+                custodian,
+                from,
+                to,
+                ticker,
+                threshold,
+                period,
+                next_run,
+            });
+            Self::deposit_event(Event::SweepRuleAdded(custodian, id, from, to, ticker, threshold));
+            Ok(())
+        }
+
+        /// Removes the sweep rule `id`. Must be called by the custodian that added the rule.
+        ///
+        /// # Errors
+        /// * `NoSuchSweepRule` if the rule doesn't exist.
+        /// * `UnauthorizedCustodian` if the caller is not the custodian of the rule.
+        ///
+        /// # Permissions
+        /// * Portfolio
+        #[weight = <T as Config>::WeightInfo::remove_sweep_rule()]
+        pub fn remove_sweep_rule(origin, id: SweepRuleId) -> DispatchResult {
+            let PermissionedCallOriginData {
+                primary_did,
+                secondary_key,
+                ..
+            } = Identity::<T>::ensure_origin_call_permissions(origin)?;
+            let rule = SweepRules::<T>::get(id).ok_or(Error::<T>::NoSuchSweepRule)?;
+            ensure!(rule.custodian == primary_did, Error::<T>::UnauthorizedCustodian);
+            Self::ensure_user_portfolio_permission(secondary_key.as_ref(), rule.from)?;
+
+            Self::remove_sweep(id, &rule);
+            Ok(())
+        }
+
+        /// Moves `amount` of `ticker` in portfolio `pid` from the sub-balance labelled `from`
+        /// to the one labelled `to`, where `None` stands for the unlabelled free balance.
+        /// Must be called by the custodian of the portfolio.
+        ///
+        /// Labelled balances can't be moved out of the portfolio nor locked, except by settlements
+        /// when the label is the settlement lock label of the asset in the portfolio.
+        ///
+        /// # Errors
+        /// * `PortfolioDoesNotExist` if `pid` references an invalid portfolio.
+        /// * `UnauthorizedCustodian` if the caller is not the custodian of `pid`.
+        /// * `SameBalanceLabel` if `from` and `to` are the same.
+        /// * `InsufficientPortfolioBalance` if `from` is `None` and there isn't enough unlabelled free balance.
+        /// * `InsufficientLabelledBalance` if the sub-balance labelled `from` is too low.
+        ///
+        /// # Permissions
+        /// * Portfolio
+        #[weight = <T as Config>::WeightInfo::move_labelled_balance()]
+        pub fn move_labelled_balance(
+            origin,
+            pid: PortfolioId,
+            ticker: Ticker,
+            from: Option<PortfolioBalanceLabel>,
+            to: Option<PortfolioBalanceLabel>,
+            amount: Balance,
+        ) -> DispatchResult {
+            Self::base_move_labelled_balance(origin, pid, ticker, from, to, amount)
+        }
+
+        /// Sets the labelled sub-balance that settlements lock `ticker` from in portfolio `pid`.
+        /// With `None`, settlements lock from the unlabelled free balance again.
+        /// Must be called by the custodian of the portfolio.
+        ///
+        /// # Errors
+        /// * `PortfolioDoesNotExist` if `pid` references an invalid portfolio.
+        /// * `UnauthorizedCustodian` if the caller is not the custodian of `pid`.
+        ///
+        /// # Permissions
+        /// * Portfolio
+        #[weight = <T as Config>::WeightInfo::set_settlement_lock_label()]
+        pub fn set_settlement_lock_label(
+            origin,
+            pid: PortfolioId,
+            ticker: Ticker,
+            label: Option<PortfolioBalanceLabel>,
+        ) -> DispatchResult {
+            let custodian = Self::ensure_custodian_origin(origin, pid)?;
+            if let Some(label) = &label {
+                pallet_base::ensure_string_limited::<T>(label)?;
+            }
+
+            SettlementLockLabel::mutate(&pid, &ticker, |l| *l = label.clone());
+            Self::deposit_event(Event::SettlementLockLabelSet(custodian, pid, ticker, label));
+            Ok(())
+        }
+
+        /// Designates `observer` as an observer of portfolio `pid`, e.g. an auditor or a fund administrator.
+        /// Observers have no custody rights over the portfolio.
+        /// Must be called by the owner of the portfolio.
+        ///
+        /// # Errors
+        /// * `PortfolioDoesNotExist` if `pid` references an invalid portfolio.
+        /// * `DidDoesNotExist` if `observer` doesn't exist.
+        /// * `ObserverAlreadyAdded` if `observer` is already an observer of `pid`.
+        /// * `TooManyObservers` if `pid` already has `MAX_PORTFOLIO_OBSERVERS` observers.
+        ///
+        /// # Permissions
+        /// * Portfolio
+        #[weight = <T as Config>::WeightInfo::add_portfolio_observer()]
+        pub fn add_portfolio_observer(origin, pid: PortfolioId, observer: IdentityId) -> DispatchResult {
+            let owner = Self::ensure_owner_origin(origin, pid)?;
+            Identity::<T>::ensure_id_record_exists(observer)?;
+            ensure!(!PortfolioObservers::get(&pid, &observer), Error::<T>::ObserverAlreadyAdded);
+            ensure!(
+                PortfolioObservers::iter_prefix(&pid).count() < MAX_PORTFOLIO_OBSERVERS,
+                Error::<T>::TooManyObservers
+            );
+
+            PortfolioObservers::insert(&pid, &observer, true);
+            ObservedPortfolios::insert(&observer, &pid, true);
+            Self::deposit_event(Event::PortfolioObserverAdded(owner, pid, observer));
+            Ok(())
+        }
+
+        /// Removes `observer` from the observers of portfolio `pid`.
+        /// Must be called by the owner of the portfolio.
+        ///
+        /// # Errors
+        /// * `ObserverNotFound` if `observer` is not an observer of `pid`.
+        ///
+        /// # Permissions
+        /// * Portfolio
+        #[weight = <T as Config>::WeightInfo::remove_portfolio_observer()]
+        pub fn remove_portfolio_observer(origin, pid: PortfolioId, observer: IdentityId) -> DispatchResult {
+            let owner = Self::ensure_owner_origin(origin, pid)?;
+            ensure!(PortfolioObservers::get(&pid, &observer), Error::<T>::ObserverNotFound);
+
+            PortfolioObservers::remove(&pid, &observer);
+            ObservedPortfolios::remove(&observer, &pid);
+            Self::deposit_event(Event::PortfolioObserverRemoved(owner, pid, observer));
+            Ok(())
+        }
+
+        /// Creates a portfolio with the given `name`, numbered deterministically from `salt`
+        /// instead of the identity's next sequential number.
+        /// The same salt yields the same number on any chain, see `salted_portfolio_number`.
+        ///
+        /// # Errors
+        /// * `PortfolioNameAlreadyInUse` if the identity already has a portfolio named `name`.
+        /// * `PortfolioNumberAlreadyInUse` if the identity already has a portfolio created with `salt`.
+        #[weight = <T as Config>::WeightInfo::create_portfolio_with_salt()]
+        pub fn create_portfolio_with_salt(origin, name: PortfolioName, salt: [u8; 32]) {
+            let did = Identity::<T>::ensure_perms(origin)?;
+            Self::ensure_name_unique(&did, &name)?;
+
+            let num = Self::salted_portfolio_number(&salt);
+            ensure!(!Portfolios::contains_key(&did, &num), Error::<T>::PortfolioNumberAlreadyInUse);
+            NameToNumber::insert(&did, &name, num);
+            Portfolios::insert(&did, &num, name.clone());
+            Self::deposit_event(Event::PortfolioCreated(did, num, name));
+        }
+
+        /// Executes the sweep rule `id` and schedules its next run.
+        /// Dispatched by the scheduler for the root origin.
+        ///
+        /// # Errors
+        /// * `BadOrigin` - Only root allowed.
+        #[weight = <T as Config>::WeightInfo::execute_sweep_rule()]
+        pub fn execute_sweep_rule(origin, id: SweepRuleId) {
+            ensure_root(origin)?;
+            Self::execute_sweep(id, frame_system::Pallet::<T>::block_number());
+        }
+
         fn on_runtime_upgrade() -> Weight {
             use polymesh_primitives::storage_migrate_on;
 
@@ -421,6 +737,23 @@ impl<T: Config> Module<T> {
         PortfolioCustodian::get(&pid).unwrap_or(pid.did)
     }
 
+    /// Moves the balances of `old` in the portfolios of `did` to `new`.
+    /// Fails if some of these balances are locked, e.g. by pending instructions, or labelled.
+    pub fn migrate_ticker_balances(did: IdentityId, old: &Ticker, new: &Ticker) -> DispatchResult {
+        let user_portfolios = Portfolios::iter_key_prefix(did).map(PortfolioKind::User);
+        for kind in iter::once(PortfolioKind::Default).chain(user_portfolios) {
+            let pid = PortfolioId { did, kind };
+            ensure!(
+                Self::locked_assets(&pid, old).is_zero()
+                    && Self::labelled_total(&pid, old).is_zero(),
+                Error::<T>::BalanceNotMigratable
+            );
+            PortfolioAssetBalances::swap(&pid, old, &pid, new);
+            SettlementLockLabel::swap(&pid, old, &pid, new);
+        }
+        Ok(())
+    }
+
     /// Returns the ticker balance of the identity's default portfolio.
     pub fn default_portfolio_balance(did: IdentityId, ticker: &Ticker) -> Balance {
         Self::portfolio_asset_balances(PortfolioId::default_portfolio(did), ticker)
@@ -449,6 +782,14 @@ impl<T: Config> Module<T> {
         NextPortfolioNumber::mutate(did, |num| mem::replace(num, PortfolioNumber(num.0 + 1)))
     }
 
+    /// Returns the number of a portfolio created with `salt`.
+    pub fn salted_portfolio_number(salt: &[u8; 32]) -> PortfolioNumber {
+        let hash = blake2_256(&(b"portfolio", salt).encode());
+        let mut num = [0u8; 8];
+        num.copy_from_slice(&hash[..8]);
+        PortfolioNumber(u64::from_le_bytes(num) | SALTED_PORTFOLIO_NUMBER_BIT)
+    }
+
     /// Ensures that there is no portfolio with the desired `name` yet.
     fn ensure_name_unique(did: &IdentityId, name: &PortfolioName) -> DispatchResult {
         pallet_base::ensure_string_limited::<T>(name)?;
@@ -593,12 +934,13 @@ impl<T: Config> Module<T> {
         ticker: &Ticker,
         amount: Balance,
     ) -> DispatchResult {
-        // Ensure portfolio has enough free balance
+        // Ensure portfolio has enough unlabelled free balance
         let total_balance = Self::portfolio_asset_balances(&pid, ticker);
-        let locked_balance = Self::locked_assets(&pid, ticker);
+        let reserved_balance =
+            Self::locked_assets(&pid, ticker).saturating_add(Self::labelled_total(&pid, ticker));
         let remaining_balance = total_balance
             .checked_sub(amount)
-            .filter(|rb| rb >= &locked_balance)
+            .filter(|rb| rb >= &reserved_balance)
             .ok_or(Error::<T>::InsufficientPortfolioBalance)?;
 
         // Update portfolio balance.
@@ -619,6 +961,13 @@ impl<T: Config> Module<T> {
         Self::ensure_user_portfolio_permission(secondary_key, portfolio)
     }
 
+    /// Returns the balance of `ticker` in `portfolio` that is neither locked nor labelled.
+    pub fn unlabelled_free_balance(portfolio: &PortfolioId, ticker: &Ticker) -> Balance {
+        Self::portfolio_asset_balances(portfolio, ticker)
+            .saturating_sub(Self::locked_assets(portfolio, ticker))
+            .saturating_sub(Self::labelled_total(portfolio, ticker))
+    }
+
     /// Ensure `portfolio` has sufficient balance of `ticker` to lock/withdraw `amount`.
     pub fn ensure_sufficient_balance(
         portfolio: &PortfolioId,
@@ -626,8 +975,7 @@ impl<T: Config> Module<T> {
         amount: Balance,
     ) -> DispatchResult {
         T::Asset::ensure_granular(ticker, amount)?;
-        Self::portfolio_asset_balances(portfolio, ticker)
-            .saturating_sub(Self::locked_assets(portfolio, ticker))
+        Self::unlabelled_free_balance(portfolio, ticker)
             .checked_sub(amount)
             .ok_or_else(|| Error::<T>::InsufficientPortfolioBalance.into())
             .map(drop)
@@ -640,8 +988,25 @@ impl<T: Config> Module<T> {
         PortfolioLockedAssets::mutate(portfolio, ticker, |l| *l = l.saturating_add(amount));
     }
 
-    fn base_accept_portfolio_custody(origin: T::RuntimeOrigin, auth_id: u64) -> DispatchResult {
+    fn base_accept_portfolio_custody(
+        origin: T::RuntimeOrigin,
+        auth_id: u64,
+    ) -> DispatchResultWithPostInfo {
         let to = Identity::<T>::ensure_perms(origin)?;
+        let handler_weight = Self::unsafe_accept_portfolio_custody(to, auth_id)?;
+        Ok(Some(
+            <T as Config>::WeightInfo::accept_portfolio_custody().saturating_add(handler_weight),
+        )
+        .into())
+    }
+
+    /// Accepts the portfolio custody authorization `auth_id` on behalf of `to`,
+    /// returning the weight consumed by the custody handler.
+    fn unsafe_accept_portfolio_custody(
+        to: IdentityId,
+        auth_id: u64,
+    ) -> Result<Weight, DispatchError> {
+        let mut handler_weight = Weight::zero();
         Identity::<T>::accept_auth_with(&to.into(), auth_id, |data, from| {
             let pid = extract_auth!(data, PortfolioCustody(p));
 
@@ -659,8 +1024,10 @@ impl<T: Config> Module<T> {
             }
 
             Self::deposit_event(Event::PortfolioCustodianChanged(to, pid, to));
+            handler_weight = T::CustodyHandler::on_custody_changed(&pid, to);
             Ok(())
-        })
+        })?;
+        Ok(handler_weight)
     }
 
     /// Verifies if the portfolios are different, if the move is between the same identity, if the receiving portfolio exists,
@@ -690,6 +1057,166 @@ impl<T: Config> Module<T> {
         Ok(origin_data.primary_did)
     }
 
+    /// Schedules the execution of the sweep rule `id` in block `at`.
+    fn schedule_sweep(id: SweepRuleId, at: T::BlockNumber) -> DispatchResult {
+        T::PortfolioScheduler::schedule_named(
+            id.schedule_name(&at),
+            DispatchTime::At(at),
+            None,
+            LOWEST_PRIORITY,
+            RawOrigin::Root.into(),
+            T::PortfolioScheduledCalls::execute_sweep_rule(id),
+        )
+        .map_err(|_| Error::<T>::FailedToScheduleSweep)?;
+        Ok(())
+    }
+
+    /// Removes the sweep rule `id` and its scheduled execution.
+    fn remove_sweep(id: SweepRuleId, rule: &SweepRule<T::BlockNumber>) {
+        let _ = T::PortfolioScheduler::cancel_named(id.schedule_name(&rule.next_run));
+        SweepRules::<T>::remove(id);
+        Self::deposit_event(Event::SweepRuleRemoved(rule.custodian, id));
+    }
+
+    /// Executes the sweep rule `id` in block `now` and schedules its next execution.
+    /// The rule is removed if its custodian no longer has custody of the source portfolio,
+    /// if either portfolio no longer exists, or if its next execution can't be scheduled.
+    fn execute_sweep(id: SweepRuleId, now: T::BlockNumber) {
+        let mut rule = match SweepRules::<T>::get(id) {
+            Some(rule) => rule,
+            None => return,
+        };
+        let valid = Self::ensure_portfolio_custody(rule.from, rule.custodian)
+            .and_then(|_| Self::ensure_portfolio_validity(&rule.from))
+            .and_then(|_| Self::ensure_portfolio_validity(&rule.to));
+        if valid.is_err() {
+            // Custody has been lost or a portfolio has been deleted,
+            // the rule no longer applies. It is no longer scheduled.
+            SweepRules::<T>::remove(id);
+            Self::deposit_event(Event::SweepRuleRemoved(rule.custodian, id));
+            return;
+        }
+
+        // Only sweep the unlabelled free balance above the threshold.
+        let free = Self::unlabelled_free_balance(&rule.from, &rule.ticker);
+        let mut amount = free.saturating_sub(rule.threshold);
+        if T::Asset::ensure_granular(&rule.ticker, amount).is_err() {
+            // Amounts of indivisible assets are rounded down to whole units.
+            amount -= amount % ONE_UNIT;
+        }
+        if !amount.is_zero() {
+            Self::unchecked_transfer_portfolio_balance(&rule.from, &rule.to, &rule.ticker, amount);
+            Self::deposit_event(Event::MovedBetweenPortfolios(
+                rule.custodian,
+                rule.from,
+                rule.to,
+                rule.ticker,
+                amount,
+                None,
+            ));
+        }
+        Self::deposit_event(Event::SweepRuleExecuted(rule.custodian, id, amount));
+
+        rule.next_run = now.saturating_add(rule.period);
+        if Self::schedule_sweep(id, rule.next_run).is_err() {
+            SweepRules::<T>::remove(id);
+            Self::deposit_event(Event::SweepRuleRemoved(rule.custodian, id));
+            return;
+        }
+        SweepRules::<T>::insert(id, rule);
+    }
+
+    /// Ensures that `origin` is permissioned for `pid`, that it exists,
+    /// and that the caller is its custodian. Returns the custodian.
+    /// Ensures that `origin` is the owner of `pid`, with permission over the portfolio.
+    fn ensure_owner_origin(
+        origin: T::RuntimeOrigin,
+        pid: PortfolioId,
+    ) -> Result<IdentityId, DispatchError> {
+        let PermissionedCallOriginData {
+            primary_did,
+            secondary_key,
+            ..
+        } = Identity::<T>::ensure_origin_call_permissions(origin)?;
+        ensure!(pid.did == primary_did, Error::<T>::PortfolioDoesNotExist);
+        Self::ensure_portfolio_validity(&pid)?;
+        Self::ensure_user_portfolio_permission(secondary_key.as_ref(), pid)?;
+        Ok(primary_did)
+    }
+
+    fn ensure_custodian_origin(
+        origin: T::RuntimeOrigin,
+        pid: PortfolioId,
+    ) -> Result<IdentityId, DispatchError> {
+        let PermissionedCallOriginData {
+            primary_did,
+            secondary_key,
+            ..
+        } = Identity::<T>::ensure_origin_call_permissions(origin)?;
+        Self::ensure_portfolio_validity(&pid)?;
+        Self::ensure_portfolio_custody_and_permission(pid, primary_did, secondary_key.as_ref())?;
+        Ok(primary_did)
+    }
+
+    fn base_move_labelled_balance(
+        origin: T::RuntimeOrigin,
+        pid: PortfolioId,
+        ticker: Ticker,
+        from: Option<PortfolioBalanceLabel>,
+        to: Option<PortfolioBalanceLabel>,
+        amount: Balance,
+    ) -> DispatchResult {
+        let custodian = Self::ensure_custodian_origin(origin, pid)?;
+        ensure!(from != to, Error::<T>::SameBalanceLabel);
+        if let Some(label) = &to {
+            pallet_base::ensure_string_limited::<T>(label)?;
+        }
+        match &from {
+            Some(label) => Self::ensure_sufficient_labelled_balance(&pid, &ticker, label, amount)?,
+            None => Self::ensure_sufficient_balance(&pid, &ticker, amount)?,
+        }
+
+        if let Some(label) = &from {
+            Self::unchecked_reduce_labelled_balance(&pid, &ticker, label, amount);
+        }
+        if let Some(label) = &to {
+            LabelledBalances::mutate(&pid, (&ticker, label), |b| *b = b.saturating_add(amount));
+            LabelledTotals::mutate(&pid, &ticker, |t| *t = t.saturating_add(amount));
+        }
+        Self::deposit_event(Event::LabelledBalanceMoved(
+            custodian, pid, ticker, from, to, amount,
+        ));
+        Ok(())
+    }
+
+    /// Ensure the sub-balance of `ticker` labelled `label` in `portfolio` is at least `amount`.
+    fn ensure_sufficient_labelled_balance(
+        portfolio: &PortfolioId,
+        ticker: &Ticker,
+        label: &PortfolioBalanceLabel,
+        amount: Balance,
+    ) -> DispatchResult {
+        T::Asset::ensure_granular(ticker, amount)?;
+        ensure!(
+            Self::labelled_balance(portfolio, (ticker, label)) >= amount,
+            Error::<T>::InsufficientLabelledBalance
+        );
+        Ok(())
+    }
+
+    /// Reduces the sub-balance of `ticker` labelled `label` in `portfolio` by `amount`.
+    fn unchecked_reduce_labelled_balance(
+        portfolio: &PortfolioId,
+        ticker: &Ticker,
+        label: &PortfolioBalanceLabel,
+        amount: Balance,
+    ) {
+        LabelledBalances::mutate(portfolio, (ticker, label), |b| {
+            *b = b.saturating_sub(amount)
+        });
+        LabelledTotals::mutate(portfolio, ticker, |t| *t = t.saturating_sub(amount));
+    }
+
     /// Verifies if the sending portfolio has the right balance for the transfer.
     fn ensure_valid_balances(
         sender_portfolio: &PortfolioId,
@@ -713,6 +1240,10 @@ impl<T: Config> Module<T> {
         let mut unique_tickers = BTreeSet::new();
         // Ensure there are sufficient funds for all moves
         for fund in funds {
+            ensure!(
+                fund.memo.as_ref().map_or(true, |memo| memo.is_valid_len()),
+                Error::<T>::MemoTooLong
+            );
             match &fund.description {
                 FundDescription::Fungible { ticker, amount } => {
                     ensure!(
@@ -796,10 +1327,30 @@ impl<T: Config> PortfolioSubTrait<T::AccountId> for Module<T> {
     /// This is used internally by the settlement engine to prevent users from using the same funds
     /// in multiple ongoing settlements
     ///
+    /// The tokens are taken from the settlement lock label of the asset in the portfolio, if any,
+    /// and from the unlabelled free balance otherwise. Unlocked tokens are always unlabelled.
+    ///
     /// # Errors
     /// * `InsufficientPortfolioBalance` if the portfolio does not have enough free balance to lock
+    /// * `InsufficientLabelledBalance` if the settlement lock label does not have enough balance to lock
+    /// * `InsufficientPortfolioBalance` if the labelled balance isn't backed by enough usable balance
     fn lock_tokens(portfolio: &PortfolioId, ticker: &Ticker, amount: Balance) -> DispatchResult {
-        Self::ensure_sufficient_balance(portfolio, ticker, amount)?;
+        match Self::settlement_lock_label(portfolio, ticker) {
+            Some(label) => {
+                Self::ensure_sufficient_labelled_balance(portfolio, ticker, &label, amount)?;
+                // The label must also be backed by usable balance not reserved by other labels,
+                // so that the sum of labels and locks never exceeds the portfolio balance.
+                let other_labels = Self::labelled_total(portfolio, ticker)
+                    .saturating_sub(Self::labelled_balance(portfolio, (ticker, &label)));
+                Self::portfolio_asset_balances(portfolio, ticker)
+                    .saturating_sub(Self::locked_assets(portfolio, ticker))
+                    .saturating_sub(other_labels)
+                    .checked_sub(amount)
+                    .ok_or(Error::<T>::InsufficientPortfolioBalance)?;
+                Self::unchecked_reduce_labelled_balance(portfolio, ticker, &label, amount);
+            }
+            None => Self::ensure_sufficient_balance(portfolio, ticker, amount)?,
+        }
         Self::unchecked_lock_tokens(portfolio, ticker, amount);
         Ok(())
     }
@@ -870,4 +1421,8 @@ impl<T: Config> PortfolioSubTrait<T::AccountId> for Module<T> {
         PortfolioLockedNFT::remove(portfolio_id, (ticker, nft_id));
         Ok(())
     }
+
+    fn accept_portfolio_custody(to: IdentityId, auth_id: u64) -> DispatchResult {
+        Self::unsafe_accept_portfolio_custody(to, auth_id).map(drop)
+    }
 }
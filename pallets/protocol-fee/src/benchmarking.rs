@@ -29,4 +29,15 @@ benchmarks! {
         let origin = RawOrigin::Root;
         let op = ProtocolOp::AssetRegisterTicker;
     }: _(origin, op, 0)
+
+    schedule_fee_holiday {
+        let origin = RawOrigin::Root;
+        let op = ProtocolOp::AssetRegisterTicker;
+    }: _(origin, op, 1u32.into(), 100u32.into())
+
+    cancel_fee_holiday {
+        let op = ProtocolOp::AssetRegisterTicker;
+        Module::<T>::schedule_fee_holiday(RawOrigin::Root.into(), op, 1u32.into(), 100u32.into())
+            .expect("failed to schedule fee holiday");
+    }: _(RawOrigin::Root, op)
 }
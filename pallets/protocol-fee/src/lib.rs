@@ -26,6 +26,9 @@
 //!
 //! - [change_coefficient](Module::change_coefficient) - It changes the fee coefficient.
 //! - [change_base_fee](Module::change_base_fee) - It changes the base fee.
+//! - [schedule_fee_holiday](Module::schedule_fee_holiday) - It schedules a window without fees for
+//! an operation.
+//! - [cancel_fee_holiday](Module::cancel_fee_holiday) - It cancels the fee holiday of an operation.
 //!
 //! ### Public Functions
 //!
@@ -38,9 +41,11 @@
 #[cfg(feature = "runtime-benchmarks")]
 pub mod benchmarking;
 
+use codec::{Decode, Encode};
 use frame_support::{
     decl_error, decl_event, decl_module, decl_storage,
     dispatch::{DispatchError, DispatchResult},
+    ensure,
     traits::{Currency, ExistenceRequirement, OnUnbalanced, WithdrawReasons},
     weights::Weight,
 };
@@ -53,6 +58,7 @@ use polymesh_common_utilities::{
     GC_DID,
 };
 use polymesh_primitives::{Balance, IdentityId, PosRatio};
+use scale_info::TypeInfo;
 use sp_runtime::{traits::Zero, Perbill};
 use sp_std::vec;
 
@@ -65,6 +71,25 @@ type WithdrawFeeResult<T> = sp_std::result::Result<NegativeImbalanceOf<T>, Dispa
 pub trait WeightInfo {
     fn change_coefficient() -> Weight;
     fn change_base_fee() -> Weight;
+    fn schedule_fee_holiday() -> Weight;
+    fn cancel_fee_holiday() -> Weight;
+}
+
+/// A window of blocks `[start, end)` during which an operation is free of protocol fees.
+#[derive(Encode, Decode, TypeInfo)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FeeHoliday<BlockNumber> {
+    /// The first block of the holiday.
+    pub start: BlockNumber,
+    /// The block at which fees apply again.
+    pub end: BlockNumber,
+}
+
+impl<BlockNumber: PartialOrd> FeeHoliday<BlockNumber> {
+    /// Returns `true` if `block_number` is within the holiday.
+    pub fn contains(&self, block_number: &BlockNumber) -> bool {
+        self.start <= *block_number && *block_number < self.end
+    }
 }
 
 pub trait Config: frame_system::Config + IdentityConfig {
@@ -88,6 +113,10 @@ decl_error! {
         UnHandledImbalances,
         /// Insufficient subsidy balance to pay the fee.
         InsufficientSubsidyBalance,
+        /// The fee holiday must end after it starts and after the current block.
+        InvalidFeeHoliday,
+        /// The operation has no fee holiday.
+        NoFeeHoliday,
     }
 }
 
@@ -103,12 +132,16 @@ decl_storage! {
                 config.coefficient
             }
         }): PosRatio;
+        /// Scheduled windows without fees for operations.
+        pub FeeHolidays get(fn fee_holidays):
+            map hasher(twox_64_concat) ProtocolOp => Option<FeeHoliday<T::BlockNumber>>;
     }
 }
 
 decl_event! {
     pub enum Event<T> where
         AccountId = <T as frame_system::Config>::AccountId,
+        BlockNumber = <T as frame_system::Config>::BlockNumber,
     {
         /// The protocol fee of an operation.
         FeeSet(IdentityId, Balance),
@@ -116,6 +149,10 @@ decl_event! {
         CoefficientSet(IdentityId, PosRatio),
         /// Fee charged.
         FeeCharged(AccountId, Balance),
+        /// A fee holiday has been scheduled for an operation (caller DID, op, start, end).
+        FeeHolidayScheduled(IdentityId, ProtocolOp, BlockNumber, BlockNumber),
+        /// The fee holiday of an operation has been cancelled (caller DID, op).
+        FeeHolidayCancelled(IdentityId, ProtocolOp),
     }
 }
 
@@ -146,27 +183,78 @@ decl_module! {
             BaseFees::insert(op, &base_fee);
             Self::deposit_event(Event::<T>::FeeSet(GC_DID, base_fee));
         }
+
+        /// Schedules a fee holiday for the root origin. The operation `op` is free of protocol
+        /// fees from block `start` until block `end` (exclusive), after which its base fee applies
+        /// again. It replaces any previous fee holiday of `op`. The holiday is removed when `op` is
+        /// first charged after it has ended.
+        ///
+        /// # Errors
+        /// * `BadOrigin` - Only root allowed.
+        /// * `InvalidFeeHoliday` - `end` is not after `start` and the current block.
+        #[weight = <T as Config>::WeightInfo::schedule_fee_holiday()]
+        pub fn schedule_fee_holiday(origin, op: ProtocolOp, start: T::BlockNumber, end: T::BlockNumber) {
+            ensure_root(origin)?;
+            ensure!(
+                start < end && <frame_system::Pallet<T>>::block_number() < end,
+                Error::<T>::InvalidFeeHoliday
+            );
+            <FeeHolidays<T>>::insert(op, FeeHoliday { start, end });
+            Self::deposit_event(Event::<T>::FeeHolidayScheduled(GC_DID, op, start, end));
+        }
+
+        /// Cancels the fee holiday of `op` for the root origin.
+        ///
+        /// # Errors
+        /// * `BadOrigin` - Only root allowed.
+        /// * `NoFeeHoliday` - `op` has no fee holiday.
+        #[weight = <T as Config>::WeightInfo::cancel_fee_holiday()]
+        pub fn cancel_fee_holiday(origin, op: ProtocolOp) {
+            ensure_root(origin)?;
+            ensure!(<FeeHolidays<T>>::contains_key(op), Error::<T>::NoFeeHoliday);
+            <FeeHolidays<T>>::remove(op);
+            Self::deposit_event(Event::<T>::FeeHolidayCancelled(GC_DID, op));
+        }
     }
 }
 
 impl<T: Config> Module<T> {
     /// Computes the fee of the operation as `(base_fee * coefficient.0) / coefficient.1`.
+    /// Operations on a fee holiday are free.
     pub fn compute_fee(ops: &[ProtocolOp]) -> Balance {
         let coefficient = Self::coefficient();
         let ratio = Perbill::from_rational(coefficient.0, coefficient.1);
+        let now = <frame_system::Pallet<T>>::block_number();
         let base = ops
             .iter()
+            .filter(|op| !Self::is_fee_holiday(op, &now))
             .fold(Zero::zero(), |a: Balance, e| a + Self::base_fees(e));
         ratio * base
     }
 
+    /// Returns `true` if `op` is on a fee holiday at block `now`.
+    fn is_fee_holiday(op: &ProtocolOp, now: &T::BlockNumber) -> bool {
+        Self::fee_holidays(op).map_or(false, |holiday| holiday.contains(now))
+    }
+
+    /// Removes the fee holidays of `ops` which have ended.
+    fn remove_past_fee_holidays(ops: &[ProtocolOp]) {
+        let now = <frame_system::Pallet<T>>::block_number();
+        for op in ops {
+            if Self::fee_holidays(op).map_or(false, |holiday| holiday.end <= now) {
+                <FeeHolidays<T>>::remove(op);
+            }
+        }
+    }
+
     /// Computes the fee of the operations and charges it to the current payer. The fee is then
     /// credited to the intended recipients according to the implementation of
-    /// `OnProtocolFeePayment`.
+    /// `OnProtocolFeePayment`. Past fee holidays of the operations are removed.
     pub fn charge_fees(ops: &[ProtocolOp]) -> DispatchResult {
         if ops.is_empty() {
             return Ok(());
         }
+        Self::remove_past_fee_holidays(ops);
         let fee = Self::compute_fee(ops);
         if fee.is_zero() {
             return Ok(());
@@ -175,7 +263,9 @@ impl<T: Config> Module<T> {
     }
 
     /// Computes the fee for `count` similar operations, and charges that fee to the current payer.
+    /// A past fee holiday of the operation is removed.
     pub fn batch_charge_fee(op: ProtocolOp, count: usize) -> DispatchResult {
+        Self::remove_past_fee_holidays(&[op]);
         let fee = Self::compute_fee(&[op]).saturating_mul(Balance::from(count as u32));
         if fee.is_zero() {
             return Ok(());
@@ -38,7 +38,9 @@ use frame_support::traits::{Currency, OnUnbalanced};
 use frame_system as system;
 use pallet_authorship as authorship;
 use pallet_balances as balances;
-use polymesh_primitives::Balance;
+use pallet_identity::types::AccountOverview;
+use polymesh_common_utilities::traits::balances::AccountData;
+use polymesh_primitives::{Balance, IdentityId};
 use sp_runtime::traits::Convert;
 
 pub struct Author<R>(sp_std::marker::PhantomData<R>);
@@ -87,3 +89,22 @@ where
         x * Self::factor()
     }
 }
+
+/// Returns the identity, POLYX balances and subsidy of `acc`.
+/// It is served by the `get_account_overview` method of the identity runtime API.
+pub fn account_overview<R>(acc: R::AccountId) -> AccountOverview<IdentityId, R::AccountId>
+where
+    R: pallet_relayer::Config + system::Config<AccountData = AccountData>,
+{
+    let balance = system::Pallet::<R>::account(&acc).data;
+    let subsidy = pallet_relayer::Module::<R>::subsidies(&acc)
+        .map(|subsidy| (subsidy.paying_key, subsidy.remaining));
+    AccountOverview {
+        key_identity_data: pallet_identity::Module::<R>::get_key_identity_data(acc),
+        free_balance: balance.free,
+        reserved_balance: balance.reserved,
+        misc_frozen_balance: balance.misc_frozen,
+        fee_frozen_balance: balance.fee_frozen,
+        subsidy,
+    }
+}
@@ -89,6 +89,12 @@ parameter_types! {
     pub const PolyXBaseFee: Balance = 3 * CENTS;
     /// MultiSig balance limit: 1 POLYX
     pub const MultiSigBalanceLimit: Balance = POLY;
+    /// Challenge period of a primary key recovery: 7 days.
+    pub const KeyRecoveryChallengePeriod: Moment = 7 * 24 * 60 * 60 * 1000;
+    /// Delay before an identity can remove one of its call filters: 7 days.
+    pub const CallFilterRemovalDelay: Moment = 7 * 24 * 60 * 60 * 1000;
+    /// Notice given to the sole agent of an asset before governance replaces it: 30 days.
+    pub const AgentReplacementDelay: Moment = 30 * 24 * 60 * 60 * 1000;
     /// The maximum weight of the pips extrinsic `enact_snapshot_results` which equals to
     /// `MaximumBlockWeight * AvailableBlockRatio`.
     pub const PipsEnactSnapshotMaximumWeight: Weight = MAXIMUM_BLOCK_WEIGHT.saturating_mul(75).saturating_div(100);
@@ -113,7 +119,9 @@ parameter_types! {
     pub RuntimeBlockWeights: BlockWeights = BlockWeights::builder()
         .base_block(BlockExecutionWeight::get())
         .for_class(DispatchClass::all(), |weights| {
-            weights.base_extrinsic = ExtrinsicBaseWeight::get();
+            // Every extrinsic looks up the call filters of its caller's identity.
+            weights.base_extrinsic =
+                ExtrinsicBaseWeight::get().saturating_add(RocksDbWeight::get().reads(1));
         })
     .for_class(DispatchClass::Normal, |weights| {
         weights.max_total = Some(NORMAL_DISPATCH_RATIO * MAXIMUM_BLOCK_WEIGHT);
@@ -34,7 +34,8 @@ macro_rules! misc_pallet_impls {
 
         impl frame_system::Config for Runtime {
             /// The basic call filter to use in dispatchable.
-            type BaseCallFilter = frame_support::traits::Everything;
+            /// Rejects the calls filtered by the identity making them, including nested calls.
+            type BaseCallFilter = pallet_identity::IdentityCallFilter<Runtime>;
             /// Block & extrinsics weights: base values and limits.
             type BlockWeights = polymesh_runtime_common::RuntimeBlockWeights;
             /// The maximum length of a block (in bytes).
@@ -156,6 +157,7 @@ macro_rules! misc_pallet_impls {
             type GovernanceCommittee = PolymeshCommittee;
             type CddProviders = CddServiceProviders;
             type Identity = Identity;
+            type WeightInfo = polymesh_weights::pallet_transaction_payment::SubstrateWeight;
         }
 
         impl polymesh_common_utilities::traits::CommonConfig for Runtime {
@@ -291,11 +293,61 @@ macro_rules! misc_pallet_impls {
             type WeightInfo = polymesh_weights::pallet_portfolio::SubstrateWeight;
             type MaxNumberOfFungibleMoves = MaxNumberOfFungibleMoves;
             type MaxNumberOfNFTsMoves = MaxNumberOfNFTsMoves;
+            type CustodyHandler = Settlement;
+            type PortfolioScheduler = Scheduler;
+            type PortfolioScheduledCalls = Runtime;
+        }
+
+        impl polymesh_common_utilities::traits::portfolio::PortfolioSchedulerCalls<RuntimeCall>
+            for Runtime
+        {
+            fn execute_sweep_rule(
+                id: polymesh_common_utilities::traits::portfolio::SweepRuleId,
+            ) -> RuntimeCall {
+                pallet_portfolio::Call::<Runtime>::execute_sweep_rule { id }.into()
+            }
+        }
+
+        impl polymesh_common_utilities::traits::identity::IdentitySchedulerCalls<Runtime>
+            for Runtime
+        {
+            fn revoke_scheduled_claim(
+                target: polymesh_primitives::IdentityId,
+                claim_type: polymesh_primitives::ClaimType,
+                issuer: polymesh_primitives::IdentityId,
+                scope: Option<polymesh_primitives::Scope>,
+                at: polymesh_primitives::Moment,
+            ) -> RuntimeCall {
+                pallet_identity::Call::<Runtime>::revoke_scheduled_claim {
+                    target,
+                    claim_type,
+                    issuer,
+                    scope,
+                    at,
+                }
+                .into()
+            }
+
+            fn revert_temporary_permissions(
+                key: polymesh_primitives::AccountId,
+                revert_at: polymesh_primitives::BlockNumber,
+            ) -> RuntimeCall {
+                pallet_identity::Call::<Runtime>::revert_temporary_permissions { key, revert_at }
+                    .into()
+            }
+
+            fn end_key_probation(
+                key: polymesh_primitives::AccountId,
+                ends_at: polymesh_primitives::BlockNumber,
+            ) -> RuntimeCall {
+                pallet_identity::Call::<Runtime>::end_key_probation { key, ends_at }.into()
+            }
         }
 
         impl pallet_external_agents::Config for Runtime {
             type RuntimeEvent = RuntimeEvent;
             type WeightInfo = polymesh_weights::pallet_external_agents::SubstrateWeight;
+            type AgentReplacementDelay = polymesh_runtime_common::AgentReplacementDelay;
         }
 
         impl pallet_relayer::Config for Runtime {
@@ -322,6 +374,7 @@ macro_rules! misc_pallet_impls {
             type WeightInfo = polymesh_weights::pallet_asset::SubstrateWeight;
             type CPWeightInfo = polymesh_weights::pallet_checkpoint::SubstrateWeight;
             type NFTFn = pallet_nft::Module<Runtime>;
+            type TickerMigration = (Settlement, CorporateAction, Sto);
         }
 
         impl polymesh_contracts::Config for Runtime {
@@ -362,6 +415,8 @@ macro_rules! misc_pallet_impls {
             type Asset = Asset;
             type WeightInfo = polymesh_weights::pallet_compliance_manager::SubstrateWeight;
             type MaxConditionComplexity = MaxConditionComplexity;
+            type ComplianceScheduler = Scheduler;
+            type ComplianceSchedulerCall = RuntimeCall;
         }
 
         impl pallet_corporate_actions::Config for Runtime {
@@ -378,6 +433,8 @@ macro_rules! misc_pallet_impls {
             type Asset = Asset;
             type MaxStatsPerAsset = MaxStatsPerAsset;
             type MaxTransferConditionsPerAsset = MaxTransferConditionsPerAsset;
+            type MaxExemptionsPerBatch = MaxExemptionsPerBatch;
+            type TransferVolumeEraLength = TransferVolumeEraLength;
             type WeightInfo = polymesh_weights::pallet_statistics::SubstrateWeight;
         }
 
@@ -473,6 +530,10 @@ macro_rules! misc_pallet_impls {
             type MaxNumberOfFungibleAssets = MaxNumberOfFungibleAssets;
             type MaxNumberOfNFTsPerLeg = MaxNumberOfNFTsPerLeg;
             type MaxNumberOfNFTs = MaxNumberOfNFTs;
+            type MaxExecutionWeight = MaxSettlementExecutionWeight;
+            type InstructionStatusRetention = InstructionStatusRetention;
+            type RejectedInstructionRetention = RejectedInstructionRetention;
+            type InstructionRequestLifetime = InstructionRequestLifetime;
         }
 
         impl pallet_sto::Config for Runtime {
@@ -562,7 +623,7 @@ macro_rules! runtime_apis {
         use node_rpc_runtime_api::asset as rpc_api_asset;
         use frame_support::dispatch::GetStorageVersion;
         use sp_inherents::{CheckInherentsResult, InherentData};
-        use pallet_identity::types::{AssetDidResult, CddStatus, RpcDidRecords, DidStatus, KeyIdentityData};
+        use pallet_identity::types::{AccountOverview, AssetDidResult, CddStatus, RpcDidRecords, DidStatus, DidStatusDetails, KeyIdentityData, KeyPermissionsData};
         use pallet_pips::{Vote, VoteCount};
         use pallet_protocol_fee_rpc_runtime_api::CappedFee;
         use polymesh_primitives::{calendar::CheckpointId, compliance_manager::AssetComplianceResult, IdentityId, Index, PortfolioId, Signatory, Ticker, NFTs};
@@ -594,6 +655,12 @@ macro_rules! runtime_apis {
         pub type SignedPayload = generic::SignedPayload<RuntimeCall, SignedExtra>;
         /// Extrinsic type that has already been checked.
         pub type CheckedExtrinsic = generic::CheckedExtrinsic<polymesh_primitives::AccountId, RuntimeCall, SignedExtra>;
+
+        /// Returns the account that signed `uxt`, if any.
+        fn extrinsic_signer(uxt: &UncheckedExtrinsic) -> Option<polymesh_primitives::AccountId> {
+            let (address, _, _) = uxt.signature.as_ref()?;
+            <Indices as StaticLookup>::lookup(address.clone()).ok()
+        }
         /// Executive: handles dispatch to the various modules.
         pub type Executive = pallet_executive::Executive<
             Runtime,
@@ -817,11 +884,13 @@ macro_rules! runtime_apis {
                 Block,
             > for Runtime {
                 fn query_info(uxt: <Block as BlockT>::Extrinsic, len: u32) -> RuntimeDispatchInfo<Balance> {
-                    TransactionPayment::query_info(uxt, len)
+                    let payer = extrinsic_signer(&uxt);
+                    TransactionPayment::query_info(uxt, len, payer)
                 }
 
                 fn query_fee_details(uxt: <Block as BlockT>::Extrinsic, len: u32) -> pallet_transaction_payment::FeeDetails<Balance> {
-                    TransactionPayment::query_fee_details(uxt, len)
+                    let payer = extrinsic_signer(&uxt);
+                    TransactionPayment::query_fee_details(uxt, len, payer)
                 }
             }
 
@@ -912,10 +981,47 @@ macro_rules! runtime_apis {
                     Identity::get_did_status(dids)
                 }
 
+                /// Retrieve the status of the DIDs with the details of their CDD claims
+                fn get_did_status_details(dids: Vec<IdentityId>) -> Vec<DidStatusDetails> {
+                    Identity::get_did_status_details(dids)
+                }
+
+                fn has_consented(
+                    did: IdentityId,
+                    publisher: IdentityId,
+                    id: polymesh_primitives::AgreementId,
+                ) -> bool {
+                    Identity::has_consented(did, publisher, id)
+                }
+
                 fn get_key_identity_data(acc: polymesh_primitives::AccountId) -> Option<KeyIdentityData<IdentityId>> {
                     Identity::get_key_identity_data(acc)
                 }
 
+                fn get_account_overview(
+                    acc: polymesh_primitives::AccountId
+                ) -> AccountOverview<IdentityId, polymesh_primitives::AccountId> {
+                    polymesh_runtime_common::impls::account_overview::<Runtime>(acc)
+                }
+
+                fn get_risk_scores(did: IdentityId) -> Vec<polymesh_primitives::IdentityClaim> {
+                    Identity::latest_risk_scores(did)
+                }
+
+                /// Provide the permissions of a given `AccountId` and the restrictions applying to them.
+                fn get_key_permissions(acc: polymesh_primitives::AccountId) -> Option<KeyPermissionsData<IdentityId>> {
+                    Identity::get_key_permissions(acc)
+                }
+
+                /// Returns `true` if `acc` is currently allowed to call `extrinsic` of `pallet`.
+                fn can_call(
+                    acc: polymesh_primitives::AccountId,
+                    pallet: polymesh_primitives::PalletName,
+                    extrinsic: polymesh_primitives::DispatchableName,
+                ) -> bool {
+                    Identity::can_call(&acc, &pallet, &extrinsic)
+                }
+
                 /// Retrieve list of a authorization for a given signatory
                 fn get_filtered_authorizations(
                     signatory: Signatory<polymesh_primitives::AccountId>,
@@ -953,6 +1059,47 @@ macro_rules! runtime_apis {
                 {
                     Asset::unsafe_can_transfer_granular(from_custodian, from_portfolio, to_custodian, to_portfolio, ticker, value)
                 }
+
+                #[inline]
+                fn authorized_capital(ticker: Ticker) -> polymesh_primitives::asset::AuthorizedCapital {
+                    Asset::authorized_capital(&ticker)
+                }
+
+                #[inline]
+                fn verify_document_hashes(
+                    ticker: Ticker,
+                    documents: Vec<(polymesh_primitives::DocumentId, polymesh_primitives::DocumentHash)>
+                ) -> Vec<polymesh_primitives::DocumentHashCheck> {
+                    Asset::verify_document_hashes(&ticker, documents)
+                }
+
+                #[inline]
+                fn describe_asset(ticker: Ticker) -> Option<polymesh_primitives::asset::AssetDescription> {
+                    Asset::describe_asset(&ticker)
+                }
+
+                #[inline]
+                fn get_holders(
+                    ticker: Ticker,
+                    checkpoint: Option<polymesh_primitives::calendar::CheckpointId>,
+                    start_after: Option<IdentityId>,
+                    limit: u32
+                ) -> Option<polymesh_primitives::asset::AssetHolders> {
+                    let limit = limit.min(rpc_api_asset::MAX_HOLDERS_QUERY_SIZE);
+                    Asset::holder_balances(&ticker, checkpoint, start_after, limit)
+                }
+
+                #[inline]
+                fn document_acknowledgements(
+                    ticker: Ticker,
+                    id: polymesh_primitives::DocumentId,
+                    version: u32,
+                    start_after: Option<IdentityId>,
+                    limit: u32
+                ) -> Vec<(IdentityId, polymesh_primitives::Moment)> {
+                    let limit = limit.min(rpc_api_asset::MAX_ACKNOWLEDGEMENTS_QUERY_SIZE);
+                    Asset::document_acknowledgements(ticker, id, version, start_after, limit)
+                }
             }
 
             impl node_rpc_runtime_api::compliance_manager::ComplianceManagerApi<Block, polymesh_primitives::AccountId>
@@ -968,6 +1115,28 @@ macro_rules! runtime_apis {
                     use polymesh_common_utilities::compliance_manager::Config;
                     ComplianceManager::verify_restriction_granular(&ticker, from_did, to_did)
                 }
+
+                #[inline]
+                fn compliance_report(
+                    ticker: Ticker,
+                    from_did: Option<IdentityId>,
+                    to_did: Option<IdentityId>,
+                    amount: Balance,
+                ) -> polymesh_primitives::compliance_manager::AssetComplianceReport
+                {
+                    ComplianceManager::compliance_report(&ticker, from_did, to_did, amount)
+                }
+
+                #[inline]
+                fn simulate_requirements(
+                    ticker: Ticker,
+                    requirements: Vec<polymesh_primitives::compliance_manager::ComplianceRequirement>,
+                    start_after: Option<IdentityId>,
+                    limit: u32,
+                ) -> polymesh_primitives::compliance_manager::RequirementsImpact
+                {
+                    ComplianceManager::simulate_requirements(&ticker, &requirements, start_after, limit)
+                }
             }
 
             impl pallet_group_rpc_runtime_api::GroupApi<Block> for Runtime {
@@ -995,6 +1164,159 @@ macro_rules! runtime_apis {
                 }
             }
 
+            impl node_rpc_runtime_api::settlement::SettlementApi<Block> for Runtime {
+                #[inline]
+                fn get_unauthorized_venue_tickers(
+                    venue_id: pallet_settlement::VenueId,
+                    tickers: Vec<Ticker>
+                ) -> Result<Vec<Ticker>, frame_support::dispatch::DispatchError> {
+                    Settlement::unauthorized_venue_tickers(&venue_id, &tickers)
+                }
+
+                #[inline]
+                fn get_instruction_leg_history(
+                    instruction_id: pallet_settlement::InstructionId
+                ) -> Vec<(pallet_settlement::LegId, pallet_settlement::LegRevision)> {
+                    Settlement::instruction_leg_history(&instruction_id)
+                }
+
+                #[inline]
+                fn get_pending_instructions(did: IdentityId) -> Vec<pallet_settlement::InstructionId> {
+                    Settlement::pending_instructions(did)
+                }
+
+                #[inline]
+                fn get_instruction_legs(
+                    instruction_id: pallet_settlement::InstructionId
+                ) -> Vec<(pallet_settlement::LegId, pallet_settlement::LegV2)> {
+                    Settlement::get_instruction_legs(&instruction_id)
+                }
+
+                #[inline]
+                fn get_affirmation_status(
+                    instruction_id: pallet_settlement::InstructionId,
+                    portfolio: PortfolioId
+                ) -> pallet_settlement::AffirmationStatus {
+                    Settlement::user_affirmations(portfolio, instruction_id)
+                }
+
+                #[inline]
+                fn can_execute_instruction(
+                    instruction_id: pallet_settlement::InstructionId
+                ) -> Result<Vec<(pallet_settlement::LegId, frame_support::dispatch::DispatchError)>, frame_support::dispatch::DispatchError> {
+                    Settlement::can_execute_instruction(&instruction_id)
+                }
+
+                #[inline]
+                fn get_receipts_used(
+                    signer: polymesh_primitives::AccountId
+                ) -> Vec<(u64, pallet_settlement::ReceiptUsageDetails)> {
+                    Settlement::receipts_used_by(&signer)
+                }
+
+                #[inline]
+                fn get_tagged_instructions(
+                    tag: pallet_settlement::InstructionTag
+                ) -> Vec<pallet_settlement::InstructionId> {
+                    Settlement::instructions_by_tag(&tag)
+                }
+
+                #[inline]
+                fn get_locked_assets(
+                    portfolio: PortfolioId
+                ) -> Vec<(pallet_settlement::InstructionId, pallet_settlement::LegId, pallet_settlement::LockedAsset)> {
+                    Settlement::locked_assets(&portfolio)
+                }
+
+                #[inline]
+                fn get_net_obligations(
+                    did: IdentityId,
+                    start: BlockNumber,
+                    end: BlockNumber
+                ) -> Vec<pallet_settlement::NetObligation> {
+                    Settlement::net_obligations(did, start, end)
+                }
+
+                #[inline]
+                fn get_venue_signers_usage(
+                    venue_id: pallet_settlement::VenueId
+                ) -> Vec<(polymesh_primitives::AccountId, pallet_settlement::VenueSignerUsage<BlockNumber>)> {
+                    Settlement::venue_signers_usage(venue_id)
+                }
+
+                #[inline]
+                fn get_instruction_audit_export(
+                    instruction_id: pallet_settlement::InstructionId
+                ) -> Option<pallet_settlement::InstructionAuditExport> {
+                    Settlement::instruction_audit_export(instruction_id)
+                }
+
+                #[inline]
+                fn verify_instruction_audit(
+                    instruction_id: pallet_settlement::InstructionId,
+                    record: Vec<u8>
+                ) -> bool {
+                    Settlement::verify_instruction_audit(instruction_id, &record)
+                }
+            }
+
+            impl node_rpc_runtime_api::statistics::StatisticsApi<Block> for Runtime {
+                #[inline]
+                fn get_current_transfer_volume_era() -> polymesh_primitives::statistics::TransferVolumeEra {
+                    Statistics::current_transfer_volume_era()
+                }
+
+                #[inline]
+                fn get_transfer_volumes(
+                    ticker: Ticker,
+                    start_era: polymesh_primitives::statistics::TransferVolumeEra,
+                    end_era: polymesh_primitives::statistics::TransferVolumeEra
+                ) -> Vec<(polymesh_primitives::statistics::TransferVolumeEra, polymesh_primitives::statistics::TransferVolume)> {
+                    Statistics::transfer_volumes_in_range(ticker.into(), start_era, end_era)
+                }
+
+                #[inline]
+                fn get_exempt_entities(
+                    exempt_key: polymesh_primitives::transfer_compliance::TransferConditionExemptKey,
+                    start_after: Option<polymesh_primitives::ScopeId>,
+                    limit: u32
+                ) -> Vec<polymesh_primitives::ScopeId> {
+                    Statistics::exempt_entities_page(&exempt_key, start_after, limit)
+                }
+            }
+
+            impl node_rpc_runtime_api::state_health::StateHealthApi<Block> for Runtime {
+                #[inline]
+                fn get_state_counters() -> polymesh_primitives::StateCounters {
+                    polymesh_primitives::StateCounters {
+                        claims: Identity::claim_count(),
+                        authorizations: Identity::authorization_count(),
+                        open_instructions: Settlement::open_instruction_count(),
+                    }
+                }
+            }
+
+            impl node_rpc_runtime_api::bridge::BridgeApi<
+                Block,
+                polymesh_primitives::AccountId,
+                polymesh_primitives::BlockNumber,
+            > for Runtime {
+                #[inline]
+                fn get_withdrawal_queue() -> Vec<(
+                    pallet_bridge::WithdrawalId,
+                    pallet_bridge::Withdrawal<polymesh_primitives::AccountId, polymesh_primitives::BlockNumber>,
+                )> {
+                    Bridge::withdrawal_queue()
+                }
+
+                #[inline]
+                fn get_withdrawal(
+                    id: pallet_bridge::WithdrawalId
+                ) -> Option<pallet_bridge::Withdrawal<polymesh_primitives::AccountId, polymesh_primitives::BlockNumber>> {
+                    Bridge::withdrawals(id)
+                }
+            }
+
             $($extra)*
         }
     }
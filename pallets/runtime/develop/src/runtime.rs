@@ -56,10 +56,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
     authoring_version: 1,
     // `spec_version: aaa_bbb_ccd` should match node version v`aaa.bbb.cc`
     // N.B. `d` is unpinned from the binary version
-    spec_version: 5_004_000,
+    spec_version: 5_005_000,
     impl_version: 0,
     apis: RUNTIME_API_VERSIONS,
-    transaction_version: 3,
+    transaction_version: 5,
     state_version: 1,
 };
 
@@ -94,6 +94,10 @@ parameter_types! {
     pub const MaxNumberOfFungibleAssets: u32 = 10;
     pub const MaxNumberOfNFTsPerLeg: u32 = 10;
     pub const MaxNumberOfNFTs: u32 = 100;
+    pub MaxSettlementExecutionWeight: Weight = Perbill::from_percent(40) * MaximumBlockWeight::get();
+    pub const InstructionStatusRetention: Option<BlockNumber> = Some(30 * DAYS);
+    pub const RejectedInstructionRetention: BlockNumber = 7 * DAYS;
+    pub const InstructionRequestLifetime: BlockNumber = 7 * DAYS;
 
     // I'm online:
     pub const ImOnlineUnsignedPriority: TransactionPriority = TransactionPriority::max_value();
@@ -120,6 +124,8 @@ parameter_types! {
     // Statistics:
     pub const MaxStatsPerAsset: u32 = 10 + BENCHMARK_MAX_INCREASE;
     pub const MaxTransferConditionsPerAsset: u32 = 4 + BENCHMARK_MAX_INCREASE;
+    pub const MaxExemptionsPerBatch: u32 = 1_000;
+    pub const TransferVolumeEraLength: BlockNumber = EPOCH_DURATION_IN_BLOCKS * SessionsPerEra::get();
 
     // Scheduler:
     pub MaximumSchedulerWeight: Weight = Perbill::from_percent(80) * MaximumBlockWeight::get();
@@ -218,6 +224,10 @@ impl polymesh_common_utilities::traits::identity::Config for Runtime {
     type SchedulerOrigin = OriginCaller;
     type InitialPOLYX = InitialPOLYX;
     type MultiSigBalanceLimit = polymesh_runtime_common::MultiSigBalanceLimit;
+    type KeyRecoveryChallengePeriod = polymesh_runtime_common::KeyRecoveryChallengePeriod;
+    type CallFilterRemovalDelay = polymesh_runtime_common::CallFilterRemovalDelay;
+    type IdentityScheduler = Scheduler;
+    type IdentityScheduledCalls = Runtime;
 }
 
 impl pallet_committee::Config<GovernanceCommittee> for Runtime {
@@ -355,6 +365,7 @@ mod benches {
         [pallet_test_utils, TestUtils]
         [polymesh_contracts, PolymeshContracts]
         [pallet_nft, Nft]
+        [pallet_transaction_payment, TransactionPayment]
     );
 }
 
@@ -374,7 +385,7 @@ construct_runtime!(
         Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
 
         // TransactionPayment: Genesis config dependencies: Balance.
-        TransactionPayment: pallet_transaction_payment::{Pallet, Event<T>, Storage},
+        TransactionPayment: pallet_transaction_payment::{Pallet, Call, Event<T>, Storage},
 
         // Identity: Genesis config deps: Timestamp.
         Identity: pallet_identity::{Pallet, Call, Storage, Event<T>, Config<T>},
@@ -436,7 +447,7 @@ construct_runtime!(
         Treasury: pallet_treasury::{Pallet, Call, Event<T>},
         Utility: pallet_utility::{Pallet, Call, Storage, Event},
         Base: pallet_base::{Pallet, Call, Event},
-        ExternalAgents: pallet_external_agents::{Pallet, Call, Storage, Event},
+        ExternalAgents: pallet_external_agents::{Pallet, Call, Storage, Event<T>},
         Relayer: pallet_relayer::{Pallet, Call, Storage, Event<T>},
         Rewards: pallet_rewards::{Pallet, Call, Storage, Event<T>, Config<T>},
 
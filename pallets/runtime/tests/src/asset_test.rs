@@ -3,7 +3,7 @@ use crate::{
     nft::create_nft_collection,
     pips_test::assert_balance,
     storage::{
-        add_secondary_key, make_account_without_cdd, provide_scope_claim,
+        add_secondary_key, get_last_auth_id, make_account_without_cdd, provide_scope_claim,
         provide_scope_claim_to_multiple_parties, register_keyring_account, root, Checkpoint,
         TestStorage, User,
     },
@@ -12,6 +12,7 @@ use chrono::prelude::Utc;
 use frame_support::{
     assert_noop, assert_ok,
     dispatch::{DispatchError, DispatchResult},
+    traits::OnInitialize,
     IterableStorageDoubleMap, IterableStorageMap, StorageDoubleMap, StorageMap, StorageValue,
 };
 use hex_literal::hex;
@@ -21,8 +22,10 @@ use pallet_asset::{
     self as asset, AssetMetadataLocalKeyToName, AssetMetadataLocalNameToKey,
     AssetMetadataLocalSpecs, AssetMetadataValues, AssetOwnershipRelation, ClassicTickerImport,
     ClassicTickerRegistration, ClassicTickers, Config as AssetConfig, CustomTypeIdSequence,
-    CustomTypes, CustomTypesInverse, ScopeIdOf, SecurityToken, TickerRegistration,
-    TickerRegistrationConfig, Tickers,
+    CustomTypes, CustomTypesInverse, ScheduledIssuanceId, ScheduledIssuancesAt, ScopeIdOf,
+    SecurityToken, TickerConfig, TickerRegistration, TickerRegistrationConfig, Tickers,
+    MAX_CONTROLLER_BATCH_TRANSFERS, MAX_ISSUER_BATCH_TRANSFERS, MAX_SCHEDULED_ISSUANCES_PER_BLOCK,
+    MAX_SCHEDULED_ISSUANCE_BLOCKS,
 };
 use pallet_balances as balances;
 use pallet_compliance_manager as compliance_manager;
@@ -34,13 +37,18 @@ use polymesh_common_utilities::{
     protocol_fee::ProtocolOp,
     traits::balances::Memo,
     traits::checkpoint::{ScheduleId, StoredSchedule},
+    traits::compliance_manager::Config as _,
+    traits::portfolio::PortfolioSubTrait as _,
     traits::CddAndFeeDetails as _,
     SystematicIssuers,
 };
 use polymesh_primitives::ethereum;
 use polymesh_primitives::{
     agent::AgentGroup,
-    asset::{AssetName, AssetType, CustomAssetTypeId, FundingRoundName, NonFungibleType},
+    asset::{
+        AssetDescription, AssetLifecycleState, AssetName, AssetType, CustomAssetTypeId,
+        FundingRoundName, NonFungibleType,
+    },
     asset_metadata::{
         AssetMetadataKey, AssetMetadataLocalKey, AssetMetadataLockStatus, AssetMetadataName,
         AssetMetadataSpec, AssetMetadataValue, AssetMetadataValueDetail,
@@ -48,15 +56,18 @@ use polymesh_primitives::{
     calendar::{
         CalendarPeriod, CalendarUnit, CheckpointId, CheckpointSchedule, FixedOrVariableCalendarUnit,
     },
+    compliance_manager::ComplianceSummary,
     statistics::StatType,
-    AccountId, AssetIdentifier, AssetPermissions, AuthorizationData, AuthorizationError, Document,
-    DocumentId, IdentityId, InvestorUid, Moment, NFTCollectionKeys, Permissions, PortfolioId,
-    PortfolioKind, PortfolioName, SecondaryKey, Signatory, Ticker,
+    AccountId, AssetIdentifier, AssetPermissions, AuthorizationData, AuthorizationError, Claim,
+    ClaimType, Condition, ConditionType, Document, DocumentHash, DocumentHashCheck, DocumentId,
+    IdentityId, InvestorUid, Moment, NFTCollectionKeys, PalletPermissions, Permissions,
+    PortfolioId, PortfolioKind, PortfolioName, PortfolioNumber, Scope, SecondaryKey, Signatory,
+    SubsetRestriction, Ticker,
 };
 use rand::Rng;
 use sp_consensus_babe::Slot;
 use sp_io::hashing::keccak_256;
-use sp_runtime::AnySignature;
+use sp_runtime::{AnySignature, Permill};
 use sp_std::{
     convert::{From, TryFrom, TryInto},
     iter,
@@ -377,6 +388,154 @@ fn issuers_can_redeem_tokens() {
         })
 }
 
+#[test]
+fn authorized_supply() {
+    ExtBuilder::default().build().execute_with(|| {
+        set_time_to_now();
+
+        let owner = User::new(AccountKeyring::Dave);
+        let bob = User::new(AccountKeyring::Bob);
+
+        // Create asset.
+        let (ticker, token) = a_token(owner.did);
+        assert_ok!(basic_asset(owner, ticker, &token));
+        let cap = token.total_supply * 2;
+
+        // Only agents may propose, and never below the issued supply.
+        assert_noop!(
+            Asset::propose_authorized_supply(bob.origin(), ticker, Some(cap)),
+            EAError::UnauthorizedAgent
+        );
+        assert_noop!(
+            Asset::propose_authorized_supply(owner.origin(), ticker, Some(token.total_supply - 1)),
+            AssetError::AuthorizedSupplyBelowIssued
+        );
+        assert_noop!(
+            Asset::approve_authorized_supply(owner.origin(), ticker),
+            AssetError::NoPendingAuthorizedSupply
+        );
+
+        // The proposer cannot approve their own proposal.
+        assert_ok!(Asset::propose_authorized_supply(
+            owner.origin(),
+            ticker,
+            Some(cap)
+        ));
+        assert_eq!(Asset::authorized_supply(ticker), None);
+        assert_noop!(
+            Asset::approve_authorized_supply(owner.origin(), ticker),
+            AssetError::AuthorizedSupplyApproverIsProposer
+        );
+
+        // A second agent co-approves.
+        assert_ok!(ExternalAgents::unchecked_add_agent(
+            ticker,
+            bob.did,
+            AgentGroup::Full
+        ));
+        assert_ok!(Asset::approve_authorized_supply(bob.origin(), ticker));
+        assert_eq!(Asset::authorized_supply(ticker), Some(cap));
+        assert_eq!(Asset::pending_authorized_supply(ticker), None);
+
+        // Issuance is capped by the authorized supply.
+        assert_noop!(
+            Asset::issue(owner.origin(), ticker, token.total_supply + 1),
+            AssetError::AuthorizedSupplyExceeded
+        );
+        assert_ok!(Asset::issue(owner.origin(), ticker, token.total_supply));
+        let capital = Asset::authorized_capital(&ticker);
+        assert_eq!(capital.authorized_supply, Some(cap));
+        assert_eq!(capital.issued_supply, cap);
+
+        // Removing the cap also requires co-approval.
+        assert_ok!(Asset::propose_authorized_supply(bob.origin(), ticker, None));
+        assert_ok!(Asset::approve_authorized_supply(owner.origin(), ticker));
+        assert_eq!(Asset::authorized_supply(ticker), None);
+        assert_ok!(Asset::issue(owner.origin(), ticker, 1));
+    })
+}
+
+#[test]
+fn large_issuance_approval() {
+    ExtBuilder::default().build().execute_with(|| {
+        set_time_to_now();
+
+        let owner = User::new(AccountKeyring::Dave);
+        let bob = User::new(AccountKeyring::Bob);
+
+        // Create asset.
+        let (ticker, token) = a_token(owner.did);
+        assert_ok!(basic_asset(owner, ticker, &token));
+        let threshold = token.total_supply / 10;
+
+        // Only governance sets the threshold.
+        assert_noop!(
+            Asset::set_large_issuance_threshold(
+                owner.origin(),
+                Some(Permill::from_percent(10)),
+                1000
+            ),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(Asset::set_large_issuance_threshold(
+            root(),
+            Some(Permill::from_percent(10)),
+            1000
+        ));
+
+        // Issuances above the threshold must be proposed.
+        assert_ok!(Asset::issue(owner.origin(), ticker, threshold));
+        let supply = token.total_supply + threshold;
+        assert_noop!(
+            Asset::issue(owner.origin(), ticker, supply / 10 + 1),
+            AssetError::IssuanceRequiresApproval
+        );
+        assert_noop!(
+            Asset::approve_issuance(owner.origin(), ticker),
+            AssetError::NoPendingIssuance
+        );
+        assert_ok!(Asset::propose_issuance(owner.origin(), ticker, supply));
+        assert_eq!(Asset::token_details(ticker).total_supply, supply);
+
+        // The proposer cannot approve their own proposal, nor anyone before the timelock.
+        assert_noop!(
+            Asset::approve_issuance(owner.origin(), ticker),
+            AssetError::IssuanceApproverIsProposer
+        );
+        assert_ok!(ExternalAgents::unchecked_add_agent(
+            ticker,
+            bob.did,
+            AgentGroup::Full
+        ));
+        assert_noop!(
+            Asset::approve_issuance(bob.origin(), ticker),
+            AssetError::IssuanceTimelockNotExpired
+        );
+
+        // A second agent approves once the timelock is over.
+        set_timestamp(now() + 1000);
+        assert_ok!(Asset::approve_issuance(bob.origin(), ticker));
+        assert_eq!(Asset::token_details(ticker).total_supply, supply * 2);
+        assert_eq!(Asset::pending_issuance(ticker), None);
+
+        // Governance can approve as well, and cancelled proposals can't be approved.
+        assert_ok!(Asset::propose_issuance(bob.origin(), ticker, supply));
+        assert_ok!(Asset::cancel_issuance(owner.origin(), ticker));
+        assert_noop!(
+            Asset::approve_issuance(root(), ticker),
+            AssetError::NoPendingIssuance
+        );
+        assert_ok!(Asset::propose_issuance(owner.origin(), ticker, supply));
+        set_timestamp(now() + 2000);
+        assert_ok!(Asset::approve_issuance(root(), ticker));
+        assert_eq!(Asset::token_details(ticker).total_supply, supply * 3);
+
+        // Disabling the threshold allows any issuance again.
+        assert_ok!(Asset::set_large_issuance_threshold(root(), None, 0));
+        assert_ok!(Asset::issue(owner.origin(), ticker, supply));
+    })
+}
+
 fn default_transfer(from: User, to: User, ticker: Ticker, val: u128) {
     assert_ok!(Asset::unsafe_transfer(
         PortfolioId::default_portfolio(from.did),
@@ -506,6 +665,201 @@ fn register_ticker() {
     })
 }
 
+#[test]
+fn ticker_auto_renewal_and_grace_period() {
+    let fee = 50;
+    let fees = MockProtocolBaseFees(vec![(ProtocolOp::AssetRegisterTicker, fee)]);
+    ExtBuilder::default()
+        .set_protocol_base_fees(fees)
+        .build()
+        .execute_with(|| {
+            set_time_to_now();
+            let owner = User::new(AccountKeyring::Dave).balance(1_000);
+            let alice = User::new(AccountKeyring::Alice);
+            let ticker = ticker("EXPIRY");
+
+            assert_noop!(
+                Asset::set_ticker_expiry_periods(owner.origin(), 1_000, 5_000),
+                DispatchError::BadOrigin
+            );
+            assert_ok!(Asset::set_ticker_expiry_periods(root(), 1_000, 5_000));
+            assert_ok!(Asset::register_ticker(owner.origin(), ticker));
+            let expiry = Asset::ticker_registration(ticker).expiry.unwrap();
+
+            // Only the owner can enable auto-renewal.
+            assert_noop!(
+                Asset::set_ticker_auto_renewal(alice.origin(), ticker, true),
+                AssetError::TickerRegistrationExpired
+            );
+            assert_ok!(Asset::set_ticker_auto_renewal(owner.origin(), ticker, true));
+
+            // Nothing happens before the notice period.
+            Asset::on_initialize(System::block_number());
+            assert_eq!(Asset::ticker_registration(ticker).expiry, Some(expiry));
+
+            // Within the notice period, the registration is renewed at the cost of the owner.
+            TestStorage::set_payer_context(None);
+            set_timestamp(expiry - 500);
+            Asset::on_initialize(System::block_number());
+            let renewed_expiry = Asset::ticker_registration(ticker).expiry.unwrap();
+            assert!(renewed_expiry > expiry);
+            assert_balance(owner.acc(), 1_000 - fee, 0);
+            assert_eq!(TestStorage::get_payer_from_context(), None);
+
+            // During the grace period, only the previous owner can register the ticker again.
+            assert_ok!(Asset::set_ticker_auto_renewal(
+                owner.origin(),
+                ticker,
+                false
+            ));
+            set_timestamp(renewed_expiry + 1);
+            assert_eq!(Asset::is_ticker_registry_valid(&ticker, owner.did), false);
+            assert_eq!(Asset::is_ticker_available(&ticker), false);
+            assert_noop!(
+                Asset::register_ticker(alice.origin(), ticker),
+                AssetError::TickerAlreadyRegistered
+            );
+            assert_ok!(Asset::register_ticker(owner.origin(), ticker));
+
+            // Once the grace period is over, anyone can register it.
+            let expiry = Asset::ticker_registration(ticker).expiry.unwrap();
+            set_timestamp(expiry + 5_001);
+            assert_eq!(Asset::is_ticker_available(&ticker), true);
+            assert_ok!(Asset::register_ticker(alice.origin(), ticker));
+        });
+}
+
+#[test]
+fn expiring_tickers_out_of_registration_order() {
+    ExtBuilder::default().build().execute_with(|| {
+        set_time_to_now();
+        let owner = User::new(AccountKeyring::Dave);
+        let long = ticker("LONG");
+        let short = ticker("SHORT");
+        assert_ok!(Asset::set_ticker_expiry_periods(root(), 1_000, 0));
+
+        // The second registration expires first.
+        assert_ok!(Asset::register_ticker(owner.origin(), long));
+        let long_expiry = Asset::ticker_registration(long).expiry.unwrap();
+        TickerConfig::<TestStorage>::mutate(|config| config.registration_length = Some(2_000));
+        assert_ok!(Asset::register_ticker(owner.origin(), short));
+        let short_expiry = Asset::ticker_registration(short).expiry.unwrap();
+        assert!(short_expiry < long_expiry);
+        assert_ok!(Asset::set_ticker_auto_renewal(owner.origin(), long, true));
+        assert_ok!(Asset::set_ticker_auto_renewal(owner.origin(), short, true));
+
+        // The later registration doesn't hold back the one expiring first.
+        set_timestamp(short_expiry - 500);
+        Asset::on_initialize(System::block_number());
+        assert!(Asset::ticker_registration(short).expiry.unwrap() > short_expiry);
+        assert_eq!(Asset::ticker_registration(long).expiry, Some(long_expiry));
+
+        set_timestamp(long_expiry - 500);
+        Asset::on_initialize(System::block_number());
+        assert!(Asset::ticker_registration(long).expiry.unwrap() > long_expiry);
+    });
+}
+
+#[test]
+fn ticker_renewal_agent() {
+    let fee = 50;
+    let fees = MockProtocolBaseFees(vec![(ProtocolOp::AssetRegisterTicker, fee)]);
+    ExtBuilder::default()
+        .set_protocol_base_fees(fees)
+        .build()
+        .execute_with(|| {
+            set_time_to_now();
+            let owner = User::new(AccountKeyring::Dave).balance(1_000);
+            let agent = User::new(AccountKeyring::Bob).balance(1_000);
+            let alice = User::new(AccountKeyring::Alice);
+            let ticker = ticker("RENEW");
+
+            assert_ok!(Asset::set_ticker_expiry_periods(root(), 1_000, 0));
+            assert_ok!(Asset::register_ticker(owner.origin(), ticker));
+            let expiry = Asset::ticker_registration(ticker).expiry.unwrap();
+
+            // Only an authorization issued by the owner can be accepted.
+            let add_renewal_auth = |from: User| {
+                let sig = Signatory::from(agent.did);
+                assert_ok!(Identity::add_authorization(
+                    from.origin(),
+                    sig.clone(),
+                    AuthorizationData::BecomeTickerRenewalAgent(ticker),
+                    None
+                ));
+                get_last_auth_id(&sig)
+            };
+            let auth_id = add_renewal_auth(alice);
+            assert_noop!(
+                Asset::accept_ticker_renewal_agent(agent.origin(), auth_id),
+                AssetError::TickerRegistrationExpired
+            );
+            let auth_id = add_renewal_auth(owner);
+            assert_ok!(Asset::accept_ticker_renewal_agent(agent.origin(), auth_id));
+            assert_eq!(Asset::ticker_renewal_agent(ticker), Some(agent.did));
+
+            // Either the agent or the owner can remove the renewal agent.
+            assert_noop!(
+                Asset::remove_ticker_renewal_agent(alice.origin(), ticker),
+                AssetError::UnauthorizedTickerRenewal
+            );
+            assert_ok!(Asset::remove_ticker_renewal_agent(agent.origin(), ticker));
+            assert_eq!(Asset::ticker_renewal_agent(ticker), None);
+            assert_noop!(
+                Asset::remove_ticker_renewal_agent(owner.origin(), ticker),
+                AssetError::NoTickerRenewalAgent
+            );
+            let auth_id = add_renewal_auth(owner);
+            assert_ok!(Asset::accept_ticker_renewal_agent(agent.origin(), auth_id));
+            assert_ok!(Asset::remove_ticker_renewal_agent(owner.origin(), ticker));
+            let auth_id = add_renewal_auth(owner);
+            assert_ok!(Asset::accept_ticker_renewal_agent(agent.origin(), auth_id));
+
+            // Only the owner and the renewal agent can renew the registration.
+            assert_noop!(
+                Asset::renew_ticker_registration(alice.origin(), ticker),
+                AssetError::UnauthorizedTickerRenewal
+            );
+            // The renewal agent pays the renewal fee, and has no other permission over the ticker.
+            TestStorage::set_payer_context(Some(agent.acc()));
+            assert_ok!(Asset::renew_ticker_registration(agent.origin(), ticker));
+            let renewed_expiry = Asset::ticker_registration(ticker).expiry.unwrap();
+            assert!(renewed_expiry > expiry);
+            assert_balance(agent.acc(), 1_000 - fee, 0);
+            assert_balance(owner.acc(), 1_000, 0);
+            assert_noop!(
+                Asset::set_ticker_auto_renewal(agent.origin(), ticker, true),
+                AssetError::TickerRegistrationExpired
+            );
+
+            // Automatic renewals are also paid by the renewal agent.
+            assert_ok!(Asset::set_ticker_auto_renewal(owner.origin(), ticker, true));
+            TestStorage::set_payer_context(None);
+            set_timestamp(renewed_expiry - 500);
+            Asset::on_initialize(System::block_number());
+            assert!(Asset::ticker_registration(ticker).expiry.unwrap() > renewed_expiry);
+            assert_balance(agent.acc(), 1_000 - 2 * fee, 0);
+            assert_balance(owner.acc(), 1_000, 0);
+
+            // The renewal agent is removed once the asset is created.
+            assert_ok!(Asset::create_asset(
+                owner.origin(),
+                ticker.as_ref().into(),
+                ticker,
+                true,
+                AssetType::default(),
+                Vec::new(),
+                None,
+                false,
+            ));
+            assert_eq!(Asset::ticker_renewal_agent(ticker), None);
+            assert_noop!(
+                Asset::renew_ticker_registration(owner.origin(), ticker),
+                AssetError::TickerRegistrationHasNoExpiry
+            );
+        });
+}
+
 #[test]
 fn transfer_ticker() {
     ExtBuilder::default().build().execute_with(|| {
@@ -849,6 +1203,200 @@ fn adding_removing_documents() {
     });
 }
 
+#[test]
+fn verify_document_hashes() {
+    ExtBuilder::default().build().execute_with(|| {
+        let owner = User::new(AccountKeyring::Dave);
+        let (ticker, token) = a_token(owner.did);
+        assert_ok!(basic_asset(owner, ticker, &token));
+
+        let hash = |byte: u8| -> DocumentHash { [byte; 64][..].try_into().unwrap() };
+        let document = Document {
+            name: b"A".into(),
+            uri: b"www.a.com".into(),
+            content_hash: hash(1),
+            doc_type: None,
+            filing_date: None,
+        };
+        assert_ok!(Asset::add_documents(owner.origin(), vec![document], ticker));
+
+        assert_eq!(
+            Asset::verify_document_hashes(
+                &ticker,
+                vec![
+                    (DocumentId(0), hash(1)),
+                    (DocumentId(0), hash(2)),
+                    (DocumentId(1), hash(1)),
+                ]
+            ),
+            vec![
+                DocumentHashCheck::Matches,
+                DocumentHashCheck::Differs,
+                DocumentHashCheck::NotFound,
+            ]
+        );
+    });
+}
+
+#[test]
+fn update_and_acknowledge_documents() {
+    ExtBuilder::default().build().execute_with(|| {
+        set_time_to_now();
+        let owner = User::new(AccountKeyring::Dave);
+        let alice = User::new(AccountKeyring::Alice);
+        let bob = User::new(AccountKeyring::Bob);
+        let (ticker, token) = a_token(owner.did);
+        assert_ok!(basic_asset(owner, ticker, &token));
+
+        let document = |uri: &[u8]| Document {
+            name: b"A".into(),
+            uri: uri.into(),
+            content_hash: [1u8; 64][..].try_into().unwrap(),
+            doc_type: None,
+            filing_date: None,
+        };
+        let (v0, v1) = (document(b"www.a.com"), document(b"www.b.com"));
+        let id = DocumentId(0);
+
+        // Only existing documents can be updated or acknowledged.
+        assert_noop!(
+            Asset::update_document(owner.origin(), ticker, id, v1.clone()),
+            AssetError::NoSuchDoc
+        );
+        assert_noop!(
+            Asset::acknowledge_document(alice.origin(), ticker, id),
+            AssetError::NoSuchDoc
+        );
+        assert_ok!(Asset::add_documents(
+            owner.origin(),
+            vec![v0.clone()],
+            ticker
+        ));
+        assert_noop!(
+            Asset::update_document(alice.origin(), ticker, id, v1.clone()),
+            EAError::UnauthorizedAgent
+        );
+
+        // Acknowledge the first version, only once.
+        assert_ok!(Asset::acknowledge_document(alice.origin(), ticker, id));
+        assert_noop!(
+            Asset::acknowledge_document(alice.origin(), ticker, id),
+            AssetError::DocumentAlreadyAcknowledged
+        );
+
+        // Update the document, archiving the first version.
+        assert_ok!(Asset::update_document(
+            owner.origin(),
+            ticker,
+            id,
+            v1.clone()
+        ));
+        assert_eq!(Asset::asset_documents(ticker, id), v1);
+        assert_eq!(Asset::asset_document_version(ticker, id), 1);
+        assert_eq!(Asset::asset_document_history(ticker, (id, 0)), Some(v0));
+
+        // The new version must be acknowledged again.
+        assert_ok!(Asset::acknowledge_document(alice.origin(), ticker, id));
+        assert_ok!(Asset::acknowledge_document(bob.origin(), ticker, id));
+
+        let now = Timestamp::get();
+        assert_eq!(
+            Asset::document_acknowledgements(ticker, id, 0, None, 10),
+            vec![(alice.did, now)]
+        );
+        let mut acks = vec![(alice.did, now), (bob.did, now)];
+        acks.sort();
+        assert_eq!(
+            Asset::document_acknowledgements(ticker, id, 1, None, 10),
+            acks
+        );
+        assert_eq!(
+            Asset::document_acknowledgements(ticker, id, 1, None, 1),
+            acks[..1].to_vec()
+        );
+        assert_eq!(
+            Asset::document_acknowledgements(ticker, id, 1, Some(acks[0].0), 10),
+            acks[1..].to_vec()
+        );
+
+        // The acknowledgements and previous versions of a document are removed once it is removed.
+        let remove_acks =
+            |limit| Asset::remove_document_acknowledgements(owner.origin(), ticker, id, limit);
+        assert_noop!(remove_acks(10), AssetError::DocumentNotRemoved);
+        assert_noop!(
+            Asset::remove_document_acknowledgements(owner.origin(), ticker, DocumentId(9), 10),
+            AssetError::NoSuchDoc
+        );
+        assert_ok!(Asset::remove_documents(owner.origin(), vec![id], ticker));
+        assert_eq!(Asset::asset_document_version(ticker, id), 1);
+        assert_ok!(remove_acks(2));
+        assert_eq!(Asset::asset_document_version(ticker, id), 1);
+        assert_ok!(remove_acks(10));
+        assert_eq!(Asset::asset_document_version(ticker, id), 0);
+        assert_eq!(Asset::asset_document_history(ticker, (id, 0)), None);
+        for version in 0..=1 {
+            assert_eq!(
+                Asset::document_acknowledgements(ticker, id, version, None, 10),
+                vec![]
+            );
+        }
+    });
+}
+
+#[test]
+fn describe_asset() {
+    ExtBuilder::default().build().execute_with(|| {
+        let owner = User::new(AccountKeyring::Dave);
+        let (ticker, token) = a_token(owner.did);
+        assert_eq!(Asset::describe_asset(&ticker), None);
+        assert_ok!(asset_with_ids(owner, ticker, &token, vec![cusip()]));
+
+        let document = Document {
+            name: b"Prospectus".into(),
+            uri: b"www.a.com".into(),
+            content_hash: DocumentHash::None,
+            doc_type: None,
+            filing_date: None,
+        };
+        assert_ok!(Asset::add_documents(
+            owner.origin(),
+            vec![document.clone()],
+            ticker
+        ));
+        assert_ok!(Asset::register_and_set_local_asset_metadata(
+            owner.origin(),
+            ticker,
+            AssetMetadataName(b"coupon".to_vec()),
+            AssetMetadataSpec::default(),
+            AssetMetadataValue(b"5%".to_vec()),
+            None,
+        ));
+        allow_all_transfers(ticker, owner);
+
+        assert_eq!(
+            Asset::describe_asset(&ticker),
+            Some(AssetDescription {
+                ticker,
+                name: ticker.as_ref().to_vec(),
+                asset_type: b"EquityCommon".to_vec(),
+                divisible: true,
+                owner: owner.did,
+                total_supply: TOTAL_SUPPLY,
+                funding_round: Vec::new(),
+                identifiers: vec![(b"CUSIP".to_vec(), b"037833100".to_vec())],
+                documents: vec![(DocumentId(0), document)],
+                metadata: vec![(b"coupon".to_vec(), b"5%".to_vec())],
+                compliance: ComplianceSummary {
+                    paused: false,
+                    requirements: 1,
+                    disabled_requirements: 0,
+                    trusted_issuers: Vec::new(),
+                },
+            })
+        );
+    });
+}
+
 #[test]
 fn freeze_unfreeze_asset() {
     ExtBuilder::default().build().execute_with(|| {
@@ -904,6 +1452,102 @@ fn freeze_unfreeze_asset() {
     });
 }
 
+#[test]
+fn asset_lifecycle_states() {
+    ExtBuilder::default().build().execute_with(|| {
+        set_time_to_now();
+
+        let owner = User::new(AccountKeyring::Alice);
+        let bob = User::new(AccountKeyring::Bob);
+        let (ticker, token) = a_token(owner.did);
+
+        // Fungible assets start in pre-issuance and are activated by their first issuance.
+        assert_ok!(Asset::create_asset(
+            owner.origin(),
+            ticker.as_ref().into(),
+            ticker,
+            token.divisible,
+            token.asset_type.clone(),
+            vec![],
+            None,
+            true,
+        ));
+        assert_eq!(
+            Asset::lifecycle_state(ticker),
+            AssetLifecycleState::PreIssuance
+        );
+        assert_ok!(Asset::issue(owner.origin(), ticker, token.total_supply));
+        assert_eq!(Asset::lifecycle_state(ticker), AssetLifecycleState::Active);
+        allow_all_transfers(ticker, owner);
+
+        assert_noop!(
+            Asset::set_lifecycle_state(bob.origin(), ticker, AssetLifecycleState::Matured),
+            EAError::UnauthorizedAgent
+        );
+        assert_noop!(
+            Asset::set_lifecycle_state(owner.origin(), ticker, AssetLifecycleState::PreIssuance),
+            AssetError::InvalidLifecycleTransition
+        );
+        assert_noop!(
+            Asset::set_lifecycle_state(owner.origin(), ticker, AssetLifecycleState::Retired),
+            AssetError::InvalidLifecycleTransition
+        );
+
+        // Freezing suspends the asset, and unfreezing activates it again.
+        assert_ok!(Asset::freeze(owner.origin(), ticker));
+        assert_eq!(
+            Asset::lifecycle_state(ticker),
+            AssetLifecycleState::Suspended
+        );
+        assert_ok!(Asset::set_lifecycle_state(
+            owner.origin(),
+            ticker,
+            AssetLifecycleState::Active
+        ));
+        assert_eq!(Asset::frozen(ticker), false);
+
+        // Matured assets can't be issued or transferred, but can still be redeemed.
+        assert_ok!(Asset::set_lifecycle_state(
+            owner.origin(),
+            ticker,
+            AssetLifecycleState::Matured
+        ));
+        assert_noop!(
+            Asset::issue(owner.origin(), ticker, 1),
+            AssetError::IssuanceNotAllowed
+        );
+        assert_eq!(
+            Asset::_is_valid_transfer(
+                &ticker,
+                PortfolioId::default_portfolio(owner.did),
+                PortfolioId::default_portfolio(bob.did),
+                1
+            ),
+            Ok(ERC1400_TRANSFERS_HALTED)
+        );
+        assert_noop!(
+            Asset::freeze(owner.origin(), ticker),
+            AssetError::InvalidLifecycleTransition
+        );
+        assert_ok!(Asset::redeem(owner.origin(), ticker, 1));
+
+        // Retired assets can't be redeemed nor leave the retired state.
+        assert_ok!(Asset::set_lifecycle_state(
+            owner.origin(),
+            ticker,
+            AssetLifecycleState::Retired
+        ));
+        assert_noop!(
+            Asset::redeem(owner.origin(), ticker, 1),
+            AssetError::RedemptionNotAllowed
+        );
+        assert_noop!(
+            Asset::set_lifecycle_state(owner.origin(), ticker, AssetLifecycleState::Active),
+            AssetError::InvalidLifecycleTransition
+        );
+    });
+}
+
 #[test]
 fn frozen_secondary_keys_create_asset() {
     ExtBuilder::default()
@@ -2790,3 +3434,440 @@ fn remove_metadata_value() {
         assert_eq!(AssetMetadataValues::get(&ticker, &asset_metada_key), None);
     })
 }
+
+#[test]
+fn issuer_batch_transfer() {
+    let eve = AccountKeyring::Eve.to_account_id();
+    ExtBuilder::default()
+        .cdd_providers(vec![eve.clone()])
+        .build()
+        .execute_with(|| {
+            set_time_to_now();
+
+            let owner = User::new(AccountKeyring::Dave);
+            let alice = User::new(AccountKeyring::Alice);
+            let bob = User::new(AccountKeyring::Bob);
+            let charlie = User::new(AccountKeyring::Charlie);
+            let (ticker, _) = create_token(owner);
+            // Charlie lacks the scope claim.
+            provide_scope_claim_to_multiple_parties(&[owner.did, alice.did, bob.did], ticker, eve);
+            allow_all_transfers(ticker, owner);
+
+            let from_portfolio = PortfolioId::default_portfolio(owner.did);
+            let to = |user: User| PortfolioId::default_portfolio(user.did);
+            let batch_transfer = |user: User, transfers| {
+                Asset::issuer_batch_transfer(user.origin(), ticker, from_portfolio, transfers)
+            };
+            assert_noop!(
+                batch_transfer(alice, vec![(to(bob), 100)]),
+                EAError::UnauthorizedAgent
+            );
+            assert_noop!(
+                batch_transfer(
+                    owner,
+                    vec![(to(alice), 1); MAX_ISSUER_BATCH_TRANSFERS as usize + 1]
+                ),
+                AssetError::TooManyBatchTransfers
+            );
+
+            // The failed transfers are skipped.
+            assert_ok!(batch_transfer(
+                owner,
+                vec![
+                    (to(alice), 100),
+                    (to(charlie), 100),
+                    (to(bob), TOTAL_SUPPLY),
+                    (to(bob), 200),
+                ]
+            ));
+            let balance_of = |user: User| Asset::balance_of(&ticker, user.did);
+            assert_eq!(balance_of(alice), 100);
+            assert_eq!(balance_of(bob), 200);
+            assert_eq!(balance_of(charlie), 0);
+            assert_eq!(balance_of(owner), TOTAL_SUPPLY - 300);
+        })
+}
+
+#[test]
+fn controller_transfer_batch() {
+    ExtBuilder::default().build().execute_with(|| {
+        set_time_to_now();
+
+        let owner = User::new(AccountKeyring::Dave);
+        let alice = User::new(AccountKeyring::Alice);
+        let bob = User::new(AccountKeyring::Bob);
+        let charlie = User::new(AccountKeyring::Charlie);
+        let (ticker, _) = create_token(owner);
+        allow_all_transfers(ticker, owner);
+        assert_ok!(transfer(ticker, owner, alice, 300));
+        assert_ok!(transfer(ticker, owner, bob, 300));
+        let of = |user: User| PortfolioId::default_portfolio(user.did);
+        let vault = new_portfolio(owner.acc(), "vault");
+
+        let batch_transfer = |user: User, transfers| {
+            Asset::controller_transfer_batch(user.origin(), ticker, transfers)
+        };
+        assert_noop!(
+            batch_transfer(alice, vec![(of(bob), of(alice), 100)]),
+            EAError::UnauthorizedAgent
+        );
+        assert_noop!(
+            batch_transfer(
+                owner,
+                vec![(of(alice), of(owner), 1); MAX_CONTROLLER_BATCH_TRANSFERS as usize + 1]
+            ),
+            AssetError::TooManyBatchTransfers
+        );
+        assert_noop!(
+            batch_transfer(
+                owner,
+                vec![(
+                    of(alice),
+                    PortfolioId::user_portfolio(owner.did, PortfolioNumber(2)),
+                    100
+                )]
+            ),
+            PortfolioError::PortfolioDoesNotExist
+        );
+
+        // Tokens can't be forced into a portfolio the agent isn't the custodian of.
+        assert_noop!(
+            batch_transfer(owner, vec![(of(alice), of(charlie), 100)]),
+            PortfolioError::UnauthorizedCustodian
+        );
+        assert_noop!(
+            batch_transfer(
+                owner,
+                vec![(of(alice), of(owner), 100), (of(bob), of(alice), 100)]
+            ),
+            PortfolioError::UnauthorizedCustodian
+        );
+
+        // Nothing is transferred if any transfer fails.
+        assert_noop!(
+            batch_transfer(owner, vec![(of(alice), vault, 100), (of(bob), vault, 400)]),
+            PortfolioError::InsufficientPortfolioBalance
+        );
+
+        // Locked tokens can't be transferred.
+        assert_ok!(Portfolio::lock_tokens(&of(bob), &ticker, 100));
+        assert_noop!(
+            batch_transfer(owner, vec![(of(bob), vault, 300)]),
+            PortfolioError::InsufficientPortfolioBalance
+        );
+        assert_ok!(Portfolio::unlock_tokens(&of(bob), &ticker, 100));
+
+        // Transfers are forced from several holders at once.
+        assert_ok!(batch_transfer(
+            owner,
+            vec![
+                (of(alice), vault, 100),
+                (of(bob), vault, 300),
+                (of(alice), of(owner), 200),
+            ]
+        ));
+        let balance_of = |user: User| Asset::balance_of(&ticker, user.did);
+        assert_eq!(balance_of(alice), 0);
+        assert_eq!(balance_of(bob), 0);
+        assert_eq!(balance_of(charlie), 0);
+        assert_eq!(balance_of(owner), TOTAL_SUPPLY);
+        assert_eq!(Portfolio::portfolio_asset_balances(vault, ticker), 400);
+    });
+}
+
+#[test]
+fn scheduled_issuance() {
+    ExtBuilder::default().build().execute_with(|| {
+        set_time_to_now();
+
+        let owner = User::new(AccountKeyring::Dave);
+        let alice = User::new(AccountKeyring::Alice);
+        let (ticker, _) = create_token(owner);
+        let portfolio_num = NextPortfolioNumber::get(&owner.did);
+        assert_ok!(Portfolio::create_portfolio(
+            owner.origin(),
+            PortfolioName(b"Vesting".to_vec())
+        ));
+        let vesting = PortfolioKind::User(portfolio_num);
+        let now = System::block_number();
+
+        assert_noop!(
+            Asset::schedule_issuance(alice.origin(), ticker, 100, vesting, now + 2),
+            EAError::UnauthorizedAgent
+        );
+        assert_noop!(
+            Asset::schedule_issuance(owner.origin(), ticker, 100, vesting, now),
+            AssetError::InvalidIssuanceBlock
+        );
+
+        // Two tranches are scheduled, and the second one is cancelled.
+        assert_ok!(Asset::schedule_issuance(
+            owner.origin(),
+            ticker,
+            100,
+            vesting,
+            now + 2
+        ));
+        let first = Asset::scheduled_issuance_counter();
+        assert_ok!(Asset::schedule_issuance(
+            owner.origin(),
+            ticker,
+            200,
+            vesting,
+            now + 3
+        ));
+        let second = Asset::scheduled_issuance_counter();
+        assert_noop!(
+            Asset::cancel_scheduled_issuance(alice.origin(), ticker, second),
+            EAError::UnauthorizedAgent
+        );
+        assert_ok!(Asset::cancel_scheduled_issuance(
+            owner.origin(),
+            ticker,
+            second
+        ));
+        assert_noop!(
+            Asset::cancel_scheduled_issuance(owner.origin(), ticker, second),
+            AssetError::NoSuchScheduledIssuance
+        );
+        assert!(Asset::scheduled_issuances_at(now + 3).is_empty());
+
+        // The first tranche is issued into the vesting portfolio at its block.
+        let portfolio = PortfolioId::user_portfolio(owner.did, portfolio_num);
+        Asset::on_initialize(now + 1);
+        assert_eq!(PortfolioAssetBalances::get(&portfolio, &ticker), 0);
+        Asset::on_initialize(now + 2);
+        assert_eq!(PortfolioAssetBalances::get(&portfolio, &ticker), 100);
+        assert_eq!(
+            Asset::token_details(ticker).total_supply,
+            TOTAL_SUPPLY + 100
+        );
+        assert_eq!(Asset::balance_of(&ticker, owner.did), TOTAL_SUPPLY + 100);
+        assert_eq!(Asset::scheduled_issuance(first), None);
+        Asset::on_initialize(now + 3);
+        assert_eq!(
+            Asset::token_details(ticker).total_supply,
+            TOTAL_SUPPLY + 100
+        );
+
+        // Issuances are scheduled in the first block with room for them, within a bounded search.
+        let at = now + 10;
+        let full: Vec<_> = (0..MAX_SCHEDULED_ISSUANCES_PER_BLOCK as u64)
+            .map(ScheduledIssuanceId)
+            .collect();
+        for i in 0..MAX_SCHEDULED_ISSUANCE_BLOCKS - 1 {
+            ScheduledIssuancesAt::<TestStorage>::insert(at + i, full.clone());
+        }
+        let last = at + MAX_SCHEDULED_ISSUANCE_BLOCKS - 1;
+        assert_ok!(Asset::schedule_issuance(
+            owner.origin(),
+            ticker,
+            100,
+            vesting,
+            at
+        ));
+        assert_eq!(Asset::scheduled_issuances_at(last).len(), 1);
+        ScheduledIssuancesAt::<TestStorage>::insert(last, full);
+        assert_noop!(
+            Asset::schedule_issuance(owner.origin(), ticker, 100, vesting, at),
+            AssetError::ScheduledIssuanceBlocksFull
+        );
+
+        // The issuance is dropped if its agent can no longer issue tokens.
+        assert_ok!(ExternalAgents::unchecked_add_agent(
+            ticker,
+            alice.did,
+            AgentGroup::Full
+        ));
+        assert_ok!(Asset::schedule_issuance(
+            alice.origin(),
+            ticker,
+            100,
+            PortfolioKind::Default,
+            now + 4
+        ));
+        let sto_only = SubsetRestriction::elem(PalletPermissions::entire_pallet("Sto".into()));
+        assert_ok!(ExternalAgents::create_and_change_custom_group(
+            owner.origin(),
+            ticker,
+            sto_only,
+            alice.did
+        ));
+        Asset::on_initialize(now + 4);
+        assert_eq!(Asset::balance_of(&ticker, alice.did), 0);
+        assert_eq!(
+            Asset::token_details(ticker).total_supply,
+            TOTAL_SUPPLY + 100
+        );
+    });
+}
+
+#[test]
+fn holder_balances() {
+    ExtBuilder::default().build().execute_with(|| {
+        set_time_to_now();
+
+        let owner = User::new(AccountKeyring::Dave);
+        let alice = User::new(AccountKeyring::Alice);
+        let bob = User::new(AccountKeyring::Bob);
+        let (ticker, _) = create_token(owner);
+        let holders = |checkpoint, start_after, limit| {
+            Asset::holder_balances(&ticker, checkpoint, start_after, limit).unwrap()
+        };
+        let sorted = |mut balances: Vec<(IdentityId, u128)>| {
+            balances.sort();
+            balances
+        };
+
+        default_transfer(owner, bob, ticker, 100);
+        assert_ok!(Checkpoint::create_checkpoint(owner.origin(), ticker));
+        default_transfer(owner, alice, ticker, 50);
+        default_transfer(bob, owner, ticker, 100);
+
+        // Holders whose balance went back to zero are skipped.
+        let current = sorted(vec![(owner.did, TOTAL_SUPPLY - 50), (alice.did, 50)]);
+        assert_eq!(holders(None, None, 10).holders, current);
+        let at_checkpoint = sorted(vec![(owner.did, TOTAL_SUPPLY - 100), (bob.did, 100)]);
+        assert_eq!(
+            holders(Some(CheckpointId(1)), None, 10).holders,
+            at_checkpoint
+        );
+        assert_eq!(
+            Asset::holder_balances(&ticker, Some(CheckpointId(2)), None, 10),
+            None
+        );
+
+        // Holders are paginated.
+        let first_page = holders(None, None, 1);
+        assert_eq!(first_page.holders, current[..1].to_vec());
+        assert_eq!(first_page.next_holder, Some(current[0].0));
+        let second_page = holders(None, first_page.next_holder, 1);
+        assert_eq!(second_page.holders, current[1..].to_vec());
+        assert_eq!(second_page.next_holder, None);
+    });
+}
+
+#[test]
+fn migrate_ticker() {
+    ExtBuilder::default().build().execute_with(|| {
+        set_time_to_now();
+
+        let owner = User::new(AccountKeyring::Dave);
+        let alice = User::new(AccountKeyring::Alice);
+        let (ticker, _) = create_token(owner);
+        let document = Document {
+            name: b"Prospectus".into(),
+            uri: b"www.a.com".into(),
+            content_hash: DocumentHash::None,
+            doc_type: None,
+            filing_date: None,
+        };
+        assert_ok!(Asset::add_documents(
+            owner.origin(),
+            vec![document.clone()],
+            ticker
+        ));
+        default_transfer(owner, alice, ticker, 100);
+        assert_ok!(Checkpoint::create_checkpoint(owner.origin(), ticker));
+
+        // Alice's accreditation for the asset is checked by its compliance rules.
+        let issuer = User::new(AccountKeyring::Bob);
+        let accredited = |ticker| Claim::Accredited(Scope::Ticker(ticker));
+        let accredited_condition = |ticker| {
+            Condition::from_dids(ConditionType::IsPresent(accredited(ticker)), &[issuer.did])
+        };
+        assert_ok!(Identity::add_claim(
+            issuer.origin(),
+            alice.did,
+            accredited(ticker),
+            None
+        ));
+        assert_ok!(ComplianceManager::add_compliance_requirement(
+            owner.origin(),
+            ticker,
+            vec![accredited_condition(ticker)],
+            vec![]
+        ));
+        let fetch_accredited = |ticker| {
+            Identity::fetch_claim(
+                alice.did,
+                ClaimType::Accredited,
+                issuer.did,
+                Some(Scope::Ticker(ticker)),
+            )
+        };
+
+        let taken = Ticker::from_slice_truncated(b"TAKEN");
+        assert_ok!(Asset::register_ticker(alice.origin(), taken));
+        assert_noop!(
+            Asset::migrate_ticker(owner.origin(), ticker, taken, 10, 100),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            Asset::migrate_ticker(root(), ticker, taken, 10, 100),
+            AssetError::TickerAlreadyRegistered
+        );
+        let new = Ticker::from_slice_truncated(b"REBRAND");
+        assert_noop!(
+            Asset::migrate_ticker(root(), ticker, new, 1, 100),
+            AssetError::TooManyTickerHolders
+        );
+        assert_noop!(
+            Asset::migrate_ticker(root(), ticker, new, 10, 1),
+            BaseError::TooLong
+        );
+
+        assert_ok!(Asset::migrate_ticker(root(), ticker, new, 10, 100));
+        assert_eq!(Asset::ticker_migration(ticker), Some(new));
+        assert_eq!(Asset::token_details(new).total_supply, TOTAL_SUPPLY);
+        assert!(Asset::is_owner(&new, owner.did));
+        assert!(!Asset::is_owner(&ticker, owner.did));
+        assert_eq!(Asset::balance_of(&new, alice.did), 100);
+        assert_eq!(Asset::balance_of(&ticker, alice.did), 0);
+        assert_eq!(
+            PortfolioAssetBalances::get(&PortfolioId::default_portfolio(alice.did), &new),
+            100
+        );
+        assert_eq!(
+            asset::AssetDocuments::iter_prefix_values(new).collect::<Vec<_>>(),
+            vec![document]
+        );
+        assert_eq!(Checkpoint::checkpoint_id_sequence(new), CheckpointId(1));
+        assert_eq!(
+            ExternalAgents::agents(new, owner.did),
+            Some(AgentGroup::Full)
+        );
+
+        // The claims and the conditions scoped to the asset are rescoped to `new`.
+        assert_eq!(fetch_accredited(ticker), None);
+        assert_eq!(
+            fetch_accredited(new).map(|id_claim| id_claim.claim),
+            Some(accredited(new))
+        );
+        assert_eq!(
+            ComplianceManager::asset_compliance(new).requirements[0].sender_conditions,
+            vec![accredited_condition(new)]
+        );
+        assert!(ComplianceManager::verify_restriction_granular(&new, Some(alice.did), None).result);
+
+        // The asset keeps working under its new ticker, and the old one can't be registered again.
+        default_transfer(alice, owner, new, 50);
+        assert_eq!(Asset::balance_of(&new, alice.did), 50);
+        assert_noop!(
+            Asset::register_ticker(alice.origin(), ticker),
+            AssetError::MigratedTicker
+        );
+        assert_noop!(
+            Asset::migrate_ticker(root(), new, ticker, 10, 100),
+            AssetError::MigratedTicker
+        );
+
+        // Migrating the asset again redirects its previous tickers to the latest one.
+        let latest = Ticker::from_slice_truncated(b"REBRAND2");
+        assert_ok!(Asset::migrate_ticker(root(), new, latest, 10, 100));
+        assert_eq!(Asset::ticker_migration(ticker), Some(latest));
+        assert_eq!(Asset::ticker_migration(new), Some(latest));
+        assert_eq!(Asset::ticker_migration_sources(latest), vec![ticker, new]);
+        assert_eq!(Asset::balance_of(&latest, alice.did), 50);
+    });
+}
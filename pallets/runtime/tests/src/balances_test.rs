@@ -328,3 +328,66 @@ fn transfer_with_memo_we() {
         assert!(system_events.contains(&expected));
     });
 }
+
+#[test]
+fn batch_transfer_with_memos() {
+    ExtBuilder::default()
+        .balance_factor(1_000)
+        .monied(true)
+        .cdd_providers(vec![AccountKeyring::Ferdie.to_account_id()])
+        .build()
+        .execute_with(batch_transfer_with_memos_we);
+}
+
+fn batch_transfer_with_memos_we() {
+    let alice = AccountKeyring::Alice.to_account_id();
+    let alice_id = register_keyring_account(AccountKeyring::Alice).unwrap();
+    let bob = AccountKeyring::Bob.to_account_id();
+    let bob_id = register_keyring_account(AccountKeyring::Bob).unwrap();
+    let dave = AccountKeyring::Dave.to_account_id();
+    register_keyring_account(AccountKeyring::Dave).unwrap();
+    let charlie = AccountKeyring::Charlie.to_account_id();
+    Balances::make_free_balance_be(&bob, 0);
+    Balances::make_free_balance_be(&dave, 0);
+
+    // A single recipient without CDD fails the whole batch.
+    assert_noop!(
+        Balances::batch_transfer_with_memos(
+            Origin::signed(alice.clone()),
+            vec![(bob.clone().into(), 100, None), (charlie.into(), 100, None)]
+        ),
+        Error::ReceiverCddMissing
+    );
+
+    let memo = Some(Memo([7u8; 32]));
+    assert_ok!(Balances::batch_transfer_with_memos(
+        Origin::signed(alice.clone()),
+        vec![
+            (bob.clone().into(), 100, memo.clone()),
+            (dave.clone().into(), 200, None)
+        ]
+    ));
+    assert_eq!(Balances::free_balance(&bob), 100);
+    assert_eq!(Balances::free_balance(&dave), 200);
+
+    let system_events = System::events();
+    let expected_events = vec![
+        EventTest::Balances(BalancesRawEvent::Transfer(
+            Some(alice_id),
+            alice.clone(),
+            Some(bob_id),
+            bob,
+            100,
+            memo,
+        )),
+        EventTest::Balances(BalancesRawEvent::BatchTransfer(
+            Some(alice_id),
+            alice,
+            2,
+            300,
+        )),
+    ];
+    expected_events.into_iter().for_each(|expected| {
+        assert!(system_events.iter().any(|record| record.event == expected));
+    });
+}
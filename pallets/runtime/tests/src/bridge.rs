@@ -9,8 +9,10 @@ use frame_support::{
 };
 use pallet_bridge::{
     self as bridge, BridgeTx as GBridgeTx, BridgeTxDetail as GBridgeTxDetail, BridgeTxStatus,
+    WithdrawalId, WithdrawalStatus, DEFAULT_WITHDRAWAL_CHALLENGE_PERIOD,
 };
 use polymesh_primitives::AccountId;
+use sp_core::H160;
 use test_client::AccountKeyring::*;
 
 type Bridge = bridge::Module<TestStorage>;
@@ -664,3 +666,98 @@ fn do_remove_txs(signers: &[AccountId]) {
         .all(|tx| tx.status == BridgeTxStatus::Absent));
     assert!(Bridge::get_tx_details(&txs[0]).status == BridgeTxStatus::Timelocked);
 }
+
+#[test]
+fn withdrawal_queue() {
+    test_with_controller(&do_withdrawal_queue)
+}
+
+fn do_withdrawal_queue(_signers: &[AccountId]) {
+    let alice = User::existing(Alice);
+    let eve = User::existing(Eve);
+    let ferdie = User::existing(Ferdie);
+    let admin = signed_admin();
+    let recipient = H160::repeat_byte(0xEE);
+    let threshold = AMOUNT;
+    let challenge_period = 10;
+    let withdrawal_status = |id| Bridge::withdrawals(WithdrawalId(id)).map(|w| w.status);
+
+    // The challenge period can't be disabled.
+    assert_eq!(
+        Bridge::withdrawal_challenge_period(),
+        DEFAULT_WITHDRAWAL_CHALLENGE_PERIOD
+    );
+    assert_noop!(
+        Bridge::change_withdrawal_queue_params(admin.clone(), threshold, 0),
+        Error::ZeroChallengePeriod
+    );
+    assert_ok!(Bridge::change_withdrawal_queue_params(
+        admin.clone(),
+        threshold,
+        challenge_period
+    ));
+    assert_ok!(Bridge::add_freeze_admin(admin.clone(), ferdie.acc()));
+    let starting_balance = alice_balance();
+
+    // Withdrawals up to the threshold are released immediately.
+    assert_noop!(
+        Bridge::withdraw(alice.origin(), recipient, 0),
+        Error::ZeroWithdrawal
+    );
+    assert_ok!(Bridge::withdraw(alice.origin(), recipient, threshold));
+    assert_eq!(withdrawal_status(0), None);
+    assert_eq!(alice_balance(), starting_balance - threshold);
+
+    // Larger withdrawals are queued for the challenge period.
+    assert_ok!(Bridge::withdraw(alice.origin(), recipient, threshold + 1));
+    assert_ok!(Bridge::withdraw(alice.origin(), recipient, threshold + 2));
+    assert_eq!(withdrawal_status(1), Some(WithdrawalStatus::Queued));
+    assert_eq!(Bridge::withdrawal_queue().len(), 2);
+
+    // Only freeze admins can halt queued withdrawals.
+    assert_noop!(
+        Bridge::halt_withdrawals(eve.origin(), vec![WithdrawalId(1)]),
+        Error::BadAdmin
+    );
+    assert_noop!(
+        Bridge::halt_withdrawals(ferdie.origin(), vec![WithdrawalId(0)]),
+        Error::NoSuchWithdrawal
+    );
+    assert_ok!(Bridge::halt_withdrawals(
+        ferdie.origin(),
+        vec![WithdrawalId(1), WithdrawalId(2)]
+    ));
+
+    // Halted withdrawals are not released at the end of the challenge period.
+    fast_forward_blocks(challenge_period + 1);
+    assert_eq!(withdrawal_status(1), Some(WithdrawalStatus::Halted));
+
+    // Resuming restarts the challenge period.
+    assert_noop!(
+        Bridge::resume_withdrawals(ferdie.origin(), vec![WithdrawalId(1)]),
+        Error::BadAdmin
+    );
+    assert_ok!(Bridge::resume_withdrawals(
+        admin.clone(),
+        vec![WithdrawalId(1)]
+    ));
+    assert_noop!(
+        Bridge::halt_withdrawals(ferdie.origin(), vec![WithdrawalId(2), WithdrawalId(2)]),
+        Error::WithdrawalNotQueued
+    );
+    next_block();
+    assert_eq!(withdrawal_status(1), Some(WithdrawalStatus::Queued));
+    fast_forward_blocks(challenge_period);
+    assert_eq!(withdrawal_status(1), None);
+
+    // Cancelling refunds the sender.
+    let balance_before_cancel = alice_balance();
+    assert_noop!(
+        Bridge::cancel_withdrawals(admin.clone(), vec![WithdrawalId(1)]),
+        Error::NoSuchWithdrawal
+    );
+    assert_ok!(Bridge::cancel_withdrawals(admin, vec![WithdrawalId(2)]));
+    assert_eq!(withdrawal_status(2), None);
+    assert_eq!(alice_balance(), balance_before_cancel + threshold + 2);
+    assert!(Bridge::withdrawal_queue().is_empty());
+}
@@ -1,28 +1,40 @@
 use super::{
-    asset_test::{allow_all_transfers, create_token, set_timestamp},
+    asset_test::{allow_all_transfers, basic_asset, create_token, set_timestamp, token},
     storage::{
-        create_cdd_id, create_investor_uid, get_primary_key,
-        provide_scope_claim_to_multiple_parties, set_curr_did, TestStorage, User,
+        create_cdd_id, create_investor_uid, fast_forward_blocks, get_primary_key, next_block,
+        provide_scope_claim_to_multiple_parties, root, set_curr_did, TestStorage, User,
     },
     ExtBuilder,
 };
 use chrono::prelude::Utc;
-use frame_support::{assert_noop, assert_ok, traits::Currency};
+use frame_support::{
+    assert_noop, assert_ok,
+    dispatch::DispatchError,
+    traits::{Currency, OnInitialize},
+};
 use pallet_balances as balances;
-use pallet_compliance_manager::{self as compliance_manager, Error as CMError};
+use pallet_compliance_manager::{
+    self as compliance_manager, Error as CMError, ScheduledReenables,
+    MAX_SCHEDULED_REENABLES_PER_BLOCK, MAX_SCHEDULED_REENABLE_BLOCKS,
+    MAX_SCHEDULED_REQUIREMENT_CHANGES,
+};
 use pallet_group as group;
 use pallet_identity as identity;
 use polymesh_common_utilities::{
     compliance_manager::Config as _,
     constants::{ERC1400_TRANSFER_FAILURE, ERC1400_TRANSFER_SUCCESS},
+    GC_DID,
 };
 use polymesh_primitives::{
     agent::AgentGroup,
+    asset::AssetType,
     compliance_manager::{
-        AssetComplianceResult, ComplianceRequirement, ComplianceRequirementResult,
+        AssetComplianceResult, ComplianceRequirement, ComplianceRequirementChange,
+        ComplianceRequirementResult, ComplianceTemplateId,
     },
+    statistics::AssetScope,
     AuthorizationData, Claim, ClaimType, Condition, ConditionType, CountryCode, IdentityId,
-    PortfolioId, Scope, Signatory, TargetIdentity, Ticker, TrustedFor,
+    PortfolioId, RiskScore, Scope, Signatory, TargetIdentity, Ticker, TrustedFor, TrustedIssuer,
 };
 use sp_std::prelude::*;
 use test_client::AccountKeyring;
@@ -33,8 +45,10 @@ type Balances = balances::Module<TestStorage>;
 type Timestamp = pallet_timestamp::Pallet<TestStorage>;
 type Asset = pallet_asset::Module<TestStorage>;
 type ComplianceManager = compliance_manager::Module<TestStorage>;
+type Statistics = pallet_statistics::Module<TestStorage>;
 type CDDGroup = group::Module<TestStorage, group::Instance2>;
 type Moment = u64;
+type System = frame_system::Pallet<TestStorage>;
 type Origin = <TestStorage as frame_system::Config>::RuntimeOrigin;
 type ExternalAgents = pallet_external_agents::Module<TestStorage>;
 type EAError = pallet_external_agents::Error<TestStorage>;
@@ -481,6 +495,130 @@ fn pause_resume_asset_compliance_we() {
     assert_invalid_transfer!(ticker, owner.did, receiver.did, 10);
 }
 
+#[test]
+fn disable_enable_compliance_requirements() {
+    ExtBuilder::default()
+        .build()
+        .execute_with(disable_enable_compliance_requirements_we);
+}
+
+fn disable_enable_compliance_requirements_we() {
+    let owner = User::new(AccountKeyring::Alice);
+    let receiver = User::new(AccountKeyring::Charlie);
+    let (ticker, _) = create_token(owner);
+    provide_scope_claim_to_multiple_parties(
+        &[owner.did, receiver.did],
+        ticker,
+        AccountKeyring::Eve.to_account_id(),
+    );
+
+    // A single requirement that allows every transfer.
+    allow_all_transfers(ticker, owner);
+    let id = get_latest_requirement_id(ticker);
+    assert_valid_transfer!(ticker, owner.did, receiver.did, 10);
+
+    // Unknown ids and past blocks are rejected.
+    assert_noop!(
+        ComplianceManager::disable_compliance_requirements(
+            owner.origin(),
+            ticker,
+            vec![id + 1],
+            None
+        ),
+        CMError::<TestStorage>::InvalidComplianceRequirementId
+    );
+    System::set_block_number(5);
+    assert_noop!(
+        ComplianceManager::disable_compliance_requirements(
+            owner.origin(),
+            ticker,
+            vec![id],
+            Some(5)
+        ),
+        CMError::<TestStorage>::InvalidReenableBlock
+    );
+    assert_noop!(
+        ComplianceManager::enable_compliance_requirements(owner.origin(), ticker, vec![id]),
+        CMError::<TestStorage>::ComplianceRequirementNotDisabled
+    );
+
+    // A disabled requirement can't make the transfer valid until it is enabled again.
+    assert_ok!(ComplianceManager::disable_compliance_requirements(
+        owner.origin(),
+        ticker,
+        vec![id],
+        None
+    ));
+    assert_invalid_transfer!(ticker, owner.did, receiver.did, 10);
+    assert!(
+        !ComplianceManager::verify_restriction_granular(
+            &ticker,
+            Some(owner.did),
+            Some(receiver.did)
+        )
+        .requirements[0]
+            .result
+    );
+    assert_ok!(ComplianceManager::enable_compliance_requirements(
+        owner.origin(),
+        ticker,
+        vec![id]
+    ));
+    assert_valid_transfer!(ticker, owner.did, receiver.did, 10);
+
+    // The requirement is enabled again in its re-enable block.
+    assert_ok!(ComplianceManager::disable_compliance_requirements(
+        owner.origin(),
+        ticker,
+        vec![id],
+        Some(7)
+    ));
+    ComplianceManager::on_initialize(6);
+    assert_invalid_transfer!(ticker, owner.did, receiver.did, 10);
+    ComplianceManager::on_initialize(7);
+    assert!(ComplianceManager::disabled_requirements(ticker).is_empty());
+    assert_valid_transfer!(ticker, owner.did, receiver.did, 10);
+
+    // Re-enables that don't fit in the requested block spill into the next blocks with room.
+    let full = vec![(ticker, id + 1); MAX_SCHEDULED_REENABLES_PER_BLOCK as usize];
+    ScheduledReenables::<TestStorage>::insert(9, full.clone());
+    let disable = |at| {
+        ComplianceManager::disable_compliance_requirements(
+            owner.origin(),
+            ticker,
+            vec![id],
+            Some(at),
+        )
+    };
+    assert_ok!(disable(9));
+    assert_eq!(
+        ComplianceManager::disabled_requirements(ticker).get(&id),
+        Some(&Some(10))
+    );
+    ComplianceManager::on_initialize(9);
+    assert_invalid_transfer!(ticker, owner.did, receiver.did, 10);
+    ComplianceManager::on_initialize(10);
+    assert_valid_transfer!(ticker, owner.did, receiver.did, 10);
+    for at in 11..11 + MAX_SCHEDULED_REENABLE_BLOCKS {
+        ScheduledReenables::<TestStorage>::insert(at, full.clone());
+    }
+    assert_noop!(disable(11), CMError::<TestStorage>::ReenableBlocksFull);
+
+    // Removing a disabled requirement clears its disabled state.
+    assert_ok!(ComplianceManager::disable_compliance_requirements(
+        owner.origin(),
+        ticker,
+        vec![id],
+        None
+    ));
+    assert_ok!(ComplianceManager::remove_compliance_requirement(
+        owner.origin(),
+        ticker,
+        id
+    ));
+    assert!(ComplianceManager::disabled_requirements(ticker).is_empty());
+}
+
 #[test]
 fn should_successfully_add_and_use_default_issuers() {
     ExtBuilder::default()
@@ -1424,6 +1562,178 @@ fn should_limit_compliance_requirements_complexity_we() {
     assert_eq!(asset_compliance.requirements.len(), 1);
 }
 
+#[test]
+fn requirements_templates() {
+    ExtBuilder::default()
+        .build()
+        .execute_with(requirements_templates_we);
+}
+
+fn requirements_templates_we() {
+    let owner = User::new(AccountKeyring::Alice);
+    let other = User::new(AccountKeyring::Bob);
+
+    let requirement = |id, did| ComplianceRequirement {
+        sender_conditions: vec![],
+        receiver_conditions: vec![Condition::from_dids(
+            ConditionType::IsPresent(Claim::Jurisdiction(CountryCode::CA, Scope::Identity(did))),
+            &[did],
+        )],
+        id,
+    };
+    let equity = AssetType::EquityCommon;
+
+    // Only root can set the default template.
+    assert_noop!(
+        ComplianceManager::set_default_requirements_template(
+            owner.origin(),
+            equity,
+            vec![requirement(7, owner.did)]
+        ),
+        DispatchError::BadOrigin
+    );
+    assert_ok!(ComplianceManager::set_default_requirements_template(
+        root(),
+        equity,
+        vec![requirement(7, owner.did)]
+    ));
+    // Requirement ids are reassigned.
+    let default_template = vec![requirement(1, owner.did)];
+    assert_eq!(
+        ComplianceManager::default_requirements_template(equity),
+        default_template
+    );
+
+    // New equities get the default template.
+    let (ticker, _) = create_token(owner);
+    assert_eq!(
+        ComplianceManager::asset_compliance(ticker).requirements,
+        default_template
+    );
+
+    // Other asset types are unaffected.
+    let (fund, mut fund_token) = token(b"FUND", owner.did);
+    fund_token.asset_type = AssetType::Fund;
+    assert_ok!(basic_asset(owner, fund, &fund_token));
+    assert!(ComplianceManager::asset_compliance(fund)
+        .requirements
+        .is_empty());
+
+    // The issuer's template takes precedence over the default one.
+    let issuer_template = vec![requirement(1, other.did), requirement(2, other.did)];
+    assert_ok!(ComplianceManager::set_issuer_requirements_template(
+        other.origin(),
+        equity,
+        issuer_template.clone()
+    ));
+    let (other_ticker, other_token) = token(b"OTHER", other.did);
+    assert_ok!(basic_asset(other, other_ticker, &other_token));
+    assert_eq!(
+        ComplianceManager::asset_compliance(other_ticker).requirements,
+        issuer_template
+    );
+
+    // Templates are bounded by the maximum complexity.
+    let ty = ConditionType::IsPresent(Claim::KnowYourCustomer(Scope::Identity(owner.did)));
+    let conditions = vec![Condition::from_dids(ty, &[owner.did]); 30];
+    assert_noop!(
+        ComplianceManager::set_issuer_requirements_template(
+            owner.origin(),
+            equity,
+            vec![ComplianceRequirement {
+                sender_conditions: conditions.clone(),
+                receiver_conditions: conditions,
+                id: 1,
+            }]
+        ),
+        CMError::<TestStorage>::ComplianceRequirementTooComplex
+    );
+}
+
+#[test]
+fn compliance_templates() {
+    ExtBuilder::default()
+        .build()
+        .execute_with(compliance_templates_we);
+}
+
+fn compliance_templates_we() {
+    let owner = User::new(AccountKeyring::Alice);
+    let other = User::new(AccountKeyring::Bob);
+    let (ticker, _) = create_token(owner);
+
+    let requirement = |id, did| ComplianceRequirement {
+        sender_conditions: vec![],
+        receiver_conditions: vec![Condition::from_dids(
+            ConditionType::IsPresent(Claim::Accredited(Scope::Identity(did))),
+            &[did],
+        )],
+        id,
+    };
+    let register = |origin, reqs| {
+        ComplianceManager::register_compliance_template(origin, "template".into(), reqs)
+    };
+    let apply = |id| ComplianceManager::apply_compliance_template(owner.origin(), ticker, id);
+    let requirements = || ComplianceManager::asset_compliance(ticker).requirements;
+
+    // Root registers chain-level templates, identities register their own.
+    let chain_id = ComplianceTemplateId(1);
+    let owner_id = ComplianceTemplateId(2);
+    let other_id = ComplianceTemplateId(3);
+    assert_ok!(register(root(), vec![requirement(5, owner.did)]));
+    assert_ok!(register(
+        owner.origin(),
+        vec![requirement(1, owner.did), requirement(1, other.did)]
+    ));
+    assert_ok!(register(other.origin(), vec![requirement(1, other.did)]));
+    assert_eq!(ComplianceManager::compliance_template_counter(), other_id);
+    let template = ComplianceManager::compliance_template(chain_id).unwrap();
+    assert_eq!(template.owner, GC_DID);
+    // Requirement ids are reassigned.
+    assert_eq!(template.requirements, vec![requirement(1, owner.did)]);
+
+    // Chain-level templates and the caller's own can be applied, replacing the asset's requirements.
+    assert_ok!(apply(chain_id));
+    assert_eq!(requirements(), vec![requirement(1, owner.did)]);
+    assert_ok!(ComplianceManager::disable_compliance_requirements(
+        owner.origin(),
+        ticker,
+        vec![1],
+        None
+    ));
+    assert_ok!(apply(owner_id));
+    assert_eq!(
+        requirements(),
+        vec![requirement(1, owner.did), requirement(2, other.did)]
+    );
+    assert!(ComplianceManager::disabled_requirements(ticker).is_empty());
+    assert_noop!(apply(other_id), CMError::<TestStorage>::Unauthorized);
+    assert_noop!(
+        apply(ComplianceTemplateId(4)),
+        CMError::<TestStorage>::ComplianceTemplateNotFound
+    );
+
+    // Only the owner of a template can remove it, which leaves the assets it was applied to as they are.
+    assert_noop!(
+        ComplianceManager::remove_compliance_template(owner.origin(), chain_id),
+        CMError::<TestStorage>::Unauthorized
+    );
+    assert_ok!(ComplianceManager::remove_compliance_template(
+        root(),
+        chain_id
+    ));
+    assert_ok!(ComplianceManager::remove_compliance_template(
+        owner.origin(),
+        owner_id
+    ));
+    assert_eq!(ComplianceManager::compliance_template(owner_id), None);
+    assert_noop!(
+        apply(chain_id),
+        CMError::<TestStorage>::ComplianceTemplateNotFound
+    );
+    assert_eq!(requirements().len(), 2);
+}
+
 #[test]
 fn check_new_return_type_of_rpc() {
     ExtBuilder::default().build().execute_with(|| {
@@ -1460,3 +1770,589 @@ fn check_new_return_type_of_rpc() {
         assert_valid_transfer!(ticker, owner.did, receiver.did, 100);
     });
 }
+
+#[test]
+fn claim_freshness() {
+    ExtBuilder::default()
+        .build()
+        .execute_with(claim_freshness_we);
+}
+
+fn claim_freshness_we() {
+    const DAY: u64 = 24 * 60 * 60 * 1000;
+
+    let owner = User::new(AccountKeyring::Alice);
+    let receiver = User::new(AccountKeyring::Charlie);
+    let claim_issuer = User::new(AccountKeyring::Bob);
+    let (ticker, _) = create_token(owner);
+    let claim = Claim::Accredited(owner.scope());
+
+    let receiver_condition = Condition::from_dids(
+        ConditionType::IsPresentWithin(claim.clone(), 30),
+        &[claim_issuer.did],
+    );
+    assert_ok!(ComplianceManager::add_compliance_requirement(
+        owner.origin(),
+        ticker,
+        vec![],
+        vec![receiver_condition]
+    ));
+    let is_fresh = || {
+        ComplianceManager::verify_restriction_granular(&ticker, None, Some(receiver.did))
+            .requirements[0]
+            .receiver_conditions[0]
+            .result
+    };
+
+    set_timestamp(DAY);
+    assert!(!is_fresh());
+    assert_add_claim!(claim_issuer.origin(), receiver.did, claim.clone(), None);
+    set_timestamp(20 * DAY);
+    assert!(is_fresh());
+
+    // The claim is still valid, but was issued too long ago.
+    set_timestamp(40 * DAY);
+    assert!(!is_fresh());
+
+    // Re-affirming the claim makes it fresh again.
+    assert_add_claim!(claim_issuer.origin(), receiver.did, claim, None);
+    assert!(is_fresh());
+}
+
+#[test]
+fn delegated_claim_issuance() {
+    ExtBuilder::default()
+        .build()
+        .execute_with(delegated_claim_issuance_we);
+}
+
+fn delegated_claim_issuance_we() {
+    let owner = User::new(AccountKeyring::Alice);
+    let issuer = User::new(AccountKeyring::Bob);
+    let subsidiary = User::new(AccountKeyring::Dave);
+    let receiver = User::new(AccountKeyring::Charlie);
+    let (ticker, _) = create_token(owner);
+    let accredited = Claim::Accredited(owner.scope());
+    let affiliate = Claim::Affiliate(owner.scope());
+
+    let conditions = vec![
+        Condition::from_dids(ConditionType::IsPresent(accredited.clone()), &[issuer.did]),
+        Condition::from_dids(ConditionType::IsPresent(affiliate.clone()), &[issuer.did]),
+    ];
+    assert_ok!(ComplianceManager::add_compliance_requirement(
+        owner.origin(),
+        ticker,
+        vec![],
+        conditions
+    ));
+    let results = || {
+        ComplianceManager::verify_restriction_granular(&ticker, None, Some(receiver.did))
+            .requirements[0]
+            .receiver_conditions
+            .iter()
+            .map(|condition| condition.result)
+            .collect::<Vec<_>>()
+    };
+
+    assert_add_claim!(subsidiary.origin(), receiver.did, accredited.clone(), None);
+    assert_add_claim!(subsidiary.origin(), receiver.did, affiliate.clone(), None);
+    assert_eq!(results(), [false, false]);
+
+    // Only the claims of the delegated types are treated as issued by the delegator.
+    assert_noop!(
+        Identity::delegate_claim_issuance(issuer.origin(), issuer.did, vec![ClaimType::Accredited]),
+        IdError::InvalidClaimIssuanceDelegate
+    );
+    assert_ok!(Identity::delegate_claim_issuance(
+        issuer.origin(),
+        subsidiary.did,
+        vec![ClaimType::Accredited, ClaimType::Accredited]
+    ));
+    assert_eq!(
+        Identity::claim_issuance_delegation(issuer.did, subsidiary.did),
+        vec![ClaimType::Accredited]
+    );
+    assert_eq!(results(), [true, false]);
+
+    // Revoking the delegation.
+    assert_ok!(Identity::delegate_claim_issuance(
+        issuer.origin(),
+        subsidiary.did,
+        vec![]
+    ));
+    assert_eq!(results(), [false, false]);
+    assert_eq!(Identity::claim_issuance_delegate_count(issuer.did), 0);
+
+    // The number of delegates of an identity is limited.
+    let delegate =
+        |did| Identity::delegate_claim_issuance(issuer.origin(), did, vec![ClaimType::Accredited]);
+    let eve = User::new(AccountKeyring::Eve);
+    let delegates = [owner.did, receiver.did, subsidiary.did, eve.did];
+    assert_eq!(
+        delegates.len() as u32,
+        identity::MAX_CLAIM_ISSUANCE_DELEGATES
+    );
+    for did in delegates {
+        assert_ok!(delegate(did));
+    }
+    // Updating an existing delegation doesn't count as a new delegate.
+    assert_ok!(delegate(owner.did));
+    let ferdie = User::new(AccountKeyring::Ferdie);
+    assert_noop!(delegate(ferdie.did), IdError::TooManyClaimIssuanceDelegates);
+}
+
+#[test]
+fn minimum_holding_period() {
+    ExtBuilder::default()
+        .build()
+        .execute_with(minimum_holding_period_we);
+}
+
+fn minimum_holding_period_we() {
+    const PERIOD: Moment = 1_000;
+
+    let owner = User::new(AccountKeyring::Alice);
+    let receiver = User::new(AccountKeyring::Charlie);
+    let other = User::new(AccountKeyring::Dave);
+
+    // Issuance is recorded as an acquisition by the issuer.
+    set_timestamp(10_000);
+    let (ticker, _) = create_token(owner);
+    assert_eq!(Asset::acquired_at(ticker, owner.did), Some(10_000));
+
+    let sender_condition = ConditionType::MinimumHoldingPeriod(PERIOD).into();
+    assert_ok!(ComplianceManager::add_compliance_requirement(
+        owner.origin(),
+        ticker,
+        vec![sender_condition],
+        vec![]
+    ));
+    let has_held = |holder: User| {
+        let result =
+            ComplianceManager::verify_restriction_granular(&ticker, Some(holder.did), None);
+        result.requirements[0].sender_conditions[0].result
+    };
+
+    assert!(!has_held(owner));
+    set_timestamp(10_000 + PERIOD);
+    assert!(has_held(owner));
+
+    // Holders without a recorded acquisition count from when acquisitions started being recorded.
+    pallet_asset::AcquisitionsTrackedSince::<TestStorage>::put(10_500);
+    assert!(!has_held(other));
+    set_timestamp(10_500 + PERIOD);
+    assert!(has_held(other));
+
+    // Receiving tokens starts the holding period of the receiver only.
+    let send = |from: User, to: User, amount| {
+        Asset::unsafe_transfer(
+            PortfolioId::default_portfolio(from.did),
+            PortfolioId::default_portfolio(to.did),
+            &ticker,
+            amount,
+        )
+    };
+    set_timestamp(20_000);
+    assert_ok!(send(owner, receiver, 1_000_000));
+    assert_eq!(Asset::acquired_at(ticker, receiver.did), Some(20_000));
+    assert!(has_held(owner));
+    assert!(!has_held(receiver));
+    set_timestamp(20_000 + PERIOD - 1);
+    assert!(!has_held(receiver));
+
+    // Topping up a position, e.g. with dust, doesn't restart its holding period.
+    assert_ok!(send(owner, receiver, 1));
+    assert_eq!(Asset::acquired_at(ticker, receiver.did), Some(20_000));
+    set_timestamp(20_000 + PERIOD);
+    assert!(has_held(receiver));
+
+    // A position opened again after being closed starts a new holding period.
+    assert_ok!(send(receiver, owner, 1_000_001));
+    set_timestamp(30_000);
+    assert_ok!(send(owner, receiver, 1_000_000));
+    assert_eq!(Asset::acquired_at(ticker, receiver.did), Some(30_000));
+    assert!(!has_held(receiver));
+}
+
+#[test]
+fn transfer_limits() {
+    ExtBuilder::default()
+        .build()
+        .execute_with(transfer_limits_we);
+}
+
+fn transfer_limits_we() {
+    const DAY: u64 = 24 * 60 * 60 * 1000;
+
+    let owner = User::new(AccountKeyring::Alice);
+    let receiver = User::new(AccountKeyring::Charlie);
+    let (ticker, _) = create_token(owner);
+
+    let sender_conditions = vec![
+        ConditionType::MaxTransferAmount(1_000).into(),
+        ConditionType::MaxTransferVolume(1_500, 30).into(),
+    ];
+    assert_ok!(ComplianceManager::add_compliance_requirement(
+        owner.origin(),
+        ticker,
+        sender_conditions,
+        vec![]
+    ));
+    let is_compliant = |amount| {
+        ComplianceManager::verify_restriction(&ticker, Some(owner.did), Some(receiver.did), amount)
+            == Ok(ERC1400_TRANSFER_SUCCESS)
+    };
+    let transfer = |amount| {
+        assert_ok!(Asset::unsafe_transfer(
+            PortfolioId::default_portfolio(owner.did),
+            PortfolioId::default_portfolio(receiver.did),
+            &ticker,
+            amount
+        ));
+    };
+
+    // Limit per transfer.
+    set_timestamp(DAY);
+    assert!(is_compliant(1_000));
+    assert!(!is_compliant(1_001));
+
+    // Limit over 30 days, counting the transfers of both sides.
+    transfer(1_000);
+    let volume_of = |user: User, days| {
+        Statistics::investor_transfer_volume(AssetScope::Ticker(ticker), user.did, days)
+    };
+    assert_eq!(volume_of(owner, 30), 1_000);
+    assert_eq!(volume_of(receiver, 30), 1_000);
+    assert!(is_compliant(500));
+    assert!(!is_compliant(501));
+
+    // The transfer leaves the window after 30 days.
+    set_timestamp(30 * DAY);
+    assert!(!is_compliant(501));
+    set_timestamp(31 * DAY);
+    assert_eq!(volume_of(owner, 30), 0);
+    assert_eq!(volume_of(owner, 31), 1_000);
+    assert!(is_compliant(1_000));
+
+    // Granular results evaluate the limits for the given amount.
+    let result = Asset::unsafe_can_transfer_granular(
+        None,
+        PortfolioId::default_portfolio(owner.did),
+        None,
+        PortfolioId::default_portfolio(receiver.did),
+        &ticker,
+        1_001,
+    );
+    assert!(!result.compliance_result.result);
+
+    // Volumes are only recorded while a condition limits them.
+    assert_ok!(ComplianceManager::reset_asset_compliance(
+        owner.origin(),
+        ticker
+    ));
+    let asset = AssetScope::Ticker(ticker);
+    assert!(!Statistics::investor_volume_tracking(asset));
+    transfer(1);
+    assert_eq!(volume_of(owner, 1), 0);
+}
+
+#[test]
+fn risk_score_conditions() {
+    ExtBuilder::default()
+        .build()
+        .execute_with(risk_score_conditions_we);
+}
+
+fn risk_score_conditions_we() {
+    let owner = User::new(AccountKeyring::Alice);
+    let provider = User::new(AccountKeyring::Bob);
+    let other_provider = User::new(AccountKeyring::Dave);
+    let receiver = User::new(AccountKeyring::Charlie);
+    let (ticker, _) = create_token(owner);
+
+    let conditions = vec![
+        Condition::from_dids(
+            ConditionType::RiskScoreAtMost(owner.scope(), 50),
+            &[provider.did, other_provider.did],
+        ),
+        Condition::from_dids(
+            ConditionType::RiskScoreAtLeast(owner.scope(), 20),
+            &[provider.did, other_provider.did],
+        ),
+    ];
+    assert_ok!(ComplianceManager::add_compliance_requirement(
+        owner.origin(),
+        ticker,
+        vec![],
+        conditions
+    ));
+    let results = || {
+        ComplianceManager::verify_restriction_granular(&ticker, None, Some(receiver.did))
+            .requirements[0]
+            .receiver_conditions
+            .iter()
+            .map(|condition| condition.result)
+            .collect::<Vec<_>>()
+    };
+    let score = |score| {
+        Claim::RiskScore(
+            RiskScore {
+                score,
+                model_version: 1,
+            },
+            owner.scope(),
+        )
+    };
+
+    // Without any score, neither bound holds.
+    assert_eq!(results(), vec![false, false]);
+
+    assert_add_claim!(provider.origin(), receiver.did, score(10), None);
+    assert_eq!(results(), vec![true, false]);
+    assert_add_claim!(provider.origin(), receiver.did, score(50), None);
+    assert_eq!(results(), vec![true, true]);
+    assert_add_claim!(provider.origin(), receiver.did, score(51), None);
+    assert_eq!(results(), vec![false, true]);
+
+    // Every score must be within the upper bound.
+    assert_add_claim!(provider.origin(), receiver.did, score(30), None);
+    assert_add_claim!(other_provider.origin(), receiver.did, score(60), None);
+    assert_eq!(results(), vec![false, true]);
+
+    // Scores of another scope are ignored.
+    let other_scope = Claim::RiskScore(RiskScore::default(), receiver.scope());
+    assert_add_claim!(other_provider.origin(), receiver.did, other_scope, None);
+    assert_eq!(results(), vec![false, true]);
+}
+
+#[test]
+fn compliance_report() {
+    ExtBuilder::default()
+        .build()
+        .execute_with(compliance_report_we);
+}
+
+fn compliance_report_we() {
+    let owner = User::new(AccountKeyring::Alice);
+    let claim_issuer = User::new(AccountKeyring::Bob);
+    let receiver = User::new(AccountKeyring::Charlie);
+    let (ticker, _) = create_token(owner);
+
+    let claim = Claim::Accredited(Scope::Identity(owner.did));
+    assert_ok!(ComplianceManager::add_compliance_requirement(
+        owner.origin(),
+        ticker,
+        vec![ConditionType::MaxTransferAmount(1_000).into()],
+        vec![Condition::from_dids(
+            ConditionType::IsPresent(claim.clone()),
+            &[claim_issuer.did]
+        )]
+    ));
+    let report = |amount| {
+        ComplianceManager::compliance_report(&ticker, Some(owner.did), Some(receiver.did), amount)
+    };
+
+    // Both conditions fail, and the receiver's one reports the claim it lacks.
+    let result = report(2_000);
+    assert!(!result.result);
+    let requirement = &result.requirements[0];
+    assert!(!requirement.result);
+    assert!(!requirement.disabled);
+    assert!(!requirement.sender_conditions[0].result);
+    assert!(requirement.sender_conditions[0].missing_claims.is_empty());
+    assert!(!requirement.receiver_conditions[0].result);
+    assert_eq!(
+        requirement.receiver_conditions[0].missing_claims,
+        vec![(claim.clone(), vec![TrustedIssuer::from(claim_issuer.did)])]
+    );
+
+    // Once the claim is provided and the amount is within the limit, the transfer is compliant.
+    assert_ok!(Identity::add_claim(
+        claim_issuer.origin(),
+        receiver.did,
+        claim,
+        None
+    ));
+    let result = report(1_000);
+    assert!(result.result);
+    let requirement = &result.requirements[0];
+    assert!(requirement.sender_conditions[0].result);
+    assert!(requirement.receiver_conditions[0].result);
+    assert!(requirement.receiver_conditions[0].missing_claims.is_empty());
+}
+
+#[test]
+fn simulate_requirements() {
+    ExtBuilder::default()
+        .build()
+        .execute_with(simulate_requirements_we);
+}
+
+fn simulate_requirements_we() {
+    let owner = User::new(AccountKeyring::Alice);
+    let claim_issuer = User::new(AccountKeyring::Bob);
+    let holder = User::new(AccountKeyring::Charlie);
+    let (ticker, _) = create_token(owner);
+    assert_ok!(Asset::unsafe_transfer(
+        PortfolioId::default_portfolio(owner.did),
+        PortfolioId::default_portfolio(holder.did),
+        &ticker,
+        1_000
+    ));
+
+    // Only the owner is accredited, so the holder could no longer send the asset.
+    let claim = Claim::Accredited(Scope::Identity(owner.did));
+    assert_ok!(Identity::add_claim(
+        claim_issuer.origin(),
+        owner.did,
+        claim.clone(),
+        None
+    ));
+    let requirements = vec![ComplianceRequirement {
+        sender_conditions: vec![Condition::from_dids(
+            ConditionType::IsPresent(claim),
+            &[claim_issuer.did],
+        )],
+        receiver_conditions: vec![],
+        id: 1,
+    }];
+    let simulate = |start_after, limit| {
+        ComplianceManager::simulate_requirements(&ticker, &requirements, start_after, limit)
+    };
+    let impact = simulate(None, 10);
+    assert_eq!(impact.evaluated, 2);
+    assert_eq!(impact.cannot_send, vec![holder.did]);
+    assert!(impact.cannot_receive.is_empty());
+    assert_eq!(impact.next_holder, None);
+
+    // Holders can be evaluated in batches.
+    let mut holders = vec![owner.did, holder.did];
+    holders.sort();
+    let impact = simulate(None, 1);
+    assert_eq!(impact.evaluated, 1);
+    assert_eq!(impact.next_holder, Some(holders[0]));
+    let impact = simulate(impact.next_holder, 1);
+    assert_eq!(impact.evaluated, 1);
+    assert_eq!(impact.next_holder, None);
+
+    // Without requirements, no transfer is possible.
+    let impact = ComplianceManager::simulate_requirements(&ticker, &[], None, 10);
+    assert_eq!(impact.cannot_send, holders);
+    assert_eq!(impact.cannot_receive, holders);
+}
+
+#[test]
+fn scheduled_requirements() {
+    ExtBuilder::default()
+        .build()
+        .execute_with(scheduled_requirements_we);
+}
+
+fn scheduled_requirements_we() {
+    use ComplianceRequirementChange::{Add, Remove, Replace};
+
+    let owner = User::new(AccountKeyring::Alice);
+    let (ticker, _) = create_token(owner);
+    let requirements = || ComplianceManager::asset_compliance(ticker).requirements;
+    let scheduled = || ComplianceManager::scheduled_requirement_changes(ticker);
+
+    let ty = ConditionType::IsPresent(Claim::KnowYourCustomer(Scope::Ticker(ticker)));
+    let conditions = |n| vec![Condition::from_dids(ty.clone(), &[owner.did]); n];
+    let req = |id, n| ComplianceRequirement {
+        sender_conditions: conditions(n),
+        receiver_conditions: vec![],
+        id,
+    };
+    let schedule = |change, effective_from| {
+        ComplianceManager::schedule_compliance_requirement_change(
+            owner.origin(),
+            ticker,
+            change,
+            effective_from,
+        )
+    };
+    let cancel =
+        |id| ComplianceManager::cancel_compliance_requirement_change(owner.origin(), ticker, id);
+
+    // Changes can only be scheduled for future blocks.
+    System::set_block_number(2);
+    assert_noop!(
+        schedule(Add(req(0, 1)), 2),
+        CMError::<TestStorage>::InvalidEffectiveBlock
+    );
+
+    // A requirement to add gets its id when scheduled, and is added in the given block.
+    assert_ok!(schedule(Add(req(0, 1)), 4));
+    assert_eq!(scheduled()[&1].2, Add(req(1, 1)));
+    assert_ok!(ComplianceManager::add_compliance_requirement(
+        owner.origin(),
+        ticker,
+        vec![],
+        conditions(1)
+    ));
+    let added = requirements().pop().unwrap();
+    assert_eq!(added.id, 2);
+    next_block();
+    assert_eq!(requirements().len(), 1);
+    next_block();
+    assert!(scheduled().is_empty());
+    assert_eq!(requirements(), vec![req(1, 1), added.clone()]);
+
+    // Only existing requirements can be replaced or removed,
+    // and each requirement has a single pending change.
+    assert_noop!(
+        schedule(Replace(req(3, 2)), 6),
+        CMError::<TestStorage>::InvalidComplianceRequirementId
+    );
+    assert_noop!(
+        schedule(Remove(3), 6),
+        CMError::<TestStorage>::InvalidComplianceRequirementId
+    );
+    assert_ok!(schedule(Replace(req(1, 2)), 6));
+    assert_noop!(
+        schedule(Remove(1), 6),
+        CMError::<TestStorage>::RequirementChangeAlreadyScheduled
+    );
+    assert_ok!(schedule(Remove(2), 6));
+    next_block();
+    next_block();
+    assert_eq!(requirements(), vec![req(1, 2)]);
+
+    // Cancelled changes are not applied.
+    assert_ok!(schedule(Remove(1), 8));
+    assert_ok!(cancel(1));
+    assert_noop!(
+        cancel(1),
+        CMError::<TestStorage>::NoScheduledRequirementChange
+    );
+    next_block();
+    next_block();
+    assert_eq!(requirements(), vec![req(1, 2)]);
+
+    // Changes exceeding the maximum complexity once applied are dropped.
+    assert_ok!(schedule(Add(req(0, 30)), 9));
+    assert_ok!(ComplianceManager::add_compliance_requirement(
+        owner.origin(),
+        ticker,
+        conditions(30),
+        vec![]
+    ));
+    next_block();
+    assert!(scheduled().is_empty());
+    assert_eq!(requirements().len(), 2);
+
+    // The number of pending changes of an asset is limited.
+    for _ in 0..MAX_SCHEDULED_REQUIREMENT_CHANGES {
+        assert_ok!(schedule(Add(req(0, 1)), 12));
+    }
+    assert_noop!(
+        schedule(Add(req(0, 1)), 12),
+        CMError::<TestStorage>::TooManyScheduledRequirements
+    );
+    let ids = scheduled().into_keys().collect::<Vec<_>>();
+    for id in ids {
+        assert_ok!(cancel(id));
+    }
+    fast_forward_blocks(3);
+    assert_eq!(requirements().len(), 2);
+}
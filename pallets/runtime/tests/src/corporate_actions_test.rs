@@ -2303,3 +2303,42 @@ fn dist_claim_existing_checkpoint() {
 fn dist_claim_scheduled_checkpoint() {
     dist_claim_cp_test(|ticker, owner| dist_ca(owner, ticker, Some(2000)).unwrap());
 }
+
+#[test]
+fn migrate_ticker_moves_corporate_actions() {
+    currency_test(|ticker, currency, [owner, ..]| {
+        let id = dist_ca(owner, ticker, Some(1)).unwrap();
+        assert_ok!(Dist::distribute(
+            owner.origin(),
+            id,
+            None,
+            currency,
+            1,
+            1_000,
+            5,
+            Some(6)
+        ));
+        let ca = get_ca(id).unwrap();
+        let dist = Dist::distributions(id).unwrap();
+
+        // Holders may be paid until the distribution expires.
+        let new = Ticker::from_slice_truncated(b"REBRAND");
+        set_timestamp(5);
+        assert_noop!(
+            Asset::migrate_ticker(root(), ticker, new, 10, 100),
+            DistError::DistributionNotMigratable
+        );
+
+        set_timestamp(6);
+        assert_ok!(Asset::migrate_ticker(root(), ticker, new, 10, 100));
+        let new_id = CAId {
+            ticker: new,
+            local_id: id.local_id,
+        };
+        assert_eq!(get_ca(id), None);
+        assert_eq!(get_ca(new_id), Some(ca));
+        assert_eq!(Dist::distributions(id), None);
+        assert_eq!(Dist::distributions(new_id), Some(dist));
+        assert_eq!(next_ca_id(new).local_id, LocalCAId(id.local_id.0 + 1));
+    });
+}
@@ -1,25 +1,31 @@
-use crate::asset_test::{a_token, an_asset, basic_asset};
+use crate::asset_test::{a_token, an_asset, basic_asset, set_timestamp};
 use crate::ext_builder::ExtBuilder;
 use crate::identity_test::test_with_bad_ext_perms;
-use crate::storage::{TestStorage, User};
+use crate::storage::{root, TestStorage, User};
+use codec::Encode;
 use frame_support::dispatch::DispatchResult;
 use frame_support::{
-    assert_noop, assert_ok, IterableStorageDoubleMap, StorageDoubleMap, StorageMap,
+    assert_noop, assert_ok, traits::Get, IterableStorageDoubleMap, StorageDoubleMap, StorageMap,
 };
 use pallet_external_agents::{AGIdSequence, AgentOf, GroupOfAgent, NumFullAgents};
 use pallet_permissions::StoreCallMetadata;
 use polymesh_common_utilities::constants::currency::POLY;
+use polymesh_common_utilities::traits::identity::TargetIdAuthorization;
 use polymesh_primitives::{
-    agent::{AGId, AgentGroup},
+    agent::{AGId, AgentGroup, AgentReplacement, SessionKeyWithAuth},
     AuthorizationData, ExtrinsicPermissions, PalletPermissions, Signatory, SubsetRestriction,
     Ticker,
 };
+use polymesh_runtime_common::AgentReplacementDelay;
+use sp_core::H512;
+use sp_runtime::DispatchError;
 use test_client::AccountKeyring;
 
 type ExternalAgents = pallet_external_agents::Module<TestStorage>;
 type BaseError = pallet_base::Error<TestStorage>;
 type Error = pallet_external_agents::Error<TestStorage>;
 type Id = pallet_identity::Module<TestStorage>;
+type PermError = pallet_permissions::Error<TestStorage>;
 
 fn set_extrinsic(name: &str) {
     StoreCallMetadata::<TestStorage>::set_call_metadata(
@@ -394,3 +400,161 @@ fn atredis_multi_group_perms() {
         assert_ok!(set(b));
     });
 }
+
+#[test]
+fn session_keys_works() {
+    ExtBuilder::default().build().execute_with(|| {
+        let owner = User::new(AccountKeyring::Alice);
+        let bob = User::new(AccountKeyring::Bob);
+        let session = User::new_with(owner.did, AccountKeyring::Dave);
+        let ticker = an_asset(owner, false);
+        set_timestamp(10);
+
+        let expires_at = 100;
+        let auth_encoded = TargetIdAuthorization {
+            target_id: owner.did,
+            nonce: Id::offchain_authorization_nonce(owner.did),
+            expires_at,
+        }
+        .encode();
+        let keys = vec![SessionKeyWithAuth {
+            key: session.acc(),
+            auth_signature: H512::from(session.ring.sign(&auth_encoded)),
+        }];
+        let perms = make_perms("pallet_external_agent");
+        let register = |user: User, expiry| {
+            ExternalAgents::register_session_keys(
+                user.origin(),
+                ticker,
+                perms.clone(),
+                keys.clone(),
+                expires_at,
+                expiry,
+            )
+        };
+
+        // Only agents can register session keys, and they must expire in the future.
+        assert_noop!(register(bob, expires_at), Error::UnauthorizedAgent);
+        assert_noop!(register(owner, 10), Error::SessionKeyExpiryInPast);
+
+        assert_ok!(register(owner, expires_at));
+        assert!(Id::is_secondary_key(owner.did, &session.acc()));
+        assert_eq!(Id::secondary_key_expiry(session.acc()), Some(expires_at));
+
+        // The session key can act as an agent of `ticker` until it expires.
+        set_extrinsic("create_group");
+        assert_ok!(ExternalAgents::create_group(
+            session.origin(),
+            ticker,
+            <_>::default()
+        ));
+        set_timestamp(expires_at);
+        assert_noop!(
+            ExternalAgents::create_group(session.origin(), ticker, <_>::default()),
+            PermError::UnauthorizedCaller
+        );
+
+        // Only the agent that registered the key can expire it.
+        assert_noop!(
+            ExternalAgents::expire_session_keys(bob.origin(), ticker, vec![session.acc()]),
+            Error::NoSuchSessionKey
+        );
+        assert_ok!(ExternalAgents::expire_session_keys(
+            owner.origin(),
+            ticker,
+            vec![session.acc()]
+        ));
+        assert!(!Id::is_secondary_key(owner.did, &session.acc()));
+        assert_eq!(Id::secondary_key_expiry(session.acc()), None);
+    });
+}
+
+#[test]
+fn replace_agent_works() {
+    ExtBuilder::default().build().execute_with(|| {
+        let owner = User::new(AccountKeyring::Alice);
+        let bob = User::new(AccountKeyring::Bob);
+        let charlie = User::new(AccountKeyring::Charlie);
+        let ticker = an_asset(owner, false);
+        set_timestamp(10);
+
+        // Only governance can schedule a replacement.
+        assert_noop!(
+            ExternalAgents::schedule_agent_replacement(bob.origin(), ticker, bob.did),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(ExternalAgents::schedule_agent_replacement(
+            root(),
+            ticker,
+            bob.did
+        ));
+        let effective_at = 10 + AgentReplacementDelay::get();
+        assert_eq!(
+            ExternalAgents::agent_replacement(ticker),
+            Some(AgentReplacement {
+                new_agent: bob.did,
+                effective_at,
+            })
+        );
+        assert_noop!(
+            ExternalAgents::schedule_agent_replacement(root(), ticker, charlie.did),
+            Error::AgentReplacementAlreadyScheduled
+        );
+
+        // A responsive agent can cancel it.
+        set_extrinsic("cancel_agent_replacement");
+        assert_ok!(ExternalAgents::cancel_agent_replacement(
+            owner.origin(),
+            ticker
+        ));
+        assert_noop!(
+            ExternalAgents::replace_agent(root(), ticker, 1, 1),
+            Error::NoAgentReplacementScheduled
+        );
+
+        // The notice has to pass before the agent is replaced.
+        assert_ok!(ExternalAgents::schedule_agent_replacement(
+            root(),
+            ticker,
+            bob.did
+        ));
+        assert_noop!(
+            ExternalAgents::replace_agent(root(), ticker, 1, 1),
+            Error::AgentReplacementNoticeNotOver
+        );
+        set_timestamp(effective_at);
+
+        // The agent must not have any pending `BecomeAgent` authorization.
+        let auth_id = Id::add_auth(
+            owner.did,
+            Signatory::Identity(charlie.did),
+            AuthorizationData::BecomeAgent(ticker, AgentGroup::Full),
+            None,
+        );
+        assert_noop!(
+            ExternalAgents::replace_agent(root(), ticker, 1, 0),
+            Error::TooManyAgentAuthorizations
+        );
+        assert_noop!(
+            ExternalAgents::replace_agent(root(), ticker, 0, 1),
+            Error::TooManyAgents
+        );
+        assert_noop!(
+            ExternalAgents::replace_agent(root(), ticker, 1, 1),
+            Error::PendingAgentAuthorization
+        );
+        assert_ok!(Id::remove_authorization(
+            owner.origin(),
+            Signatory::Identity(charlie.did),
+            auth_id,
+            false
+        ));
+
+        assert_ok!(ExternalAgents::replace_agent(root(), ticker, 1, 1));
+        assert_eq!(GroupOfAgent::get(ticker, owner.did), None);
+        assert!(!AgentOf::contains_key(owner.did, ticker));
+        assert_eq!(GroupOfAgent::get(ticker, bob.did), Some(AgentGroup::Full));
+        assert_eq!(NumFullAgents::get(ticker), 1);
+        assert_eq!(ExternalAgents::agent_replacement(ticker), None);
+    });
+}
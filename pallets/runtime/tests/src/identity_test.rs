@@ -5,20 +5,27 @@ use super::{
     storage::{
         account_from, add_secondary_key, add_secondary_key_with_perms,
         create_cdd_id_and_investor_uid, get_identity_id, get_last_auth_id, get_primary_key,
-        get_secondary_keys, provide_scope_claim, register_keyring_account,
-        register_keyring_account_with_balance, GovernanceCommittee, TestStorage, User,
+        get_secondary_keys, make_account_without_cdd, next_block, provide_scope_claim,
+        register_keyring_account, register_keyring_account_with_balance, GovernanceCommittee,
+        TestStorage, User,
     },
     ExtBuilder,
 };
 use codec::Encode;
 use confidential_identity_v1::mocked::make_investor_uid;
 use frame_support::{
-    assert_noop, assert_ok, dispatch::DispatchResult, traits::Currency, StorageDoubleMap,
-    StorageMap, StorageValue,
+    assert_noop, assert_ok,
+    dispatch::{DispatchError, DispatchResult, GetDispatchInfo, Pays},
+    traits::{Contains, Currency, Get, GetCallMetadata},
+    StorageDoubleMap, StorageMap, StorageValue,
 };
 use pallet_asset::SecurityToken;
 use pallet_balances as balances;
-use pallet_identity::{CustomClaimIdSequence, CustomClaims, CustomClaimsInverse};
+use pallet_identity::{
+    CddClaimDetails, CustomClaimIdSequence, CustomClaims, CustomClaimsInverse, DidStatus,
+    DidStatusDetails, IdentityCallFilter, MAX_AUTHORIZATIONS_PER_BATCH,
+};
+use pallet_permissions::with_call_metadata;
 use polymesh_common_utilities::{
     asset::AssetSubTrait,
     constants::currency::POLY,
@@ -32,15 +39,18 @@ use polymesh_common_utilities::{
     },
     SystematicIssuers, GC_DID,
 };
+use polymesh_primitives::identity_claim::{RiskScore, MAX_RISK_SCORE};
 use polymesh_primitives::{
-    investor_zkproof_data::v2, AccountId, AssetPermissions, AuthorizationData, AuthorizationType,
-    CddId, Claim, ClaimType, CustomClaimTypeId, DispatchableName, ExtrinsicPermissions,
-    IdentityClaim, IdentityId, InvestorUid, KeyRecord, PalletName, PalletPermissions, Permissions,
-    PortfolioId, PortfolioNumber, Scope, SecondaryKey, Signatory, SubsetRestriction, Ticker,
-    TransactionError,
+    investor_zkproof_data::v2, AccountId, AgreementConsent, AgreementId, AssetPermissions,
+    AuthorizationData, AuthorizationType, BatchImport, CallFilter, CddId, Claim, ClaimType,
+    CustomClaimField, CustomClaimFieldType, CustomClaimSchema, CustomClaimTypeId, DispatchableName,
+    ExtrinsicPermissions, IdentityClaim, IdentityId, InvestorUid, KeyRecord, PalletName,
+    PalletPermissions, Permissions, PortfolioId, PortfolioNumber, RecoveryGuardians, Scope,
+    SecondaryKey, Signatory, SubsetRestriction, Ticker, TransactionError,
 };
 use polymesh_runtime_develop::runtime::{CddHandler, RuntimeCall};
-use sp_core::H512;
+use sp_core::{H256, H512};
+use sp_runtime::traits::{Dispatchable, SignedExtension};
 use sp_runtime::transaction_validity::InvalidTransaction;
 use std::convert::From;
 use test_client::AccountKeyring;
@@ -51,10 +61,14 @@ type Balances = balances::Module<TestStorage>;
 type BaseError = pallet_base::Error<TestStorage>;
 type Identity = pallet_identity::Module<TestStorage>;
 type MultiSig = pallet_multisig::Module<TestStorage>;
+type PermissionsModule = pallet_permissions::Module<TestStorage>;
+type PermissionsError = pallet_permissions::Error<TestStorage>;
+type StoreCallMetadata = pallet_permissions::StoreCallMetadata<TestStorage>;
 type System = frame_system::Pallet<TestStorage>;
 type Timestamp = pallet_timestamp::Pallet<TestStorage>;
 
 type Origin = <TestStorage as frame_system::Config>::RuntimeOrigin;
+type TestCall = <TestStorage as frame_system::Config>::RuntimeCall;
 type CddServiceProviders = <TestStorage as IdentityConfig>::CddServiceProviders;
 type Error = pallet_identity::Error<TestStorage>;
 type PError = pallet_permissions::Error<TestStorage>;
@@ -204,6 +218,54 @@ fn revoking_claims() {
     });
 }
 
+#[test]
+fn risk_score_claims() {
+    ExtBuilder::default().build().execute_with(|| {
+        let target = User::new(AccountKeyring::Alice);
+        let bob = User::new(AccountKeyring::Bob);
+        let charlie = User::new(AccountKeyring::Charlie);
+        let score = |score, scope: u128| {
+            Claim::RiskScore(
+                RiskScore {
+                    score,
+                    model_version: 1,
+                },
+                Scope::from(IdentityId::from(scope)),
+            )
+        };
+        let add = |provider: User, claim, expiry| {
+            Identity::add_claim(provider.origin(), target.did, claim, expiry)
+        };
+
+        assert_noop!(
+            add(bob, score(MAX_RISK_SCORE + 1, 0), None),
+            Error::InvalidRiskScore
+        );
+
+        set_timestamp(1);
+        assert_ok!(add(bob, score(20, 0), None));
+        set_timestamp(10);
+        assert_ok!(add(bob, score(30, 1), None));
+        assert_ok!(add(charlie, score(50, 0), Some(20)));
+
+        let latest_scores = || {
+            let mut scores = Identity::latest_risk_scores(target.did)
+                .into_iter()
+                .map(|id_claim| (id_claim.claim_issuer, id_claim.claim))
+                .collect::<Vec<_>>();
+            scores.sort_by_key(|(did, _)| *did);
+            scores
+        };
+        let mut expected = vec![(bob.did, score(30, 1)), (charlie.did, score(50, 0))];
+        expected.sort_by_key(|(did, _)| *did);
+        assert_eq!(latest_scores(), expected);
+
+        // Expired scores are ignored.
+        set_timestamp(30);
+        assert_eq!(latest_scores(), vec![(bob.did, score(30, 1))]);
+    });
+}
+
 #[test]
 fn revoking_batch_claims() {
     ExtBuilder::default().build().execute_with(|| {
@@ -239,6 +301,283 @@ fn revoking_batch_claims() {
     });
 }
 
+#[test]
+fn add_claims_batch() {
+    ExtBuilder::default().build().execute_with(|| {
+        let alice = User::new(AccountKeyring::Alice);
+        let bob = User::new(AccountKeyring::Bob);
+        let claim_issuer = User::new(AccountKeyring::Charlie);
+        let scope = Scope::from(IdentityId::from(0));
+
+        let add = |claims| Identity::add_claims_batch(claim_issuer.origin(), claims);
+        let fetch = |target, claim_type, scope| {
+            Identity::fetch_claim(target, claim_type, claim_issuer.did, scope)
+        };
+
+        // Claims are all validated before any is added.
+        assert_noop!(
+            add(vec![
+                (alice.did, Claim::Accredited(scope.clone()), None),
+                (IdentityId::from(999), Claim::NoData, None),
+            ]),
+            Error::DidMustAlreadyExist
+        );
+        assert_noop!(
+            add(vec![
+                (alice.did, Claim::Accredited(scope.clone()), None),
+                (
+                    bob.did,
+                    Claim::InvestorUniqueness(
+                        scope.clone(),
+                        Default::default(),
+                        Default::default()
+                    ),
+                    None
+                ),
+            ]),
+            Error::ClaimVariantNotAllowed
+        );
+        let too_many = (0..=pallet_identity::MAX_CLAIMS_PER_BATCH)
+            .map(|_| (alice.did, Claim::NoData, None))
+            .collect();
+        assert_noop!(add(too_many), Error::TooManyClaims);
+
+        assert_ok!(add(vec![
+            (alice.did, Claim::Accredited(scope.clone()), Some(100u64)),
+            (bob.did, Claim::Affiliate(scope.clone()), None),
+            (bob.did, Claim::NoData, None),
+        ]));
+        assert!(fetch(alice.did, ClaimType::Accredited, Some(scope.clone())).is_some());
+        assert!(fetch(bob.did, ClaimType::Affiliate, Some(scope.clone())).is_some());
+        assert!(fetch(bob.did, ClaimType::NoType, None).is_some());
+
+        // Each claim emits its own event.
+        let added = System::events()
+            .into_iter()
+            .filter(|r| {
+                matches!(
+                    r.event,
+                    super::storage::EventTest::Identity(RawEvent::ClaimAdded(..))
+                )
+            })
+            .count();
+        assert_eq!(added, 3);
+    });
+}
+
+#[test]
+fn claim_import() {
+    ExtBuilder::default().build().execute_with(|| {
+        let alice = User::new(AccountKeyring::Alice);
+        let bob = User::new(AccountKeyring::Bob);
+        let dave = User::new(AccountKeyring::Dave);
+        let claim_issuer = User::new(AccountKeyring::Charlie);
+        let claim = Claim::Accredited(Scope::from(IdentityId::from(0)));
+
+        let targets = vec![alice.did, bob.did, dave.did];
+        let mut expected = BatchImport::new(targets.len() as u32, Default::default());
+        let batch_hashes = targets
+            .iter()
+            .map(|target| {
+                expected.add_entry(target);
+                expected.running_hash
+            })
+            .collect::<Vec<_>>();
+        let import = |targets: &[IdentityId], batch_hash| {
+            Identity::import_claim_batch(
+                claim_issuer.origin(),
+                claim.clone(),
+                targets.to_vec(),
+                batch_hash,
+            )
+        };
+        let has_claim = |target| {
+            Identity::fetch_claim(
+                target,
+                ClaimType::Accredited,
+                claim_issuer.did,
+                claim.as_scope().cloned(),
+            )
+            .map(|id_claim| id_claim.expiry)
+        };
+
+        // Finalizing requires a started import.
+        assert_noop!(
+            Identity::finalize_claim_import(claim_issuer.origin(), claim.clone()),
+            Error::ClaimImportNotFound
+        );
+        assert_noop!(
+            Identity::start_claim_import(
+                claim_issuer.origin(),
+                Claim::InvestorUniqueness(
+                    Scope::from(IdentityId::from(0)),
+                    Default::default(),
+                    Default::default()
+                ),
+                None,
+                1,
+                Default::default()
+            ),
+            Error::ClaimVariantNotAllowed
+        );
+        assert_ok!(Identity::start_claim_import(
+            claim_issuer.origin(),
+            claim.clone(),
+            Some(100u64),
+            targets.len() as u32,
+            expected.running_hash,
+        ));
+        assert_noop!(
+            Identity::start_claim_import(
+                claim_issuer.origin(),
+                claim.clone(),
+                None,
+                1,
+                Default::default()
+            ),
+            Error::ClaimImportInProgress
+        );
+
+        // Batches are verified before any claim is added.
+        assert_noop!(
+            import(&targets[1..2], batch_hashes[0]),
+            Error::ClaimBatchHashMismatch
+        );
+        let unknown = IdentityId::from(999);
+        let mut unknown_import = BatchImport::new(1, Default::default());
+        unknown_import.add_entry(&unknown);
+        assert_noop!(
+            import(&[unknown], unknown_import.running_hash),
+            Error::DidMustAlreadyExist
+        );
+
+        // Import the first batch.
+        assert_ok!(import(&targets[..2], batch_hashes[1]));
+        assert_eq!(has_claim(alice.did), Some(Some(100)));
+        assert_eq!(has_claim(bob.did), Some(Some(100)));
+        assert_eq!(has_claim(dave.did), None);
+        assert_noop!(
+            Identity::finalize_claim_import(claim_issuer.origin(), claim.clone()),
+            Error::ClaimImportIncomplete
+        );
+        assert_noop!(
+            import(&targets, expected.running_hash),
+            Error::ClaimImportOverflow
+        );
+
+        // Import the rest and finalize.
+        assert_ok!(import(&targets[2..], batch_hashes[2]));
+        assert_eq!(has_claim(dave.did), Some(Some(100)));
+        assert_ok!(Identity::finalize_claim_import(
+            claim_issuer.origin(),
+            claim.clone()
+        ));
+        assert_eq!(Identity::claim_import(claim_issuer.did, &claim), None);
+
+        // An import whose targets don't match its expected hash can't be finalized, only aborted.
+        assert_ok!(Identity::start_claim_import(
+            claim_issuer.origin(),
+            claim.clone(),
+            None,
+            1,
+            Default::default(),
+        ));
+        assert_ok!(import(&targets[..1], batch_hashes[0]));
+        assert_noop!(
+            Identity::finalize_claim_import(claim_issuer.origin(), claim.clone()),
+            Error::ClaimImportHashMismatch
+        );
+        assert_ok!(Identity::abort_claim_import(
+            claim_issuer.origin(),
+            claim.clone()
+        ));
+        assert_noop!(
+            Identity::abort_claim_import(claim_issuer.origin(), claim.clone()),
+            Error::ClaimImportNotFound
+        );
+        // The claims added before the import was aborted are kept.
+        assert_eq!(has_claim(alice.did), Some(None));
+    });
+}
+
+#[test]
+fn scheduled_claim_revocation() {
+    ExtBuilder::default().build().execute_with(|| {
+        let alice = User::new(AccountKeyring::Alice);
+        let claim_issuer = User::new(AccountKeyring::Charlie);
+        let scope = Scope::from(IdentityId::from(0));
+        let claim = Claim::Accredited(scope.clone());
+
+        let schedule = |at| {
+            Identity::schedule_claim_revocation(claim_issuer.origin(), alice.did, claim.clone(), at)
+        };
+        let fetch = || {
+            Identity::fetch_claim(
+                alice.did,
+                ClaimType::Accredited,
+                claim_issuer.did,
+                Some(scope.clone()),
+            )
+        };
+        let (pk, sk) = Identity::get_claim_keys(
+            alice.did,
+            ClaimType::Accredited,
+            claim_issuer.did,
+            Some(scope.clone()),
+        );
+
+        let run_blocks = |n| {
+            for _ in 0..n {
+                next_block();
+            }
+        };
+
+        // With blocks of 6ms, the removal is scheduled `(at - now) / 6 + 1` blocks ahead.
+        set_timestamp(1_000);
+        assert_noop!(schedule(1_060), Error::ClaimDoesNotExist);
+        assert_ok!(Identity::add_claim(
+            claim_issuer.origin(),
+            alice.did,
+            claim.clone(),
+            Some(2_000),
+        ));
+        assert_noop!(schedule(1_000), Error::InvalidClaimRevocationTime);
+        assert_noop!(schedule(2_000), Error::InvalidClaimRevocationTime);
+        assert_ok!(schedule(1_060));
+        assert_eq!(fetch().unwrap().expiry, Some(1_060));
+
+        // The removal is rescheduled when executed before the revocation time.
+        set_timestamp(1_030);
+        run_blocks(11);
+        assert!(fetch().is_some());
+
+        // The claim expires at the revocation time, and is removed by the scheduler.
+        set_timestamp(1_070);
+        assert!(fetch().is_none());
+        assert!(pallet_identity::Claims::contains_key(&pk, &sk));
+        run_blocks(6);
+        assert!(!pallet_identity::Claims::contains_key(&pk, &sk));
+
+        // A claim re-issued before its removal is kept.
+        assert_ok!(Identity::add_claim(
+            claim_issuer.origin(),
+            alice.did,
+            claim.clone(),
+            None
+        ));
+        assert_ok!(schedule(1_100));
+        assert_ok!(Identity::add_claim(
+            claim_issuer.origin(),
+            alice.did,
+            claim.clone(),
+            None
+        ));
+        set_timestamp(1_200);
+        run_blocks(6);
+        assert!(fetch().is_some());
+    });
+}
+
 #[test]
 fn only_primary_key_can_add_secondary_key_permissions() {
     ExtBuilder::default()
@@ -395,125 +734,440 @@ fn freeze_secondary_keys_with_externalities() {
     assert_eq!(is_auth(dave.acc()), true);
 }
 
-/// It double-checks that frozen keys are removed too.
 #[test]
-fn remove_frozen_secondary_keys_test() {
+fn secondary_key_expiry() {
     ExtBuilder::default()
         .monied(true)
         .build()
-        .execute_with(&remove_frozen_secondary_keys_with_externalities);
+        .execute_with(&secondary_key_expiry_with_externalities);
 }
 
-fn remove_frozen_secondary_keys_with_externalities() {
+fn secondary_key_expiry_with_externalities() {
     let alice = User::new(AccountKeyring::Alice);
     let bob = User::new_with(alice.did, AccountKeyring::Bob);
-    let charlie = User::new_with(alice.did, AccountKeyring::Charlie);
+    let charlie = User::new(AccountKeyring::Charlie);
+    let is_auth = |key| Identity::is_key_authorized(alice.did, &key);
+    let set_expiry = |origin, key, expiry| Identity::set_secondary_key_expiry(origin, key, expiry);
 
-    // Add secondary keys.
     add_secondary_key(alice.did, bob.acc());
-    add_secondary_key(alice.did, charlie.acc());
+    set_timestamp(1_000);
 
-    // Freeze all secondary keys
-    assert_ok!(Identity::freeze_secondary_keys(alice.origin()));
+    // Only the primary key can set an expiry, and only on its own secondary keys.
+    assert_noop!(
+        set_expiry(bob.origin(), bob.acc(), Some(5_000)),
+        Error::KeyNotAllowed
+    );
+    assert_noop!(
+        set_expiry(alice.origin(), charlie.acc(), Some(5_000)),
+        Error::NotASigner
+    );
+    assert_noop!(
+        set_expiry(alice.origin(), bob.acc(), Some(1_000)),
+        Error::SecondaryKeyExpiryInPast
+    );
 
-    // Remove Bob's key.
+    assert_ok!(set_expiry(alice.origin(), bob.acc(), Some(5_000)));
+    assert_eq!(Identity::secondary_key_expiry(bob.acc()), Some(5_000));
+    assert_eq!(is_auth(bob.acc()), true);
+
+    // Once the expiry is reached, the key loses its permissions.
+    set_timestamp(5_000);
+    assert_eq!(is_auth(bob.acc()), false);
+
+    // Clearing the expiry restores the key.
+    assert_ok!(set_expiry(alice.origin(), bob.acc(), None));
+    assert_eq!(Identity::secondary_key_expiry(bob.acc()), None);
+    assert_eq!(is_auth(bob.acc()), true);
+
+    // Removing the key also removes its expiry.
+    assert_ok!(set_expiry(alice.origin(), bob.acc(), Some(10_000)));
     assert_ok!(Identity::remove_secondary_keys(
         alice.origin(),
         vec![bob.acc()]
     ));
-    // Check DidRecord.
-    assert_eq!(
-        get_secondary_keys(alice.did),
-        vec![SecondaryKey::new(charlie.acc(), Permissions::default())]
-    );
+    assert_eq!(Identity::secondary_key_expiry(bob.acc()), None);
 }
 
-/// It double-checks that frozen keys are removed too.
 #[test]
-fn frozen_secondary_keys_cdd_verification_test() {
+fn temporary_permissions() {
     ExtBuilder::default()
+        .monied(true)
         .build()
-        .execute_with(&frozen_secondary_keys_cdd_verification_test_we);
+        .execute_with(&temporary_permissions_with_externalities);
 }
 
-fn frozen_secondary_keys_cdd_verification_test_we() {
-    // 0. Create identity for Alice and secondary key from Bob.
-    let alice = AccountKeyring::Alice.to_account_id();
-    let bob = AccountKeyring::Bob.to_account_id();
-    let charlie = AccountKeyring::Charlie.to_account_id();
-    TestStorage::set_payer_context(Some(alice.clone()));
-    let alice_id = register_keyring_account(AccountKeyring::Alice).unwrap();
-    TestStorage::set_payer_context(Some(charlie.clone()));
-    let _charlie_id = register_keyring_account_with_balance(AccountKeyring::Charlie, 100).unwrap();
-    assert_eq!(Balances::free_balance(charlie.clone()), 100);
-
-    // 1. Add Bob as signatory to Alice ID.
-    TestStorage::set_payer_context(Some(alice.clone()));
-    add_secondary_key(alice_id, bob.clone());
-    assert_ok!(Balances::transfer_with_memo(
-        Origin::signed(alice.clone()),
-        bob.clone().into(),
-        25_000,
-        None
-    ));
-    assert_eq!(Balances::free_balance(bob.clone()), 25_000);
-
-    // 2. Bob can transfer some funds to Charlie ID.
-    TestStorage::set_payer_context(Some(bob.clone()));
-    assert_ok!(Balances::transfer_with_memo(
-        Origin::signed(bob.clone()),
-        charlie.clone().into(),
-        1_000,
-        None
-    ));
-    assert_eq!(Balances::free_balance(charlie.clone()), 1100);
+fn temporary_permissions_with_externalities() {
+    let alice = User::new(AccountKeyring::Alice);
+    let bob = User::new_with(alice.did, AccountKeyring::Bob);
+    let charlie = User::new_with(alice.did, AccountKeyring::Charlie);
+    let perms_of = |key| match Identity::key_records(key) {
+        Some(KeyRecord::SecondaryKey(_, perms)) => Some(perms),
+        _ => None,
+    };
+    let grant = |origin, key, duration| {
+        Identity::grant_temporary_permissions(origin, key, Permissions::default(), duration)
+    };
 
-    // 3. Alice freezes her secondary keys.
-    assert_ok!(Identity::freeze_secondary_keys(Origin::signed(
-        alice.clone()
-    )));
+    add_secondary_key_with_perms(alice.did, bob.acc(), Permissions::empty());
+    add_secondary_key_with_perms(alice.did, charlie.acc(), Permissions::empty());
+    System::set_block_number(10);
 
-    // 4. Bob should NOT transfer any amount. SE is simulated.
-    // Balances::transfer_with_memo(Origin::signed(bob), charlie, 1_000, None),
-    let payer = CddHandler::get_valid_payer(
-        &RuntimeCall::Balances(balances::Call::transfer_with_memo {
-            dest: AccountKeyring::Charlie.to_account_id().into(),
-            value: 1_000,
-            memo: None,
-        }),
-        &AccountKeyring::Bob.to_account_id(),
+    assert_noop!(grant(bob.origin(), bob.acc(), 5), Error::KeyNotAllowed);
+    assert_noop!(
+        grant(alice.origin(), bob.acc(), 0),
+        Error::InvalidPermissionGrantDuration
     );
+    assert_ok!(grant(alice.origin(), bob.acc(), 5));
     assert_noop!(
-        payer,
-        InvalidTransaction::Custom(TransactionError::MissingIdentity as u8)
+        grant(alice.origin(), bob.acc(), 5),
+        Error::TemporaryPermissionsAlreadyGranted
+    );
+    assert_eq!(perms_of(bob.acc()), Some(Permissions::default()));
+    assert_eq!(
+        Identity::temporary_permission_grant(bob.acc()),
+        Some((Permissions::empty(), 15))
     );
 
-    assert_eq!(Balances::free_balance(charlie.clone()), 1100);
-
-    // 5. Alice still can make transfers.
-    assert_ok!(Balances::transfer_with_memo(
-        Origin::signed(alice.clone()),
-        charlie.clone().into(),
-        1_000,
-        None
+    // Setting the permissions explicitly ends the grant of charlie.
+    assert_ok!(grant(alice.origin(), charlie.acc(), 5));
+    let charlie_perms =
+        Permissions::from_pallet_permissions(vec![PalletPermissions::entire_pallet(
+            "Identity".into(),
+        )]);
+    assert_ok!(Identity::set_secondary_key_permissions(
+        alice.origin(),
+        charlie.acc(),
+        charlie_perms.clone(),
     ));
-    assert_eq!(Balances::free_balance(charlie.clone()), 2100);
+    assert_eq!(Identity::temporary_permission_grant(charlie.acc()), None);
 
-    // 6. Unfreeze signatory keys, and Bob should be able to transfer again.
-    assert_ok!(Identity::unfreeze_secondary_keys(Origin::signed(alice)));
-    assert_ok!(Balances::transfer_with_memo(
-        Origin::signed(bob),
-        charlie.clone().into(),
-        1_000,
-        None
-    ));
-    assert_eq!(Balances::free_balance(charlie), 3100);
+    // Nothing is restored before the grants end.
+    for _ in 0..4 {
+        next_block();
+    }
+    assert_eq!(perms_of(bob.acc()), Some(Permissions::default()));
+
+    // The scheduler restores the permissions at the end of the grant.
+    next_block();
+    assert_eq!(perms_of(bob.acc()), Some(Permissions::empty()));
+    assert_eq!(perms_of(charlie.acc()), Some(charlie_perms));
+    assert_eq!(Identity::temporary_permission_grant(bob.acc()), None);
 }
 
-fn run_add_secondary_key_with_perm_test(
-    add_key_with_perms: impl Fn(User, Permissions) -> DispatchResult,
-) {
-    let alice = User::new(AccountKeyring::Alice);
+#[test]
+fn key_usage_audit() {
+    ExtBuilder::default()
+        .monied(true)
+        .build()
+        .execute_with(&key_usage_audit_with_externalities);
+}
+
+fn key_usage_audit_with_externalities() {
+    let alice = User::new(AccountKeyring::Alice);
+    let bob = User::new_with(alice.did, AccountKeyring::Bob);
+    add_secondary_key(alice.did, bob.acc());
+
+    let call: TestCall = pallet_portfolio::Call::create_portfolio {
+        name: "name".into(),
+    }
+    .into();
+    let use_key = |user: User| {
+        with_call_metadata(call.get_call_metadata(), || {
+            PermissionsModule::ensure_call_permissions(&user.acc()).map(|_| ())
+        })
+    };
+    let key_uses = || {
+        System::events()
+            .into_iter()
+            .filter_map(|r| match r.event {
+                super::storage::EventTest::Identity(RawEvent::SecondaryKeyUsed(
+                    did,
+                    key,
+                    pallet,
+                    dispatchable,
+                )) => Some((did, key, pallet, dispatchable)),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+    };
+
+    // Without the audit trail, no event is emitted.
+    assert_ok!(use_key(bob));
+    assert!(key_uses().is_empty());
+
+    assert_noop!(
+        Identity::set_key_usage_audit(bob.origin(), true),
+        Error::KeyNotAllowed
+    );
+    assert_ok!(Identity::set_key_usage_audit(alice.origin(), true));
+    assert!(Identity::key_usage_audit(alice.did));
+
+    // Only the calls of secondary keys are recorded.
+    assert_ok!(use_key(alice));
+    assert_ok!(use_key(bob));
+    assert_eq!(
+        key_uses(),
+        vec![(
+            alice.did,
+            bob.acc(),
+            "Portfolio".into(),
+            "create_portfolio".into()
+        )]
+    );
+
+    assert_ok!(Identity::set_key_usage_audit(alice.origin(), false));
+    assert_ok!(use_key(bob));
+    assert_eq!(key_uses().len(), 1);
+}
+
+#[test]
+fn key_permissions_introspection() {
+    ExtBuilder::default()
+        .build()
+        .execute_with(key_permissions_introspection_we);
+}
+
+fn key_permissions_introspection_we() {
+    let alice = User::new(AccountKeyring::Alice);
+    let bob = User::new_with(alice.did, AccountKeyring::Bob);
+    add_secondary_key(alice.did, bob.acc());
+    let bob_perms = Permissions::from_pallet_permissions(vec![PalletPermissions::entire_pallet(
+        "Portfolio".into(),
+    )]);
+    assert_ok!(Identity::set_secondary_key_permissions(
+        alice.origin(),
+        bob.acc(),
+        bob_perms.clone(),
+    ));
+
+    let portfolio: PalletName = "Portfolio".into();
+    let create_portfolio: DispatchableName = "create_portfolio".into();
+    let add_claim: DispatchableName = "add_claim".into();
+    let can_call = |user: User, pallet: &PalletName, dispatchable: &DispatchableName| {
+        Identity::can_call(&user.acc(), pallet, dispatchable)
+    };
+
+    // Keys that aren't linked to an identity have no permissions.
+    let charlie = AccountKeyring::Charlie.to_account_id();
+    assert!(Identity::get_key_permissions(charlie.clone()).is_none());
+    assert!(!Identity::can_call(&charlie, &portfolio, &create_portfolio));
+
+    let bob_data = Identity::get_key_permissions(bob.acc()).unwrap();
+    assert_eq!(bob_data.identity, alice.did);
+    assert_eq!(bob_data.permissions, Some(bob_perms));
+    assert!(!bob_data.frozen && !bob_data.expired);
+    assert_eq!(
+        Identity::get_key_permissions(alice.acc())
+            .unwrap()
+            .permissions,
+        None
+    );
+    assert!(can_call(bob, &portfolio, &create_portfolio));
+    assert!(!can_call(bob, &"Identity".into(), &add_claim));
+    assert!(can_call(alice, &"Identity".into(), &add_claim));
+
+    // The call filters of the identity apply to all of its keys.
+    let filter = CallFilter {
+        pallet_name: portfolio.clone(),
+        dispatchable_name: Some(create_portfolio.clone()),
+    };
+    assert_ok!(Identity::add_call_filter(alice.origin(), filter.clone()));
+    assert_eq!(
+        Identity::get_key_permissions(bob.acc())
+            .unwrap()
+            .call_filters,
+        vec![filter]
+    );
+    assert!(!can_call(alice, &portfolio, &create_portfolio));
+    assert!(!can_call(bob, &portfolio, &create_portfolio));
+    assert!(can_call(bob, &portfolio, &"rename_portfolio".into()));
+
+    // Frozen secondary keys can't call anything, unlike the primary key.
+    assert_ok!(Identity::freeze_secondary_keys(alice.origin()));
+    assert!(Identity::get_key_permissions(bob.acc()).unwrap().frozen);
+    assert!(!Identity::get_key_permissions(alice.acc()).unwrap().frozen);
+    assert!(!can_call(bob, &portfolio, &"rename_portfolio".into()));
+    assert!(can_call(alice, &portfolio, &"rename_portfolio".into()));
+}
+
+#[test]
+fn key_probation() {
+    ExtBuilder::default()
+        .monied(true)
+        .build()
+        .execute_with(&key_probation_with_externalities);
+}
+
+fn key_probation_with_externalities() {
+    let alice = User::new(AccountKeyring::Alice);
+    let bob = User::new_with(alice.did, AccountKeyring::Bob);
+    let charlie = User::new_with(alice.did, AccountKeyring::Charlie);
+    System::set_block_number(10);
+
+    assert_noop!(
+        Identity::set_key_probation_period(alice.origin(), Some(0)),
+        Error::InvalidProbationPeriod
+    );
+    assert_ok!(Identity::set_key_probation_period(alice.origin(), Some(5)));
+    assert_eq!(Identity::key_probation_period(alice.did), Some(5));
+
+    // New keys are on probation until block 15.
+    add_secondary_key(alice.did, bob.acc());
+    add_secondary_key(alice.did, charlie.acc());
+    assert_eq!(Identity::probationary_key(bob.acc()), Some(15));
+    assert_eq!(Identity::probationary_key(charlie.acc()), Some(15));
+
+    assert_noop!(
+        Identity::confirm_secondary_key(bob.origin(), charlie.acc()),
+        Error::KeyNotAllowed
+    );
+    assert_ok!(Identity::confirm_secondary_key(
+        alice.origin(),
+        charlie.acc()
+    ));
+    assert_eq!(Identity::probationary_key(charlie.acc()), None);
+    assert_noop!(
+        Identity::confirm_secondary_key(alice.origin(), charlie.acc()),
+        Error::KeyNotOnProbation
+    );
+
+    // Nothing is removed before the probation ends.
+    for _ in 0..4 {
+        next_block();
+    }
+    assert!(Identity::key_records(bob.acc()).is_some());
+
+    // At the end of the probation, the scheduler only removes the unconfirmed key.
+    next_block();
+    assert_eq!(Identity::key_records(bob.acc()), None);
+    assert_eq!(Identity::probationary_key(bob.acc()), None);
+    assert!(Identity::key_records(charlie.acc()).is_some());
+
+    // Keys joining without a probation period are kept.
+    assert_ok!(Identity::set_key_probation_period(alice.origin(), None));
+    add_secondary_key(alice.did, bob.acc());
+    assert_eq!(Identity::probationary_key(bob.acc()), None);
+}
+
+/// It double-checks that frozen keys are removed too.
+#[test]
+fn remove_frozen_secondary_keys_test() {
+    ExtBuilder::default()
+        .monied(true)
+        .build()
+        .execute_with(&remove_frozen_secondary_keys_with_externalities);
+}
+
+fn remove_frozen_secondary_keys_with_externalities() {
+    let alice = User::new(AccountKeyring::Alice);
+    let bob = User::new_with(alice.did, AccountKeyring::Bob);
+    let charlie = User::new_with(alice.did, AccountKeyring::Charlie);
+
+    // Add secondary keys.
+    add_secondary_key(alice.did, bob.acc());
+    add_secondary_key(alice.did, charlie.acc());
+
+    // Freeze all secondary keys
+    assert_ok!(Identity::freeze_secondary_keys(alice.origin()));
+
+    // Remove Bob's key.
+    assert_ok!(Identity::remove_secondary_keys(
+        alice.origin(),
+        vec![bob.acc()]
+    ));
+    // Check DidRecord.
+    assert_eq!(
+        get_secondary_keys(alice.did),
+        vec![SecondaryKey::new(charlie.acc(), Permissions::default())]
+    );
+}
+
+/// It double-checks that frozen keys are removed too.
+#[test]
+fn frozen_secondary_keys_cdd_verification_test() {
+    ExtBuilder::default()
+        .build()
+        .execute_with(&frozen_secondary_keys_cdd_verification_test_we);
+}
+
+fn frozen_secondary_keys_cdd_verification_test_we() {
+    // 0. Create identity for Alice and secondary key from Bob.
+    let alice = AccountKeyring::Alice.to_account_id();
+    let bob = AccountKeyring::Bob.to_account_id();
+    let charlie = AccountKeyring::Charlie.to_account_id();
+    TestStorage::set_payer_context(Some(alice.clone()));
+    let alice_id = register_keyring_account(AccountKeyring::Alice).unwrap();
+    TestStorage::set_payer_context(Some(charlie.clone()));
+    let _charlie_id = register_keyring_account_with_balance(AccountKeyring::Charlie, 100).unwrap();
+    assert_eq!(Balances::free_balance(charlie.clone()), 100);
+
+    // 1. Add Bob as signatory to Alice ID.
+    TestStorage::set_payer_context(Some(alice.clone()));
+    add_secondary_key(alice_id, bob.clone());
+    assert_ok!(Balances::transfer_with_memo(
+        Origin::signed(alice.clone()),
+        bob.clone().into(),
+        25_000,
+        None
+    ));
+    assert_eq!(Balances::free_balance(bob.clone()), 25_000);
+
+    // 2. Bob can transfer some funds to Charlie ID.
+    TestStorage::set_payer_context(Some(bob.clone()));
+    assert_ok!(Balances::transfer_with_memo(
+        Origin::signed(bob.clone()),
+        charlie.clone().into(),
+        1_000,
+        None
+    ));
+    assert_eq!(Balances::free_balance(charlie.clone()), 1100);
+
+    // 3. Alice freezes her secondary keys.
+    assert_ok!(Identity::freeze_secondary_keys(Origin::signed(
+        alice.clone()
+    )));
+
+    // 4. Bob should NOT transfer any amount. SE is simulated.
+    // Balances::transfer_with_memo(Origin::signed(bob), charlie, 1_000, None),
+    let payer = CddHandler::get_valid_payer(
+        &RuntimeCall::Balances(balances::Call::transfer_with_memo {
+            dest: AccountKeyring::Charlie.to_account_id().into(),
+            value: 1_000,
+            memo: None,
+        }),
+        &AccountKeyring::Bob.to_account_id(),
+    );
+    assert_noop!(
+        payer,
+        InvalidTransaction::Custom(TransactionError::MissingIdentity as u8)
+    );
+
+    assert_eq!(Balances::free_balance(charlie.clone()), 1100);
+
+    // 5. Alice still can make transfers.
+    assert_ok!(Balances::transfer_with_memo(
+        Origin::signed(alice.clone()),
+        charlie.clone().into(),
+        1_000,
+        None
+    ));
+    assert_eq!(Balances::free_balance(charlie.clone()), 2100);
+
+    // 6. Unfreeze signatory keys, and Bob should be able to transfer again.
+    assert_ok!(Identity::unfreeze_secondary_keys(Origin::signed(alice)));
+    assert_ok!(Balances::transfer_with_memo(
+        Origin::signed(bob),
+        charlie.clone().into(),
+        1_000,
+        None
+    ));
+    assert_eq!(Balances::free_balance(charlie), 3100);
+}
+
+fn run_add_secondary_key_with_perm_test(
+    add_key_with_perms: impl Fn(User, Permissions) -> DispatchResult,
+) {
+    let alice = User::new(AccountKeyring::Alice);
 
     let perm1 = Permissions::empty();
     let perm2 = Permissions::from_pallet_permissions(vec![PalletPermissions::entire_pallet(
@@ -2145,57 +2799,869 @@ fn invalid_custom_claim_type() {
 }
 
 #[test]
-fn cdd_register_did_events() {
-    ExtBuilder::default()
-        .cdd_providers(vec![AccountKeyring::Eve.to_account_id()])
-        .build()
-        .execute_with(|| {
-            System::set_block_number(1);
-            // Register an Identity for alice with two secundary keys
-            let cdd_provider = Origin::signed(AccountKeyring::Eve.to_account_id());
-            let alice_account_id = AccountKeyring::Alice.to_account_id();
-            let alice_secundary_keys = vec![
-                SecondaryKey::from_account_id(AccountKeyring::Dave.to_account_id()),
-                SecondaryKey::from_account_id(AccountKeyring::Charlie.to_account_id()),
-            ];
-            assert_ok!(Identity::cdd_register_did(
-                cdd_provider,
-                alice_account_id.clone(),
-                alice_secundary_keys.clone()
-            ));
-            let alice_did = get_identity_id(AccountKeyring::Alice).unwrap();
-            // Make sure one Authorization event was sent for each secundary key
-            let mut system_events = System::events();
-            assert_eq!(
-                system_events.pop().unwrap().event,
-                super::storage::EventTest::Identity(RawEvent::AuthorizationAdded(
-                    alice_did,
-                    None,
-                    Some(AccountKeyring::Charlie.to_account_id()),
-                    Identity::multi_purpose_nonce(),
-                    AuthorizationData::JoinIdentity(alice_secundary_keys[1].permissions.clone()),
-                    None,
-                ))
-            );
-            assert_eq!(
-                system_events.pop().unwrap().event,
-                super::storage::EventTest::Identity(RawEvent::AuthorizationAdded(
-                    alice_did,
-                    None,
-                    Some(AccountKeyring::Dave.to_account_id()),
-                    Identity::multi_purpose_nonce() - 1,
-                    AuthorizationData::JoinIdentity(alice_secundary_keys[0].permissions.clone()),
-                    None,
-                ))
-            );
-            // Make sure a Did Created event was sent
-            assert_eq!(
-                system_events.pop().unwrap().event,
-                super::storage::EventTest::Identity(RawEvent::DidCreated(
-                    alice_did,
-                    alice_account_id,
-                    alice_secundary_keys.clone()
-                ))
+fn custom_claim_schemas() {
+    ExtBuilder::default().build().execute_with(|| {
+        let alice = User::new(AccountKeyring::Alice);
+        let bob = User::new(AccountKeyring::Bob);
+        let field = |name: &str, field_type| CustomClaimField {
+            name: name.into(),
+            field_type,
+        };
+        let register = |fields| {
+            Identity::register_custom_claim_type_with_schema(
+                alice.origin(),
+                b"accreditation".to_vec(),
+                CustomClaimSchema { fields },
+            )
+        };
+
+        assert_noop!(
+            register(vec![
+                field("level", CustomClaimFieldType::U32),
+                field("level", CustomClaimFieldType::Bool),
+            ]),
+            Error::InvalidCustomClaimSchema
+        );
+        assert_noop!(
+            register(vec![field("notes", CustomClaimFieldType::Text(u32::MAX))]),
+            BaseError::TooLong
+        );
+        let fields = vec![
+            field("level", CustomClaimFieldType::U32),
+            field("professional", CustomClaimFieldType::Bool),
+            field("regulator", CustomClaimFieldType::Text(8)),
+        ];
+        assert_ok!(register(fields.clone()));
+        let id = Identity::custom_claim_id_seq();
+        assert_eq!(Identity::custom_claim_schema(id).unwrap().fields, fields);
+
+        let add_claim = |claim| Identity::add_claim(alice.origin(), bob.did, claim, None);
+        let with_payload = |payload: Vec<u8>| Claim::CustomWithPayload(id, None, payload);
+
+        // Payloads must be the encoding of the fields of the schema, in order.
+        assert_noop!(
+            add_claim(with_payload((1u32, true).encode())),
+            Error::CustomClaimPayloadMismatch
+        );
+        assert_noop!(
+            add_claim(with_payload((1u32, true, "too long text").encode())),
+            Error::CustomClaimPayloadMismatch
+        );
+        assert_noop!(
+            add_claim(with_payload((1u32, true, "SEC", 0u8).encode())),
+            Error::CustomClaimPayloadMismatch
+        );
+        assert_noop!(
+            add_claim(Claim::Custom(id, None)),
+            Error::CustomClaimPayloadMismatch
+        );
+        assert_ok!(add_claim(with_payload((1u32, true, "SEC").encode())));
+
+        // Payloads can only be added to the claims of types with a schema.
+        assert_ok!(Identity::register_custom_claim_type(
+            alice.origin(),
+            b"no schema".to_vec()
+        ));
+        let no_schema_id = Identity::custom_claim_id_seq();
+        assert_noop!(
+            add_claim(Claim::CustomWithPayload(no_schema_id, None, vec![1])),
+            Error::CustomClaimSchemaNotFound
+        );
+        assert_ok!(add_claim(Claim::Custom(no_schema_id, None)));
+    });
+}
+
+#[test]
+fn state_counters_track_claims_and_authorizations() {
+    ExtBuilder::default().build().execute_with(|| {
+        let alice = User::new(AccountKeyring::Alice);
+        let bob = User::new(AccountKeyring::Bob);
+        let claims = Identity::claim_count();
+        let auths = Identity::authorization_count();
+
+        let claim = Claim::Affiliate(Scope::Identity(alice.did));
+        assert_ok!(Identity::add_claim(
+            alice.origin(),
+            bob.did,
+            claim.clone(),
+            None
+        ));
+        assert_eq!(Identity::claim_count(), claims + 1);
+        // Re-issuing the same claim replaces it.
+        assert_ok!(Identity::add_claim(
+            alice.origin(),
+            bob.did,
+            claim.clone(),
+            None
+        ));
+        assert_eq!(Identity::claim_count(), claims + 1);
+        assert_ok!(Identity::revoke_claim(alice.origin(), bob.did, claim));
+        assert_eq!(Identity::claim_count(), claims);
+
+        let target = Signatory::Identity(bob.did);
+        assert_ok!(Identity::add_authorization(
+            alice.origin(),
+            target.clone(),
+            AuthorizationData::JoinIdentity(Permissions::default()),
+            None
+        ));
+        assert_eq!(Identity::authorization_count(), auths + 1);
+        let auth_id = get_last_auth_id(&target);
+        assert_ok!(Identity::remove_authorization(
+            alice.origin(),
+            target,
+            auth_id,
+            false
+        ));
+        assert_eq!(Identity::authorization_count(), auths);
+    });
+}
+
+#[test]
+fn authorization_bulk_management() {
+    ExtBuilder::default().build().execute_with(|| {
+        let alice = User::new(AccountKeyring::Alice);
+        let bob = User::new(AccountKeyring::Bob);
+        let charlie = User::new(AccountKeyring::Charlie);
+        let alice_sig = Signatory::Identity(alice.did);
+        let add_auth = |from: User, data, expiry| {
+            assert_ok!(Identity::add_authorization(
+                from.origin(),
+                alice_sig.clone(),
+                data,
+                expiry
+            ));
+            get_last_auth_id(&alice_sig)
+        };
+        let auths_of_alice = || {
+            let mut ids: Vec<_> =
+                pallet_identity::Authorizations::<TestStorage>::iter_prefix_values(&alice_sig)
+                    .map(|auth| auth.auth_id)
+                    .collect();
+            ids.sort();
+            ids
+        };
+
+        // Only the expired authorizations are removed, by anyone.
+        set_timestamp(1_000);
+        let join = || AuthorizationData::JoinIdentity(Permissions::default());
+        let expired = [
+            add_auth(bob, join(), Some(2_000)),
+            add_auth(bob, join(), Some(3_000)),
+        ];
+        set_timestamp(5_000);
+        let remove = |start_after, max_scanned| {
+            let pays_fee = Identity::remove_expired_authorizations(
+                charlie.origin(),
+                alice_sig.clone(),
+                start_after,
+                max_scanned,
+            )
+            .unwrap()
+            .pays_fee;
+            let last_scanned = match System::events().pop().unwrap().event {
+                super::storage::EventTest::Identity(RawEvent::ExpiredAuthorizationsRemoved(
+                    ..,
+                    last_scanned,
+                )) => last_scanned,
+                _ => panic!("no removal event"),
+            };
+            (pays_fee, last_scanned)
+        };
+        // The scan is bounded, and resumes after the last scanned authorization.
+        // A scan that removed everything it went through is free.
+        let (pays_fee, last_scanned) = remove(None, 1);
+        assert_eq!(pays_fee, Pays::No);
+        assert!(last_scanned.is_some());
+        assert_eq!(auths_of_alice().len(), 1);
+        assert_eq!(remove(last_scanned, 10), (Pays::No, None));
+        assert!(auths_of_alice().is_empty());
+        // Scanning authorizations that aren't expired isn't refunded.
+        let pending = add_auth(bob, join(), None);
+        assert_eq!(remove(None, 10), (Pays::Yes, None));
+        assert_eq!(auths_of_alice(), vec![pending]);
+        assert!(expired.iter().all(|id| !auths_of_alice().contains(id)));
+
+        // Authorizations are accepted in a batch, all or none.
+        let custody = |user: User| {
+            AuthorizationData::PortfolioCustody(PortfolioId::default_portfolio(user.did))
+        };
+        let bob_custody = add_auth(bob, custody(bob), None);
+        let charlie_custody = add_auth(charlie, custody(charlie), None);
+        assert_noop!(
+            Identity::consume_authorizations_batch(alice.origin(), vec![bob_custody, pending]),
+            Error::UnsupportedBatchAuthorization
+        );
+        assert_noop!(
+            Identity::consume_authorizations_batch(
+                alice.origin(),
+                vec![bob_custody; MAX_AUTHORIZATIONS_PER_BATCH as usize + 1]
+            ),
+            BaseError::TooLong
+        );
+        assert_ok!(Identity::consume_authorizations_batch(
+            alice.origin(),
+            vec![bob_custody, charlie_custody]
+        ));
+        let custodian = |user: User| {
+            pallet_portfolio::Module::<TestStorage>::portfolio_custodian(
+                PortfolioId::default_portfolio(user.did),
+            )
+        };
+        assert_eq!(custodian(bob), Some(alice.did));
+        assert_eq!(custodian(charlie), Some(alice.did));
+        assert_eq!(auths_of_alice(), vec![pending]);
+    });
+}
+
+#[test]
+fn cdd_register_did_events() {
+    ExtBuilder::default()
+        .cdd_providers(vec![AccountKeyring::Eve.to_account_id()])
+        .build()
+        .execute_with(|| {
+            System::set_block_number(1);
+            // Register an Identity for alice with two secundary keys
+            let cdd_provider = Origin::signed(AccountKeyring::Eve.to_account_id());
+            let alice_account_id = AccountKeyring::Alice.to_account_id();
+            let alice_secundary_keys = vec![
+                SecondaryKey::from_account_id(AccountKeyring::Dave.to_account_id()),
+                SecondaryKey::from_account_id(AccountKeyring::Charlie.to_account_id()),
+            ];
+            assert_ok!(Identity::cdd_register_did(
+                cdd_provider,
+                alice_account_id.clone(),
+                alice_secundary_keys.clone()
+            ));
+            let alice_did = get_identity_id(AccountKeyring::Alice).unwrap();
+            // Make sure one Authorization event was sent for each secundary key
+            let mut system_events = System::events();
+            assert_eq!(
+                system_events.pop().unwrap().event,
+                super::storage::EventTest::Identity(RawEvent::AuthorizationAdded(
+                    alice_did,
+                    None,
+                    Some(AccountKeyring::Charlie.to_account_id()),
+                    Identity::multi_purpose_nonce(),
+                    AuthorizationData::JoinIdentity(alice_secundary_keys[1].permissions.clone()),
+                    None,
+                ))
+            );
+            assert_eq!(
+                system_events.pop().unwrap().event,
+                super::storage::EventTest::Identity(RawEvent::AuthorizationAdded(
+                    alice_did,
+                    None,
+                    Some(AccountKeyring::Dave.to_account_id()),
+                    Identity::multi_purpose_nonce() - 1,
+                    AuthorizationData::JoinIdentity(alice_secundary_keys[0].permissions.clone()),
+                    None,
+                ))
+            );
+            // Make sure a Did Created event was sent
+            assert_eq!(
+                system_events.pop().unwrap().event,
+                super::storage::EventTest::Identity(RawEvent::DidCreated(
+                    alice_did,
+                    alice_account_id,
+                    alice_secundary_keys.clone()
+                ))
+            );
+        });
+}
+
+#[test]
+fn key_recovery_by_cdd_provider() {
+    ExtBuilder::default()
+        .monied(true)
+        .cdd_providers(vec![AccountKeyring::Eve.to_account_id()])
+        .build()
+        .execute_with(key_recovery_by_cdd_provider_we);
+}
+
+fn key_recovery_by_cdd_provider_we() {
+    let alice = User::new(AccountKeyring::Alice);
+    let bob = User::new(AccountKeyring::Bob);
+    let eve = User::existing(AccountKeyring::Eve);
+    let charlie = AccountKeyring::Charlie.to_account_id();
+    let challenge_period = <TestStorage as IdentityConfig>::KeyRecoveryChallengePeriod::get();
+    set_timestamp(1);
+
+    let initiate =
+        |caller: User| Identity::initiate_key_recovery(caller.origin(), alice.did, charlie.clone());
+
+    // Only pre-authorized CDD providers can initiate a recovery.
+    assert_noop!(
+        Identity::set_key_recovery_delegate(alice.origin(), Some(bob.did)),
+        Error::UnAuthorizedCddProvider
+    );
+    assert_noop!(initiate(eve), Error::KeyRecoveryNotAuthorized);
+    assert_ok!(Identity::set_key_recovery_delegate(
+        alice.origin(),
+        Some(eve.did)
+    ));
+    assert_noop!(initiate(bob), Error::UnAuthorizedCddProvider);
+
+    // The recovery can be cancelled during the challenge period.
+    assert_ok!(initiate(eve));
+    assert_noop!(initiate(eve), Error::KeyRecoveryAlreadyPending);
+    assert_noop!(
+        Identity::cancel_key_recovery(bob.origin(), alice.did),
+        Error::Unauthorized
+    );
+    assert_ok!(Identity::cancel_key_recovery(alice.origin(), alice.did));
+    assert_eq!(Identity::pending_key_recovery(alice.did), None);
+
+    // The recovery can only be executed after the challenge period.
+    assert_ok!(initiate(eve));
+    assert_noop!(
+        Identity::execute_key_recovery(eve.origin(), alice.did),
+        Error::KeyRecoveryChallengePeriodNotOver
+    );
+    set_timestamp(1 + challenge_period);
+    assert_ok!(Identity::execute_key_recovery(eve.origin(), alice.did));
+    assert_eq!(get_primary_key(alice.did), charlie);
+    assert_ok!(Identity::ensure_key_did_unlinked(&alice.acc()));
+    assert_eq!(Identity::pending_key_recovery(alice.did), None);
+}
+
+#[test]
+fn key_recovery_cancelled_by_delegate_change() {
+    ExtBuilder::default()
+        .monied(true)
+        .cdd_providers(vec![AccountKeyring::Eve.to_account_id()])
+        .build()
+        .execute_with(key_recovery_cancelled_by_delegate_change_we);
+}
+
+fn key_recovery_cancelled_by_delegate_change_we() {
+    let alice = User::new(AccountKeyring::Alice);
+    let eve = User::existing(AccountKeyring::Eve);
+    let charlie = AccountKeyring::Charlie.to_account_id();
+
+    assert_ok!(Identity::set_key_recovery_delegate(
+        alice.origin(),
+        Some(eve.did)
+    ));
+    assert_ok!(Identity::initiate_key_recovery(
+        eve.origin(),
+        alice.did,
+        charlie
+    ));
+
+    // Revoking the consent cancels the pending recovery.
+    assert_ok!(Identity::set_key_recovery_delegate(alice.origin(), None));
+    assert_eq!(Identity::key_recovery_delegate(alice.did), None);
+    assert_eq!(Identity::pending_key_recovery(alice.did), None);
+    assert_noop!(
+        Identity::execute_key_recovery(eve.origin(), alice.did),
+        Error::NoPendingKeyRecovery
+    );
+}
+
+#[test]
+fn social_key_recovery() {
+    ExtBuilder::default()
+        .monied(true)
+        .build()
+        .execute_with(social_key_recovery_we);
+}
+
+fn social_key_recovery_we() {
+    let alice = User::new(AccountKeyring::Alice);
+    let bob = User::new(AccountKeyring::Bob);
+    let charlie = User::new(AccountKeyring::Charlie);
+    let dave = User::new(AccountKeyring::Dave);
+    let ferdie = User::new(AccountKeyring::Ferdie);
+    let eve_key = AccountKeyring::Eve.to_account_id();
+    let other_key = AccountKeyring::Two.to_account_id();
+    let challenge_period = <TestStorage as IdentityConfig>::KeyRecoveryChallengePeriod::get();
+    set_timestamp(1);
+
+    let set_guardians = |guardians: Vec<IdentityId>, threshold| {
+        Identity::set_recovery_guardians(
+            alice.origin(),
+            Some(RecoveryGuardians {
+                guardians,
+                threshold,
+            }),
+        )
+    };
+    let approve = |guardian: User, key: AccountId| {
+        Identity::approve_social_recovery(guardian.origin(), alice.did, key)
+    };
+    let execute = || Identity::execute_social_recovery(Origin::signed(eve_key.clone()), alice.did);
+
+    // The threshold must be reachable by distinct guardians other than the identity itself.
+    for (guardians, threshold) in [
+        (vec![bob.did, charlie.did], 0),
+        (vec![bob.did, charlie.did], 3),
+        (vec![bob.did, alice.did], 1),
+        (vec![bob.did, bob.did], 1),
+    ] {
+        assert_noop!(
+            set_guardians(guardians, threshold),
+            Error::InvalidRecoveryGuardians
+        );
+    }
+    assert_ok!(set_guardians(vec![bob.did, charlie.did, dave.did], 2));
+
+    // The challenge period starts once enough guardians approved the same key.
+    assert_noop!(
+        approve(ferdie, eve_key.clone()),
+        Error::NotARecoveryGuardian
+    );
+    assert_ok!(approve(bob, eve_key.clone()));
+    assert_noop!(
+        approve(bob, eve_key.clone()),
+        Error::SocialRecoveryAlreadyApproved
+    );
+    // A guardian approving another key withdraws its approval of the previous one.
+    assert_ok!(approve(bob, other_key.clone()));
+    assert_ok!(approve(charlie, eve_key.clone()));
+    assert_noop!(execute(), Error::SocialRecoveryThresholdNotReached);
+    assert_ok!(approve(bob, eve_key.clone()));
+    let recovery = Identity::pending_social_recovery(alice.did).unwrap();
+    assert_eq!(
+        recovery.approvals,
+        vec![(charlie.did, eve_key.clone()), (bob.did, eve_key.clone())]
+    );
+    assert_eq!(
+        recovery.executable,
+        Some((eve_key.clone(), 1 + challenge_period))
+    );
+    assert_noop!(
+        approve(dave, other_key.clone()),
+        Error::SocialRecoveryAlreadyInitiated
+    );
+
+    // During the challenge period, the recovery is cancelled by the guardians' threshold.
+    assert_noop!(execute(), Error::KeyRecoveryChallengePeriodNotOver);
+    assert_noop!(
+        Identity::cancel_social_recovery(ferdie.origin(), alice.did),
+        Error::Unauthorized
+    );
+    assert_ok!(Identity::cancel_social_recovery(dave.origin(), alice.did));
+    assert_noop!(
+        Identity::cancel_social_recovery(dave.origin(), alice.did),
+        Error::SocialRecoveryCancellationAlreadyApproved
+    );
+    assert_eq!(
+        Identity::pending_social_recovery(alice.did)
+            .unwrap()
+            .cancellations,
+        vec![dave.did]
+    );
+    assert_ok!(Identity::cancel_social_recovery(bob.origin(), alice.did));
+    assert_eq!(Identity::pending_social_recovery(alice.did), None);
+
+    // Or by the identity itself.
+    assert_ok!(approve(dave, eve_key.clone()));
+    assert_ok!(Identity::cancel_social_recovery(alice.origin(), alice.did));
+    assert_eq!(Identity::pending_social_recovery(alice.did), None);
+
+    // Only the new key can execute the recovery, after the challenge period.
+    assert_ok!(approve(dave, eve_key.clone()));
+    assert_ok!(approve(charlie, eve_key.clone()));
+    set_timestamp(1 + challenge_period);
+    assert_noop!(
+        Identity::execute_social_recovery(bob.origin(), alice.did),
+        Error::Unauthorized
+    );
+    assert_ok!(execute());
+    assert_eq!(get_primary_key(alice.did), eve_key);
+    assert_ok!(Identity::ensure_key_did_unlinked(&alice.acc()));
+    assert_eq!(Identity::pending_social_recovery(alice.did), None);
+}
+
+#[test]
+fn child_identities() {
+    ExtBuilder::default()
+        .monied(true)
+        .build()
+        .execute_with(child_identities_we);
+}
+
+fn child_identities_we() {
+    let alice = User::new(AccountKeyring::Alice);
+    let bob = User::new(AccountKeyring::Bob);
+    let dave = User::new(AccountKeyring::Dave);
+    let charlie = User::new_with(bob.did, AccountKeyring::Charlie);
+    let scope = Scope::from(IdentityId::from(0));
+    let fetch =
+        |did| Identity::fetch_claim(did, ClaimType::Accredited, dave.did, Some(scope.clone()));
+    add_secondary_key(bob.did, charlie.acc());
+    assert_ok!(Identity::add_claim(
+        dave.origin(),
+        alice.did,
+        Claim::Accredited(scope.clone()),
+        None,
+    ));
+
+    // The child must approve its parent first.
+    assert_noop!(
+        Identity::link_child_identity(alice.origin(), bob.did, true),
+        Error::ParentIdentityNotApproved
+    );
+    assert_noop!(
+        Identity::approve_parent_identity(bob.origin(), Some(bob.did)),
+        Error::InvalidParentIdentity
+    );
+    assert_ok!(Identity::approve_parent_identity(
+        bob.origin(),
+        Some(alice.did)
+    ));
+    assert_ok!(Identity::link_child_identity(alice.origin(), bob.did, true));
+    assert_eq!(Identity::parent_did(bob.did), Some(alice.did));
+    assert_eq!(Identity::parent_identity_approval(bob.did), None);
+
+    // Child identities can't have children.
+    assert_ok!(Identity::approve_parent_identity(
+        dave.origin(),
+        Some(bob.did)
+    ));
+    assert_noop!(
+        Identity::link_child_identity(bob.origin(), dave.did, false),
+        Error::NestedChildIdentity
+    );
+
+    // The child inherits the claims of its parent.
+    assert!(fetch(alice.did).is_some());
+    assert!(fetch(bob.did).is_some());
+
+    // Only the parent can administer the secondary keys of the child.
+    assert_noop!(
+        Identity::set_child_secondary_keys_frozen(dave.origin(), bob.did, true),
+        Error::NotParentIdentity
+    );
+    assert_ok!(Identity::set_child_secondary_key_permissions(
+        alice.origin(),
+        bob.did,
+        charlie.acc(),
+        Permissions::empty()
+    ));
+    assert_eq!(
+        Identity::key_records(charlie.acc()),
+        Some(KeyRecord::SecondaryKey(bob.did, Permissions::empty()))
+    );
+    assert_ok!(Identity::set_child_secondary_keys_frozen(
+        alice.origin(),
+        bob.did,
+        true
+    ));
+    assert!(Identity::is_did_frozen(bob.did));
+    assert_ok!(Identity::remove_child_secondary_keys(
+        alice.origin(),
+        bob.did,
+        vec![charlie.acc()]
+    ));
+    assert_eq!(Identity::key_records(charlie.acc()), None);
+
+    // The child can leave its parent, and its inherited claims.
+    assert_noop!(
+        Identity::unlink_child_identity(dave.origin(), bob.did),
+        Error::NotParentIdentity
+    );
+    assert_ok!(Identity::unlink_child_identity(bob.origin(), bob.did));
+    assert_eq!(Identity::parent_did(bob.did), None);
+    assert!(fetch(bob.did).is_none());
+
+    // A secondary key of the parent can become the primary key of a new child.
+    add_secondary_key(alice.did, charlie.acc());
+    assert_ok!(Identity::create_child_identity(
+        alice.origin(),
+        charlie.acc(),
+        true
+    ));
+    let child = Identity::get_identity(&charlie.acc()).unwrap();
+    assert_ne!(child, alice.did);
+    assert_eq!(get_primary_key(child), charlie.acc());
+    assert_eq!(Identity::parent_did(child), Some(alice.did));
+    assert!(fetch(child).is_some());
+    // The new child must be verified by a CDD provider itself.
+    assert!(Identity::has_valid_cdd(alice.did));
+    assert!(!Identity::has_valid_cdd(child));
+}
+
+#[test]
+fn claim_issuance_rate_limits() {
+    ExtBuilder::default()
+        .build()
+        .execute_with(claim_issuance_rate_limits_we);
+}
+
+fn claim_issuance_rate_limits_we() {
+    let alice = User::new(AccountKeyring::Alice);
+    let bob = User::new(AccountKeyring::Bob);
+    let era_length = 1_000;
+    let add = |issuer: User, id: u128| {
+        Identity::add_claim(
+            issuer.origin(),
+            alice.did,
+            Claim::Accredited(Scope::from(IdentityId::from(id))),
+            None,
+        )
+    };
+    set_timestamp(1);
+
+    // Only governance can set the limits.
+    assert_noop!(
+        Identity::set_claim_issuance_limit(alice.origin(), Some(2), era_length),
+        DispatchError::BadOrigin
+    );
+    assert_ok!(Identity::set_claim_issuance_limit(
+        Origin::root(),
+        Some(2),
+        era_length
+    ));
+
+    // The default limit applies to every issuer, per era.
+    assert_ok!(add(alice, 1));
+    assert_ok!(add(alice, 2));
+    assert_noop!(add(alice, 3), Error::ClaimIssuanceLimitReached);
+    assert_ok!(add(bob, 1));
+    set_timestamp(1 + era_length);
+    assert_ok!(add(alice, 3));
+
+    // Governance can override the limit of a single issuer.
+    assert_ok!(Identity::set_issuer_claim_issuance_limit(
+        Origin::root(),
+        bob.did,
+        Some(0)
+    ));
+    assert_noop!(add(bob, 2), Error::ClaimIssuanceLimitReached);
+    assert_ok!(Identity::set_issuer_claim_issuance_limit(
+        Origin::root(),
+        alice.did,
+        Some(5)
+    ));
+    assert_ok!(add(alice, 4));
+    assert_ok!(add(alice, 5));
+    assert_eq!(Identity::claims_issued(alice.did).1, 3);
+
+    // Removing the override restores the default limit.
+    assert_ok!(Identity::set_issuer_claim_issuance_limit(
+        Origin::root(),
+        bob.did,
+        None
+    ));
+    assert_ok!(add(bob, 2));
+}
+
+#[test]
+fn self_imposed_call_filters() {
+    ExtBuilder::default()
+        .build()
+        .execute_with(self_imposed_call_filters_we);
+}
+
+fn self_imposed_call_filters_we() {
+    let alice = User::new(AccountKeyring::Alice);
+    let bob = User::new(AccountKeyring::Bob);
+    let removal_delay = <TestStorage as IdentityConfig>::CallFilterRemovalDelay::get();
+    set_timestamp(1);
+
+    let filter = CallFilter {
+        pallet_name: "Portfolio".into(),
+        dispatchable_name: Some("create_portfolio".into()),
+    };
+    let call: TestCall = pallet_portfolio::Call::create_portfolio {
+        name: "name".into(),
+    }
+    .into();
+    let validate = |user: User, call: &TestCall| {
+        StoreCallMetadata::new().validate(&user.acc(), call, &call.get_dispatch_info(), 0)
+    };
+
+    assert_ok!(Identity::add_call_filter(alice.origin(), filter.clone()));
+    assert_noop!(
+        Identity::add_call_filter(alice.origin(), filter.clone()),
+        Error::CallFilterAlreadyAdded
+    );
+
+    // The filtered call is rejected for the identity that added the filter only.
+    assert_eq!(validate(alice, &call), Err(InvalidTransaction::Call.into()));
+    assert_ok!(validate(bob, &call));
+    // Nested calls are rejected by the permission checks.
+    assert_eq!(
+        with_call_metadata(call.get_call_metadata(), || {
+            PermissionsModule::ensure_call_permissions(&alice.acc()).map(|_| ())
+        }),
+        Err(PermissionsError::UnauthorizedCaller.into())
+    );
+    // Calls nested in batches and multisig proposals are rejected by the base call filter,
+    // using the identity in the context.
+    TestStorage::set_current_identity(&alice.did);
+    assert!(!IdentityCallFilter::<TestStorage>::contains(&call));
+    assert_noop!(
+        call.clone().dispatch(alice.origin()),
+        frame_system::Error::<TestStorage>::CallFiltered
+    );
+    TestStorage::set_current_identity(&bob.did);
+    assert!(IdentityCallFilter::<TestStorage>::contains(&call));
+    TestStorage::clear_context();
+
+    // Filtering all the calls of the identity pallet doesn't prevent the removal of filters.
+    let identity_filter = CallFilter {
+        pallet_name: "Identity".into(),
+        dispatchable_name: None,
+    };
+    assert_ok!(Identity::add_call_filter(
+        alice.origin(),
+        identity_filter.clone()
+    ));
+    let remove_call: TestCall = pallet_identity::Call::remove_call_filter {
+        filter: filter.clone(),
+    }
+    .into();
+    assert_ok!(validate(alice, &remove_call));
+
+    // The filter can only be removed once its removal delay is over.
+    assert_noop!(
+        Identity::remove_call_filter(alice.origin(), filter.clone()),
+        Error::CallFilterRemovalNotRequested
+    );
+    assert_ok!(Identity::request_call_filter_removal(
+        alice.origin(),
+        filter.clone()
+    ));
+    assert_noop!(
+        Identity::request_call_filter_removal(alice.origin(), filter.clone()),
+        Error::CallFilterRemovalAlreadyRequested
+    );
+    assert_noop!(
+        Identity::remove_call_filter(alice.origin(), filter.clone()),
+        Error::CallFilterRemovalDelayNotOver
+    );
+    set_timestamp(1 + removal_delay);
+    assert_ok!(Identity::remove_call_filter(alice.origin(), filter.clone()));
+    assert!(!Identity::has_call_filter(alice.did, filter));
+    assert_ok!(validate(alice, &call));
+}
+
+#[test]
+fn agreement_consents() {
+    ExtBuilder::default()
+        .build()
+        .execute_with(agreement_consents_we);
+}
+
+fn agreement_consents_we() {
+    let alice = User::new(AccountKeyring::Alice);
+    let bob = User::new(AccountKeyring::Bob);
+    let id = AgreementId(1);
+    set_timestamp(10);
+
+    // Alice publishes an agreement, which gets her first agreement id.
+    assert_ok!(Identity::publish_agreement(
+        alice.origin(),
+        H256::repeat_byte(1)
+    ));
+    assert_eq!(Identity::agreement_counter(alice.did), id);
+    assert_noop!(
+        Identity::give_consent(bob.origin(), alice.did, AgreementId(2), 1),
+        Error::AgreementNotFound
+    );
+    assert_noop!(
+        Identity::give_consent(bob.origin(), alice.did, id, 2),
+        Error::AgreementVersionMismatch
+    );
+
+    // Bob consents to the first version.
+    assert_ok!(Identity::give_consent(bob.origin(), alice.did, id, 1));
+    assert_eq!(
+        Identity::agreement_consent(bob.did, (alice.did, id)),
+        Some(AgreementConsent {
+            hash: H256::repeat_byte(1),
+            version: 1,
+            given_at: 10,
+        })
+    );
+    assert!(Identity::has_consented(bob.did, alice.did, id));
+
+    // A new version is no longer covered by the consent to the first one.
+    assert_noop!(
+        Identity::update_agreement(bob.origin(), id, H256::repeat_byte(2)),
+        Error::AgreementNotFound
+    );
+    assert_ok!(Identity::update_agreement(
+        alice.origin(),
+        id,
+        H256::repeat_byte(2)
+    ));
+    assert_eq!(
+        Identity::agreement(alice.did, id).map(|a| a.version),
+        Some(2)
+    );
+    assert!(!Identity::has_consented(bob.did, alice.did, id));
+    assert_ok!(Identity::give_consent(bob.origin(), alice.did, id, 2));
+    assert!(Identity::has_consented(bob.did, alice.did, id));
+
+    // Bob revokes the consent.
+    assert_ok!(Identity::revoke_consent(bob.origin(), alice.did, id));
+    assert!(!Identity::has_consented(bob.did, alice.did, id));
+    assert_noop!(
+        Identity::revoke_consent(bob.origin(), alice.did, id),
+        Error::ConsentNotFound
+    );
+}
+
+#[test]
+fn did_status_details() {
+    ExtBuilder::default()
+        .cdd_providers(vec![AccountKeyring::Eve.to_account_id()])
+        .build()
+        .execute_with(|| {
+            let eve = Origin::signed(AccountKeyring::Eve.to_account_id());
+            let eve_did = get_identity_id(AccountKeyring::Eve).unwrap();
+            let (_, charlie) =
+                make_account_without_cdd(AccountKeyring::Charlie.to_account_id()).unwrap();
+            let unknown = IdentityId::from(999);
+            let details = |did| Identity::get_did_status_details(vec![did]).pop().unwrap();
+
+            assert_eq!(
+                details(unknown),
+                DidStatusDetails {
+                    status: DidStatus::Unknown,
+                    cdd: None
+                }
+            );
+            assert_eq!(
+                details(charlie),
+                DidStatusDetails {
+                    status: DidStatus::Exists,
+                    cdd: None
+                }
+            );
+
+            // The details of the valid CDD claim are given along with the status.
+            set_timestamp(1_000);
+            assert_ok!(Identity::add_claim(
+                eve,
+                charlie,
+                Claim::CustomerDueDiligence(create_cdd_id_and_investor_uid(charlie).0),
+                Some(5_000)
+            ));
+            assert_eq!(
+                details(charlie),
+                DidStatusDetails {
+                    status: DidStatus::CddVerified,
+                    cdd: Some(CddClaimDetails {
+                        issuer: eve_did,
+                        issuance_date: 1_000,
+                        expiry: Some(5_000),
+                    })
+                }
+            );
+
+            // Once the claim has expired, the DID only exists.
+            set_timestamp(5_001);
+            assert_eq!(
+                details(charlie),
+                DidStatusDetails {
+                    status: DidStatus::Exists,
+                    cdd: None
+                }
             );
         });
 }
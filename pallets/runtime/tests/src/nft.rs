@@ -1,8 +1,12 @@
 use chrono::prelude::Utc;
+use frame_support::traits::OnInitialize;
 use frame_support::{assert_noop, assert_ok};
 use frame_support::{StorageDoubleMap, StorageMap};
-use pallet_nft::{Collection, CollectionKeys, MetadataValue, NumberOfNFTs};
-use pallet_portfolio::PortfolioNFT;
+use pallet_nft::{
+    Collection, CollectionKeySet, CollectionKeys, LeasesEndingAt, MetadataValue, NumberOfNFTs,
+    MAX_LEASES_ENDING_PER_BLOCK,
+};
+use pallet_portfolio::{PortfolioLockedNFT, PortfolioNFT};
 use polymesh_common_utilities::with_transaction;
 use polymesh_primitives::asset::{AssetType, NonFungibleType};
 use polymesh_primitives::asset_metadata::{
@@ -10,22 +14,25 @@ use polymesh_primitives::asset_metadata::{
     AssetMetadataValue,
 };
 use polymesh_primitives::{
-    NFTCollectionId, NFTCollectionKeys, NFTId, NFTMetadataAttribute, NFTs, PortfolioId,
+    AuthorizationError, ConditionType, NFTCollectionId, NFTCollectionKeys, NFTId, NFTLeaseId,
+    NFTMetadataAttribute, NFTMetadataKeySetId, NFTMetadataKeySpec, NFTs, PortfolioId,
     PortfolioKind, PortfolioNumber, Ticker,
 };
 use test_client::AccountKeyring;
 
 use super::asset_test::set_timestamp;
 use crate::ext_builder::ExtBuilder;
-use crate::storage::{TestStorage, User};
+use crate::storage::{get_last_auth_id, TestStorage, User};
 
 type Asset = pallet_asset::Module<TestStorage>;
 type ComplianceManager = pallet_compliance_manager::Module<TestStorage>;
+type EAError = pallet_external_agents::Error<TestStorage>;
 type Identity = pallet_identity::Module<TestStorage>;
 type NFT = pallet_nft::Module<TestStorage>;
 type NFTError = pallet_nft::Error<TestStorage>;
 type Portfolio = pallet_portfolio::Module<TestStorage>;
 type PortfolioError = pallet_portfolio::Error<TestStorage>;
+type System = frame_system::Pallet<TestStorage>;
 
 /// Successfully creates an NFT collection and an Asset.
 #[test]
@@ -796,3 +803,415 @@ fn transfer_nft() {
         );
     });
 }
+
+/// An NFT of a designated collection can be linked as a credential, and is unlinked when transferred.
+#[test]
+fn nft_credential() {
+    ExtBuilder::default().build().execute_with(|| {
+        set_timestamp(Utc::now().timestamp() as _);
+
+        let alice: User = User::new(AccountKeyring::Alice);
+        let bob: User = User::new(AccountKeyring::Bob);
+        let ticker: Ticker = Ticker::from_slice_truncated(b"PASS".as_ref());
+        let gated_ticker: Ticker = Ticker::from_slice_truncated(b"GATED".as_ref());
+        create_nft_collection(
+            alice.clone(),
+            ticker.clone(),
+            AssetType::NonFungible(NonFungibleType::Derivative),
+            vec![].into(),
+        );
+        mint_nft(
+            alice.clone(),
+            ticker.clone(),
+            vec![],
+            PortfolioKind::Default,
+        );
+        ComplianceManager::pause_asset_compliance(alice.origin(), ticker.clone()).unwrap();
+
+        // Receivers of the gated asset must hold a pass.
+        assert_ok!(Asset::create_asset(
+            alice.origin(),
+            gated_ticker.as_ref().into(),
+            gated_ticker,
+            true,
+            AssetType::EquityCommon,
+            Vec::new(),
+            None,
+            false,
+        ));
+        assert_ok!(ComplianceManager::add_compliance_requirement(
+            alice.origin(),
+            gated_ticker,
+            vec![],
+            vec![ConditionType::HoldsNFTCredential(ticker).into()]
+        ));
+        let holds_pass = |user: User| {
+            ComplianceManager::verify_restriction_granular(&gated_ticker, None, Some(user.did))
+                .result
+        };
+
+        // Only the NFTs of designated collections can be linked.
+        assert_noop!(
+            NFT::link_nft_credential(alice.origin(), ticker, NFTId(1), PortfolioKind::Default),
+            NFTError::NotCredentialCollection
+        );
+        assert_noop!(
+            NFT::set_credential_collection(bob.origin(), ticker, true),
+            EAError::UnauthorizedAgent
+        );
+        assert_ok!(NFT::set_credential_collection(alice.origin(), ticker, true));
+
+        // Only the holder of the NFT can link it.
+        assert_noop!(
+            NFT::link_nft_credential(bob.origin(), ticker, NFTId(1), PortfolioKind::Default),
+            NFTError::NFTNotFound
+        );
+        assert!(!holds_pass(alice.clone()));
+        assert_ok!(NFT::link_nft_credential(
+            alice.origin(),
+            ticker,
+            NFTId(1),
+            PortfolioKind::Default
+        ));
+        assert_eq!(Identity::nft_credential(alice.did, ticker), Some(NFTId(1)));
+        assert!(holds_pass(alice.clone()));
+
+        // Transferring the NFT unlinks it.
+        let nfts = NFTs::new(ticker, vec![NFTId(1)]).unwrap();
+        assert_ok!(with_transaction(|| {
+            NFT::base_nft_transfer(
+                &PortfolioId::default_portfolio(alice.did),
+                &PortfolioId::default_portfolio(bob.did),
+                &nfts,
+            )
+        }));
+        assert_eq!(Identity::nft_credential(alice.did, ticker), None);
+        assert!(!holds_pass(alice.clone()));
+        assert!(!holds_pass(bob.clone()));
+
+        // The new holder can link and unlink it.
+        assert_ok!(NFT::link_nft_credential(
+            bob.origin(),
+            ticker,
+            NFTId(1),
+            PortfolioKind::Default
+        ));
+        assert!(holds_pass(bob.clone()));
+        assert_ok!(NFT::unlink_nft_credential(bob.origin(), ticker));
+        assert!(!holds_pass(bob.clone()));
+        assert_noop!(
+            NFT::unlink_nft_credential(bob.origin(), ticker),
+            NFTError::NFTCredentialNotLinked
+        );
+    });
+}
+
+/// Collections can be created with the metadata keys of a reusable key-set.
+#[test]
+fn metadata_key_sets() {
+    ExtBuilder::default().build().execute_with(|| {
+        set_timestamp(Utc::now().timestamp() as _);
+
+        let alice: User = User::new(AccountKeyring::Alice);
+        let bob: User = User::new(AccountKeyring::Bob);
+        let key_spec = |name: &str, description: Option<&str>| NFTMetadataKeySpec {
+            name: AssetMetadataName(name.as_bytes().to_vec()),
+            spec: AssetMetadataSpec {
+                url: None,
+                description: description.map(|d| d.as_bytes().to_vec().into()),
+                type_def: None,
+            },
+        };
+        let key_set_id = NFTMetadataKeySetId(1);
+        let nft_type = Some(NonFungibleType::Derivative);
+
+        assert_noop!(
+            NFT::register_metadata_key_set(
+                alice.origin(),
+                vec![key_spec("size", None), key_spec("size", None)]
+            ),
+            NFTError::DuplicateMetadataKey
+        );
+        let keys = vec![key_spec("size", None), key_spec("color", Some("rgb"))];
+        assert_ok!(NFT::register_metadata_key_set(alice.origin(), keys.clone()));
+        assert_eq!(NFT::metadata_key_set(key_set_id).unwrap().keys, keys);
+
+        // Only the owner of the key-set can use it.
+        let ticker: Ticker = Ticker::from_slice_truncated(b"TICKER".as_ref());
+        assert_noop!(
+            NFT::create_nft_collection_from_key_set(bob.origin(), ticker, nft_type, key_set_id),
+            NFTError::NotMetadataKeySetOwner
+        );
+        assert_noop!(
+            NFT::create_nft_collection_from_key_set(
+                alice.origin(),
+                ticker,
+                nft_type,
+                NFTMetadataKeySetId(2)
+            ),
+            NFTError::MetadataKeySetNotFound
+        );
+
+        // The keys of the set are registered as local keys of each collection.
+        assert_ok!(NFT::create_nft_collection_from_key_set(
+            alice.origin(),
+            ticker,
+            nft_type,
+            key_set_id
+        ));
+        let collection_id = NFT::collection_ticker(&ticker);
+        assert_eq!(CollectionKeySet::get(collection_id), Some(key_set_id));
+        assert_eq!(CollectionKeys::get(collection_id).len(), 2);
+        for key in &keys {
+            let local_key = Asset::asset_metadata_local_name_to_key(ticker, &key.name).unwrap();
+            assert!(CollectionKeys::get(collection_id).contains(&local_key.into()));
+        }
+
+        // Local keys already registered with the same specs are reused.
+        let create_asset = |ticker: Ticker, key: &NFTMetadataKeySpec, spec: AssetMetadataSpec| {
+            Asset::create_asset(
+                alice.origin(),
+                ticker.as_ref().into(),
+                ticker,
+                false,
+                AssetType::NonFungible(NonFungibleType::Derivative),
+                Vec::new(),
+                None,
+                true,
+            )
+            .expect("failed to create an asset");
+            assert_ok!(Asset::register_asset_metadata_local_type(
+                alice.origin(),
+                ticker,
+                key.name.clone(),
+                spec
+            ));
+            Asset::asset_metadata_local_name_to_key(ticker, &key.name).unwrap()
+        };
+        let ticker2: Ticker = Ticker::from_slice_truncated(b"TICKER2".as_ref());
+        let size_key = create_asset(ticker2, &keys[0], keys[0].spec.clone());
+        assert_ok!(NFT::create_nft_collection_from_key_set(
+            alice.origin(),
+            ticker2,
+            None,
+            key_set_id
+        ));
+        let collection_keys = CollectionKeys::get(NFT::collection_ticker(&ticker2));
+        assert_eq!(collection_keys.len(), 2);
+        assert!(collection_keys.contains(&size_key.into()));
+
+        // Local keys already registered with different specs can't be reused.
+        let ticker3: Ticker = Ticker::from_slice_truncated(b"TICKER3".as_ref());
+        create_asset(ticker3, &keys[1], AssetMetadataSpec::default());
+        assert_noop!(
+            NFT::create_nft_collection_from_key_set(alice.origin(), ticker3, None, key_set_id),
+            NFTError::MetadataKeySpecMismatch
+        );
+    });
+}
+
+/// A leased NFT is locked in the lessee's portfolio once the lessee accepted the lease, and returned
+/// to the lessor at the end of the lease or once both parties agreed to end it.
+#[test]
+fn lease_nft() {
+    ExtBuilder::default().build().execute_with(|| {
+        set_timestamp(Utc::now().timestamp() as _);
+
+        let alice: User = User::new(AccountKeyring::Alice);
+        let bob: User = User::new(AccountKeyring::Bob);
+        let charlie: User = User::new(AccountKeyring::Charlie);
+        let ticker: Ticker = Ticker::from_slice_truncated(b"TICKER".as_ref());
+        create_nft_collection(
+            alice.clone(),
+            ticker.clone(),
+            AssetType::NonFungible(NonFungibleType::Derivative),
+            vec![].into(),
+        );
+        mint_nft(
+            alice.clone(),
+            ticker.clone(),
+            vec![],
+            PortfolioKind::Default,
+        );
+        ComplianceManager::pause_asset_compliance(alice.origin(), ticker.clone()).unwrap();
+        let lessor = PortfolioId::default_portfolio(alice.did);
+        let lessee = PortfolioId::default_portfolio(bob.did);
+        let now = System::block_number();
+
+        assert_noop!(
+            NFT::lease_nft(
+                alice.origin(),
+                ticker,
+                NFTId(1),
+                PortfolioKind::Default,
+                lessee,
+                now
+            ),
+            NFTError::InvalidLeaseEnd
+        );
+        assert_ok!(NFT::lease_nft(
+            alice.origin(),
+            ticker,
+            NFTId(1),
+            PortfolioKind::Default,
+            lessee,
+            now + 2
+        ));
+        let lease_id = NFT::lease_id();
+        let auth_id = get_last_auth_id(&bob.did.into());
+
+        // Nothing is transferred until the lessee accepts the lease.
+        assert!(NFT::lease_offer(lease_id).is_some());
+        assert_eq!(NFT::lease(lease_id), None);
+        assert!(PortfolioNFT::get(&lessor, (&ticker, NFTId(1))));
+        assert_noop!(
+            NFT::accept_nft_lease(charlie.origin(), auth_id),
+            AuthorizationError::Invalid
+        );
+        assert_ok!(NFT::accept_nft_lease(bob.origin(), auth_id));
+        assert_eq!(NFT::lease_offer(lease_id), None);
+        assert!(NFT::lease(lease_id).is_some());
+        assert_eq!(NumberOfNFTs::get(&ticker, bob.did), 1);
+        assert!(PortfolioNFT::get(&lessee, (&ticker, NFTId(1))));
+        assert!(PortfolioLockedNFT::get(&lessee, (&ticker, NFTId(1))));
+
+        // The lessee can't dispose of the NFT.
+        assert_noop!(
+            NFT::lease_nft(
+                bob.origin(),
+                ticker,
+                NFTId(1),
+                PortfolioKind::Default,
+                lessor,
+                now + 5
+            ),
+            NFTError::NFTIsLocked
+        );
+
+        // Only the parties of the lease can agree to end it, which requires both of them.
+        assert_noop!(
+            NFT::approve_nft_lease_end(charlie.origin(), lease_id),
+            NFTError::NotLeaseParty
+        );
+        assert_ok!(NFT::approve_nft_lease_end(bob.origin(), lease_id));
+        assert_eq!(NFT::lease_end_approval(lease_id), Some(lessee));
+        assert!(NFT::lease(lease_id).is_some());
+
+        // The NFT is returned at the end of the lease.
+        NFT::on_initialize(now + 1);
+        assert!(PortfolioNFT::get(&lessee, (&ticker, NFTId(1))));
+        NFT::on_initialize(now + 2);
+        assert_eq!(NFT::lease(lease_id), None);
+        assert_eq!(NFT::lease_end_approval(lease_id), None);
+        assert_eq!(NumberOfNFTs::get(&ticker, bob.did), 0);
+        assert_eq!(NumberOfNFTs::get(&ticker, alice.did), 1);
+        assert!(!PortfolioNFT::get(&lessee, (&ticker, NFTId(1))));
+        assert!(!PortfolioLockedNFT::get(&lessee, (&ticker, NFTId(1))));
+        assert!(PortfolioNFT::get(&lessor, (&ticker, NFTId(1))));
+
+        // The NFT is returned early once both parties agreed.
+        assert_ok!(NFT::lease_nft(
+            alice.origin(),
+            ticker,
+            NFTId(1),
+            PortfolioKind::Default,
+            lessee,
+            now + 10
+        ));
+        let lease_id = NFT::lease_id();
+        assert_ok!(NFT::accept_nft_lease(
+            bob.origin(),
+            get_last_auth_id(&bob.did.into())
+        ));
+        assert_ok!(NFT::approve_nft_lease_end(alice.origin(), lease_id));
+        assert!(PortfolioNFT::get(&lessee, (&ticker, NFTId(1))));
+        assert_ok!(NFT::approve_nft_lease_end(bob.origin(), lease_id));
+        assert_eq!(NFT::lease(lease_id), None);
+        assert!(NFT::leases_ending_at(now + 10).is_empty());
+        assert!(PortfolioNFT::get(&lessor, (&ticker, NFTId(1))));
+        assert_noop!(
+            NFT::approve_nft_lease_end(bob.origin(), lease_id),
+            NFTError::LeaseNotFound
+        );
+    });
+}
+
+/// A pending lease can be withdrawn by the lessor, and a lease can't end in a block that is full.
+#[test]
+fn lease_nft_offer() {
+    ExtBuilder::default().build().execute_with(|| {
+        set_timestamp(Utc::now().timestamp() as _);
+
+        let alice: User = User::new(AccountKeyring::Alice);
+        let bob: User = User::new(AccountKeyring::Bob);
+        let ticker: Ticker = Ticker::from_slice_truncated(b"TICKER".as_ref());
+        create_nft_collection(
+            alice.clone(),
+            ticker.clone(),
+            AssetType::NonFungible(NonFungibleType::Derivative),
+            vec![].into(),
+        );
+        mint_nft(
+            alice.clone(),
+            ticker.clone(),
+            vec![],
+            PortfolioKind::Default,
+        );
+        ComplianceManager::pause_asset_compliance(alice.origin(), ticker.clone()).unwrap();
+        let lessee = PortfolioId::default_portfolio(bob.did);
+        let now = System::block_number();
+
+        assert_ok!(NFT::lease_nft(
+            alice.origin(),
+            ticker,
+            NFTId(1),
+            PortfolioKind::Default,
+            lessee,
+            now + 2
+        ));
+        let lease_id = NFT::lease_id();
+        let auth_id = get_last_auth_id(&bob.did.into());
+
+        // Only the lessor can withdraw a pending lease.
+        assert_noop!(
+            NFT::approve_nft_lease_end(bob.origin(), lease_id),
+            NFTError::NotLeaseParty
+        );
+        assert_ok!(NFT::approve_nft_lease_end(alice.origin(), lease_id));
+        assert_eq!(NFT::lease_offer(lease_id), None);
+        assert_noop!(
+            NFT::accept_nft_lease(bob.origin(), auth_id),
+            NFTError::LeaseNotFound
+        );
+
+        // Leases can't be offered nor accepted for a block in which too many leases already end.
+        assert_ok!(NFT::lease_nft(
+            alice.origin(),
+            ticker,
+            NFTId(1),
+            PortfolioKind::Default,
+            lessee,
+            now + 3
+        ));
+        let auth_id = get_last_auth_id(&bob.did.into());
+        LeasesEndingAt::<TestStorage>::insert(
+            now + 3,
+            vec![NFTLeaseId(u64::MAX); MAX_LEASES_ENDING_PER_BLOCK as usize],
+        );
+        assert_noop!(
+            NFT::lease_nft(
+                alice.origin(),
+                ticker,
+                NFTId(1),
+                PortfolioKind::Default,
+                lessee,
+                now + 3
+            ),
+            NFTError::LeaseEndBlockFull
+        );
+        assert_noop!(
+            NFT::accept_nft_lease(bob.origin(), auth_id),
+            NFTError::LeaseEndBlockFull
+        );
+    });
+}
@@ -3,16 +3,17 @@ use super::{
     asset_test::{create_token, max_len_bytes},
     nft::{create_nft_collection, mint_nft},
     settlement_test::create_venue,
-    storage::{EventTest, System, TestStorage, User},
+    storage::{fast_forward_to_block, EventTest, System, TestStorage, User},
     ExtBuilder,
 };
 use frame_support::storage::StorageDoubleMap;
-use frame_support::{assert_noop, assert_ok, StorageMap};
+use frame_support::{assert_noop, assert_ok, dispatch::DispatchError, StorageMap};
 use frame_system::EventRecord;
 use pallet_portfolio::{
-    Event, MovePortfolioItem, NameToNumber, PortfolioAssetBalances, PortfolioNFT,
+    Event, MovePortfolioItem, NameToNumber, PortfolioAssetBalances, PortfolioLockedAssets,
+    PortfolioNFT, SweepRuleId,
 };
-use pallet_settlement::{InstructionMemo, LegAsset, LegV2, SettlementType};
+use pallet_settlement::{LegAsset, LegV2, SettlementType};
 use polymesh_common_utilities::balances::Memo;
 use polymesh_common_utilities::portfolio::PortfolioSubTrait;
 use polymesh_primitives::asset::{AssetType, NonFungibleType};
@@ -21,8 +22,8 @@ use polymesh_primitives::asset_metadata::{
 };
 use polymesh_primitives::{
     AuthorizationData, AuthorizationError, Fund, FundDescription, NFTCollectionKeys, NFTId,
-    NFTMetadataAttribute, NFTs, PortfolioId, PortfolioKind, PortfolioName, PortfolioNumber,
-    Signatory, Ticker,
+    NFTMetadataAttribute, NFTs, PortfolioBalanceLabel, PortfolioId, PortfolioKind, PortfolioName,
+    PortfolioNumber, Signatory, Ticker,
 };
 use test_client::AccountKeyring;
 
@@ -142,6 +143,47 @@ fn can_delete_recreate_portfolio() {
     });
 }
 
+#[test]
+fn create_portfolio_with_salt() {
+    ExtBuilder::default().build().execute_with(|| {
+        let alice = User::new(AccountKeyring::Alice);
+        let bob = User::new(AccountKeyring::Bob);
+        let create = |user: User, name: &str, salt| {
+            Portfolio::create_portfolio_with_salt(user.origin(), name.into(), salt)
+        };
+
+        // The number only depends on the salt, and doesn't consume a sequential number.
+        let num = Portfolio::salted_portfolio_number(&[1; 32]);
+        assert_ne!(num, Portfolio::salted_portfolio_number(&[2; 32]));
+        assert_ok!(create(alice, "foo", [1; 32]));
+        assert_ok!(create(bob, "foo", [1; 32]));
+        assert_eq!(Portfolio::portfolios(alice.did, num), "foo".into());
+        assert_eq!(Portfolio::portfolios(bob.did, num), "foo".into());
+        assert_eq!(
+            Portfolio::next_portfolio_number(alice.did),
+            PortfolioNumber(1)
+        );
+
+        // Salts and names can't be reused by the same identity.
+        assert_noop!(
+            create(alice, "bar", [1; 32]),
+            Error::PortfolioNumberAlreadyInUse
+        );
+        assert_noop!(
+            create(alice, "foo", [2; 32]),
+            Error::PortfolioNameAlreadyInUse
+        );
+
+        // A deleted portfolio can be recreated with the same number.
+        assert_ok!(Portfolio::delete_portfolio(alice.origin(), num));
+        assert_ok!(create(alice, "bar", [1; 32]));
+        assert_eq!(
+            Portfolio::name_to_number(alice.did, PortfolioName::from("bar")),
+            Some(num)
+        );
+    });
+}
+
 #[test]
 fn cannot_delete_portfolio_with_asset() {
     ExtBuilder::default().build().execute_with(|| {
@@ -674,7 +716,7 @@ fn delete_portfolio_with_locked_nfts() {
             None,
             legs,
             vec![PortfolioId::user_portfolio(alice.did, PortfolioNumber(1))],
-            Some(InstructionMemo::default()),
+            Some(polymesh_primitives::Memo::default()),
         ));
 
         assert_noop!(
@@ -856,3 +898,270 @@ fn move_more_funds() {
         );
     });
 }
+
+#[test]
+fn sweep_rules() {
+    ExtBuilder::default().build().execute_with(|| {
+        System::set_block_number(1);
+
+        let (owner, num) = create_portfolio();
+        let bob = User::new(AccountKeyring::Bob);
+        let (ticker, token) = create_token(owner);
+        let from = PortfolioId::default_portfolio(owner.did);
+        let to = PortfolioId::user_portfolio(owner.did, num);
+        let threshold = token.total_supply / 4;
+
+        // Only the custodian of the source portfolio can add a rule, with a non-zero period.
+        assert_noop!(
+            Portfolio::add_sweep_rule(bob.origin(), from, to, ticker, threshold, 5),
+            Error::UnauthorizedCustodian
+        );
+        assert_noop!(
+            Portfolio::add_sweep_rule(owner.origin(), from, to, ticker, threshold, 0),
+            Error::InvalidSweepPeriod
+        );
+        assert_ok!(Portfolio::add_sweep_rule(
+            owner.origin(),
+            from,
+            to,
+            ticker,
+            threshold,
+            5
+        ));
+        let id = SweepRuleId(0);
+        assert_eq!(Portfolio::sweep_rules(id).unwrap().next_run, 6);
+
+        // Only the scheduler executes the rule.
+        assert_noop!(
+            Portfolio::execute_sweep_rule(owner.origin(), id),
+            DispatchError::BadOrigin
+        );
+
+        // Locked assets are never swept.
+        assert_ok!(Portfolio::lock_tokens(&from, &ticker, threshold));
+        fast_forward_to_block(5);
+        assert_eq!(
+            Portfolio::default_portfolio_balance(owner.did, &ticker),
+            threshold * 2
+        );
+        assert_eq!(
+            Portfolio::user_portfolio_balance(owner.did, num, &ticker),
+            token.total_supply - threshold * 2
+        );
+        assert_eq!(
+            System::events().last().unwrap().event,
+            EventTest::Portfolio(Event::SweepRuleExecuted(
+                owner.did,
+                id,
+                token.total_supply - threshold * 2
+            ))
+        );
+        assert_eq!(Portfolio::sweep_rules(id).unwrap().next_run, 11);
+
+        // Only the custodian can remove the rule.
+        assert_noop!(
+            Portfolio::remove_sweep_rule(bob.origin(), id),
+            Error::UnauthorizedCustodian
+        );
+        assert_noop!(
+            Portfolio::remove_sweep_rule(owner.origin(), SweepRuleId(1)),
+            Error::NoSuchSweepRule
+        );
+        assert_ok!(Portfolio::remove_sweep_rule(owner.origin(), id));
+        assert_eq!(Portfolio::sweep_rules(id), None);
+
+        // The cancelled run no longer moves any balance.
+        fast_forward_to_block(11);
+        assert_eq!(
+            Portfolio::default_portfolio_balance(owner.did, &ticker),
+            threshold * 2
+        );
+    });
+}
+
+#[test]
+fn labelled_balances() {
+    ExtBuilder::default().build().execute_with(|| {
+        let (owner, num) = create_portfolio();
+        let bob = User::new(AccountKeyring::Bob);
+        let (ticker, token) = create_token(owner);
+        let pid = PortfolioId::default_portfolio(owner.did);
+        let user_pid = PortfolioId::user_portfolio(owner.did, num);
+        let margin = PortfolioBalanceLabel(b"margin".to_vec());
+        let available = PortfolioBalanceLabel(b"available".to_vec());
+        let quarter = token.total_supply / 4;
+        let move_labelled = |from: &Option<PortfolioBalanceLabel>, to: &Option<_>, amount| {
+            Portfolio::move_labelled_balance(
+                owner.origin(),
+                pid,
+                ticker,
+                from.clone(),
+                to.clone(),
+                amount,
+            )
+        };
+        let move_out = |amount| {
+            Portfolio::move_portfolio_funds(
+                owner.origin(),
+                pid,
+                user_pid,
+                vec![MovePortfolioItem {
+                    ticker,
+                    amount,
+                    memo: None,
+                }],
+            )
+        };
+
+        // Only the custodian can move balances between labels.
+        assert_noop!(
+            Portfolio::move_labelled_balance(bob.origin(), pid, ticker, None, None, 1),
+            Error::UnauthorizedCustodian
+        );
+        assert_noop!(move_labelled(&None, &None, 1), Error::SameBalanceLabel);
+        assert_noop!(
+            move_labelled(&None, &Some(margin.clone()), token.total_supply + 1),
+            Error::InsufficientPortfolioBalance
+        );
+
+        // Label half of the balance as margin and a quarter as available.
+        assert_ok!(move_labelled(&None, &Some(margin.clone()), quarter * 3));
+        assert_ok!(move_labelled(
+            &Some(margin.clone()),
+            &Some(available.clone()),
+            quarter
+        ));
+        assert_last_event!(
+            EventTest::Portfolio(Event::LabelledBalanceMoved(
+                did,
+                p,
+                t,
+                Some(from),
+                Some(to),
+                amount
+            )),
+            did == &owner.did
+                && p == &pid
+                && t == &ticker
+                && from == &margin
+                && to == &available
+                && *amount == quarter
+        );
+        assert_noop!(
+            move_labelled(&Some(available.clone()), &None, quarter + 1),
+            Error::InsufficientLabelledBalance
+        );
+        assert_eq!(
+            Portfolio::labelled_balance(pid, (ticker, &margin)),
+            quarter * 2
+        );
+        assert_eq!(
+            Portfolio::labelled_balance(pid, (ticker, &available)),
+            quarter
+        );
+        assert_eq!(Portfolio::labelled_total(pid, ticker), quarter * 3);
+        assert_eq!(
+            Portfolio::default_portfolio_balance(owner.did, &ticker),
+            token.total_supply
+        );
+
+        // Labelled balances can't leave the portfolio nor be locked.
+        assert_noop!(move_out(quarter + 1), Error::InsufficientPortfolioBalance);
+        assert_noop!(
+            Portfolio::lock_tokens(&pid, &ticker, quarter + 1),
+            Error::InsufficientPortfolioBalance
+        );
+
+        // Settlement locks take from the designated label and unlock into the unlabelled balance.
+        assert_ok!(Portfolio::set_settlement_lock_label(
+            owner.origin(),
+            pid,
+            ticker,
+            Some(margin.clone())
+        ));
+        assert_eq!(
+            Portfolio::settlement_lock_label(pid, ticker),
+            Some(margin.clone())
+        );
+        assert_noop!(
+            Portfolio::lock_tokens(&pid, &ticker, quarter * 2 + 1),
+            Error::InsufficientLabelledBalance
+        );
+
+        // Labelled locks are also bounded by the usable balance of the portfolio.
+        PortfolioLockedAssets::insert(pid, ticker, quarter * 2);
+        assert_noop!(
+            Portfolio::lock_tokens(&pid, &ticker, quarter + 1),
+            Error::InsufficientPortfolioBalance
+        );
+        PortfolioLockedAssets::insert(pid, ticker, 0);
+
+        assert_ok!(Portfolio::lock_tokens(&pid, &ticker, quarter * 2));
+        assert_eq!(Portfolio::labelled_balance(pid, (ticker, &margin)), 0);
+        assert_eq!(Portfolio::labelled_total(pid, ticker), quarter);
+        assert_eq!(Portfolio::locked_assets(pid, ticker), quarter * 2);
+        assert_noop!(move_out(quarter + 1), Error::InsufficientPortfolioBalance);
+        assert_ok!(Portfolio::unlock_tokens(&pid, &ticker, quarter * 2));
+        assert_ok!(move_out(quarter * 3));
+        assert_noop!(move_out(1), Error::InsufficientPortfolioBalance);
+
+        // Clearing the label makes locks take from the unlabelled balance again.
+        assert_ok!(Portfolio::set_settlement_lock_label(
+            owner.origin(),
+            pid,
+            ticker,
+            None
+        ));
+        assert_ok!(move_labelled(&Some(available.clone()), &None, quarter));
+        assert_ok!(Portfolio::lock_tokens(&pid, &ticker, quarter));
+        assert_eq!(Portfolio::labelled_total(pid, ticker), 0);
+    });
+}
+
+#[test]
+fn portfolio_observers() {
+    ExtBuilder::default().build().execute_with(|| {
+        let (owner, num) = create_portfolio();
+        let bob = User::new(AccountKeyring::Bob);
+        let charlie = User::new(AccountKeyring::Charlie);
+        let pid = PortfolioId::user_portfolio(owner.did, num);
+
+        // Only the owner can add observers, not its custodian.
+        set_custodian_ok(owner, charlie, pid);
+        assert_noop!(
+            Portfolio::add_portfolio_observer(charlie.origin(), pid, bob.did),
+            Error::PortfolioDoesNotExist
+        );
+
+        assert_ok!(Portfolio::add_portfolio_observer(
+            owner.origin(),
+            pid,
+            bob.did
+        ));
+        assert_last_event!(
+            EventTest::Portfolio(Event::PortfolioObserverAdded(did, p, observer)),
+            did == &owner.did && p == &pid && observer == &bob.did
+        );
+        assert_noop!(
+            Portfolio::add_portfolio_observer(owner.origin(), pid, bob.did),
+            Error::ObserverAlreadyAdded
+        );
+        assert!(Portfolio::portfolio_observer(pid, bob.did));
+        assert!(Portfolio::observed_portfolio(bob.did, pid));
+
+        // Observers have no custody rights.
+        assert!(Portfolio::quit_portfolio_custody(bob.origin(), pid).is_err());
+
+        assert_ok!(Portfolio::remove_portfolio_observer(
+            owner.origin(),
+            pid,
+            bob.did
+        ));
+        assert_noop!(
+            Portfolio::remove_portfolio_observer(owner.origin(), pid, bob.did),
+            Error::ObserverNotFound
+        );
+        assert!(!Portfolio::portfolio_observer(pid, bob.did));
+        assert!(!Portfolio::observed_portfolio(bob.did, pid));
+    });
+}
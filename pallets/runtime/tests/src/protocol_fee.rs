@@ -11,6 +11,8 @@ use test_client::AccountKeyring;
 
 type Error = pallet_protocol_fee::Error<TestStorage>;
 type ProtocolFee = pallet_protocol_fee::Module<TestStorage>;
+type Origin = <TestStorage as frame_system::Config>::RuntimeOrigin;
+type System = frame_system::Pallet<TestStorage>;
 
 #[test]
 fn can_compute_fee() {
@@ -40,3 +42,58 @@ fn can_charge_fee_batch() {
         );
     });
 }
+
+#[test]
+fn fee_holidays() {
+    ExtBuilder::default().build().execute_with(|| {
+        let op = ProtocolOp::AssetIssue;
+        System::set_block_number(5);
+
+        // The holiday must end after it starts and in the future.
+        assert_noop!(
+            ProtocolFee::schedule_fee_holiday(Origin::root(), op, 10, 10),
+            Error::InvalidFeeHoliday
+        );
+        assert_noop!(
+            ProtocolFee::schedule_fee_holiday(Origin::root(), op, 1, 5),
+            Error::InvalidFeeHoliday
+        );
+        assert_ok!(ProtocolFee::schedule_fee_holiday(
+            Origin::root(),
+            op,
+            10,
+            20
+        ));
+
+        // Fees apply until the holiday starts and are reverted when it ends.
+        assert_eq!(ProtocolFee::compute_fee(&[op]), PROTOCOL_OP_BASE_FEE);
+        System::set_block_number(10);
+        assert_eq!(ProtocolFee::compute_fee(&[op]), 0);
+        assert_eq!(
+            ProtocolFee::compute_fee(&[op, ProtocolOp::AssetRegisterTicker]),
+            PROTOCOL_OP_BASE_FEE
+        );
+        System::set_block_number(20);
+        assert_eq!(ProtocolFee::compute_fee(&[op]), PROTOCOL_OP_BASE_FEE);
+
+        // A past holiday is removed when the operation is charged.
+        assert!(ProtocolFee::fee_holidays(op).is_some());
+        assert_ok!(ProtocolFee::charge_fees(&[op]));
+        assert_eq!(ProtocolFee::fee_holidays(op), None);
+
+        // A cancelled holiday no longer applies.
+        assert_ok!(ProtocolFee::schedule_fee_holiday(
+            Origin::root(),
+            op,
+            20,
+            30
+        ));
+        assert_eq!(ProtocolFee::compute_fee(&[op]), 0);
+        assert_ok!(ProtocolFee::cancel_fee_holiday(Origin::root(), op));
+        assert_eq!(ProtocolFee::compute_fee(&[op]), PROTOCOL_OP_BASE_FEE);
+        assert_noop!(
+            ProtocolFee::cancel_fee_holiday(Origin::root(), op),
+            Error::NoFeeHoliday
+        );
+    });
+}
@@ -1,19 +1,25 @@
 use super::{
-    storage::{get_last_auth_id, make_account_without_cdd, RuntimeCall, TestStorage, User},
+    storage::{
+        add_secondary_key, get_last_auth_id, make_account_without_cdd, RuntimeCall, TestStorage,
+        User,
+    },
     ExtBuilder,
 };
 use frame_support::{
     assert_noop, assert_ok,
     dispatch::{DispatchInfo, Pays, PostDispatchInfo, Weight},
+    traits::ReservableCurrency,
     StorageMap,
 };
 use frame_system;
+use pallet_identity::types::{AccountOverview, KeyIdentityData};
 use pallet_relayer::Subsidy;
 use polymesh_common_utilities::{
     constants::currency::POLY, protocol_fee::ProtocolOp,
     traits::transaction_payment::CddAndFeeDetails,
 };
-use polymesh_primitives::{AccountId, Balance, Signatory, Ticker, TransactionError};
+use polymesh_primitives::{AccountId, Balance, Permissions, Signatory, Ticker, TransactionError};
+use polymesh_runtime_common::impls::account_overview;
 use polymesh_runtime_develop::runtime::{CddHandler, RuntimeCall as DevRuntimeCall};
 use sp_runtime::{
     traits::{Dispatchable, SignedExtension},
@@ -568,3 +574,54 @@ fn do_relayer_accept_cdd_and_fees_test() {
         Ok(Some(alice.acc()))
     );
 }
+
+#[test]
+fn account_overview_test() {
+    ExtBuilder::default()
+        .monied(true)
+        .build()
+        .execute_with(&do_account_overview_test);
+}
+
+fn do_account_overview_test() {
+    let alice = User::new(AccountKeyring::Alice).balance(1_000 * POLY);
+    let bob = User::new(AccountKeyring::Bob);
+    let dave = AccountKeyring::Dave.to_account_id();
+    let overview = |acc: AccountId| account_overview::<TestStorage>(acc);
+
+    // An unlinked account has no identity.
+    let unlinked = overview(AccountKeyring::Eve.to_account_id());
+    assert_eq!(unlinked.key_identity_data, None);
+    assert_eq!(unlinked.subsidy, None);
+
+    // A primary key has no permissions, and its balances are reported.
+    assert_ok!(Balances::reserve(&alice.acc(), 100 * POLY));
+    assert_eq!(
+        overview(alice.acc()),
+        AccountOverview {
+            key_identity_data: Some(KeyIdentityData {
+                identity: alice.did,
+                permissions: None,
+            }),
+            free_balance: 900 * POLY,
+            reserved_balance: 100 * POLY,
+            misc_frozen_balance: 0,
+            fee_frozen_balance: 0,
+            subsidy: None,
+        }
+    );
+
+    // A secondary key reports its permissions.
+    add_secondary_key(alice.did, dave.clone());
+    assert_eq!(
+        overview(dave).key_identity_data,
+        Some(KeyIdentityData {
+            identity: alice.did,
+            permissions: Some(Permissions::default()),
+        })
+    );
+
+    // A subsidised key reports its paying key and remaining subsidy.
+    setup_subsidy(bob, alice, 10 * POLY);
+    assert_eq!(overview(bob.acc()).subsidy, Some((alice.acc(), 10 * POLY)));
+}
@@ -1,39 +1,54 @@
 use super::{
-    asset_test::{allow_all_transfers, max_len_bytes},
+    asset_test::{allow_all_transfers, max_len_bytes, set_timestamp},
     next_block,
     nft::{create_nft_collection, mint_nft},
     storage::{
-        default_portfolio_vec, make_account_without_cdd, provide_scope_claim_to_multiple_parties,
-        user_portfolio_vec, TestStorage, User,
+        default_portfolio_vec, make_account_without_cdd, make_remark_proposal,
+        provide_scope_claim_to_multiple_parties, root, user_portfolio_vec,
+        InstructionRequestLifetime, MaxScheduledPerBlock, TestStorage, User,
+        INSTRUCTION_STATUS_RETENTION, REJECTED_INSTRUCTION_RETENTION, SETTLEMENT_EXECUTION_WEIGHT,
     },
     ExtBuilder,
 };
-use codec::Encode;
-use frame_support::{assert_noop, assert_ok, IterableStorageDoubleMap, StorageDoubleMap};
+use codec::{Decode, Encode};
+use frame_support::{
+    assert_noop, assert_ok,
+    dispatch::Weight,
+    traits::{Get, OnIdle, OnInitialize},
+    IterableStorageDoubleMap, StorageDoubleMap,
+};
 use pallet_asset as asset;
 use pallet_balances as balances;
 use pallet_compliance_manager as compliance_manager;
 use pallet_identity as identity;
 use pallet_nft::NumberOfNFTs;
-use pallet_portfolio::{MovePortfolioItem, PortfolioLockedNFT, PortfolioNFT};
+use pallet_portfolio::{
+    MovePortfolioItem, PortfolioAssetBalances, PortfolioLockedNFT, PortfolioNFT,
+};
 use pallet_scheduler as scheduler;
+use pallet_settlement::test_utils::{create_user_portfolio, create_venue_, UserData};
 use pallet_settlement::{
-    AffirmationStatus, Instruction, InstructionId, InstructionMemo, InstructionStatus, Leg,
-    LegAsset, LegId, LegStatus, LegV2, Receipt, ReceiptDetails, ReceiptMetadata, SettlementType,
-    VenueDetails, VenueId, VenueInstructions, VenueType,
+    AffirmationStatus, ExecutionProgress, Instruction, InstructionAuditRecord, InstructionId,
+    InstructionStatus, InstructionTag, Leg, LegAsset, LegConsideration, LegId, LegRevision,
+    LegStatus, LegV2, LockedAsset, MarketHours, NetObligation, PortfolioAlias, Receipt,
+    ReceiptDetails, ReceiptMetadata, ReceiptMetadataSchema, ReceiptUsageDetails, RecurrenceStop,
+    RecurringInstructionId, RejectReason, SettlementType, VenueDetails, VenueFee, VenueId,
+    VenueInstructions, VenueQuota, VenueSignerUsage, VenueType, WrappedMemoKey,
+    MAX_INSTRUCTION_MEDIATORS, MAX_MEMO_READERS, MAX_RECEIPTS_PER_AFFIRMATION,
+    MAX_VENUE_INSTRUCTION_REQUESTS,
 };
 use polymesh_common_utilities::constants::ERC1400_TRANSFER_SUCCESS;
 use polymesh_primitives::{
     asset::{AssetType, NonFungibleType},
     asset_metadata::{AssetMetadataKey, AssetMetadataLocalKey, AssetMetadataValue},
     checked_inc::CheckedInc,
-    AccountId, AuthorizationData, Balance, Claim, Condition, ConditionType, IdentityId,
+    AccountId, AuthorizationData, Balance, Claim, Condition, ConditionType, IdentityId, Memo,
     NFTCollectionKeys, NFTId, NFTMetadataAttribute, NFTs, PortfolioId, PortfolioKind,
-    PortfolioName, PortfolioNumber, Signatory, Ticker,
+    PortfolioName, PortfolioNumber, Signatory, Ticker, MAX_MEMO_LEN,
 };
 use rand::{prelude::*, thread_rng};
-use sp_runtime::AnySignature;
-use std::collections::HashMap;
+use sp_runtime::{AnySignature, DispatchError};
+use std::collections::{BTreeSet, HashMap};
 use std::convert::TryFrom;
 use std::ops::Deref;
 use test_client::AccountKeyring;
@@ -51,6 +66,7 @@ type Origin = <TestStorage as frame_system::Config>::RuntimeOrigin;
 type Moment = <TestStorage as pallet_timestamp::Config>::Moment;
 type BlockNumber = <TestStorage as frame_system::Config>::BlockNumber;
 type Settlement = pallet_settlement::Module<TestStorage>;
+type Statistics = pallet_statistics::Module<TestStorage>;
 type System = frame_system::Pallet<TestStorage>;
 type Error = pallet_settlement::Error<TestStorage>;
 type Scheduler = scheduler::Pallet<TestStorage>;
@@ -348,6 +364,58 @@ fn basic_settlement() {
     });
 }
 
+#[test]
+fn settled_volume() {
+    test_with_cdd_provider(|eve| {
+        let alice = User::new(AccountKeyring::Alice);
+        let bob = User::new(AccountKeyring::Bob);
+        let venue_counter = create_token_and_venue(TICKER, alice);
+        provide_scope_claim_to_multiple_parties(&[alice.did, bob.did], TICKER, eve);
+        let day = 24 * 60 * 60 * 1000;
+        set_timestamp(day);
+
+        // The tokens transferred by executed legs are added to the volume settled today.
+        let settle = |amounts: &[u128]| {
+            let instruction_id = Settlement::instruction_counter();
+            let legs = amounts
+                .iter()
+                .map(|&amount| Leg {
+                    from: PortfolioId::default_portfolio(alice.did),
+                    to: PortfolioId::default_portfolio(bob.did),
+                    asset: TICKER,
+                    amount,
+                })
+                .collect();
+            assert_ok!(Settlement::add_and_affirm_instruction(
+                alice.origin(),
+                venue_counter,
+                SettlementType::SettleManual(System::block_number()),
+                None,
+                None,
+                legs,
+                default_portfolio_vec(alice.did),
+            ));
+            assert_affirm_instruction_with_zero_leg!(bob.origin(), instruction_id, bob.did);
+            assert_ok!(Settlement::execute_manual_instruction(
+                alice.origin(),
+                instruction_id,
+                amounts.len() as u32,
+                None
+            ));
+        };
+        settle(&[100, 30]);
+        settle(&[20]);
+        assert_eq!(Statistics::settled_volume(&TICKER, 1), 150);
+
+        // The volume settled is kept per day.
+        set_timestamp(3 * day);
+        settle(&[20]);
+        assert_eq!(Statistics::settled_volume(&TICKER, 1), 20);
+        assert_eq!(Statistics::settled_volume(&TICKER, 3), 170);
+        assert_eq!(Statistics::settled_volume(&TICKER, 0), 0);
+    });
+}
+
 #[test]
 fn create_and_affirm_instruction() {
     test_with_cdd_provider(|eve| {
@@ -497,6 +565,7 @@ fn token_swap() {
             created_at: Some(Timestamp::get()),
             trade_date: None,
             value_date: None,
+            expiry_block: None,
         };
         assert_instruction_status(instruction_id, InstructionStatus::Pending);
         assert_instruction_details(instruction_id, instruction_details);
@@ -624,6 +693,7 @@ fn settle_on_block() {
             created_at: Some(Timestamp::get()),
             trade_date: None,
             value_date: None,
+            expiry_block: None,
         };
         assert_instruction_status(instruction_id, InstructionStatus::Pending);
         assert_eq!(
@@ -740,6 +810,7 @@ fn failed_execution() {
             created_at: Some(Timestamp::get()),
             trade_date: None,
             value_date: None,
+            expiry_block: None,
         };
         assert_instruction_status(instruction_id, InstructionStatus::Pending);
         assert_eq!(
@@ -860,11 +931,23 @@ fn venue_filtering() {
             ),
             Error::UnauthorizedVenue
         );
+        assert_eq!(
+            Settlement::unauthorized_venue_tickers(&venue_counter, &[TICKER, TICKER2]),
+            Ok(vec![TICKER])
+        );
+        assert_eq!(
+            Settlement::unauthorized_venue_tickers(&VenueId(u64::MAX), &[TICKER]),
+            Err(Error::InvalidVenue.into())
+        );
         assert_ok!(Settlement::allow_venues(
             alice.origin(),
             TICKER,
             vec![venue_counter]
         ));
+        assert_eq!(
+            Settlement::unauthorized_venue_tickers(&venue_counter, &[TICKER, TICKER2]),
+            Ok(vec![])
+        );
         assert_ok!(Settlement::add_and_affirm_instruction(
             alice.origin(),
             venue_counter,
@@ -1139,6 +1222,8 @@ fn basic_fuzzing() {
                 instruction_id,
                 PortfolioId::default_portfolio(users[0].did),
                 legs.len() as u32,
+                None,
+                None,
             ));
             assert_eq!(
                 Settlement::instruction_status(instruction_id),
@@ -1873,12 +1958,14 @@ fn reject_instruction() {
         let venue_counter = create_token_and_venue(TICKER, alice);
         let amount = 100u128;
 
-        let reject_instruction = |user: &User, instruction_id| {
+        let reject_instruction = |user: &User, instruction_id, reason| {
             Settlement::reject_instruction(
                 user.origin(),
                 instruction_id,
                 PortfolioId::default_portfolio(user.did),
                 1,
+                reason,
+                None,
             )
         };
 
@@ -1908,11 +1995,12 @@ fn reject_instruction() {
         next_block();
         // Try rejecting the instruction from a non-party account.
         assert_noop!(
-            reject_instruction(&charlie, instruction_id),
+            reject_instruction(&charlie, instruction_id, None),
             Error::CallerIsNotAParty
         );
         next_block();
-        assert_ok!(reject_instruction(&alice, instruction_id,));
+        assert_ok!(reject_instruction(&alice, instruction_id, None));
+        assert_eq!(Settlement::instruction_rejection(instruction_id), None);
         next_block();
         // Instruction should've been deleted
         assert_user_affirmations(
@@ -1924,7 +2012,15 @@ fn reject_instruction() {
         // Test that the receiver can also reject the instruction
         let instruction_id2 = create_instruction(&alice, &bob, venue_counter, TICKER, amount);
 
-        assert_ok!(reject_instruction(&bob, instruction_id2,));
+        assert_ok!(reject_instruction(
+            &bob,
+            instruction_id2,
+            Some(RejectReason::Pricing)
+        ));
+        assert_eq!(
+            Settlement::instruction_rejection(instruction_id2),
+            Some((RejectReason::Pricing, None))
+        );
         next_block();
         // Instruction should've been deleted
         assert_user_affirmations(
@@ -2030,7 +2126,9 @@ fn reject_failed_instruction() {
             bob.origin(),
             instruction_id,
             PortfolioId::default_portfolio(bob.did),
-            1
+            1,
+            None,
+            None
         ));
 
         // Go to next block to have the scheduled execution run and ensure it has pruned the instruction.
@@ -2239,16 +2337,13 @@ fn basic_settlement_with_memo() {
                 asset: TICKER,
                 amount: amount
             }],
-            Some(InstructionMemo::default()),
+            Some(Memo::default()),
         ));
         alice.assert_all_balances_unchanged();
         bob.assert_all_balances_unchanged();
 
         // check that the memo was stored correctly
-        assert_eq!(
-            Settlement::memo(instruction_id).unwrap(),
-            InstructionMemo::default()
-        );
+        assert_eq!(Settlement::memo(instruction_id).unwrap(), Memo::default());
 
         assert_affirm_instruction_with_one_leg!(alice.origin(), instruction_id, alice.did);
 
@@ -2479,7 +2574,7 @@ fn add_nft_instruction_with_duplicated_nfts() {
                 None,
                 None,
                 legs,
-                Some(InstructionMemo::default()),
+                Some(Memo::default()),
             ),
             NFTError::DuplicatedNFTId
         );
@@ -2523,7 +2618,7 @@ fn add_nft_instruction_exceeding_nfts() {
                 None,
                 None,
                 legs,
-                Some(InstructionMemo::default()),
+                Some(Memo::default()),
             ),
             NFTError::MaxNumberOfNFTsPerLegExceeded
         );
@@ -2551,7 +2646,7 @@ fn add_nft_instruction() {
             None,
             None,
             legs,
-            Some(InstructionMemo::default()),
+            Some(Memo::default()),
         ));
     });
 }
@@ -2595,7 +2690,7 @@ fn add_and_affirm_nft_instruction() {
             None,
             legs,
             default_portfolio_vec(alice.did),
-            Some(InstructionMemo::default()),
+            Some(Memo::default()),
         ));
 
         // Before bob accepts the transaction balances must not be changed and the NFT must be locked.
@@ -2688,7 +2783,7 @@ fn add_and_affirm_nft_not_owned() {
                 None,
                 legs,
                 default_portfolio_vec(alice.did),
-                Some(InstructionMemo::default()),
+                Some(Memo::default()),
             ),
             PortfolioError::NFTNotFoundInPortfolio
         );
@@ -2745,7 +2840,7 @@ fn add_same_nft_different_legs() {
                 None,
                 legs,
                 default_portfolio_vec(alice.did),
-                Some(InstructionMemo::default()),
+                Some(Memo::default()),
             ),
             PortfolioError::NFTAlreadyLocked
         );
@@ -2787,7 +2882,7 @@ fn add_and_affirm_with_receipts_nfts() {
             None,
             None,
             legs,
-            Some(InstructionMemo::default()),
+            Some(Memo::default()),
         ));
         assert_noop!(
             Settlement::affirm_with_receipts(
@@ -2819,66 +2914,3493 @@ fn add_and_affirm_with_receipts_nfts() {
     });
 }
 
-#[track_caller]
-fn assert_instruction_details(
-    instruction_id: InstructionId,
-    details: Instruction<Moment, BlockNumber>,
-) {
-    assert_eq!(Settlement::instruction_details(instruction_id), details);
-}
+/// Edits the legs of a pending instruction, making sure leg ids are never reused.
+#[test]
+fn edit_instruction_legs() {
+    ExtBuilder::default().build().execute_with(|| {
+        let alice = User::new(AccountKeyring::Alice);
+        let bob = User::new(AccountKeyring::Bob);
+        let charlie = User::new(AccountKeyring::Charlie);
+        let venue_counter = create_token_and_venue(TICKER, alice);
+        let instruction_id = Settlement::instruction_counter();
+        let fungible_leg = |to: &User, amount: Balance| LegV2 {
+            from: PortfolioId::default_portfolio(alice.did),
+            to: PortfolioId::default_portfolio(to.did),
+            asset: LegAsset::Fungible {
+                ticker: TICKER,
+                amount,
+            },
+        };
 
-#[track_caller]
-fn assert_instruction_status(
-    instruction_id: InstructionId,
-    status: InstructionStatus<BlockNumber>,
-) {
-    assert_eq!(Settlement::instruction_status(instruction_id), status);
-}
+        assert_ok!(Settlement::add_instruction_with_memo_v2(
+            alice.origin(),
+            venue_counter,
+            SettlementType::SettleOnAffirmation,
+            None,
+            None,
+            vec![fungible_leg(&bob, 10), fungible_leg(&bob, 20)],
+            None,
+        ));
+        assert_eq!(
+            Settlement::instruction_leg_history(&instruction_id),
+            vec![
+                (LegId(0), LegRevision::default()),
+                (LegId(1), LegRevision::default())
+            ]
+        );
 
-#[track_caller]
-fn assert_balance(ticker: &Ticker, user: &User, balance: Balance) {
-    assert_eq!(Asset::balance_of(&ticker, user.did), balance);
-}
+        // Only the venue creator can edit the instruction, and only existing legs can be removed.
+        assert_noop!(
+            Settlement::edit_instruction_legs(bob.origin(), instruction_id, vec![], vec![], 2),
+            Error::Unauthorized
+        );
+        assert_noop!(
+            Settlement::edit_instruction_legs(
+                alice.origin(),
+                instruction_id,
+                vec![LegId(5)],
+                vec![],
+                2
+            ),
+            Error::LegNotFound
+        );
+        assert_noop!(
+            Settlement::edit_instruction_legs(alice.origin(), instruction_id, vec![], vec![], 1),
+            Error::LegCountTooSmall
+        );
 
-#[track_caller]
-fn assert_user_affirms(instruction_id: InstructionId, user: &User, status: AffirmationStatus) {
-    assert_eq!(
-        Settlement::user_affirmations(PortfolioId::default_portfolio(user.did), instruction_id),
-        status
-    );
+        // Replaces the first leg with a transfer to charlie.
+        assert_ok!(Settlement::edit_instruction_legs(
+            alice.origin(),
+            instruction_id,
+            vec![LegId(0)],
+            vec![fungible_leg(&charlie, 30)],
+            2
+        ));
+        assert_eq!(Settlement::instruction_revision(instruction_id), 1);
+        assert_eq!(
+            Settlement::instruction_leg_history(&instruction_id),
+            vec![
+                (
+                    LegId(0),
+                    LegRevision {
+                        added_in: 0,
+                        removed_in: Some(1)
+                    }
+                ),
+                (LegId(1), LegRevision::default()),
+                (
+                    LegId(2),
+                    LegRevision {
+                        added_in: 1,
+                        removed_in: None
+                    }
+                )
+            ]
+        );
+        assert_eq!(
+            Settlement::removed_instruction_legs(instruction_id, LegId(0)),
+            Some(fungible_leg(&bob, 10))
+        );
+        assert_eq!(
+            Settlement::get_instruction_leg(&instruction_id, &LegId(2)),
+            fungible_leg(&charlie, 30)
+        );
+        assert_affirms_pending(instruction_id, 3);
+        assert_user_affirms(instruction_id, &charlie, AffirmationStatus::Pending);
 
-    let affirms_received_status = match status {
-        AffirmationStatus::Pending => AffirmationStatus::Unknown,
-        AffirmationStatus::Affirmed => AffirmationStatus::Affirmed,
-        _ => return,
-    };
+        // Removing the newly added leg does not free its id.
+        assert_ok!(Settlement::edit_instruction_legs(
+            alice.origin(),
+            instruction_id,
+            vec![LegId(2)],
+            vec![fungible_leg(&bob, 5)],
+            2
+        ));
+        let changed_since_first_edit: Vec<LegId> =
+            Settlement::instruction_leg_history(&instruction_id)
+                .into_iter()
+                .filter(|(_, revision)| revision.changed_since(1))
+                .map(|(leg_id, _)| leg_id)
+                .collect();
+        assert_eq!(changed_since_first_edit, vec![LegId(2), LegId(3)]);
+        assert_affirms_pending(instruction_id, 2);
+        assert_eq!(
+            Settlement::user_affirmations(
+                PortfolioId::default_portfolio(charlie.did),
+                instruction_id
+            ),
+            AffirmationStatus::Unknown
+        );
 
-    assert_eq!(
-        Settlement::affirms_received(instruction_id, PortfolioId::default_portfolio(user.did)),
-        affirms_received_status
-    );
+        // Once a party has affirmed, the legs can no longer be edited.
+        assert_ok!(Settlement::affirm_instruction_v2(
+            alice.origin(),
+            instruction_id,
+            default_portfolio_vec(alice.did),
+            2,
+            0
+        ));
+        assert_noop!(
+            Settlement::edit_instruction_legs(alice.origin(), instruction_id, vec![], vec![], 2),
+            Error::UnexpectedAffirmationStatus
+        );
+    });
 }
 
-#[track_caller]
-fn assert_leg_status(instruction_id: InstructionId, leg: LegId, status: LegStatus<AccountId>) {
-    assert_eq!(
-        Settlement::instruction_leg_status(instruction_id, leg),
-        status
-    );
-}
+/// Amends a leg of a pending instruction as an edit, resetting the affirmations of its mediators.
+#[test]
+fn amend_instruction_leg() {
+    ExtBuilder::default().build().execute_with(|| {
+        let alice = User::new(AccountKeyring::Alice);
+        let bob = User::new(AccountKeyring::Bob);
+        let dave = User::new(AccountKeyring::Dave);
+        let venue_counter = create_token_and_venue(TICKER, alice);
+        let instruction_id = Settlement::instruction_counter();
+        let fungible_leg = |amount: Balance| LegV2 {
+            from: PortfolioId::default_portfolio(alice.did),
+            to: PortfolioId::default_portfolio(bob.did),
+            asset: LegAsset::Fungible {
+                ticker: TICKER,
+                amount,
+            },
+        };
 
-#[track_caller]
-fn assert_affirms_pending(instruction_id: InstructionId, pending: u64) {
-    assert_eq!(
-        Settlement::instruction_affirms_pending(instruction_id),
-        pending
-    );
+        assert_ok!(Settlement::add_instruction_with_mediators(
+            alice.origin(),
+            venue_counter,
+            SettlementType::SettleOnAffirmation,
+            None,
+            None,
+            vec![fungible_leg(10)],
+            None,
+            [dave.did].into_iter().collect(),
+        ));
+        assert_ok!(Settlement::affirm_instruction_as_mediator(
+            dave.origin(),
+            instruction_id,
+            1,
+            0
+        ));
+        assert_affirms_pending(instruction_id, 2);
+
+        // Only the venue creator can amend the instruction, and only existing legs can be amended.
+        assert_noop!(
+            Settlement::amend_instruction_leg(
+                bob.origin(),
+                instruction_id,
+                LegId(0),
+                fungible_leg(15),
+                1
+            ),
+            Error::Unauthorized
+        );
+        assert_noop!(
+            Settlement::amend_instruction_leg(
+                alice.origin(),
+                instruction_id,
+                LegId(1),
+                fungible_leg(15),
+                1
+            ),
+            Error::LegNotFound
+        );
+        assert_noop!(
+            Settlement::amend_instruction_leg(
+                alice.origin(),
+                instruction_id,
+                LegId(0),
+                fungible_leg(15),
+                0
+            ),
+            Error::LegCountTooSmall
+        );
+        assert_noop!(
+            Settlement::amend_instruction_leg(
+                alice.origin(),
+                instruction_id,
+                LegId(0),
+                fungible_leg(0),
+                1
+            ),
+            Error::ZeroAmount
+        );
+
+        // The amended leg is replaced by a leg with a fresh id, its consideration is removed
+        // and the mediator has to affirm again.
+        assert_ok!(Settlement::set_leg_consideration(
+            alice.origin(),
+            instruction_id,
+            LegId(0),
+            Some(LegConsideration {
+                currency: Ticker::from_slice_truncated(b"USD"),
+                amount: 1_000,
+            })
+        ));
+        assert_ok!(Settlement::amend_instruction_leg(
+            alice.origin(),
+            instruction_id,
+            LegId(0),
+            fungible_leg(15),
+            1
+        ));
+        assert_eq!(
+            Settlement::get_instruction_leg(&instruction_id, &LegId(1)),
+            fungible_leg(15)
+        );
+        assert_eq!(
+            Settlement::removed_instruction_legs(instruction_id, LegId(0)),
+            Some(fungible_leg(10))
+        );
+        assert_eq!(Settlement::instruction_revision(instruction_id), 1);
+        assert_eq!(
+            Settlement::instruction_leg_history(&instruction_id),
+            vec![
+                (
+                    LegId(0),
+                    LegRevision {
+                        added_in: 0,
+                        removed_in: Some(1)
+                    }
+                ),
+                (
+                    LegId(1),
+                    LegRevision {
+                        added_in: 1,
+                        removed_in: None
+                    }
+                )
+            ]
+        );
+        assert_eq!(
+            Settlement::instruction_leg_consideration(instruction_id, LegId(0)),
+            None
+        );
+        assert_eq!(
+            Settlement::instruction_mediators(instruction_id, dave.did),
+            AffirmationStatus::Pending
+        );
+        assert_affirms_pending(instruction_id, 3);
+
+        // Once a counterparty has affirmed, the legs can no longer be amended.
+        assert_ok!(Settlement::affirm_instruction_v2(
+            alice.origin(),
+            instruction_id,
+            default_portfolio_vec(alice.did),
+            1,
+            0
+        ));
+        assert_noop!(
+            Settlement::amend_instruction_leg(
+                alice.origin(),
+                instruction_id,
+                LegId(1),
+                fungible_leg(20),
+                1
+            ),
+            Error::UnexpectedAffirmationStatus
+        );
+    });
 }
 
-#[track_caller]
-fn assert_locked_assets(ticker: &Ticker, user: &User, num_of_assets: Balance) {
-    assert_eq!(
+/// Records the consideration of a leg, which is removed with the instruction.
+#[test]
+fn leg_consideration() {
+    test_with_cdd_provider(|eve| {
+        let alice = User::new(AccountKeyring::Alice);
+        let bob = User::new(AccountKeyring::Bob);
+        let venue_counter = create_token_and_venue(TICKER, alice);
+        provide_scope_claim_to_multiple_parties(&[alice.did, bob.did], TICKER, eve);
+        let instruction_id = Settlement::instruction_counter();
+        let consideration = LegConsideration {
+            currency: Ticker::from_slice_truncated(b"USD"),
+            amount: 1_000,
+        };
+
+        assert_ok!(Settlement::add_instruction_with_memo_v2(
+            alice.origin(),
+            venue_counter,
+            SettlementType::SettleOnAffirmation,
+            None,
+            None,
+            vec![LegV2 {
+                from: PortfolioId::default_portfolio(alice.did),
+                to: PortfolioId::default_portfolio(bob.did),
+                asset: LegAsset::Fungible {
+                    ticker: TICKER,
+                    amount: 10,
+                },
+            }],
+            None,
+        ));
+
+        assert_noop!(
+            Settlement::set_leg_consideration(
+                bob.origin(),
+                instruction_id,
+                LegId(0),
+                Some(consideration)
+            ),
+            Error::Unauthorized
+        );
+        assert_noop!(
+            Settlement::set_leg_consideration(
+                alice.origin(),
+                instruction_id,
+                LegId(1),
+                Some(consideration)
+            ),
+            Error::LegNotFound
+        );
+        assert_noop!(
+            Settlement::set_leg_consideration(
+                alice.origin(),
+                instruction_id,
+                LegId(0),
+                Some(LegConsideration {
+                    amount: 0,
+                    ..consideration
+                })
+            ),
+            Error::ZeroAmount
+        );
+        assert_ok!(Settlement::set_leg_consideration(
+            alice.origin(),
+            instruction_id,
+            LegId(0),
+            Some(consideration)
+        ));
+        assert_eq!(
+            Settlement::instruction_leg_consideration(instruction_id, LegId(0)),
+            Some(consideration)
+        );
+
+        // The consideration can't change once a counterparty has affirmed.
+        assert_ok!(Settlement::affirm_instruction_v2(
+            alice.origin(),
+            instruction_id,
+            default_portfolio_vec(alice.did),
+            1,
+            0
+        ));
+        assert_noop!(
+            Settlement::set_leg_consideration(alice.origin(), instruction_id, LegId(0), None),
+            Error::UnexpectedAffirmationStatus
+        );
+
+        // The consideration is not transferred, and is removed with the executed instruction.
+        assert_ok!(Settlement::affirm_instruction_v2(
+            bob.origin(),
+            instruction_id,
+            default_portfolio_vec(bob.did),
+            1,
+            0
+        ));
+        next_block();
+        assert_eq!(Asset::balance_of(&TICKER, bob.did), 10);
+        assert_eq!(
+            Settlement::instruction_leg_consideration(instruction_id, LegId(0)),
+            None
+        );
+    });
+}
+
+/// Off-hours executions are deferred to the next market opening, unless deferral is disabled
+/// or the instruction expires before.
+#[test]
+fn market_hours() {
+    ExtBuilder::default().build().execute_with(|| {
+        let alice = User::new(AccountKeyring::Alice);
+        let bob = User::new(AccountKeyring::Bob);
+        let venue_counter = create_token_and_venue(TICKER, alice);
+        let market_hours = MarketHours {
+            period: 10,
+            open: 5,
+            close: 8,
+            priority: 50,
+            defer_off_hours: true,
+        };
+        let add_instruction = |block_number: BlockNumber| {
+            assert_ok!(Settlement::add_instruction_with_memo_v2(
+                alice.origin(),
+                venue_counter,
+                SettlementType::SettleOnBlock(block_number),
+                None,
+                None,
+                vec![LegV2 {
+                    from: PortfolioId::default_portfolio(alice.did),
+                    to: PortfolioId::default_portfolio(bob.did),
+                    asset: LegAsset::Fungible {
+                        ticker: TICKER,
+                        amount: 10,
+                    },
+                }],
+                None,
+            ));
+        };
+        let scheduled =
+            |block_number: BlockNumber| scheduler::Agenda::<TestStorage>::get(block_number).len();
+        System::set_block_number(10);
+
+        assert_noop!(
+            Settlement::set_market_hours(alice.origin(), Some(market_hours)),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            Settlement::set_market_hours(
+                root(),
+                Some(MarketHours {
+                    close: 5,
+                    ..market_hours
+                })
+            ),
+            Error::InvalidMarketHours
+        );
+        assert_ok!(Settlement::set_market_hours(root(), Some(market_hours)));
+
+        // Off-hours executions are deferred to the next opening.
+        add_instruction(12);
+        assert_eq!(scheduled(12), 0);
+        assert_eq!(scheduled(15), 1);
+
+        // Executions during market hours are not deferred.
+        add_instruction(17);
+        assert_eq!(scheduled(17), 1);
+
+        // Executions are not deferred past the expiry of their instruction.
+        assert_ok!(Settlement::set_venue_default_expiry(
+            alice.origin(),
+            venue_counter,
+            Some(10)
+        ));
+        add_instruction(18);
+        assert_eq!(scheduled(25), 0);
+        // The expiry and the execution of the instruction.
+        assert_eq!(scheduled(20), 2);
+        assert_ok!(Settlement::set_venue_default_expiry(
+            alice.origin(),
+            venue_counter,
+            None
+        ));
+
+        // Without deferral, off-hours executions happen as planned.
+        assert_ok!(Settlement::set_market_hours(
+            root(),
+            Some(MarketHours {
+                defer_off_hours: false,
+                ..market_hours
+            })
+        ));
+        add_instruction(19);
+        assert_eq!(scheduled(19), 1);
+    });
+}
+
+/// Instructions scheduled for the same block are executed in rounds across venues.
+#[test]
+fn execution_rounds() {
+    ExtBuilder::default().build().execute_with(|| {
+        let alice = User::new(AccountKeyring::Alice);
+        let bob = User::new(AccountKeyring::Bob);
+        let venue_a = create_token_and_venue(TICKER, alice);
+        let venue_b = create_venue(alice);
+        let execution_at = System::block_number() + 5;
+        let add_instruction = |venue_id| {
+            let id = Settlement::instruction_counter();
+            assert_ok!(Settlement::add_instruction_with_memo_v2(
+                alice.origin(),
+                venue_id,
+                SettlementType::SettleOnBlock(execution_at),
+                None,
+                None,
+                vec![LegV2 {
+                    from: PortfolioId::default_portfolio(alice.did),
+                    to: PortfolioId::default_portfolio(bob.did),
+                    asset: LegAsset::Fungible {
+                        ticker: TICKER,
+                        amount: 10,
+                    },
+                }],
+                None,
+            ));
+            id
+        };
+        let scheduled_rounds = || {
+            System::events()
+                .iter()
+                .filter_map(|record| match &record.event {
+                    super::storage::EventTest::Settlement(
+                        pallet_settlement::RawEvent::InstructionExecutionScheduled(id, _, round),
+                    ) => Some((*id, *round)),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+        };
+
+        // The second instruction of venue A comes after the first instruction of venue B.
+        let a1 = add_instruction(venue_a);
+        let a2 = add_instruction(venue_a);
+        let b1 = add_instruction(venue_b);
+        assert_eq!(scheduled_rounds(), vec![(a1, 0), (a2, 1), (b1, 0)]);
+        assert_eq!(Settlement::execution_rounds(execution_at, venue_a), 2);
+        assert_eq!(Settlement::execution_rounds(execution_at, venue_b), 1);
+
+        // The rounds are cleared once the block starts.
+        System::set_block_number(execution_at);
+        Settlement::on_initialize(execution_at);
+        assert_eq!(Settlement::execution_rounds(execution_at, venue_a), 0);
+        assert_eq!(Settlement::execution_rounds(execution_at, venue_b), 0);
+    });
+}
+
+/// Instructions can be tagged at their creation and looked up by tag.
+#[test]
+fn instruction_tags() {
+    ExtBuilder::default().build().execute_with(|| {
+        let alice = User::new(AccountKeyring::Alice);
+        let bob = User::new(AccountKeyring::Bob);
+        let venue_counter = create_token_and_venue(TICKER, alice);
+        let trade_tag = InstructionTag(*b"TRADE-0000000001");
+        let desk_tag = InstructionTag(*b"DESK-FX-00000000");
+        let add_instruction = |tags: Vec<InstructionTag>| {
+            Settlement::add_instruction_with_tags(
+                alice.origin(),
+                venue_counter,
+                SettlementType::SettleOnAffirmation,
+                None,
+                None,
+                vec![LegV2 {
+                    from: PortfolioId::default_portfolio(alice.did),
+                    to: PortfolioId::default_portfolio(bob.did),
+                    asset: LegAsset::Fungible {
+                        ticker: TICKER,
+                        amount: 10,
+                    },
+                }],
+                None,
+                tags.into_iter().collect(),
+            )
+        };
+
+        let too_many_tags = (0..=pallet_settlement::MAX_INSTRUCTION_TAGS)
+            .map(|i| InstructionTag([i as u8; 16]))
+            .collect();
+        assert_noop!(
+            add_instruction(too_many_tags),
+            Error::TooManyInstructionTags
+        );
+
+        let first_id = Settlement::instruction_counter();
+        assert_ok!(add_instruction(vec![trade_tag, desk_tag]));
+        let second_id = Settlement::instruction_counter();
+        assert_ok!(add_instruction(vec![desk_tag]));
+        assert_ok!(add_instruction(vec![]));
+
+        assert_eq!(
+            Settlement::instruction_tags(first_id),
+            vec![desk_tag, trade_tag]
+        );
+        assert_eq!(Settlement::instructions_by_tag(&trade_tag), vec![first_id]);
+        assert_eq!(
+            Settlement::instructions_by_tag(&desk_tag),
+            vec![first_id, second_id]
+        );
+
+        // Tags are kept once the instruction is settled, for reconciliation.
+        assert_affirm_instruction_with_one_leg!(alice.origin(), first_id, alice.did);
+        assert_affirm_instruction_with_one_leg!(bob.origin(), first_id, bob.did);
+        next_block();
+        assert_instruction_status(first_id, InstructionStatus::Success(System::block_number()));
+        assert_eq!(Settlement::instructions_by_tag(&trade_tag), vec![first_id]);
+    });
+}
+
+#[test]
+fn batched_execution() {
+    ExtBuilder::default().build().execute_with(|| {
+        let alice = User::new(AccountKeyring::Alice);
+        let bob = User::new(AccountKeyring::Bob);
+        let venue_counter = create_token_and_venue(TICKER, alice);
+        let alice_balance = Asset::balance_of(&TICKER, alice.did);
+        let alice_portfolio = PortfolioId::default_portfolio(alice.did);
+        let leg = LegV2 {
+            from: alice_portfolio,
+            to: PortfolioId::default_portfolio(bob.did),
+            asset: LegAsset::Fungible {
+                ticker: TICKER,
+                amount: 10,
+            },
+        };
+        let add_instruction = |settlement_type| {
+            let instruction_id = Settlement::instruction_counter();
+            assert_ok!(Settlement::add_instruction(
+                alice.origin(),
+                venue_counter,
+                settlement_type,
+                None,
+                None,
+                vec![leg.clone(), leg.clone(), leg.clone()],
+                None,
+            ));
+            assert_affirm_instruction!(alice.origin(), instruction_id, alice.did, 3);
+            assert_affirm_instruction!(bob.origin(), instruction_id, bob.did, 3);
+            instruction_id
+        };
+
+        // Only a single leg fits in a block.
+        SETTLEMENT_EXECUTION_WEIGHT.with(|w| *w.borrow_mut() = Some(Weight::zero()));
+
+        // Instructions not allowing partial execution are still executed at once.
+        let instruction_id = add_instruction(SettlementType::SettleOnAffirmation);
+        next_block();
+        assert_instruction_status(
+            instruction_id,
+            InstructionStatus::Success(System::block_number()),
+        );
+        assert_balance(&TICKER, &alice, alice_balance - 30);
+        assert_balance(&TICKER, &bob, 30);
+
+        let instruction_id = add_instruction(SettlementType::SettleOnAffirmationPartial);
+        next_block();
+        assert_balance(&TICKER, &alice, alice_balance - 40);
+        assert_balance(&TICKER, &bob, 40);
+        assert_eq!(
+            Settlement::instruction_execution_progress(instruction_id)
+                .unwrap()
+                .next_leg_id,
+            LegId(1)
+        );
+        assert!(System::events().iter().any(|record| matches!(
+            &record.event,
+            super::storage::EventTest::Settlement(
+                pallet_settlement::RawEvent::InstructionExecutionContinued(_, id, LegId(1))
+            ) if *id == instruction_id
+        )));
+        assert_noop!(
+            Settlement::withdraw_affirmation(
+                alice.origin(),
+                instruction_id,
+                default_portfolio_vec(alice.did),
+                3
+            ),
+            Error::InstructionExecutionInProgress
+        );
+
+        next_block();
+        assert_balance(&TICKER, &alice, alice_balance - 50);
+        assert_eq!(Portfolio::locked_assets(alice_portfolio, &TICKER), 10);
+
+        // Rejecting the instruction keeps the executed legs and releases the lock of the last one.
+        assert_ok!(Settlement::reject_instruction(
+            alice.origin(),
+            instruction_id,
+            alice_portfolio,
+            3,
+            None,
+            None,
+        ));
+        assert_eq!(
+            Settlement::instruction_status(instruction_id),
+            InstructionStatus::Rejected(System::block_number())
+        );
+        assert_eq!(Portfolio::locked_assets(alice_portfolio, &TICKER), 0);
+        assert_balance(&TICKER, &alice, alice_balance - 50);
+        assert_balance(&TICKER, &bob, 50);
+        assert_eq!(
+            Settlement::instruction_execution_progress(instruction_id),
+            None
+        );
+    });
+}
+
+#[test]
+fn migrate_instruction_legs() {
+    ExtBuilder::default().build().execute_with(|| {
+        let alice = User::new(AccountKeyring::Alice);
+        let bob = User::new(AccountKeyring::Bob);
+        let charlie = User::new(AccountKeyring::Charlie);
+        let venue_counter = create_token_and_venue(TICKER, alice);
+        let leg = Leg {
+            from: PortfolioId::default_portfolio(alice.did),
+            to: PortfolioId::default_portfolio(bob.did),
+            asset: TICKER,
+            amount: 10,
+        };
+
+        // Deprecated instructions are stored with the new legs.
+        let instruction_id = Settlement::instruction_counter();
+        assert_ok!(Settlement::add_instruction(
+            alice.origin(),
+            venue_counter,
+            SettlementType::SettleOnAffirmation,
+            None,
+            None,
+            vec![leg.clone()],
+        ));
+        assert_eq!(
+            pallet_settlement::InstructionLegs::iter_prefix(instruction_id).count(),
+            0
+        );
+        assert_eq!(
+            Settlement::instruction_legsv2(instruction_id, LegId(0)),
+            LegV2::from(leg.clone())
+        );
+
+        // Legs stored before the upgrade are read without being moved.
+        let legacy_id = InstructionId(100);
+        pallet_settlement::InstructionLegs::insert(legacy_id, LegId(0), leg.clone());
+        pallet_settlement::InstructionLegs::insert(legacy_id, LegId(1), leg.clone());
+        assert_eq!(
+            Settlement::get_instruction_legs(&legacy_id),
+            vec![
+                (LegId(0), LegV2::from(leg.clone())),
+                (LegId(1), LegV2::from(leg.clone()))
+            ]
+        );
+        assert_eq!(
+            pallet_settlement::InstructionLegs::iter_prefix(legacy_id).count(),
+            2
+        );
+
+        // They are moved by anyone, given the number of legs to migrate.
+        assert_noop!(
+            Settlement::migrate_instruction_legs(
+                charlie.origin(),
+                vec![legacy_id, instruction_id],
+                1
+            ),
+            Error::LegCountTooSmall
+        );
+        assert_ok!(Settlement::migrate_instruction_legs(
+            charlie.origin(),
+            vec![legacy_id, instruction_id],
+            2
+        ));
+        assert_eq!(
+            pallet_settlement::InstructionLegs::iter_prefix(legacy_id).count(),
+            0
+        );
+        assert_eq!(
+            Settlement::instruction_legsv2(legacy_id, LegId(1)),
+            LegV2::from(leg)
+        );
+    });
+}
+
+#[test]
+fn portfolio_alias() {
+    ExtBuilder::default().build().execute_with(|| {
+        let alice = User::new(AccountKeyring::Alice);
+        let bob = User::new(AccountKeyring::Bob);
+        let charlie = User::new(AccountKeyring::Charlie);
+        let venue_counter = create_token_and_venue(TICKER, alice);
+        let bob_portfolio = PortfolioId::default_portfolio(bob.did);
+        let salt = [7; 32];
+        let alias = PortfolioAlias::new(&bob_portfolio, &salt);
+
+        let instruction_id = Settlement::instruction_counter();
+        assert_ok!(Settlement::add_instruction(
+            alice.origin(),
+            venue_counter,
+            SettlementType::SettleOnAffirmation,
+            None,
+            None,
+            vec![Leg {
+                from: PortfolioId::default_portfolio(alice.did),
+                to: alias.placeholder(),
+                asset: TICKER,
+                amount: 10,
+            }],
+        ));
+        let reveal = |user: User, salt| {
+            Settlement::reveal_portfolio_alias(
+                user.origin(),
+                instruction_id,
+                alias,
+                bob_portfolio,
+                salt,
+                1,
+                0,
+            )
+        };
+
+        // The alias is only revealed when the instruction can be executed.
+        assert_noop!(reveal(bob, salt), Error::InstructionNotAffirmed);
+        assert_affirm_instruction_with_one_leg!(alice.origin(), instruction_id, alice.did);
+        next_block();
+        assert_instruction_status(instruction_id, InstructionStatus::Pending);
+
+        // Only the custodian of the committed portfolio can reveal it.
+        assert_noop!(reveal(bob, [8; 32]), Error::InvalidPortfolioAlias);
+        assert_noop!(reveal(charlie, salt), PortfolioError::UnauthorizedCustodian);
+
+        // Revealing the alias affirms and executes the instruction.
+        assert_ok!(reveal(bob, salt));
+        assert_instruction_status(
+            instruction_id,
+            InstructionStatus::Success(System::block_number()),
+        );
+        assert_balance(&TICKER, &bob, 10);
+    });
+}
+
+#[test]
+fn instruction_status_retention() {
+    ExtBuilder::default().build().execute_with(|| {
+        let alice = User::new(AccountKeyring::Alice);
+        let bob = User::new(AccountKeyring::Bob);
+        let venue_counter = create_token_and_venue(TICKER, alice);
+        INSTRUCTION_STATUS_RETENTION.with(|r| *r.borrow_mut() = Some(2));
+
+        let instruction_id = Settlement::instruction_counter();
+        assert_ok!(Settlement::add_instruction(
+            alice.origin(),
+            venue_counter,
+            SettlementType::SettleOnAffirmation,
+            None,
+            None,
+            vec![Leg {
+                from: PortfolioId::default_portfolio(alice.did),
+                to: PortfolioId::default_portfolio(bob.did),
+                asset: TICKER,
+                amount: 10,
+            }],
+        ));
+        assert_affirm_instruction_with_one_leg!(alice.origin(), instruction_id, alice.did);
+        assert_affirm_instruction_with_one_leg!(bob.origin(), instruction_id, bob.did);
+        next_block();
+        let settled_at = System::block_number();
+        assert_instruction_status(instruction_id, InstructionStatus::Success(settled_at));
+
+        // The status and audit digest are kept during the retention period.
+        let max_weight = Weight::from_ref_time(u64::MAX);
+        Settlement::on_idle(settled_at + 2, max_weight);
+        assert_instruction_status(instruction_id, InstructionStatus::Success(settled_at));
+        assert!(Settlement::instruction_audit_digest(instruction_id).is_some());
+
+        // Pruning stops when the block runs out of weight.
+        assert_eq!(
+            Settlement::on_idle(settled_at + 3, Weight::zero()),
+            Weight::zero()
+        );
+        assert_instruction_status(instruction_id, InstructionStatus::Success(settled_at));
+
+        Settlement::on_idle(settled_at + 3, max_weight);
+        assert_instruction_status(instruction_id, InstructionStatus::Unknown);
+        assert_eq!(Settlement::instruction_audit_export(instruction_id), None);
+        assert_eq!(Settlement::instruction_audit_digest(instruction_id), None);
+        assert!(System::events().iter().any(|record| matches!(
+            &record.event,
+            super::storage::EventTest::Settlement(
+                pallet_settlement::RawEvent::InstructionStatusesPruned(ids)
+            ) if *ids == vec![instruction_id]
+        )));
+    });
+}
+
+#[test]
+fn rejected_instruction_retention() {
+    ExtBuilder::default().build().execute_with(|| {
+        let alice = User::new(AccountKeyring::Alice);
+        let bob = User::new(AccountKeyring::Bob);
+        let venue_counter = create_token_and_venue(TICKER, alice);
+        REJECTED_INSTRUCTION_RETENTION.with(|r| *r.borrow_mut() = 2);
+
+        let instruction_id = create_instruction(&alice, &bob, venue_counter, TICKER, 10);
+        assert_locked_assets(&TICKER, &alice, 10);
+        assert_ok!(Settlement::reject_instruction(
+            bob.origin(),
+            instruction_id,
+            PortfolioId::default_portfolio(bob.did),
+            1,
+            Some(RejectReason::Pricing),
+            None,
+        ));
+        let rejected_at = System::block_number();
+        assert_instruction_status(instruction_id, InstructionStatus::Rejected(rejected_at));
+        assert_locked_assets(&TICKER, &alice, 0);
+
+        // Legs and affirmations are kept for inspection, but the instruction can't be used anymore.
+        assert_eq!(Settlement::get_instruction_legs(&instruction_id).len(), 1);
+        assert_eq!(
+            Settlement::affirms_received(instruction_id, PortfolioId::default_portfolio(alice.did)),
+            AffirmationStatus::Affirmed
+        );
+        assert_noop!(
+            Settlement::reject_instruction(
+                alice.origin(),
+                instruction_id,
+                PortfolioId::default_portfolio(alice.did),
+                1,
+                None,
+                None,
+            ),
+            Error::InstructionNotPending
+        );
+        assert_noop!(
+            Settlement::affirm_instruction(
+                bob.origin(),
+                instruction_id,
+                default_portfolio_vec(bob.did),
+                1
+            ),
+            Error::InstructionNotPending
+        );
+
+        let max_weight = Weight::from_ref_time(u64::MAX);
+        Settlement::on_idle(rejected_at + 2, max_weight);
+        assert_eq!(Settlement::get_instruction_legs(&instruction_id).len(), 1);
+
+        Settlement::on_idle(rejected_at + 3, max_weight);
+        assert!(Settlement::get_instruction_legs(&instruction_id).is_empty());
+        assert_eq!(
+            Settlement::affirms_received(instruction_id, PortfolioId::default_portfolio(alice.did)),
+            AffirmationStatus::Unknown
+        );
+        assert_instruction_status(instruction_id, InstructionStatus::Rejected(rejected_at));
+        assert!(System::events().iter().any(|record| matches!(
+            &record.event,
+            super::storage::EventTest::Settlement(
+                pallet_settlement::RawEvent::RejectedInstructionsPruned(ids)
+            ) if *ids == vec![instruction_id]
+        )));
+    });
+}
+
+#[test]
+fn long_instruction_memo() {
+    ExtBuilder::default().build().execute_with(|| {
+        let alice = User::new(AccountKeyring::Alice);
+        let bob = User::new(AccountKeyring::Bob);
+        let venue_counter = create_token_and_venue(TICKER, alice);
+        let legs = vec![LegV2 {
+            from: PortfolioId::default_portfolio(alice.did),
+            to: PortfolioId::default_portfolio(bob.did),
+            asset: LegAsset::Fungible {
+                ticker: TICKER,
+                amount: 10,
+            },
+        }];
+        let add_instruction = |memo: Memo| {
+            Settlement::add_instruction_with_memo_v2(
+                alice.origin(),
+                venue_counter,
+                SettlementType::SettleOnAffirmation,
+                None,
+                None,
+                legs.clone(),
+                Some(memo),
+            )
+        };
+
+        // Memos longer than `MAX_MEMO_LEN` are rejected.
+        assert_noop!(
+            add_instruction(Memo(vec![7u8; MAX_MEMO_LEN + 1])),
+            Error::MemoTooLong
+        );
+
+        let memo = Memo(vec![7u8; MAX_MEMO_LEN]);
+        let instruction_id = Settlement::instruction_counter();
+        assert_ok!(add_instruction(memo.clone()));
+        assert_eq!(Settlement::memo(instruction_id), Some(memo));
+
+        // Rejection memos are bounded as well.
+        let portfolio = PortfolioId::default_portfolio(bob.did);
+        assert_noop!(
+            Settlement::reject_instruction_v2(
+                bob.origin(),
+                instruction_id,
+                portfolio,
+                1,
+                0,
+                None,
+                Some(Memo(vec![7u8; MAX_MEMO_LEN + 1]))
+            ),
+            Error::MemoTooLong
+        );
+        assert_ok!(Settlement::reject_instruction_v2(
+            bob.origin(),
+            instruction_id,
+            portfolio,
+            1,
+            0,
+            None,
+            Some(Memo(b"ISIN US0378331005 / TRADE 42 / CPTY 7".to_vec()))
+        ));
+    });
+}
+
+#[test]
+fn requested_instruction() {
+    ExtBuilder::default().build().execute_with(|| {
+        let alice = User::new(AccountKeyring::Alice);
+        let bob = User::new(AccountKeyring::Bob);
+        let charlie = User::new(AccountKeyring::Charlie);
+        let venue_counter = create_token_and_venue(TICKER, alice);
+        let legs = vec![LegV2 {
+            from: PortfolioId::default_portfolio(alice.did),
+            to: PortfolioId::default_portfolio(bob.did),
+            asset: LegAsset::Fungible {
+                ticker: TICKER,
+                amount: 10,
+            },
+        }];
+        let request_instruction = |user: User| {
+            Settlement::request_instruction(
+                user.origin(),
+                venue_counter,
+                SettlementType::SettleOnAffirmation,
+                None,
+                None,
+                legs.clone(),
+                Some(Memo(b"TRADE 42".to_vec())),
+            )
+        };
+
+        // Only counterparties can request an instruction.
+        assert_noop!(request_instruction(charlie), Error::RequesterIsNotAParty);
+
+        let request_id = Settlement::instruction_request_counter();
+        assert_ok!(request_instruction(bob));
+        assert_eq!(
+            Settlement::instruction_request(request_id).map(|request| request.requester),
+            Some(bob.did)
+        );
+
+        // Only the venue creator can approve the request, with the right number of transfers.
+        assert_noop!(
+            Settlement::approve_requested_instruction(bob.origin(), request_id, 1, 0),
+            Error::Unauthorized
+        );
+        assert_noop!(
+            Settlement::approve_requested_instruction(alice.origin(), request_id, 0, 0),
+            Error::LegCountTooSmall
+        );
+        let instruction_id = Settlement::instruction_counter();
+        assert_ok!(Settlement::approve_requested_instruction(
+            alice.origin(),
+            request_id,
+            1,
+            0
+        ));
+        assert_eq!(Settlement::instruction_request(request_id), None);
+        assert_instruction_status(instruction_id, InstructionStatus::Pending);
+        assert_eq!(
+            Settlement::get_instruction_legs(&instruction_id),
+            vec![(LegId(0), legs[0].clone())]
+        );
+        assert_eq!(
+            Settlement::memo(instruction_id),
+            Some(Memo(b"TRADE 42".to_vec()))
+        );
+        assert_noop!(
+            Settlement::approve_requested_instruction(alice.origin(), request_id, 1, 0),
+            Error::UnknownInstructionRequest
+        );
+
+        // A request can be withdrawn by its requester or declined by the venue creator.
+        let request_id = Settlement::instruction_request_counter();
+        assert_ok!(request_instruction(bob));
+        assert_noop!(
+            Settlement::remove_instruction_request(charlie.origin(), request_id),
+            Error::Unauthorized
+        );
+        assert_ok!(Settlement::remove_instruction_request(
+            alice.origin(),
+            request_id
+        ));
+        assert_eq!(Settlement::instruction_request(request_id), None);
+
+        // Expired requests can't be approved, and can be removed by anyone.
+        let request_id = Settlement::instruction_request_counter();
+        assert_ok!(request_instruction(bob));
+        System::set_block_number(System::block_number() + InstructionRequestLifetime::get() + 1);
+        assert_noop!(
+            Settlement::approve_requested_instruction(alice.origin(), request_id, 1, 0),
+            Error::InstructionRequestExpired
+        );
+        assert_ok!(Settlement::remove_instruction_request(
+            charlie.origin(),
+            request_id
+        ));
+        assert_eq!(
+            Settlement::venue_instruction_requests_count(venue_counter),
+            0
+        );
+
+        // A venue has a limited number of pending requests.
+        let request_id = Settlement::instruction_request_counter();
+        for _ in 0..MAX_VENUE_INSTRUCTION_REQUESTS {
+            assert_ok!(request_instruction(bob));
+        }
+        assert_noop!(request_instruction(bob), Error::TooManyInstructionRequests);
+        assert_ok!(Settlement::remove_instruction_request(
+            bob.origin(),
+            request_id
+        ));
+        assert_ok!(request_instruction(bob));
+    });
+}
+
+#[test]
+fn encrypted_memo_access_list() {
+    ExtBuilder::default().build().execute_with(|| {
+        let alice = User::new(AccountKeyring::Alice);
+        let bob = User::new(AccountKeyring::Bob);
+        let charlie = User::new(AccountKeyring::Charlie);
+        let venue_counter = create_token_and_venue(TICKER, alice);
+        let legs = vec![LegV2 {
+            from: PortfolioId::default_portfolio(alice.did),
+            to: PortfolioId::default_portfolio(bob.did),
+            asset: LegAsset::Fungible {
+                ticker: TICKER,
+                amount: 10,
+            },
+        }];
+        let add_instruction = |memo: Option<Memo>| {
+            let instruction_id = Settlement::instruction_counter();
+            assert_ok!(Settlement::add_instruction_with_memo_v2(
+                alice.origin(),
+                venue_counter,
+                SettlementType::SettleOnAffirmation,
+                None,
+                None,
+                legs.clone(),
+                memo,
+            ));
+            instruction_id
+        };
+        let wrapped_key = |did: IdentityId| (did, WrappedMemoKey(did.encode()));
+
+        // An access list requires a memo commitment.
+        let no_memo_id = add_instruction(None);
+        assert_noop!(
+            Settlement::set_memo_access_list(alice.origin(), no_memo_id, vec![bob.did]),
+            Error::InstructionHasNoMemo
+        );
+
+        let instruction_id = add_instruction(Some(Memo(vec![7u8; 32])));
+        assert_noop!(
+            Settlement::set_memo_access_list(bob.origin(), instruction_id, vec![bob.did]),
+            Error::Unauthorized
+        );
+        assert_ok!(Settlement::set_memo_access_list(
+            alice.origin(),
+            instruction_id,
+            vec![charlie.did, bob.did, bob.did]
+        ));
+        let mut expected_list = vec![bob.did, charlie.did];
+        expected_list.sort();
+        assert_eq!(Settlement::memo_access_list(instruction_id), expected_list);
+
+        // Keys can only be wrapped for identities of the access list.
+        assert_noop!(
+            Settlement::wrap_memo_keys(
+                alice.origin(),
+                instruction_id,
+                vec![wrapped_key(alice.did)]
+            ),
+            Error::NotInMemoAccessList
+        );
+        assert_ok!(Settlement::wrap_memo_keys(
+            alice.origin(),
+            instruction_id,
+            vec![wrapped_key(bob.did), wrapped_key(charlie.did)]
+        ));
+        assert_eq!(
+            Settlement::memo_key_wraps(instruction_id, bob.did),
+            Some(wrapped_key(bob.did).1)
+        );
+
+        // Removing an identity from the access list deletes its wrapped key.
+        assert_ok!(Settlement::set_memo_access_list(
+            alice.origin(),
+            instruction_id,
+            vec![bob.did]
+        ));
+        assert_eq!(
+            Settlement::memo_key_wraps(instruction_id, charlie.did),
+            None
+        );
+        assert!(Settlement::memo_key_wraps(instruction_id, bob.did).is_some());
+
+        // The access list is capped.
+        assert_noop!(
+            Settlement::set_memo_access_list(
+                alice.origin(),
+                instruction_id,
+                (0..=MAX_MEMO_READERS as u128)
+                    .map(IdentityId::from)
+                    .collect()
+            ),
+            Error::TooManyMemoReaders
+        );
+
+        // The access list and wrapped keys are deleted with the instruction.
+        assert_ok!(Settlement::reject_instruction(
+            alice.origin(),
+            instruction_id,
+            PortfolioId::default_portfolio(alice.did),
+            legs.len() as u32,
+            None,
+            None,
+        ));
+        assert!(Settlement::memo_access_list(instruction_id).is_empty());
+        assert_eq!(Settlement::memo_key_wraps(instruction_id, bob.did), None);
+    });
+}
+
+#[test]
+fn instruction_expiry() {
+    test_with_cdd_provider(|eve| {
+        let alice = User::new(AccountKeyring::Alice);
+        let bob = User::new(AccountKeyring::Bob);
+        let venue_counter = create_token_and_venue(TICKER, alice);
+        provide_scope_claim_to_multiple_parties(&[alice.did, bob.did], TICKER, eve);
+        let amount = 100u128;
+        let legs = vec![LegV2 {
+            from: PortfolioId::default_portfolio(alice.did),
+            to: PortfolioId::default_portfolio(bob.did),
+            asset: LegAsset::Fungible {
+                ticker: TICKER,
+                amount,
+            },
+        }];
+
+        // Only the venue creator can set a non-zero default expiry.
+        assert_noop!(
+            Settlement::set_venue_default_expiry(bob.origin(), venue_counter, Some(5)),
+            Error::Unauthorized
+        );
+        assert_noop!(
+            Settlement::set_venue_default_expiry(alice.origin(), venue_counter, Some(0)),
+            Error::InvalidExpiry
+        );
+        assert_ok!(Settlement::set_venue_default_expiry(
+            alice.origin(),
+            venue_counter,
+            Some(5)
+        ));
+
+        let instruction_id = Settlement::instruction_counter();
+        let expiry_block = System::block_number() + 5;
+        assert_ok!(Settlement::add_instruction_with_memo_v2(
+            alice.origin(),
+            venue_counter,
+            SettlementType::SettleOnAffirmation,
+            None,
+            None,
+            legs,
+            None,
+        ));
+        assert_eq!(
+            Settlement::instruction_details(instruction_id).expiry_block,
+            Some(expiry_block)
+        );
+        assert_affirm_instruction_with_one_leg!(alice.origin(), instruction_id, alice.did);
+        assert_locked_assets(&TICKER, &alice, amount);
+
+        // Bob never affirms, so the instruction expires and the locked assets are released.
+        for _ in System::block_number()..expiry_block {
+            next_block();
+        }
+        assert_instruction_status(instruction_id, InstructionStatus::Rejected(expiry_block));
+        assert_affirms_pending(instruction_id, 0);
+        assert_user_affirms(instruction_id, &alice, AffirmationStatus::Unknown);
+        assert_locked_assets(&TICKER, &alice, 0);
+        assert_eq!(venue_instructions(venue_counter), vec![]);
+    });
+}
+
+#[test]
+fn cancellation_fee() {
+    test_with_cdd_provider(|eve| {
+        let alice = User::new(AccountKeyring::Alice);
+        let bob = User::new(AccountKeyring::Bob);
+        let venue_counter = create_token_and_venue(TICKER, alice);
+        provide_scope_claim_to_multiple_parties(&[alice.did, bob.did], TICKER, eve);
+        let legs = vec![LegV2 {
+            from: PortfolioId::default_portfolio(alice.did),
+            to: PortfolioId::default_portfolio(bob.did),
+            asset: LegAsset::Fungible {
+                ticker: TICKER,
+                amount: 100,
+            },
+        }];
+        let add_instruction = || {
+            let instruction_id = Settlement::instruction_counter();
+            assert_ok!(Settlement::add_instruction_with_memo_v2(
+                alice.origin(),
+                venue_counter,
+                SettlementType::SettleOnAffirmation,
+                None,
+                None,
+                legs.clone(),
+                None,
+            ));
+            instruction_id
+        };
+        let fee = 1_000;
+
+        // Only the venue creator can set the fee, which applies to new instructions.
+        let feeless_id = add_instruction();
+        assert_noop!(
+            Settlement::set_venue_cancellation_fee(bob.origin(), venue_counter, Some(fee)),
+            Error::Unauthorized
+        );
+        assert_ok!(Settlement::set_venue_cancellation_fee(
+            alice.origin(),
+            venue_counter,
+            Some(fee)
+        ));
+        let instruction_id = add_instruction();
+        assert_eq!(Settlement::instruction_cancellation_fee(feeless_id), None);
+        assert_eq!(
+            Settlement::instruction_cancellation_fee(instruction_id),
+            Some(fee)
+        );
+
+        // Rejecting without having affirmed is free.
+        let alice_balance = Balances::free_balance(&alice.acc());
+        let bob_balance = Balances::free_balance(&bob.acc());
+        assert_affirm_instruction_with_one_leg!(alice.origin(), feeless_id, alice.did);
+        assert_ok!(Settlement::reject_instruction_v2(
+            alice.origin(),
+            feeless_id,
+            PortfolioId::default_portfolio(alice.did),
+            1,
+            0,
+            None,
+            None
+        ));
+        assert_ok!(Settlement::reject_instruction_v2(
+            bob.origin(),
+            instruction_id,
+            PortfolioId::default_portfolio(bob.did),
+            1,
+            0,
+            None,
+            None
+        ));
+        assert_eq!(Balances::free_balance(&alice.acc()), alice_balance);
+        assert_eq!(Balances::free_balance(&bob.acc()), bob_balance);
+
+        // Rejecting after having affirmed pays the fee to the other counterparties.
+        let instruction_id = add_instruction();
+        assert_affirm_instruction_with_one_leg!(alice.origin(), instruction_id, alice.did);
+        assert_ok!(Settlement::reject_instruction_v2(
+            alice.origin(),
+            instruction_id,
+            PortfolioId::default_portfolio(alice.did),
+            1,
+            0,
+            None,
+            None
+        ));
+        assert_eq!(Balances::free_balance(&alice.acc()), alice_balance - fee);
+        assert_eq!(Balances::free_balance(&bob.acc()), bob_balance + fee);
+        assert_eq!(
+            Settlement::instruction_cancellation_fee(instruction_id),
+            None
+        );
+    });
+}
+
+#[test]
+fn partial_settlement() {
+    test_with_cdd_provider(|eve| {
+        let alice = User::new(AccountKeyring::Alice);
+        let bob = User::new(AccountKeyring::Bob);
+        let venue_counter = create_token_and_venue(TICKER, alice);
+        create_token(TICKER2, bob);
+        provide_scope_claim_to_multiple_parties(&[alice.did, bob.did], TICKER, eve.clone());
+        provide_scope_claim_to_multiple_parties(&[alice.did, bob.did], TICKER2, eve);
+        let amount = 100;
+        let legs = vec![
+            LegV2 {
+                from: PortfolioId::default_portfolio(alice.did),
+                to: PortfolioId::default_portfolio(bob.did),
+                asset: LegAsset::Fungible {
+                    ticker: TICKER,
+                    amount,
+                },
+            },
+            LegV2 {
+                from: PortfolioId::default_portfolio(bob.did),
+                to: PortfolioId::default_portfolio(alice.did),
+                asset: LegAsset::Fungible {
+                    ticker: TICKER2,
+                    amount,
+                },
+            },
+        ];
+        let alice_balance = Asset::balance_of(&TICKER, alice.did);
+        let bob_balance = Asset::balance_of(&TICKER2, bob.did);
+
+        let instruction_id = Settlement::instruction_counter();
+        assert_ok!(Settlement::add_instruction_with_memo_v2(
+            alice.origin(),
+            venue_counter,
+            SettlementType::SettleOnAffirmationPartial,
+            None,
+            None,
+            legs,
+            None,
+        ));
+        assert_affirm_instruction_with_one_leg!(alice.origin(), instruction_id, alice.did);
+        assert_affirm_instruction_with_one_leg!(bob.origin(), instruction_id, bob.did);
+        assert_locked_assets(&TICKER, &alice, amount);
+        assert_locked_assets(&TICKER2, &bob, amount);
+
+        // The second leg fails, but the first one is still executed.
+        assert_ok!(Asset::freeze(bob.origin(), TICKER2));
+        next_block();
+
+        assert_instruction_status(
+            instruction_id,
+            InstructionStatus::Success(System::block_number()),
+        );
+        assert_balance(&TICKER, &alice, alice_balance - amount);
+        assert_balance(&TICKER, &bob, amount);
+        assert_balance(&TICKER2, &bob, bob_balance);
+        assert_balance(&TICKER2, &alice, 0);
+        assert_locked_assets(&TICKER, &alice, 0);
+        assert_locked_assets(&TICKER2, &bob, 0);
+    });
+}
+
+#[test]
+fn batch_affirmation() {
+    test_with_cdd_provider(|eve| {
+        let alice = User::new(AccountKeyring::Alice);
+        let bob = User::new(AccountKeyring::Bob);
+        let venue_counter = create_token_and_venue(TICKER, alice);
+        provide_scope_claim_to_multiple_parties(&[alice.did, bob.did], TICKER, eve);
+        let amount = 100;
+        let legs = vec![LegV2 {
+            from: PortfolioId::default_portfolio(alice.did),
+            to: PortfolioId::default_portfolio(bob.did),
+            asset: LegAsset::Fungible {
+                ticker: TICKER,
+                amount,
+            },
+        }];
+        let ids: Vec<_> = (0..3)
+            .map(|_| {
+                let instruction_id = Settlement::instruction_counter();
+                assert_ok!(Settlement::add_instruction_with_memo_v2(
+                    alice.origin(),
+                    venue_counter,
+                    SettlementType::SettleOnAffirmation,
+                    None,
+                    None,
+                    legs.clone(),
+                    None,
+                ));
+                instruction_id
+            })
+            .collect();
+        let alice_balance = Asset::balance_of(&TICKER, alice.did);
+        let portfolios = default_portfolio_vec(alice.did);
+
+        // A single instruction that cannot be affirmed makes the whole batch fail.
+        assert_affirm_instruction_with_one_leg!(alice.origin(), ids[2], alice.did);
+        assert_noop!(
+            Settlement::affirm_instructions(alice.origin(), ids.clone(), portfolios.clone(), 1, 0),
+            Error::UnexpectedAffirmationStatus
+        );
+        assert_user_affirms(ids[0], &alice, AffirmationStatus::Pending);
+
+        assert_ok!(Settlement::affirm_instructions(
+            alice.origin(),
+            ids[..2].to_vec(),
+            portfolios,
+            1,
+            0
+        ));
+        assert_locked_assets(&TICKER, &alice, amount * 3);
+        assert_ok!(Settlement::affirm_instructions(
+            bob.origin(),
+            ids.clone(),
+            default_portfolio_vec(bob.did),
+            0,
+            0
+        ));
+        next_block();
+
+        for id in ids {
+            assert_instruction_status(id, InstructionStatus::Success(System::block_number()));
+        }
+        assert_balance(&TICKER, &alice, alice_balance - amount * 3);
+        assert_balance(&TICKER, &bob, amount * 3);
+        assert_locked_assets(&TICKER, &alice, 0);
+    });
+}
+
+#[test]
+fn instruction_mediators() {
+    test_with_cdd_provider(|eve| {
+        let alice = User::new(AccountKeyring::Alice);
+        let bob = User::new(AccountKeyring::Bob);
+        let charlie = User::new(AccountKeyring::Charlie);
+        let venue_counter = create_token_and_venue(TICKER, alice);
+        provide_scope_claim_to_multiple_parties(&[alice.did, bob.did], TICKER, eve);
+        let amount = 100;
+        let legs = vec![LegV2 {
+            from: PortfolioId::default_portfolio(alice.did),
+            to: PortfolioId::default_portfolio(bob.did),
+            asset: LegAsset::Fungible {
+                ticker: TICKER,
+                amount,
+            },
+        }];
+        let add_instruction = |mediators: Vec<IdentityId>| {
+            Settlement::add_instruction_with_mediators(
+                alice.origin(),
+                venue_counter,
+                SettlementType::SettleOnAffirmation,
+                None,
+                None,
+                legs.clone(),
+                None,
+                mediators.into_iter().collect(),
+            )
+        };
+        let alice_balance = Asset::balance_of(&TICKER, alice.did);
+
+        assert_noop!(
+            add_instruction(vec![IdentityId::from(999)]),
+            Error::UnknownMediator
+        );
+        let instruction_id = Settlement::instruction_counter();
+        assert_ok!(add_instruction(vec![charlie.did]));
+        assert_affirms_pending(instruction_id, 3);
+        assert_eq!(
+            Settlement::instruction_mediators(instruction_id, charlie.did),
+            AffirmationStatus::Pending
+        );
+
+        // Only mediators can affirm as a mediator.
+        assert_noop!(
+            Settlement::affirm_instruction_as_mediator(bob.origin(), instruction_id, 1, 0),
+            Error::CallerIsNotAMediator
+        );
+        assert_noop!(
+            Settlement::withdraw_affirmation_as_mediator(charlie.origin(), instruction_id),
+            Error::UnexpectedAffirmationStatus
+        );
+
+        // The instruction is not executed until the mediator affirms it.
+        assert_affirm_instruction_with_one_leg!(alice.origin(), instruction_id, alice.did);
+        assert_affirm_instruction_with_zero_leg!(bob.origin(), instruction_id, bob.did);
+        assert_affirms_pending(instruction_id, 1);
+        next_block();
+        assert_instruction_status(instruction_id, InstructionStatus::Pending);
+
+        // Mediators can't affirm while the settlement of an asset of the instruction is frozen.
+        assert_ok!(Settlement::freeze_settlement(alice.origin(), TICKER));
+        assert_noop!(
+            Settlement::affirm_instruction_as_mediator(charlie.origin(), instruction_id, 1, 0),
+            Error::AssetSettlementFrozen
+        );
+        assert_ok!(Settlement::unfreeze_settlement(alice.origin(), TICKER));
+
+        assert_ok!(Settlement::affirm_instruction_as_mediator(
+            charlie.origin(),
+            instruction_id,
+            1,
+            0
+        ));
+        assert_affirms_pending(instruction_id, 0);
+        assert_ok!(Settlement::withdraw_affirmation_as_mediator(
+            charlie.origin(),
+            instruction_id
+        ));
+        assert_affirms_pending(instruction_id, 1);
+        assert_ok!(Settlement::affirm_instruction_as_mediator(
+            charlie.origin(),
+            instruction_id,
+            1,
+            0
+        ));
+        next_block();
+
+        assert_instruction_status(
+            instruction_id,
+            InstructionStatus::Success(System::block_number()),
+        );
+        assert_balance(&TICKER, &alice, alice_balance - amount);
+        assert_balance(&TICKER, &bob, amount);
+        assert_eq!(
+            Settlement::instruction_mediators(instruction_id, charlie.did),
+            AffirmationStatus::Unknown
+        );
+    });
+}
+
+#[test]
+fn edit_instruction_legs_with_mediators() {
+    ExtBuilder::default().build().execute_with(|| {
+        let alice = User::new(AccountKeyring::Alice);
+        let bob = User::new(AccountKeyring::Bob);
+        let charlie = User::new(AccountKeyring::Charlie);
+        let venue_counter = create_token_and_venue(TICKER, alice);
+        let leg = |amount: Balance| LegV2 {
+            from: PortfolioId::default_portfolio(alice.did),
+            to: PortfolioId::default_portfolio(bob.did),
+            asset: LegAsset::Fungible {
+                ticker: TICKER,
+                amount,
+            },
+        };
+        let add_instruction = |mediators: BTreeSet<IdentityId>| {
+            Settlement::add_instruction_with_mediators(
+                alice.origin(),
+                venue_counter,
+                SettlementType::SettleOnAffirmation,
+                None,
+                None,
+                vec![leg(10)],
+                None,
+                mediators,
+            )
+        };
+
+        assert_noop!(
+            add_instruction(
+                (0..=MAX_INSTRUCTION_MEDIATORS as u128)
+                    .map(IdentityId::from)
+                    .collect()
+            ),
+            Error::TooManyMediators
+        );
+        let instruction_id = Settlement::instruction_counter();
+        assert_ok!(add_instruction([charlie.did].into()));
+        assert_ok!(Settlement::affirm_instruction_as_mediator(
+            charlie.origin(),
+            instruction_id,
+            1,
+            0
+        ));
+        assert_affirms_pending(instruction_id, 2);
+
+        // The mediator has to affirm the edited instruction again.
+        assert_ok!(Settlement::edit_instruction_legs(
+            alice.origin(),
+            instruction_id,
+            vec![LegId(0)],
+            vec![leg(20)],
+            1
+        ));
+        assert_affirms_pending(instruction_id, 3);
+        assert_eq!(
+            Settlement::instruction_mediators(instruction_id, charlie.did),
+            AffirmationStatus::Pending
+        );
+    });
+}
+
+#[test]
+fn instruction_operator() {
+    test_with_cdd_provider(|eve| {
+        let alice = User::new(AccountKeyring::Alice);
+        let bob = User::new(AccountKeyring::Bob);
+        let charlie = User::new(AccountKeyring::Charlie);
+        let venue_counter = create_token_and_venue(TICKER, alice);
+        provide_scope_claim_to_multiple_parties(&[alice.did, bob.did], TICKER, eve);
+        let amount = 100;
+        let legs = vec![LegV2 {
+            from: PortfolioId::default_portfolio(alice.did),
+            to: PortfolioId::default_portfolio(bob.did),
+            asset: LegAsset::Fungible {
+                ticker: TICKER,
+                amount,
+            },
+        }];
+        let add_instruction = || {
+            let instruction_id = Settlement::instruction_counter();
+            assert_ok!(Settlement::add_instruction_with_memo_v2(
+                alice.origin(),
+                venue_counter,
+                SettlementType::SettleManual(System::block_number()),
+                None,
+                None,
+                legs.clone(),
+                None,
+            ));
+            instruction_id
+        };
+
+        // Only the venue creator can set the operator.
+        let instruction_id = add_instruction();
+        assert_noop!(
+            Settlement::set_instruction_operator(
+                charlie.origin(),
+                instruction_id,
+                Some(charlie.did)
+            ),
+            Error::Unauthorized
+        );
+        assert_noop!(
+            Settlement::set_instruction_operator(
+                alice.origin(),
+                instruction_id,
+                Some(IdentityId::from(999))
+            ),
+            Error::UnknownOperator
+        );
+        assert_noop!(
+            Settlement::reject_instruction_as_operator(
+                charlie.origin(),
+                instruction_id,
+                1,
+                0,
+                None,
+                None
+            ),
+            Error::Unauthorized
+        );
+        assert_ok!(Settlement::set_instruction_operator(
+            alice.origin(),
+            instruction_id,
+            Some(charlie.did)
+        ));
+        assert_eq!(
+            Settlement::instruction_operator(instruction_id),
+            Some(charlie.did)
+        );
+
+        // The operator can reject the instruction, releasing the locked assets.
+        assert_affirm_instruction_with_one_leg!(alice.origin(), instruction_id, alice.did);
+        assert_locked_assets(&TICKER, &alice, amount);
+        assert_ok!(Settlement::reject_instruction_as_operator(
+            charlie.origin(),
+            instruction_id,
+            1,
+            0,
+            None,
+            Some(Memo(vec![7u8; 32]))
+        ));
+        assert_instruction_status(
+            instruction_id,
+            InstructionStatus::Rejected(System::block_number()),
+        );
+        // A memo without a reason is recorded as `Other`.
+        assert_eq!(
+            Settlement::instruction_rejection(instruction_id),
+            Some((RejectReason::Other, Some(Memo(vec![7u8; 32]))))
+        );
+        assert_locked_assets(&TICKER, &alice, 0);
+        assert_eq!(Settlement::instruction_operator(instruction_id), None);
+
+        // The operator can execute the instruction manually.
+        let instruction_id = add_instruction();
+        assert_ok!(Settlement::set_instruction_operator(
+            alice.origin(),
+            instruction_id,
+            Some(charlie.did)
+        ));
+        assert_affirm_instruction_with_one_leg!(alice.origin(), instruction_id, alice.did);
+        assert_affirm_instruction_with_zero_leg!(bob.origin(), instruction_id, bob.did);
+        assert_ok!(Settlement::execute_manual_instruction(
+            charlie.origin(),
+            instruction_id,
+            1,
+            None
+        ));
+        assert_instruction_status(
+            instruction_id,
+            InstructionStatus::Success(System::block_number()),
+        );
+        assert_balance(&TICKER, &bob, amount);
+    });
+}
+
+#[test]
+fn pending_instructions() {
+    test_with_cdd_provider(|eve| {
+        let alice = User::new(AccountKeyring::Alice);
+        let bob = User::new(AccountKeyring::Bob);
+        let venue_counter = create_token_and_venue(TICKER, alice);
+        provide_scope_claim_to_multiple_parties(&[alice.did, bob.did], TICKER, eve);
+        let legs = vec![LegV2 {
+            from: PortfolioId::default_portfolio(alice.did),
+            to: PortfolioId::default_portfolio(bob.did),
+            asset: LegAsset::Fungible {
+                ticker: TICKER,
+                amount: 100,
+            },
+        }];
+        let instruction_id = Settlement::instruction_counter();
+        assert_ok!(Settlement::add_instruction_with_memo_v2(
+            alice.origin(),
+            venue_counter,
+            SettlementType::SettleManual(System::block_number()),
+            None,
+            None,
+            legs.clone(),
+            None,
+        ));
+
+        assert_eq!(Settlement::get_instruction_legs(&instruction_id).len(), 1);
+        assert_eq!(
+            Settlement::pending_instructions(alice.did),
+            vec![instruction_id]
+        );
+        assert_eq!(
+            Settlement::pending_instructions(bob.did),
+            vec![instruction_id]
+        );
+
+        assert_affirm_instruction_with_one_leg!(alice.origin(), instruction_id, alice.did);
+        assert!(Settlement::pending_instructions(alice.did).is_empty());
+        assert_eq!(
+            Settlement::user_affirmations(
+                PortfolioId::default_portfolio(alice.did),
+                instruction_id
+            ),
+            AffirmationStatus::Affirmed
+        );
+        assert_eq!(
+            Settlement::pending_instructions(bob.did),
+            vec![instruction_id]
+        );
+    });
+}
+
+#[test]
+fn locked_assets() {
+    test_with_cdd_provider(|eve| {
+        let alice = User::new(AccountKeyring::Alice);
+        let bob = User::new(AccountKeyring::Bob);
+        let venue_counter = create_token_and_venue(TICKER, alice);
+        provide_scope_claim_to_multiple_parties(&[alice.did, bob.did], TICKER, eve);
+        let alice_portfolio = PortfolioId::default_portfolio(alice.did);
+        let fungible = |amount| LegAsset::Fungible {
+            ticker: TICKER,
+            amount,
+        };
+        let legs = vec![
+            LegV2 {
+                from: alice_portfolio,
+                to: PortfolioId::default_portfolio(bob.did),
+                asset: fungible(100),
+            },
+            LegV2 {
+                from: PortfolioId::default_portfolio(bob.did),
+                to: alice_portfolio,
+                asset: fungible(20),
+            },
+            LegV2 {
+                from: alice_portfolio,
+                to: PortfolioId::default_portfolio(bob.did),
+                asset: fungible(30),
+            },
+        ];
+        let instruction_id = Settlement::instruction_counter();
+        assert_ok!(Settlement::add_instruction_with_memo_v2(
+            alice.origin(),
+            venue_counter,
+            SettlementType::SettleManual(System::block_number()),
+            None,
+            None,
+            legs,
+            None,
+        ));
+
+        // Nothing is locked before the affirmation.
+        assert!(Settlement::locked_assets(&alice_portfolio).is_empty());
+
+        // Only the legs sent from the portfolio are locked once affirmed.
+        assert_ok!(Settlement::affirm_instruction(
+            alice.origin(),
+            instruction_id,
+            vec![alice_portfolio],
+            2
+        ));
+        assert_eq!(
+            Settlement::locked_assets(&alice_portfolio),
+            vec![
+                (
+                    instruction_id,
+                    LegId(0),
+                    LockedAsset::Fungible {
+                        ticker: TICKER,
+                        amount: 100
+                    }
+                ),
+                (
+                    instruction_id,
+                    LegId(2),
+                    LockedAsset::Fungible {
+                        ticker: TICKER,
+                        amount: 30
+                    }
+                ),
+            ]
+        );
+        assert_eq!(Portfolio::locked_assets(alice_portfolio, &TICKER), 130);
+        assert!(Settlement::locked_assets(&PortfolioId::default_portfolio(bob.did)).is_empty());
+
+        // Legs executed in a previous block no longer hold a lock.
+        pallet_settlement::InstructionExecutionProgress::insert(
+            instruction_id,
+            ExecutionProgress {
+                next_leg_id: LegId(1),
+                failed_legs: Vec::new(),
+            },
+        );
+        assert_eq!(
+            Settlement::locked_assets(&alice_portfolio),
+            vec![(
+                instruction_id,
+                LegId(2),
+                LockedAsset::Fungible {
+                    ticker: TICKER,
+                    amount: 30
+                }
+            )]
+        );
+        pallet_settlement::InstructionExecutionProgress::remove(instruction_id);
+
+        // Withdrawing the affirmation releases the locks.
+        assert_ok!(Settlement::withdraw_affirmation(
+            alice.origin(),
+            instruction_id,
+            vec![alice_portfolio],
+            2
+        ));
+        assert!(Settlement::locked_assets(&alice_portfolio).is_empty());
+    });
+}
+
+#[test]
+fn affirmations_revoked_on_custody_change() {
+    test_with_cdd_provider(|eve| {
+        let alice = User::new(AccountKeyring::Alice);
+        let bob = User::new(AccountKeyring::Bob);
+        let venue_counter = create_token_and_venue(TICKER, alice);
+        provide_scope_claim_to_multiple_parties(&[alice.did, bob.did], TICKER, eve);
+
+        // Alice gives the custody of a funded portfolio to Bob.
+        let alice_num = Portfolio::next_portfolio_number(&alice.did);
+        assert_ok!(Portfolio::create_portfolio(
+            alice.origin(),
+            PortfolioName::from([42u8].to_vec())
+        ));
+        let portfolio = PortfolioId::user_portfolio(alice.did, alice_num);
+        assert_ok!(Portfolio::move_portfolio_funds(
+            alice.origin(),
+            PortfolioId::default_portfolio(alice.did),
+            portfolio,
+            vec![MovePortfolioItem {
+                ticker: TICKER,
+                amount: 100,
+                memo: None
+            }]
+        ));
+        let give_custody = || {
+            let auth_id = Identity::add_auth(
+                alice.did,
+                Signatory::from(bob.did),
+                AuthorizationData::PortfolioCustody(portfolio),
+                None,
+            );
+            assert_ok!(Portfolio::accept_portfolio_custody(bob.origin(), auth_id));
+        };
+        give_custody();
+
+        // Bob affirms an instruction sending from the portfolio.
+        let add_and_affirm = || {
+            let instruction_id = Settlement::instruction_counter();
+            assert_ok!(Settlement::add_instruction_with_memo_v2(
+                alice.origin(),
+                venue_counter,
+                SettlementType::SettleManual(System::block_number() + 1),
+                None,
+                None,
+                vec![LegV2 {
+                    from: portfolio,
+                    to: PortfolioId::default_portfolio(bob.did),
+                    asset: LegAsset::Fungible {
+                        ticker: TICKER,
+                        amount: 100,
+                    },
+                }],
+                None,
+            ));
+            assert_ok!(Settlement::affirm_instruction(
+                bob.origin(),
+                instruction_id,
+                vec![portfolio],
+                1
+            ));
+            assert_eq!(Portfolio::locked_assets(portfolio, &TICKER), 100);
+            instruction_id
+        };
+        let instruction_id = add_and_affirm();
+
+        // Quitting the custody withdraws Bob's affirmations and unlocks the assets.
+        assert_ok!(Portfolio::quit_portfolio_custody(bob.origin(), portfolio));
+        assert_eq!(Portfolio::locked_assets(portfolio, &TICKER), 0);
+        assert_eq!(
+            Settlement::user_affirmations(portfolio, instruction_id),
+            AffirmationStatus::Pending
+        );
+
+        // Only the custodian can revoke the affirmations explicitly.
+        give_custody();
+        let instruction_id = add_and_affirm();
+        assert_noop!(
+            Settlement::revoke_affirmations_for_portfolio(alice.origin(), portfolio, None, 1),
+            PortfolioError::UnauthorizedCustodian
+        );
+        assert_ok!(Settlement::revoke_affirmations_for_portfolio(
+            bob.origin(),
+            portfolio,
+            None,
+            10
+        ));
+        assert_eq!(Portfolio::locked_assets(portfolio, &TICKER), 0);
+        assert_eq!(
+            Settlement::user_affirmations(portfolio, instruction_id),
+            AffirmationStatus::Pending
+        );
+
+        // Affirmations that can't be withdrawn are skipped and reported.
+        assert_ok!(Settlement::affirm_instruction(
+            bob.origin(),
+            instruction_id,
+            vec![portfolio],
+            1
+        ));
+        pallet_settlement::InstructionExecutionProgress::insert(
+            instruction_id,
+            ExecutionProgress::default(),
+        );
+        assert_ok!(Settlement::revoke_affirmations_for_portfolio(
+            bob.origin(),
+            portfolio,
+            None,
+            10
+        ));
+        assert_eq!(
+            Settlement::user_affirmations(portfolio, instruction_id),
+            AffirmationStatus::Affirmed
+        );
+        assert!(System::events().iter().any(|record| matches!(
+            &record.event,
+            super::storage::EventTest::Settlement(
+                pallet_settlement::RawEvent::PortfolioAffirmationsRevoked(_, p, revoked, failed, None)
+            ) if *p == portfolio && revoked.is_empty() && *failed == vec![instruction_id]
+        )));
+
+        // The instructions of the portfolio are scanned in pages.
+        let scanned = |start_after| {
+            System::reset_events();
+            assert_ok!(Settlement::revoke_affirmations_for_portfolio(
+                bob.origin(),
+                portfolio,
+                start_after,
+                1
+            ));
+            System::events()
+                .into_iter()
+                .find_map(|record| match record.event {
+                    super::storage::EventTest::Settlement(
+                        pallet_settlement::RawEvent::PortfolioAffirmationsRevoked(
+                            _,
+                            _,
+                            _,
+                            _,
+                            last_scanned,
+                        ),
+                    ) => Some(last_scanned),
+                    _ => None,
+                })
+        };
+        let first = scanned(None).unwrap();
+        let second = scanned(first).unwrap();
+        assert!(first.is_some() && second.is_some() && first != second);
+        assert_eq!(scanned(second), None);
+    });
+}
+
+#[test]
+fn can_execute_instruction() {
+    test_with_cdd_provider(|eve| {
+        let alice = User::new(AccountKeyring::Alice);
+        let bob = User::new(AccountKeyring::Bob);
+        let venue_counter = create_token_and_venue(TICKER, alice);
+        provide_scope_claim_to_multiple_parties(&[alice.did, bob.did], TICKER, eve);
+        let alice_balance = Asset::balance_of(&TICKER, alice.did);
+        let amount = 100;
+        let legs = vec![LegV2 {
+            from: PortfolioId::default_portfolio(alice.did),
+            to: PortfolioId::default_portfolio(bob.did),
+            asset: LegAsset::Fungible {
+                ticker: TICKER,
+                amount,
+            },
+        }];
+        let instruction_id = Settlement::instruction_counter();
+        assert_ok!(Settlement::add_instruction_with_memo_v2(
+            alice.origin(),
+            venue_counter,
+            SettlementType::SettleManual(System::block_number()),
+            None,
+            None,
+            legs,
+            None,
+        ));
+
+        // Affirmations are still pending.
+        assert_eq!(
+            Settlement::can_execute_instruction(&instruction_id),
+            Err(Error::InstructionFailed.into())
+        );
+        assert_affirm_instruction_with_one_leg!(alice.origin(), instruction_id, alice.did);
+        assert_affirm_instruction_with_zero_leg!(bob.origin(), instruction_id, bob.did);
+        assert_eq!(
+            Settlement::can_execute_instruction(&instruction_id),
+            Ok(vec![])
+        );
+
+        // The dry run doesn't change any state.
+        assert_locked_assets(&TICKER, &alice, amount);
+        assert_balance(&TICKER, &alice, alice_balance);
+        assert_balance(&TICKER, &bob, 0);
+
+        // The dry run performs the same checks as the execution.
+        assert_ok!(Settlement::freeze_settlement(alice.origin(), TICKER));
+        assert_eq!(
+            Settlement::can_execute_instruction(&instruction_id),
+            Err(Error::AssetSettlementFrozen.into())
+        );
+        assert_ok!(Settlement::unfreeze_settlement(alice.origin(), TICKER));
+
+        // A frozen asset makes the leg fail.
+        assert_ok!(Asset::freeze(alice.origin(), TICKER));
+        assert_eq!(
+            Settlement::can_execute_instruction(&instruction_id),
+            Ok(vec![(LegId(0), AssetError::InvalidTransfer.into())])
+        );
+        assert_locked_assets(&TICKER, &alice, amount);
+    });
+}
+
+#[test]
+fn venue_fees() {
+    test_with_cdd_provider(|eve| {
+        let alice = User::new(AccountKeyring::Alice);
+        let bob = User::new(AccountKeyring::Bob);
+        let venue_counter = create_token_and_venue(TICKER, alice);
+        provide_scope_claim_to_multiple_parties(&[alice.did, bob.did], TICKER, eve);
+        let fee = VenueFee::PerLeg(1_000);
+
+        // Only the venue creator can set its fee.
+        assert_noop!(
+            Settlement::set_venue_fee(bob.origin(), venue_counter, Some(fee)),
+            Error::Unauthorized
+        );
+        assert_ok!(Settlement::set_venue_fee(
+            alice.origin(),
+            venue_counter,
+            Some(fee)
+        ));
+        assert_eq!(Settlement::venue_fee(venue_counter), Some(fee));
+
+        let legs = vec![LegV2 {
+            from: PortfolioId::default_portfolio(alice.did),
+            to: PortfolioId::default_portfolio(bob.did),
+            asset: LegAsset::Fungible {
+                ticker: TICKER,
+                amount: 100,
+            },
+        }];
+        let instruction_id = Settlement::instruction_counter();
+        assert_ok!(Settlement::add_instruction_with_memo_v2(
+            alice.origin(),
+            venue_counter,
+            SettlementType::SettleManual(System::block_number()),
+            None,
+            None,
+            legs,
+            None,
+        ));
+        assert_eq!(Settlement::instruction_venue_fee(instruction_id), Some(fee));
+
+        // Each party is charged at affirmation.
+        let alice_polyx = Balances::free_balance(&alice.acc());
+        let bob_polyx = Balances::free_balance(&bob.acc());
+        let bob_portfolio = PortfolioId::default_portfolio(bob.did);
+        assert_affirm_instruction_with_one_leg!(alice.origin(), instruction_id, alice.did);
+        assert_affirm_instruction_with_zero_leg!(bob.origin(), instruction_id, bob.did);
+        assert_eq!(Balances::free_balance(&alice.acc()), alice_polyx - 1_000);
+        assert_eq!(Balances::free_balance(&bob.acc()), bob_polyx - 1_000);
+        assert_eq!(
+            Settlement::venue_fee_charged(instruction_id, bob_portfolio),
+            Some((bob.acc(), 1_000))
+        );
+
+        // Withdrawing the affirmation refunds the fee.
+        assert_ok!(Settlement::withdraw_affirmation(
+            bob.origin(),
+            instruction_id,
+            default_portfolio_vec(bob.did),
+            0
+        ));
+        assert_eq!(Balances::free_balance(&bob.acc()), bob_polyx);
+        assert_eq!(
+            Settlement::venue_fee_charged(instruction_id, bob_portfolio),
+            None
+        );
+        assert_affirm_instruction_with_zero_leg!(bob.origin(), instruction_id, bob.did);
+
+        // The venue creator is paid when the instruction executes.
+        next_block();
+        assert_ok!(Settlement::execute_manual_instruction(
+            alice.origin(),
+            instruction_id,
+            1,
+            None
+        ));
+        assert_eq!(Balances::free_balance(&alice.acc()), alice_polyx + 1_000);
+        assert_eq!(Balances::free_balance(&bob.acc()), bob_polyx - 1_000);
+        assert_eq!(Balances::free_balance(&Settlement::venue_fees_escrow()), 0);
+        assert_eq!(Settlement::instruction_venue_fee(instruction_id), None);
+    });
+}
+
+#[test]
+fn simulate_instruction_lifecycle() {
+    test_with_cdd_provider(|eve| {
+        let alice = User::new(AccountKeyring::Alice);
+        let bob = User::new(AccountKeyring::Bob);
+        let venue_counter = create_token_and_venue(TICKER, alice);
+        provide_scope_claim_to_multiple_parties(&[alice.did, bob.did], TICKER, eve);
+        let alice_balance = Asset::balance_of(&TICKER, alice.did);
+        let legs = vec![LegV2 {
+            from: PortfolioId::default_portfolio(alice.did),
+            to: PortfolioId::default_portfolio(bob.did),
+            asset: LegAsset::Fungible {
+                ticker: TICKER,
+                amount: 100,
+            },
+        }];
+
+        // Only the venue creator can add instructions.
+        assert_noop!(
+            Settlement::simulate_instruction_lifecycle(bob.did, venue_counter, legs.clone(), None),
+            Error::Unauthorized
+        );
+        let instruction_id =
+            Settlement::simulate_instruction_lifecycle(alice.did, venue_counter, legs, None)
+                .unwrap();
+        assert_instruction_status(
+            instruction_id,
+            InstructionStatus::Success(System::block_number()),
+        );
+        assert_balance(&TICKER, &alice, alice_balance - 100);
+        assert_balance(&TICKER, &bob, 100);
+        assert_locked_assets(&TICKER, &alice, 0);
+
+        // Nothing is left for the scheduler to execute.
+        next_block();
+        assert_balance(&TICKER, &bob, 100);
+    });
+}
+
+#[test]
+fn settlement_test_utils() {
+    test_with_cdd_provider(|eve| {
+        let alice = User::new(AccountKeyring::Alice);
+        let bob = User::new(AccountKeyring::Bob);
+        create_token(TICKER, alice);
+        provide_scope_claim_to_multiple_parties(&[alice.did, bob.did], TICKER, eve);
+
+        // Builds the scenario with the helpers shared with the benchmarks.
+        let venue_id = create_venue_::<TestStorage>(alice.did, vec![]);
+        let bob_data = UserData {
+            account: bob.acc(),
+            did: bob.did,
+        };
+        let bob_portfolio = create_user_portfolio::<TestStorage>(&bob_data);
+        let legs = vec![LegV2 {
+            from: PortfolioId::default_portfolio(alice.did),
+            to: bob_portfolio,
+            asset: LegAsset::Fungible {
+                ticker: TICKER,
+                amount: 100,
+            },
+        }];
+
+        let instruction_id =
+            Settlement::simulate_instruction_lifecycle(alice.did, venue_id, legs, None).unwrap();
+        assert_instruction_status(
+            instruction_id,
+            InstructionStatus::Success(System::block_number()),
+        );
+        assert_eq!(PortfolioAssetBalances::get(&bob_portfolio, &TICKER), 100);
+    });
+}
+
+#[test]
+fn venue_netting() {
+    test_with_cdd_provider(|eve| {
+        let alice = User::new(AccountKeyring::Alice);
+        let bob = User::new(AccountKeyring::Bob);
+        let venue_counter = create_token_and_venue(TICKER, alice);
+        provide_scope_claim_to_multiple_parties(&[alice.did, bob.did], TICKER, eve);
+        let alice_balance = Asset::balance_of(&TICKER, alice.did);
+        let leg = |from: User, to: User, amount| LegV2 {
+            from: PortfolioId::default_portfolio(from.did),
+            to: PortfolioId::default_portfolio(to.did),
+            asset: LegAsset::Fungible {
+                ticker: TICKER,
+                amount,
+            },
+        };
+
+        // Only the venue creator can enable netting.
+        assert_noop!(
+            Settlement::set_venue_netting(bob.origin(), venue_counter, true),
+            Error::Unauthorized
+        );
+        assert_ok!(Settlement::set_venue_netting(
+            alice.origin(),
+            venue_counter,
+            true
+        ));
+        assert!(Settlement::venue_netting(venue_counter));
+        assert_ok!(Settlement::simulate_instruction_lifecycle(
+            alice.did,
+            venue_counter,
+            vec![leg(alice, bob, 50)],
+            None
+        ));
+
+        // The mirrored legs are collapsed into a single transfer of the net amount.
+        let instruction_id = Settlement::simulate_instruction_lifecycle(
+            alice.did,
+            venue_counter,
+            vec![
+                leg(alice, bob, 100),
+                leg(bob, alice, 40),
+                leg(alice, bob, 20),
+            ],
+            None,
+        )
+        .unwrap();
+        assert_instruction_status(
+            instruction_id,
+            InstructionStatus::Success(System::block_number()),
+        );
+        assert_balance(&TICKER, &alice, alice_balance - 130);
+        assert_balance(&TICKER, &bob, 130);
+        assert_locked_assets(&TICKER, &alice, 0);
+        assert_locked_assets(&TICKER, &bob, 0);
+        assert!(System::events().iter().any(|record| matches!(
+            &record.event,
+            super::storage::EventTest::Settlement(pallet_settlement::RawEvent::LegsNetted(
+                _,
+                id,
+                leg_ids,
+            )) if *id == instruction_id && *leg_ids == vec![LegId(1), LegId(2)]
+        )));
+    });
+}
+
+#[test]
+fn multi_signature_receipts() {
+    ExtBuilder::default().build().execute_with(|| {
+        let alice = User::new(AccountKeyring::Alice);
+        let bob = User::new(AccountKeyring::Bob);
+        let charlie = AccountKeyring::Charlie.to_account_id();
+        let venue_counter = create_venue(alice);
+        assert_ok!(Settlement::update_venue_signers(
+            alice.origin(),
+            venue_counter,
+            vec![charlie.clone()],
+            true
+        ));
+
+        // The threshold can't exceed the number of venue signers.
+        assert_noop!(
+            Settlement::set_receipt_signatories(bob.origin(), venue_counter, 2),
+            Error::Unauthorized
+        );
+        assert_noop!(
+            Settlement::set_receipt_signatories(alice.origin(), venue_counter, 3),
+            Error::InvalidReceiptSignatories
+        );
+        assert_ok!(Settlement::set_receipt_signatories(
+            alice.origin(),
+            venue_counter,
+            2
+        ));
+        assert_noop!(
+            Settlement::update_venue_signers(
+                alice.origin(),
+                venue_counter,
+                vec![charlie.clone()],
+                false
+            ),
+            Error::InvalidReceiptSignatories
+        );
+
+        let amount = 100u128;
+        let instruction_id = Settlement::instruction_counter();
+        assert_ok!(Settlement::add_instruction(
+            alice.origin(),
+            venue_counter,
+            SettlementType::SettleOnAffirmation,
+            None,
+            None,
+            vec![Leg {
+                from: PortfolioId::default_portfolio(alice.did),
+                to: PortfolioId::default_portfolio(bob.did),
+                asset: TICKER,
+                amount,
+            }],
+        ));
+        let msg = Receipt {
+            receipt_uid: 0,
+            from: PortfolioId::default_portfolio(alice.did),
+            to: PortfolioId::default_portfolio(bob.did),
+            asset: TICKER,
+            amount,
+        };
+        let receipt = |signer: AccountKeyring| ReceiptDetails {
+            receipt_uid: 0,
+            leg_id: LegId(0),
+            signer: signer.to_account_id(),
+            signature: signer.sign(&msg.encode()).into(),
+            metadata: ReceiptMetadata::default(),
+        };
+
+        // The number of receipts is bounded.
+        let too_many = (0..=MAX_RECEIPTS_PER_AFFIRMATION)
+            .map(|_| receipt(AccountKeyring::Alice))
+            .collect();
+        assert_noop!(
+            Settlement::affirm_with_receipts(
+                alice.origin(),
+                instruction_id,
+                too_many,
+                default_portfolio_vec(alice.did),
+                1
+            ),
+            Error::TooManyReceipts
+        );
+
+        // A single signature is not enough.
+        assert_noop!(
+            Settlement::affirm_with_receipts(
+                alice.origin(),
+                instruction_id,
+                vec![receipt(AccountKeyring::Alice)],
+                default_portfolio_vec(alice.did),
+                1
+            ),
+            Error::NotEnoughReceiptSigners
+        );
+        assert_ok!(Settlement::affirm_with_receipts(
+            alice.origin(),
+            instruction_id,
+            vec![
+                receipt(AccountKeyring::Alice),
+                receipt(AccountKeyring::Charlie)
+            ],
+            default_portfolio_vec(alice.did),
+            1
+        ));
+        assert_leg_status(
+            instruction_id,
+            LegId(0),
+            LegStatus::ExecutionToBeSkipped(AccountKeyring::Alice.to_account_id(), 0),
+        );
+        assert_eq!(
+            Settlement::receipt_cosigners(instruction_id, LegId(0)),
+            vec![(charlie.clone(), 0)]
+        );
+        assert!(Settlement::receipts_used(&charlie, &0));
+
+        // Withdrawing the affirmation unclaims the receipts of all signers.
+        assert_ok!(Settlement::withdraw_affirmation(
+            alice.origin(),
+            instruction_id,
+            default_portfolio_vec(alice.did),
+            1
+        ));
+        assert!(!Settlement::receipts_used(
+            &AccountKeyring::Alice.to_account_id(),
+            &0
+        ));
+        assert!(!Settlement::receipts_used(&charlie, &0));
+        assert_eq!(
+            Settlement::receipt_cosigners(instruction_id, LegId(0)),
+            vec![]
+        );
+    });
+}
+
+#[test]
+fn receipt_metadata_schema() {
+    ExtBuilder::default().build().execute_with(|| {
+        let alice = User::new(AccountKeyring::Alice);
+        let bob = User::new(AccountKeyring::Bob);
+        let venue_counter = create_venue(alice);
+        let schema = ReceiptMetadataSchema {
+            max_length: 8,
+            required_prefix: ReceiptMetadata::from(b"REF:"),
+        };
+
+        assert_noop!(
+            Settlement::set_receipt_metadata_schema(
+                bob.origin(),
+                venue_counter,
+                Some(schema.clone())
+            ),
+            Error::Unauthorized
+        );
+        assert_noop!(
+            Settlement::set_receipt_metadata_schema(
+                alice.origin(),
+                venue_counter,
+                Some(ReceiptMetadataSchema {
+                    max_length: 3,
+                    ..schema.clone()
+                })
+            ),
+            Error::InvalidReceiptMetadataSchema
+        );
+        assert_ok!(Settlement::set_receipt_metadata_schema(
+            alice.origin(),
+            venue_counter,
+            Some(schema.clone())
+        ));
+        assert_eq!(
+            Settlement::receipt_metadata_schema(venue_counter),
+            Some(schema)
+        );
+
+        let amount = 100u128;
+        let instruction_id = Settlement::instruction_counter();
+        assert_ok!(Settlement::add_instruction(
+            alice.origin(),
+            venue_counter,
+            SettlementType::SettleOnAffirmation,
+            None,
+            None,
+            vec![Leg {
+                from: PortfolioId::default_portfolio(alice.did),
+                to: PortfolioId::default_portfolio(bob.did),
+                asset: TICKER,
+                amount,
+            }],
+        ));
+        let msg = Receipt {
+            receipt_uid: 0,
+            from: PortfolioId::default_portfolio(alice.did),
+            to: PortfolioId::default_portfolio(bob.did),
+            asset: TICKER,
+            amount,
+        };
+        let affirm = |metadata: &[u8]| {
+            Settlement::affirm_with_receipts(
+                alice.origin(),
+                instruction_id,
+                vec![ReceiptDetails {
+                    receipt_uid: 0,
+                    leg_id: LegId(0),
+                    signer: AccountKeyring::Alice.to_account_id(),
+                    signature: AccountKeyring::Alice.sign(&msg.encode()).into(),
+                    metadata: ReceiptMetadata::from(metadata),
+                }],
+                default_portfolio_vec(alice.did),
+                1,
+            )
+        };
+
+        // The metadata must start with the prefix and fit in the maximum length.
+        assert_noop!(affirm(b"1234"), Error::ReceiptMetadataSchemaViolation);
+        assert_noop!(affirm(b"REF:12345"), Error::ReceiptMetadataSchemaViolation);
+        assert_ok!(affirm(b"REF:1234"));
+        assert_eq!(
+            Settlement::receipt_usage(AccountKeyring::Alice.to_account_id(), 0)
+                .unwrap()
+                .metadata,
+            ReceiptMetadata::from(b"REF:1234")
+        );
+    });
+}
+
+#[test]
+fn receipts_used_by_signer() {
+    ExtBuilder::default().build().execute_with(|| {
+        let alice = User::new(AccountKeyring::Alice);
+        let bob = User::new(AccountKeyring::Bob);
+        let venue_counter = create_venue(alice);
+        let amount = 100u128;
+        let legs: Vec<Leg> = [TICKER, TICKER2]
+            .iter()
+            .map(|ticker| Leg {
+                from: PortfolioId::default_portfolio(alice.did),
+                to: PortfolioId::default_portfolio(bob.did),
+                asset: *ticker,
+                amount,
+            })
+            .collect();
+        let instruction_id = Settlement::instruction_counter();
+        assert_ok!(Settlement::add_instruction(
+            alice.origin(),
+            venue_counter,
+            SettlementType::SettleOnAffirmation,
+            None,
+            None,
+            legs.clone(),
+        ));
+
+        let receipts: Vec<_> = legs
+            .iter()
+            .enumerate()
+            .map(|(i, leg)| {
+                let msg = Receipt {
+                    receipt_uid: 10 - i as u64,
+                    from: leg.from,
+                    to: leg.to,
+                    asset: leg.asset,
+                    amount,
+                };
+                ReceiptDetails {
+                    receipt_uid: msg.receipt_uid,
+                    leg_id: LegId(i as u64),
+                    signer: AccountKeyring::Alice.to_account_id(),
+                    signature: AccountKeyring::Alice.sign(&msg.encode()).into(),
+                    metadata: ReceiptMetadata::from(format!("leg {}", i).as_bytes()),
+                }
+            })
+            .collect();
+        assert_ok!(Settlement::affirm_with_receipts(
+            alice.origin(),
+            instruction_id,
+            receipts,
+            default_portfolio_vec(alice.did),
+            2
+        ));
+
+        let usage = |leg_id: u64| ReceiptUsageDetails {
+            instruction_id,
+            leg_id: LegId(leg_id),
+            metadata: ReceiptMetadata::from(format!("leg {}", leg_id).as_bytes()),
+        };
+        assert_eq!(
+            Settlement::receipts_used_by(&alice.acc()),
+            vec![(9, usage(1)), (10, usage(0))]
+        );
+        assert_eq!(Settlement::receipts_used_by(&bob.acc()), vec![]);
+
+        // Unclaimed receipts are no longer reported.
+        assert_ok!(Settlement::withdraw_affirmation(
+            alice.origin(),
+            instruction_id,
+            default_portfolio_vec(alice.did),
+            2
+        ));
+        assert_eq!(Settlement::receipts_used_by(&alice.acc()), vec![]);
+    });
+}
+
+#[test]
+fn venue_signers_usage() {
+    ExtBuilder::default().build().execute_with(|| {
+        let alice = User::new(AccountKeyring::Alice);
+        let bob = User::new(AccountKeyring::Bob);
+        let venue_counter = create_venue(alice);
+        assert_ok!(Settlement::update_venue_signers(
+            alice.origin(),
+            venue_counter,
+            vec![bob.acc()],
+            true
+        ));
+        let usages = || {
+            let mut usages = Settlement::venue_signers_usage(venue_counter);
+            usages.sort_by_key(|(signer, _)| signer.clone());
+            usages
+        };
+        let mut expected = vec![
+            (alice.acc(), VenueSignerUsage::default()),
+            (bob.acc(), VenueSignerUsage::default()),
+        ];
+        expected.sort_by_key(|(signer, _)| signer.clone());
+        assert_eq!(usages(), expected);
+
+        let amount = 100u128;
+        let legs: Vec<Leg> = [TICKER, TICKER2]
+            .iter()
+            .map(|ticker| Leg {
+                from: PortfolioId::default_portfolio(alice.did),
+                to: PortfolioId::default_portfolio(bob.did),
+                asset: *ticker,
+                amount,
+            })
+            .collect();
+        let instruction_id = Settlement::instruction_counter();
+        assert_ok!(Settlement::add_instruction(
+            alice.origin(),
+            venue_counter,
+            SettlementType::SettleOnAffirmation,
+            None,
+            None,
+            legs.clone(),
+        ));
+        let receipts = legs
+            .iter()
+            .enumerate()
+            .map(|(i, leg)| {
+                let msg = Receipt {
+                    receipt_uid: i as u64,
+                    from: leg.from,
+                    to: leg.to,
+                    asset: leg.asset,
+                    amount,
+                };
+                ReceiptDetails {
+                    receipt_uid: msg.receipt_uid,
+                    leg_id: LegId(i as u64),
+                    signer: AccountKeyring::Alice.to_account_id(),
+                    signature: AccountKeyring::Alice.sign(&msg.encode()).into(),
+                    metadata: ReceiptMetadata::default(),
+                }
+            })
+            .collect();
+        set_current_block_number(5);
+        assert_ok!(Settlement::affirm_with_receipts(
+            alice.origin(),
+            instruction_id,
+            receipts,
+            default_portfolio_vec(alice.did),
+            2
+        ));
+        let alice_usage = VenueSignerUsage {
+            receipts_signed: 2,
+            last_used: Some(5),
+        };
+        for (signer, usage) in &mut expected {
+            if *signer == alice.acc() {
+                *usage = alice_usage.clone();
+            }
+        }
+        assert_eq!(usages(), expected);
+
+        // Unclaiming the receipts keeps the usage, while removing a signer resets it.
+        assert_ok!(Settlement::withdraw_affirmation(
+            alice.origin(),
+            instruction_id,
+            default_portfolio_vec(alice.did),
+            2
+        ));
+        assert_eq!(usages(), expected);
+        assert_ok!(Settlement::update_venue_signers(
+            alice.origin(),
+            venue_counter,
+            vec![bob.acc()],
+            false
+        ));
+        assert_eq!(usages(), vec![(alice.acc(), alice_usage)]);
+    });
+}
+
+#[test]
+fn recurring_instructions() {
+    test_with_cdd_provider(|eve| {
+        let alice = User::new(AccountKeyring::Alice);
+        let bob = User::new(AccountKeyring::Bob);
+        let venue_counter = create_token_and_venue(TICKER, alice);
+        provide_scope_claim_to_multiple_parties(&[alice.did, bob.did], TICKER, eve);
+        let alice_balance = Asset::balance_of(&TICKER, alice.did);
+        let legs = vec![LegV2 {
+            from: PortfolioId::default_portfolio(alice.did),
+            to: PortfolioId::default_portfolio(bob.did),
+            asset: LegAsset::Fungible {
+                ticker: TICKER,
+                amount: 10,
+            },
+        }];
+        let create = |user: User, period, stop| {
+            Settlement::create_recurring_instruction(
+                user.origin(),
+                venue_counter,
+                legs.clone(),
+                None,
+                period,
+                stop,
+            )
+        };
+        let generated = || {
+            System::events()
+                .iter()
+                .filter_map(|record| match &record.event {
+                    super::storage::EventTest::Settlement(
+                        pallet_settlement::RawEvent::RecurringInstructionGenerated(_, _, id),
+                    ) => Some(*id),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+        };
+
+        // Only the venue creator can create a recurring instruction, with a valid period and stop.
+        assert_noop!(
+            create(bob, 5, RecurrenceStop::AfterOccurrences(2)),
+            Error::Unauthorized
+        );
+        assert_noop!(
+            create(alice, 0, RecurrenceStop::AfterOccurrences(2)),
+            Error::InvalidRecurrencePeriod
+        );
+        assert_noop!(
+            create(alice, 5, RecurrenceStop::AfterOccurrences(0)),
+            Error::InvalidRecurrenceStop
+        );
+        let now = System::block_number();
+        assert_noop!(
+            create(alice, 5, RecurrenceStop::AfterBlock(now + 4)),
+            Error::InvalidRecurrenceStop
+        );
+        assert_ok!(create(alice, 5, RecurrenceStop::AfterOccurrences(2)));
+        let id = RecurringInstructionId(0);
+        assert_eq!(
+            Settlement::recurring_instruction(id).unwrap().next_run,
+            now + 5
+        );
+
+        // An instruction with the same legs is generated every period.
+        for _ in 0..5 {
+            next_block();
+        }
+        let first = generated();
+        assert_eq!(first.len(), 1);
+        assert_instruction_status(first[0], InstructionStatus::Pending);
+        assert_eq!(Settlement::instruction_legsv2(first[0], LegId(0)), legs[0]);
+        assert_eq!(
+            Settlement::recurring_instruction(id).unwrap().occurrences,
+            1
+        );
+
+        // Generated instructions settle once affirmed.
+        assert_ok!(Settlement::affirm_instruction_v2(
+            alice.origin(),
+            first[0],
+            default_portfolio_vec(alice.did),
+            1,
+            0
+        ));
+        assert_ok!(Settlement::affirm_instruction_v2(
+            bob.origin(),
+            first[0],
+            default_portfolio_vec(bob.did),
+            1,
+            0
+        ));
+        next_block();
+        assert_balance(&TICKER, &alice, alice_balance - 10);
+        assert_balance(&TICKER, &bob, 10);
+
+        // The recurring instruction ends after its last occurrence.
+        for _ in 0..4 {
+            next_block();
+        }
+        assert_eq!(generated().len(), 2);
+        assert_eq!(Settlement::recurring_instruction(id), None);
+        for _ in 0..5 {
+            next_block();
+        }
+        assert_eq!(generated().len(), 2);
+
+        // Only the creator can cancel a recurring instruction, after which nothing is generated.
+        assert_ok!(create(alice, 5, RecurrenceStop::AfterBlock(now + 100)));
+        let id = RecurringInstructionId(1);
+        assert_noop!(
+            Settlement::cancel_recurring_instruction(bob.origin(), id),
+            Error::Unauthorized
+        );
+        assert_ok!(Settlement::cancel_recurring_instruction(alice.origin(), id));
+        assert_noop!(
+            Settlement::cancel_recurring_instruction(alice.origin(), id),
+            Error::UnknownRecurringInstruction
+        );
+        for _ in 0..5 {
+            next_block();
+        }
+        assert_eq!(generated().len(), 2);
+
+        // A recurring instruction whose first occurrence can't be scheduled isn't created.
+        let now = System::block_number();
+        for _ in 0..MaxScheduledPerBlock::get() {
+            assert_ok!(Scheduler::schedule(
+                root(),
+                now + 5,
+                None,
+                0,
+                Box::new(make_remark_proposal())
+            ));
+        }
+        assert_noop!(
+            create(alice, 5, RecurrenceStop::AfterOccurrences(2)),
+            Error::FailedToSchedule
+        );
+    });
+}
+
+#[test]
+fn settlement_freeze() {
+    test_with_cdd_provider(|eve| {
+        let mut alice = UserWithBalance::new(AccountKeyring::Alice, &[TICKER]);
+        let mut bob = UserWithBalance::new(AccountKeyring::Bob, &[TICKER]);
+        let venue_counter = create_token_and_venue(TICKER, alice.user);
+        provide_scope_claim_to_multiple_parties(&[alice.did, bob.did], TICKER, eve);
+        let instruction_id = Settlement::instruction_counter();
+        let block_number = System::block_number() + 1;
+        let amount = 10u128;
+        alice.refresh_init_balances();
+        bob.refresh_init_balances();
+
+        assert_ok!(Settlement::add_instruction(
+            alice.origin(),
+            venue_counter,
+            SettlementType::SettleManual(block_number),
+            None,
+            None,
+            vec![Leg {
+                from: PortfolioId::default_portfolio(alice.did),
+                to: PortfolioId::default_portfolio(bob.did),
+                asset: TICKER,
+                amount,
+            }],
+        ));
+        assert_affirm_instruction_with_one_leg!(alice.origin(), instruction_id, alice.did);
+
+        // Only the asset's agents can freeze its settlement.
+        assert_noop!(
+            Settlement::freeze_settlement(bob.origin(), TICKER),
+            pallet_external_agents::Error::<TestStorage>::UnauthorizedAgent
+        );
+        assert_noop!(
+            Settlement::unfreeze_settlement(alice.origin(), TICKER),
+            Error::SettlementNotFrozen
+        );
+        assert_ok!(Settlement::freeze_settlement(alice.origin(), TICKER));
+        assert_noop!(
+            Settlement::freeze_settlement(alice.origin(), TICKER),
+            Error::SettlementAlreadyFrozen
+        );
+
+        // Legs involving the ticker can't be affirmed while frozen.
+        assert_noop!(
+            Settlement::affirm_instruction(
+                bob.origin(),
+                instruction_id,
+                default_portfolio_vec(bob.did),
+                0
+            ),
+            Error::AssetSettlementFrozen
+        );
+        assert_ok!(Settlement::unfreeze_settlement(alice.origin(), TICKER));
+        assert_affirm_instruction_with_zero_leg!(bob.origin(), instruction_id, bob.did);
+
+        // Execution fails while frozen, without releasing the locked tokens.
+        assert_ok!(Settlement::freeze_settlement(alice.origin(), TICKER));
+        next_block();
+        assert_eq!(
+            Settlement::execute_manual_instruction(alice.origin(), instruction_id, 1, None),
+            Err(Error::AssetSettlementFrozen.into())
+        );
+        assert_instruction_status(instruction_id, InstructionStatus::Failed);
+        assert_locked_assets(&TICKER, &alice, amount);
+        alice.assert_all_balances_unchanged();
+        bob.assert_all_balances_unchanged();
+
+        // Once unfrozen, the failed instruction can be rescheduled and executed.
+        assert_ok!(Settlement::unfreeze_settlement(alice.origin(), TICKER));
+        assert_ok!(Settlement::reschedule_instruction(
+            alice.origin(),
+            instruction_id
+        ));
+        next_block();
+        alice.assert_balance_decreased(&TICKER, amount);
+        bob.assert_balance_increased(&TICKER, amount);
+    });
+}
+
+#[test]
+fn venue_quota() {
+    test_with_cdd_provider(|_eve| {
+        let alice = User::new(AccountKeyring::Alice);
+        let bob = User::new(AccountKeyring::Bob);
+        let venue_counter = create_token_and_venue(TICKER, alice);
+        let add_instruction = || {
+            Settlement::add_instruction(
+                alice.origin(),
+                venue_counter,
+                SettlementType::SettleOnAffirmation,
+                None,
+                None,
+                vec![Leg {
+                    from: PortfolioId::default_portfolio(alice.did),
+                    to: PortfolioId::default_portfolio(bob.did),
+                    asset: TICKER,
+                    amount: 10,
+                }],
+            )
+        };
+        let per_block_quota = VenueQuota {
+            max_open_instructions: None,
+            max_instructions_per_block: Some(1),
+        };
+
+        // Only the venue creator can set its quota, and an unlimited quota is removed.
+        assert_noop!(
+            Settlement::set_venue_quota(bob.origin(), venue_counter, Some(per_block_quota)),
+            Error::Unauthorized
+        );
+        assert_ok!(Settlement::set_venue_quota(
+            alice.origin(),
+            venue_counter,
+            Some(VenueQuota::default())
+        ));
+        assert_eq!(Settlement::venue_quota(venue_counter), None);
+
+        // A key can only create one instruction per block.
+        assert_ok!(Settlement::set_venue_quota(
+            alice.origin(),
+            venue_counter,
+            Some(per_block_quota)
+        ));
+        let instruction_id = Settlement::instruction_counter();
+        assert_ok!(add_instruction());
+        assert_noop!(add_instruction(), Error::VenueBlockInstructionsQuotaReached);
+        next_block();
+        assert_ok!(add_instruction());
+        assert_eq!(Settlement::venue_open_instructions(venue_counter), 2);
+
+        // No instruction can be created while the venue has too many open instructions.
+        assert_ok!(Settlement::set_venue_quota(
+            alice.origin(),
+            venue_counter,
+            Some(VenueQuota {
+                max_open_instructions: Some(2),
+                max_instructions_per_block: None,
+            })
+        ));
+        assert_noop!(add_instruction(), Error::VenueOpenInstructionsQuotaReached);
+        assert_ok!(Settlement::reject_instruction(
+            alice.origin(),
+            instruction_id,
+            PortfolioId::default_portfolio(alice.did),
+            1,
+            None,
+            None,
+        ));
+        assert_eq!(Settlement::venue_open_instructions(venue_counter), 1);
+        assert_ok!(add_instruction());
+    });
+}
+
+#[test]
+fn venue_pause_and_close() {
+    test_with_cdd_provider(|eve| {
+        let mut alice = UserWithBalance::new(AccountKeyring::Alice, &[TICKER]);
+        let mut bob = UserWithBalance::new(AccountKeyring::Bob, &[TICKER]);
+        let venue_counter = create_token_and_venue(TICKER, alice.user);
+        provide_scope_claim_to_multiple_parties(&[alice.did, bob.did], TICKER, eve);
+        let amount = 10u128;
+        alice.refresh_init_balances();
+        bob.refresh_init_balances();
+        let add_instruction = || {
+            Settlement::add_instruction(
+                alice.origin(),
+                venue_counter,
+                SettlementType::SettleOnAffirmation,
+                None,
+                None,
+                vec![Leg {
+                    from: PortfolioId::default_portfolio(alice.did),
+                    to: PortfolioId::default_portfolio(bob.did),
+                    asset: TICKER,
+                    amount,
+                }],
+            )
+        };
+        let instruction_id = Settlement::instruction_counter();
+        assert_ok!(add_instruction());
+        let request_id = Settlement::instruction_request_counter();
+        assert_ok!(Settlement::request_instruction(
+            bob.origin(),
+            venue_counter,
+            SettlementType::SettleOnAffirmation,
+            None,
+            None,
+            vec![LegV2 {
+                from: PortfolioId::default_portfolio(alice.did),
+                to: PortfolioId::default_portfolio(bob.did),
+                asset: LegAsset::Fungible {
+                    ticker: TICKER,
+                    amount,
+                },
+            }],
+            None,
+        ));
+
+        // Only the venue creator can pause the venue, which blocks new instructions.
+        assert_noop!(
+            Settlement::pause_venue(bob.origin(), venue_counter),
+            Error::Unauthorized
+        );
+        assert_noop!(
+            Settlement::resume_venue(alice.origin(), venue_counter),
+            Error::VenueNotPaused
+        );
+        assert_ok!(Settlement::pause_venue(alice.origin(), venue_counter));
+        assert_noop!(
+            Settlement::pause_venue(alice.origin(), venue_counter),
+            Error::VenuePaused
+        );
+        assert_noop!(add_instruction(), Error::VenuePaused);
+        assert_ok!(Settlement::resume_venue(alice.origin(), venue_counter));
+        assert_ok!(Settlement::pause_venue(alice.origin(), venue_counter));
+
+        // The venue can't be closed until its existing instruction settles.
+        assert_noop!(
+            Settlement::close_venue(alice.origin(), venue_counter, 1, 1),
+            Error::VenueHasOpenInstructions
+        );
+        assert_affirm_instruction_with_one_leg!(alice.origin(), instruction_id, alice.did);
+        assert_affirm_instruction_with_zero_leg!(bob.origin(), instruction_id, bob.did);
+        next_block();
+        alice.assert_balance_decreased(&TICKER, amount);
+        bob.assert_balance_increased(&TICKER, amount);
+
+        assert_ok!(Settlement::set_receipt_metadata_schema(
+            alice.origin(),
+            venue_counter,
+            Some(ReceiptMetadataSchema {
+                max_length: 8,
+                required_prefix: ReceiptMetadata::from(b"REF:"),
+            })
+        ));
+        assert_noop!(
+            Settlement::close_venue(alice.origin(), venue_counter, 0, 1),
+            Error::SignersCountTooSmall
+        );
+        assert_noop!(
+            Settlement::close_venue(alice.origin(), venue_counter, 1, 0),
+            Error::InstructionsCountTooSmall
+        );
+        assert_ok!(Settlement::close_venue(alice.origin(), venue_counter, 1, 1));
+        assert_eq!(Settlement::venue_info(venue_counter), None);
+        assert!(venue_instructions(venue_counter).is_empty());
+        assert_eq!(Settlement::receipt_metadata_schema(venue_counter), None);
+        assert_eq!(Settlement::instruction_request(request_id), None);
+        assert_eq!(
+            Settlement::venue_instruction_requests_count(venue_counter),
+            0
+        );
+        assert!(!Settlement::user_venues(alice.did).contains(&venue_counter));
+        assert!(!Settlement::venue_signers(venue_counter, alice.acc()));
+        assert!(!Settlement::venue_paused(venue_counter));
+        assert_noop!(add_instruction(), Error::InvalidVenue);
+    });
+}
+
+#[track_caller]
+fn assert_instruction_details(
+    instruction_id: InstructionId,
+    details: Instruction<Moment, BlockNumber>,
+) {
+    assert_eq!(Settlement::instruction_details(instruction_id), details);
+}
+
+#[track_caller]
+fn assert_instruction_status(
+    instruction_id: InstructionId,
+    status: InstructionStatus<BlockNumber>,
+) {
+    assert_eq!(Settlement::instruction_status(instruction_id), status);
+}
+
+#[track_caller]
+fn assert_balance(ticker: &Ticker, user: &User, balance: Balance) {
+    assert_eq!(Asset::balance_of(&ticker, user.did), balance);
+}
+
+#[track_caller]
+fn assert_user_affirms(instruction_id: InstructionId, user: &User, status: AffirmationStatus) {
+    assert_eq!(
+        Settlement::user_affirmations(PortfolioId::default_portfolio(user.did), instruction_id),
+        status
+    );
+
+    let affirms_received_status = match status {
+        AffirmationStatus::Pending => AffirmationStatus::Unknown,
+        AffirmationStatus::Affirmed => AffirmationStatus::Affirmed,
+        _ => return,
+    };
+
+    assert_eq!(
+        Settlement::affirms_received(instruction_id, PortfolioId::default_portfolio(user.did)),
+        affirms_received_status
+    );
+}
+
+#[track_caller]
+fn assert_leg_status(instruction_id: InstructionId, leg: LegId, status: LegStatus<AccountId>) {
+    assert_eq!(
+        Settlement::instruction_leg_status(instruction_id, leg),
+        status
+    );
+}
+
+#[track_caller]
+fn assert_affirms_pending(instruction_id: InstructionId, pending: u64) {
+    assert_eq!(
+        Settlement::instruction_affirms_pending(instruction_id),
+        pending
+    );
+}
+
+#[track_caller]
+fn assert_locked_assets(ticker: &Ticker, user: &User, num_of_assets: Balance) {
+    assert_eq!(
         Portfolio::locked_assets(PortfolioId::default_portfolio(user.did), ticker),
         num_of_assets
     );
 }
+
+#[test]
+fn net_obligations() {
+    test_with_cdd_provider(|eve| {
+        let alice = User::new(AccountKeyring::Alice);
+        let bob = User::new(AccountKeyring::Bob);
+        let venue_counter = create_token_and_venue(TICKER, alice);
+        provide_scope_claim_to_multiple_parties(&[alice.did, bob.did], TICKER, eve);
+        let alice_portfolio = PortfolioId::default_portfolio(alice.did);
+        let bob_portfolio = PortfolioId::default_portfolio(bob.did);
+        let leg = |from, to, amount| LegV2 {
+            from,
+            to,
+            asset: LegAsset::Fungible {
+                ticker: TICKER,
+                amount,
+            },
+        };
+        let add_instruction = |block, legs| {
+            assert_ok!(Settlement::add_instruction_with_memo_v2(
+                alice.origin(),
+                venue_counter,
+                SettlementType::SettleOnBlock(block),
+                None,
+                None,
+                legs,
+                None,
+            ));
+        };
+        let block = System::block_number();
+        add_instruction(
+            block + 10,
+            vec![
+                leg(alice_portfolio, bob_portfolio, 100),
+                leg(bob_portfolio, alice_portfolio, 20),
+            ],
+        );
+        add_instruction(block + 50, vec![leg(alice_portfolio, bob_portfolio, 30)]);
+        let obligation = |counterparty, receivable, deliverable| NetObligation {
+            counterparty,
+            ticker: TICKER,
+            receivable,
+            deliverable,
+        };
+
+        // Only the instructions settling within the range are included.
+        assert_eq!(
+            Settlement::net_obligations(alice.did, block, block + 20),
+            vec![obligation(bob.did, 20, 100)]
+        );
+        assert_eq!(
+            Settlement::net_obligations(alice.did, block, block + 100),
+            vec![obligation(bob.did, 20, 130)]
+        );
+        assert_eq!(
+            Settlement::net_obligations(bob.did, block + 20, block + 100),
+            vec![obligation(alice.did, 30, 0)]
+        );
+        assert!(Settlement::net_obligations(alice.did, block + 60, block + 100).is_empty());
+    });
+}
+
+#[test]
+fn portfolio_settlement_agents() {
+    test_with_cdd_provider(|eve| {
+        let alice = User::new(AccountKeyring::Alice);
+        let bob = User::new(AccountKeyring::Bob);
+        let agent = User::new(AccountKeyring::Charlie);
+        let venue_counter = create_token_and_venue(TICKER, alice);
+        provide_scope_claim_to_multiple_parties(&[alice.did, bob.did], TICKER, eve);
+        let alice_portfolio = PortfolioId::default_portfolio(alice.did);
+        let bob_portfolio = PortfolioId::default_portfolio(bob.did);
+        let instruction_id = Settlement::instruction_counter();
+        assert_ok!(Settlement::add_instruction_with_memo_v2(
+            alice.origin(),
+            venue_counter,
+            SettlementType::SettleOnBlock(System::block_number() + 10),
+            None,
+            None,
+            vec![LegV2 {
+                from: alice_portfolio,
+                to: bob_portfolio,
+                asset: LegAsset::Fungible {
+                    ticker: TICKER,
+                    amount: 100,
+                },
+            }],
+            None,
+        ));
+        let affirm = |portfolio| {
+            Settlement::affirm_instruction_v2(agent.origin(), instruction_id, vec![portfolio], 1, 0)
+        };
+
+        assert_noop!(
+            Settlement::set_portfolio_settlement_agent(
+                agent.origin(),
+                alice_portfolio,
+                Some(agent.did)
+            ),
+            PortfolioError::UnauthorizedCustodian
+        );
+        assert_noop!(
+            Settlement::set_portfolio_settlement_agent(
+                alice.origin(),
+                alice_portfolio,
+                Some(IdentityId::from(999))
+            ),
+            Error::InvalidSettlementAgent
+        );
+        assert_noop!(
+            affirm(alice_portfolio),
+            PortfolioError::UnauthorizedCustodian
+        );
+        assert_ok!(Settlement::set_portfolio_settlement_agent(
+            alice.origin(),
+            alice_portfolio,
+            Some(agent.did)
+        ));
+
+        // The agent can affirm, and withdraw affirmations, for the portfolio only.
+        assert_ok!(affirm(alice_portfolio));
+        assert_user_affirms(instruction_id, &alice, AffirmationStatus::Affirmed);
+        assert_locked_assets(&TICKER, &alice, 100);
+        assert_noop!(affirm(bob_portfolio), PortfolioError::UnauthorizedCustodian);
+        assert_ok!(Settlement::withdraw_affirmation_v2(
+            agent.origin(),
+            instruction_id,
+            vec![alice_portfolio],
+            1,
+            0
+        ));
+        assert_user_affirms(instruction_id, &alice, AffirmationStatus::Pending);
+        assert_locked_assets(&TICKER, &alice, 0);
+
+        assert_ok!(Settlement::set_portfolio_settlement_agent(
+            alice.origin(),
+            alice_portfolio,
+            None
+        ));
+        assert_noop!(
+            affirm(alice_portfolio),
+            PortfolioError::UnauthorizedCustodian
+        );
+
+        // The agent appointed by a custodian is removed when the custody changes.
+        assert_ok!(Settlement::set_portfolio_settlement_agent(
+            alice.origin(),
+            alice_portfolio,
+            Some(agent.did)
+        ));
+        let auth_id = Identity::add_auth(
+            alice.did,
+            Signatory::from(bob.did),
+            AuthorizationData::PortfolioCustody(alice_portfolio),
+            None,
+        );
+        assert_ok!(Portfolio::accept_portfolio_custody(bob.origin(), auth_id));
+        assert_eq!(
+            Settlement::portfolio_settlement_agent(alice_portfolio),
+            None
+        );
+        assert_noop!(
+            affirm(alice_portfolio),
+            PortfolioError::UnauthorizedCustodian
+        );
+    });
+}
+
+#[test]
+fn instruction_audit_export() {
+    test_with_cdd_provider(|eve| {
+        let alice = User::new(AccountKeyring::Alice);
+        let bob = User::new(AccountKeyring::Bob);
+        let venue_counter = create_token_and_venue(TICKER, alice);
+        let instruction_id = Settlement::instruction_counter();
+        provide_scope_claim_to_multiple_parties(&[alice.did, bob.did], TICKER, eve);
+
+        assert_ok!(Settlement::add_instruction(
+            alice.origin(),
+            venue_counter,
+            SettlementType::SettleOnAffirmation,
+            None,
+            None,
+            vec![Leg {
+                from: PortfolioId::default_portfolio(alice.did),
+                to: PortfolioId::default_portfolio(bob.did),
+                asset: TICKER,
+                amount: 100
+            }],
+        ));
+        assert_affirm_instruction_with_one_leg!(alice.origin(), instruction_id, alice.did);
+        // No audit record exists before the instruction is executed.
+        assert_eq!(Settlement::instruction_audit_export(instruction_id), None);
+
+        assert_affirm_instruction_with_zero_leg!(bob.origin(), instruction_id, bob.did);
+        next_block();
+        let executed_at = System::block_number();
+        assert_instruction_status(instruction_id, InstructionStatus::Success(executed_at));
+
+        let export = Settlement::instruction_audit_export(instruction_id).unwrap();
+        let record =
+            InstructionAuditRecord::<AccountId, u64, u32>::decode(&mut export.record.as_slice())
+                .unwrap();
+        assert_eq!(record.instruction_id, instruction_id);
+        assert_eq!(record.venue_id, venue_counter);
+        assert_eq!(record.legs.len(), 1);
+        assert_eq!(record.executed_at, executed_at);
+        assert!(record
+            .affirmations
+            .contains(&PortfolioId::default_portfolio(alice.did)));
+        assert!(record
+            .affirmations
+            .contains(&PortfolioId::default_portfolio(bob.did)));
+        assert!(record.receipts.is_empty());
+
+        // The digest verifies the exported record and rejects tampered ones.
+        assert!(Settlement::verify_instruction_audit(
+            instruction_id,
+            &export.record
+        ));
+        let mut tampered = export.record.clone();
+        *tampered.last_mut().unwrap() ^= 1;
+        assert!(!Settlement::verify_instruction_audit(
+            instruction_id,
+            &tampered
+        ));
+    });
+}
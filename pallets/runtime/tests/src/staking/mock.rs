@@ -51,8 +51,9 @@ use polymesh_common_utilities::{
     },
 };
 use polymesh_primitives::{
-    identity_id::GenesisIdentityRecord, Authorization, AuthorizationData, CddId, Claim, IdentityId,
-    InvestorUid, Moment, NFTId, Permissions, PortfolioId, ScopeId, SecondaryKey, Signatory, Ticker,
+    identity_id::GenesisIdentityRecord, Authorization, AuthorizationData, CddId, Claim, ClaimType,
+    IdentityId, InvestorUid, Moment, NFTId, Permissions, PortfolioId, Scope, ScopeId, SecondaryKey,
+    Signatory, Ticker,
 };
 use sp_core::H256;
 use sp_npos_elections::{
@@ -330,6 +331,37 @@ impl polymesh_common_utilities::traits::identity::Config for Test {
     type SchedulerOrigin = OriginCaller;
     type InitialPOLYX = InitialPOLYX;
     type MultiSigBalanceLimit = polymesh_runtime_common::MultiSigBalanceLimit;
+    type KeyRecoveryChallengePeriod = polymesh_runtime_common::KeyRecoveryChallengePeriod;
+    type CallFilterRemovalDelay = polymesh_runtime_common::CallFilterRemovalDelay;
+    type IdentityScheduler = Scheduler;
+    type IdentityScheduledCalls = Test;
+}
+
+impl polymesh_common_utilities::traits::identity::IdentitySchedulerCalls<Test> for Test {
+    fn revoke_scheduled_claim(
+        target: IdentityId,
+        claim_type: ClaimType,
+        issuer: IdentityId,
+        scope: Option<Scope>,
+        at: Moment,
+    ) -> RuntimeCall {
+        pallet_identity::Call::<Test>::revoke_scheduled_claim {
+            target,
+            claim_type,
+            issuer,
+            scope,
+            at,
+        }
+        .into()
+    }
+
+    fn revert_temporary_permissions(key: AccountId, revert_at: BlockNumber) -> RuntimeCall {
+        pallet_identity::Call::<Test>::revert_temporary_permissions { key, revert_at }.into()
+    }
+
+    fn end_key_probation(key: AccountId, ends_at: BlockNumber) -> RuntimeCall {
+        pallet_identity::Call::<Test>::end_key_probation { key, ends_at }.into()
+    }
 }
 
 parameter_types! {
@@ -466,6 +498,12 @@ impl AssetSubTrait for Test {
     fn ensure_investor_uniqueness_claims_allowed(_: &Ticker) -> DispatchResult {
         Ok(())
     }
+    fn accept_ticker_transfer(_: IdentityId, _: u64) -> DispatchResult {
+        unimplemented!()
+    }
+    fn accept_asset_ownership_transfer(_: IdentityId, _: u64) -> DispatchResult {
+        unimplemented!()
+    }
 }
 
 impl MultiSigSubTrait<AccountId> for Test {
@@ -502,6 +540,10 @@ impl PortfolioSubTrait<AccountId> for Test {
     fn unlock_nft(_: &PortfolioId, _: &Ticker, _: &NFTId) -> DispatchResult {
         unimplemented!()
     }
+
+    fn accept_portfolio_custody(_: IdentityId, _: u64) -> DispatchResult {
+        unimplemented!()
+    }
 }
 
 impl CheckCdd<AccountId> for Test {
@@ -4,7 +4,7 @@ use frame_support::{
     assert_ok,
     dispatch::{DispatchInfo, DispatchResult, Weight},
     parameter_types,
-    traits::{Currency, Imbalance, KeyOwnerProofSystem, OnInitialize, OnUnbalanced},
+    traits::{Currency, Get, Imbalance, KeyOwnerProofSystem, OnInitialize, OnUnbalanced},
     weights::{
         RuntimeDbWeight, WeightToFeeCoefficient, WeightToFeeCoefficients, WeightToFeePolynomial,
     },
@@ -173,6 +173,7 @@ parameter_types! {
     pub const MaxNumberOfFungibleAssets: u32 = 100;
     pub const MaxNumberOfNFTsPerLeg: u32 = 10;
     pub const MaxNumberOfNFTs: u32 = 100;
+    pub const InstructionRequestLifetime: BlockNumber = 10;
     pub const ImOnlineUnsignedPriority: TransactionPriority = TransactionPriority::max_value();
     pub const MaxAuthorities: u32 = 100_000;
     pub const MaxKeys: u32 = 10_000;
@@ -201,7 +202,7 @@ frame_support::construct_runtime!(
         Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>} = 5,
 
         // TransactionPayment: Genesis config dependencies: Balance.
-        TransactionPayment: pallet_transaction_payment::{Pallet, Event<T>, Storage} = 6,
+        TransactionPayment: pallet_transaction_payment::{Pallet, Call, Event<T>, Storage} = 6,
 
         // Identity: Genesis config deps: Timestamp.
         Identity: pallet_identity::{Pallet, Call, Storage, Event<T>, Config<T>} = 7,
@@ -263,7 +264,7 @@ frame_support::construct_runtime!(
         Treasury: pallet_treasury::{Pallet, Call, Event<T>} = 40,
         Utility: pallet_utility::{Pallet, Call, Storage, Event} = 41,
         Base: pallet_base::{Pallet, Call, Event} = 42,
-        ExternalAgents: pallet_external_agents::{Pallet, Call, Storage, Event} = 43,
+        ExternalAgents: pallet_external_agents::{Pallet, Call, Storage, Event<T>} = 43,
         Relayer: pallet_relayer::{Pallet, Call, Storage, Event<T>} = 44,
         Rewards: pallet_rewards::{Pallet, Call, Storage, Event<T>, Config<T>} = 45,
 
@@ -415,6 +416,8 @@ parameter_types! {
 
     pub const MaxStatsPerAsset: u32 = 10 + BENCHMARK_MAX_INCREASE;
     pub const MaxTransferConditionsPerAsset: u32 = 4 + BENCHMARK_MAX_INCREASE;
+    pub const MaxExemptionsPerBatch: u32 = 1_000;
+    pub const TransferVolumeEraLength: BlockNumber = EPOCH_DURATION_IN_BLOCKS * SessionsPerEra::get();
 
     pub const MaxConditionComplexity: u32 = 50;
     pub const MaxDefaultTrustedClaimIssuers: usize = 10;
@@ -442,6 +445,36 @@ parameter_types! {
 thread_local! {
     pub static FORCE_SESSION_END: RefCell<bool> = RefCell::new(false);
     pub static SESSION_LENGTH: RefCell<BlockNumber> = RefCell::new(2);
+    pub static SETTLEMENT_EXECUTION_WEIGHT: RefCell<Option<Weight>> = RefCell::new(None);
+    pub static INSTRUCTION_STATUS_RETENTION: RefCell<Option<BlockNumber>> = RefCell::new(None);
+    pub static REJECTED_INSTRUCTION_RETENTION: RefCell<BlockNumber> = RefCell::new(0);
+}
+
+/// Maximum weight of the legs of an instruction executed in a block.
+/// Tests can lower it through `SETTLEMENT_EXECUTION_WEIGHT`.
+pub struct MaxSettlementExecutionWeight;
+impl Get<Weight> for MaxSettlementExecutionWeight {
+    fn get() -> Weight {
+        SETTLEMENT_EXECUTION_WEIGHT
+            .with(|w| *w.borrow())
+            .unwrap_or_else(|| Perbill::from_percent(40) * MaximumBlockWeight::get())
+    }
+}
+
+/// Statuses of settled instructions are kept forever, unless a test sets `INSTRUCTION_STATUS_RETENTION`.
+pub struct InstructionStatusRetention;
+impl Get<Option<BlockNumber>> for InstructionStatusRetention {
+    fn get() -> Option<BlockNumber> {
+        INSTRUCTION_STATUS_RETENTION.with(|r| *r.borrow())
+    }
+}
+
+/// Rejected instructions are pruned on rejection, unless a test sets `REJECTED_INSTRUCTION_RETENTION`.
+pub struct RejectedInstructionRetention;
+impl Get<BlockNumber> for RejectedInstructionRetention {
+    fn get() -> BlockNumber {
+        REJECTED_INSTRUCTION_RETENTION.with(|r| *r.borrow())
+    }
 }
 
 pub type NegativeImbalance<T> =
@@ -587,6 +620,10 @@ impl polymesh_common_utilities::traits::identity::Config for TestStorage {
     type SchedulerOrigin = OriginCaller;
     type InitialPOLYX = InitialPOLYX;
     type MultiSigBalanceLimit = polymesh_runtime_common::MultiSigBalanceLimit;
+    type KeyRecoveryChallengePeriod = polymesh_runtime_common::KeyRecoveryChallengePeriod;
+    type CallFilterRemovalDelay = polymesh_runtime_common::CallFilterRemovalDelay;
+    type IdentityScheduler = Scheduler;
+    type IdentityScheduledCalls = TestStorage;
 }
 
 pub struct TestSessionHandler;
@@ -1,21 +1,26 @@
 use super::ext_builder::ExtBuilder;
-use super::storage::{RuntimeCall, TestStorage};
+use super::storage::{root, RuntimeCall, TestStorage, User};
 use codec::Encode;
 use frame_support::{
-    dispatch::{DispatchClass, DispatchInfo, GetDispatchInfo, Pays, PostDispatchInfo, Weight},
+    assert_noop, assert_ok,
+    dispatch::{
+        DispatchClass, DispatchError, DispatchInfo, GetDispatchInfo, Pays, PostDispatchInfo, Weight,
+    },
     traits::Currency,
     weights::WeightToFee,
 };
 use pallet_balances::Call as BalancesCall;
-use pallet_transaction_payment::{ChargeTransactionPayment, Multiplier, RuntimeDispatchInfo};
-use polymesh_primitives::AccountId;
+use pallet_transaction_payment::{
+    ChargeTransactionPayment, FeeTier, Multiplier, RuntimeDispatchInfo, MAX_FEE_TIERS,
+};
 use polymesh_primitives::TransactionError;
+use polymesh_primitives::{AccountId, Claim, ClaimType, CustomClaimTypeId};
 use sp_arithmetic::traits::One;
 use sp_runtime::{
     testing::TestXt,
     traits::SignedExtension,
     transaction_validity::{InvalidTransaction, TransactionValidityError},
-    FixedPointNumber, MultiAddress,
+    FixedPointNumber, MultiAddress, Permill,
 };
 use test_client::AccountKeyring;
 
@@ -29,6 +34,8 @@ fn call() -> <TestStorage as frame_system::Config>::RuntimeCall {
 type Balances = pallet_balances::Module<TestStorage>;
 type System = frame_system::Pallet<TestStorage>;
 type TransactionPayment = pallet_transaction_payment::Module<TestStorage>;
+type Identity = pallet_identity::Module<TestStorage>;
+type Error = pallet_transaction_payment::Error<TestStorage>;
 
 /// create a transaction info struct from weight. Handy to avoid building the whole struct.
 pub fn info_from_weight(w: u64) -> DispatchInfo {
@@ -240,7 +247,7 @@ fn query_info_works() {
             TransactionPayment::put_next_fee_multiplier(Multiplier::saturating_from_rational(3, 2));
 
             assert_eq!(
-                TransactionPayment::query_info(xt, len),
+                TransactionPayment::query_info(xt, len, None),
                 RuntimeDispatchInfo {
                     weight: info.weight,
                     class: info.class,
@@ -552,3 +559,97 @@ fn operational_tx_with_tip_ext(cdd: AccountId, gc: AccountId) {
         .pre_dispatch(&cdd, &call, &operational_info, len)
         .is_ok());
 }
+
+#[test]
+fn fee_tiers_reduce_length_and_weight_fees() {
+    ExtBuilder::default()
+        .monied(true)
+        .transaction_fees(5, 1, 1)
+        .build()
+        .execute_with(|| {
+            let issuer = User::new(AccountKeyring::Dave);
+            let user = User::new(AccountKeyring::Charlie).balance(1_000_000_000);
+            assert_ok!(Identity::register_custom_claim_type(
+                issuer.origin(),
+                "market-maker".into()
+            ));
+            let claim_id = CustomClaimTypeId(1);
+            let tier = FeeTier {
+                claim_type: ClaimType::Custom(claim_id),
+                issuer: issuer.did,
+                length_fee: Permill::from_percent(50),
+                weight_fee: Permill::from_percent(20),
+            };
+
+            // Only governance sets the fee tiers.
+            assert_noop!(
+                TransactionPayment::set_fee_tiers(user.origin(), vec![tier.clone()]),
+                DispatchError::BadOrigin
+            );
+            assert_noop!(
+                TransactionPayment::set_fee_tiers(root(), vec![tier.clone(); MAX_FEE_TIERS + 1]),
+                Error::TooManyFeeTiers
+            );
+            assert_ok!(TransactionPayment::set_fee_tiers(root(), vec![tier]));
+
+            let len = 10;
+            let info = info_from_weight(100);
+            let charge = || {
+                let before = Balances::free_balance(&user.acc());
+                let pre = ChargeTransactionPayment::<TestStorage>::from(0)
+                    .pre_dispatch(&user.acc(), &call(), &info, len)
+                    .unwrap();
+                ChargeTransactionPayment::<TestStorage>::post_dispatch(
+                    Some(pre),
+                    &info,
+                    &default_post_info(),
+                    len,
+                    &Ok(()),
+                )
+                .unwrap();
+                before - Balances::free_balance(&user.acc())
+            };
+
+            // Identities without the claim pay the full fee.
+            assert_eq!(
+                charge(),
+                TransactionPayment::compute_fee(len as u32, &info, 0)
+            );
+
+            // Holders of the claim pay reduced length and weight fees.
+            assert_ok!(Identity::add_claim(
+                issuer.origin(),
+                user.did,
+                Claim::Custom(claim_id, None),
+                None
+            ));
+            let fee = TransactionPayment::compute_fee_details(len as u32, &info, 0)
+                .inclusion_fee
+                .unwrap();
+            assert_eq!(
+                charge(),
+                fee.base_fee + fee.len_fee / 2 + fee.adjusted_weight_fee / 5
+            );
+
+            // Fee queries report the reduced fee of the signer.
+            let xt = TestXt::new(call(), Some((0u64, ())));
+            let len = xt.encode().len() as u32;
+            let fee = TransactionPayment::compute_fee_details(len, &xt.get_dispatch_info(), 0)
+                .inclusion_fee
+                .unwrap();
+            let reduced_fee = fee.base_fee + fee.len_fee / 2 + fee.adjusted_weight_fee / 5;
+            assert_eq!(
+                TransactionPayment::query_info(xt.clone(), len, Some(user.acc())).partial_fee,
+                reduced_fee
+            );
+            assert_eq!(
+                TransactionPayment::query_fee_details(xt.clone(), len, Some(user.acc()))
+                    .final_fee(),
+                reduced_fee
+            );
+            assert_eq!(
+                TransactionPayment::query_info(xt, len, None).partial_fee,
+                fee.base_fee + fee.len_fee + fee.adjusted_weight_fee
+            );
+        });
+}
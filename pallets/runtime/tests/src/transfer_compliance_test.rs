@@ -7,11 +7,14 @@ use super::{
 use frame_support::{
     assert_noop, assert_ok,
     dispatch::{DispatchError, DispatchResult},
+    traits::Get,
+    StorageDoubleMap,
 };
+use pallet_statistics::{TransferVolumes, MAX_TRANSFER_VOLUME_ERAS};
 use polymesh_primitives::{
     asset::AssetType, investor_zkproof_data::v1::InvestorZKProofData, jurisdiction::CountryCode,
-    statistics::*, transfer_compliance::*, AccountId, Balance, CddId, Claim, ClaimType, IdentityId,
-    InvestorUid, PortfolioId, Scope, ScopeId, Ticker,
+    statistics::*, transfer_compliance::*, AccountId, Balance, BatchImport, CddId, Claim,
+    ClaimType, IdentityId, InvestorUid, PortfolioId, Scope, ScopeId, Ticker,
 };
 use sp_arithmetic::Permill;
 use std::collections::{HashMap, HashSet};
@@ -21,6 +24,7 @@ type Origin = <TestStorage as frame_system::Config>::RuntimeOrigin;
 type Identity = pallet_identity::Module<TestStorage>;
 type Asset = pallet_asset::Module<TestStorage>;
 type Statistics = pallet_statistics::Module<TestStorage>;
+type System = frame_system::Pallet<TestStorage>;
 type ComplianceManager = pallet_compliance_manager::Module<TestStorage>;
 type Error = pallet_statistics::Error<TestStorage>;
 type AssetError = pallet_asset::Error<TestStorage>;
@@ -1140,3 +1144,245 @@ fn ensure_invalid_transfer_conditions_ext() {
         Error::TransferConditionLimitReached
     );
 }
+
+#[test]
+fn exemption_import() {
+    ExtBuilder::default()
+        .cdd_providers(vec![CDD_PROVIDER.to_account_id()])
+        .build()
+        .execute_with(exemption_import_ext);
+}
+fn exemption_import_ext() {
+    // Create an asset.
+    let tracker = AssetTracker::new();
+    let exempt_key = TransferConditionExemptKey {
+        asset: tracker.asset_scope,
+        op: StatOpType::Count,
+        claim_type: None,
+    };
+    let entities = (0u128..10).map(IdentityId::from).collect::<Vec<ScopeId>>();
+    let mut expected = BatchImport::new(entities.len() as u32, Default::default());
+    let batch_hashes = entities
+        .iter()
+        .map(|entity| {
+            expected.add_entry(entity);
+            expected.running_hash
+        })
+        .collect::<Vec<_>>();
+
+    // Finalizing requires a started import.
+    assert_noop!(
+        Statistics::finalize_exemption_import(tracker.owner_origin(), exempt_key),
+        Error::ExemptionImportNotFound
+    );
+    assert_ok!(Statistics::start_exemption_import(
+        tracker.owner_origin(),
+        exempt_key,
+        entities.len() as u32,
+        expected.running_hash,
+    ));
+    assert_noop!(
+        Statistics::start_exemption_import(
+            tracker.owner_origin(),
+            exempt_key,
+            1,
+            Default::default()
+        ),
+        Error::ExemptionImportInProgress
+    );
+
+    // A batch that doesn't match its hash is rejected.
+    assert_noop!(
+        Statistics::import_exemption_batch(
+            tracker.owner_origin(),
+            exempt_key,
+            entities[1..7].to_vec(),
+            batch_hashes[5],
+        ),
+        Error::ExemptionBatchHashMismatch
+    );
+
+    // Import the first batch.
+    assert_ok!(Statistics::import_exemption_batch(
+        tracker.owner_origin(),
+        exempt_key,
+        entities[..6].to_vec(),
+        batch_hashes[5],
+    ));
+    assert_noop!(
+        Statistics::finalize_exemption_import(tracker.owner_origin(), exempt_key),
+        Error::ExemptionImportIncomplete
+    );
+    assert_noop!(
+        Statistics::import_exemption_batch(
+            tracker.owner_origin(),
+            exempt_key,
+            entities.clone(),
+            expected.running_hash
+        ),
+        Error::ExemptionImportOverflow
+    );
+
+    // Import the rest and finalize.
+    assert_ok!(Statistics::import_exemption_batch(
+        tracker.owner_origin(),
+        exempt_key,
+        entities[6..].to_vec(),
+        batch_hashes[9],
+    ));
+    assert_ok!(Statistics::finalize_exemption_import(
+        tracker.owner_origin(),
+        exempt_key
+    ));
+    assert_eq!(Statistics::exemption_imports(exempt_key), None);
+    for entity in &entities {
+        assert!(Statistics::transfer_condition_exempt_entities(
+            exempt_key, entity
+        ));
+    }
+
+    // Export in pages.
+    let first = Statistics::exempt_entities_page(&exempt_key, None, 4);
+    assert_eq!(first.len(), 4);
+    let rest = Statistics::exempt_entities_page(&exempt_key, first.last().cloned(), 10);
+    assert_eq!(rest.len(), 6);
+    let exported = first.into_iter().chain(rest).collect::<HashSet<_>>();
+    assert_eq!(exported, entities.iter().cloned().collect::<HashSet<_>>());
+}
+
+#[test]
+fn exemption_import_hash_mismatch() {
+    ExtBuilder::default()
+        .cdd_providers(vec![CDD_PROVIDER.to_account_id()])
+        .build()
+        .execute_with(exemption_import_hash_mismatch_ext);
+}
+fn exemption_import_hash_mismatch_ext() {
+    // Create an asset.
+    let tracker = AssetTracker::new();
+    let exempt_key = TransferConditionExemptKey {
+        asset: tracker.asset_scope,
+        op: StatOpType::Count,
+        claim_type: None,
+    };
+    let entities = (0u128..3).map(IdentityId::from).collect::<Vec<ScopeId>>();
+
+    let mut import = BatchImport::new(entities.len() as u32, Default::default());
+    entities.iter().for_each(|entity| import.add_entry(entity));
+
+    assert_ok!(Statistics::start_exemption_import(
+        tracker.owner_origin(),
+        exempt_key,
+        entities.len() as u32,
+        Default::default(),
+    ));
+    assert_ok!(Statistics::import_exemption_batch(
+        tracker.owner_origin(),
+        exempt_key,
+        entities,
+        import.running_hash,
+    ));
+    assert_noop!(
+        Statistics::finalize_exemption_import(tracker.owner_origin(), exempt_key),
+        Error::ExemptionImportHashMismatch
+    );
+
+    // Aborting drops the import progress.
+    assert_ok!(Statistics::abort_exemption_import(
+        tracker.owner_origin(),
+        exempt_key
+    ));
+    assert_eq!(Statistics::exemption_imports(exempt_key), None);
+}
+
+#[test]
+fn transfer_volume_tracking() {
+    ExtBuilder::default()
+        .cdd_providers(vec![CDD_PROVIDER.to_account_id()])
+        .build()
+        .execute_with(transfer_volume_tracking_ext);
+}
+fn transfer_volume_tracking_ext() {
+    // Create an asset.
+    let mut tracker = AssetTracker::new();
+    let owner_id = tracker.owner().id;
+    let investor = tracker.new_investor();
+    tracker.mint(1_000_000);
+
+    // Transfers aren't tracked before the asset opts in.
+    tracker.do_valid_transfer(owner_id, investor, 1_000);
+    let era = Statistics::current_transfer_volume_era();
+    assert_eq!(
+        Statistics::transfer_volumes(tracker.asset_scope, era),
+        TransferVolume::default()
+    );
+
+    assert_ok!(Statistics::set_transfer_volume_tracking(
+        tracker.owner_origin(),
+        tracker.asset_scope,
+        true
+    ));
+    // Minting isn't counted as a transfer.
+    tracker.mint(1_000);
+    tracker.do_valid_transfer(owner_id, investor, 1_000);
+    tracker.do_valid_transfer(investor, owner_id, 500);
+    assert_eq!(
+        Statistics::transfer_volumes(tracker.asset_scope, era),
+        TransferVolume {
+            count: 2,
+            volume: 1_500
+        }
+    );
+
+    // Transfers in the next era are aggregated separately.
+    let era_length = <TestStorage as pallet_statistics::Config>::TransferVolumeEraLength::get();
+    System::set_block_number(System::block_number() + era_length);
+    tracker.do_valid_transfer(owner_id, investor, 100);
+    assert_eq!(
+        Statistics::transfer_volumes_in_range(tracker.asset_scope, era, era + 1),
+        vec![
+            (
+                era,
+                TransferVolume {
+                    count: 2,
+                    volume: 1_500
+                }
+            ),
+            (
+                era + 1,
+                TransferVolume {
+                    count: 1,
+                    volume: 100
+                }
+            ),
+        ]
+    );
+
+    // Only the first `MAX_TRANSFER_VOLUME_ERAS` eras of a range are read.
+    let last_era = era + MAX_TRANSFER_VOLUME_ERAS;
+    TransferVolumes::insert(tracker.asset_scope, last_era, TransferVolume::default());
+    let eras = |start_era, end_era| {
+        Statistics::transfer_volumes_in_range(tracker.asset_scope, start_era, end_era)
+            .into_iter()
+            .map(|(era, _)| era)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(eras(era, last_era), vec![era, era + 1]);
+    assert_eq!(eras(era + 1, u32::MAX), vec![era + 1, last_era]);
+    TransferVolumes::remove(tracker.asset_scope, last_era);
+
+    // Disabling the tracking keeps the past volumes.
+    assert_ok!(Statistics::set_transfer_volume_tracking(
+        tracker.owner_origin(),
+        tracker.asset_scope,
+        false
+    ));
+    tracker.do_valid_transfer(owner_id, investor, 100);
+    assert_eq!(
+        Statistics::transfer_volumes(tracker.asset_scope, era + 1),
+        TransferVolume {
+            count: 1,
+            volume: 100
+        }
+    );
+}
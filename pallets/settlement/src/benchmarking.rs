@@ -13,6 +13,7 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <http://www.gnu.org/licenses/>.
 
+use crate::test_utils::*;
 use crate::*;
 
 pub use frame_benchmarking::{account, benchmarks};
@@ -30,14 +31,12 @@ use polymesh_common_utilities::{
 };
 use polymesh_primitives::{
     asset::NonFungibleType,
-    checked_inc::CheckedInc,
-    statistics::{Stat2ndKey, StatType, StatUpdate},
+    statistics::{AssetScope, Stat2ndKey, StatType, StatUpdate},
     transfer_compliance::{TransferCondition, TransferConditionExemptKey},
-    Claim, Condition, ConditionType, CountryCode, IdentityId, NFTId, PortfolioId, PortfolioKind,
-    PortfolioName, PortfolioNumber, Scope, Ticker, TrustedIssuer,
+    Claim, ClaimType, Condition, ConditionType, CountryCode, IdentityId, NFTId, PortfolioId,
+    PortfolioKind, Scope, Ticker, TrustedIssuer, MAX_MEMO_LEN,
 };
 use scale_info::prelude::format;
-use sp_runtime::SaturatedConversion;
 use sp_std::convert::TryInto;
 use sp_std::prelude::*;
 
@@ -47,14 +46,8 @@ use sp_runtime::MultiSignature;
 const MAX_VENUE_DETAILS_LENGTH: u32 = ENSURED_MAX_LEN;
 const MAX_SIGNERS_ALLOWED: u32 = 50;
 const MAX_VENUE_ALLOWED: u32 = 100;
-
-type Portfolio<T> = pallet_portfolio::Module<T>;
-
-#[derive(Encode, Decode, Clone, Copy)]
-pub struct UserData<T: Config> {
-    pub account: T::AccountId,
-    pub did: IdentityId,
-}
+// Maximum number of tasks the scheduler accepts for a block.
+const MAX_SCHEDULED_PER_BLOCK: u32 = 50;
 
 impl<T: Config> From<&User<T>> for UserData<T> {
     fn from(user: &User<T>) -> Self {
@@ -65,6 +58,7 @@ impl<T: Config> From<&User<T>> for UserData<T> {
     }
 }
 
+/// The parameters returned by [`setup_v2_extrinsics_parameters`].
 pub struct BaseV2Parameters<T: Config> {
     pub sender: User<T>,
     pub receiver: User<T>,
@@ -75,41 +69,188 @@ pub struct BaseV2Parameters<T: Config> {
     pub sender_portfolios: Vec<PortfolioId>,
     pub settlement_type: SettlementType<T::BlockNumber>,
     pub date: Option<T::Moment>,
-    pub memo: Option<InstructionMemo>,
+    pub memo: Option<Memo>,
 }
 
-fn set_block_number<T: Config>(new_block_no: u64) {
-    frame_system::Pallet::<T>::set_block_number(new_block_no.saturated_into::<T::BlockNumber>());
+/// Creates an asset owned by `owner`.
+pub fn create_asset_<T: Config>(owner: &User<T>) -> Ticker {
+    make_asset::<T>(owner, Some(&Ticker::generate(8u64)))
 }
 
-fn creator<T: Config + TestUtilsFn<AccountIdOf<T>>>() -> User<T> {
-    UserBuilder::<T>::default().generate_did().build("creator")
+/// Creates a user portfolio for `user`, or for a new user if `user` is `None`.
+fn generate_portfolio<T: Config + TestUtilsFn<AccountIdOf<T>>>(
+    portfolio_to: &'static str,
+    pseudo_random_no: u32,
+    user: Option<UserData<T>>,
+) -> PortfolioId {
+    let u = match user {
+        None => {
+            let user = UserBuilder::<T>::default()
+                .generate_did()
+                .seed(pseudo_random_no)
+                .build(portfolio_to);
+            UserData::from(&user)
+        }
+        Some(u) => u,
+    };
+    create_user_portfolio::<T>(&u)
+}
+
+/// Creates a fungible asset for the given `ticker` and returns a `Vec<LegV2>` containing `n_legs`.
+fn setup_fungible_legs_v2<T: Config>(
+    sender: User<T>,
+    receiver: User<T>,
+    ticker: Ticker,
+    n_legs: u32,
+) -> Vec<LegV2> {
+    make_asset(&sender, Some(ticker.as_ref()));
+    (0..n_legs)
+        .map(|_| LegV2 {
+            from: PortfolioId {
+                did: sender.did(),
+                kind: PortfolioKind::Default,
+            },
+            to: PortfolioId {
+                did: receiver.did(),
+                kind: PortfolioKind::Default,
+            },
+            asset: LegAsset::Fungible {
+                ticker: ticker.clone(),
+                amount: ONE_UNIT,
+            },
+        })
+        .collect()
+}
+
+/// Creates an nft collection for `ticker`, mints `n_nfts` for `token_sender`, and returns a `Vec<LegV2>`
+/// containing `n_legs` with a total of `n_nfts` split among the legs.
+/// For this function only calls with the minimum number of legs to contain all `n_nfts` are allowed.
+/// E.g: If n_nfts = 78, n_legs must be equal to 8 (considering that MaxNumberOfNFTsPerLeg is equal to 10).
+fn setup_nft_legs<T: Config>(
+    sender: User<T>,
+    receiver: User<T>,
+    ticker: Ticker,
+    n_legs: u32,
+    n_nfts: u32,
+) -> Vec<LegV2> {
+    create_collection_issue_nfts::<T>(
+        sender.origin().into(),
+        ticker,
+        Some(NonFungibleType::Derivative),
+        0,
+        n_nfts,
+        PortfolioKind::Default,
+    );
+
+    let max_nfts_per_leg = T::MaxNumberOfNFTsPerLeg::get();
+    let last_leg_len = n_nfts % max_nfts_per_leg;
+    let full_legs = n_nfts / max_nfts_per_leg;
+
+    // Creates the NFTs for each leg. All legs except the last one will have T::MaxNumberOfNFTsPerLeg NFTs each.
+    let mut nfts: Vec<NFTs> = (0..full_legs)
+        .map(|leg_index| {
+            NFTs::new(
+                ticker,
+                (0..max_nfts_per_leg)
+                    .map(|nft_index| NFTId((leg_index * max_nfts_per_leg + nft_index + 1) as u64))
+                    .collect(),
+            )
+            .unwrap()
+        })
+        .collect();
+    // The last leg may have less than T::MaxNumberOfNFTsPerLeg NFTs
+    if last_leg_len > 0 {
+        nfts.push(NFTs::new_unverified(
+            ticker,
+            (0..last_leg_len)
+                .map(|nft_index| {
+                    NFTId((max_nfts_per_leg * (nfts.len() as u32) + nft_index + 1) as u64)
+                })
+                .collect(),
+        ));
+    }
+    // For this function only calls with the minimum number of legs to contain all `n_nfts` are allowed
+    assert_eq!(nfts.len() as u32, n_legs);
+    // Creates each leg
+    (0..n_legs)
+        .map(|index| LegV2 {
+            from: PortfolioId {
+                did: sender.did(),
+                kind: PortfolioKind::Default,
+            },
+            to: PortfolioId {
+                did: receiver.did(),
+                kind: PortfolioKind::Default,
+            },
+
+            asset: LegAsset::NonFungible(nfts[index as usize].clone()),
+        })
+        .collect()
 }
 
-/// Set venue related storage without any sanity checks.
-fn create_venue_<T: Config>(did: IdentityId, signers: Vec<T::AccountId>) -> VenueId {
-    let venue = Venue {
-        creator: did,
-        venue_type: VenueType::Distribution,
+/// Creates the basic environment for executing the benchmarks for the v2 extrinsics.
+/// This includes: creating one fungible asset, one NFT collection with `n_nfts`, and
+/// the instruction legs filled with `n_nfts` and `fungible_transfers`.
+/// All other parameters are also included in the `BaseV2Parameters` struct.
+fn setup_v2_extrinsics_parameters<T>(fungible_transfers: u32, n_nfts: u32) -> BaseV2Parameters<T>
+where
+    T: TestUtilsFn<AccountIdOf<T>> + Config,
+{
+    let max_nfts = T::MaxNumberOfNFTsPerLeg::get();
+    let alice = UserBuilder::<T>::default().generate_did().build("Alice");
+    let bob = UserBuilder::<T>::default().generate_did().build("Bob");
+    let fungible_ticker: Ticker = Ticker::from_slice_truncated(b"TICKER".as_ref());
+    let nft_ticker: Ticker = Ticker::from_slice_truncated(b"TICKER0".as_ref());
+    let venue_id = create_venue_::<T>(alice.did(), vec![]);
+    let sender_portfolios = vec![PortfolioId {
+        did: alice.did(),
+        kind: PortfolioKind::Default,
+    }];
+
+    let mut fungible_legs = setup_fungible_legs_v2(
+        alice.clone(),
+        bob.clone(),
+        fungible_ticker,
+        fungible_transfers,
+    );
+    let n_nft_legs = if n_nfts % max_nfts == 0 {
+        n_nfts / max_nfts
+    } else {
+        n_nfts / max_nfts + 1
     };
-    // NB: Venue counter starts with 1.
-    let venue_counter = Module::<T>::venue_counter();
-    VenueInfo::insert(venue_counter, venue);
-    for signer in signers {
-        <VenueSigners<T>>::insert(venue_counter, signer, true);
-    }
-    VenueCounter::put(venue_counter.checked_inc().unwrap());
-    venue_counter
+    let mut non_fungible_legs =
+        setup_nft_legs(alice.clone(), bob.clone(), nft_ticker, n_nft_legs, n_nfts);
+    non_fungible_legs.append(&mut fungible_legs);
+    let legs_v2 = non_fungible_legs;
+
+    let settlement_type = SettlementType::SettleOnBlock(100u32.into());
+    let date = Some(99999999u32.into());
+    let memo = Some(Memo::default());
+
+    BaseV2Parameters::<T> {
+        sender: alice,
+        receiver: bob,
+        fungible_ticker,
+        nft_ticker,
+        venue_id,
+        legs_v2,
+        sender_portfolios,
+        settlement_type,
+        date,
+        memo,
+    }
 }
 
-// create asset
-pub fn create_asset_<T: Config>(owner: &User<T>) -> Ticker {
-    make_asset::<T>(owner, Some(&Ticker::generate(8u64)))
+/// Moves to the next block, running the scheduled calls of that block.
+pub fn next_block<T: Config + pallet_scheduler::Config>() {
+    use frame_support::traits::OnInitialize;
+    let block_number = frame_system::Pallet::<T>::block_number() + 1u32.into();
+    frame_system::Pallet::<T>::set_block_number(block_number);
+    pallet_scheduler::Pallet::<T>::on_initialize(block_number);
 }
 
-// fund portfolio
-fn fund_portfolio<T: Config>(portfolio: &PortfolioId, ticker: &Ticker, amount: Balance) {
-    PortfolioAssetBalances::insert(portfolio, ticker, amount);
+fn creator<T: Config + TestUtilsFn<AccountIdOf<T>>>() -> User<T> {
+    UserBuilder::<T>::default().generate_did().build("creator")
 }
 
 fn setup_leg_and_portfolio<T: Config + TestUtilsFn<AccountIdOf<T>>>(
@@ -142,32 +283,6 @@ fn setup_leg_and_portfolio<T: Config + TestUtilsFn<AccountIdOf<T>>>(
     sender_portfolios.push(portfolio_from);
 }
 
-fn generate_portfolio<T: Config + TestUtilsFn<AccountIdOf<T>>>(
-    portfolio_to: &'static str,
-    pseudo_random_no: u32,
-    user: Option<UserData<T>>,
-) -> PortfolioId {
-    let u = match user {
-        None => {
-            let user = UserBuilder::<T>::default()
-                .generate_did()
-                .seed(pseudo_random_no)
-                .build(portfolio_to);
-            UserData::from(&user)
-        }
-        Some(u) => u,
-    };
-    let portfolio_no = (Portfolio::<T>::next_portfolio_number(u.did)).0;
-    let portfolio_name =
-        PortfolioName::try_from(vec![b'P'; portfolio_no as usize].as_slice()).unwrap();
-    Portfolio::<T>::create_portfolio(
-        RawOrigin::Signed(u.account.clone()).into(),
-        portfolio_name.clone(),
-    )
-    .expect("Failed to generate portfolio");
-    PortfolioId::user_portfolio(u.did, PortfolioNumber::from(portfolio_no))
-}
-
 fn populate_legs_for_instruction<T: Config + TestUtilsFn<AccountIdOf<T>>>(
     index: u32,
     legs: &mut Vec<Leg>,
@@ -323,6 +438,7 @@ fn add_trusted_issuer<T: Config>(
 pub fn setup_conditions<T: Config>(
     count: u32,
     trusted_issuer: TrustedIssuer,
+    claim_issuer: IdentityId,
     dids: Vec<IdentityId>,
 ) -> Vec<Condition> {
     (0..count)
@@ -334,7 +450,7 @@ pub fn setup_conditions<T: Config>(
                     did.clone(),
                     claim.clone(),
                     Some(scope.clone()),
-                    trusted_issuer.issuer,
+                    claim_issuer,
                     None,
                 );
             }
@@ -362,8 +478,32 @@ pub fn compliance_setup<T: Config>(
     // Add trusted issuer.
     add_trusted_issuer::<T>(origin.clone(), ticker, trusted_issuer.clone());
 
-    let conditions =
-        setup_conditions::<T>(max_complexity / 2, trusted_issuer, vec![from_did, to_did]);
+    // The claims are issued by the last of the maximum number of claim issuance delegates of the trusted issuer,
+    // so that all of its delegations are read.
+    let mut claim_issuer = trusted_issuer.issuer;
+    for i in 0..identity::MAX_CLAIM_ISSUANCE_DELEGATES {
+        claim_issuer = UserBuilder::<T>::default()
+            .generate_did()
+            .seed(i)
+            .build("ClaimIssuanceDelegate")
+            .did();
+        identity::ClaimIssuanceDelegations::insert(
+            trusted_issuer.issuer,
+            claim_issuer,
+            vec![ClaimType::Jurisdiction],
+        );
+    }
+    identity::ClaimIssuanceDelegateCount::insert(
+        trusted_issuer.issuer,
+        identity::MAX_CLAIM_ISSUANCE_DELEGATES,
+    );
+
+    let conditions = setup_conditions::<T>(
+        max_complexity / 2,
+        trusted_issuer,
+        claim_issuer,
+        vec![from_did, to_did],
+    );
     pallet_compliance_manager::Module::<T>::add_compliance_requirement(
         origin.clone().into(),
         ticker,
@@ -371,6 +511,8 @@ pub fn compliance_setup<T: Config>(
         conditions,
     )
     .expect("Failed to add the asset compliance");
+    // Transfers also record the transfer volumes of both investors.
+    <pallet_statistics::Module<T>>::set_investor_volume_tracking(AssetScope::Ticker(ticker), true);
 }
 
 fn setup_affirm_instruction<T: Config + TestUtilsFn<AccountIdOf<T>>>(
@@ -573,153 +715,8 @@ fn setup_manual_instruction<T: Config + TestUtilsFn<AccountIdOf<T>>>(
     )
 }
 
-/// Creates a fungible asset for the given `ticker` and returns a `Vec<LegV2>` containing `n_legs`.
-fn setup_fungible_legs_v2<T: Config>(
-    sender: User<T>,
-    receiver: User<T>,
-    ticker: Ticker,
-    n_legs: u32,
-) -> Vec<LegV2> {
-    make_asset(&sender, Some(ticker.as_ref()));
-    (0..n_legs)
-        .map(|_| LegV2 {
-            from: PortfolioId {
-                did: sender.did(),
-                kind: PortfolioKind::Default,
-            },
-            to: PortfolioId {
-                did: receiver.did(),
-                kind: PortfolioKind::Default,
-            },
-            asset: LegAsset::Fungible {
-                ticker: ticker.clone(),
-                amount: ONE_UNIT,
-            },
-        })
-        .collect()
-}
-
-/// Creates an nft collection for `ticker`, mints `n_nfts` for `token_sender`, and returns a `Vec<LegV2>`
-/// containing `n_legs` with a total of `n_nfts` split among the legs.
-/// For this function only calls with the minimum number of legs to contain all `n_nfts` are allowed.
-/// E.g: If n_nfts = 78, n_legs must be equal to 8 (considering that MaxNumberOfNFTsPerLeg is equal to 10).
-fn setup_nft_legs<T: Config>(
-    sender: User<T>,
-    receiver: User<T>,
-    ticker: Ticker,
-    n_legs: u32,
-    n_nfts: u32,
-) -> Vec<LegV2> {
-    create_collection_issue_nfts::<T>(
-        sender.origin().into(),
-        ticker,
-        Some(NonFungibleType::Derivative),
-        0,
-        n_nfts,
-        PortfolioKind::Default,
-    );
-
-    let max_nfts_per_leg = T::MaxNumberOfNFTsPerLeg::get();
-    let last_leg_len = n_nfts % max_nfts_per_leg;
-    let full_legs = n_nfts / max_nfts_per_leg;
-
-    // Creates the NFTs for each leg. All legs except the last one will have T::MaxNumberOfNFTsPerLeg NFTs each.
-    let mut nfts: Vec<NFTs> = (0..full_legs)
-        .map(|leg_index| {
-            NFTs::new(
-                ticker,
-                (0..max_nfts_per_leg)
-                    .map(|nft_index| NFTId((leg_index * max_nfts_per_leg + nft_index + 1) as u64))
-                    .collect(),
-            )
-            .unwrap()
-        })
-        .collect();
-    // The last leg may have less than T::MaxNumberOfNFTsPerLeg NFTs
-    if last_leg_len > 0 {
-        nfts.push(NFTs::new_unverified(
-            ticker,
-            (0..last_leg_len)
-                .map(|nft_index| {
-                    NFTId((max_nfts_per_leg * (nfts.len() as u32) + nft_index + 1) as u64)
-                })
-                .collect(),
-        ));
-    }
-    // For this function only calls with the minimum number of legs to contain all `n_nfts` are allowed
-    assert_eq!(nfts.len() as u32, n_legs);
-    // Creates each leg
-    (0..n_legs)
-        .map(|index| LegV2 {
-            from: PortfolioId {
-                did: sender.did(),
-                kind: PortfolioKind::Default,
-            },
-            to: PortfolioId {
-                did: receiver.did(),
-                kind: PortfolioKind::Default,
-            },
-
-            asset: LegAsset::NonFungible(nfts[index as usize].clone()),
-        })
-        .collect()
-}
-
-/// Creates the basic environment for executing the benchmarks for the v2 extrinsics.
-/// This includes: creating one fungible asset, one NFT collection with `n_nfts`, and
-/// the instruction legs filled with `n_nfts` and `fungible_transfers`.
-/// All other parameters are also included in the `BaseV2Parameters` struct.
-fn setup_v2_extrinsics_parameters<T>(fungible_transfers: u32, n_nfts: u32) -> BaseV2Parameters<T>
-where
-    T: TestUtilsFn<AccountIdOf<T>> + Config,
-{
-    let max_nfts = T::MaxNumberOfNFTsPerLeg::get();
-    let alice = UserBuilder::<T>::default().generate_did().build("Alice");
-    let bob = UserBuilder::<T>::default().generate_did().build("Bob");
-    let fungible_ticker: Ticker = Ticker::from_slice_truncated(b"TICKER".as_ref());
-    let nft_ticker: Ticker = Ticker::from_slice_truncated(b"TICKER0".as_ref());
-    let venue_id = create_venue_::<T>(alice.did(), vec![]);
-    let sender_portfolios = vec![PortfolioId {
-        did: alice.did(),
-        kind: PortfolioKind::Default,
-    }];
-
-    let mut fungible_legs = setup_fungible_legs_v2(
-        alice.clone(),
-        bob.clone(),
-        fungible_ticker,
-        fungible_transfers,
-    );
-    let n_nft_legs = if n_nfts % max_nfts == 0 {
-        n_nfts / max_nfts
-    } else {
-        n_nfts / max_nfts + 1
-    };
-    let mut non_fungible_legs =
-        setup_nft_legs(alice.clone(), bob.clone(), nft_ticker, n_nft_legs, n_nfts);
-    non_fungible_legs.append(&mut fungible_legs);
-    let legs_v2 = non_fungible_legs;
-
-    let settlement_type = SettlementType::SettleOnBlock(100u32.into());
-    let date = Some(99999999u32.into());
-    let memo = Some(InstructionMemo::default());
-
-    BaseV2Parameters::<T> {
-        sender: alice,
-        receiver: bob,
-        fungible_ticker,
-        nft_ticker,
-        venue_id,
-        legs_v2,
-        sender_portfolios,
-        settlement_type,
-        date,
-        memo,
-    }
-}
-
-benchmarks! {
-    where_clause { where T: TestUtilsFn<AccountIdOf<T>>, T: pallet_scheduler::Config }
+benchmarks! {
+    where_clause { where T: TestUtilsFn<AccountIdOf<T>>, T: pallet_scheduler::Config }
 
     create_venue {
         // Variations for the venue_details length.
@@ -848,6 +845,25 @@ benchmarks! {
     }
 
 
+    freeze_settlement {
+        let user = creator::<T>();
+        let ticker = create_asset_::<T>(&user);
+    }: _(user.origin, ticker)
+    verify {
+        assert!(Module::<T>::settlement_frozen(ticker), "Fail: freeze_settlement failed");
+    }
+
+
+    unfreeze_settlement {
+        let user = creator::<T>();
+        let ticker = create_asset_::<T>(&user);
+        Module::<T>::freeze_settlement(user.origin.clone().into(), ticker).unwrap();
+    }: _(user.origin, ticker)
+    verify {
+        assert!(!Module::<T>::settlement_frozen(ticker), "Fail: unfreeze_settlement failed");
+    }
+
+
     allow_venues {
         // Count of venue is variant for this dispatchable.
         let v in 0 .. MAX_VENUE_ALLOWED;
@@ -914,7 +930,7 @@ benchmarks! {
         Module::<T>::add_and_affirm_instruction((origin.clone()).into(), venue_id, SettlementType::SettleOnAffirmation, None, None, legs, portfolios.clone()).expect("Unable to add and affirm the instruction");
         let instruction_id = InstructionId(1);
         let portfolio_id = (l - 1) as usize;
-    }: _(origin, instruction_id, portfolios[portfolio_id], l)
+    }: _(origin, instruction_id, portfolios[portfolio_id], l, Some(RejectReason::Other), Some(Memo::default()))
     verify {
         assert_eq!(Module::<T>::instruction_status(instruction_id), InstructionStatus::Rejected(frame_system::Pallet::<T>::block_number()));
     }
@@ -993,9 +1009,9 @@ benchmarks! {
         // Emulate the add instruction and get all the necessary arguments.
         let (legs, venue_id, origin, did , _, _, _ ) = emulate_add_instruction::<T>(l, false, true).unwrap();
 
-    }: add_instruction_with_memo(origin, venue_id, settlement_type, Some(99999999u32.into()), Some(99999999u32.into()), legs, Some(InstructionMemo::default()))
+    }: add_instruction_with_memo(origin, venue_id, settlement_type, Some(99999999u32.into()), Some(99999999u32.into()), legs, Some(Memo::default()))
     verify {
-        assert_eq!(Module::<T>::memo(instruction_id).unwrap(), InstructionMemo::default());
+        assert_eq!(Module::<T>::memo(instruction_id).unwrap(), Memo::default());
     }
 
     add_and_affirm_instruction_with_memo_and_settle_on_block_type {
@@ -1007,10 +1023,10 @@ benchmarks! {
         // Emulate the add instruction and get all the necessary arguments.
         let (legs, venue_id, origin, did , portfolios, _, _) = emulate_add_instruction::<T>(l, true, true).unwrap();
         let s_portfolios = portfolios.clone();
-    }: add_and_affirm_instruction_with_memo(origin, venue_id, settlement_type, Some(99999999u32.into()), Some(99999999u32.into()), legs, s_portfolios, Some(InstructionMemo::default()))
+    }: add_and_affirm_instruction_with_memo(origin, venue_id, settlement_type, Some(99999999u32.into()), Some(99999999u32.into()), legs, s_portfolios, Some(Memo::default()))
     verify {
         verify_add_and_affirm_instruction::<T>(venue_id, settlement_type, portfolios).unwrap();
-        assert_eq!(Module::<T>::memo(instruction_id).unwrap(), InstructionMemo::default());
+        assert_eq!(Module::<T>::memo(instruction_id).unwrap(), Memo::default());
     }
 
     execute_manual_instruction {
@@ -1048,6 +1064,7 @@ benchmarks! {
         let traded_amount = before_transfer_balance - after_transfer_balance;
         let expected_transfer_amount = first_leg.amount;
         assert_eq!(traded_amount, expected_transfer_amount,"Settlement: Failed to execute the instruction");
+        assert!(Module::<T>::instruction_audit_digest(instruction_id).is_some());
     }
 
     add_instruction_with_memo_v2 {
@@ -1111,7 +1128,631 @@ benchmarks! {
             parameters.sender_portfolios.clone(),
             parameters.memo
         ).expect("failed to add instruction");
-    }: _(parameters.sender.origin, InstructionId(1), parameters.sender_portfolios[0], f, n)
+    }: _(parameters.sender.origin, InstructionId(1), parameters.sender_portfolios[0], f, n, Some(RejectReason::Other), Some(Memo::default()))
+
+    edit_instruction_legs {
+        let l in 1..T::MaxNumberOfFungibleAssets::get() as u32;
+
+        let parameters = setup_v2_extrinsics_parameters::<T>(l, 0);
+        Module::<T>::add_instruction_with_memo_v2(
+            parameters.sender.clone().origin.into(),
+            parameters.venue_id,
+            parameters.settlement_type,
+            parameters.date,
+            parameters.date,
+            parameters.legs_v2.clone(),
+            parameters.memo
+        ).expect("failed to add instruction");
+        let mediators: BTreeSet<IdentityId> = (0..MAX_INSTRUCTION_MEDIATORS)
+            .map(|i| UserBuilder::<T>::default().generate_did().seed(i).build("Mediator").did())
+            .collect();
+        Module::<T>::add_mediators(parameters.sender.did(), InstructionId(1), mediators)
+            .expect("failed to add mediators");
+        let removed_legs: Vec<LegId> = (0..l as u64).map(LegId).collect();
+    }: _(parameters.sender.origin, InstructionId(1), removed_legs, parameters.legs_v2, l)
+
+    amend_instruction_leg {
+        let l in 1..T::MaxNumberOfFungibleAssets::get() as u32;
+
+        let parameters = setup_v2_extrinsics_parameters::<T>(l, 0);
+        Module::<T>::add_instruction_with_memo_v2(
+            parameters.sender.clone().origin.into(),
+            parameters.venue_id,
+            parameters.settlement_type,
+            parameters.date,
+            parameters.date,
+            parameters.legs_v2.clone(),
+            parameters.memo
+        ).expect("failed to add instruction");
+        let mediators: BTreeSet<IdentityId> = (0..MAX_INSTRUCTION_MEDIATORS)
+            .map(|i| UserBuilder::<T>::default().generate_did().seed(i).build("Mediator").did())
+            .collect();
+        Module::<T>::add_mediators(parameters.sender.did(), InstructionId(1), mediators)
+            .expect("failed to add mediators");
+        let new_leg = parameters.legs_v2[0].clone();
+    }: _(parameters.sender.origin, InstructionId(1), LegId(0), new_leg, l)
+    verify {
+        assert_eq!(Module::<T>::instruction_revision(InstructionId(1)), 1);
+    }
+
+    set_leg_consideration {
+        let parameters = setup_v2_extrinsics_parameters::<T>(1, 0);
+        Module::<T>::add_instruction_with_memo_v2(
+            parameters.sender.clone().origin.into(),
+            parameters.venue_id,
+            parameters.settlement_type,
+            parameters.date,
+            parameters.date,
+            parameters.legs_v2,
+            parameters.memo
+        ).expect("failed to add instruction");
+        let consideration = LegConsideration {
+            currency: Ticker::from_slice_truncated(b"USD"),
+            amount: ONE_UNIT,
+        };
+    }: _(parameters.sender.origin, InstructionId(1), LegId(0), Some(consideration))
+    verify {
+        assert_eq!(Module::<T>::instruction_leg_consideration(InstructionId(1), LegId(0)), Some(consideration));
+    }
+
+    set_market_hours {
+        let market_hours = MarketHours {
+            period: 14_400u32.into(),
+            open: 3_600u32.into(),
+            close: 9_600u32.into(),
+            priority: 50,
+            defer_off_hours: true,
+        };
+    }: _(RawOrigin::Root, Some(market_hours))
+    verify {
+        assert_eq!(Module::<T>::market_hours(), Some(market_hours));
+    }
+
+    clear_execution_rounds {
+        // Number of venues with instructions scheduled for the block.
+        let v in 0..MAX_SCHEDULED_PER_BLOCK;
+
+        let now = frame_system::Pallet::<T>::block_number();
+        for venue_id in 0..v {
+            ExecutionRounds::<T>::insert(now, VenueId(venue_id as u64), 1);
+        }
+    }: {
+        Module::<T>::clear_execution_rounds(now);
+    }
+    verify {
+        assert_eq!(ExecutionRounds::<T>::iter_prefix(now).count(), 0);
+    }
+
+    set_memo_access_list {
+        let d in 1..MAX_MEMO_READERS;
+
+        let parameters = setup_v2_extrinsics_parameters::<T>(1, 0);
+        Module::<T>::add_instruction_with_memo_v2(
+            parameters.sender.clone().origin.into(),
+            parameters.venue_id,
+            parameters.settlement_type,
+            parameters.date,
+            parameters.date,
+            parameters.legs_v2,
+            parameters.memo
+        ).expect("failed to add instruction");
+        let dids: Vec<IdentityId> = (0..d as u128).map(IdentityId::from).collect();
+    }: _(parameters.sender.origin, InstructionId(1), dids)
+
+    wrap_memo_keys {
+        let k in 1..MAX_MEMO_READERS;
+
+        let parameters = setup_v2_extrinsics_parameters::<T>(1, 0);
+        Module::<T>::add_instruction_with_memo_v2(
+            parameters.sender.clone().origin.into(),
+            parameters.venue_id,
+            parameters.settlement_type,
+            parameters.date,
+            parameters.date,
+            parameters.legs_v2,
+            parameters.memo
+        ).expect("failed to add instruction");
+        let dids: Vec<IdentityId> = (0..k as u128).map(IdentityId::from).collect();
+        Module::<T>::set_memo_access_list(
+            parameters.sender.clone().origin.into(),
+            InstructionId(1),
+            dids.clone()
+        ).expect("failed to set memo access list");
+        let wrapped_keys: Vec<(IdentityId, WrappedMemoKey)> = dids
+            .into_iter()
+            .map(|did| (did, WrappedMemoKey(vec![0; ENSURED_MAX_LEN as usize])))
+            .collect();
+    }: _(parameters.sender.origin, InstructionId(1), wrapped_keys)
+
+    set_venue_default_expiry {
+        let alice = UserBuilder::<T>::default().generate_did().build("Alice");
+        let venue_id = create_venue_::<T>(alice.did(), vec![]);
+    }: _(alice.origin, venue_id, Some(100u32.into()))
+    verify {
+        assert_eq!(Module::<T>::venue_default_expiry(venue_id), Some(100u32.into()));
+    }
+
+    expire_instruction {
+        let f in 1..T::MaxNumberOfFungibleAssets::get() as u32;
+        let n in 1..T::MaxNumberOfNFTs::get() as u32;
+
+        let parameters = setup_v2_extrinsics_parameters::<T>(f, n);
+        Module::<T>::add_and_affirm_instruction_with_memo_v2(
+            parameters.sender.clone().origin.into(),
+            parameters.venue_id,
+            parameters.settlement_type,
+            parameters.date,
+            parameters.date,
+            parameters.legs_v2.clone(),
+            parameters.sender_portfolios.clone(),
+            parameters.memo
+        ).expect("failed to add instruction");
+    }: _(RawOrigin::Root, InstructionId(1), f, n)
+    verify {
+        assert_eq!(Module::<T>::instruction_affirms_pending(InstructionId(1)), 0);
+    }
+
+    set_venue_cancellation_fee {
+        let alice = UserBuilder::<T>::default().generate_did().build("Alice");
+        let venue_id = create_venue_::<T>(alice.did(), vec![]);
+    }: _(alice.origin, venue_id, Some(ONE_UNIT))
+    verify {
+        assert_eq!(Module::<T>::venue_cancellation_fee(venue_id), Some(ONE_UNIT));
+    }
+
+    pay_cancellation_fee {
+        let p in 1..T::MaxNumberOfFungibleAssets::get() as u32;
+
+        let alice = UserBuilder::<T>::default().generate_did().build("Alice");
+        let sender_portfolio = PortfolioId::default_portfolio(alice.did());
+        let legs: Vec<(LegId, LegV2)> = (0..p)
+            .map(|i| {
+                let receiver = UserBuilder::<T>::default().generate_did().seed(i).build("Receiver");
+                let leg = LegV2 {
+                    from: sender_portfolio,
+                    to: PortfolioId::default_portfolio(receiver.did()),
+                    asset: LegAsset::Fungible { ticker: Ticker::generate_into(i as u64), amount: ONE_UNIT },
+                };
+                (LegId(i as u64), leg)
+            })
+            .collect();
+        InstructionCancellationFees::insert(InstructionId(1), ONE_UNIT);
+    }: {
+        Module::<T>::pay_cancellation_fee(InstructionId(1), &alice.account(), alice.did(), &legs)?;
+    }
+
+    set_venue_fee {
+        let alice = UserBuilder::<T>::default().generate_did().build("Alice");
+        let venue_id = create_venue_::<T>(alice.did(), vec![]);
+    }: _(alice.origin, venue_id, Some(VenueFee::PerLeg(ONE_UNIT)))
+    verify {
+        assert_eq!(Module::<T>::venue_fee(venue_id), Some(VenueFee::PerLeg(ONE_UNIT)));
+    }
+
+    transfer_venue_fees {
+        let p in 1..10;
+
+        let escrow = Module::<T>::venue_fees_escrow();
+        let _ = <T as IdentityConfig>::Balances::deposit_creating(&escrow, ONE_UNIT * p as Balance);
+        let portfolios: BTreeSet<PortfolioId> = (0..p)
+            .map(|i| {
+                let payer = UserBuilder::<T>::default().generate_did().seed(i).build("Payer");
+                let portfolio = PortfolioId::default_portfolio(payer.did());
+                VenueFeesCharged::<T>::insert(InstructionId(1), portfolio, (payer.account(), ONE_UNIT));
+                portfolio
+            })
+            .collect();
+    }: {
+        Module::<T>::refund_venue_fees(InstructionId(1), &portfolios);
+    }
+
+    set_receipt_signatories {
+        let s in 1 .. MAX_SIGNERS_ALLOWED;
+
+        let User {account, origin, did, .. } = creator::<T>();
+        let mut signers = vec![account];
+        for signer in 1 .. s {
+            signers.push(UserBuilder::<T>::default().generate_did().seed(signer).build("signers").account());
+        }
+        let venue_id = create_venue_::<T>(did.unwrap(), signers);
+    }: _(origin, venue_id, s)
+    verify {
+        assert_eq!(Module::<T>::receipt_signatories(venue_id), s);
+    }
+
+    set_receipt_metadata_schema {
+        let User {origin, did, .. } = creator::<T>();
+        let venue_id = create_venue_::<T>(did.unwrap(), vec![]);
+        let schema = ReceiptMetadataSchema {
+            max_length: T::MaxLen::get(),
+            required_prefix: ReceiptMetadata::from(vec![b'a'; T::MaxLen::get() as usize]),
+        };
+    }: _(origin, venue_id, Some(schema.clone()))
+    verify {
+        assert_eq!(Module::<T>::receipt_metadata_schema(venue_id), Some(schema));
+    }
+
+    set_venue_netting {
+        let alice = UserBuilder::<T>::default().generate_did().build("Alice");
+        let venue_id = create_venue_::<T>(alice.did(), vec![]);
+    }: _(alice.origin, venue_id, true)
+    verify {
+        assert!(Module::<T>::venue_netting(venue_id));
+    }
+
+    set_venue_quota {
+        let alice = UserBuilder::<T>::default().generate_did().build("Alice");
+        let venue_id = create_venue_::<T>(alice.did(), vec![]);
+        let quota = VenueQuota {
+            max_open_instructions: Some(100),
+            max_instructions_per_block: Some(10),
+        };
+    }: _(alice.origin, venue_id, Some(quota))
+    verify {
+        assert_eq!(Module::<T>::venue_quota(venue_id), Some(quota));
+    }
+
+    pause_venue {
+        let alice = UserBuilder::<T>::default().generate_did().build("Alice");
+        let venue_id = create_venue_::<T>(alice.did(), vec![]);
+    }: _(alice.origin, venue_id)
+    verify {
+        assert!(Module::<T>::venue_paused(venue_id));
+    }
+
+    resume_venue {
+        let alice = UserBuilder::<T>::default().generate_did().build("Alice");
+        let venue_id = create_venue_::<T>(alice.did(), vec![]);
+        Module::<T>::pause_venue(alice.origin.clone().into(), venue_id).unwrap();
+    }: _(alice.origin, venue_id)
+    verify {
+        assert!(!Module::<T>::venue_paused(venue_id));
+    }
+
+    close_venue {
+        let s in 0 .. MAX_SIGNERS_ALLOWED;
+        let i in 0 .. 1_000;
+
+        let alice = UserBuilder::<T>::default().generate_did().build("Alice");
+        let signers = (0..s).map(|i| account("signer", i, 0)).collect::<Vec<T::AccountId>>();
+        let venue_id = create_venue_::<T>(alice.did(), signers);
+        Module::<T>::pause_venue(alice.origin.clone().into(), venue_id).unwrap();
+        // The venue has the maximum number of pending instruction requests.
+        for i in 0..MAX_VENUE_INSTRUCTION_REQUESTS as u64 {
+            let request = InstructionRequest {
+                requester: alice.did(),
+                venue_id,
+                settlement_type: SettlementType::SettleOnAffirmation,
+                trade_date: None,
+                value_date: None,
+                legs: Vec::new(),
+                memo: None,
+                expires_at: 0u32.into(),
+            };
+            InstructionRequests::<T>::insert(InstructionRequestId(i), request);
+            VenueInstructionRequests::insert(venue_id, InstructionRequestId(i), ());
+        }
+        VenueInstructionRequestsCount::insert(venue_id, MAX_VENUE_INSTRUCTION_REQUESTS);
+        for id in 0..i as u64 {
+            VenueInstructions::insert(venue_id, InstructionId(id), ());
+        }
+    }: _(alice.origin, venue_id, s, i)
+    verify {
+        assert!(Module::<T>::venue_info(venue_id).is_none());
+        assert_eq!(VenueInstructions::iter_prefix(venue_id).count(), 0);
+        assert!(VenueInstructionRequests::iter_prefix(venue_id).next().is_none());
+    }
+
+    create_recurring_instruction {
+        let l in 1..T::MaxNumberOfFungibleAssets::get() as u32;
+
+        let parameters = setup_v2_extrinsics_parameters::<T>(l, 0);
+        let id = Module::<T>::recurring_instruction_counter();
+    }: _(
+        parameters.sender.origin,
+        parameters.venue_id,
+        parameters.legs_v2,
+        parameters.memo,
+        10u32.into(),
+        RecurrenceStop::AfterOccurrences(12)
+    )
+    verify {
+        assert!(RecurringInstructions::<T>::contains_key(id));
+    }
+
+    cancel_recurring_instruction {
+        let parameters = setup_v2_extrinsics_parameters::<T>(1, 0);
+        let id = Module::<T>::recurring_instruction_counter();
+        Module::<T>::create_recurring_instruction(
+            parameters.sender.clone().origin.into(),
+            parameters.venue_id,
+            parameters.legs_v2,
+            parameters.memo,
+            10u32.into(),
+            RecurrenceStop::AfterOccurrences(12)
+        ).unwrap();
+    }: _(parameters.sender.origin, id)
+    verify {
+        assert!(!RecurringInstructions::<T>::contains_key(id));
+    }
+
+    execute_recurring_instruction {
+        let l in 1..T::MaxNumberOfFungibleAssets::get() as u32;
+
+        let parameters = setup_v2_extrinsics_parameters::<T>(l, 0);
+        let id = Module::<T>::recurring_instruction_counter();
+        Module::<T>::create_recurring_instruction(
+            parameters.sender.clone().origin.into(),
+            parameters.venue_id,
+            parameters.legs_v2,
+            parameters.memo,
+            10u32.into(),
+            RecurrenceStop::AfterOccurrences(12)
+        ).unwrap();
+        let instruction_id = Module::<T>::instruction_counter();
+    }: {
+        Module::<T>::base_execute_recurring_instruction(id);
+    }
+    verify {
+        assert_eq!(Module::<T>::instruction_status(instruction_id), InstructionStatus::Pending);
+        assert_eq!(Module::<T>::recurring_instruction(id).unwrap().occurrences, 1);
+    }
+
+    add_mediators {
+        let m in 1..MAX_INSTRUCTION_MEDIATORS;
+
+        let parameters = setup_v2_extrinsics_parameters::<T>(1, 0);
+        Module::<T>::add_instruction_with_memo_v2(
+            parameters.sender.clone().origin.into(),
+            parameters.venue_id,
+            parameters.settlement_type,
+            parameters.date,
+            parameters.date,
+            parameters.legs_v2,
+            parameters.memo
+        ).expect("failed to add instruction");
+        let mediators: BTreeSet<IdentityId> = (0..m)
+            .map(|i| UserBuilder::<T>::default().generate_did().seed(i).build("Mediator").did())
+            .collect();
+    }: {
+        Module::<T>::add_mediators(parameters.sender.did(), InstructionId(1), mediators)?;
+    }
+    verify {
+        assert_eq!(Module::<T>::instruction_affirms_pending(InstructionId(1)), 2 + m as u64);
+    }
+
+    add_instruction_tags {
+        let t in 1..MAX_INSTRUCTION_TAGS;
+
+        let parameters = setup_v2_extrinsics_parameters::<T>(1, 0);
+        Module::<T>::add_instruction_with_memo_v2(
+            parameters.sender.clone().origin.into(),
+            parameters.venue_id,
+            parameters.settlement_type,
+            parameters.date,
+            parameters.date,
+            parameters.legs_v2,
+            parameters.memo
+        ).expect("failed to add instruction");
+        let tags: BTreeSet<InstructionTag> = (0..t)
+            .map(|i| InstructionTag([i as u8; 16]))
+            .collect();
+    }: {
+        Module::<T>::add_instruction_tags(parameters.sender.did(), InstructionId(1), tags);
+    }
+    verify {
+        assert_eq!(Module::<T>::instruction_tags(InstructionId(1)).len(), t as usize);
+    }
+
+    migrate_instruction_legs {
+        let i in 1..100;
+        let l in 0..1000;
+
+        let caller = UserBuilder::<T>::default().build("Caller");
+        let ids: Vec<InstructionId> = (0..i as u64).map(InstructionId).collect();
+        // The legs are spread over the instructions.
+        for leg_id in 0..l as u64 {
+            InstructionLegs::insert(InstructionId(leg_id % i as u64), LegId(leg_id), Leg::default());
+        }
+    }: _(caller.origin, ids, l)
+    verify {
+        assert!(InstructionLegs::iter_prefix(InstructionId(0)).next().is_none());
+        assert_eq!(InstructionLegsV2::iter_prefix(InstructionId(0)).count(), ((l + i - 1) / i) as usize);
+    }
+
+    reveal_portfolio_alias {
+        let l in 1..T::MaxNumberOfFungibleAssets::get() as u32;
+
+        let mut parameters = setup_v2_extrinsics_parameters::<T>(l, 0);
+        let receiver_portfolio = PortfolioId::default_portfolio(parameters.receiver.did());
+        let alias = PortfolioAlias::new(&receiver_portfolio, &[7; 32]);
+        for leg in parameters.legs_v2.iter_mut() {
+            leg.to = alias.placeholder();
+        }
+        Module::<T>::add_and_affirm_instruction_with_memo_v2(
+            parameters.sender.clone().origin.into(),
+            parameters.venue_id,
+            SettlementType::SettleOnAffirmation,
+            None,
+            None,
+            parameters.legs_v2,
+            parameters.sender_portfolios,
+            parameters.memo
+        ).expect("failed to add instruction");
+    }: _(parameters.receiver.origin, InstructionId(1), alias, receiver_portfolio, [7; 32], l, 0)
+    verify {
+        assert!(matches!(Module::<T>::instruction_status(InstructionId(1)), InstructionStatus::Success(_)));
+    }
+
+    prune_instruction_status {
+        let settled_at: T::BlockNumber = 1u32.into();
+        InstructionStatuses::<T>::insert(InstructionId(1), InstructionStatus::Success(settled_at));
+        SettledInstructions::<T>::insert(settled_at, InstructionId(1), ());
+        InstructionAuditDigests::insert(InstructionId(1), [0; 32]);
+        StatusPruneCursor::<T>::put(settled_at);
+        let now = settled_at + T::InstructionStatusRetention::get().unwrap_or_default() + 1u32.into();
+        let step_weight = <T as Config>::WeightInfo::prune_instruction_status();
+    }: {
+        Module::<T>::prune_instruction_statuses(now, step_weight);
+    }
+    verify {
+        assert_eq!(Module::<T>::instruction_status(InstructionId(1)), InstructionStatus::Unknown);
+        assert!(Module::<T>::instruction_audit_digest(InstructionId(1)).is_none());
+    }
+
+    prune_rejected_instruction {
+        let l in 1..T::MaxNumberOfFungibleAssets::get() as u32;
+
+        let (legs, venue_id, origin, _, portfolios, _, _) = emulate_add_instruction::<T>(l, true, true).unwrap();
+        Module::<T>::add_and_affirm_instruction(origin.into(), venue_id, SettlementType::SettleOnAffirmation, None, None, legs, portfolios).expect("Unable to add and affirm the instruction");
+        let rejected_at: T::BlockNumber = 1u32.into();
+        InstructionStatuses::<T>::insert(InstructionId(1), InstructionStatus::Rejected(rejected_at));
+        RejectedInstructions::<T>::insert(rejected_at, InstructionId(1), ());
+        RejectedPruneCursor::<T>::put(rejected_at);
+        let now = rejected_at + T::RejectedInstructionRetention::get() + 1u32.into();
+        let max_legs = T::MaxNumberOfFungibleAssets::get() + T::MaxNumberOfNFTs::get();
+        let step_weight = <T as Config>::WeightInfo::prune_rejected_instruction(max_legs);
+    }: {
+        Module::<T>::prune_rejected_instructions(now, step_weight);
+    }
+    verify {
+        assert!(InstructionLegsV2::iter_prefix(InstructionId(1)).next().is_none());
+    }
+
+    request_instruction {
+        let l in 1..T::MaxNumberOfFungibleAssets::get() as u32;
+
+        let parameters = setup_v2_extrinsics_parameters::<T>(l, 0);
+        let id = Module::<T>::instruction_request_counter();
+    }: _(
+        parameters.receiver.origin,
+        parameters.venue_id,
+        parameters.settlement_type,
+        parameters.date,
+        parameters.date,
+        parameters.legs_v2,
+        parameters.memo
+    )
+    verify {
+        assert!(InstructionRequests::<T>::contains_key(id));
+    }
+
+    approve_requested_instruction {
+        let f in 1..T::MaxNumberOfFungibleAssets::get() as u32;
+        let n in 1..T::MaxNumberOfNFTs::get() as u32;
+
+        let parameters = setup_v2_extrinsics_parameters::<T>(f, n);
+        let id = Module::<T>::instruction_request_counter();
+        Module::<T>::request_instruction(
+            parameters.receiver.origin.clone().into(),
+            parameters.venue_id,
+            parameters.settlement_type,
+            parameters.date,
+            parameters.date,
+            parameters.legs_v2,
+            parameters.memo,
+        ).expect("failed to request instruction");
+    }: _(parameters.sender.origin, id, f, n)
+    verify {
+        assert!(!InstructionRequests::<T>::contains_key(id));
+        assert_eq!(Module::<T>::instruction_status(InstructionId(1)), InstructionStatus::Pending);
+    }
+
+    remove_instruction_request {
+        let parameters = setup_v2_extrinsics_parameters::<T>(1, 0);
+        let id = Module::<T>::instruction_request_counter();
+        Module::<T>::request_instruction(
+            parameters.receiver.origin.clone().into(),
+            parameters.venue_id,
+            parameters.settlement_type,
+            parameters.date,
+            parameters.date,
+            parameters.legs_v2,
+            parameters.memo,
+        ).expect("failed to request instruction");
+    }: _(parameters.receiver.origin, id)
+    verify {
+        assert!(!InstructionRequests::<T>::contains_key(id));
+    }
+
+    set_portfolio_settlement_agent {
+        let custodian = user::<T>("custodian", 0);
+        let agent = user::<T>("agent", 0);
+        let portfolio = PortfolioId::default_portfolio(custodian.did());
+    }: _(custodian.origin, portfolio, Some(agent.did()))
+    verify {
+        assert_eq!(Module::<T>::portfolio_settlement_agent(portfolio), Some(agent.did()));
+    }
+
+    instruction_memo {
+        let m in 0..MAX_MEMO_LEN as u32;
+
+        let memo = Some(Memo(vec![7u8; m as usize]));
+    }: {
+        Module::<T>::ensure_valid_memo(&memo).unwrap();
+        InstructionMemos::insert(InstructionId(1), memo.clone().unwrap());
+    }
+    verify {
+        assert_eq!(Module::<T>::memo(InstructionId(1)), memo);
+    }
+
+    affirm_instruction_as_mediator {
+        let f in 1..T::MaxNumberOfFungibleAssets::get() as u32;
+        let n in 1..T::MaxNumberOfNFTs::get() as u32;
+
+        let parameters = setup_v2_extrinsics_parameters::<T>(f, n);
+        let mediator = UserBuilder::<T>::default().generate_did().build("Mediator");
+        Module::<T>::add_instruction_with_mediators(
+            parameters.sender.clone().origin.into(),
+            parameters.venue_id,
+            SettlementType::SettleOnAffirmation,
+            parameters.date,
+            parameters.date,
+            parameters.legs_v2,
+            parameters.memo,
+            [mediator.did()].into(),
+        ).expect("failed to add instruction");
+    }: _(mediator.origin, InstructionId(1), f, n)
+    verify {
+        assert_eq!(Module::<T>::instruction_mediators(InstructionId(1), mediator.did()), AffirmationStatus::Affirmed);
+    }
+
+    withdraw_affirmation_as_mediator {
+        let parameters = setup_v2_extrinsics_parameters::<T>(1, 0);
+        let mediator = UserBuilder::<T>::default().generate_did().build("Mediator");
+        Module::<T>::add_instruction_with_mediators(
+            parameters.sender.clone().origin.into(),
+            parameters.venue_id,
+            SettlementType::SettleOnAffirmation,
+            parameters.date,
+            parameters.date,
+            parameters.legs_v2,
+            parameters.memo,
+            [mediator.did()].into(),
+        ).expect("failed to add instruction");
+        Module::<T>::affirm_instruction_as_mediator(mediator.origin().into(), InstructionId(1), 1, 0)
+            .expect("failed to affirm instruction");
+    }: _(mediator.origin, InstructionId(1))
+    verify {
+        assert_eq!(Module::<T>::instruction_mediators(InstructionId(1), mediator.did()), AffirmationStatus::Pending);
+    }
+
+    set_instruction_operator {
+        let parameters = setup_v2_extrinsics_parameters::<T>(1, 0);
+        let operator = UserBuilder::<T>::default().generate_did().build("Operator");
+        Module::<T>::add_instruction_with_memo_v2(
+            parameters.sender.clone().origin.into(),
+            parameters.venue_id,
+            parameters.settlement_type,
+            parameters.date,
+            parameters.date,
+            parameters.legs_v2,
+            parameters.memo
+        ).expect("failed to add instruction");
+    }: _(parameters.sender.origin, InstructionId(1), Some(operator.did()))
+    verify {
+        assert_eq!(Module::<T>::instruction_operator(InstructionId(1)), Some(operator.did()));
+    }
 
     execute_scheduled_instruction {
         let f in 1..T::MaxNumberOfFungibleAssets::get() as u32;
@@ -1208,12 +1849,9 @@ benchmarks! {
         .expect("failed to affirm instruction");
 
     }: execute_scheduled_instruction_v2(RawOrigin::Root, InstructionId(1), f, n)
+    verify {
+        assert!(Module::<T>::instruction_audit_record(InstructionId(1)).is_some());
+        assert!(Module::<T>::instruction_audit_digest(InstructionId(1)).is_some());
+    }
 
 }
-
-pub fn next_block<T: Config + pallet_scheduler::Config>() {
-    use frame_support::traits::OnInitialize;
-    let block_number = frame_system::Pallet::<T>::block_number() + 1u32.into();
-    frame_system::Pallet::<T>::set_block_number(block_number);
-    pallet_scheduler::Pallet::<T>::on_initialize(block_number);
-}
@@ -24,7 +24,8 @@
 //! can allow/block certain venues from settling trades that involve their tokens.
 //! An atomic settlement is called an Instruction. An instruction can contain multiple legs. Legs are essentially simple one to one transfers.
 //! When an instruction is settled, either all legs are executed successfully or none are. In other words, if one of the leg fails due to
-//! compliance failure, all other legs will also fail.
+//! compliance failure, all other legs will also fail. Instructions using `SettleOnAffirmationPartial` are the exception:
+//! each of their legs is executed on its own and the failing legs are skipped and reported.
 //!
 //! An instruction must be authorized by all the counter parties involved for it to be executed.
 //! An instruction can be set to automatically execute in the next block when all authorizations are received or at a particular block number.
@@ -32,22 +33,56 @@
 //! Offchain settlements are represented via receipts. If a leg has a receipt attached to it, it will not be executed onchain.
 //! All other legs will be executed onchain during settlement.
 //!
+//! The instructions scheduled for execution in the same block are executed in rounds: each round executes
+//! at most one instruction of every venue, in the order the instructions were scheduled. The first instructions
+//! of a venue thus gain no priority over the instructions of other venues.
+//!
 //! ## Dispatchable Functions
 //!
 //! - `create_venue` - Registers a new venue.
 //! - `add_instruction` - Adds a new instruction.
 //! - `affirm_instruction` - Provides affirmation to an existing instruction.
+//! - `affirm_instructions` - Provides affirmation to several existing instructions at once.
 //! - `withdraw_affirmation` - Withdraw an existing affirmation to given instruction.
-//! - `reject_instruction` - Rejects an existing instruction.
+//! - `reject_instruction` - Rejects an existing instruction, optionally giving the reason.
 //! - `set_venue_filtering` - Enables or disabled venue filtering for a token.
 //! - `allow_venues` - Allows additional venues to create instructions involving an asset.
 //! - `disallow_venues` - Revokes permission given to venues for creating instructions involving a particular asset.
+//! - `edit_instruction_legs` - Adds and removes legs of a pending instruction that has not been affirmed yet.
+//! - `amend_instruction_leg` - Replaces a leg of a pending instruction that has not been affirmed yet.
+//! - `set_leg_consideration` - Records the agreed price of a leg of a pending instruction.
+//! - `set_market_hours` - Sets the block ranges during which settlement executions are prioritized.
+//! - `set_memo_access_list` - Sets the identities allowed to receive the key of an encrypted memo.
+//! - `wrap_memo_keys` - Records the memo decryption key wrapped for identities of the access list.
+//! - `set_venue_default_expiry` - Sets the number of blocks after which new instructions of a venue expire.
+//! - `set_venue_cancellation_fee` - Sets the POLYX fee paid by parties that reject an instruction after affirming it.
+//! - `add_instruction_with_mediators` - Adds a new instruction that also requires the affirmation of mediators.
+//! - `affirm_instruction_as_mediator` - Provides the affirmation of a mediator to an existing instruction.
+//! - `withdraw_affirmation_as_mediator` - Withdraws the affirmation of a mediator.
+//! - `set_instruction_operator` - Delegates the management of an instruction to an operator.
+//! - `reject_instruction_as_operator` - Rejects an instruction on behalf of its creator.
+//! - `set_venue_fee` - Sets the POLYX fee charged to counterparties affirming instructions of a venue.
+//! - `set_receipt_signatories` - Sets how many venue signers must sign each receipt of a venue.
+//! - `set_receipt_metadata_schema` - Sets the constraints on the metadata of the receipts of a venue.
+//! - `set_venue_netting` - Enables or disables the netting of offsetting legs of a venue's instructions.
+//! - `create_recurring_instruction` - Generates an instruction with the same legs every given number of blocks.
+//! - `cancel_recurring_instruction` - Stops generating instructions from a recurring instruction.
+//! - `set_venue_quota` - Limits the instructions that can be created for a venue.
+//! - `pause_venue` - Stops the creation of new instructions under a venue.
+//! - `resume_venue` - Allows the creation of new instructions under a paused venue again.
+//! - `close_venue` - Removes a venue that has no open instructions.
+//! - `freeze_settlement` - Blocks the affirmation and execution of legs involving an asset.
+//! - `unfreeze_settlement` - Allows the affirmation and execution of legs involving an asset again.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![recursion_limit = "256"]
 
 #[cfg(feature = "runtime-benchmarks")]
 pub mod benchmarking;
+#[cfg(any(feature = "testing", feature = "runtime-benchmarks"))]
+pub mod test_utils;
+#[cfg(feature = "testing")]
+pub mod testing;
 
 use codec::{Decode, Encode};
 use frame_support::{
@@ -56,37 +91,51 @@ use frame_support::{
     ensure,
     storage::{with_transaction as frame_storage_with_transaction, TransactionOutcome},
     traits::{
-        schedule::{DispatchTime, Named as ScheduleNamed},
-        Get,
+        schedule::{DispatchTime, Named as ScheduleNamed, Priority},
+        Currency, ExistenceRequirement, Get,
     },
     weights::Weight,
-    IterableStorageDoubleMap,
+    IterableStorageDoubleMap, StorageDoubleMap,
 };
-use frame_system::{ensure_root, RawOrigin};
-use pallet_base::{ensure_string_limited, try_next_post};
+use frame_system::{ensure_root, ensure_signed, RawOrigin};
+use pallet_base::{ensure_string_limited, try_next_post, try_next_pre, try_use_entry};
 use pallet_identity::{self as identity, PermissionedCallOriginData};
 use polymesh_common_utilities::{
     constants::queue_priority::SETTLEMENT_INSTRUCTION_EXECUTION_PRIORITY,
     traits::{
-        asset, identity::Config as IdentityConfig, portfolio::PortfolioSubTrait, CommonConfig,
+        asset::{self, TickerMigrationHandler},
+        identity::Config as IdentityConfig,
+        portfolio::{PortfolioCustodyHandler, PortfolioSubTrait},
+        CommonConfig,
     },
     with_transaction,
     SystematicIssuers::Settlement as SettlementDID,
 };
 use polymesh_primitives::{
-    impl_checked_inc, storage_migrate_on, storage_migration_ver, Balance, IdentityId, NFTs,
-    PortfolioId, SecondaryKey, Ticker,
+    impl_checked_inc, storage_migrate_on, storage_migration_ver, Balance, IdentityId, Memo, NFTId,
+    NFTs, PortfolioId, SecondaryKey, Ticker,
 };
 use polymesh_primitives_derive::VecU8StrongTyped;
 use scale_info::TypeInfo;
-use sp_runtime::traits::{One, Verify};
-use sp_std::{collections::btree_set::BTreeSet, convert::TryFrom, prelude::*};
+use sp_io::{hashing::blake2_256, KillStorageResult};
+use sp_runtime::traits::{
+    AccountIdConversion, AtLeast32BitUnsigned, One, Saturating, Verify, Zero,
+};
+#[cfg(feature = "std")]
+use sp_runtime::{Deserialize, Serialize};
+use sp_std::{
+    collections::{btree_map::BTreeMap, btree_set::BTreeSet},
+    convert::TryFrom,
+    iter,
+    prelude::*,
+};
 
 type Identity<T> = identity::Module<T>;
 type System<T> = frame_system::Pallet<T>;
 type Asset<T> = pallet_asset::Module<T>;
 type ExternalAgents<T> = pallet_external_agents::Module<T>;
 type Nft<T> = pallet_nft::Module<T>;
+type Statistics<T> = pallet_statistics::Module<T>;
 
 pub trait Config:
     frame_system::Config
@@ -117,9 +166,22 @@ pub trait Config:
     type MaxNumberOfNFTsPerLeg: Get<u32>;
     /// Maximum number of NFTs that can be transferred in a instruction.
     type MaxNumberOfNFTs: Get<u32>;
+    /// Maximum weight of the legs of an instruction executed in a single block.
+    /// The legs of larger instructions allowing partial execution are executed over several blocks.
+    /// Other instructions are always executed at once, so that they settle all-or-nothing.
+    type MaxExecutionWeight: Get<Weight>;
+    /// Number of blocks the status of an executed or rejected instruction is kept for.
+    /// `None` keeps statuses forever, e.g. for chains serving as archives.
+    type InstructionStatusRetention: Get<Option<Self::BlockNumber>>;
+    /// Number of blocks the legs and affirmations of a rejected instruction are kept for, so that
+    /// counterparties can inspect what was rejected. Zero prunes them on rejection.
+    type RejectedInstructionRetention: Get<Self::BlockNumber>;
+    /// Number of blocks an instruction request can be approved for. Anyone can remove it afterwards.
+    type InstructionRequestLifetime: Get<Self::BlockNumber>;
 }
 
 /// A global and unique venue ID.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 #[derive(Encode, Decode, TypeInfo)]
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Default, Debug)]
 pub struct VenueId(pub u64);
@@ -190,6 +252,7 @@ impl<AccountId> Default for LegStatus<AccountId> {
 
 /// Status of an affirmation
 #[derive(Encode, Decode, TypeInfo, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum AffirmationStatus {
     /// Invalid affirmation
     Unknown,
@@ -215,6 +278,9 @@ pub enum SettlementType<BlockNumber> {
     SettleOnBlock(BlockNumber),
     /// Instruction must be settled manually on or after BlockNumber.
     SettleManual(BlockNumber),
+    /// Instruction should be settled in the next block as soon as all affirmations are received,
+    /// executing each leg on its own and skipping the legs that fail.
+    SettleOnAffirmationPartial,
 }
 
 impl<BlockNumber> Default for SettlementType<BlockNumber> {
@@ -223,14 +289,50 @@ impl<BlockNumber> Default for SettlementType<BlockNumber> {
     }
 }
 
+impl<BlockNumber> SettlementType<BlockNumber> {
+    /// Returns `true` if the instruction is executed as soon as all affirmations are received.
+    pub fn settles_on_affirmation(&self) -> bool {
+        matches!(
+            self,
+            Self::SettleOnAffirmation | Self::SettleOnAffirmationPartial
+        )
+    }
+
+    /// Returns `true` if the legs of the instruction are executed individually.
+    pub fn allows_partial_execution(&self) -> bool {
+        matches!(self, Self::SettleOnAffirmationPartial)
+    }
+}
+
 /// A per-Instruction leg ID.
+/// Leg ids are assigned in increasing order and are never reused within an instruction, even across edits.
 #[derive(Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Default, Debug)]
 pub struct LegId(pub u64);
 impl_checked_inc!(LegId);
 
+/// Tracks in which revisions of an instruction a leg was added and removed.
+#[derive(Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, PartialEq, Eq, Default, Debug)]
+pub struct LegRevision {
+    /// Revision in which the leg was added (`0` for legs created with the instruction).
+    pub added_in: u32,
+    /// Revision in which the leg was removed, if any.
+    pub removed_in: Option<u32>,
+}
+
+impl LegRevision {
+    /// Returns `true` if the leg was added or removed after `revision`.
+    pub fn changed_since(&self, revision: u32) -> bool {
+        self.added_in > revision || self.removed_in.map_or(false, |r| r > revision)
+    }
+}
+
 /// A global and unique instruction ID.
 #[derive(Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Default, Debug)]
 pub struct InstructionId(pub u64);
 impl_checked_inc!(InstructionId);
@@ -240,12 +342,166 @@ impl InstructionId {
     pub fn execution_name(&self) -> Vec<u8> {
         (polymesh_common_utilities::constants::schedule_name_prefix::SETTLEMENT_INSTRUCTION_EXECUTION, self.0).encode()
     }
+
+    /// Converts an instruction id into a scheduler name for its expiry.
+    pub fn expiry_name(&self) -> Vec<u8> {
+        (polymesh_common_utilities::constants::schedule_name_prefix::SETTLEMENT_INSTRUCTION_EXPIRY, self.0).encode()
+    }
+}
+
+/// A global and unique recurring instruction ID.
+#[derive(Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Default, Debug)]
+pub struct RecurringInstructionId(pub u64);
+impl_checked_inc!(RecurringInstructionId);
+
+impl RecurringInstructionId {
+    /// Converts a recurring instruction id into a scheduler name for the given occurrence.
+    /// Each occurrence has its own name, as the next one is scheduled while the current one runs.
+    pub fn schedule_name(&self, occurrence: u32) -> Vec<u8> {
+        (polymesh_common_utilities::constants::schedule_name_prefix::SETTLEMENT_RECURRING_INSTRUCTION, self.0, occurrence).encode()
+    }
+}
+
+/// Condition after which no more instructions are generated from a recurring instruction.
+#[derive(Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum RecurrenceStop<BlockNumber> {
+    /// Stop after generating the given number of instructions.
+    AfterOccurrences(u32),
+    /// Stop once the next instruction would be generated after the given block.
+    AfterBlock(BlockNumber),
+}
+
+/// Legs that are turned into a new instruction every `period` blocks until `stop` is reached.
+#[derive(Encode, Decode, TypeInfo)]
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct RecurringInstruction<BlockNumber> {
+    /// The venue creator that created the recurring instruction.
+    pub creator: IdentityId,
+    /// The venue of the generated instructions.
+    pub venue_id: VenueId,
+    /// The legs of each generated instruction.
+    pub legs: Vec<LegV2>,
+    /// The memo of each generated instruction.
+    pub memo: Option<Memo>,
+    /// The number of blocks between two generated instructions.
+    pub period: BlockNumber,
+    /// When to stop generating instructions.
+    pub stop: RecurrenceStop<BlockNumber>,
+    /// The block in which the next instruction is generated.
+    pub next_run: BlockNumber,
+    /// The number of instructions generated so far.
+    pub occurrences: u32,
+}
+
+/// A global and unique instruction request ID.
+#[derive(Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Default, Debug)]
+pub struct InstructionRequestId(pub u64);
+impl_checked_inc!(InstructionRequestId);
+
+/// An instruction proposed by one of its counterparties, which the venue creator can turn into an instruction.
+#[derive(Encode, Decode, TypeInfo)]
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct InstructionRequest<Moment, BlockNumber> {
+    /// The counterparty that proposed the instruction.
+    pub requester: IdentityId,
+    /// The venue the instruction is proposed to.
+    pub venue_id: VenueId,
+    /// Type of settlement of the instruction.
+    pub settlement_type: SettlementType<BlockNumber>,
+    /// Date from which the instruction is valid.
+    pub trade_date: Option<Moment>,
+    /// Date after which the instruction should be settled (not enforced).
+    pub value_date: Option<Moment>,
+    /// The legs of the instruction.
+    pub legs: Vec<LegV2>,
+    /// The memo of the instruction.
+    pub memo: Option<Memo>,
+    /// Block after which the request can no longer be approved.
+    pub expires_at: BlockNumber,
+}
+
+/// Progress of an instruction whose legs are executed over several blocks.
+#[derive(Encode, Decode, TypeInfo)]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ExecutionProgress {
+    /// Id of the first leg that has not been executed yet.
+    pub next_leg_id: LegId,
+    /// Legs that failed in the previous blocks, for instructions that allow partial execution.
+    pub failed_legs: Vec<LegId>,
+}
+
+/// Maximum number of tags that can be attached to an instruction.
+pub const MAX_INSTRUCTION_TAGS: u32 = 4;
+
+/// Maximum number of mediators of an instruction.
+pub const MAX_INSTRUCTION_MEDIATORS: u32 = 10;
+
+/// Maximum number of identities in the memo access list of an instruction.
+pub const MAX_MEMO_READERS: u32 = 100;
+
+/// Maximum number of receipts provided in a single affirmation.
+pub const MAX_RECEIPTS_PER_AFFIRMATION: u32 = 100;
+
+/// Maximum number of affirmations withdrawn when the custody of a portfolio changes.
+/// Remaining affirmations can be withdrawn with `revoke_affirmations_for_portfolio`.
+pub const MAX_AFFIRMATIONS_REVOKED_ON_CUSTODY_CHANGE: u32 = 10;
+
+/// Maximum number of pending instruction requests of a venue.
+pub const MAX_VENUE_INSTRUCTION_REQUESTS: u32 = 32;
+
+/// A short tag attached to an instruction, e.g. to reference an off-chain trade id.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, TypeInfo)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct InstructionTag(pub [u8; 16]);
+
+/// An opaque counterparty of an instruction, committing to a portfolio as `blake2_256((portfolio, salt))`.
+/// Legs are created with its `placeholder` portfolio, which the custodian reveals to be the real one
+/// when the instruction is executed.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, TypeInfo)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct PortfolioAlias(pub [u8; 32]);
+
+impl PortfolioAlias {
+    /// Returns the alias of `portfolio` for the custodian's secret `salt`.
+    pub fn new(portfolio: &PortfolioId, salt: &[u8; 32]) -> Self {
+        Self(blake2_256(&(portfolio, salt).encode()))
+    }
+
+    /// Returns the portfolio standing for the alias in the legs of an instruction.
+    /// No identity can act for it, so the instruction can't execute before the alias is revealed.
+    pub fn placeholder(&self) -> PortfolioId {
+        PortfolioId::default_portfolio(IdentityId::from(self.0))
+    }
+}
+
+/// Reason given by a party for rejecting an instruction.
+#[derive(Encode, Decode, TypeInfo)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum RejectReason {
+    /// The trade fails the compliance rules of one of the parties.
+    ComplianceFailure,
+    /// The trade was not made at the agreed price.
+    Pricing,
+    /// The instruction duplicates another instruction.
+    Duplicate,
+    /// Any other reason, usually detailed in the rejection memo.
+    Other,
 }
 
-/// A wrapper for InstructionMemo
+/// The decryption key of an encrypted instruction memo, wrapped (encrypted) for a single identity.
+/// The wrapping scheme is agreed off-chain between the venue and the receiving party.
 #[derive(Encode, Decode, TypeInfo)]
 #[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
-pub struct InstructionMemo(pub [u8; 32]);
+pub struct WrappedMemoKey(pub Vec<u8>);
 
 /// Details about an instruction.
 #[derive(Encode, Decode, TypeInfo)]
@@ -263,6 +519,8 @@ pub struct Instruction<Moment, BlockNumber> {
     pub trade_date: Option<Moment>,
     /// Date after which the instruction should be settled (not enforced)
     pub value_date: Option<Moment>,
+    /// Block at which the instruction is pruned if it is still waiting for affirmations
+    pub expiry_block: Option<BlockNumber>,
 }
 
 /// Details of a leg including the leg id in the instruction.
@@ -297,6 +555,7 @@ impl TryFrom<LegV2> for Leg {
 
 /// Type of assets that can be transferred in a `Leg`.
 #[derive(Clone, Debug, Decode, Encode, Eq, PartialEq, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum LegAsset {
     Fungible { ticker: Ticker, amount: Balance },
     NonFungible(NFTs),
@@ -323,6 +582,7 @@ impl Default for LegAsset {
 
 /// Defines a leg (i.e the action of a settlement).
 #[derive(Clone, Debug, Decode, Default, Encode, Eq, PartialEq, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct LegV2 {
     /// Portfolio of the sender.
     pub from: PortfolioId,
@@ -381,6 +641,118 @@ pub struct Venue {
     pub venue_type: VenueType,
 }
 
+/// POLYX fee charged by a venue to each portfolio affirming one of its instructions.
+/// The fee is held in escrow until the instruction settles, and refunded otherwise.
+#[derive(Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum VenueFee {
+    /// The same fee for every affirming portfolio.
+    Flat(Balance),
+    /// A fee for each leg the affirming portfolio is part of.
+    PerLeg(Balance),
+}
+
+impl VenueFee {
+    /// Returns the fee owed by a portfolio that is part of `legs` legs.
+    pub fn amount(&self, legs: usize) -> Balance {
+        match self {
+            Self::Flat(fee) => *fee,
+            Self::PerLeg(fee) => fee.saturating_mul(legs as Balance),
+        }
+    }
+
+    /// Returns `true` if the fee is zero.
+    pub fn is_zero(&self) -> bool {
+        match self {
+            Self::Flat(fee) | Self::PerLeg(fee) => *fee == 0,
+        }
+    }
+}
+
+/// Limits on the instructions created for a venue, protecting the chain and the venue's
+/// counterparties from buggy venue software flooding instructions.
+#[derive(Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, Default, PartialEq, Eq, Debug)]
+pub struct VenueQuota {
+    /// Maximum number of instructions of the venue that have been neither executed nor rejected.
+    pub max_open_instructions: Option<u64>,
+    /// Maximum number of instructions of the venue that a key can create in a single block.
+    pub max_instructions_per_block: Option<u32>,
+}
+
+impl VenueQuota {
+    /// Returns `true` if the quota doesn't limit anything.
+    pub fn is_unlimited(&self) -> bool {
+        self.max_open_instructions.is_none() && self.max_instructions_per_block.is_none()
+    }
+}
+
+/// Recurring block ranges, e.g. the trading hours of a regulated market, during which the
+/// execution of instructions is prioritized by the scheduler.
+#[derive(Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, Default, PartialEq, Eq, Debug)]
+pub struct MarketHours<BlockNumber> {
+    /// Number of blocks after which the market hours repeat, e.g. the number of blocks in a day.
+    pub period: BlockNumber,
+    /// Offset in the period of the first block of the market hours.
+    pub open: BlockNumber,
+    /// Offset in the period of the first block after the market hours.
+    /// The market hours wrap around the end of the period if it is lower than `open`.
+    pub close: BlockNumber,
+    /// Scheduler priority of the executions during market hours.
+    pub priority: Priority,
+    /// Whether executions planned outside of market hours are deferred to the next opening.
+    pub defer_off_hours: bool,
+}
+
+impl<BlockNumber: AtLeast32BitUnsigned + Copy> MarketHours<BlockNumber> {
+    /// Returns `true` if the market hours are within their period and not empty.
+    pub fn is_valid(&self) -> bool {
+        !self.period.is_zero()
+            && self.open < self.period
+            && self.close <= self.period
+            && self.open != self.close
+    }
+
+    /// Returns `true` if `block` is within the market hours.
+    pub fn is_open(&self, block: BlockNumber) -> bool {
+        let offset = block % self.period;
+        if self.open < self.close {
+            self.open <= offset && offset < self.close
+        } else {
+            self.open <= offset || offset < self.close
+        }
+    }
+
+    /// Returns the first block of the next market hours after `block`, which must be off-hours.
+    pub fn next_open(&self, block: BlockNumber) -> BlockNumber {
+        let offset = block % self.period;
+        let period_start = block - offset;
+        if offset < self.open {
+            period_start.saturating_add(self.open)
+        } else {
+            period_start
+                .saturating_add(self.period)
+                .saturating_add(self.open)
+        }
+    }
+}
+
+/// The consideration agreed for a leg, e.g. the price paid off-chain for the tokens of a DvP trade.
+/// It is only recorded, and never transferred.
+#[derive(Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, Default, PartialEq, Eq, Debug)]
+pub struct LegConsideration {
+    /// Currency of the consideration, which doesn't have to be an asset of the chain.
+    pub currency: Ticker,
+    /// Amount of `currency` agreed for the leg.
+    pub amount: Balance,
+}
+
 /// Details about an offchain transaction receipt
 #[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, PartialOrd, Ord)]
 pub struct Receipt<Balance> {
@@ -398,6 +770,7 @@ pub struct Receipt<Balance> {
 
 /// A wrapper for VenueDetails
 #[derive(Encode, Decode, TypeInfo, VecU8StrongTyped)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 #[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
 pub struct ReceiptMetadata(Vec<u8>);
 
@@ -416,6 +789,115 @@ pub struct ReceiptDetails<AccountId, OffChainSignature> {
     pub metadata: ReceiptMetadata,
 }
 
+/// Where a claimed receipt was used, along with the metadata it was claimed with.
+#[derive(Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ReceiptUsageDetails {
+    /// Instruction the receipt was claimed for.
+    pub instruction_id: InstructionId,
+    /// Leg of the instruction settled by the receipt.
+    pub leg_id: LegId,
+    /// Metadata attached to the receipt when it was claimed.
+    pub metadata: ReceiptMetadata,
+}
+
+/// How much a venue signer has been used to sign receipts.
+#[derive(Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct VenueSignerUsage<BlockNumber> {
+    /// Number of receipts signed by the signer claimed for instructions of the venue.
+    pub receipts_signed: u64,
+    /// Block in which a receipt signed by the signer was last claimed.
+    pub last_used: Option<BlockNumber>,
+}
+
+/// The audit record of an executed instruction.
+/// Its SCALE encoding is digested with `blake2_256` when the instruction is executed,
+/// so that exports of the record can be notarized and later verified against the chain.
+#[derive(Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InstructionAuditRecord<AccountId, Moment, BlockNumber> {
+    /// Id of the instruction.
+    pub instruction_id: InstructionId,
+    /// Id of the venue of the instruction.
+    pub venue_id: VenueId,
+    /// Date at which the instruction was created.
+    pub created_at: Option<Moment>,
+    /// Trade date of the instruction.
+    pub trade_date: Option<Moment>,
+    /// Value date of the instruction.
+    pub value_date: Option<Moment>,
+    /// Legs of the instruction, with their leg id.
+    pub legs: Vec<(LegId, LegV2)>,
+    /// Portfolios that affirmed the instruction.
+    pub affirmations: Vec<PortfolioId>,
+    /// Receipts claimed for the legs of the instruction, as (leg id, signer, receipt uid).
+    pub receipts: Vec<(LegId, AccountId, u64)>,
+    /// Block in which the instruction was executed.
+    pub executed_at: BlockNumber,
+    /// Date at which the instruction was executed.
+    pub executed_on: Moment,
+}
+
+/// An export of the audit record of an executed instruction.
+#[derive(Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InstructionAuditExport {
+    /// SCALE encoding of the `InstructionAuditRecord`.
+    pub record: Vec<u8>,
+    /// `blake2_256` digest of `record`.
+    pub digest: [u8; 32],
+}
+
+/// Constraints on the metadata of the receipts claimed for the instructions of a venue.
+#[derive(Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ReceiptMetadataSchema {
+    /// Maximum length of the metadata, in bytes.
+    pub max_length: u32,
+    /// Prefix, e.g. a tag, the metadata must start with.
+    pub required_prefix: ReceiptMetadata,
+}
+
+impl ReceiptMetadataSchema {
+    /// Returns `true` if `metadata` satisfies the schema.
+    pub fn accepts(&self, metadata: &ReceiptMetadata) -> bool {
+        metadata.len() <= self.max_length as usize && metadata.starts_with(&self.required_prefix)
+    }
+}
+
+/// An asset of a portfolio locked by an affirmed leg until its instruction is executed or rejected.
+#[derive(Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LockedAsset {
+    /// `amount` tokens of `ticker`.
+    Fungible { ticker: Ticker, amount: Balance },
+    /// The NFT `nft_id` of the `ticker` collection.
+    NonFungible { ticker: Ticker, nft_id: NFTId },
+}
+
+/// What an identity is due to receive from, and deliver to, a counterparty in a ticker
+/// through pending instructions. The net obligation is `receivable - deliverable`.
+#[derive(Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct NetObligation {
+    /// The identity on the other side of the legs.
+    pub counterparty: IdentityId,
+    /// Ticker of the assets being transferred.
+    pub ticker: Ticker,
+    /// Amount the identity is due to receive from `counterparty`.
+    pub receivable: Balance,
+    /// Amount the identity is due to deliver to `counterparty`.
+    pub deliverable: Balance,
+}
+
 /// Stores information about an Instruction.
 struct InstructionInfo {
     /// Unique counter parties involved in the instruction.
@@ -479,6 +961,53 @@ pub trait WeightInfo {
     fn affirm_instruction_v2(f: u32, n: u32) -> Weight;
     fn withdraw_affirmation_v2(f: u32, n: u32) -> Weight;
     fn reject_instruction_v2(f: u32, n: u32) -> Weight;
+    fn edit_instruction_legs(l: u32) -> Weight;
+    fn set_memo_access_list(d: u32) -> Weight;
+    fn wrap_memo_keys(k: u32) -> Weight;
+    fn set_venue_default_expiry() -> Weight;
+    fn expire_instruction(f: u32, n: u32) -> Weight;
+    fn set_venue_cancellation_fee() -> Weight;
+    fn pay_cancellation_fee(p: u32) -> Weight;
+    fn add_mediators(m: u32) -> Weight;
+    fn affirm_instruction_as_mediator(f: u32, n: u32) -> Weight;
+    fn withdraw_affirmation_as_mediator() -> Weight;
+    fn set_instruction_operator() -> Weight;
+    fn set_venue_fee() -> Weight;
+    fn transfer_venue_fees(p: u32) -> Weight;
+    fn set_receipt_signatories(s: u32) -> Weight;
+    fn set_receipt_metadata_schema() -> Weight;
+    fn set_venue_netting() -> Weight;
+    fn create_recurring_instruction(l: u32) -> Weight;
+    fn cancel_recurring_instruction() -> Weight;
+    fn execute_recurring_instruction(l: u32) -> Weight;
+    fn freeze_settlement() -> Weight;
+    fn set_venue_quota() -> Weight;
+    fn pause_venue() -> Weight;
+    fn resume_venue() -> Weight;
+    fn close_venue(s: u32, i: u32) -> Weight;
+    fn unfreeze_settlement() -> Weight;
+    fn amend_instruction_leg(l: u32) -> Weight;
+    fn set_leg_consideration() -> Weight;
+    fn set_market_hours() -> Weight;
+    fn clear_execution_rounds(v: u32) -> Weight;
+    fn add_instruction_tags(t: u32) -> Weight;
+    fn migrate_instruction_legs(i: u32, l: u32) -> Weight;
+    fn reveal_portfolio_alias(l: u32) -> Weight;
+    fn prune_instruction_status() -> Weight;
+    fn prune_rejected_instruction(l: u32) -> Weight;
+    fn instruction_memo(m: u32) -> Weight;
+    fn request_instruction(l: u32) -> Weight;
+    fn approve_requested_instruction(f: u32, n: u32) -> Weight;
+    fn remove_instruction_request() -> Weight;
+    fn set_portfolio_settlement_agent() -> Weight;
+    fn revoke_affirmations(i: u32, f: u32, n: u32) -> Weight {
+        Self::withdraw_affirmation_v2(f, n)
+            .saturating_add(Self::transfer_venue_fees(1))
+            .saturating_mul(i.into())
+    }
+    fn memo(memo: &Option<Memo>) -> Weight {
+        Self::instruction_memo(memo.as_ref().map_or(0, |memo| memo.len() as u32))
+    }
     fn add_and_affirm_instruction_with_memo_v2_legs(legs_v2: &[LegV2]) -> Weight {
         let (f, n) = get_transfer_by_asset(legs_v2);
         Self::add_and_affirm_instruction_with_memo_v2(f, n)
@@ -521,14 +1050,19 @@ decl_event!(
             Option<Moment>,
             Option<Moment>,
             Vec<Leg>,
-            Option<InstructionMemo>,
+            Option<Memo>,
         ),
         /// An instruction has been affirmed (did, portfolio, instruction_id)
         InstructionAffirmed(IdentityId, PortfolioId, InstructionId),
         /// An affirmation has been withdrawn (did, portfolio, instruction_id)
         AffirmationWithdrawn(IdentityId, PortfolioId, InstructionId),
-        /// An instruction has been rejected (did, instruction_id)
-        InstructionRejected(IdentityId, InstructionId),
+        /// An instruction has been rejected (did, instruction_id, reason, memo)
+        InstructionRejected(
+            IdentityId,
+            InstructionId,
+            Option<RejectReason>,
+            Option<Memo>,
+        ),
         /// A receipt has been claimed (did, instruction_id, leg_id, receipt_uid, signer, receipt metadata)
         ReceiptClaimed(
             IdentityId,
@@ -575,10 +1109,136 @@ decl_event!(
             Option<Moment>,
             Option<Moment>,
             Vec<LegV2>,
-            Option<InstructionMemo>,
+            Option<Memo>,
         ),
         /// Failed to execute instruction.
         FailedToExecuteInstruction(InstructionId, DispatchError),
+        /// The legs of an instruction have been edited.
+        /// (did, instruction_id, revision, removed_legs, added_legs)
+        InstructionLegsEdited(IdentityId, InstructionId, u32, Vec<LegId>, Vec<LegId>),
+        /// The access list of an encrypted instruction memo has been set (did, instruction_id, dids)
+        MemoAccessListSet(IdentityId, InstructionId, Vec<IdentityId>),
+        /// Memo decryption keys have been wrapped for identities of the access list
+        /// (did, instruction_id, dids)
+        MemoKeysWrapped(IdentityId, InstructionId, Vec<IdentityId>),
+        /// The default expiry of new instructions of a venue has been set (did, venue_id, expiry)
+        VenueDefaultExpirySet(IdentityId, VenueId, Option<BlockNumber>),
+        /// An instruction has expired before collecting all affirmations and has been pruned (instruction_id)
+        InstructionExpired(InstructionId),
+        /// The cancellation fee of a venue has been set (did, venue_id, fee)
+        VenueCancellationFeeSet(IdentityId, VenueId, Option<Balance>),
+        /// A new instruction is subject to a cancellation fee (instruction_id, fee)
+        InstructionCancellationFeeDisclosed(InstructionId, Balance),
+        /// A party rejecting an instruction it had affirmed paid the cancellation fee
+        /// to the other counterparties (did, instruction_id, fee)
+        CancellationFeePaid(IdentityId, InstructionId, Balance),
+        /// Mediators whose affirmation is required have been added to an instruction
+        /// (did, instruction_id, mediators)
+        InstructionMediatorsAdded(IdentityId, InstructionId, Vec<IdentityId>),
+        /// A mediator has affirmed an instruction (did, instruction_id)
+        MediatorAffirmationReceived(IdentityId, InstructionId),
+        /// A mediator has withdrawn its affirmation (did, instruction_id)
+        MediatorAffirmationWithdrawn(IdentityId, InstructionId),
+        /// The operator managing an instruction on behalf of its creator has been set
+        /// (did, instruction_id, operator)
+        InstructionOperatorSet(IdentityId, InstructionId, Option<IdentityId>),
+        /// Instruction executed with some of its legs skipped (did, instruction_id, failed_legs)
+        InstructionPartiallyExecuted(IdentityId, InstructionId, Vec<LegId>),
+        /// The fee charged to counterparties affirming instructions of a venue has been set
+        /// (did, venue_id, fee)
+        VenueFeeSet(IdentityId, VenueId, Option<VenueFee>),
+        /// A new instruction is subject to a venue fee (instruction_id, fee)
+        InstructionVenueFeeDisclosed(InstructionId, VenueFee),
+        /// The venue fee of an affirming portfolio has been charged and held in escrow
+        /// (did, instruction_id, portfolio, amount)
+        VenueFeeCharged(IdentityId, InstructionId, PortfolioId, Balance),
+        /// The venue fee of a portfolio has been refunded (instruction_id, portfolio, amount)
+        VenueFeeRefunded(InstructionId, PortfolioId, Balance),
+        /// The venue fees of a settled instruction have been paid to the venue creator
+        /// (venue creator did, instruction_id, amount)
+        VenueFeesPaid(IdentityId, InstructionId, Balance),
+        /// The number of venue signers that must sign each receipt has been set
+        /// (did, venue_id, threshold)
+        ReceiptSignatoriesSet(IdentityId, VenueId, u32),
+        /// The schema of the receipt metadata of a venue has been set or removed
+        /// (did, venue_id, schema)
+        ReceiptMetadataSchemaSet(IdentityId, VenueId, Option<ReceiptMetadataSchema>),
+        /// The netting of offsetting legs has been enabled or disabled for a venue
+        /// (did, venue_id, enabled)
+        VenueNettingSet(IdentityId, VenueId, bool),
+        /// Legs whose transfers were netted into another leg of the instruction, or fully offset
+        /// (did, instruction_id, leg_ids)
+        LegsNetted(IdentityId, InstructionId, Vec<LegId>),
+        /// A recurring instruction has been created
+        /// (did, venue_id, recurring_instruction_id, period, stop)
+        RecurringInstructionCreated(
+            IdentityId,
+            VenueId,
+            RecurringInstructionId,
+            BlockNumber,
+            RecurrenceStop<BlockNumber>,
+        ),
+        /// An instruction has been generated from a recurring instruction
+        /// (did, recurring_instruction_id, instruction_id)
+        RecurringInstructionGenerated(IdentityId, RecurringInstructionId, InstructionId),
+        /// An instruction could not be generated from a recurring instruction
+        /// (recurring_instruction_id, error)
+        RecurringInstructionFailed(RecurringInstructionId, DispatchError),
+        /// A recurring instruction has reached its stop condition, or has been cancelled by
+        /// its creator (did, recurring_instruction_id)
+        RecurringInstructionEnded(IdentityId, RecurringInstructionId),
+        /// Affirmation and execution of legs involving a ticker have been frozen (did, ticker)
+        SettlementFrozen(IdentityId, Ticker),
+        /// Affirmation and execution of legs involving a ticker have been unfrozen (did, ticker)
+        SettlementUnfrozen(IdentityId, Ticker),
+        /// The instruction quota of a venue has been set (did, venue_id, quota)
+        VenueQuotaSet(IdentityId, VenueId, Option<VenueQuota>),
+        /// No new instruction can be created under a venue (did, venue_id)
+        VenuePaused(IdentityId, VenueId),
+        /// New instructions can be created under a paused venue again (did, venue_id)
+        VenueResumed(IdentityId, VenueId),
+        /// A venue without open instructions has been removed (did, venue_id)
+        VenueClosed(IdentityId, VenueId),
+        /// A leg of an instruction has been replaced before any affirmation
+        /// (did, instruction_id, leg_id, new_leg_id)
+        InstructionAmended(IdentityId, InstructionId, LegId, LegId),
+        /// The consideration of a leg has been set or removed
+        /// (did, instruction_id, leg_id, consideration)
+        LegConsiderationSet(IdentityId, InstructionId, LegId, Option<LegConsideration>),
+        /// The market hours during which executions are prioritized have been set (market_hours)
+        MarketHoursSet(Option<MarketHours<BlockNumber>>),
+        /// An instruction has been scheduled for execution (instruction_id, block_number, round).
+        /// Within a block, instructions are executed by increasing round.
+        InstructionExecutionScheduled(InstructionId, BlockNumber, u32),
+        /// Tags have been attached to a new instruction (did, instruction_id, tags)
+        InstructionTagsAdded(IdentityId, InstructionId, Vec<InstructionTag>),
+        /// The legs of an instruction that fit in a block have been executed, and its execution
+        /// continues from the given leg in a later block (did, instruction_id, next_leg_id)
+        InstructionExecutionContinued(IdentityId, InstructionId, LegId),
+        /// The statuses of instructions settled before the retention period have been pruned (instruction_ids)
+        InstructionStatusesPruned(Vec<InstructionId>),
+        /// The legs and affirmations of instructions rejected before the retention period have been pruned (instruction_ids)
+        RejectedInstructionsPruned(Vec<InstructionId>),
+        /// The portfolio behind an alias of an instruction has been revealed (did, instruction_id, alias, portfolio)
+        PortfolioAliasRevealed(IdentityId, InstructionId, PortfolioAlias, PortfolioId),
+        /// A counterparty has proposed an instruction to a venue (did, venue_id, request_id)
+        InstructionRequested(IdentityId, VenueId, InstructionRequestId),
+        /// The venue creator has created the instruction proposed by a request (did, request_id, instruction_id)
+        InstructionRequestApproved(IdentityId, InstructionRequestId, InstructionId),
+        /// A request has been withdrawn by its requester, declined by the venue creator or removed once expired (did, request_id)
+        InstructionRequestRemoved(IdentityId, InstructionRequestId),
+        /// The custodian of a portfolio has appointed or removed its settlement agent (did, portfolio, agent)
+        PortfolioSettlementAgentSet(IdentityId, PortfolioId, Option<IdentityId>),
+        /// The affirmations of a portfolio have been withdrawn, skipping those that couldn't be.
+        /// The last instruction scanned is given if more affirmations may remain
+        /// (did, portfolio, revoked_instruction_ids, failed_instruction_ids, last_scanned)
+        PortfolioAffirmationsRevoked(
+            IdentityId,
+            PortfolioId,
+            Vec<InstructionId>,
+            Vec<InstructionId>,
+            Option<InstructionId>,
+        ),
     }
 );
 
@@ -656,11 +1316,97 @@ decl_error! {
         /// Deprecated function has been called on a v2 instruction.
         DeprecatedCallOnV2Instruction,
         /// Off-chain receipts are not accepted for non-fungible tokens.
-        ReceiptForNonFungibleAsset
+        ReceiptForNonFungibleAsset,
+        /// The given leg is not part of the instruction.
+        LegNotFound,
+        /// The instruction has no memo.
+        InstructionHasNoMemo,
+        /// The identity is not in the access list of the instruction memo.
+        NotInMemoAccessList,
+        /// The instruction expiry must be at least one block.
+        InvalidExpiry,
+        /// The mediator identity does not exist.
+        UnknownMediator,
+        /// The caller is not a mediator of this instruction.
+        CallerIsNotAMediator,
+        /// The operator identity does not exist.
+        UnknownOperator,
+        /// The identity paying the venue fee has no primary key.
+        VenueFeePayerNotFound,
+        /// The receipt threshold is zero or above the number of venue signers.
+        InvalidReceiptSignatories,
+        /// A leg has fewer receipt signers than the threshold of the venue.
+        NotEnoughReceiptSigners,
+        /// A signer provided more than one receipt for the same leg.
+        DuplicateReceiptSigner,
+        /// The required prefix of a receipt metadata schema is longer than its maximum length.
+        InvalidReceiptMetadataSchema,
+        /// The metadata of a receipt doesn't satisfy the schema of the venue.
+        ReceiptMetadataSchemaViolation,
+        /// The period of a recurring instruction must be at least one block.
+        InvalidRecurrencePeriod,
+        /// The stop condition of a recurring instruction would not let it generate any instruction.
+        InvalidRecurrenceStop,
+        /// The recurring instruction doesn't exist.
+        UnknownRecurringInstruction,
+        /// Settlement of the asset is already frozen.
+        SettlementAlreadyFrozen,
+        /// Settlement of the asset is not frozen.
+        SettlementNotFrozen,
+        /// A leg involves an asset whose settlement is frozen.
+        AssetSettlementFrozen,
+        /// The venue has reached its maximum number of open instructions.
+        VenueOpenInstructionsQuotaReached,
+        /// The key has reached the maximum number of instructions it can create for the venue in this block.
+        VenueBlockInstructionsQuotaReached,
+        /// The venue is paused.
+        VenuePaused,
+        /// The venue is not paused.
+        VenueNotPaused,
+        /// The venue still has instructions that have been neither executed nor rejected.
+        VenueHasOpenInstructions,
+        /// The given number of venue signers is smaller than the actual number of signers of the venue.
+        SignersCountTooSmall,
+        /// A leg involves an asset that has matured or been retired.
+        AssetTransfersEnded,
+        /// The market hours are empty or don't fit in their period.
+        InvalidMarketHours,
+        /// More than `MAX_INSTRUCTION_TAGS` tags were given for an instruction.
+        TooManyInstructionTags,
+        /// Some legs of the instruction have already been executed, so it can only be executed further or rejected.
+        InstructionExecutionInProgress,
+        /// The alias is not a counterparty of the instruction.
+        PortfolioAliasNotFound,
+        /// The portfolio and salt don't match the alias.
+        InvalidPortfolioAlias,
+        /// The revealed portfolio is already a counterparty of the instruction.
+        PortfolioAlreadyCounterparty,
+        /// The memo is longer than `MAX_MEMO_LEN` bytes.
+        MemoTooLong,
+        /// The instruction request does not exist.
+        UnknownInstructionRequest,
+        /// The caller doesn't own any portfolio of the requested instruction.
+        RequesterIsNotAParty,
+        /// The identity of the settlement agent doesn't exist.
+        InvalidSettlementAgent,
+        /// The memo access list has more than `MAX_MEMO_READERS` identities.
+        TooManyMemoReaders,
+        /// The instruction has more than `MAX_INSTRUCTION_MEDIATORS` mediators.
+        TooManyMediators,
+        /// More than `MAX_RECEIPTS_PER_AFFIRMATION` receipts were provided.
+        TooManyReceipts,
+        /// Portfolio aliases can only be revealed in instructions settling on affirmation.
+        AliasRequiresSettleOnAffirmation,
+        /// The venue has reached `MAX_VENUE_INSTRUCTION_REQUESTS` pending instruction requests.
+        TooManyInstructionRequests,
+        /// The instruction request has expired and can no longer be approved.
+        InstructionRequestExpired,
+        /// The given number of venue instructions is smaller than the actual number of instructions of the venue.
+        InstructionsCountTooSmall,
     }
 }
 
-storage_migration_ver!(1);
+storage_migration_ver!(2);
 
 decl_storage! {
     trait Store for Module<T: Config> as Settlement {
@@ -716,15 +1462,165 @@ decl_storage! {
         /// Number of instructions in the system (It's one more than the actual number)
         InstructionCounter get(fn instruction_counter) build(|_| InstructionId(1u64)): InstructionId;
         /// Storage version.
-        StorageVersion get(fn storage_version) build(|_| Version::new(1)): Version;
-        /// Instruction memo
-        InstructionMemos get(fn memo): map hasher(twox_64_concat) InstructionId => Option<InstructionMemo>;
+        StorageVersion get(fn storage_version) build(|_| Version::new(2)): Version;
+        /// Instruction memo. For encrypted memos, this is a commitment to the encrypted memo.
+        InstructionMemos get(fn memo): map hasher(twox_64_concat) InstructionId => Option<Memo>;
+        /// Reason and memo given when rejecting an instruction. instruction_id -> (reason, memo)
+        InstructionRejections get(fn instruction_rejection):
+            map hasher(twox_64_concat) InstructionId => Option<(RejectReason, Option<Memo>)>;
         /// Instruction statuses. instruction_id -> InstructionStatus
         InstructionStatuses get(fn instruction_status):
             map hasher(twox_64_concat) InstructionId => InstructionStatus<T::BlockNumber>;
         /// Legs under an instruction. (instruction_id, leg_id) -> Leg
         pub InstructionLegsV2 get(fn instruction_legsv2):
             double_map hasher(twox_64_concat) InstructionId, hasher(twox_64_concat) LegId => LegV2;
+        /// Number of times the legs of an instruction have been edited. instruction_id -> revision
+        pub InstructionRevision get(fn instruction_revision):
+            map hasher(twox_64_concat) InstructionId => u32;
+        /// Next leg id to assign to a leg added to an edited instruction. instruction_id -> leg_id
+        /// Unset for instructions that have never been edited, in which case it is the number of legs.
+        InstructionNextLegId get(fn instruction_next_leg_id):
+            map hasher(twox_64_concat) InstructionId => Option<LegId>;
+        /// Revisions of legs added or removed by an edit. (instruction_id, leg_id) -> LegRevision
+        /// Legs without an entry were created with the instruction and are still part of it.
+        pub InstructionLegRevisions get(fn instruction_leg_revisions):
+            double_map hasher(twox_64_concat) InstructionId, hasher(twox_64_concat) LegId => Option<LegRevision>;
+        /// Legs removed from an instruction by an edit. (instruction_id, leg_id) -> Leg
+        pub RemovedInstructionLegs get(fn removed_instruction_legs):
+            double_map hasher(twox_64_concat) InstructionId, hasher(twox_64_concat) LegId => Option<LegV2>;
+        /// Identities whose off-chain systems may receive the decryption key of an encrypted memo.
+        /// A non-empty list marks the instruction memo as a commitment. instruction_id -> dids
+        pub MemoAccessList get(fn memo_access_list):
+            map hasher(twox_64_concat) InstructionId => Vec<IdentityId>;
+        /// Memo decryption key wrapped for an identity of the access list.
+        /// (instruction_id, did) -> wrapped_key
+        pub MemoKeyWraps get(fn memo_key_wraps):
+            double_map hasher(twox_64_concat) InstructionId, hasher(twox_64_concat) IdentityId => Option<WrappedMemoKey>;
+        /// Number of blocks after which new instructions of a venue expire. venue_id -> expiry
+        pub VenueDefaultExpiry get(fn venue_default_expiry):
+            map hasher(twox_64_concat) VenueId => Option<T::BlockNumber>;
+        /// POLYX fee paid by a party rejecting an instruction of a venue after affirming it. venue_id -> fee
+        pub VenueCancellationFee get(fn venue_cancellation_fee):
+            map hasher(twox_64_concat) VenueId => Option<Balance>;
+        /// Cancellation fee of an instruction, as disclosed at its creation. instruction_id -> fee
+        pub InstructionCancellationFees get(fn instruction_cancellation_fee):
+            map hasher(twox_64_concat) InstructionId => Option<Balance>;
+        /// Affirmation status of the mediators of an instruction. (instruction_id, mediator) -> AffirmationStatus
+        pub InstructionMediators get(fn instruction_mediators):
+            double_map hasher(twox_64_concat) InstructionId, hasher(twox_64_concat) IdentityId => AffirmationStatus;
+        /// Identity managing an instruction on behalf of its creator. instruction_id -> operator
+        pub InstructionOperators get(fn instruction_operator):
+            map hasher(twox_64_concat) InstructionId => Option<IdentityId>;
+        /// POLYX fee charged to counterparties affirming instructions of a venue. venue_id -> fee
+        pub VenueFees get(fn venue_fee):
+            map hasher(twox_64_concat) VenueId => Option<VenueFee>;
+        /// Venue fee of an instruction, as disclosed at its creation. instruction_id -> fee
+        pub InstructionVenueFees get(fn instruction_venue_fee):
+            map hasher(twox_64_concat) InstructionId => Option<VenueFee>;
+        /// Venue fees held in escrow for the affirming portfolios of an instruction.
+        /// (instruction_id, portfolio) -> (payer, amount)
+        pub VenueFeesCharged get(fn venue_fee_charged):
+            double_map hasher(twox_64_concat) InstructionId, hasher(twox_64_concat) PortfolioId => Option<(T::AccountId, Balance)>;
+        /// Number of venue signers that must sign the receipt of a leg, i.e. the `m` of the
+        /// `m`-of-`n` venue signers. Zero is the same as one. venue_id -> threshold
+        pub ReceiptSignatories get(fn receipt_signatories):
+            map hasher(twox_64_concat) VenueId => u32;
+        /// Receipts claimed for a leg in addition to the one recorded in its `LegStatus`.
+        /// (instruction_id, leg_id) -> [(signer, receipt_uid)]
+        pub ReceiptCosigners get(fn receipt_cosigners):
+            double_map hasher(twox_64_concat) InstructionId, hasher(twox_64_concat) LegId => Vec<(T::AccountId, u64)>;
+        /// Instruction, leg and metadata of the receipts currently claimed by each signer.
+        /// (signer, receipt_uid) -> receipt usage
+        pub ReceiptUsage get(fn receipt_usage):
+            double_map hasher(twox_64_concat) T::AccountId, hasher(blake2_128_concat) u64 => Option<ReceiptUsageDetails>;
+        /// How much each signer of a venue has been used to sign receipts. (venue_id, signer) -> usage
+        pub VenueSignerUsages get(fn venue_signer_usage):
+            double_map hasher(twox_64_concat) VenueId, hasher(twox_64_concat) T::AccountId
+                => VenueSignerUsage<T::BlockNumber>;
+        /// Constraints on the metadata of the receipts of a venue. venue_id -> schema
+        pub ReceiptMetadataSchemas get(fn receipt_metadata_schema):
+            map hasher(twox_64_concat) VenueId => Option<ReceiptMetadataSchema>;
+        /// Venues whose instructions have their offsetting legs netted on execution. venue_id -> enabled
+        pub VenueNetting get(fn venue_netting):
+            map hasher(twox_64_concat) VenueId => bool;
+        /// Number of recurring instructions in the system. Also the next recurring instruction id.
+        pub RecurringInstructionCounter get(fn recurring_instruction_counter): RecurringInstructionId;
+        /// Recurring instructions whose stop condition has not been reached yet.
+        /// recurring_instruction_id -> recurring instruction
+        pub RecurringInstructions get(fn recurring_instruction):
+            map hasher(twox_64_concat) RecurringInstructionId => Option<RecurringInstruction<T::BlockNumber>>;
+        /// Tickers whose legs can't be affirmed nor executed. Ticker -> frozen
+        pub SettlementFrozen get(fn settlement_frozen):
+            map hasher(blake2_128_concat) Ticker => bool;
+        /// Limits on the instructions created for a venue. venue_id -> quota
+        pub VenueQuotas get(fn venue_quota):
+            map hasher(twox_64_concat) VenueId => Option<VenueQuota>;
+        /// Number of instructions of a venue that have been neither executed nor rejected.
+        /// venue_id -> open_instructions
+        pub VenueOpenInstructions get(fn venue_open_instructions):
+            map hasher(twox_64_concat) VenueId => u64;
+        /// Number of instructions of all venues that have been neither executed nor rejected.
+        pub OpenInstructionCount get(fn open_instruction_count): u64;
+        /// Instructions created by a key for a venue in a block. Only tracked for venues that limit
+        /// the number of instructions per block. (venue_id, key) -> (block, instructions)
+        InstructionsCreatedByKey get(fn instructions_created_by_key):
+            double_map hasher(twox_64_concat) VenueId, hasher(twox_64_concat) T::AccountId => (T::BlockNumber, u32);
+        /// Venues under which no new instruction can be created. venue_id -> paused
+        pub PausedVenues get(fn venue_paused):
+            map hasher(twox_64_concat) VenueId => bool;
+        /// Consideration agreed for a leg of an instruction. (instruction_id, leg_id) -> consideration
+        pub InstructionLegConsiderations get(fn instruction_leg_consideration):
+            double_map hasher(twox_64_concat) InstructionId, hasher(twox_64_concat) LegId => Option<LegConsideration>;
+        /// Block ranges during which the execution of instructions is prioritized.
+        pub MarketHoursConfig get(fn market_hours): Option<MarketHours<T::BlockNumber>>;
+        /// Number of instructions of a venue scheduled for execution at a block. (block_number, venue_id) -> count
+        pub ExecutionRounds get(fn execution_rounds):
+            double_map hasher(twox_64_concat) T::BlockNumber, hasher(twox_64_concat) VenueId => u32;
+        /// Tags attached to an instruction at its creation. instruction_id -> tags
+        pub InstructionTags get(fn instruction_tags):
+            map hasher(twox_64_concat) InstructionId => Vec<InstructionTag>;
+        /// Instructions carrying a tag. (tag, instruction_id) -> ()
+        pub TaggedInstructions get(fn tagged_instructions):
+            double_map hasher(blake2_128_concat) InstructionTag, hasher(twox_64_concat) InstructionId => ();
+        /// Progress of the instructions whose legs are executed over several blocks. instruction_id -> progress
+        pub InstructionExecutionProgress get(fn instruction_execution_progress):
+            map hasher(twox_64_concat) InstructionId => Option<ExecutionProgress>;
+        /// Instructions executed or rejected in a block, whose statuses are pruned once past retention.
+        /// (block_number, instruction_id) -> ()
+        SettledInstructions get(fn settled_instructions):
+            double_map hasher(twox_64_concat) T::BlockNumber, hasher(twox_64_concat) InstructionId => ();
+        /// First block whose settled instructions may still have a status. Zero until an instruction is settled.
+        StatusPruneCursor get(fn status_prune_cursor): T::BlockNumber;
+        /// Instructions rejected in a block, whose legs and affirmations are pruned once past retention.
+        /// (block_number, instruction_id) -> ()
+        RejectedInstructions get(fn rejected_instructions):
+            double_map hasher(twox_64_concat) T::BlockNumber, hasher(twox_64_concat) InstructionId => ();
+        /// First block whose rejected instructions may still have legs. Zero until an instruction is rejected.
+        RejectedPruneCursor get(fn rejected_prune_cursor): T::BlockNumber;
+        /// Number of instruction requests in the system. Also the next instruction request id.
+        pub InstructionRequestCounter get(fn instruction_request_counter): InstructionRequestId;
+        /// Instructions proposed by a counterparty and not yet approved by the venue creator.
+        /// request_id -> request
+        pub InstructionRequests get(fn instruction_request):
+            map hasher(twox_64_concat) InstructionRequestId => Option<InstructionRequest<T::Moment, T::BlockNumber>>;
+        /// Pending instruction requests of a venue. (venue_id, request_id) -> ()
+        pub VenueInstructionRequests get(fn venue_instruction_requests):
+            double_map hasher(twox_64_concat) VenueId, hasher(twox_64_concat) InstructionRequestId => ();
+        /// Number of pending instruction requests of a venue. venue_id -> count
+        pub VenueInstructionRequestsCount get(fn venue_instruction_requests_count):
+            map hasher(twox_64_concat) VenueId => u32;
+        /// Identity allowed to affirm instructions, and withdraw affirmations, on behalf of the custodian
+        /// of a portfolio. Removed when the custody of the portfolio changes. portfolio -> agent
+        pub PortfolioSettlementAgents get(fn portfolio_settlement_agent):
+            map hasher(twox_64_concat) PortfolioId => Option<IdentityId>;
+        /// Audit records of executed instructions, pruned along with their statuses. instruction_id -> record
+        pub InstructionAuditRecords get(fn instruction_audit_record):
+            map hasher(twox_64_concat) InstructionId
+                => Option<InstructionAuditRecord<T::AccountId, T::Moment, T::BlockNumber>>;
+        /// Digests of the audit records of executed instructions, pruned along with their statuses.
+        /// instruction_id -> digest
+        pub InstructionAuditDigests get(fn instruction_audit_digest):
+            map hasher(twox_64_concat) InstructionId => Option<[u8; 32]>;
     }
 }
 
@@ -738,10 +1634,24 @@ decl_module! {
             storage_migrate_on!(StorageVersion, 1, {
                 migration::migrate_v1::<T>();
             });
+            storage_migrate_on!(StorageVersion, 2, {
+                migration::migrate_v2::<T>();
+            });
 
             Weight::zero()
         }
 
+        fn on_initialize(now: T::BlockNumber) -> Weight {
+            Self::clear_execution_rounds(now)
+        }
+
+        fn on_idle(now: T::BlockNumber, remaining_weight: Weight) -> Weight {
+            let consumed = Self::prune_instruction_statuses(now, remaining_weight);
+            consumed.saturating_add(
+                Self::prune_rejected_instructions(now, remaining_weight.saturating_sub(consumed))
+            )
+        }
+
         /// Registers a new venue.
         ///
         /// * `details` - Extra details about a venue
@@ -825,7 +1735,7 @@ decl_module! {
             value_date: Option<T::Moment>,
             legs: Vec<Leg>,
         ) {
-            let did = Identity::<T>::ensure_perms(origin)?;
+            let did = Self::ensure_perms_and_key_quota(origin, venue_id)?;
             let legs: Vec<LegV2> = legs.into_iter().map(|leg| leg.into()).collect();
             Self::base_add_instruction(did, venue_id, settlement_type, trade_date, value_date, legs, None, true)?;
         }
@@ -857,7 +1767,7 @@ decl_module! {
             legs: Vec<Leg>,
             portfolios: Vec<PortfolioId>,
         ) -> DispatchResult {
-            let did = Identity::<T>::ensure_perms(origin.clone())?;
+            let did = Self::ensure_perms_and_key_quota(origin.clone(), venue_id)?;
             let legs: Vec<LegV2> = legs.into_iter().map(|leg| leg.into()).collect();
             with_transaction(|| {
                 let portfolios_set = portfolios.into_iter().collect::<BTreeSet<_>>();
@@ -876,7 +1786,8 @@ decl_module! {
         ///
         /// # Permissions
         /// * Portfolio
-        #[weight = <T as Config>::WeightInfo::affirm_instruction(*max_legs_count as u32)]
+        #[weight = <T as Config>::WeightInfo::affirm_instruction(*max_legs_count as u32)
+            .saturating_add(<T as Config>::WeightInfo::transfer_venue_fees(portfolios.len() as u32))]
         pub fn affirm_instruction(origin, id: InstructionId, portfolios: Vec<PortfolioId>, max_legs_count: u32) -> DispatchResult {
             Self::affirm_and_maybe_schedule_instruction(origin, id, portfolios.into_iter(), max_legs_count, None)
         }
@@ -890,14 +1801,15 @@ decl_module! {
         ///
         /// # Permissions
         /// * Portfolio
-        #[weight = <T as Config>::WeightInfo::withdraw_affirmation(*max_legs_count as u32)]
+        #[weight = <T as Config>::WeightInfo::withdraw_affirmation(*max_legs_count as u32)
+            .saturating_add(<T as Config>::WeightInfo::transfer_venue_fees(portfolios.len() as u32))]
         pub fn withdraw_affirmation(origin, id: InstructionId, portfolios: Vec<PortfolioId>, max_legs_count: u32) {
             let (did, secondary_key, details) = Self::ensure_origin_perm_and_instruction_validity(origin, id, false)?;
             let portfolios_set = portfolios.into_iter().collect::<BTreeSet<_>>();
 
             // Withdraw an affirmation.
             Self::unsafe_withdraw_instruction_affirmation(did, id, portfolios_set, secondary_key.as_ref(), max_legs_count, None)?;
-            if details.settlement_type == SettlementType::SettleOnAffirmation {
+            if details.settlement_type.settles_on_affirmation() {
                 // Cancel the scheduled task for the execution of a given instruction.
                 let _ = T::Scheduler::cancel_named(id.execution_name());
             }
@@ -909,12 +1821,17 @@ decl_module! {
         /// * `id` - Instruction id to reject.
         /// * `portfolio` - Portfolio to reject the instruction.
         /// * `num_of_legs` - Number of legs in the instruction.
+        /// * `reason` - Optional reason for the rejection.
+        /// * `memo` - Optional memo detailing the rejection.
         ///
         /// # Permissions
         /// * Portfolio
-        #[weight = <T as Config>::WeightInfo::reject_instruction(*num_of_legs)]
-        pub fn reject_instruction(origin, id: InstructionId, portfolio: PortfolioId, num_of_legs: u32) -> DispatchResult {
-            Self::base_reject_instruction(origin, id, portfolio, num_of_legs, None)
+        #[weight = <T as Config>::WeightInfo::reject_instruction(*num_of_legs)
+            .saturating_add(<T as Config>::WeightInfo::pay_cancellation_fee(*num_of_legs))
+            .saturating_add(<T as Config>::WeightInfo::transfer_venue_fees(num_of_legs.saturating_mul(2)))
+            .saturating_add(<T as Config>::WeightInfo::memo(memo))]
+        pub fn reject_instruction(origin, id: InstructionId, portfolio: PortfolioId, num_of_legs: u32, reason: Option<RejectReason>, memo: Option<Memo>) -> DispatchResult {
+            Self::base_reject_instruction(origin, id, portfolio, num_of_legs, None, reason, memo)
         }
 
         /// Accepts an instruction and claims a signed receipt.
@@ -927,9 +1844,13 @@ decl_module! {
         /// * `signed_data` - Signed receipt.
         /// * `portfolios` - Portfolios that the sender controls and wants to accept this instruction with
         ///
+        /// # Errors
+        /// * `TooManyReceipts` if more than `MAX_RECEIPTS_PER_AFFIRMATION` receipts are provided.
+        ///
         /// # Permissions
         /// * Portfolio
-        #[weight = <T as Config>::WeightInfo::affirm_with_receipts(*max_legs_count as u32).max(<T as Config>::WeightInfo::affirm_instruction(*max_legs_count as u32))]
+        #[weight = <T as Config>::WeightInfo::affirm_with_receipts((receipt_details.len() as u32).max(*max_legs_count)).max(<T as Config>::WeightInfo::affirm_instruction(*max_legs_count as u32))
+            .saturating_add(<T as Config>::WeightInfo::transfer_venue_fees(portfolios.len() as u32))]
         pub fn affirm_with_receipts(origin, id: InstructionId, receipt_details: Vec<ReceiptDetails<T::AccountId, T::OffChainSignature>>, portfolios: Vec<PortfolioId>, max_legs_count: u32) -> DispatchResult {
             Self::affirm_with_receipts_and_maybe_schedule_instruction(origin, id, receipt_details, portfolios, max_legs_count)
         }
@@ -1072,7 +1993,8 @@ decl_module! {
         #[weight = <T as Config>::WeightInfo::add_instruction_with_memo_and_settle_on_block_type(legs.len() as u32)
         .saturating_add(
             <T as Config>::WeightInfo::execute_scheduled_instruction(legs.len() as u32, 0)
-        )]
+        )
+        .saturating_add(<T as Config>::WeightInfo::memo(instruction_memo))]
         pub fn add_instruction_with_memo(
             origin,
             venue_id: VenueId,
@@ -1080,9 +2002,9 @@ decl_module! {
             trade_date: Option<T::Moment>,
             value_date: Option<T::Moment>,
             legs: Vec<Leg>,
-            instruction_memo: Option<InstructionMemo>,
+            instruction_memo: Option<Memo>,
         ) {
-            let did = Identity::<T>::ensure_perms(origin)?;
+            let did = Self::ensure_perms_and_key_quota(origin, venue_id)?;
             let legs: Vec<LegV2> = legs.into_iter().map(|leg| leg.into()).collect();
             Self::base_add_instruction(did, venue_id, settlement_type, trade_date, value_date, legs, instruction_memo, true)?;
         }
@@ -1104,7 +2026,8 @@ decl_module! {
         #[weight = <T as Config>::WeightInfo::add_and_affirm_instruction_with_memo_and_settle_on_block_type(legs.len() as u32)
         .saturating_add(
             <T as Config>::WeightInfo::execute_scheduled_instruction(legs.len() as u32, 0)
-        )]
+        )
+        .saturating_add(<T as Config>::WeightInfo::memo(instruction_memo))]
         pub fn add_and_affirm_instruction_with_memo(
             origin,
             venue_id: VenueId,
@@ -1113,9 +2036,9 @@ decl_module! {
             value_date: Option<T::Moment>,
             legs: Vec<Leg>,
             portfolios: Vec<PortfolioId>,
-            instruction_memo: Option<InstructionMemo>,
+            instruction_memo: Option<Memo>,
         ) -> DispatchResult {
-            let did = Identity::<T>::ensure_perms(origin.clone())?;
+            let did = Self::ensure_perms_and_key_quota(origin.clone(), venue_id)?;
             let legs: Vec<LegV2> = legs.into_iter().map(|leg| leg.into()).collect();
             with_transaction(|| {
                 let portfolios_set = portfolios.into_iter().collect::<BTreeSet<_>>();
@@ -1133,7 +2056,8 @@ decl_module! {
         ///
         /// # Errors
         /// * `InstructionNotFailed` - Instruction not in a failed state or does not exist.
-        #[weight = <T as Config>::WeightInfo::execute_manual_instruction(*legs_count)]
+        #[weight = <T as Config>::WeightInfo::execute_manual_instruction(*legs_count)
+            .saturating_add(<T as Config>::WeightInfo::transfer_venue_fees(legs_count.saturating_mul(2)))]
         pub fn execute_manual_instruction(origin, id: InstructionId, legs_count: u32, portfolio: Option<PortfolioId>) {
             // check origin has the permissions required and valid instruction
             let (did, sk, instruction_details) = Self::ensure_origin_perm_and_instruction_validity(origin, id, true)?;
@@ -1150,8 +2074,8 @@ decl_module! {
                     );
                 }
                 None => {
-                    // Ensure venue exists & sender is its creator.
-                    Self::venue_for_management(instruction_details.venue_id, did)?;
+                    // Ensure sender is the venue creator or the instruction operator.
+                    Self::ensure_instruction_manager(id, instruction_details.venue_id, did)?;
                 }
             }
 
@@ -1180,6 +2104,7 @@ decl_module! {
         #[weight =
             <T as Config>::WeightInfo::add_instruction_with_memo_v2(legs.len() as u32)
             .saturating_add( <T as Config>::WeightInfo::execute_scheduled_instruction_v2(legs))
+            .saturating_add(<T as Config>::WeightInfo::memo(instruction_memo))
         ]
         pub fn add_instruction_with_memo_v2(
             origin,
@@ -1188,9 +2113,9 @@ decl_module! {
             trade_date: Option<T::Moment>,
             value_date: Option<T::Moment>,
             legs: Vec<LegV2>,
-            instruction_memo: Option<InstructionMemo>,
+            instruction_memo: Option<Memo>,
         ) {
-            let did = Identity::<T>::ensure_perms(origin)?;
+            let did = Self::ensure_perms_and_key_quota(origin, venue_id)?;
             Self::base_add_instruction(did, venue_id, settlement_type, trade_date, value_date, legs, instruction_memo, false)?;
         }
 
@@ -1211,6 +2136,7 @@ decl_module! {
         #[weight =
             <T as Config>::WeightInfo::add_and_affirm_instruction_with_memo_v2_legs(legs)
             .saturating_add( <T as Config>::WeightInfo::execute_scheduled_instruction_v2(legs))
+            .saturating_add(<T as Config>::WeightInfo::memo(instruction_memo))
         ]
         pub fn add_and_affirm_instruction_with_memo_v2(
             origin,
@@ -1220,9 +2146,9 @@ decl_module! {
             value_date: Option<T::Moment>,
             legs: Vec<LegV2>,
             portfolios: Vec<PortfolioId>,
-            instruction_memo: Option<InstructionMemo>,
+            instruction_memo: Option<Memo>,
         ) -> DispatchResult {
-            let did = Identity::<T>::ensure_perms(origin.clone())?;
+            let did = Self::ensure_perms_and_key_quota(origin.clone(), venue_id)?;
             with_transaction(|| {
                 let portfolios_set = portfolios.into_iter().collect::<BTreeSet<_>>();
                 let (fungible_transfers, nfts_transfers) = get_transfer_by_asset(&legs);
@@ -1247,7 +2173,8 @@ decl_module! {
         ///
         /// # Permissions
         /// * Portfolio
-        #[weight = <T as Config>::WeightInfo::affirm_instruction_v2(*fungible_transfers, *nfts_transfers)]
+        #[weight = <T as Config>::WeightInfo::affirm_instruction_v2(*fungible_transfers, *nfts_transfers)
+            .saturating_add(<T as Config>::WeightInfo::transfer_venue_fees(portfolios.len() as u32))]
         pub fn affirm_instruction_v2(origin, id: InstructionId, portfolios: Vec<PortfolioId>, fungible_transfers: u32, nfts_transfers: u32) -> DispatchResult {
             Self::affirm_and_maybe_schedule_instruction(
                 origin, id, portfolios.into_iter(),
@@ -1266,14 +2193,15 @@ decl_module! {
         ///
         /// # Permissions
         /// * Portfolio
-        #[weight = <T as Config>::WeightInfo::withdraw_affirmation_v2(*fungible_transfers, *nfts_transfers)]
+        #[weight = <T as Config>::WeightInfo::withdraw_affirmation_v2(*fungible_transfers, *nfts_transfers)
+            .saturating_add(<T as Config>::WeightInfo::transfer_venue_fees(portfolios.len() as u32))]
         pub fn withdraw_affirmation_v2(origin, id: InstructionId, portfolios: Vec<PortfolioId>, fungible_transfers: u32, nfts_transfers: u32) -> DispatchResult {
             let (did, secondary_key, details) = Self::ensure_origin_perm_and_instruction_validity(origin, id, false)?;
             let portfolios_set = portfolios.into_iter().collect::<BTreeSet<_>>();
 
             // Withdraw an affirmation.
             Self::unsafe_withdraw_instruction_affirmation(did, id, portfolios_set, secondary_key.as_ref(), fungible_transfers, Some(nfts_transfers))?;
-            if details.settlement_type == SettlementType::SettleOnAffirmation {
+            if details.settlement_type.settles_on_affirmation() {
                 // Cancel the scheduled task for the execution of a given instruction.
                 let _fix_this = T::Scheduler::cancel_named(id.execution_name());
             }
@@ -1281,1032 +2209,3794 @@ decl_module! {
         }
 
         /// Rejects an existing instruction.
+        /// If some legs were already executed in previous blocks, only the locks of the other legs are released.
         ///
         /// # Arguments
         /// * `id` - Instruction id to reject.
         /// * `portfolio` - Portfolio to reject the instruction.
         /// * `fungible_transfers` - number of fungible transfers in the instruction.
         /// * `nfts_transfers` - total number of NFTs being transferred in the instruction.
+        /// * `reason` - Optional reason for the rejection.
+        /// * `memo` - Optional memo detailing the rejection.
         ///
         /// # Permissions
         /// * Portfolio
-        #[weight = <T as Config>::WeightInfo::reject_instruction_v2(*fungible_transfers, *nfts_transfers)]
-        pub fn reject_instruction_v2(origin, id: InstructionId, portfolio: PortfolioId, fungible_transfers: u32, nfts_transfers: u32) -> DispatchResult {
-            Self::base_reject_instruction(origin, id, portfolio, fungible_transfers, Some(nfts_transfers))
+        #[weight = <T as Config>::WeightInfo::reject_instruction_v2(*fungible_transfers, *nfts_transfers)
+            .saturating_add(<T as Config>::WeightInfo::pay_cancellation_fee(fungible_transfers.saturating_add(*nfts_transfers)))
+            .saturating_add(<T as Config>::WeightInfo::transfer_venue_fees(fungible_transfers.saturating_add(*nfts_transfers).saturating_mul(2)))
+            .saturating_add(<T as Config>::WeightInfo::memo(memo))]
+        pub fn reject_instruction_v2(origin, id: InstructionId, portfolio: PortfolioId, fungible_transfers: u32, nfts_transfers: u32, reason: Option<RejectReason>, memo: Option<Memo>) -> DispatchResult {
+            Self::base_reject_instruction(origin, id, portfolio, fungible_transfers, Some(nfts_transfers), reason, memo)
         }
 
         /// Root callable extrinsic, used as an internal call to execute a scheduled settlement instruction.
+        /// For instructions allowing partial execution, the transfers only cover the legs executed in the block.
         #[weight = <T as Config>::WeightInfo::execute_scheduled_instruction(*_fungible_transfers, *_nfts_transfers)]
         fn execute_scheduled_instruction_v2(origin, id: InstructionId, _fungible_transfers: u32, _nfts_transfers: u32) {
             ensure_root(origin)?;
             Self::base_execute_scheduled_instruction(id);
         }
-    }
-}
 
-impl<T: Config> Module<T> {
-    fn lock_via_leg(leg: &LegV2) -> DispatchResult {
-        match &leg.asset {
-            LegAsset::Fungible { ticker, amount } => {
-                T::Portfolio::lock_tokens(&leg.from, &ticker, *amount)
-            }
-            LegAsset::NonFungible(nfts) => with_transaction(|| {
-                for nft_id in nfts.ids() {
-                    T::Portfolio::lock_nft(&leg.from, nfts.ticker(), &nft_id)?;
-                }
-                Ok(())
-            }),
+        /// Edits the legs of a pending instruction that no party has affirmed yet.
+        ///
+        /// Removed legs keep their ids and new legs always get fresh ones, so a `LegId` is never reused
+        /// within an instruction. Each edit bumps the instruction's revision, which lets parties find
+        /// the legs that changed since they last reviewed it (see `instruction_leg_history`).
+        ///
+        /// # Arguments
+        /// * `id` - Instruction id to edit.
+        /// * `removed_legs` - Ids of the legs to remove from the instruction.
+        /// * `new_legs` - Legs to add to the instruction.
+        /// * `legs_count` - Number of legs currently in the instruction.
+        ///
+        /// # Permissions
+        /// * Venue creator
+        #[weight = <T as Config>::WeightInfo::edit_instruction_legs(legs_count.saturating_add(new_legs.len() as u32))]
+        pub fn edit_instruction_legs(
+            origin,
+            id: InstructionId,
+            removed_legs: Vec<LegId>,
+            new_legs: Vec<LegV2>,
+            legs_count: u32,
+        ) -> DispatchResult {
+            let did = Identity::<T>::ensure_perms(origin)?;
+            Self::base_edit_instruction_legs(did, id, removed_legs, new_legs, legs_count)
         }
-    }
 
-    fn unlock_via_leg(leg: &LegV2) -> DispatchResult {
-        match &leg.asset {
-            LegAsset::Fungible { ticker, amount } => {
-                T::Portfolio::unlock_tokens(&leg.from, &ticker, *amount)
-            }
-            LegAsset::NonFungible(nfts) => with_transaction(|| {
-                for nft_id in nfts.ids() {
-                    T::Portfolio::unlock_nft(&leg.from, nfts.ticker(), &nft_id)?;
-                }
-                Ok(())
-            }),
+        /// Sets the identities allowed to receive the decryption key of an encrypted instruction
+        /// memo. The instruction memo is then a commitment to the encrypted memo, which is kept
+        /// off-chain. Wrapped keys of identities removed from the list are deleted.
+        /// The access list and the wrapped keys are deleted once the instruction is pruned.
+        ///
+        /// # Arguments
+        /// * `id` - Instruction id.
+        /// * `dids` - Identities allowed to read the memo, at most `MAX_MEMO_READERS`.
+        ///
+        /// # Permissions
+        /// * Venue creator
+        #[weight = <T as Config>::WeightInfo::set_memo_access_list(dids.len() as u32)]
+        pub fn set_memo_access_list(origin, id: InstructionId, dids: Vec<IdentityId>) -> DispatchResult {
+            let did = Identity::<T>::ensure_perms(origin)?;
+            Self::base_set_memo_access_list(did, id, dids)
         }
-    }
 
-    /// Ensure origin call permission and the given instruction validity.
-    fn ensure_origin_perm_and_instruction_validity(
-        origin: <T as frame_system::Config>::RuntimeOrigin,
-        id: InstructionId,
-        is_execute: bool,
-    ) -> EnsureValidInstructionResult<T::AccountId, T::Moment, T::BlockNumber> {
-        let PermissionedCallOriginData {
-            primary_did,
-            secondary_key,
-            ..
-        } = Identity::<T>::ensure_origin_call_permissions(origin)?;
-        Ok((
-            primary_did,
-            secondary_key,
-            Self::ensure_instruction_validity(id, is_execute)?,
-        ))
-    }
+        /// Records the memo decryption key of an instruction wrapped for identities of its
+        /// memo access list.
+        ///
+        /// # Arguments
+        /// * `id` - Instruction id.
+        /// * `wrapped_keys` - Pairs of identity and the memo key wrapped for that identity.
+        ///
+        /// # Permissions
+        /// * Venue creator
+        #[weight = <T as Config>::WeightInfo::wrap_memo_keys(wrapped_keys.len() as u32)]
+        pub fn wrap_memo_keys(
+            origin,
+            id: InstructionId,
+            wrapped_keys: Vec<(IdentityId, WrappedMemoKey)>
+        ) -> DispatchResult {
+            let did = Identity::<T>::ensure_perms(origin)?;
+            Self::base_wrap_memo_keys(did, id, wrapped_keys)
+        }
 
-    // Extract `Venue` with `id`, assuming it was created by `did`, or error.
-    fn venue_for_management(id: VenueId, did: IdentityId) -> Result<Venue, DispatchError> {
-        // Ensure venue exists & that DID created it.
-        let venue = Self::venue_info(id).ok_or(Error::<T>::InvalidVenue)?;
-        ensure!(venue.creator == did, Error::<T>::Unauthorized);
-        Ok(venue)
-    }
+        /// Sets the number of blocks after which new instructions of a venue expire
+        /// if they have not collected all affirmations. Expired instructions are pruned
+        /// and their locked assets released. Existing instructions are not affected.
+        ///
+        /// # Arguments
+        /// * `id` - Venue id.
+        /// * `expiry` - Number of blocks, or `None` for instructions that never expire.
+        ///
+        /// # Permissions
+        /// * Venue creator
+        #[weight = <T as Config>::WeightInfo::set_venue_default_expiry()]
+        pub fn set_venue_default_expiry(origin, id: VenueId, expiry: Option<T::BlockNumber>) -> DispatchResult {
+            let did = Identity::<T>::ensure_perms(origin)?;
+            Self::venue_for_management(id, did)?;
+            ensure!(expiry.map_or(true, |e| !e.is_zero()), Error::<T>::InvalidExpiry);
 
-    pub fn base_add_instruction(
-        did: IdentityId,
-        venue_id: VenueId,
-        settlement_type: SettlementType<T::BlockNumber>,
-        trade_date: Option<T::Moment>,
-        value_date: Option<T::Moment>,
-        legs: Vec<LegV2>,
-        memo: Option<InstructionMemo>,
-        emit_deprecated_event: bool,
-    ) -> Result<InstructionId, DispatchError> {
-        // Verifies if the block number is in the future so that `T::Scheduler::schedule_named` doesn't fail.
-        if let SettlementType::SettleOnBlock(block_number) = &settlement_type {
-            ensure!(
-                *block_number > System::<T>::block_number(),
-                Error::<T>::SettleOnPastBlock
-            );
+            VenueDefaultExpiry::<T>::mutate(id, |e| *e = expiry);
+            Self::deposit_event(RawEvent::VenueDefaultExpirySet(did, id, expiry));
+            Ok(())
         }
 
-        // Ensure that instruction dates are valid.
-        if let (Some(trade_date), Some(value_date)) = (trade_date, value_date) {
-            ensure!(
-                value_date >= trade_date,
-                Error::<T>::InstructionDatesInvalid
+        /// Root callable extrinsic, used as an internal call to expire a settlement instruction.
+        #[weight = <T as Config>::WeightInfo::expire_instruction(*_fungible_transfers, *_nfts_transfers)]
+        fn expire_instruction(origin, id: InstructionId, _fungible_transfers: u32, _nfts_transfers: u32) {
+            ensure_root(origin)?;
+            Self::base_expire_instruction(id);
+        }
+
+        /// Sets the POLYX fee charged to a party that rejects an instruction of the venue after
+        /// having affirmed it. The fee is split equally among the other counterparties that have
+        /// a primary key. The fee of an instruction is fixed at its creation.
+        ///
+        /// # Arguments
+        /// * `id` - Venue id.
+        /// * `fee` - Cancellation fee, or `None` to disable cancellation fees.
+        ///
+        /// # Permissions
+        /// * Venue creator
+        #[weight = <T as Config>::WeightInfo::set_venue_cancellation_fee()]
+        pub fn set_venue_cancellation_fee(origin, id: VenueId, fee: Option<Balance>) -> DispatchResult {
+            let did = Identity::<T>::ensure_perms(origin)?;
+            Self::venue_for_management(id, did)?;
+
+            let fee = fee.filter(|fee| *fee > 0);
+            VenueCancellationFee::mutate(id, |f| *f = fee);
+            Self::deposit_event(RawEvent::VenueCancellationFeeSet(did, id, fee));
+            Ok(())
+        }
+
+        /// Provides affirmation to several existing instructions at once.
+        /// Either all instructions are affirmed or none are.
+        ///
+        /// # Arguments
+        /// * `ids` - Instruction ids to affirm.
+        /// * `portfolios` - Portfolios that the sender controls and wants to affirm the instructions.
+        /// * `fungible_transfers` - maximum number of fungible transfers in any of the instructions.
+        /// * `nfts_transfers` - maximum number of NFTs being transferred in any of the instructions.
+        ///
+        /// # Permissions
+        /// * Portfolio
+        #[weight = <T as Config>::WeightInfo::affirm_instruction_v2(*fungible_transfers, *nfts_transfers)
+            .saturating_add(<T as Config>::WeightInfo::transfer_venue_fees(portfolios.len() as u32))
+            .saturating_mul(ids.len() as u64)]
+        pub fn affirm_instructions(origin, ids: Vec<InstructionId>, portfolios: Vec<PortfolioId>, fungible_transfers: u32, nfts_transfers: u32) -> DispatchResult {
+            with_transaction(|| {
+                for id in ids {
+                    Self::affirm_and_maybe_schedule_instruction(
+                        origin.clone(), id, portfolios.iter().copied(),
+                        fungible_transfers,
+                        Some(nfts_transfers)
+                    )?;
+                }
+                Ok(())
+            })
+        }
+
+        /// Adds a new instruction that, besides its counterparties, must be affirmed by `mediators`
+        /// before being executed. Mediators do not need to be part of any leg.
+        ///
+        /// # Arguments
+        /// * `venue_id` - ID of the venue this instruction belongs to.
+        /// * `settlement_type` - Defines if the instruction should be settled
+        ///    in the next block after receiving all affirmations or waiting till a specific block.
+        /// * `trade_date` - Optional date from which people can interact with this instruction.
+        /// * `value_date` - Optional date after which the instruction should be settled (not enforced)
+        /// * `legs` - Legs included in this instruction.
+        /// * `memo` - Memo field for this instruction.
+        /// * `mediators` - Identities whose affirmation is required, at most `MAX_INSTRUCTION_MEDIATORS`.
+        #[weight =
+            <T as Config>::WeightInfo::add_instruction_with_memo_v2(legs.len() as u32)
+            .saturating_add(<T as Config>::WeightInfo::add_mediators(mediators.len() as u32))
+            .saturating_add(<T as Config>::WeightInfo::execute_scheduled_instruction_v2(legs))
+            .saturating_add(<T as Config>::WeightInfo::memo(instruction_memo))
+        ]
+        pub fn add_instruction_with_mediators(
+            origin,
+            venue_id: VenueId,
+            settlement_type: SettlementType<T::BlockNumber>,
+            trade_date: Option<T::Moment>,
+            value_date: Option<T::Moment>,
+            legs: Vec<LegV2>,
+            instruction_memo: Option<Memo>,
+            mediators: BTreeSet<IdentityId>,
+        ) -> DispatchResult {
+            let did = Self::ensure_perms_and_key_quota(origin, venue_id)?;
+            with_transaction(|| {
+                let instruction_id = Self::base_add_instruction(did, venue_id, settlement_type, trade_date, value_date, legs, instruction_memo, false)?;
+                Self::add_mediators(did, instruction_id, mediators)
+            })
+        }
+
+        /// Provides the affirmation of a mediator to an existing instruction.
+        ///
+        /// # Arguments
+        /// * `id` - Instruction id to affirm.
+        /// * `fungible_transfers` - number of fungible transfers in the instruction.
+        /// * `nfts_transfers` - total number of NFTs being transferred in the instruction.
+        ///
+        /// # Errors
+        /// * `AssetSettlementFrozen` if the settlement of an asset of the instruction is frozen.
+        ///
+        /// # Permissions
+        /// * Mediator of the instruction
+        #[weight = <T as Config>::WeightInfo::affirm_instruction_as_mediator(*fungible_transfers, *nfts_transfers)]
+        pub fn affirm_instruction_as_mediator(origin, id: InstructionId, fungible_transfers: u32, nfts_transfers: u32) -> DispatchResult {
+            let did = Identity::<T>::ensure_perms(origin)?;
+            Self::base_affirm_instruction_as_mediator(did, id, fungible_transfers, nfts_transfers)
+        }
+
+        /// Withdraws the affirmation of a mediator to a given instruction.
+        ///
+        /// # Arguments
+        /// * `id` - Instruction id for that affirmation get withdrawn.
+        ///
+        /// # Permissions
+        /// * Mediator of the instruction
+        #[weight = <T as Config>::WeightInfo::withdraw_affirmation_as_mediator()]
+        pub fn withdraw_affirmation_as_mediator(origin, id: InstructionId) -> DispatchResult {
+            let did = Identity::<T>::ensure_perms(origin)?;
+            Self::base_withdraw_affirmation_as_mediator(did, id)
+        }
+
+        /// Delegates the management of an instruction to `operator`, who can then execute it manually
+        /// and reject it on behalf of the venue creator.
+        ///
+        /// # Arguments
+        /// * `id` - Instruction id.
+        /// * `operator` - Identity managing the instruction, or `None` to remove the current operator.
+        ///
+        /// # Permissions
+        /// * Venue creator
+        #[weight = <T as Config>::WeightInfo::set_instruction_operator()]
+        pub fn set_instruction_operator(origin, id: InstructionId, operator: Option<IdentityId>) -> DispatchResult {
+            let did = Identity::<T>::ensure_perms(origin)?;
+            Self::base_set_instruction_operator(did, id, operator)
+        }
+
+        /// Rejects an existing instruction on behalf of its creator.
+        /// Unlike `reject_instruction`, no cancellation fee is charged.
+        /// If some legs were already executed in previous blocks, only the locks of the other legs are released.
+        ///
+        /// # Arguments
+        /// * `id` - Instruction id to reject.
+        /// * `fungible_transfers` - number of fungible transfers in the instruction.
+        /// * `nfts_transfers` - total number of NFTs being transferred in the instruction.
+        /// * `reason` - Optional reason for the rejection.
+        /// * `memo` - Optional memo detailing the rejection.
+        ///
+        /// # Permissions
+        /// * Venue creator or instruction operator
+        #[weight = <T as Config>::WeightInfo::reject_instruction_v2(*fungible_transfers, *nfts_transfers)
+            .saturating_add(<T as Config>::WeightInfo::memo(memo))]
+        pub fn reject_instruction_as_operator(origin, id: InstructionId, fungible_transfers: u32, nfts_transfers: u32, reason: Option<RejectReason>, memo: Option<Memo>) -> DispatchResult {
+            let did = Identity::<T>::ensure_perms(origin)?;
+            Self::base_reject_instruction_as_operator(did, id, fungible_transfers, nfts_transfers, reason, memo)
+        }
+
+        /// Sets the POLYX fee charged to each portfolio affirming an instruction of the venue.
+        /// The fee is held in escrow and paid to the venue creator when the instruction settles,
+        /// or refunded if the affirmation is withdrawn or the instruction rejected.
+        /// The fee of an instruction is fixed at its creation.
+        ///
+        /// # Arguments
+        /// * `id` - Venue id.
+        /// * `fee` - Venue fee, or `None` to disable venue fees.
+        ///
+        /// # Permissions
+        /// * Venue creator
+        #[weight = <T as Config>::WeightInfo::set_venue_fee()]
+        pub fn set_venue_fee(origin, id: VenueId, fee: Option<VenueFee>) -> DispatchResult {
+            let did = Identity::<T>::ensure_perms(origin)?;
+            Self::venue_for_management(id, did)?;
+
+            let fee = fee.filter(|fee| !fee.is_zero());
+            VenueFees::mutate(id, |f| *f = fee);
+            Self::deposit_event(RawEvent::VenueFeeSet(did, id, fee));
+            Ok(())
+        }
+
+        /// Sets the number of venue signers that must each sign the receipt of a leg
+        /// for the receipt to be accepted by `affirm_with_receipts`.
+        ///
+        /// # Arguments
+        /// * `id` - Venue id.
+        /// * `threshold` - Number of distinct venue signers required per receipt.
+        ///
+        /// # Errors
+        /// * `InvalidReceiptSignatories` if `threshold` is zero or above the number of venue signers.
+        ///
+        /// # Permissions
+        /// * Venue creator
+        #[weight = <T as Config>::WeightInfo::set_receipt_signatories(*threshold)]
+        pub fn set_receipt_signatories(origin, id: VenueId, threshold: u32) -> DispatchResult {
+            let did = Identity::<T>::ensure_perms(origin)?;
+            Self::venue_for_management(id, did)?;
+            let threshold_len = threshold as usize;
+            ensure!(
+                threshold > 0 && <VenueSigners<T>>::iter_prefix(id).take(threshold_len).count() == threshold_len,
+                Error::<T>::InvalidReceiptSignatories
+            );
+
+            ReceiptSignatories::insert(id, threshold);
+            Self::deposit_event(RawEvent::ReceiptSignatoriesSet(did, id, threshold));
+            Ok(())
+        }
+
+        /// Sets the constraints on the metadata of the receipts that `affirm_with_receipts`
+        /// accepts for the instructions of a venue.
+        ///
+        /// # Arguments
+        /// * `id` - Venue id.
+        /// * `schema` - Receipt metadata schema, or `None` to accept any metadata.
+        ///
+        /// # Errors
+        /// * `InvalidReceiptMetadataSchema` if the required prefix is longer than the maximum length.
+        ///
+        /// # Permissions
+        /// * Venue creator
+        #[weight = <T as Config>::WeightInfo::set_receipt_metadata_schema()]
+        pub fn set_receipt_metadata_schema(origin, id: VenueId, schema: Option<ReceiptMetadataSchema>) -> DispatchResult {
+            let did = Identity::<T>::ensure_perms(origin)?;
+            Self::venue_for_management(id, did)?;
+            if let Some(schema) = &schema {
+                ensure_string_limited::<T>(&schema.required_prefix)?;
+                ensure!(
+                    schema.required_prefix.len() <= schema.max_length as usize,
+                    Error::<T>::InvalidReceiptMetadataSchema
+                );
+            }
+
+            ReceiptMetadataSchemas::mutate(id, |s| *s = schema.clone());
+            Self::deposit_event(RawEvent::ReceiptMetadataSchemaSet(did, id, schema));
+            Ok(())
+        }
+
+        /// Enables or disables the netting of offsetting legs for the instructions of a venue.
+        ///
+        /// When enabled, the pending fungible legs of the same ticker between the same two portfolios
+        /// are collapsed into a single transfer of their net amount when an instruction is executed.
+        /// Instructions that allow partial execution are never netted.
+        ///
+        /// # Arguments
+        /// * `id` - Venue id.
+        /// * `enabled` - Whether the offsetting legs of the venue's instructions are netted.
+        ///
+        /// # Permissions
+        /// * Venue creator
+        #[weight = <T as Config>::WeightInfo::set_venue_netting()]
+        pub fn set_venue_netting(origin, id: VenueId, enabled: bool) -> DispatchResult {
+            let did = Identity::<T>::ensure_perms(origin)?;
+            Self::venue_for_management(id, did)?;
+
+            VenueNetting::insert(id, enabled);
+            Self::deposit_event(RawEvent::VenueNettingSet(did, id, enabled));
+            Ok(())
+        }
+
+        /// Sets the limits on the instructions created for a venue. Instructions that are already
+        /// open are not affected, but no new instruction can be created while the venue is at its quota.
+        ///
+        /// # Arguments
+        /// * `id` - Venue id.
+        /// * `quota` - Venue quota, or `None` to remove all limits.
+        ///
+        /// # Permissions
+        /// * Venue creator
+        #[weight = <T as Config>::WeightInfo::set_venue_quota()]
+        pub fn set_venue_quota(origin, id: VenueId, quota: Option<VenueQuota>) -> DispatchResult {
+            let did = Identity::<T>::ensure_perms(origin)?;
+            Self::venue_for_management(id, did)?;
+
+            let quota = quota.filter(|quota| !quota.is_unlimited());
+            VenueQuotas::mutate(id, |q| *q = quota);
+            Self::deposit_event(RawEvent::VenueQuotaSet(did, id, quota));
+            Ok(())
+        }
+
+        /// Pauses a venue: no new instruction can be created under it, while its existing
+        /// instructions can still be affirmed, withdrawn, executed or rejected.
+        ///
+        /// # Arguments
+        /// * `id` - Venue id.
+        ///
+        /// # Permissions
+        /// * Venue creator
+        #[weight = <T as Config>::WeightInfo::pause_venue()]
+        pub fn pause_venue(origin, id: VenueId) -> DispatchResult {
+            let did = Identity::<T>::ensure_perms(origin)?;
+            Self::venue_for_management(id, did)?;
+            ensure!(!Self::venue_paused(id), Error::<T>::VenuePaused);
+
+            PausedVenues::insert(id, true);
+            Self::deposit_event(RawEvent::VenuePaused(did, id));
+            Ok(())
+        }
+
+        /// Resumes a venue paused by `pause_venue`.
+        ///
+        /// # Arguments
+        /// * `id` - Venue id.
+        ///
+        /// # Permissions
+        /// * Venue creator
+        #[weight = <T as Config>::WeightInfo::resume_venue()]
+        pub fn resume_venue(origin, id: VenueId) -> DispatchResult {
+            let did = Identity::<T>::ensure_perms(origin)?;
+            Self::venue_for_management(id, did)?;
+            ensure!(Self::venue_paused(id), Error::<T>::VenueNotPaused);
+
+            PausedVenues::remove(id);
+            Self::deposit_event(RawEvent::VenueResumed(did, id));
+            Ok(())
+        }
+
+        /// Closes a venue that has no open instructions, removing it, all of its settings, its pending instruction requests
+        /// and the list of its instructions.
+        /// Pausing the venue first prevents new instructions from being created while the
+        /// existing ones settle.
+        ///
+        /// # Arguments
+        /// * `id` - Venue id.
+        /// * `signers_count` - Number of signers of the venue.
+        /// * `instructions_count` - Number of instructions of the venue that haven't been pruned.
+        ///
+        /// # Permissions
+        /// * Venue creator
+        #[weight = <T as Config>::WeightInfo::close_venue(*signers_count, *instructions_count)]
+        pub fn close_venue(origin, id: VenueId, signers_count: u32, instructions_count: u32) -> DispatchResult {
+            let did = Identity::<T>::ensure_perms(origin)?;
+            Self::base_close_venue(did, id, signers_count, instructions_count)
+        }
+
+        /// Creates a recurring instruction, from which a new instruction with the same `legs`
+        /// is generated every `period` blocks until `stop` is reached. The first instruction is
+        /// generated `period` blocks from now. Generated instructions settle on affirmation.
+        /// If an occurrence can't be scheduled, the recurring instruction ends.
+        ///
+        /// # Arguments
+        /// * `venue_id` - ID of the venue of the generated instructions.
+        /// * `legs` - Legs of each generated instruction.
+        /// * `memo` - Memo of each generated instruction.
+        /// * `period` - Number of blocks between two generated instructions.
+        /// * `stop` - When to stop generating instructions.
+        ///
+        /// # Permissions
+        /// * Venue creator
+        #[weight = <T as Config>::WeightInfo::create_recurring_instruction(legs.len() as u32)
+            .saturating_add(<T as Config>::WeightInfo::memo(memo))]
+        pub fn create_recurring_instruction(
+            origin,
+            venue_id: VenueId,
+            legs: Vec<LegV2>,
+            memo: Option<Memo>,
+            period: T::BlockNumber,
+            stop: RecurrenceStop<T::BlockNumber>,
+        ) -> DispatchResult {
+            let did = Identity::<T>::ensure_perms(origin)?;
+            Self::base_create_recurring_instruction(did, venue_id, legs, memo, period, stop)
+        }
+
+        /// Cancels a recurring instruction. Instructions already generated from it are not affected.
+        ///
+        /// # Arguments
+        /// * `id` - Recurring instruction id.
+        ///
+        /// # Permissions
+        /// * Creator of the recurring instruction
+        #[weight = <T as Config>::WeightInfo::cancel_recurring_instruction()]
+        pub fn cancel_recurring_instruction(origin, id: RecurringInstructionId) -> DispatchResult {
+            let did = Identity::<T>::ensure_perms(origin)?;
+            let recurring = Self::recurring_instruction(id).ok_or(Error::<T>::UnknownRecurringInstruction)?;
+            ensure!(recurring.creator == did, Error::<T>::Unauthorized);
+
+            let _ = T::Scheduler::cancel_named(id.schedule_name(recurring.occurrences));
+            RecurringInstructions::<T>::remove(id);
+            Self::deposit_event(RawEvent::RecurringInstructionEnded(did, id));
+            Ok(())
+        }
+
+        /// Freezes the settlement of an asset: legs involving `ticker` can no longer be affirmed,
+        /// and instructions with such legs fail to execute. Tokens already locked by
+        /// affirmations are not released. Unlike freezing the asset, this doesn't block
+        /// minting nor corporate actions.
+        ///
+        /// # Arguments
+        /// * `ticker` - Ticker of the token in question.
+        ///
+        /// # Permissions
+        /// * Asset
+        #[weight = <T as Config>::WeightInfo::freeze_settlement()]
+        pub fn freeze_settlement(origin, ticker: Ticker) -> DispatchResult {
+            let did = <ExternalAgents<T>>::ensure_perms(origin, ticker)?;
+            ensure!(!Self::settlement_frozen(ticker), Error::<T>::SettlementAlreadyFrozen);
+
+            SettlementFrozen::insert(ticker, true);
+            Self::deposit_event(RawEvent::SettlementFrozen(did, ticker));
+            Ok(())
+        }
+
+        /// Unfreezes the settlement of an asset frozen by `freeze_settlement`.
+        ///
+        /// # Arguments
+        /// * `ticker` - Ticker of the token in question.
+        ///
+        /// # Permissions
+        /// * Asset
+        #[weight = <T as Config>::WeightInfo::unfreeze_settlement()]
+        pub fn unfreeze_settlement(origin, ticker: Ticker) -> DispatchResult {
+            let did = <ExternalAgents<T>>::ensure_perms(origin, ticker)?;
+            ensure!(Self::settlement_frozen(ticker), Error::<T>::SettlementNotFrozen);
+
+            SettlementFrozen::remove(ticker);
+            Self::deposit_event(RawEvent::SettlementUnfrozen(did, ticker));
+            Ok(())
+        }
+
+        /// Replaces the leg `leg_id` of a pending instruction that no counterparty has affirmed yet,
+        /// e.g. to fix a wrong amount without rejecting and recreating the whole instruction.
+        ///
+        /// This is an edit of the instruction replacing `leg_id` by a leg with a fresh id, see
+        /// `edit_instruction_legs`. Mediators that already affirmed the instruction have to
+        /// affirm it again.
+        ///
+        /// # Arguments
+        /// * `id` - Instruction id to amend.
+        /// * `leg_id` - Id of the leg to replace.
+        /// * `new_leg` - Leg replacing the current one.
+        /// * `legs_count` - Number of legs currently in the instruction.
+        ///
+        /// # Permissions
+        /// * Venue creator
+        #[weight = <T as Config>::WeightInfo::amend_instruction_leg(*legs_count)]
+        pub fn amend_instruction_leg(
+            origin,
+            id: InstructionId,
+            leg_id: LegId,
+            new_leg: LegV2,
+            legs_count: u32,
+        ) -> DispatchResult {
+            let did = Identity::<T>::ensure_perms(origin)?;
+            Self::base_amend_instruction_leg(did, id, leg_id, new_leg, legs_count)
+        }
+
+        /// Records the consideration agreed for a leg of a pending instruction that no counterparty
+        /// has affirmed yet, e.g. the price of a DvP trade. The consideration is never transferred,
+        /// and the counterparties agree to it by affirming the instruction.
+        ///
+        /// # Arguments
+        /// * `id` - Instruction id.
+        /// * `leg_id` - Id of the leg.
+        /// * `consideration` - Consideration of the leg, or `None` to remove it.
+        ///
+        /// # Permissions
+        /// * Venue creator
+        #[weight = <T as Config>::WeightInfo::set_leg_consideration()]
+        pub fn set_leg_consideration(
+            origin,
+            id: InstructionId,
+            leg_id: LegId,
+            consideration: Option<LegConsideration>,
+        ) -> DispatchResult {
+            let did = Identity::<T>::ensure_perms(origin)?;
+            Self::base_set_leg_consideration(did, id, leg_id, consideration)
+        }
+
+        /// Sets the market hours during which the execution of instructions gets the given
+        /// scheduler priority. Executions planned off-hours can be deferred to the next opening,
+        /// but never past the expiry of their instruction.
+        /// Only executions scheduled after the change are affected.
+        ///
+        /// # Arguments
+        /// * `market_hours` - The market hours, or `None` to schedule executions as soon as possible.
+        ///
+        /// # Permissions
+        /// * Root
+        #[weight = <T as Config>::WeightInfo::set_market_hours()]
+        pub fn set_market_hours(origin, market_hours: Option<MarketHours<T::BlockNumber>>) -> DispatchResult {
+            ensure_root(origin)?;
+            if let Some(market_hours) = &market_hours {
+                ensure!(market_hours.is_valid(), Error::<T>::InvalidMarketHours);
+            }
+
+            MarketHoursConfig::<T>::set(market_hours);
+            Self::deposit_event(RawEvent::MarketHoursSet(market_hours));
+            Ok(())
+        }
+
+        /// Adds a new instruction with tags that can be used to look it up, e.g. by an off-chain trade id.
+        ///
+        /// # Arguments
+        /// * `venue_id` - ID of the venue this instruction belongs to.
+        /// * `settlement_type` - Defines if the instruction should be settled in the next block, after receiving all affirmations
+        ///    or waiting till a specific block.
+        /// * `trade_date` - Optional date from which people can interact with this instruction.
+        /// * `value_date` - Optional date after which the instruction should be settled (not enforced).
+        /// * `legs` - Legs included in this instruction.
+        /// * `instruction_memo` - Memo field for this instruction.
+        /// * `tags` - Up to `MAX_INSTRUCTION_TAGS` tags attached to the instruction.
+        ///
+        /// # Permissions
+        /// * Venue creator
+        #[weight =
+            <T as Config>::WeightInfo::add_instruction_with_memo_v2(legs.len() as u32)
+            .saturating_add(<T as Config>::WeightInfo::add_instruction_tags(tags.len() as u32))
+            .saturating_add(<T as Config>::WeightInfo::execute_scheduled_instruction_v2(legs))
+            .saturating_add(<T as Config>::WeightInfo::memo(instruction_memo))
+        ]
+        pub fn add_instruction_with_tags(
+            origin,
+            venue_id: VenueId,
+            settlement_type: SettlementType<T::BlockNumber>,
+            trade_date: Option<T::Moment>,
+            value_date: Option<T::Moment>,
+            legs: Vec<LegV2>,
+            instruction_memo: Option<Memo>,
+            tags: BTreeSet<InstructionTag>,
+        ) -> DispatchResult {
+            ensure!(tags.len() <= MAX_INSTRUCTION_TAGS as usize, Error::<T>::TooManyInstructionTags);
+            let did = Self::ensure_perms_and_key_quota(origin, venue_id)?;
+            with_transaction(|| {
+                let instruction_id = Self::base_add_instruction(did, venue_id, settlement_type, trade_date, value_date, legs, instruction_memo, false)?;
+                Self::add_instruction_tags(did, instruction_id, tags);
+                Ok(())
+            })
+        }
+
+        /// Moves the legs of the given instructions from the deprecated `InstructionLegs` storage to `InstructionLegsV2`.
+        /// Instructions without deprecated legs are ignored.
+        ///
+        /// # Arguments
+        /// * `ids` - Ids of the instructions to migrate.
+        /// * `legs_count` - Total number of deprecated legs of the instructions.
+        ///
+        /// # Errors
+        /// * `LegCountTooSmall` - The instructions have more than `legs_count` deprecated legs.
+        ///
+        /// # Permissions
+        /// * Any signed account
+        #[weight = <T as Config>::WeightInfo::migrate_instruction_legs(ids.len() as u32, *legs_count)]
+        pub fn migrate_instruction_legs(origin, ids: Vec<InstructionId>, legs_count: u32) {
+            ensure_signed(origin)?;
+            let legacy_legs: usize = ids
+                .iter()
+                .map(|id| InstructionLegs::iter_key_prefix(id).count())
+                .sum();
+            ensure!(legacy_legs <= legs_count as usize, Error::<T>::LegCountTooSmall);
+            for id in ids {
+                Self::migrate_legacy_legs(&id);
+            }
+        }
+
+        /// Reveals the portfolio behind `alias` in the legs of a pending instruction, affirms it and executes
+        /// the instruction, all in the same call. The portfolio is therefore only public once the instruction
+        /// has been executed: if the execution fails, nothing is revealed.
+        ///
+        /// # Arguments
+        /// * `id` - Instruction id.
+        /// * `alias` - Alias used for the portfolio when the instruction was created.
+        /// * `portfolio` - Portfolio the alias commits to.
+        /// * `salt` - Secret used to derive the alias.
+        /// * `fungible_transfers` - number of fungible transfers in the instruction.
+        /// * `nfts_transfers` - total number of NFTs being transferred in the instruction.
+        ///
+        /// # Errors
+        /// * `InstructionNotAffirmed` - Another counterparty of the instruction has not affirmed it yet.
+        /// * `AliasRequiresSettleOnAffirmation` - The instruction doesn't settle on affirmation.
+        ///
+        /// # Permissions
+        /// * Portfolio
+        #[weight = <T as Config>::WeightInfo::reveal_portfolio_alias(*fungible_transfers)
+            .saturating_add(<T as Config>::WeightInfo::affirm_instruction_v2(0, *nfts_transfers))
+            .saturating_add(<T as Config>::WeightInfo::execute_scheduled_instruction(0, *nfts_transfers))]
+        pub fn reveal_portfolio_alias(
+            origin,
+            id: InstructionId,
+            alias: PortfolioAlias,
+            portfolio: PortfolioId,
+            salt: [u8; 32],
+            fungible_transfers: u32,
+            nfts_transfers: u32,
+        ) {
+            let (did, secondary_key, details) = Self::ensure_origin_perm_and_instruction_validity(origin, id, false)?;
+            T::Portfolio::ensure_portfolio_custody_and_permission(portfolio, did, secondary_key.as_ref())?;
+            ensure!(
+                details.settlement_type.settles_on_affirmation(),
+                Error::<T>::AliasRequiresSettleOnAffirmation
+            );
+            with_transaction(|| {
+                Self::base_reveal_portfolio_alias(did, id, alias, portfolio, salt, fungible_transfers, nfts_transfers)?;
+                Self::unsafe_affirm_instruction(
+                    did,
+                    id,
+                    iter::once(portfolio).collect(),
+                    fungible_transfers,
+                    Some(nfts_transfers),
+                    secondary_key.as_ref(),
+                )?;
+                Self::execute_instruction(id, None)?;
+                Self::prune_instruction(id, true);
+                Ok(())
+            })
+            .map_err(|e: DispatchError| e)?;
+        }
+
+        /// Proposes an instruction to a venue. The instruction is only created once the venue
+        /// creator approves the request with `approve_requested_instruction`, which must happen
+        /// within `InstructionRequestLifetime` blocks.
+        ///
+        /// # Arguments
+        /// * `venue_id` - ID of the venue the instruction is proposed to.
+        /// * `settlement_type` - Defines if the instruction should be settled in the next block, after receiving all affirmations or waiting till a specific block.
+        /// * `trade_date` - Optional date from which people can interact with this instruction.
+        /// * `value_date` - Optional date after which the instruction should be settled (not enforced)
+        /// * `legs` - Legs of the instruction, at least one of which involves a portfolio of the caller.
+        /// * `memo` - Memo field for the instruction.
+        ///
+        /// # Errors
+        /// * `TooManyInstructionRequests` - The venue has `MAX_VENUE_INSTRUCTION_REQUESTS` pending requests.
+        ///
+        /// # Permissions
+        /// * Counterparty of the instruction
+        #[weight = <T as Config>::WeightInfo::request_instruction(legs.len() as u32)
+            .saturating_add(<T as Config>::WeightInfo::memo(memo))]
+        pub fn request_instruction(
+            origin,
+            venue_id: VenueId,
+            settlement_type: SettlementType<T::BlockNumber>,
+            trade_date: Option<T::Moment>,
+            value_date: Option<T::Moment>,
+            legs: Vec<LegV2>,
+            memo: Option<Memo>,
+        ) -> DispatchResult {
+            let did = Identity::<T>::ensure_perms(origin)?;
+            Self::base_request_instruction(did, venue_id, settlement_type, trade_date, value_date, legs, memo)
+        }
+
+        /// Creates the instruction proposed by an instruction request, and removes the request.
+        ///
+        /// # Arguments
+        /// * `id` - Instruction request id.
+        /// * `fungible_transfers` - Number of fungible transfers in the requested instruction.
+        /// * `nfts_transfers` - Number of NFTs transferred in the requested instruction.
+        ///
+        /// # Errors
+        /// * `InstructionRequestExpired` - The request is older than `InstructionRequestLifetime` blocks.
+        ///
+        /// # Permissions
+        /// * Venue creator
+        #[weight = <T as Config>::WeightInfo::approve_requested_instruction(*fungible_transfers, *nfts_transfers)
+            .saturating_add(<T as Config>::WeightInfo::execute_scheduled_instruction(*fungible_transfers, *nfts_transfers))]
+        pub fn approve_requested_instruction(origin, id: InstructionRequestId, fungible_transfers: u32, nfts_transfers: u32) -> DispatchResult {
+            let request = Self::instruction_request(id).ok_or(Error::<T>::UnknownInstructionRequest)?;
+            let did = Self::ensure_perms_and_key_quota(origin, request.venue_id)?;
+            Self::base_approve_requested_instruction(did, id, request, fungible_transfers, nfts_transfers)
+        }
+
+        /// Removes an instruction request without creating its instruction.
+        ///
+        /// # Arguments
+        /// * `id` - Instruction request id.
+        ///
+        /// # Permissions
+        /// * Requester or venue creator, or anyone once the request has expired
+        #[weight = <T as Config>::WeightInfo::remove_instruction_request()]
+        pub fn remove_instruction_request(origin, id: InstructionRequestId) -> DispatchResult {
+            let did = Identity::<T>::ensure_perms(origin)?;
+            let request = Self::instruction_request(id).ok_or(Error::<T>::UnknownInstructionRequest)?;
+            let is_venue_creator = Self::venue_info(request.venue_id).map_or(false, |venue| venue.creator == did);
+            let expired = System::<T>::block_number() > request.expires_at;
+            ensure!(request.requester == did || is_venue_creator || expired, Error::<T>::Unauthorized);
+
+            Self::remove_request(id, request.venue_id);
+            Self::deposit_event(RawEvent::InstructionRequestRemoved(did, id));
+            Ok(())
+        }
+
+        /// Withdraws the affirmations of `portfolio` from pending instructions, and unlocks their assets.
+        /// Lets a new custodian release the assets locked by the previous one.
+        ///
+        /// At most `max_instructions` instructions of `portfolio` are scanned, starting after `start_after`.
+        /// Affirmations that can't be withdrawn, e.g. of instructions being executed, are skipped.
+        /// The `PortfolioAffirmationsRevoked` event reports them, along with the instruction to start
+        /// the next call after if more may remain.
+        ///
+        /// # Arguments
+        /// * `portfolio` - Portfolio whose affirmations are withdrawn.
+        /// * `start_after` - Last instruction scanned by a previous call, if any.
+        /// * `max_instructions` - Maximum number of instructions to scan.
+        ///
+        /// # Permissions
+        /// * Portfolio
+        #[weight = Module::<T>::revoke_affirmations_weight(*max_instructions)]
+        pub fn revoke_affirmations_for_portfolio(
+            origin,
+            portfolio: PortfolioId,
+            start_after: Option<InstructionId>,
+            max_instructions: u32,
+        ) -> DispatchResult {
+            let PermissionedCallOriginData { primary_did, secondary_key, .. } =
+                Identity::<T>::ensure_origin_call_permissions(origin)?;
+            T::Portfolio::ensure_portfolio_custody_and_permission(portfolio, primary_did, secondary_key.as_ref())?;
+            Self::revoke_portfolio_affirmations(primary_did, secondary_key.as_ref(), portfolio, start_after, max_instructions);
+            Ok(())
+        }
+
+        /// Root callable extrinsic, used as an internal call to generate an instruction from a recurring instruction.
+        #[weight = <T as Config>::WeightInfo::execute_recurring_instruction(*_legs_count)]
+        fn execute_recurring_instruction(origin, id: RecurringInstructionId, _legs_count: u32) {
+            ensure_root(origin)?;
+            Self::base_execute_recurring_instruction(id);
+        }
+
+        /// Appoints `agent` as the settlement agent of `portfolio`, or removes the current one if `agent` is `None`.
+        /// The settlement agent can affirm instructions, and withdraw affirmations, involving `portfolio`,
+        /// but can't otherwise move its assets. It stays appointed until removed by a custodian of `portfolio`,
+        /// or until the custody of `portfolio` changes.
+        ///
+        /// # Arguments
+        /// * `portfolio` - Portfolio of the caller.
+        /// * `agent` - Identity of the new settlement agent, if any.
+        ///
+        /// # Permissions
+        /// * Portfolio
+        #[weight = <T as Config>::WeightInfo::set_portfolio_settlement_agent()]
+        pub fn set_portfolio_settlement_agent(origin, portfolio: PortfolioId, agent: Option<IdentityId>) -> DispatchResult {
+            let PermissionedCallOriginData { primary_did, secondary_key, .. } =
+                Identity::<T>::ensure_origin_call_permissions(origin)?;
+            T::Portfolio::ensure_portfolio_custody_and_permission(portfolio, primary_did, secondary_key.as_ref())?;
+            if let Some(agent) = agent {
+                ensure!(Identity::<T>::is_identity_exists(&agent), Error::<T>::InvalidSettlementAgent);
+            }
+
+            PortfolioSettlementAgents::mutate(portfolio, |current| *current = agent);
+            Self::deposit_event(RawEvent::PortfolioSettlementAgentSet(primary_did, portfolio, agent));
+            Ok(())
+        }
+    }
+}
+
+impl<T: Config> PortfolioCustodyHandler for Module<T> {
+    fn on_custody_changed(portfolio: &PortfolioId, custodian: IdentityId) -> Weight {
+        // The settlement agent was appointed by the previous custodian.
+        if PortfolioSettlementAgents::take(portfolio).is_some() {
+            Self::deposit_event(RawEvent::PortfolioSettlementAgentSet(
+                custodian, *portfolio, None,
+            ));
+        }
+        let scanned = Self::revoke_portfolio_affirmations(
+            custodian,
+            None,
+            *portfolio,
+            None,
+            MAX_AFFIRMATIONS_REVOKED_ON_CUSTODY_CHANGE,
+        );
+        Self::revoke_affirmations_weight(scanned)
+            .saturating_add(T::DbWeight::get().reads_writes(1, 1))
+    }
+
+    fn on_custody_changed_max_weight() -> Weight {
+        Self::revoke_affirmations_weight(MAX_AFFIRMATIONS_REVOKED_ON_CUSTODY_CHANGE)
+            .saturating_add(T::DbWeight::get().reads_writes(1, 1))
+    }
+}
+
+impl<T: Config> TickerMigrationHandler for Module<T> {
+    fn migrate_ticker(old: &Ticker, new: &Ticker, entries: &mut u32) -> DispatchResult {
+        VenueFiltering::swap(old, new);
+        for (venue_id, allowed) in VenueAllowList::drain_prefix(old) {
+            try_use_entry::<T>(entries)?;
+            VenueAllowList::insert(new, venue_id, allowed);
+        }
+        SettlementFrozen::swap(old, new);
+        Ok(())
+    }
+}
+
+impl<T: Config> Module<T> {
+    fn lock_via_leg(leg: &LegV2) -> DispatchResult {
+        match &leg.asset {
+            LegAsset::Fungible { ticker, amount } => {
+                T::Portfolio::lock_tokens(&leg.from, &ticker, *amount)
+            }
+            LegAsset::NonFungible(nfts) => with_transaction(|| {
+                for nft_id in nfts.ids() {
+                    T::Portfolio::lock_nft(&leg.from, nfts.ticker(), &nft_id)?;
+                }
+                Ok(())
+            }),
+        }
+    }
+
+    fn unlock_via_leg(leg: &LegV2) -> DispatchResult {
+        match &leg.asset {
+            LegAsset::Fungible { ticker, amount } => {
+                T::Portfolio::unlock_tokens(&leg.from, &ticker, *amount)
+            }
+            LegAsset::NonFungible(nfts) => with_transaction(|| {
+                for nft_id in nfts.ids() {
+                    T::Portfolio::unlock_nft(&leg.from, nfts.ticker(), &nft_id)?;
+                }
+                Ok(())
+            }),
+        }
+    }
+
+    /// Ensure origin call permission and the given instruction validity.
+    fn ensure_origin_perm_and_instruction_validity(
+        origin: <T as frame_system::Config>::RuntimeOrigin,
+        id: InstructionId,
+        is_execute: bool,
+    ) -> EnsureValidInstructionResult<T::AccountId, T::Moment, T::BlockNumber> {
+        let PermissionedCallOriginData {
+            primary_did,
+            secondary_key,
+            ..
+        } = Identity::<T>::ensure_origin_call_permissions(origin)?;
+        Ok((
+            primary_did,
+            secondary_key,
+            Self::ensure_instruction_validity(id, is_execute)?,
+        ))
+    }
+
+    // Extract `Venue` with `id`, assuming it was created by `did`, or error.
+    fn venue_for_management(id: VenueId, did: IdentityId) -> Result<Venue, DispatchError> {
+        // Ensure venue exists & that DID created it.
+        let venue = Self::venue_info(id).ok_or(Error::<T>::InvalidVenue)?;
+        ensure!(venue.creator == did, Error::<T>::Unauthorized);
+        Ok(venue)
+    }
+
+    pub fn base_add_instruction(
+        did: IdentityId,
+        venue_id: VenueId,
+        settlement_type: SettlementType<T::BlockNumber>,
+        trade_date: Option<T::Moment>,
+        value_date: Option<T::Moment>,
+        legs: Vec<LegV2>,
+        memo: Option<Memo>,
+        emit_deprecated_event: bool,
+    ) -> Result<InstructionId, DispatchError> {
+        Self::ensure_valid_memo(&memo)?;
+
+        // Verifies if the block number is in the future so that `T::Scheduler::schedule_named` doesn't fail.
+        if let SettlementType::SettleOnBlock(block_number) = &settlement_type {
+            ensure!(
+                *block_number > System::<T>::block_number(),
+                Error::<T>::SettleOnPastBlock
+            );
+        }
+
+        // Ensure that instruction dates are valid.
+        if let (Some(trade_date), Some(value_date)) = (trade_date, value_date) {
+            ensure!(
+                value_date >= trade_date,
+                Error::<T>::InstructionDatesInvalid
+            );
+        }
+
+        // Ensure venue exists & sender is its creator.
+        Self::venue_for_management(venue_id, did)?;
+        ensure!(!Self::venue_paused(venue_id), Error::<T>::VenuePaused);
+
+        // Ensure the venue has not reached its maximum number of open instructions.
+        let open_instructions = Self::venue_open_instructions(venue_id);
+        if let Some(max_open_instructions) =
+            Self::venue_quota(venue_id).and_then(|quota| quota.max_open_instructions)
+        {
+            ensure!(
+                open_instructions < max_open_instructions,
+                Error::<T>::VenueOpenInstructionsQuotaReached
+            );
+        }
+
+        // Verifies if all legs are valid.
+        let instruction_info = Self::ensure_valid_legs(&legs, venue_id)?;
+
+        // Advance and get next `instruction_id`.
+        let instruction_id = InstructionCounter::try_mutate(try_next_post::<T, _>)?;
+
+        let expiry_block = Self::venue_default_expiry(venue_id)
+            .map(|expiry| System::<T>::block_number().saturating_add(expiry));
+        let instruction = Instruction {
+            instruction_id,
+            venue_id,
+            settlement_type,
+            created_at: Some(<pallet_timestamp::Pallet<T>>::get()),
+            trade_date,
+            value_date,
+            expiry_block,
+        };
+
+        InstructionStatuses::<T>::insert(instruction_id, InstructionStatus::Pending);
+
+        // Write data to storage.
+        for counter_party in instruction_info.parties() {
+            UserAffirmations::insert(counter_party, instruction_id, AffirmationStatus::Pending);
+        }
+
+        // The venue of the instruction is needed to schedule its execution.
+        <InstructionDetails<T>>::insert(instruction_id, instruction);
+
+        if let SettlementType::SettleOnBlock(block_number) = settlement_type {
+            Self::schedule_instruction(
+                instruction_id,
+                block_number,
+                instruction_info.fungible_transfers(),
+                instruction_info.nfts_transferred(),
+            );
+        }
+
+        if let Some(expiry_block) = expiry_block {
+            Self::schedule_instruction_expiry(
+                instruction_id,
+                expiry_block,
+                instruction_info.fungible_transfers(),
+                instruction_info.nfts_transferred(),
+            );
+        }
+
+        InstructionAffirmsPending::insert(
+            instruction_id,
+            u64::try_from(instruction_info.parties().len()).unwrap_or_default(),
+        );
+        VenueInstructions::insert(venue_id, instruction_id, ());
+        VenueOpenInstructions::insert(venue_id, open_instructions.saturating_add(1));
+        OpenInstructionCount::mutate(|count| *count = count.saturating_add(1));
+        if let Some(ref memo) = memo {
+            InstructionMemos::insert(instruction_id, &memo);
+        }
+        if let Some(fee) = Self::venue_cancellation_fee(venue_id) {
+            InstructionCancellationFees::insert(instruction_id, fee);
+            Self::deposit_event(RawEvent::InstructionCancellationFeeDisclosed(
+                instruction_id,
+                fee,
+            ));
+        }
+        if let Some(fee) = Self::venue_fee(venue_id) {
+            InstructionVenueFees::insert(instruction_id, fee);
+            Self::deposit_event(RawEvent::InstructionVenueFeeDisclosed(instruction_id, fee));
+        }
+
+        legs.iter().enumerate().for_each(|(index, leg)| {
+            InstructionLegsV2::insert(instruction_id, LegId(index as u64), leg.clone())
+        });
+        if emit_deprecated_event {
+            let legs: Result<Vec<Leg>, &str> = legs
+                .into_iter()
+                .map(|leg_v2| Leg::try_from(leg_v2))
+                .collect();
+            let legs: Vec<Leg> = legs.map_err(|_| Error::<T>::InvalidLegAsset)?;
+            Self::deposit_event(RawEvent::InstructionCreated(
+                did,
+                venue_id,
+                instruction_id,
+                settlement_type,
+                trade_date,
+                value_date,
+                legs,
+                memo,
+            ))
+        } else {
+            Self::deposit_event(RawEvent::InstructionV2Created(
+                did,
+                venue_id,
+                instruction_id,
+                settlement_type,
+                trade_date,
+                value_date,
+                legs,
+                memo,
+            ));
+        }
+
+        Ok(instruction_id)
+    }
+
+    /// Makes sure the legs are valid. For both types of assets the sender and receiver must be different,
+    /// the amount being transferred must be greater than zero, and if filtering is enabled the venue list is also checked.
+    /// The number of fungible an non fungible assets in the legs must be within the valid limits allowed.
+    /// Returns a set of the unique counter parties involved in the legs.
+    fn ensure_valid_legs(
+        legs: &[LegV2],
+        venue_id: VenueId,
+    ) -> Result<InstructionInfo, DispatchError> {
+        let mut nfts_transfers = 0;
+        let mut fungible_transfers = 0;
+        let mut parties = BTreeSet::new();
+        let mut tickers = BTreeSet::new();
+        for leg in legs {
+            ensure!(leg.from != leg.to, Error::<T>::SameSenderReceiver);
+            match &leg.asset {
+                LegAsset::Fungible { ticker, amount } => {
+                    ensure!(*amount > 0, Error::<T>::ZeroAmount);
+                    Self::ensure_venue_filtering(&mut tickers, ticker.clone(), &venue_id)?;
+                    fungible_transfers += 1;
+                }
+                LegAsset::NonFungible(nfts) => {
+                    <Nft<T>>::ensure_within_nfts_transfer_limits(&nfts)?;
+                    Self::ensure_venue_filtering(&mut tickers, nfts.ticker().clone(), &venue_id)?;
+                    <Nft<T>>::ensure_no_duplicate_nfts(&nfts)?;
+                    nfts_transfers += nfts.len();
+                }
+            }
+            parties.insert(leg.from);
+            parties.insert(leg.to);
+        }
+        // Assets that have matured or been retired can't be transferred anymore.
+        for ticker in &tickers {
+            ensure!(
+                !<Asset<T>>::lifecycle_state(ticker).transfers_ended(),
+                Error::<T>::AssetTransfersEnded
+            );
+        }
+        ensure!(
+            nfts_transfers <= T::MaxNumberOfNFTs::get() as usize,
+            Error::<T>::MaxNumberOfNFTsExceeded
+        );
+        ensure!(
+            fungible_transfers <= T::MaxNumberOfFungibleAssets::get(),
+            Error::<T>::InstructionHasTooManyLegs
+        );
+        Ok(InstructionInfo::new(
+            parties,
+            TransferData::new(fungible_transfers, nfts_transfers as u32),
+        ))
+    }
+
+    fn unsafe_withdraw_instruction_affirmation(
+        did: IdentityId,
+        id: InstructionId,
+        portfolios: BTreeSet<PortfolioId>,
+        secondary_key: Option<&SecondaryKey<T::AccountId>>,
+        fungible_transfers: u32,
+        nfts_transfers: Option<u32>,
+    ) -> Result<u32, DispatchError> {
+        Self::ensure_execution_not_started(id)?;
+        // checks custodianship of portfolios and affirmation status
+        Self::ensure_portfolios_and_affirmation_status(
+            id,
+            &portfolios,
+            did,
+            secondary_key,
+            &[AffirmationStatus::Affirmed],
+        )?;
+        // Unlock tokens that were previously locked during the affirmation
+        let (total_leg_count, filtered_legs) =
+            Self::filtered_legs(&id, &portfolios, fungible_transfers, nfts_transfers)?;
+        for (leg_id, leg_details) in filtered_legs {
+            match Self::instruction_leg_status(id, leg_id) {
+                LegStatus::ExecutionToBeSkipped(signer, receipt_uid) => {
+                    // Receipt was claimed for this instruction. Therefore, no token unlocking is required, we just unclaim the receipt.
+                    Self::unclaim_leg_receipts(did, id, leg_id, signer, receipt_uid);
+                }
+                LegStatus::ExecutionPending => {
+                    // Tokens are locked, need to be unlocked.
+                    Self::unlock_via_leg(&leg_details)?;
+                }
+                LegStatus::PendingTokenLock => {
+                    return Err(Error::<T>::InstructionNotAffirmed.into());
+                }
+            };
+            <InstructionLegStatus<T>>::insert(id, leg_id, LegStatus::PendingTokenLock);
+        }
+
+        // Updates storage.
+        Self::refund_venue_fees(id, &portfolios);
+        for portfolio in &portfolios {
+            UserAffirmations::insert(portfolio, id, AffirmationStatus::Pending);
+            AffirmsReceived::remove(id, portfolio);
+            Self::deposit_event(RawEvent::AffirmationWithdrawn(did, *portfolio, id));
+        }
+
+        InstructionAffirmsPending::mutate(id, |affirms_pending| {
+            *affirms_pending += u64::try_from(portfolios.len()).unwrap_or_default()
+        });
+
+        Ok(total_leg_count)
+    }
+
+    fn ensure_instruction_validity(
+        id: InstructionId,
+        is_execute: bool,
+    ) -> Result<Instruction<T::Moment, T::BlockNumber>, DispatchError> {
+        Self::ensure_pending_or_failed(id)?;
+        let details = Self::instruction_details(id);
+
+        match (details.settlement_type, is_execute) {
+            // is_execute is true for execution
+            (SettlementType::SettleOnBlock(block_number), true) => {
+                // Ensures block number is less than or equal to current block number.
+                ensure!(
+                    block_number <= System::<T>::block_number(),
+                    Error::<T>::InstructionSettleBlockNotReached
+                );
+            }
+            // is_execute is false for affirmation
+            (SettlementType::SettleOnBlock(block_number), false) => {
+                // Ensures block number is greater than current block number.
+                ensure!(
+                    block_number > System::<T>::block_number(),
+                    Error::<T>::InstructionSettleBlockPassed
+                );
+            }
+            (SettlementType::SettleManual(block_number), true) => {
+                // Ensures block number is less than  or equal to current block number.
+                ensure!(
+                    block_number <= System::<T>::block_number(),
+                    Error::<T>::InstructionSettleBlockNotReached
+                );
+            }
+            (_, _) => {}
+        }
+
+        Ok(details)
+    }
+
+    /// Execute the instruction with `instruction_id`, pruning it once all its legs have been executed.
+    /// Instructions allowing partial execution use at most `MaxExecutionWeight`,
+    /// their remaining legs being executed in a later block.
+    /// On error, set the instruction status to failed.
+    fn execute_instruction_retryable(id: InstructionId) -> DispatchResult {
+        match Self::execute_instruction(id, Some(T::MaxExecutionWeight::get())) {
+            Ok(None) => Self::prune_instruction(id, true),
+            Ok(Some(_)) => {}
+            Err(e) => {
+                if <InstructionDetails<T>>::contains_key(id) {
+                    InstructionStatuses::<T>::insert(id, InstructionStatus::Failed);
+                }
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
+
+    /// Executes the legs of `instruction_id`. For instructions allowing partial execution,
+    /// only the legs that fit in `max_weight` are executed, or all of them if `None`.
+    ///
+    /// Returns the id of the next leg to execute when the execution continues in a later block.
+    /// Other instructions are executed at once, in a single transaction.
+    fn execute_instruction(
+        instruction_id: InstructionId,
+        max_weight: Option<Weight>,
+    ) -> Result<Option<LegId>, DispatchError> {
+        let details = Self::instruction_details(instruction_id);
+        let mut instruction_legs = Self::get_sorted_instruction_legs(&instruction_id);
+        Self::ensure_instruction_executable(instruction_id, details.venue_id, &instruction_legs)?;
+
+        // Skips the legs executed in previous blocks.
+        let mut progress = Self::instruction_execution_progress(instruction_id).unwrap_or_default();
+        instruction_legs.retain(|(leg_id, _)| *leg_id >= progress.next_leg_id);
+
+        if details.settlement_type.allows_partial_execution() {
+            // Defers the legs not fitting in `max_weight`, as each leg is executed on its own.
+            let batch_len = max_weight.map_or(instruction_legs.len(), |max_weight| {
+                Self::execution_batch_len(&instruction_legs, max_weight)
+            });
+            let remaining_legs = instruction_legs.split_off(batch_len);
+            let failed_legs =
+                Self::execute_instruction_partially(instruction_id, &instruction_legs)?;
+            progress
+                .failed_legs
+                .extend(failed_legs.into_iter().map(|(leg_id, _)| leg_id));
+
+            if let Some((next_leg_id, _)) = remaining_legs.first() {
+                progress.next_leg_id = *next_leg_id;
+                InstructionExecutionProgress::insert(instruction_id, &progress);
+                let remaining_legs: Vec<LegV2> =
+                    remaining_legs.into_iter().map(|(_, leg)| leg).collect();
+                let (fungible_transfers, nfts_transfers) = get_transfer_by_asset(&remaining_legs);
+                Self::schedule_instruction(
+                    instruction_id,
+                    System::<T>::block_number() + One::one(),
+                    fungible_transfers,
+                    nfts_transfers,
+                );
+                Self::deposit_event(RawEvent::InstructionExecutionContinued(
+                    SettlementDID.as_id(),
+                    instruction_id,
+                    progress.next_leg_id,
+                ));
+                return Ok(Some(progress.next_leg_id));
+            }
+        } else {
+            let netting = Self::venue_netting(details.venue_id);
+            if let Err((leg_id, _)) = frame_storage_with_transaction(|| {
+                Self::release_asset_locks_and_transfer_pending_legs(
+                    instruction_id,
+                    &instruction_legs,
+                    netting,
+                )
+            })? {
+                Self::deposit_event(RawEvent::LegFailedExecution(
+                    SettlementDID.as_id(),
+                    instruction_id,
+                    leg_id,
+                ));
+                Self::deposit_event(RawEvent::InstructionFailed(
+                    SettlementDID.as_id(),
+                    instruction_id,
+                ));
+                // Unclaim receipts for the failed transaction so that they can be reused
+                Self::unsafe_unclaim_receipts(instruction_id, &instruction_legs);
+                return Err(Error::<T>::InstructionFailed.into());
+            }
+        }
+
+        if progress.failed_legs.is_empty() {
+            Self::deposit_event(RawEvent::InstructionExecuted(
+                SettlementDID.as_id(),
+                instruction_id,
+            ));
+        } else {
+            Self::deposit_event(RawEvent::InstructionPartiallyExecuted(
+                SettlementDID.as_id(),
+                instruction_id,
+                progress.failed_legs,
+            ));
+        }
+        Ok(None)
+    }
+
+    /// Returns the legs of `instruction_id` in execution order.
+    ///
+    /// NB: The order of execution of the legs matter in some edge cases around compliance.
+    /// E.g: Consider a token with a total supply of 100 and maximum percentage ownership of 10%.
+    /// In a given moment, Alice owns 10 tokens, Bob owns 5 and Charlie owns 0.
+    /// Now, consider one instruction with two legs: 1. Alice transfers 5 tokens to Charlie; 2. Bob transfers 5 tokens to Alice;
+    /// If the second leg gets executed before the first leg, Alice will momentarily hold 15% of the asset and hence the settlement will fail compliance.
+    fn get_sorted_instruction_legs(instruction_id: &InstructionId) -> Vec<(LegId, LegV2)> {
+        let mut instruction_legs = Self::get_instruction_legs(instruction_id);
+        instruction_legs.sort_by_key(|leg_id_leg| leg_id_leg.0);
+        instruction_legs
+    }
+
+    /// Verifies that `instruction_id` can be executed: it has no pending affirmations, it is pending,
+    /// and the settlement of its assets is neither frozen nor forbidden to its venue.
+    fn ensure_instruction_executable(
+        instruction_id: InstructionId,
+        venue_id: VenueId,
+        instruction_legs: &[(LegId, LegV2)],
+    ) -> DispatchResult {
+        // Verifies that there are no pending affirmations for the given instruction
+        ensure!(
+            Self::instruction_affirms_pending(instruction_id) == 0,
+            Error::<T>::InstructionFailed
+        );
+
+        // Verifies that the instruction is not in a Failed or in an Unknown state
+        ensure!(
+            Self::instruction_status(instruction_id) == InstructionStatus::Pending,
+            Error::<T>::InstructionNotPending
+        );
+
+        // Verifies that the venue still has the required permissions for the tokens involved.
+        let mut tickers: BTreeSet<Ticker> = BTreeSet::new();
+        for (_, leg) in instruction_legs {
+            // Each ticker is only checked once
+            let ticker = leg.asset.ticker_and_amount().0;
+            if !tickers.insert(ticker) {
+                continue;
+            }
+            ensure!(
+                !Self::settlement_frozen(ticker),
+                Error::<T>::AssetSettlementFrozen
+            );
+            if Self::venue_filtering(ticker) && !Self::venue_allow_list(ticker, venue_id) {
+                Self::deposit_event(RawEvent::VenueUnauthorized(
+                    SettlementDID.as_id(),
+                    ticker,
+                    venue_id,
+                ));
+                return Err(Error::<T>::UnauthorizedVenue.into());
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the number of fungible and non fungible transfers of the legs of `id`
+    /// executed in the next block, for an instruction allowing partial execution.
+    fn execution_batch_transfers(id: InstructionId) -> (u32, u32) {
+        let mut legs = Self::get_sorted_instruction_legs(&id);
+        if let Some(progress) = Self::instruction_execution_progress(id) {
+            legs.retain(|(leg_id, _)| *leg_id >= progress.next_leg_id);
+        }
+        let batch_len = Self::execution_batch_len(&legs, T::MaxExecutionWeight::get());
+        let batch: Vec<LegV2> = legs
+            .into_iter()
+            .take(batch_len)
+            .map(|(_, leg)| leg)
+            .collect();
+        get_transfer_by_asset(&batch)
+    }
+
+    /// Returns the number of legs, taken in order, whose execution fits in `max_weight`.
+    /// At least one leg is always executed, so that the execution of the instruction progresses.
+    fn execution_batch_len(legs: &[(LegId, LegV2)], max_weight: Weight) -> usize {
+        let mut fungible_transfers = 0u32;
+        let mut nfts_transfers = 0u32;
+        for (index, (_, leg)) in legs.iter().enumerate() {
+            match &leg.asset {
+                LegAsset::Fungible { .. } => fungible_transfers += 1,
+                LegAsset::NonFungible(nfts) => nfts_transfers += nfts.len() as u32,
+            }
+            let weight = <T as Config>::WeightInfo::execute_scheduled_instruction(
+                fungible_transfers,
+                nfts_transfers,
+            );
+            if index > 0 && weight.any_gt(max_weight) {
+                return index;
+            }
+        }
+        legs.len()
+    }
+
+    fn release_asset_locks_and_transfer_pending_legs(
+        instruction_id: InstructionId,
+        instruction_legs: &[(LegId, LegV2)],
+        netting: bool,
+    ) -> TransactionOutcome<Result<Result<(), (LegId, DispatchError)>, DispatchError>> {
+        Self::unchecked_release_locks(instruction_id, instruction_legs);
+        let mut pending_legs: Vec<(LegId, LegV2)> = instruction_legs
+            .iter()
+            .filter(|(leg_id, _)| {
+                Self::instruction_leg_status(instruction_id, leg_id) == LegStatus::ExecutionPending
+            })
+            .cloned()
+            .collect();
+        if netting {
+            let (net_legs, netted_leg_ids) = Self::net_legs(pending_legs);
+            if !netted_leg_ids.is_empty() {
+                Self::deposit_event(RawEvent::LegsNetted(
+                    SettlementDID.as_id(),
+                    instruction_id,
+                    netted_leg_ids,
+                ));
+            }
+            pending_legs = net_legs;
+        }
+        let mut settled = BTreeMap::new();
+        for (leg_id, leg) in &pending_legs {
+            if let Err(e) = Self::transfer_leg(leg, &mut settled) {
+                return TransactionOutcome::Rollback(Ok(Err((*leg_id, e))));
+            }
+        }
+        Self::record_settled_volumes(settled);
+        TransactionOutcome::Commit(Ok(Ok(())))
+    }
+
+    /// Collapses the fungible legs of the same ticker between the same two portfolios into a single
+    /// leg transferring their net amount, which takes the place of the first of these legs.
+    /// Legs must be sorted by `LegId`.
+    ///
+    /// Returns the net legs and the ids of the legs that were netted into another leg or fully offset.
+    fn net_legs(legs: Vec<(LegId, LegV2)>) -> (Vec<(LegId, LegV2)>, Vec<LegId>) {
+        // The portfolios of a netting group are ordered, so that mirrored legs share the same key.
+        let netting_key = |leg: &LegV2| match &leg.asset {
+            LegAsset::Fungible { ticker, amount } if leg.from < leg.to => {
+                Some(((*ticker, leg.from, leg.to), *amount, 0))
+            }
+            LegAsset::Fungible { ticker, amount } => {
+                Some(((*ticker, leg.to, leg.from), 0, *amount))
+            }
+            LegAsset::NonFungible(_) => None,
+        };
+
+        // (ticker, lower portfolio, higher portfolio) -> (first leg id, sent by lower, sent by higher)
+        let mut groups: BTreeMap<(Ticker, PortfolioId, PortfolioId), (LegId, Balance, Balance)> =
+            BTreeMap::new();
+        for (leg_id, leg) in &legs {
+            if let Some((key, sent, received)) = netting_key(leg) {
+                let group = groups.entry(key).or_insert((*leg_id, 0, 0));
+                group.1 = group.1.saturating_add(sent);
+                group.2 = group.2.saturating_add(received);
+            }
+        }
+
+        let mut net_legs = Vec::with_capacity(legs.len());
+        let mut netted_leg_ids = Vec::new();
+        for (leg_id, leg) in legs {
+            let ((ticker, lower, higher), ..) = match netting_key(&leg) {
+                Some(netting_key) => netting_key,
+                None => {
+                    net_legs.push((leg_id, leg));
+                    continue;
+                }
+            };
+            let (first_leg_id, sent, received) = groups[&(ticker, lower, higher)];
+            let (from, to, amount) = if sent >= received {
+                (lower, higher, sent - received)
+            } else {
+                (higher, lower, received - sent)
+            };
+            if leg_id != first_leg_id || amount == 0 {
+                netted_leg_ids.push(leg_id);
+            } else {
+                let asset = LegAsset::Fungible { ticker, amount };
+                net_legs.push((leg_id, LegV2 { from, to, asset }));
+            }
+        }
+        (net_legs, netted_leg_ids)
+    }
+
+    /// Releases the locks of all legs and executes each pending leg in its own transaction,
+    /// skipping and reporting the legs that fail.
+    ///
+    /// Returns the ids of the legs that failed, along with the reason of each failure.
+    fn execute_instruction_partially(
+        instruction_id: InstructionId,
+        instruction_legs: &[(LegId, LegV2)],
+    ) -> Result<Vec<(LegId, DispatchError)>, DispatchError> {
+        Self::unchecked_release_locks(instruction_id, instruction_legs);
+        let mut failed_legs = Vec::new();
+        let mut settled = BTreeMap::new();
+        for (leg_id, leg) in instruction_legs {
+            if Self::instruction_leg_status(instruction_id, leg_id) != LegStatus::ExecutionPending {
+                continue;
+            }
+            let transferred =
+                frame_storage_with_transaction(|| match Self::transfer_leg(leg, &mut settled) {
+                    Ok(_) => TransactionOutcome::Commit(Ok(Ok(()))),
+                    Err(e) => TransactionOutcome::Rollback(Ok(Err(e))),
+                })?;
+            if let Err(e) = transferred {
+                Self::deposit_event(RawEvent::LegFailedExecution(
+                    SettlementDID.as_id(),
+                    instruction_id,
+                    *leg_id,
+                ));
+                failed_legs.push((*leg_id, e));
+            }
+        }
+        Self::record_settled_volumes(settled);
+        Ok(failed_legs)
+    }
+
+    /// Transfers the asset of `leg` from the sender to the receiver portfolio,
+    /// and adds the tokens transferred to the volume `settled` per ticker.
+    fn transfer_leg(leg: &LegV2, settled: &mut BTreeMap<Ticker, Balance>) -> DispatchResult {
+        match &leg.asset {
+            LegAsset::Fungible { ticker, amount } => {
+                <Asset<T>>::base_transfer(leg.from, leg.to, ticker, *amount)?
+            }
+            LegAsset::NonFungible(nfts) => <Nft<T>>::base_nft_transfer(&leg.from, &leg.to, nfts)?,
+        }
+        let (ticker, amount) = leg.asset.ticker_and_amount();
+        let volume = settled.entry(ticker).or_default();
+        *volume = volume.saturating_add(amount);
+        Ok(())
+    }
+
+    /// Adds the volume `settled` by an instruction to the daily settled volume of each ticker.
+    fn record_settled_volumes(settled: BTreeMap<Ticker, Balance>) {
+        for (ticker, volume) in settled {
+            <Statistics<T>>::record_settled_volume(&ticker, volume);
+        }
+    }
+
+    fn prune_instruction(id: InstructionId, executed: bool) {
+        Self::close_instruction(id, executed);
+        Self::prune_legs_and_affirmations(id);
+    }
+
+    /// Closes rejected instruction `id`, keeping its legs and affirmations for
+    /// `RejectedInstructionRetention` blocks.
+    fn close_rejected_instruction(id: InstructionId) {
+        Self::close_instruction(id, false);
+        if T::RejectedInstructionRetention::get().is_zero() {
+            Self::prune_legs_and_affirmations(id);
+            return;
+        }
+        let now = System::<T>::block_number();
+        RejectedInstructions::<T>::insert(now, id, ());
+        if Self::rejected_prune_cursor().is_zero() {
+            RejectedPruneCursor::<T>::put(now);
+        }
+    }
+
+    /// Records the final status of instruction `id` and removes all of its storage,
+    /// except for its details, legs and affirmations.
+    fn close_instruction(id: InstructionId, executed: bool) {
+        let legs: Vec<(LegId, LegV2)> = Self::get_instruction_legs(&id);
+        let details = Self::instruction_details(id);
+        let audit_record = executed.then(|| Self::audit_record(&details, &legs));
+        Self::settle_venue_fees(id, details.venue_id, executed);
+        InstructionVenueFees::remove(id);
+        VenueInstructions::remove(details.venue_id, id);
+        VenueOpenInstructions::mutate(details.venue_id, |open_instructions| {
+            *open_instructions = open_instructions.saturating_sub(1)
+        });
+        OpenInstructionCount::mutate(|count| *count = count.saturating_sub(1));
+        InstructionRevision::remove(id);
+        if details.expiry_block.is_some() {
+            let _ = T::Scheduler::cancel_named(id.expiry_name());
+        }
+        InstructionCancellationFees::remove(id);
+        #[allow(deprecated)]
+        InstructionMediators::remove_prefix(id, None);
+        InstructionOperators::remove(id);
+        #[allow(deprecated)]
+        InstructionLegRevisions::remove_prefix(id, None);
+        #[allow(deprecated)]
+        RemovedInstructionLegs::remove_prefix(id, None);
+        #[allow(deprecated)]
+        <ReceiptCosigners<T>>::remove_prefix(id, None);
+        #[allow(deprecated)]
+        InstructionLegConsiderations::remove_prefix(id, None);
+        InstructionExecutionProgress::remove(id);
+
+        let now = System::<T>::block_number();
+        if let Some(record) = audit_record {
+            InstructionAuditDigests::insert(id, blake2_256(&record.encode()));
+            <InstructionAuditRecords<T>>::insert(id, record);
+        }
+        if executed {
+            InstructionStatuses::<T>::insert(id, InstructionStatus::Success(now));
+        } else {
+            InstructionStatuses::<T>::insert(id, InstructionStatus::Rejected(now));
+        }
+        if T::InstructionStatusRetention::get().is_some() {
+            SettledInstructions::<T>::insert(now, id, ());
+            if Self::status_prune_cursor().is_zero() {
+                StatusPruneCursor::<T>::put(now);
+            }
+        }
+
+        // We remove duplicates in memory before triggering storage actions
+        let mut counter_parties = BTreeSet::new();
+        for (_, leg) in &legs {
+            counter_parties.insert(leg.from);
+            counter_parties.insert(leg.to);
+        }
+        for counter_party in counter_parties {
+            UserAffirmations::remove(counter_party, id);
+        }
+    }
+
+    /// Removes the details, legs and affirmations of instruction `id`.
+    fn prune_legs_and_affirmations(id: InstructionId) {
+        Self::drain_instruction_legs(&id);
+        <InstructionDetails<T>>::remove(id);
+        InstructionNextLegId::remove(id);
+        #[allow(deprecated)]
+        <InstructionLegStatus<T>>::remove_prefix(id, None);
+        InstructionAffirmsPending::remove(id);
+        #[allow(deprecated)]
+        AffirmsReceived::remove_prefix(id, None);
+        for reader in MemoAccessList::take(id) {
+            MemoKeyWraps::remove(id, reader);
+        }
+    }
+
+    pub fn unsafe_affirm_instruction(
+        did: IdentityId,
+        id: InstructionId,
+        portfolios: BTreeSet<PortfolioId>,
+        fungible_transfers: u32,
+        nfts_trasferred: Option<u32>,
+        secondary_key: Option<&SecondaryKey<T::AccountId>>,
+    ) -> Result<u32, DispatchError> {
+        // Checks portfolio's custodian and if it is a counter party with a pending affirmation.
+        Self::ensure_portfolios_and_affirmation_status(
+            id,
+            &portfolios,
+            did,
+            secondary_key,
+            &[AffirmationStatus::Pending],
+        )?;
+
+        let (total_leg_count, filtered_legs) =
+            Self::filtered_legs(&id, &portfolios, fungible_transfers, nfts_trasferred)?;
+        Self::ensure_settlement_not_frozen(&id)?;
+        Self::charge_venue_fees(did, id, &portfolios)?;
+        with_transaction(|| {
+            for (leg_id, leg_details) in filtered_legs {
+                Self::lock_via_leg(&leg_details)?;
+                <InstructionLegStatus<T>>::insert(id, leg_id, LegStatus::ExecutionPending);
+            }
+            Ok(())
+        })
+        .map_err(|e: DispatchError| e)?;
+
+        let affirms_pending = Self::instruction_affirms_pending(id);
+
+        // Updates storage
+        for portfolio in &portfolios {
+            UserAffirmations::insert(portfolio, id, AffirmationStatus::Affirmed);
+            AffirmsReceived::insert(id, portfolio, AffirmationStatus::Affirmed);
+            Self::deposit_event(RawEvent::InstructionAffirmed(did, *portfolio, id));
+        }
+        InstructionAffirmsPending::insert(
+            id,
+            affirms_pending.saturating_sub(u64::try_from(portfolios.len()).unwrap_or_default()),
+        );
+
+        Ok(total_leg_count)
+    }
+
+    // Unclaims all receipts for an instruction
+    // Should only be used if user is unclaiming, or instruction has failed
+    fn unsafe_unclaim_receipts(id: InstructionId, legs: &[(LegId, LegV2)]) {
+        for (leg_id, _) in legs {
+            match Self::instruction_leg_status(id, leg_id) {
+                LegStatus::ExecutionToBeSkipped(signer, receipt_uid) => {
+                    Self::unclaim_leg_receipts(
+                        SettlementDID.as_id(),
+                        id,
+                        *leg_id,
+                        signer,
+                        receipt_uid,
+                    );
+                }
+                LegStatus::PendingTokenLock | LegStatus::ExecutionPending => {}
+            }
+        }
+    }
+
+    /// Unclaims the receipt of `signer` for leg `leg_id` of instruction `id`, along with its cosigners' receipts.
+    fn unclaim_leg_receipts(
+        did: IdentityId,
+        id: InstructionId,
+        leg_id: LegId,
+        signer: T::AccountId,
+        receipt_uid: u64,
+    ) {
+        let cosigners = <ReceiptCosigners<T>>::take(id, leg_id);
+        for (signer, receipt_uid) in iter::once((signer, receipt_uid)).chain(cosigners) {
+            <ReceiptsUsed<T>>::insert(&signer, receipt_uid, false);
+            <ReceiptUsage<T>>::remove(&signer, receipt_uid);
+            Self::deposit_event(RawEvent::ReceiptUnclaimed(
+                did,
+                id,
+                leg_id,
+                receipt_uid,
+                signer,
+            ));
+        }
+    }
+
+    fn unchecked_release_locks(id: InstructionId, instruction_legs: &[(LegId, LegV2)]) {
+        for (leg_id, leg) in instruction_legs {
+            match Self::instruction_leg_status(id, leg_id) {
+                LegStatus::ExecutionPending => {
+                    // This can never return an error since the settlement module
+                    // must've locked these tokens when instruction was affirmed
+                    let _ = Self::unlock_via_leg(&leg);
+                }
+                LegStatus::ExecutionToBeSkipped(_, _) | LegStatus::PendingTokenLock => {}
+            }
+        }
+    }
+
+    /// Schedule a given instruction to be executed on the next block only if the
+    /// settlement type is `SettleOnAffirmation` and no. of affirms pending is 0.
+    fn maybe_schedule_instruction(
+        affirms_pending: u64,
+        id: InstructionId,
+        fungible_transfers: u32,
+        nfts_tranferred: u32,
+    ) {
+        if affirms_pending == 0
+            && Self::instruction_details(id)
+                .settlement_type
+                .settles_on_affirmation()
+        {
+            // Schedule instruction to be executed in the next block.
+            let execution_at = System::<T>::block_number() + One::one();
+            Self::schedule_instruction(id, execution_at, fungible_transfers, nfts_tranferred);
+        }
+    }
+
+    /// Schedule execution of given instruction at given block number.
+    ///
+    /// NB - It is expected to execute the given instruction into the given block number but
+    /// it is not a guaranteed behavior, Scheduler may have other high priority task scheduled
+    /// for the given block so there are chances where the instruction execution block no. may drift.
+    fn schedule_instruction(
+        id: InstructionId,
+        execution_at: T::BlockNumber,
+        fungible_transfers: u32,
+        nfts_transfers: u32,
+    ) {
+        let details = Self::instruction_details(id);
+        let (_fungible_transfers, _nfts_transfers) =
+            if details.settlement_type.allows_partial_execution() {
+                // Only the legs fitting in `MaxExecutionWeight` are executed in the block.
+                Self::execution_batch_transfers(id)
+            } else {
+                (fungible_transfers, nfts_transfers)
+            };
+        let call = Call::<T>::execute_scheduled_instruction_v2 {
+            id,
+            _fungible_transfers,
+            _nfts_transfers,
+        }
+        .into();
+        let (execution_at, priority) =
+            Self::market_execution_slot(execution_at, details.expiry_block);
+        let venue_id = details.venue_id;
+        let round = ExecutionRounds::<T>::get(execution_at, venue_id);
+        // The scheduler executes tasks by increasing priority value, then in scheduling order.
+        let priority = priority.saturating_add(round.min(Priority::MAX.into()) as Priority);
+        if let Err(_) = T::Scheduler::schedule_named(
+            id.execution_name(),
+            DispatchTime::At(execution_at),
+            None,
+            priority,
+            RawOrigin::Root.into(),
+            call,
+        ) {
+            Self::deposit_event(RawEvent::SchedulingFailed(
+                Error::<T>::FailedToSchedule.into(),
+            ));
+            return;
+        }
+        ExecutionRounds::<T>::insert(execution_at, venue_id, round.saturating_add(1));
+        Self::deposit_event(RawEvent::InstructionExecutionScheduled(
+            id,
+            execution_at,
+            round,
+        ));
+    }
+
+    /// Removes the execution rounds of the instructions scheduled for block `now`,
+    /// as no more instructions can be scheduled for it.
+    fn clear_execution_rounds(now: T::BlockNumber) -> Weight {
+        let removed = match ExecutionRounds::<T>::remove_prefix(now, None) {
+            KillStorageResult::AllRemoved(n) | KillStorageResult::SomeRemaining(n) => n,
+        };
+        <T as Config>::WeightInfo::clear_execution_rounds(removed)
+    }
+
+    /// Returns the block and the scheduler priority of an execution planned at `execution_at`,
+    /// according to the market hours.
+    ///
+    /// Executions are never deferred past `expiry_block`: if the next opening is after it,
+    /// the execution is planned off-hours at `expiry_block` instead.
+    fn market_execution_slot(
+        execution_at: T::BlockNumber,
+        expiry_block: Option<T::BlockNumber>,
+    ) -> (T::BlockNumber, Priority) {
+        match Self::market_hours() {
+            Some(hours) if hours.is_open(execution_at) => (execution_at, hours.priority),
+            Some(hours) if hours.defer_off_hours => {
+                let next_open = hours.next_open(execution_at);
+                match expiry_block {
+                    Some(expiry_block) if next_open > expiry_block => (
+                        expiry_block.max(execution_at),
+                        SETTLEMENT_INSTRUCTION_EXECUTION_PRIORITY,
+                    ),
+                    _ => (next_open, hours.priority),
+                }
+            }
+            _ => (execution_at, SETTLEMENT_INSTRUCTION_EXECUTION_PRIORITY),
+        }
+    }
+
+    /// Schedule the expiry of given instruction at given block number.
+    fn schedule_instruction_expiry(
+        id: InstructionId,
+        expiry_block: T::BlockNumber,
+        _fungible_transfers: u32,
+        _nfts_transfers: u32,
+    ) {
+        let call = Call::<T>::expire_instruction {
+            id,
+            _fungible_transfers,
+            _nfts_transfers,
+        }
+        .into();
+        if let Err(_) = T::Scheduler::schedule_named(
+            id.expiry_name(),
+            DispatchTime::At(expiry_block),
+            None,
+            SETTLEMENT_INSTRUCTION_EXECUTION_PRIORITY,
+            RawOrigin::Root.into(),
+            call,
+        ) {
+            Self::deposit_event(RawEvent::SchedulingFailed(
+                Error::<T>::FailedToSchedule.into(),
+            ));
+        }
+    }
+
+    fn base_create_recurring_instruction(
+        did: IdentityId,
+        venue_id: VenueId,
+        legs: Vec<LegV2>,
+        memo: Option<Memo>,
+        period: T::BlockNumber,
+        stop: RecurrenceStop<T::BlockNumber>,
+    ) -> DispatchResult {
+        ensure!(!period.is_zero(), Error::<T>::InvalidRecurrencePeriod);
+        Self::ensure_valid_memo(&memo)?;
+        let next_run = System::<T>::block_number().saturating_add(period);
+        let valid_stop = match stop {
+            RecurrenceStop::AfterOccurrences(occurrences) => occurrences > 0,
+            RecurrenceStop::AfterBlock(block_number) => block_number >= next_run,
+        };
+        ensure!(valid_stop, Error::<T>::InvalidRecurrenceStop);
+
+        // Ensure venue exists & sender is its creator, and that the legs are valid.
+        Self::venue_for_management(venue_id, did)?;
+        Self::ensure_valid_legs(&legs, venue_id)?;
+
+        let id = RecurringInstructionCounter::try_mutate(try_next_post::<T, _>)?;
+        Self::schedule_recurring_instruction(id, 0, next_run, legs.len() as u32)?;
+        RecurringInstructions::<T>::insert(
+            id,
+            RecurringInstruction {
+                creator: did,
+                venue_id,
+                legs,
+                memo,
+                period,
+                stop,
+                next_run,
+                occurrences: 0,
+            },
+        );
+        Self::deposit_event(RawEvent::RecurringInstructionCreated(
+            did, venue_id, id, period, stop,
+        ));
+        Ok(())
+    }
+
+    /// Stores the instruction proposed by `did`, which must own a portfolio of its legs.
+    fn base_request_instruction(
+        did: IdentityId,
+        venue_id: VenueId,
+        settlement_type: SettlementType<T::BlockNumber>,
+        trade_date: Option<T::Moment>,
+        value_date: Option<T::Moment>,
+        legs: Vec<LegV2>,
+        memo: Option<Memo>,
+    ) -> DispatchResult {
+        Self::ensure_valid_memo(&memo)?;
+        ensure!(VenueInfo::contains_key(venue_id), Error::<T>::InvalidVenue);
+        ensure!(!Self::venue_paused(venue_id), Error::<T>::VenuePaused);
+        let instruction_info = Self::ensure_valid_legs(&legs, venue_id)?;
+        ensure!(
+            instruction_info
+                .parties()
+                .iter()
+                .any(|portfolio| portfolio.did == did),
+            Error::<T>::RequesterIsNotAParty
+        );
+        let requests_count = Self::venue_instruction_requests_count(venue_id);
+        ensure!(
+            requests_count < MAX_VENUE_INSTRUCTION_REQUESTS,
+            Error::<T>::TooManyInstructionRequests
+        );
+
+        let id = InstructionRequestCounter::try_mutate(try_next_post::<T, _>)?;
+        let expires_at = System::<T>::block_number() + T::InstructionRequestLifetime::get();
+        InstructionRequests::<T>::insert(
+            id,
+            InstructionRequest {
+                requester: did,
+                venue_id,
+                settlement_type,
+                trade_date,
+                value_date,
+                legs,
+                memo,
+                expires_at,
+            },
+        );
+        VenueInstructionRequests::insert(venue_id, id, ());
+        VenueInstructionRequestsCount::insert(venue_id, requests_count + 1);
+        Self::deposit_event(RawEvent::InstructionRequested(did, venue_id, id));
+        Ok(())
+    }
+
+    /// Creates the instruction proposed by `request` on behalf of the venue creator `did`.
+    fn base_approve_requested_instruction(
+        did: IdentityId,
+        id: InstructionRequestId,
+        request: InstructionRequest<T::Moment, T::BlockNumber>,
+        fungible_transfers: u32,
+        nfts_transfers: u32,
+    ) -> DispatchResult {
+        ensure!(
+            System::<T>::block_number() <= request.expires_at,
+            Error::<T>::InstructionRequestExpired
+        );
+        let (f, n) = get_transfer_by_asset(&request.legs);
+        Self::ensure_valid_input_cost(
+            &TransferData::new(f, n),
+            fungible_transfers,
+            Some(nfts_transfers),
+        )?;
+
+        let instruction_id = Self::base_add_instruction(
+            did,
+            request.venue_id,
+            request.settlement_type,
+            request.trade_date,
+            request.value_date,
+            request.legs,
+            request.memo,
+            false,
+        )?;
+        Self::remove_request(id, request.venue_id);
+        Self::deposit_event(RawEvent::InstructionRequestApproved(
+            did,
+            id,
+            instruction_id,
+        ));
+        Ok(())
+    }
+
+    /// Removes the instruction request `id` of venue `venue_id`.
+    fn remove_request(id: InstructionRequestId, venue_id: VenueId) {
+        InstructionRequests::<T>::remove(id);
+        VenueInstructionRequests::remove(venue_id, id);
+        VenueInstructionRequestsCount::mutate(venue_id, |count| *count = count.saturating_sub(1));
+    }
+
+    /// Generates the next instruction of the recurring instruction `id` and schedules the
+    /// following one, unless its stop condition has been reached.
+    fn base_execute_recurring_instruction(id: RecurringInstructionId) {
+        let mut recurring = match Self::recurring_instruction(id) {
+            Some(recurring) => recurring,
+            None => return,
+        };
+
+        let result = with_transaction(|| {
+            Self::base_add_instruction(
+                recurring.creator,
+                recurring.venue_id,
+                SettlementType::SettleOnAffirmation,
+                None,
+                None,
+                recurring.legs.clone(),
+                recurring.memo.clone(),
+                false,
+            )
+        });
+        match result {
+            Ok(instruction_id) => Self::deposit_event(RawEvent::RecurringInstructionGenerated(
+                recurring.creator,
+                id,
+                instruction_id,
+            )),
+            Err(e) => Self::deposit_event(RawEvent::RecurringInstructionFailed(id, e)),
+        }
+
+        recurring.occurrences = recurring.occurrences.saturating_add(1);
+        let next_run = recurring.next_run.saturating_add(recurring.period);
+        let ended = match recurring.stop {
+            RecurrenceStop::AfterOccurrences(occurrences) => recurring.occurrences >= occurrences,
+            RecurrenceStop::AfterBlock(block_number) => next_run > block_number,
+        };
+        if !ended {
+            let scheduled = Self::schedule_recurring_instruction(
+                id,
+                recurring.occurrences,
+                next_run,
+                recurring.legs.len() as u32,
+            );
+            match scheduled {
+                Ok(()) => {
+                    recurring.next_run = next_run;
+                    RecurringInstructions::<T>::insert(id, recurring);
+                    return;
+                }
+                // Without a next run, the recurring instruction can't generate anything anymore,
+                // so it is ended instead of being left behind in storage.
+                Err(e) => Self::deposit_event(RawEvent::RecurringInstructionFailed(id, e)),
+            }
+        }
+
+        RecurringInstructions::<T>::remove(id);
+        Self::deposit_event(RawEvent::RecurringInstructionEnded(recurring.creator, id));
+    }
+
+    /// Schedules the generation of an instruction from the recurring instruction `id` at the given block number.
+    ///
+    /// # Errors
+    /// * `FailedToSchedule` if the scheduler rejects the task.
+    fn schedule_recurring_instruction(
+        id: RecurringInstructionId,
+        occurrence: u32,
+        execution_at: T::BlockNumber,
+        _legs_count: u32,
+    ) -> DispatchResult {
+        let call = Call::<T>::execute_recurring_instruction { id, _legs_count }.into();
+        T::Scheduler::schedule_named(
+            id.schedule_name(occurrence),
+            DispatchTime::At(execution_at),
+            None,
+            SETTLEMENT_INSTRUCTION_EXECUTION_PRIORITY,
+            RawOrigin::Root.into(),
+            call,
+        )
+        .map_err(|_| Error::<T>::FailedToSchedule)?;
+        Ok(())
+    }
+
+    /// Prunes an instruction that is still waiting for affirmations, releasing its locked assets.
+    /// Instructions that have collected all affirmations are left for execution.
+    fn base_expire_instruction(id: InstructionId) {
+        match Self::instruction_status(id) {
+            InstructionStatus::Pending | InstructionStatus::Failed => {}
+            _ => return,
+        }
+        if Self::instruction_affirms_pending(id) == 0 {
+            return;
+        }
+
+        let legs = Self::get_instruction_legs(&id);
+        Self::unsafe_unclaim_receipts(id, &legs);
+        Self::unchecked_release_locks(id, &legs);
+        let _ = T::Scheduler::cancel_named(id.execution_name());
+        Self::prune_instruction(id, false);
+        Self::deposit_event(RawEvent::InstructionExpired(id));
+    }
+
+    pub fn base_affirm_with_receipts(
+        origin: <T as frame_system::Config>::RuntimeOrigin,
+        id: InstructionId,
+        receipt_details: Vec<ReceiptDetails<T::AccountId, T::OffChainSignature>>,
+        portfolios: Vec<PortfolioId>,
+        fungible_transfers: u32,
+    ) -> Result<u32, DispatchError> {
+        ensure!(
+            receipt_details.len() <= MAX_RECEIPTS_PER_AFFIRMATION as usize,
+            Error::<T>::TooManyReceipts
+        );
+        let (did, secondary_key, instruction_details) =
+            Self::ensure_origin_perm_and_instruction_validity(origin, id, false)?;
+        let portfolios_set = portfolios.into_iter().collect::<BTreeSet<_>>();
+
+        // Verify that the receipts provided are unique
+        let receipt_ids = receipt_details
+            .iter()
+            .map(|receipt| (receipt.signer.clone(), receipt.receipt_uid))
+            .collect::<BTreeSet<_>>();
+
+        ensure!(
+            receipt_ids.len() == receipt_details.len(),
+            Error::<T>::ReceiptAlreadyClaimed
+        );
+
+        // Verify portfolio custodianship and check if it is a counter party with a pending affirmation.
+        Self::ensure_portfolios_and_affirmation_status(
+            id,
+            &portfolios_set,
+            did,
+            secondary_key.as_ref(),
+            &[AffirmationStatus::Pending],
+        )?;
+
+        // Verify that the receipts are valid
+        let metadata_schema = Self::receipt_metadata_schema(instruction_details.venue_id);
+        let mut leg_signers: BTreeMap<LegId, BTreeSet<&T::AccountId>> = BTreeMap::new();
+        for receipt in &receipt_details {
+            ensure!(
+                Self::venue_signers(&instruction_details.venue_id, &receipt.signer),
+                Error::<T>::UnauthorizedSigner
+            );
+            ensure!(
+                metadata_schema
+                    .as_ref()
+                    .map_or(true, |schema| schema.accepts(&receipt.metadata)),
+                Error::<T>::ReceiptMetadataSchemaViolation
+            );
+            ensure!(
+                leg_signers
+                    .entry(receipt.leg_id)
+                    .or_default()
+                    .insert(&receipt.signer),
+                Error::<T>::DuplicateReceiptSigner
+            );
+            ensure!(
+                !Self::receipts_used(&receipt.signer, &receipt.receipt_uid),
+                Error::<T>::ReceiptAlreadyClaimed
+            );
+
+            let leg = Self::get_instruction_leg(&id, &receipt.leg_id);
+            if let LegAsset::NonFungible(_nfts) = leg.asset {
+                return Err(Error::<T>::ReceiptForNonFungibleAsset.into());
+            }
+            ensure!(
+                portfolios_set.contains(&leg.from),
+                Error::<T>::PortfolioMismatch
+            );
+
+            let (asset, amount) = leg.asset.ticker_and_amount();
+            ensure!(
+                !pallet_asset::Tokens::contains_key(&asset),
+                Error::<T>::UnauthorizedVenue
+            );
+
+            let msg = Receipt {
+                receipt_uid: receipt.receipt_uid,
+                from: leg.from,
+                to: leg.to,
+                asset,
+                amount,
+            };
+            ensure!(
+                receipt.signature.verify(&msg.encode()[..], &receipt.signer),
+                Error::<T>::InvalidSignature
+            );
+        }
+
+        // Every leg with a receipt must be signed by enough venue signers.
+        let threshold = Self::receipt_signatories(instruction_details.venue_id).max(1) as usize;
+        ensure!(
+            leg_signers
+                .values()
+                .all(|signers| signers.len() >= threshold),
+            Error::<T>::NotEnoughReceiptSigners
+        );
+
+        let (total_leg_count, filtered_legs) =
+            Self::filtered_legs(&id, &portfolios_set, fungible_transfers, None)?;
+        Self::ensure_settlement_not_frozen(&id)?;
+        Self::charge_venue_fees(did, id, &portfolios_set)?;
+        // Lock tokens that do not have a receipt attached to their leg.
+        with_transaction(|| {
+            for (leg_id, leg_details) in filtered_legs {
+                // Receipt for the leg was provided
+                let mut leg_receipts = receipt_details
+                    .iter()
+                    .filter(|receipt| receipt.leg_id == leg_id);
+                if let Some(receipt) = leg_receipts.next() {
+                    <InstructionLegStatus<T>>::insert(
+                        id,
+                        leg_id,
+                        LegStatus::ExecutionToBeSkipped(
+                            receipt.signer.clone(),
+                            receipt.receipt_uid,
+                        ),
+                    );
+                    let cosigners: Vec<_> = leg_receipts
+                        .map(|receipt| (receipt.signer.clone(), receipt.receipt_uid))
+                        .collect();
+                    if !cosigners.is_empty() {
+                        <ReceiptCosigners<T>>::insert(id, leg_id, cosigners);
+                    }
+                } else if let Err(_) = Self::lock_via_leg(&leg_details) {
+                    // rustc fails to infer return type of `with_transaction` if you use ?/map_err here
+                    return Err(DispatchError::from(Error::<T>::FailedToLockTokens));
+                } else {
+                    <InstructionLegStatus<T>>::insert(id, leg_id, LegStatus::ExecutionPending);
+                }
+            }
+            Ok(())
+        })?;
+
+        // Update storage
+        let affirms_pending = Self::instruction_affirms_pending(id)
+            .saturating_sub(u64::try_from(portfolios_set.len()).unwrap_or_default());
+
+        // Mark receipts used in affirmation as claimed
+        let now = frame_system::Pallet::<T>::block_number();
+        for receipt in &receipt_details {
+            <ReceiptsUsed<T>>::insert(&receipt.signer, receipt.receipt_uid, true);
+            <VenueSignerUsages<T>>::mutate(instruction_details.venue_id, &receipt.signer, |usage| {
+                usage.receipts_signed = usage.receipts_signed.saturating_add(1);
+                usage.last_used = Some(now);
+            });
+            <ReceiptUsage<T>>::insert(
+                &receipt.signer,
+                receipt.receipt_uid,
+                ReceiptUsageDetails {
+                    instruction_id: id,
+                    leg_id: receipt.leg_id,
+                    metadata: receipt.metadata.clone(),
+                },
+            );
+            Self::deposit_event(RawEvent::ReceiptClaimed(
+                did,
+                id,
+                receipt.leg_id,
+                receipt.receipt_uid,
+                receipt.signer.clone(),
+                receipt.metadata.clone(),
+            ));
+        }
+
+        for portfolio in portfolios_set {
+            UserAffirmations::insert(portfolio, id, AffirmationStatus::Affirmed);
+            AffirmsReceived::insert(id, portfolio, AffirmationStatus::Affirmed);
+            Self::deposit_event(RawEvent::InstructionAffirmed(did, portfolio, id));
+        }
+
+        InstructionAffirmsPending::insert(id, affirms_pending);
+        Ok(total_leg_count)
+    }
+
+    pub fn base_affirm_instruction(
+        origin: <T as frame_system::Config>::RuntimeOrigin,
+        id: InstructionId,
+        portfolios: impl Iterator<Item = PortfolioId>,
+        fungible_transfers: u32,
+        nfts_transferred: Option<u32>,
+    ) -> Result<u32, DispatchError> {
+        let (did, sk, _) = Self::ensure_origin_perm_and_instruction_validity(origin, id, false)?;
+        let portfolios_set = portfolios.collect::<BTreeSet<_>>();
+
+        // Provide affirmation to the instruction
+        Self::unsafe_affirm_instruction(
+            did,
+            id,
+            portfolios_set,
+            fungible_transfers,
+            nfts_transferred,
+            sk.as_ref(),
+        )
+    }
+
+    // It affirms the instruction and may schedule the instruction
+    // depends on the settlement type.
+    pub fn affirm_with_receipts_and_maybe_schedule_instruction(
+        origin: <T as frame_system::Config>::RuntimeOrigin,
+        id: InstructionId,
+        receipt_details: Vec<ReceiptDetails<T::AccountId, T::OffChainSignature>>,
+        portfolios: Vec<PortfolioId>,
+        fungible_transfers: u32,
+    ) -> DispatchResult {
+        let legs_count = Self::base_affirm_with_receipts(
+            origin,
+            id,
+            receipt_details,
+            portfolios,
+            fungible_transfers,
+        )?;
+        // Schedule instruction to be execute in the next block (expected) if conditions are met.
+        Self::maybe_schedule_instruction(Self::instruction_affirms_pending(id), id, legs_count, 0);
+        Ok(())
+    }
+
+    /// Schedule settlement instruction execution in the next block, unless already scheduled.
+    /// Used for general purpose settlement.
+    pub fn affirm_and_maybe_schedule_instruction(
+        origin: <T as frame_system::Config>::RuntimeOrigin,
+        id: InstructionId,
+        portfolios: impl Iterator<Item = PortfolioId>,
+        fungible_transfers: u32,
+        nfts_transfers: Option<u32>,
+    ) -> DispatchResult {
+        let legs_count = Self::base_affirm_instruction(
+            origin,
+            id,
+            portfolios,
+            fungible_transfers,
+            nfts_transfers,
+        )?;
+        // Schedule the instruction if conditions are met
+        Self::maybe_schedule_instruction(
+            Self::instruction_affirms_pending(id),
+            id,
+            legs_count,
+            nfts_transfers.unwrap_or_default(),
+        );
+        Ok(())
+    }
+
+    /// Affirm with or without receipts, executing the instruction when all affirmations have been received.
+    ///
+    /// NB - Use this function only in the STO pallet to support DVP settlements.
+    pub fn affirm_and_execute_instruction(
+        origin: <T as frame_system::Config>::RuntimeOrigin,
+        id: InstructionId,
+        receipt: Option<ReceiptDetails<T::AccountId, T::OffChainSignature>>,
+        portfolios: Vec<PortfolioId>,
+        max_legs_count: u32,
+        nfts_transferred: Option<u32>,
+    ) -> DispatchResult {
+        match receipt {
+            Some(receipt) => Self::base_affirm_with_receipts(
+                origin,
+                id,
+                vec![receipt],
+                portfolios,
+                max_legs_count,
+            )?,
+            None => Self::base_affirm_instruction(
+                origin,
+                id,
+                portfolios.into_iter(),
+                max_legs_count,
+                nfts_transferred,
+            )?,
+        };
+        Self::execute_settle_on_affirmation_instruction(
+            id,
+            Self::instruction_affirms_pending(id),
+            Self::instruction_details(id).settlement_type,
+        )?;
+        Self::prune_instruction(id, true);
+        Ok(())
+    }
+
+    fn execute_settle_on_affirmation_instruction(
+        id: InstructionId,
+        affirms_pending: u64,
+        settlement_type: SettlementType<T::BlockNumber>,
+    ) -> DispatchResult {
+        // We assume `settlement_type` settles on affirmation,
+        // to be defensive, however, this is checked before instruction execution.
+        if settlement_type.settles_on_affirmation() && affirms_pending == 0 {
+            // We use execute_instruction here directly
+            // and not the execute_instruction_retryable variant
+            // because direct settlement is not retryable.
+            Self::execute_instruction(id, None)?;
+        }
+        Ok(())
+    }
+
+    /// Ensures that `did` is either the custodian or the settlement agent of `portfolio`,
+    /// and that `secondary_key` has permission over `portfolio`.
+    fn ensure_portfolio_affirmation_permission(
+        portfolio: PortfolioId,
+        did: IdentityId,
+        secondary_key: Option<&SecondaryKey<T::AccountId>>,
+    ) -> DispatchResult {
+        if Self::portfolio_settlement_agent(portfolio) != Some(did) {
+            return T::Portfolio::ensure_portfolio_custody_and_permission(
+                portfolio,
+                did,
+                secondary_key,
             );
         }
+        ensure!(
+            secondary_key.map_or(true, |sk| sk.has_portfolio_permission(iter::once(portfolio))),
+            Error::<T>::Unauthorized
+        );
+        Ok(())
+    }
 
-        // Ensure venue exists & sender is its creator.
-        Self::venue_for_management(venue_id, did)?;
+    fn ensure_portfolios_and_affirmation_status(
+        id: InstructionId,
+        portfolios: &BTreeSet<PortfolioId>,
+        custodian: IdentityId,
+        secondary_key: Option<&SecondaryKey<T::AccountId>>,
+        expected_statuses: &[AffirmationStatus],
+    ) -> DispatchResult {
+        for portfolio in portfolios {
+            Self::ensure_portfolio_affirmation_permission(*portfolio, custodian, secondary_key)?;
+            let user_affirmation = Self::user_affirmations(portfolio, id);
+            ensure!(
+                expected_statuses.contains(&user_affirmation),
+                Error::<T>::UnexpectedAffirmationStatus
+            );
+        }
+        Ok(())
+    }
 
-        // Verifies if all legs are valid.
-        let instruction_info = Self::ensure_valid_legs(&legs, venue_id)?;
+    /// Returns the total number of legs of an `instruction_id` and a vector of legs where sender is in the `portfolio_set`.
+    /// Ensures that the number of fungible and non fungible assets being transferred is under the given limit.
+    fn filtered_legs(
+        id: &InstructionId,
+        portfolio_set: &BTreeSet<PortfolioId>,
+        fungible_transfers: u32,
+        nfts_transfers: Option<u32>,
+    ) -> Result<(u32, Vec<(LegId, LegV2)>), DispatchError> {
+        let instruction_legs: Vec<(LegId, LegV2)> = Self::get_instruction_legs(&id);
+        let n_instruction_legs = instruction_legs.len() as u32;
+        // Gets all legs where the sender is in the given set
+        let legs_from_set: Vec<(LegId, LegV2)> = instruction_legs
+            .into_iter()
+            .filter(|(_, leg_v2)| portfolio_set.contains(&leg_v2.from))
+            .collect();
+        let transfer_data = Self::get_transfer_data(&legs_from_set)?;
+        Self::ensure_valid_input_cost(&transfer_data, fungible_transfers, nfts_transfers)?;
+        Ok((n_instruction_legs, legs_from_set))
+    }
 
-        // Advance and get next `instruction_id`.
-        let instruction_id = InstructionCounter::try_mutate(try_next_post::<T, _>)?;
+    fn base_close_venue(
+        did: IdentityId,
+        id: VenueId,
+        signers_count: u32,
+        instructions_count: u32,
+    ) -> DispatchResult {
+        Self::venue_for_management(id, did)?;
+        ensure!(
+            Self::venue_open_instructions(id) == 0,
+            Error::<T>::VenueHasOpenInstructions
+        );
+        ensure!(
+            <VenueSigners<T>>::iter_prefix(id).count() <= signers_count as usize,
+            Error::<T>::SignersCountTooSmall
+        );
+        ensure!(
+            VenueInstructions::iter_prefix(id)
+                .take(instructions_count as usize + 1)
+                .count()
+                <= instructions_count as usize,
+            Error::<T>::InstructionsCountTooSmall
+        );
 
-        let instruction = Instruction {
-            instruction_id,
-            venue_id,
-            settlement_type,
-            created_at: Some(<pallet_timestamp::Pallet<T>>::get()),
-            trade_date,
-            value_date,
-        };
+        VenueInfo::remove(id);
+        Details::remove(id);
+        #[allow(deprecated)]
+        <VenueSigners<T>>::remove_prefix(id, None);
+        #[allow(deprecated)]
+        <VenueSignerUsages<T>>::remove_prefix(id, None);
+        UserVenues::mutate(did, |venues| venues.retain(|venue_id| *venue_id != id));
+        VenueDefaultExpiry::<T>::remove(id);
+        VenueCancellationFee::remove(id);
+        VenueFees::remove(id);
+        ReceiptSignatories::remove(id);
+        ReceiptMetadataSchemas::remove(id);
+        VenueNetting::remove(id);
+        VenueQuotas::remove(id);
+        VenueOpenInstructions::remove(id);
+        #[allow(deprecated)]
+        <InstructionsCreatedByKey<T>>::remove_prefix(id, None);
+        #[allow(deprecated)]
+        VenueInstructions::remove_prefix(id, None);
+        PausedVenues::remove(id);
+        // Pending requests can no longer be approved.
+        for (request_id, ()) in VenueInstructionRequests::drain_prefix(id) {
+            InstructionRequests::<T>::remove(request_id);
+            Self::deposit_event(RawEvent::InstructionRequestRemoved(did, request_id));
+        }
+        VenueInstructionRequestsCount::remove(id);
+        Self::deposit_event(RawEvent::VenueClosed(did, id));
+        Ok(())
+    }
 
-        InstructionStatuses::<T>::insert(instruction_id, InstructionStatus::Pending);
+    /// Ensures the permissions of `origin` and, if `venue_id` limits the number of instructions
+    /// per block, records a new instruction created by the key of `origin` in this block.
+    fn ensure_perms_and_key_quota(
+        origin: <T as frame_system::Config>::RuntimeOrigin,
+        venue_id: VenueId,
+    ) -> Result<IdentityId, DispatchError> {
+        let PermissionedCallOriginData {
+            primary_did,
+            sender,
+            ..
+        } = Identity::<T>::ensure_origin_call_permissions(origin)?;
+        if let Some(max_instructions) =
+            Self::venue_quota(venue_id).and_then(|quota| quota.max_instructions_per_block)
+        {
+            let now = System::<T>::block_number();
+            <InstructionsCreatedByKey<T>>::try_mutate(venue_id, &sender, |(block, created)| {
+                if *block != now {
+                    *block = now;
+                    *created = 0;
+                }
+                ensure!(
+                    *created < max_instructions,
+                    Error::<T>::VenueBlockInstructionsQuotaReached
+                );
+                *created += 1;
+                Ok::<_, DispatchError>(())
+            })?;
+        }
+        Ok(primary_did)
+    }
 
-        // Write data to storage.
-        for counter_party in instruction_info.parties() {
-            UserAffirmations::insert(counter_party, instruction_id, AffirmationStatus::Pending);
+    /// Ensures that none of the legs of instruction `id` involves an asset whose settlement is frozen.
+    fn ensure_settlement_not_frozen(id: &InstructionId) -> DispatchResult {
+        for (_, leg) in Self::get_instruction_legs(id) {
+            ensure!(
+                !Self::settlement_frozen(leg.asset.ticker_and_amount().0),
+                Error::<T>::AssetSettlementFrozen
+            );
         }
+        Ok(())
+    }
 
-        if let SettlementType::SettleOnBlock(block_number) = settlement_type {
-            Self::schedule_instruction(
-                instruction_id,
-                block_number,
-                instruction_info.fungible_transfers(),
-                instruction_info.nfts_transferred(),
+    fn base_update_venue_signers(
+        did: IdentityId,
+        id: VenueId,
+        signers: Vec<T::AccountId>,
+        add_signers: bool,
+    ) -> DispatchResult {
+        // Ensure venue exists & sender is its creator.
+        Self::venue_for_management(id, did)?;
+
+        if add_signers {
+            for signer in &signers {
+                ensure!(
+                    !Self::venue_signers(&id, &signer),
+                    Error::<T>::SignerAlreadyExists
+                );
+            }
+            for signer in &signers {
+                <VenueSigners<T>>::insert(&id, &signer, true);
+            }
+        } else {
+            for signer in &signers {
+                ensure!(
+                    Self::venue_signers(&id, &signer),
+                    Error::<T>::SignerDoesNotExist
+                );
+            }
+            // Keep enough signers to meet the receipt threshold.
+            let removed = signers.iter().collect::<BTreeSet<_>>().len();
+            let required = removed.saturating_add(Self::receipt_signatories(id) as usize);
+            ensure!(
+                <VenueSigners<T>>::iter_prefix(id).take(required).count() == required,
+                Error::<T>::InvalidReceiptSignatories
             );
+            for signer in &signers {
+                <VenueSigners<T>>::remove(&id, &signer);
+                <VenueSignerUsages<T>>::remove(&id, &signer);
+            }
         }
 
-        <InstructionDetails<T>>::insert(instruction_id, instruction);
+        Self::deposit_event(RawEvent::VenueSignersUpdated(did, id, signers, add_signers));
+        Ok(())
+    }
 
-        InstructionAffirmsPending::insert(
-            instruction_id,
-            u64::try_from(instruction_info.parties().len()).unwrap_or_default(),
+    fn base_reject_instruction(
+        origin: T::RuntimeOrigin,
+        id: InstructionId,
+        portfolio: PortfolioId,
+        fungible_transfers: u32,
+        nfts_transfers: Option<u32>,
+        reason: Option<RejectReason>,
+        memo: Option<Memo>,
+    ) -> DispatchResult {
+        Self::ensure_pending_or_failed(id)?;
+        Self::ensure_valid_memo(&memo)?;
+        // Gets all legs for the instruction, checks if portfolio is in any of the legs, and validates the input cost.
+        let legs_v2: Vec<(LegId, LegV2)> = Self::get_instruction_legs(&id);
+        ensure!(
+            legs_v2
+                .iter()
+                .any(|(_, leg_v2)| leg_v2.from == portfolio || leg_v2.to == portfolio),
+            Error::<T>::CallerIsNotAParty
         );
-        VenueInstructions::insert(venue_id, instruction_id, ());
-        if let Some(ref memo) = memo {
-            InstructionMemos::insert(instruction_id, &memo);
+        let transfer_data = Self::get_transfer_data(&legs_v2)?;
+        Self::ensure_valid_input_cost(&transfer_data, fungible_transfers, nfts_transfers)?;
+
+        // Verifies if the caller has the right permissions for this call
+        let origin_data = Identity::<T>::ensure_origin_call_permissions(origin)?;
+        T::Portfolio::ensure_portfolio_custody_and_permission(
+            portfolio,
+            origin_data.primary_did,
+            origin_data.secondary_key.as_ref(),
+        )?;
+
+        // Parties rejecting after having affirmed pay the cancellation fee, if any.
+        if Self::affirms_received(id, portfolio) == AffirmationStatus::Affirmed {
+            Self::pay_cancellation_fee(id, &origin_data.sender, origin_data.primary_did, &legs_v2)?;
         }
 
-        if emit_deprecated_event {
-            let legs: Result<Vec<Leg>, &str> = legs
-                .into_iter()
-                .map(|leg_v2| Leg::try_from(leg_v2))
-                .collect();
-            let legs: Vec<Leg> = legs.map_err(|_| Error::<T>::InvalidLegAsset)?;
-            legs.iter().enumerate().for_each(|(index, leg)| {
-                InstructionLegs::insert(instruction_id, LegId(index as u64), leg.clone())
-            });
-            Self::deposit_event(RawEvent::InstructionCreated(
-                did,
-                venue_id,
-                instruction_id,
-                settlement_type,
-                trade_date,
-                value_date,
-                legs,
-                memo,
-            ))
-        } else {
-            legs.iter().enumerate().for_each(|(index, leg)| {
-                InstructionLegsV2::insert(instruction_id, LegId(index as u64), leg.clone())
-            });
-            Self::deposit_event(RawEvent::InstructionV2Created(
-                did,
-                venue_id,
-                instruction_id,
-                settlement_type,
-                trade_date,
-                value_date,
-                legs,
-                memo,
-            ));
+        let unexecuted_legs = Self::unexecuted_legs(id, &legs_v2);
+        Self::unsafe_unclaim_receipts(id, &unexecuted_legs);
+        Self::unchecked_release_locks(id, &unexecuted_legs);
+        let _ = T::Scheduler::cancel_named(id.execution_name());
+        Self::close_rejected_instruction(id);
+        Self::record_rejection(origin_data.primary_did, id, reason, memo);
+        Ok(())
+    }
+
+    /// Transfers the cancellation fee of instruction `id` from `payer` to the primary keys
+    /// of the other counterparties, in equal shares. Counterparties without a primary key
+    /// get no share, the whole fee being redistributed among the others.
+    /// Rounding dust stays with `payer`.
+    fn pay_cancellation_fee(
+        id: InstructionId,
+        payer: &T::AccountId,
+        payer_did: IdentityId,
+        legs: &[(LegId, LegV2)],
+    ) -> DispatchResult {
+        let fee = match Self::instruction_cancellation_fee(id) {
+            Some(fee) => fee,
+            None => return Ok(()),
+        };
+        let recipients: Vec<T::AccountId> = legs
+            .iter()
+            .flat_map(|(_, leg)| [leg.from.did, leg.to.did])
+            .filter(|did| *did != payer_did)
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .filter_map(Identity::<T>::get_primary_key)
+            .collect();
+        if recipients.is_empty() {
+            return Ok(());
         }
 
-        Ok(instruction_id)
+        let share = fee / recipients.len() as Balance;
+        let mut paid: Balance = 0;
+        for recipient in recipients {
+            <T as IdentityConfig>::Balances::transfer(
+                payer,
+                &recipient,
+                share,
+                ExistenceRequirement::AllowDeath,
+            )?;
+            paid = paid.saturating_add(share);
+        }
+        Self::deposit_event(RawEvent::CancellationFeePaid(payer_did, id, paid));
+        Ok(())
     }
 
-    /// Makes sure the legs are valid. For both types of assets the sender and receiver must be different,
-    /// the amount being transferred must be greater than zero, and if filtering is enabled the venue list is also checked.
-    /// The number of fungible an non fungible assets in the legs must be within the valid limits allowed.
-    /// Returns a set of the unique counter parties involved in the legs.
-    fn ensure_valid_legs(
-        legs: &[LegV2],
-        venue_id: VenueId,
-    ) -> Result<InstructionInfo, DispatchError> {
-        let mut nfts_transfers = 0;
-        let mut fungible_transfers = 0;
-        let mut parties = BTreeSet::new();
-        let mut tickers = BTreeSet::new();
-        for leg in legs {
-            ensure!(leg.from != leg.to, Error::<T>::SameSenderReceiver);
-            match &leg.asset {
-                LegAsset::Fungible { ticker, amount } => {
-                    ensure!(*amount > 0, Error::<T>::ZeroAmount);
-                    Self::ensure_venue_filtering(&mut tickers, ticker.clone(), &venue_id)?;
-                    fungible_transfers += 1;
-                }
-                LegAsset::NonFungible(nfts) => {
-                    <Nft<T>>::ensure_within_nfts_transfer_limits(&nfts)?;
-                    Self::ensure_venue_filtering(&mut tickers, nfts.ticker().clone(), &venue_id)?;
-                    <Nft<T>>::ensure_no_duplicate_nfts(&nfts)?;
-                    nfts_transfers += nfts.len();
+    /// Returns the account holding the venue fees charged at affirmation until their instruction settles.
+    pub fn venue_fees_escrow() -> T::AccountId {
+        SettlementDID.as_pallet_id().into_account_truncating()
+    }
+
+    /// Charges the venue fee of instruction `id`, if any, to the primary key of `did`
+    /// for each of `portfolios`, and holds it in escrow.
+    fn charge_venue_fees(
+        did: IdentityId,
+        id: InstructionId,
+        portfolios: &BTreeSet<PortfolioId>,
+    ) -> DispatchResult {
+        let fee = match Self::instruction_venue_fee(id) {
+            Some(fee) => fee,
+            None => return Ok(()),
+        };
+        let payer = Identity::<T>::get_primary_key(did).ok_or(Error::<T>::VenueFeePayerNotFound)?;
+        let escrow = Self::venue_fees_escrow();
+        let legs = Self::get_instruction_legs(&id);
+        for portfolio in portfolios {
+            let party_legs = legs
+                .iter()
+                .filter(|(_, leg)| leg.from == *portfolio || leg.to == *portfolio)
+                .count();
+            let amount = fee.amount(party_legs);
+            if amount == 0 {
+                continue;
+            }
+            <T as IdentityConfig>::Balances::transfer(
+                &payer,
+                &escrow,
+                amount,
+                ExistenceRequirement::KeepAlive,
+            )?;
+            VenueFeesCharged::<T>::insert(id, portfolio, (payer.clone(), amount));
+            Self::deposit_event(RawEvent::VenueFeeCharged(did, id, *portfolio, amount));
+        }
+        Ok(())
+    }
+
+    /// Refunds the venue fees held in escrow for `portfolios` in instruction `id`.
+    fn refund_venue_fees(id: InstructionId, portfolios: &BTreeSet<PortfolioId>) {
+        for portfolio in portfolios {
+            if let Some((payer, amount)) = VenueFeesCharged::<T>::take(id, portfolio) {
+                Self::release_venue_fee(&payer, amount);
+                Self::deposit_event(RawEvent::VenueFeeRefunded(id, *portfolio, amount));
+            }
+        }
+    }
+
+    /// Pays the venue fees held in escrow for instruction `id` to the creator of `venue_id`
+    /// if the instruction was `executed`, or refunds them otherwise.
+    fn settle_venue_fees(id: InstructionId, venue_id: VenueId, executed: bool) {
+        let charged: Vec<_> = VenueFeesCharged::<T>::drain_prefix(id).collect();
+        if charged.is_empty() {
+            return;
+        }
+        let recipient = Self::venue_info(venue_id)
+            .filter(|_| executed)
+            .and_then(|venue| {
+                Identity::<T>::get_primary_key(venue.creator).map(|key| (venue.creator, key))
+            });
+        match recipient {
+            Some((creator, key)) => {
+                let total = charged.iter().fold(0, |total: Balance, (_, (_, amount))| {
+                    total.saturating_add(*amount)
+                });
+                Self::release_venue_fee(&key, total);
+                Self::deposit_event(RawEvent::VenueFeesPaid(creator, id, total));
+            }
+            None => {
+                for (portfolio, (payer, amount)) in charged {
+                    Self::release_venue_fee(&payer, amount);
+                    Self::deposit_event(RawEvent::VenueFeeRefunded(id, portfolio, amount));
                 }
             }
-            parties.insert(leg.from);
-            parties.insert(leg.to);
         }
-        ensure!(
-            nfts_transfers <= T::MaxNumberOfNFTs::get() as usize,
-            Error::<T>::MaxNumberOfNFTsExceeded
-        );
-        ensure!(
-            fungible_transfers <= T::MaxNumberOfFungibleAssets::get(),
-            Error::<T>::InstructionHasTooManyLegs
+    }
+
+    /// Transfers `amount` out of the venue fees escrow to `recipient`.
+    fn release_venue_fee(recipient: &T::AccountId, amount: Balance) {
+        // This can never fail since the escrow received `amount` when the fee was charged.
+        let _ = <T as IdentityConfig>::Balances::transfer(
+            &Self::venue_fees_escrow(),
+            recipient,
+            amount,
+            ExistenceRequirement::AllowDeath,
         );
-        Ok(InstructionInfo::new(
-            parties,
-            TransferData::new(fungible_transfers, nfts_transfers as u32),
-        ))
     }
 
-    fn unsafe_withdraw_instruction_affirmation(
+    /// Adds `mediators` to the parties whose affirmation instruction `id` requires.
+    pub fn add_mediators(
         did: IdentityId,
         id: InstructionId,
-        portfolios: BTreeSet<PortfolioId>,
-        secondary_key: Option<&SecondaryKey<T::AccountId>>,
-        fungible_transfers: u32,
-        nfts_transfers: Option<u32>,
-    ) -> Result<u32, DispatchError> {
-        // checks custodianship of portfolios and affirmation status
-        Self::ensure_portfolios_and_affirmation_status(
-            id,
-            &portfolios,
-            did,
-            secondary_key,
-            &[AffirmationStatus::Affirmed],
-        )?;
-        // Unlock tokens that were previously locked during the affirmation
-        let (total_leg_count, filtered_legs) =
-            Self::filtered_legs(&id, &portfolios, fungible_transfers, nfts_transfers)?;
-        for (leg_id, leg_details) in filtered_legs {
-            match Self::instruction_leg_status(id, leg_id) {
-                LegStatus::ExecutionToBeSkipped(signer, receipt_uid) => {
-                    // Receipt was claimed for this instruction. Therefore, no token unlocking is required, we just unclaim the receipt.
-                    <ReceiptsUsed<T>>::insert(&signer, receipt_uid, false);
-                    Self::deposit_event(RawEvent::ReceiptUnclaimed(
-                        did,
-                        id,
-                        leg_id,
-                        receipt_uid,
-                        signer,
-                    ));
-                }
-                LegStatus::ExecutionPending => {
-                    // Tokens are locked, need to be unlocked.
-                    Self::unlock_via_leg(&leg_details)?;
-                }
-                LegStatus::PendingTokenLock => {
-                    return Err(Error::<T>::InstructionNotAffirmed.into());
-                }
-            };
-            <InstructionLegStatus<T>>::insert(id, leg_id, LegStatus::PendingTokenLock);
+        mediators: BTreeSet<IdentityId>,
+    ) -> DispatchResult {
+        ensure!(
+            mediators.len() <= MAX_INSTRUCTION_MEDIATORS as usize,
+            Error::<T>::TooManyMediators
+        );
+        for mediator in &mediators {
+            ensure!(
+                Identity::<T>::is_identity_exists(mediator),
+                Error::<T>::UnknownMediator
+            );
         }
-
-        // Updates storage.
-        for portfolio in &portfolios {
-            UserAffirmations::insert(portfolio, id, AffirmationStatus::Pending);
-            AffirmsReceived::remove(id, portfolio);
-            Self::deposit_event(RawEvent::AffirmationWithdrawn(did, *portfolio, id));
+        if mediators.is_empty() {
+            return Ok(());
         }
 
+        for mediator in &mediators {
+            InstructionMediators::insert(id, mediator, AffirmationStatus::Pending);
+        }
         InstructionAffirmsPending::mutate(id, |affirms_pending| {
-            *affirms_pending += u64::try_from(portfolios.len()).unwrap_or_default()
+            *affirms_pending += u64::try_from(mediators.len()).unwrap_or_default()
         });
-
-        Ok(total_leg_count)
+        Self::deposit_event(RawEvent::InstructionMediatorsAdded(
+            did,
+            id,
+            mediators.into_iter().collect(),
+        ));
+        Ok(())
     }
 
-    fn ensure_instruction_validity(
+    pub fn add_instruction_tags(
+        did: IdentityId,
         id: InstructionId,
-        is_execute: bool,
-    ) -> Result<Instruction<T::Moment, T::BlockNumber>, DispatchError> {
-        let details = Self::instruction_details(id);
-        ensure!(
-            Self::instruction_status(id) != InstructionStatus::Unknown,
-            Error::<T>::UnknownInstruction
-        );
-
-        match (details.settlement_type, is_execute) {
-            // is_execute is true for execution
-            (SettlementType::SettleOnBlock(block_number), true) => {
-                // Ensures block number is less than or equal to current block number.
-                ensure!(
-                    block_number <= System::<T>::block_number(),
-                    Error::<T>::InstructionSettleBlockNotReached
-                );
-            }
-            // is_execute is false for affirmation
-            (SettlementType::SettleOnBlock(block_number), false) => {
-                // Ensures block number is greater than current block number.
-                ensure!(
-                    block_number > System::<T>::block_number(),
-                    Error::<T>::InstructionSettleBlockPassed
-                );
-            }
-            (SettlementType::SettleManual(block_number), true) => {
-                // Ensures block number is less than  or equal to current block number.
-                ensure!(
-                    block_number <= System::<T>::block_number(),
-                    Error::<T>::InstructionSettleBlockNotReached
-                );
-            }
-            (_, _) => {}
+        tags: BTreeSet<InstructionTag>,
+    ) {
+        if tags.is_empty() {
+            return;
         }
 
-        Ok(details)
+        for tag in &tags {
+            TaggedInstructions::insert(tag, id, ());
+        }
+        let tags: Vec<InstructionTag> = tags.into_iter().collect();
+        InstructionTags::insert(id, &tags);
+        Self::deposit_event(RawEvent::InstructionTagsAdded(did, id, tags));
     }
 
-    /// Execute the instruction with `instruction_id`, pruning it on success.
-    /// On error, set the instruction status to failed.
-    fn execute_instruction_retryable(id: InstructionId) -> Result<u32, DispatchError> {
-        let result = Self::execute_instruction(id);
-        if result.is_ok() {
-            Self::prune_instruction(id, true);
-        } else if <InstructionDetails<T>>::contains_key(id) {
-            InstructionStatuses::<T>::insert(id, InstructionStatus::Failed);
+    /// Sets the affirmations of the mediators of instruction `id` back to pending,
+    /// as they have to review the edited instruction again. Returns the number of mediators.
+    fn reset_mediator_affirmations(id: InstructionId) -> usize {
+        let mediators: Vec<IdentityId> = InstructionMediators::iter_prefix(id)
+            .map(|(mediator, _)| mediator)
+            .collect();
+        for mediator in &mediators {
+            InstructionMediators::insert(id, mediator, AffirmationStatus::Pending);
         }
-        result
+        mediators.len()
     }
 
-    fn execute_instruction(instruction_id: InstructionId) -> Result<u32, DispatchError> {
-        // Verifies that there are no pending affirmations for the given instruction
-        ensure!(
-            Self::instruction_affirms_pending(instruction_id) == 0,
-            Error::<T>::InstructionFailed
-        );
+    fn base_affirm_instruction_as_mediator(
+        did: IdentityId,
+        id: InstructionId,
+        fungible_transfers: u32,
+        nfts_transfers: u32,
+    ) -> DispatchResult {
+        Self::ensure_instruction_validity(id, false)?;
+        Self::ensure_mediator_affirmation_status(id, did, AffirmationStatus::Pending)?;
+        let transfer_data = Self::get_transfer_data(&Self::get_instruction_legs(&id))?;
+        Self::ensure_valid_input_cost(&transfer_data, fungible_transfers, Some(nfts_transfers))?;
+        Self::ensure_settlement_not_frozen(&id)?;
+
+        InstructionMediators::insert(id, did, AffirmationStatus::Affirmed);
+        let affirms_pending = Self::instruction_affirms_pending(id).saturating_sub(1);
+        InstructionAffirmsPending::insert(id, affirms_pending);
+        Self::deposit_event(RawEvent::MediatorAffirmationReceived(did, id));
 
-        // Verifies that the instruction is not in a Failed or in an Unknown state
-        let details = Self::instruction_details(instruction_id);
-        ensure!(
-            Self::instruction_status(instruction_id) == InstructionStatus::Pending,
-            Error::<T>::InstructionNotPending
+        // Schedule the instruction if conditions are met
+        Self::maybe_schedule_instruction(
+            affirms_pending,
+            id,
+            transfer_data.fungible(),
+            transfer_data.non_fungible(),
         );
+        Ok(())
+    }
 
-        let mut instruction_legs: Vec<(LegId, LegV2)> = Self::get_instruction_legs(&instruction_id);
-        // NB: The order of execution of the legs matter in some edge cases around compliance.
-        // E.g: Consider a token with a total supply of 100 and maximum percentage ownership of 10%.
-        // In a given moment, Alice owns 10 tokens, Bob owns 5 and Charlie owns 0.
-        // Now, consider one instruction with two legs: 1. Alice transfers 5 tokens to Charlie; 2. Bob transfers 5 tokens to Alice;
-        // If the second leg gets executed before the first leg, Alice will momentarily hold 15% of the asset and hence the settlement will fail compliance.
-        instruction_legs.sort_by_key(|leg_id_leg| leg_id_leg.0);
-
-        // Verifies that the venue still has the required permissions for the tokens involved.
-        let mut tickers: BTreeSet<Ticker> = BTreeSet::new();
-        for (_, leg) in &instruction_legs {
-            // Each ticker is only checked once
-            let ticker = leg.asset.ticker_and_amount().0;
-            if tickers.insert(ticker)
-                && Self::venue_filtering(ticker)
-                && !Self::venue_allow_list(ticker, details.venue_id)
-            {
-                Self::deposit_event(RawEvent::VenueUnauthorized(
-                    SettlementDID.as_id(),
-                    ticker,
-                    details.venue_id,
-                ));
-                return Err(Error::<T>::UnauthorizedVenue.into());
-            }
-        }
+    fn base_withdraw_affirmation_as_mediator(did: IdentityId, id: InstructionId) -> DispatchResult {
+        let details = Self::ensure_instruction_validity(id, false)?;
+        Self::ensure_execution_not_started(id)?;
+        Self::ensure_mediator_affirmation_status(id, did, AffirmationStatus::Affirmed)?;
 
-        match frame_storage_with_transaction(|| {
-            Self::release_asset_locks_and_transfer_pending_legs(instruction_id, &instruction_legs)
-        })? {
-            Ok(_) => {
-                Self::deposit_event(RawEvent::InstructionExecuted(
-                    SettlementDID.as_id(),
-                    instruction_id,
-                ));
-            }
-            Err(leg_id) => {
-                Self::deposit_event(RawEvent::LegFailedExecution(
-                    SettlementDID.as_id(),
-                    instruction_id,
-                    leg_id,
-                ));
-                Self::deposit_event(RawEvent::InstructionFailed(
-                    SettlementDID.as_id(),
-                    instruction_id,
-                ));
-                // Unclaim receipts for the failed transaction so that they can be reused
-                Self::unsafe_unclaim_receipts(instruction_id, &instruction_legs);
-                return Err(Error::<T>::InstructionFailed.into());
-            }
+        InstructionMediators::insert(id, did, AffirmationStatus::Pending);
+        InstructionAffirmsPending::mutate(id, |affirms_pending| *affirms_pending += 1);
+        if details.settlement_type.settles_on_affirmation() {
+            // Cancel the scheduled task for the execution of a given instruction.
+            let _ = T::Scheduler::cancel_named(id.execution_name());
         }
+        Self::deposit_event(RawEvent::MediatorAffirmationWithdrawn(did, id));
+        Ok(())
+    }
 
-        Ok(instruction_legs.len().try_into().unwrap_or_default())
+    /// Ensures `did` is a mediator of instruction `id` with the `expected` affirmation status.
+    fn ensure_mediator_affirmation_status(
+        id: InstructionId,
+        did: IdentityId,
+        expected: AffirmationStatus,
+    ) -> DispatchResult {
+        let status = Self::instruction_mediators(id, did);
+        ensure!(
+            status != AffirmationStatus::Unknown,
+            Error::<T>::CallerIsNotAMediator
+        );
+        ensure!(status == expected, Error::<T>::UnexpectedAffirmationStatus);
+        Ok(())
     }
 
-    fn release_asset_locks_and_transfer_pending_legs(
-        instruction_id: InstructionId,
-        instruction_legs: &[(LegId, LegV2)],
-    ) -> TransactionOutcome<Result<Result<(), LegId>, DispatchError>> {
-        Self::unchecked_release_locks(instruction_id, instruction_legs);
-        for (leg_id, leg) in instruction_legs {
-            if Self::instruction_leg_status(instruction_id, leg_id) == LegStatus::ExecutionPending {
-                match &leg.asset {
-                    LegAsset::Fungible { ticker, amount } => {
-                        if <Asset<T>>::base_transfer(leg.from, leg.to, &ticker, *amount).is_err() {
-                            return TransactionOutcome::Rollback(Ok(Err(*leg_id)));
-                        }
-                    }
-                    LegAsset::NonFungible(nfts) => {
-                        if <Nft<T>>::base_nft_transfer(&leg.from, &leg.to, &nfts).is_err() {
-                            return TransactionOutcome::Rollback(Ok(Err(*leg_id)));
-                        }
-                    }
-                }
-            }
+    /// Ensures `did` is the creator of the venue `venue_id` of instruction `id`, or its operator.
+    fn ensure_instruction_manager(
+        id: InstructionId,
+        venue_id: VenueId,
+        did: IdentityId,
+    ) -> DispatchResult {
+        if Self::instruction_operator(id) == Some(did) {
+            return Ok(());
         }
-        TransactionOutcome::Commit(Ok(Ok(())))
+        Self::venue_for_management(venue_id, did)?;
+        Ok(())
     }
 
-    fn prune_instruction(id: InstructionId, executed: bool) {
-        let legs: Vec<(LegId, LegV2)> = Self::drain_instruction_legs(&id);
-        let details = <InstructionDetails<T>>::take(id);
-        VenueInstructions::remove(details.venue_id, id);
-        #[allow(deprecated)]
-        <InstructionLegStatus<T>>::remove_prefix(id, None);
-        InstructionAffirmsPending::remove(id);
-        #[allow(deprecated)]
-        AffirmsReceived::remove_prefix(id, None);
-
-        if executed {
-            InstructionStatuses::<T>::insert(
-                id,
-                InstructionStatus::Success(System::<T>::block_number()),
-            );
-        } else {
-            InstructionStatuses::<T>::insert(
-                id,
-                InstructionStatus::Rejected(System::<T>::block_number()),
+    fn base_set_instruction_operator(
+        did: IdentityId,
+        id: InstructionId,
+        operator: Option<IdentityId>,
+    ) -> DispatchResult {
+        Self::ensure_pending_or_failed(id)?;
+        Self::venue_for_management(Self::instruction_details(id).venue_id, did)?;
+        if let Some(operator) = &operator {
+            ensure!(
+                Identity::<T>::is_identity_exists(operator),
+                Error::<T>::UnknownOperator
             );
         }
 
-        // We remove duplicates in memory before triggering storage actions
-        let mut counter_parties = BTreeSet::new();
-        for (_, leg) in &legs {
-            counter_parties.insert(leg.from);
-            counter_parties.insert(leg.to);
-        }
-        for counter_party in counter_parties {
-            UserAffirmations::remove(counter_party, id);
-        }
+        InstructionOperators::mutate(id, |op| *op = operator);
+        Self::deposit_event(RawEvent::InstructionOperatorSet(did, id, operator));
+        Ok(())
     }
 
-    pub fn unsafe_affirm_instruction(
+    fn base_reject_instruction_as_operator(
         did: IdentityId,
         id: InstructionId,
-        portfolios: BTreeSet<PortfolioId>,
         fungible_transfers: u32,
-        nfts_trasferred: Option<u32>,
-        secondary_key: Option<&SecondaryKey<T::AccountId>>,
-    ) -> Result<u32, DispatchError> {
-        // Checks portfolio's custodian and if it is a counter party with a pending affirmation.
-        Self::ensure_portfolios_and_affirmation_status(
-            id,
-            &portfolios,
-            did,
-            secondary_key,
-            &[AffirmationStatus::Pending],
-        )?;
-
-        let (total_leg_count, filtered_legs) =
-            Self::filtered_legs(&id, &portfolios, fungible_transfers, nfts_trasferred)?;
-        with_transaction(|| {
-            for (leg_id, leg_details) in filtered_legs {
-                Self::lock_via_leg(&leg_details)?;
-                <InstructionLegStatus<T>>::insert(id, leg_id, LegStatus::ExecutionPending);
-            }
-            Ok(())
-        })
-        .map_err(|e: DispatchError| e)?;
+        nfts_transfers: u32,
+        reason: Option<RejectReason>,
+        memo: Option<Memo>,
+    ) -> DispatchResult {
+        Self::ensure_pending_or_failed(id)?;
+        Self::ensure_valid_memo(&memo)?;
+        Self::ensure_instruction_manager(id, Self::instruction_details(id).venue_id, did)?;
+        let legs_v2: Vec<(LegId, LegV2)> = Self::get_instruction_legs(&id);
+        let transfer_data = Self::get_transfer_data(&legs_v2)?;
+        Self::ensure_valid_input_cost(&transfer_data, fungible_transfers, Some(nfts_transfers))?;
 
-        let affirms_pending = Self::instruction_affirms_pending(id);
+        let unexecuted_legs = Self::unexecuted_legs(id, &legs_v2);
+        Self::unsafe_unclaim_receipts(id, &unexecuted_legs);
+        Self::unchecked_release_locks(id, &unexecuted_legs);
+        let _ = T::Scheduler::cancel_named(id.execution_name());
+        Self::close_rejected_instruction(id);
+        Self::record_rejection(did, id, reason, memo);
+        Ok(())
+    }
 
-        // Updates storage
-        for portfolio in &portfolios {
-            UserAffirmations::insert(portfolio, id, AffirmationStatus::Affirmed);
-            AffirmsReceived::insert(id, portfolio, AffirmationStatus::Affirmed);
-            Self::deposit_event(RawEvent::InstructionAffirmed(did, *portfolio, id));
+    /// Stores the `reason` and `memo` given by `did` for rejecting instruction `id`.
+    /// A memo given without a reason is recorded under `RejectReason::Other`.
+    fn record_rejection(
+        did: IdentityId,
+        id: InstructionId,
+        reason: Option<RejectReason>,
+        memo: Option<Memo>,
+    ) {
+        if reason.is_some() || memo.is_some() {
+            let stored_reason = reason.unwrap_or(RejectReason::Other);
+            InstructionRejections::insert(id, (stored_reason, memo.clone()));
         }
-        InstructionAffirmsPending::insert(
-            id,
-            affirms_pending.saturating_sub(u64::try_from(portfolios.len()).unwrap_or_default()),
-        );
+        Self::deposit_event(RawEvent::InstructionRejected(did, id, reason, memo));
+    }
 
-        Ok(total_leg_count)
+    /// Returns the `legs` of instruction `id` not executed yet by an execution spanning several blocks.
+    fn unexecuted_legs(id: InstructionId, legs: &[(LegId, LegV2)]) -> Vec<(LegId, LegV2)> {
+        let next_leg_id = Self::instruction_execution_progress(id)
+            .map(|progress| progress.next_leg_id)
+            .unwrap_or_default();
+        legs.iter()
+            .filter(|(leg_id, _)| *leg_id >= next_leg_id)
+            .cloned()
+            .collect()
     }
 
-    // Unclaims all receipts for an instruction
-    // Should only be used if user is unclaiming, or instruction has failed
-    fn unsafe_unclaim_receipts(id: InstructionId, legs: &[(LegId, LegV2)]) {
-        for (leg_id, _) in legs {
-            match Self::instruction_leg_status(id, leg_id) {
-                LegStatus::ExecutionToBeSkipped(signer, receipt_uid) => {
-                    <ReceiptsUsed<T>>::insert(&signer, receipt_uid, false);
-                    Self::deposit_event(RawEvent::ReceiptUnclaimed(
-                        SettlementDID.as_id(),
-                        id,
-                        *leg_id,
-                        receipt_uid,
-                        signer,
-                    ));
-                }
-                LegStatus::PendingTokenLock | LegStatus::ExecutionPending => {}
-            }
+    /// Ensures no leg of instruction `id` has been executed by an execution spanning several blocks.
+    fn ensure_execution_not_started(id: InstructionId) -> DispatchResult {
+        ensure!(
+            !InstructionExecutionProgress::contains_key(id),
+            Error::<T>::InstructionExecutionInProgress
+        );
+        Ok(())
+    }
+
+    /// Ensures instruction `id` has not been settled, rejected or expired yet.
+    fn ensure_pending_or_failed(id: InstructionId) -> DispatchResult {
+        match Self::instruction_status(id) {
+            InstructionStatus::Pending | InstructionStatus::Failed => Ok(()),
+            InstructionStatus::Unknown => Err(Error::<T>::UnknownInstruction.into()),
+            _ => Err(Error::<T>::InstructionNotPending.into()),
         }
     }
 
-    fn unchecked_release_locks(id: InstructionId, instruction_legs: &[(LegId, LegV2)]) {
-        for (leg_id, leg) in instruction_legs {
-            match Self::instruction_leg_status(id, leg_id) {
-                LegStatus::ExecutionPending => {
-                    // This can never return an error since the settlement module
-                    // must've locked these tokens when instruction was affirmed
-                    let _ = Self::unlock_via_leg(&leg);
+    /// Returns the number of fungible and non fungible transfers in a slice of legs.
+    /// In case that T::MaxNumberOfNFTsPerLeg is exceeded an error will be returned.
+    fn get_transfer_data(legs_v2: &[(LegId, LegV2)]) -> Result<TransferData, DispatchError> {
+        let mut nfts_transfers = 0;
+        let mut fungible_transfers = 0;
+        for (_, leg_v2) in legs_v2 {
+            match &leg_v2.asset {
+                LegAsset::Fungible { .. } => fungible_transfers += 1,
+                LegAsset::NonFungible(nfts) => {
+                    ensure!(
+                        nfts.len() <= T::MaxNumberOfNFTsPerLeg::get() as usize,
+                        Error::<T>::MaxNumberOfNFTsPerLegExceeded
+                    );
+                    nfts_transfers += nfts.len();
                 }
-                LegStatus::ExecutionToBeSkipped(_, _) | LegStatus::PendingTokenLock => {}
             }
         }
+        Ok(TransferData::new(fungible_transfers, nfts_transfers as u32))
     }
 
-    /// Schedule a given instruction to be executed on the next block only if the
-    /// settlement type is `SettleOnAffirmation` and no. of affirms pending is 0.
-    fn maybe_schedule_instruction(
-        affirms_pending: u64,
-        id: InstructionId,
+    /// Ensures that `memo` is not longer than `MAX_MEMO_LEN` bytes.
+    fn ensure_valid_memo(memo: &Option<Memo>) -> DispatchResult {
+        ensure!(
+            memo.as_ref().map_or(true, Memo::is_valid_len),
+            Error::<T>::MemoTooLong
+        );
+        Ok(())
+    }
+
+    /// Returns ok if the number of fungible assets and nfts being transferred is under the input given by the user.
+    pub fn ensure_valid_input_cost(
+        transfer_data: &TransferData,
         fungible_transfers: u32,
-        nfts_tranferred: u32,
-    ) {
-        if affirms_pending == 0
-            && Self::instruction_details(id).settlement_type == SettlementType::SettleOnAffirmation
-        {
-            // Schedule instruction to be executed in the next block.
-            let execution_at = System::<T>::block_number() + One::one();
-            Self::schedule_instruction(id, execution_at, fungible_transfers, nfts_tranferred);
+        nfts_transfers: Option<u32>,
+    ) -> DispatchResult {
+        // Verifies if the number of nfts being transferred is under the limit
+        if transfer_data.non_fungible() > 0 {
+            let nfts_transfers = nfts_transfers.ok_or(Error::<T>::DeprecatedCallOnV2Instruction)?;
+            ensure!(
+                transfer_data.non_fungible() <= nfts_transfers,
+                Error::<T>::NumberOfTransferredNFTsUnderestimated
+            );
         }
+        // Verifies if the number of fungible transfers is under the limit
+        ensure!(
+            transfer_data.fungible() <= fungible_transfers,
+            Error::<T>::LegCountTooSmall
+        );
+        Ok(())
     }
 
-    /// Schedule execution of given instruction at given block number.
-    ///
-    /// NB - It is expected to execute the given instruction into the given block number but
-    /// it is not a guaranteed behavior, Scheduler may have other high priority task scheduled
-    /// for the given block so there are chances where the instruction execution block no. may drift.
-    fn schedule_instruction(
-        id: InstructionId,
-        execution_at: T::BlockNumber,
-        _fungible_transfers: u32,
-        _nfts_transfers: u32,
-    ) {
-        let call = Call::<T>::execute_scheduled_instruction_v2 {
-            id,
-            _fungible_transfers,
-            _nfts_transfers,
-        }
-        .into();
-        if let Err(_) = T::Scheduler::schedule_named(
-            id.execution_name(),
-            DispatchTime::At(execution_at),
-            None,
-            SETTLEMENT_INSTRUCTION_EXECUTION_PRIORITY,
-            RawOrigin::Root.into(),
-            call,
-        ) {
-            Self::deposit_event(RawEvent::SchedulingFailed(
-                Error::<T>::FailedToSchedule.into(),
-            ));
+    /// If `tickers` doesn't contain the given `ticker` and venue_filtering is enabled, ensures that venue_id is in the allowed list
+    fn ensure_venue_filtering(
+        tickers: &mut BTreeSet<Ticker>,
+        ticker: Ticker,
+        venue_id: &VenueId,
+    ) -> DispatchResult {
+        if tickers.insert(ticker) && Self::venue_filtering(ticker) {
+            ensure!(
+                Self::venue_allow_list(ticker, venue_id),
+                Error::<T>::UnauthorizedVenue
+            );
         }
+        Ok(())
     }
 
-    pub fn base_affirm_with_receipts(
-        origin: <T as frame_system::Config>::RuntimeOrigin,
-        id: InstructionId,
-        receipt_details: Vec<ReceiptDetails<T::AccountId, T::OffChainSignature>>,
-        portfolios: Vec<PortfolioId>,
-        fungible_transfers: u32,
-    ) -> Result<u32, DispatchError> {
-        let (did, secondary_key, instruction_details) =
-            Self::ensure_origin_perm_and_instruction_validity(origin, id, false)?;
-        let portfolios_set = portfolios.into_iter().collect::<BTreeSet<_>>();
-
-        // Verify that the receipts provided are unique
-        let receipt_ids = receipt_details
+    /// Returns the tickers, out of `tickers`, that `venue_id` is not allowed to settle.
+    ///
+    /// A venue can settle a ticker unless venue filtering is enabled for the ticker
+    /// and the venue is not part of the ticker's allow list.
+    /// Returns `InvalidVenue` if `venue_id` doesn't exist.
+    pub fn unauthorized_venue_tickers(
+        venue_id: &VenueId,
+        tickers: &[Ticker],
+    ) -> Result<Vec<Ticker>, DispatchError> {
+        ensure!(VenueInfo::contains_key(venue_id), Error::<T>::InvalidVenue);
+        Ok(tickers
             .iter()
-            .map(|receipt| (receipt.signer.clone(), receipt.receipt_uid))
-            .collect::<BTreeSet<_>>();
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .filter(|ticker| {
+                Self::venue_filtering(*ticker) && !Self::venue_allow_list(*ticker, venue_id)
+            })
+            .copied()
+            .collect())
+    }
 
-        ensure!(
-            receipt_ids.len() == receipt_details.len(),
-            Error::<T>::ReceiptAlreadyClaimed
-        );
+    /// Returns the specified leg for the given instruction and leg id.
+    /// If it doesn't exist in the InstructionLegsV2 storage it will be converted from the deprecated InstructionLegs storage.
+    pub fn get_instruction_leg(instruction_id: &InstructionId, leg_id: &LegId) -> LegV2 {
+        InstructionLegsV2::try_get(instruction_id, leg_id)
+            .unwrap_or_else(|_| InstructionLegs::get(instruction_id, leg_id).into())
+    }
 
-        // Verify portfolio custodianship and check if it is a counter party with a pending affirmation.
-        Self::ensure_portfolios_and_affirmation_status(
-            id,
-            &portfolios_set,
-            did,
-            secondary_key.as_ref(),
-            &[AffirmationStatus::Pending],
-        )?;
+    /// Returns all legs and their id for the given instruction.
+    /// If it doesn't exist in the InstructionLegsV2 storage it will be converted from the deprecated InstructionLegs storage.
+    pub fn get_instruction_legs(instruction_id: &InstructionId) -> Vec<(LegId, LegV2)> {
+        let instruction_legs: Vec<(LegId, LegV2)> =
+            InstructionLegsV2::iter_prefix(instruction_id).collect();
 
-        // Verify that the receipts are valid
-        for receipt in &receipt_details {
-            ensure!(
-                Self::venue_signers(&instruction_details.venue_id, &receipt.signer),
-                Error::<T>::UnauthorizedSigner
-            );
-            ensure!(
-                !Self::receipts_used(&receipt.signer, &receipt.receipt_uid),
-                Error::<T>::ReceiptAlreadyClaimed
-            );
+        if instruction_legs.is_empty() {
+            return InstructionLegs::iter_prefix(instruction_id)
+                .map(|(leg_id, leg)| (leg_id, leg.into()))
+                .collect();
+        }
+        instruction_legs
+    }
 
-            let leg = Self::get_instruction_leg(&id, &receipt.leg_id);
-            if let LegAsset::NonFungible(_nfts) = leg.asset {
-                return Err(Error::<T>::ReceiptForNonFungibleAsset.into());
+    /// Prunes the statuses of instructions settled more than `InstructionStatusRetention` blocks before `now`,
+    /// oldest first, while `remaining_weight` allows it. Returns the consumed weight.
+    fn prune_instruction_statuses(now: T::BlockNumber, remaining_weight: Weight) -> Weight {
+        let retention = match T::InstructionStatusRetention::get() {
+            Some(retention) => retention,
+            None => return Weight::zero(),
+        };
+        let cursor = Self::status_prune_cursor();
+        if cursor.is_zero() {
+            return Weight::zero();
+        }
+
+        let step_weight = <T as Config>::WeightInfo::prune_instruction_status();
+        let mut consumed = Weight::zero();
+        let mut block = cursor;
+        let mut pruned = Vec::new();
+        while block.saturating_add(retention) < now
+            && consumed
+                .saturating_add(step_weight)
+                .all_lte(remaining_weight)
+        {
+            consumed = consumed.saturating_add(step_weight);
+            match SettledInstructions::<T>::iter_key_prefix(block).next() {
+                Some(id) => {
+                    SettledInstructions::<T>::remove(block, id);
+                    InstructionStatuses::<T>::remove(id);
+                    <InstructionAuditRecords<T>>::remove(id);
+                    InstructionAuditDigests::remove(id);
+                    pruned.push(id);
+                }
+                None => block = block.saturating_add(One::one()),
             }
-            ensure!(
-                portfolios_set.contains(&leg.from),
-                Error::<T>::PortfolioMismatch
-            );
+        }
 
-            let (asset, amount) = leg.asset.ticker_and_amount();
-            ensure!(
-                !pallet_asset::Tokens::contains_key(&asset),
-                Error::<T>::UnauthorizedVenue
-            );
+        if block != cursor {
+            StatusPruneCursor::<T>::put(block);
+        }
+        if !pruned.is_empty() {
+            Self::deposit_event(RawEvent::InstructionStatusesPruned(pruned));
+        }
+        consumed
+    }
 
-            let msg = Receipt {
-                receipt_uid: receipt.receipt_uid,
-                from: leg.from,
-                to: leg.to,
-                asset,
-                amount,
-            };
-            ensure!(
-                receipt.signature.verify(&msg.encode()[..], &receipt.signer),
-                Error::<T>::InvalidSignature
-            );
+    /// Prunes the legs and affirmations of instructions rejected more than `RejectedInstructionRetention`
+    /// blocks before `now`, oldest first, while `remaining_weight` allows it. Returns the consumed weight.
+    fn prune_rejected_instructions(now: T::BlockNumber, remaining_weight: Weight) -> Weight {
+        let cursor = Self::rejected_prune_cursor();
+        if cursor.is_zero() {
+            return Weight::zero();
         }
 
-        let (total_leg_count, filtered_legs) =
-            Self::filtered_legs(&id, &portfolios_set, fungible_transfers, None)?;
-        // Lock tokens that do not have a receipt attached to their leg.
-        with_transaction(|| {
-            for (leg_id, leg_details) in filtered_legs {
-                // Receipt for the leg was provided
-                if let Some(receipt) = receipt_details
-                    .iter()
-                    .find(|receipt| receipt.leg_id == leg_id)
-                {
-                    <InstructionLegStatus<T>>::insert(
-                        id,
-                        leg_id,
-                        LegStatus::ExecutionToBeSkipped(
-                            receipt.signer.clone(),
-                            receipt.receipt_uid,
-                        ),
-                    );
-                } else if let Err(_) = Self::lock_via_leg(&leg_details) {
-                    // rustc fails to infer return type of `with_transaction` if you use ?/map_err here
-                    return Err(DispatchError::from(Error::<T>::FailedToLockTokens));
-                } else {
-                    <InstructionLegStatus<T>>::insert(id, leg_id, LegStatus::ExecutionPending);
+        let retention = T::RejectedInstructionRetention::get();
+        let max_legs =
+            T::MaxNumberOfFungibleAssets::get().saturating_add(T::MaxNumberOfNFTs::get());
+        let step_weight = <T as Config>::WeightInfo::prune_rejected_instruction(max_legs);
+        let mut consumed = Weight::zero();
+        let mut block = cursor;
+        let mut pruned = Vec::new();
+        while block.saturating_add(retention) < now
+            && consumed
+                .saturating_add(step_weight)
+                .all_lte(remaining_weight)
+        {
+            consumed = consumed.saturating_add(step_weight);
+            match RejectedInstructions::<T>::iter_key_prefix(block).next() {
+                Some(id) => {
+                    RejectedInstructions::<T>::remove(block, id);
+                    Self::prune_legs_and_affirmations(id);
+                    pruned.push(id);
                 }
+                None => block = block.saturating_add(One::one()),
             }
-            Ok(())
-        })?;
+        }
+
+        if block != cursor {
+            RejectedPruneCursor::<T>::put(block);
+        }
+        if !pruned.is_empty() {
+            Self::deposit_event(RawEvent::RejectedInstructionsPruned(pruned));
+        }
+        consumed
+    }
+
+    /// Replaces the placeholder of `alias` by `portfolio` in the legs and affirmations of instruction `id`,
+    /// whose only pending affirmation must be the one of `alias`.
+    fn base_reveal_portfolio_alias(
+        did: IdentityId,
+        id: InstructionId,
+        alias: PortfolioAlias,
+        portfolio: PortfolioId,
+        salt: [u8; 32],
+        fungible_transfers: u32,
+        nfts_transfers: u32,
+    ) -> DispatchResult {
+        ensure!(
+            Self::instruction_status(id) == InstructionStatus::Pending,
+            Error::<T>::InstructionNotPending
+        );
+        ensure!(
+            PortfolioAlias::new(&portfolio, &salt) == alias,
+            Error::<T>::InvalidPortfolioAlias
+        );
+        let placeholder = alias.placeholder();
+        ensure!(
+            UserAffirmations::contains_key(placeholder, id),
+            Error::<T>::PortfolioAliasNotFound
+        );
+        ensure!(
+            !UserAffirmations::contains_key(portfolio, id),
+            Error::<T>::PortfolioAlreadyCounterparty
+        );
+        ensure!(
+            Self::instruction_affirms_pending(id) == 1,
+            Error::<T>::InstructionNotAffirmed
+        );
+        let instruction_legs = Self::get_instruction_legs(&id);
+        let transfer_data = Self::get_transfer_data(&instruction_legs)?;
+        Self::ensure_valid_input_cost(&transfer_data, fungible_transfers, Some(nfts_transfers))?;
 
-        // Update storage
-        let affirms_pending = Self::instruction_affirms_pending(id)
-            .saturating_sub(u64::try_from(portfolios_set.len()).unwrap_or_default());
+        for (leg_id, mut leg) in instruction_legs {
+            if leg.from != placeholder && leg.to != placeholder {
+                continue;
+            }
+            if leg.from == placeholder {
+                leg.from = portfolio;
+            }
+            if leg.to == placeholder {
+                leg.to = portfolio;
+            }
+            InstructionLegsV2::insert(id, leg_id, leg);
+        }
+        let affirmation = UserAffirmations::take(placeholder, id);
+        UserAffirmations::insert(portfolio, id, affirmation);
+        Self::deposit_event(RawEvent::PortfolioAliasRevealed(did, id, alias, portfolio));
+        Ok(())
+    }
 
-        // Mark receipts used in affirmation as claimed
-        for receipt in &receipt_details {
-            <ReceiptsUsed<T>>::insert(&receipt.signer, receipt.receipt_uid, true);
-            Self::deposit_event(RawEvent::ReceiptClaimed(
-                did,
-                id,
-                receipt.leg_id,
-                receipt.receipt_uid,
-                receipt.signer.clone(),
-                receipt.metadata.clone(),
-            ));
+    /// Moves the legs of `instruction_id` from the deprecated InstructionLegs storage to InstructionLegsV2.
+    fn migrate_legacy_legs(instruction_id: &InstructionId) {
+        for (leg_id, leg) in InstructionLegs::drain_prefix(instruction_id) {
+            InstructionLegsV2::insert(instruction_id, leg_id, LegV2::from(leg));
         }
+    }
 
-        for portfolio in portfolios_set {
-            UserAffirmations::insert(portfolio, id, AffirmationStatus::Affirmed);
-            AffirmsReceived::insert(id, portfolio, AffirmationStatus::Affirmed);
-            Self::deposit_event(RawEvent::InstructionAffirmed(did, portfolio, id));
+    /// Returns the revision history of every leg that has been part of the instruction, ordered by `LegId`.
+    /// Legs that were removed by an edit are included, with `removed_in` set.
+    pub fn instruction_leg_history(instruction_id: &InstructionId) -> Vec<(LegId, LegRevision)> {
+        let mut history: BTreeMap<LegId, LegRevision> = Self::get_instruction_legs(instruction_id)
+            .into_iter()
+            .map(|(leg_id, _)| (leg_id, LegRevision::default()))
+            .collect();
+        history.extend(InstructionLegRevisions::iter_prefix(instruction_id));
+        history.into_iter().collect()
+    }
+
+    /// Returns the receipts currently claimed by `signer`, with the instruction and leg they settled
+    /// and the metadata they were claimed with, ordered by receipt uid.
+    pub fn receipts_used_by(signer: &T::AccountId) -> Vec<(u64, ReceiptUsageDetails)> {
+        let mut receipts: Vec<_> = <ReceiptUsage<T>>::iter_prefix(signer).collect();
+        receipts.sort_unstable_by_key(|(receipt_uid, _)| *receipt_uid);
+        receipts
+    }
+
+    /// Builds the audit record of the instruction with `details` and `legs`, executed in the current block.
+    /// Must be called before the affirmations and leg statuses of the instruction are pruned.
+    fn audit_record(
+        details: &Instruction<T::Moment, T::BlockNumber>,
+        legs: &[(LegId, LegV2)],
+    ) -> InstructionAuditRecord<T::AccountId, T::Moment, T::BlockNumber> {
+        let id = details.instruction_id;
+        let affirmations = AffirmsReceived::iter_prefix(id)
+            .filter(|(_, status)| *status == AffirmationStatus::Affirmed)
+            .map(|(portfolio, _)| portfolio)
+            .collect();
+        let receipts = legs
+            .iter()
+            .filter_map(|(leg_id, _)| {
+                let status = Self::instruction_leg_status(id, leg_id);
+                match status {
+                    LegStatus::ExecutionToBeSkipped(signer, uid) => Some((*leg_id, signer, uid)),
+                    _ => None,
+                }
+            })
+            .collect();
+        InstructionAuditRecord {
+            instruction_id: id,
+            venue_id: details.venue_id,
+            created_at: details.created_at,
+            trade_date: details.trade_date,
+            value_date: details.value_date,
+            legs: legs.to_vec(),
+            affirmations,
+            receipts,
+            executed_at: System::<T>::block_number(),
+            executed_on: <pallet_timestamp::Pallet<T>>::get(),
         }
+    }
 
-        InstructionAffirmsPending::insert(id, affirms_pending);
-        Ok(total_leg_count)
+    /// Returns the audit record of the executed instruction `id` with its digest,
+    /// unless the instruction isn't executed or its record is pruned.
+    pub fn instruction_audit_export(id: InstructionId) -> Option<InstructionAuditExport> {
+        let record = Self::instruction_audit_record(id)?.encode();
+        let digest = Self::instruction_audit_digest(id)?;
+        Some(InstructionAuditExport { record, digest })
     }
 
-    pub fn base_affirm_instruction(
-        origin: <T as frame_system::Config>::RuntimeOrigin,
-        id: InstructionId,
-        portfolios: impl Iterator<Item = PortfolioId>,
-        fungible_transfers: u32,
-        nfts_transferred: Option<u32>,
-    ) -> Result<u32, DispatchError> {
-        let (did, sk, _) = Self::ensure_origin_perm_and_instruction_validity(origin, id, false)?;
-        let portfolios_set = portfolios.collect::<BTreeSet<_>>();
+    /// Returns whether `record` is the SCALE encoding of the audit record of the executed instruction `id`.
+    /// Records can only be verified until the status of the instruction is pruned.
+    pub fn verify_instruction_audit(id: InstructionId, record: &[u8]) -> bool {
+        Self::instruction_audit_digest(id) == Some(blake2_256(record))
+    }
 
-        // Provide affirmation to the instruction
-        Self::unsafe_affirm_instruction(
-            did,
-            id,
-            portfolios_set,
-            fungible_transfers,
-            nfts_transferred,
-            sk.as_ref(),
-        )
+    /// Returns the signers of `venue_id`, with how much each was used to sign receipts.
+    pub fn venue_signers_usage(
+        venue_id: VenueId,
+    ) -> Vec<(T::AccountId, VenueSignerUsage<T::BlockNumber>)> {
+        <VenueSigners<T>>::iter_prefix(venue_id)
+            .map(|(signer, _)| {
+                let usage = Self::venue_signer_usage(venue_id, &signer);
+                (signer, usage)
+            })
+            .collect()
     }
 
-    // It affirms the instruction and may schedule the instruction
-    // depends on the settlement type.
-    pub fn affirm_with_receipts_and_maybe_schedule_instruction(
-        origin: <T as frame_system::Config>::RuntimeOrigin,
-        id: InstructionId,
-        receipt_details: Vec<ReceiptDetails<T::AccountId, T::OffChainSignature>>,
-        portfolios: Vec<PortfolioId>,
-        fungible_transfers: u32,
-    ) -> DispatchResult {
-        let legs_count = Self::base_affirm_with_receipts(
-            origin,
-            id,
-            receipt_details,
-            portfolios,
-            fungible_transfers,
-        )?;
-        // Schedule instruction to be execute in the next block (expected) if conditions are met.
-        Self::maybe_schedule_instruction(Self::instruction_affirms_pending(id), id, legs_count, 0);
-        Ok(())
+    /// Returns the instructions awaiting an affirmation from any of the portfolios owned by `did`,
+    /// ordered by `InstructionId`.
+    pub fn pending_instructions(did: IdentityId) -> Vec<InstructionId> {
+        let portfolios = pallet_portfolio::Portfolios::iter_prefix(did)
+            .map(|(num, _)| PortfolioId::user_portfolio(did, num))
+            .chain(core::iter::once(PortfolioId::default_portfolio(did)));
+        portfolios
+            .flat_map(UserAffirmations::iter_prefix)
+            .filter(|(_, status)| *status == AffirmationStatus::Pending)
+            .map(|(id, _)| id)
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect()
     }
 
-    /// Schedule settlement instruction execution in the next block, unless already scheduled.
-    /// Used for general purpose settlement.
-    pub fn affirm_and_maybe_schedule_instruction(
-        origin: <T as frame_system::Config>::RuntimeOrigin,
-        id: InstructionId,
-        portfolios: impl Iterator<Item = PortfolioId>,
-        fungible_transfers: u32,
-        nfts_transfers: Option<u32>,
-    ) -> DispatchResult {
-        let legs_count = Self::base_affirm_instruction(
-            origin,
-            id,
-            portfolios,
-            fungible_transfers,
-            nfts_transfers,
-        )?;
-        // Schedule the instruction if conditions are met
-        Self::maybe_schedule_instruction(
-            Self::instruction_affirms_pending(id),
-            id,
-            legs_count,
-            nfts_transfers.unwrap_or_default(),
-        );
-        Ok(())
+    /// Returns the assets of `portfolio` locked by affirmed legs awaiting execution, with the
+    /// instruction and leg holding each lock, ordered by `InstructionId` and `LegId`.
+    /// Legs already executed, and rejected instructions kept for inspection, hold no lock.
+    pub fn locked_assets(portfolio: &PortfolioId) -> Vec<(InstructionId, LegId, LockedAsset)> {
+        let mut locks = Vec::new();
+        let affirmed = UserAffirmations::iter_prefix(portfolio)
+            .filter(|(_, status)| *status == AffirmationStatus::Affirmed)
+            .filter(|(id, _)| {
+                matches!(
+                    Self::instruction_status(id),
+                    InstructionStatus::Pending | InstructionStatus::Failed
+                )
+            });
+        for (id, _) in affirmed {
+            let legs = Self::get_instruction_legs(&id);
+            for (leg_id, leg) in Self::unexecuted_legs(id, &legs) {
+                // Only legs sent from `portfolio` lock assets, unless settled through a receipt.
+                if leg.from != *portfolio
+                    || Self::instruction_leg_status(id, leg_id) != LegStatus::ExecutionPending
+                {
+                    continue;
+                }
+                match leg.asset {
+                    LegAsset::Fungible { ticker, amount } => {
+                        locks.push((id, leg_id, LockedAsset::Fungible { ticker, amount }))
+                    }
+                    LegAsset::NonFungible(nfts) => locks.extend(nfts.ids().iter().map(|nft_id| {
+                        let ticker = *nfts.ticker();
+                        let nft_id = *nft_id;
+                        (id, leg_id, LockedAsset::NonFungible { ticker, nft_id })
+                    })),
+                }
+            }
+        }
+        locks.sort_by_key(|(id, leg_id, _)| (*id, *leg_id));
+        locks
     }
 
-    /// Affirm with or without receipts, executing the instruction when all affirmations have been received.
-    ///
-    /// NB - Use this function only in the STO pallet to support DVP settlements.
-    pub fn affirm_and_execute_instruction(
-        origin: <T as frame_system::Config>::RuntimeOrigin,
-        id: InstructionId,
-        receipt: Option<ReceiptDetails<T::AccountId, T::OffChainSignature>>,
-        portfolios: Vec<PortfolioId>,
-        max_legs_count: u32,
-        nfts_transferred: Option<u32>,
-    ) -> DispatchResult {
-        match receipt {
-            Some(receipt) => Self::base_affirm_with_receipts(
-                origin,
-                id,
-                vec![receipt],
-                portfolios,
-                max_legs_count,
-            )?,
-            None => Self::base_affirm_instruction(
-                origin,
-                id,
-                portfolios.into_iter(),
-                max_legs_count,
-                nfts_transferred,
-            )?,
-        };
-        Self::execute_settle_on_affirmation_instruction(
-            id,
-            Self::instruction_affirms_pending(id),
-            Self::instruction_details(id).settlement_type,
-        )?;
-        Self::prune_instruction(id, true);
-        Ok(())
+    /// Returns the obligations of `did` towards each counterparty and ticker from the pending
+    /// instructions that can settle between the blocks `start` and `end`, ordered by counterparty
+    /// and ticker. Instructions settling on affirmation can settle at any block, so are always included.
+    /// Non-fungible legs count the number of NFTs transferred, and legs settled through a receipt are ignored.
+    pub fn net_obligations(
+        did: IdentityId,
+        start: T::BlockNumber,
+        end: T::BlockNumber,
+    ) -> Vec<NetObligation> {
+        let portfolios = pallet_portfolio::Portfolios::iter_prefix(did)
+            .map(|(num, _)| PortfolioId::user_portfolio(did, num))
+            .chain(core::iter::once(PortfolioId::default_portfolio(did)));
+        let instructions = portfolios
+            .flat_map(UserAffirmations::iter_prefix)
+            .map(|(id, _)| id)
+            .collect::<BTreeSet<_>>();
+
+        let mut obligations: BTreeMap<(IdentityId, Ticker), (Balance, Balance)> = BTreeMap::new();
+        for id in instructions {
+            if Self::instruction_status(id) != InstructionStatus::Pending {
+                continue;
+            }
+            let in_range = match Self::instruction_details(id).settlement_type {
+                SettlementType::SettleOnBlock(block) => start <= block && block <= end,
+                SettlementType::SettleManual(block) => block <= end,
+                SettlementType::SettleOnAffirmation
+                | SettlementType::SettleOnAffirmationPartial => true,
+            };
+            if !in_range {
+                continue;
+            }
+            for (leg_id, leg) in Self::get_instruction_legs(&id) {
+                if let LegStatus::ExecutionToBeSkipped(..) =
+                    Self::instruction_leg_status(id, leg_id)
+                {
+                    continue;
+                }
+                let (ticker, amount) = leg.asset.ticker_and_amount();
+                // Legs between portfolios of `did` are not obligations.
+                if leg.from.did == did && leg.to.did != did {
+                    let entry = obligations.entry((leg.to.did, ticker)).or_default();
+                    entry.1 = entry.1.saturating_add(amount);
+                } else if leg.to.did == did && leg.from.did != did {
+                    let entry = obligations.entry((leg.from.did, ticker)).or_default();
+                    entry.0 = entry.0.saturating_add(amount);
+                }
+            }
+        }
+        obligations
+            .into_iter()
+            .map(
+                |((counterparty, ticker), (receivable, deliverable))| NetObligation {
+                    counterparty,
+                    ticker,
+                    receivable,
+                    deliverable,
+                },
+            )
+            .collect()
     }
 
-    fn execute_settle_on_affirmation_instruction(
-        id: InstructionId,
-        affirms_pending: u64,
-        settlement_type: SettlementType<T::BlockNumber>,
-    ) -> DispatchResult {
-        // We assume `settlement_type == SettleOnAffirmation`,
-        // to be defensive, however, this is checked before instruction execution.
-        if settlement_type == SettlementType::SettleOnAffirmation && affirms_pending == 0 {
-            // We use execute_instruction here directly
-            // and not the execute_instruction_retryable variant
-            // because direct settlement is not retryable.
-            Self::execute_instruction(id)?;
+    /// Withdraws the affirmations of `portfolio` on behalf of its `custodian`, scanning at most `max`
+    /// of its instructions after `start_after`. Returns the number of scanned instructions.
+    ///
+    /// Affirmations that can't be withdrawn are skipped, and reported in the emitted event.
+    fn revoke_portfolio_affirmations(
+        custodian: IdentityId,
+        secondary_key: Option<&SecondaryKey<T::AccountId>>,
+        portfolio: PortfolioId,
+        start_after: Option<InstructionId>,
+        max: u32,
+    ) -> u32 {
+        let iter = match start_after {
+            Some(id) => {
+                let raw_key = UserAffirmations::hashed_key_for(portfolio, id);
+                UserAffirmations::iter_prefix_from(portfolio, raw_key)
+            }
+            None => UserAffirmations::iter_prefix(portfolio),
+        };
+        let scanned: Vec<(InstructionId, AffirmationStatus)> = iter.take(max as usize).collect();
+        if scanned.is_empty() {
+            return 0;
         }
-        Ok(())
+        let last_scanned = (scanned.len() == max as usize)
+            .then(|| scanned.last().map(|(id, _)| *id))
+            .flatten();
+
+        let (mut revoked, mut failed) = (Vec::new(), Vec::new());
+        for (id, status) in &scanned {
+            if *status != AffirmationStatus::Affirmed {
+                continue;
+            }
+            match Self::revoke_affirmation(custodian, secondary_key, portfolio, *id) {
+                Ok(()) => revoked.push(*id),
+                Err(_) => failed.push(*id),
+            }
+        }
+        Self::deposit_event(RawEvent::PortfolioAffirmationsRevoked(
+            custodian,
+            portfolio,
+            revoked,
+            failed,
+            last_scanned,
+        ));
+        scanned.len() as u32
     }
 
-    fn ensure_portfolios_and_affirmation_status(
-        id: InstructionId,
-        portfolios: &BTreeSet<PortfolioId>,
+    /// Withdraws the affirmation of `portfolio` from instruction `id` on behalf of its `custodian`.
+    fn revoke_affirmation(
         custodian: IdentityId,
         secondary_key: Option<&SecondaryKey<T::AccountId>>,
-        expected_statuses: &[AffirmationStatus],
+        portfolio: PortfolioId,
+        id: InstructionId,
     ) -> DispatchResult {
-        for portfolio in portfolios {
-            T::Portfolio::ensure_portfolio_custody_and_permission(
-                *portfolio,
+        with_transaction(|| {
+            let portfolios = iter::once(portfolio).collect();
+            Self::unsafe_withdraw_instruction_affirmation(
                 custodian,
+                id,
+                portfolios,
                 secondary_key,
+                T::MaxNumberOfFungibleAssets::get(),
+                Some(T::MaxNumberOfNFTs::get()),
             )?;
-            let user_affirmation = Self::user_affirmations(portfolio, id);
-            ensure!(
-                expected_statuses.contains(&user_affirmation),
-                Error::<T>::UnexpectedAffirmationStatus
-            );
-        }
-        Ok(())
+            if Self::instruction_details(id)
+                .settlement_type
+                .settles_on_affirmation()
+            {
+                // Cancel the scheduled task for the execution of the instruction.
+                let _ = T::Scheduler::cancel_named(id.execution_name());
+            }
+            Ok(())
+        })
     }
 
-    /// Returns the total number of legs of an `instruction_id` and a vector of legs where sender is in the `portfolio_set`.
-    /// Ensures that the number of fungible and non fungible assets being transferred is under the given limit.
-    fn filtered_legs(
-        id: &InstructionId,
-        portfolio_set: &BTreeSet<PortfolioId>,
-        fungible_transfers: u32,
-        nfts_transfers: Option<u32>,
-    ) -> Result<(u32, Vec<(LegId, LegV2)>), DispatchError> {
-        let instruction_legs: Vec<(LegId, LegV2)> = Self::get_instruction_legs(&id);
-        let n_instruction_legs = instruction_legs.len() as u32;
-        // Gets all legs where the sender is in the given set
-        let legs_from_set: Vec<(LegId, LegV2)> = instruction_legs
-            .into_iter()
-            .filter(|(_, leg_v2)| portfolio_set.contains(&leg_v2.from))
+    /// Returns the weight of withdrawing the affirmations of a portfolio from `i` instructions.
+    pub fn revoke_affirmations_weight(i: u32) -> Weight {
+        <T as Config>::WeightInfo::revoke_affirmations(
+            i,
+            T::MaxNumberOfFungibleAssets::get(),
+            T::MaxNumberOfNFTs::get(),
+        )
+    }
+
+    /// Returns the instructions carrying `tag`, ordered by `InstructionId`.
+    pub fn instructions_by_tag(tag: &InstructionTag) -> Vec<InstructionId> {
+        let mut ids: Vec<InstructionId> = TaggedInstructions::iter_prefix(tag)
+            .map(|(id, _)| id)
             .collect();
-        let transfer_data = Self::get_transfer_data(&legs_from_set)?;
-        Self::ensure_valid_input_cost(&transfer_data, fungible_transfers, nfts_transfers)?;
-        Ok((n_instruction_legs, legs_from_set))
+        ids.sort_unstable();
+        ids
     }
 
-    fn base_update_venue_signers(
-        did: IdentityId,
-        id: VenueId,
-        signers: Vec<T::AccountId>,
-        add_signers: bool,
-    ) -> DispatchResult {
-        // Ensure venue exists & sender is its creator.
-        Self::venue_for_management(id, did)?;
+    /// Returns the legs of `instruction_id` that would fail if the instruction was executed now,
+    /// along with the reason of each failure. An empty result means the instruction would succeed.
+    ///
+    /// The instruction goes through the same checks and execution path as `execute_instruction`,
+    /// including the netting of its legs, in a transaction that is always rolled back.
+    /// Expired instructions are pruned, so they fail the status check.
+    ///
+    /// # Errors
+    /// * `InstructionNotPending` if the instruction is not pending.
+    /// * `InstructionFailed` if the instruction still has pending affirmations.
+    /// * `AssetSettlementFrozen` if the settlement of one of its assets is frozen.
+    /// * `UnauthorizedVenue` if its venue is not allowed to settle one of its assets.
+    pub fn can_execute_instruction(
+        instruction_id: &InstructionId,
+    ) -> Result<Vec<(LegId, DispatchError)>, DispatchError> {
+        frame_storage_with_transaction(|| {
+            TransactionOutcome::Rollback(Self::dry_run_instruction(*instruction_id))
+        })
+    }
 
-        if add_signers {
-            for signer in &signers {
-                ensure!(
-                    !Self::venue_signers(&id, &signer),
-                    Error::<T>::SignerAlreadyExists
-                );
-            }
-            for signer in &signers {
-                <VenueSigners<T>>::insert(&id, &signer, true);
-            }
-        } else {
-            for signer in &signers {
-                ensure!(
-                    Self::venue_signers(&id, &signer),
-                    Error::<T>::SignerDoesNotExist
-                );
-            }
-            for signer in &signers {
-                <VenueSigners<T>>::remove(&id, &signer);
-            }
+    /// Executes `instruction_id` the way `execute_instruction` would, returning the failed legs.
+    /// Must be called inside a transaction that is rolled back.
+    fn dry_run_instruction(
+        instruction_id: InstructionId,
+    ) -> Result<Vec<(LegId, DispatchError)>, DispatchError> {
+        let details = Self::instruction_details(instruction_id);
+        let mut instruction_legs = Self::get_sorted_instruction_legs(&instruction_id);
+        Self::ensure_instruction_executable(instruction_id, details.venue_id, &instruction_legs)?;
+        if let Some(progress) = Self::instruction_execution_progress(instruction_id) {
+            instruction_legs.retain(|(leg_id, _)| *leg_id >= progress.next_leg_id);
         }
 
-        Self::deposit_event(RawEvent::VenueSignersUpdated(did, id, signers, add_signers));
-        Ok(())
+        if details.settlement_type.allows_partial_execution() {
+            return Self::execute_instruction_partially(instruction_id, &instruction_legs);
+        }
+        let result = frame_storage_with_transaction(|| {
+            Self::release_asset_locks_and_transfer_pending_legs(
+                instruction_id,
+                &instruction_legs,
+                Self::venue_netting(details.venue_id),
+            )
+        })?;
+        Ok(result.err().into_iter().collect())
     }
 
-    fn base_reject_instruction(
-        origin: T::RuntimeOrigin,
+    fn base_edit_instruction_legs(
+        did: IdentityId,
         id: InstructionId,
-        portfolio: PortfolioId,
-        fungible_transfers: u32,
-        nfts_transfers: Option<u32>,
+        removed_legs: Vec<LegId>,
+        new_legs: Vec<LegV2>,
+        legs_count: u32,
     ) -> DispatchResult {
+        let removed_legs = removed_legs.into_iter().collect::<BTreeSet<_>>();
+        let (revision, added_legs) = Self::edit_legs(did, id, &removed_legs, new_legs, legs_count)?;
+        Self::deposit_event(RawEvent::InstructionLegsEdited(
+            did,
+            id,
+            revision,
+            removed_legs.into_iter().collect(),
+            added_legs,
+        ));
+        Ok(())
+    }
+
+    /// Removes `removed_legs` from the pending instruction `id` and adds `new_legs` to it,
+    /// returning the new revision of the instruction and the ids of the added legs.
+    ///
+    /// The removed legs are kept in the leg history, and their status, receipt cosigners and
+    /// consideration are removed.
+    fn edit_legs(
+        did: IdentityId,
+        id: InstructionId,
+        removed_legs: &BTreeSet<LegId>,
+        new_legs: Vec<LegV2>,
+        legs_count: u32,
+    ) -> Result<(u32, Vec<LegId>), DispatchError> {
         ensure!(
-            Self::instruction_status(id) != InstructionStatus::Unknown,
-            Error::<T>::UnknownInstruction
+            Self::instruction_status(id) == InstructionStatus::Pending,
+            Error::<T>::InstructionNotPending
         );
-        // Gets all legs for the instruction, checks if portfolio is in any of the legs, and validates the input cost.
-        let legs_v2: Vec<(LegId, LegV2)> = Self::get_instruction_legs(&id);
+        let details = Self::instruction_details(id);
+        Self::venue_for_management(details.venue_id, did)?;
+        // Edits are only allowed before any party has affirmed, so no tokens are locked.
         ensure!(
-            legs_v2
-                .iter()
-                .any(|(_, leg_v2)| leg_v2.from == portfolio || leg_v2.to == portfolio),
-            Error::<T>::CallerIsNotAParty
+            AffirmsReceived::iter_prefix(id).next().is_none(),
+            Error::<T>::UnexpectedAffirmationStatus
         );
-        let transfer_data = Self::get_transfer_data(&legs_v2)?;
-        Self::ensure_valid_input_cost(&transfer_data, fungible_transfers, nfts_transfers)?;
 
-        // Verifies if the caller has the right permissions for this call
-        let origin_data = Identity::<T>::ensure_origin_call_permissions(origin)?;
-        T::Portfolio::ensure_portfolio_custody_and_permission(
-            portfolio,
-            origin_data.primary_did,
-            origin_data.secondary_key.as_ref(),
-        )?;
+        let current_legs = Self::get_instruction_legs(&id);
+        ensure!(
+            current_legs.len() <= legs_count as usize,
+            Error::<T>::LegCountTooSmall
+        );
+        for leg_id in removed_legs {
+            ensure!(
+                current_legs
+                    .iter()
+                    .any(|(current_id, _)| current_id == leg_id),
+                Error::<T>::LegNotFound
+            );
+        }
+        let mut next_leg_id =
+            Self::instruction_next_leg_id(id).unwrap_or(LegId(current_legs.len() as u64));
+        let mut old_parties = BTreeSet::new();
+        for (_, leg) in &current_legs {
+            old_parties.insert(leg.from);
+            old_parties.insert(leg.to);
+        }
+        let (removed, kept): (Vec<_>, Vec<_>) = current_legs
+            .into_iter()
+            .partition(|(leg_id, _)| removed_legs.contains(leg_id));
 
-        Self::unsafe_unclaim_receipts(id, &legs_v2);
-        Self::unchecked_release_locks(id, &legs_v2);
-        let _ = T::Scheduler::cancel_named(id.execution_name());
-        Self::prune_instruction(id, false);
-        Self::deposit_event(RawEvent::InstructionRejected(origin_data.primary_did, id));
-        Ok(())
-    }
+        // Verifies that the resulting set of legs is valid.
+        let legs: Vec<LegV2> = kept
+            .iter()
+            .map(|(_, leg)| leg.clone())
+            .chain(new_legs.iter().cloned())
+            .collect();
+        let instruction_info = Self::ensure_valid_legs(&legs, details.venue_id)?;
 
-    /// Returns the number of fungible and non fungible transfers in a slice of legs.
-    /// In case that T::MaxNumberOfNFTsPerLeg is exceeded an error will be returned.
-    fn get_transfer_data(legs_v2: &[(LegId, LegV2)]) -> Result<TransferData, DispatchError> {
-        let mut nfts_transfers = 0;
-        let mut fungible_transfers = 0;
-        for (_, leg_v2) in legs_v2 {
-            match &leg_v2.asset {
-                LegAsset::Fungible { .. } => fungible_transfers += 1,
-                LegAsset::NonFungible(nfts) => {
-                    ensure!(
-                        nfts.len() <= T::MaxNumberOfNFTsPerLeg::get() as usize,
-                        Error::<T>::MaxNumberOfNFTsPerLegExceeded
-                    );
-                    nfts_transfers += nfts.len();
-                }
-            }
-        }
-        Ok(TransferData::new(fungible_transfers, nfts_transfers as u32))
-    }
+        let revision = InstructionRevision::try_mutate(id, try_next_pre::<T, _>)?;
+        let added_legs = new_legs
+            .iter()
+            .map(|_| try_next_post::<T, _>(&mut next_leg_id))
+            .collect::<Result<Vec<_>, _>>()?;
 
-    /// Returns ok if the number of fungible assets and nfts being transferred is under the input given by the user.
-    pub fn ensure_valid_input_cost(
-        transfer_data: &TransferData,
-        fungible_transfers: u32,
-        nfts_transfers: Option<u32>,
-    ) -> DispatchResult {
-        // Verifies if the number of nfts being transferred is under the limit
-        if transfer_data.non_fungible() > 0 {
-            let nfts_transfers = nfts_transfers.ok_or(Error::<T>::DeprecatedCallOnV2Instruction)?;
-            ensure!(
-                transfer_data.non_fungible() <= nfts_transfers,
-                Error::<T>::NumberOfTransferredNFTsUnderestimated
+        // Legs are rewritten to `InstructionLegsV2`, which also upgrades instructions created with deprecated legs.
+        Self::drain_instruction_legs(&id);
+        for (leg_id, leg) in kept {
+            InstructionLegsV2::insert(id, leg_id, leg);
+        }
+        for (leg_id, leg) in removed {
+            <InstructionLegStatus<T>>::remove(id, leg_id);
+            <ReceiptCosigners<T>>::remove(id, leg_id);
+            InstructionLegConsiderations::remove(id, leg_id);
+            RemovedInstructionLegs::insert(id, leg_id, leg);
+            InstructionLegRevisions::mutate(id, leg_id, |leg_revision| {
+                leg_revision
+                    .get_or_insert_with(LegRevision::default)
+                    .removed_in = Some(revision)
+            });
+        }
+        for (leg_id, leg) in added_legs.iter().zip(new_legs) {
+            InstructionLegsV2::insert(id, leg_id, leg);
+            InstructionLegRevisions::insert(
+                id,
+                leg_id,
+                LegRevision {
+                    added_in: revision,
+                    removed_in: None,
+                },
             );
         }
-        // Verifies if the number of fungible transfers is under the limit
-        ensure!(
-            transfer_data.fungible() <= fungible_transfers,
-            Error::<T>::LegCountTooSmall
+        InstructionNextLegId::insert(id, next_leg_id);
+
+        // Updates the counter parties that have to affirm the instruction.
+        for counter_party in old_parties.difference(instruction_info.parties()) {
+            UserAffirmations::remove(counter_party, id);
+        }
+        for counter_party in instruction_info.parties() {
+            UserAffirmations::insert(counter_party, id, AffirmationStatus::Pending);
+        }
+        let mediators = Self::reset_mediator_affirmations(id);
+        InstructionAffirmsPending::insert(
+            id,
+            u64::try_from(instruction_info.parties().len() + mediators).unwrap_or_default(),
         );
-        Ok(())
-    }
 
-    /// If `tickers` doesn't contain the given `ticker` and venue_filtering is enabled, ensures that venue_id is in the allowed list
-    fn ensure_venue_filtering(
-        tickers: &mut BTreeSet<Ticker>,
-        ticker: Ticker,
-        venue_id: &VenueId,
-    ) -> DispatchResult {
-        if tickers.insert(ticker) && Self::venue_filtering(ticker) {
-            ensure!(
-                Self::venue_allow_list(ticker, venue_id),
-                Error::<T>::UnauthorizedVenue
+        // The scheduled execution carries the number of transfers, so it has to be replaced.
+        if let SettlementType::SettleOnBlock(block_number) = details.settlement_type {
+            let _ = T::Scheduler::cancel_named(id.execution_name());
+            Self::schedule_instruction(
+                id,
+                block_number,
+                instruction_info.fungible_transfers(),
+                instruction_info.nfts_transferred(),
             );
         }
-        Ok(())
+        Ok((revision, added_legs))
     }
 
-    /// Returns the specified leg for the given instruction and leg id.
-    /// If it doesn't exist in the InstructionLegsV2 storage it will be converted from the deprecated InstructionLegs storage.
-    pub fn get_instruction_leg(instruction_id: &InstructionId, leg_id: &LegId) -> LegV2 {
-        InstructionLegsV2::try_get(instruction_id, leg_id)
-            .unwrap_or_else(|_| InstructionLegs::get(instruction_id, leg_id).into())
+    fn base_amend_instruction_leg(
+        did: IdentityId,
+        id: InstructionId,
+        leg_id: LegId,
+        new_leg: LegV2,
+        legs_count: u32,
+    ) -> DispatchResult {
+        let (_, added_legs) = Self::edit_legs(
+            did,
+            id,
+            &iter::once(leg_id).collect(),
+            vec![new_leg],
+            legs_count,
+        )?;
+        if let Some(new_leg_id) = added_legs.into_iter().next() {
+            Self::deposit_event(RawEvent::InstructionAmended(did, id, leg_id, new_leg_id));
+        }
+        Ok(())
     }
 
-    /// Returns all legs and their id for the given instruction.
-    /// If it doesn't exist in the InstructionLegsV2 storage it will be converted from the deprecated InstructionLegs storage.
-    pub fn get_instruction_legs(instruction_id: &InstructionId) -> Vec<(LegId, LegV2)> {
-        let instruction_legs: Vec<(LegId, LegV2)> =
-            InstructionLegsV2::iter_prefix(instruction_id).collect();
-
-        if instruction_legs.is_empty() {
-            return InstructionLegs::iter_prefix(instruction_id)
-                .map(|(leg_id, leg)| (leg_id, leg.into()))
-                .collect();
+    fn base_set_leg_consideration(
+        did: IdentityId,
+        id: InstructionId,
+        leg_id: LegId,
+        consideration: Option<LegConsideration>,
+    ) -> DispatchResult {
+        ensure!(
+            Self::instruction_status(id) == InstructionStatus::Pending,
+            Error::<T>::InstructionNotPending
+        );
+        Self::venue_for_management(Self::instruction_details(id).venue_id, did)?;
+        // The counterparties agree to the consideration by affirming the instruction.
+        ensure!(
+            AffirmsReceived::iter_prefix(id).next().is_none(),
+            Error::<T>::UnexpectedAffirmationStatus
+        );
+        ensure!(
+            InstructionLegsV2::contains_key(id, leg_id)
+                || InstructionLegs::contains_key(id, leg_id),
+            Error::<T>::LegNotFound
+        );
+        if let Some(consideration) = &consideration {
+            ensure!(consideration.amount > 0, Error::<T>::ZeroAmount);
         }
-        instruction_legs
+
+        InstructionLegConsiderations::mutate(id, leg_id, |current| *current = consideration);
+        Self::deposit_event(RawEvent::LegConsiderationSet(
+            did,
+            id,
+            leg_id,
+            consideration,
+        ));
+        Ok(())
     }
 
     /// Removes all legs for the given `instruction_id`, returning a `Vec<(LegId, LegV2)>` containing the removed legs.
@@ -2322,6 +6012,72 @@ impl<T: Config> Module<T> {
         drained_legs
     }
 
+    /// Ensures `did` created the venue of the instruction `id`, which must have a memo.
+    fn ensure_memo_manager(did: IdentityId, id: InstructionId) -> DispatchResult {
+        ensure!(
+            InstructionMemos::contains_key(id),
+            Error::<T>::InstructionHasNoMemo
+        );
+        Self::venue_for_management(Self::instruction_details(id).venue_id, did)?;
+        Ok(())
+    }
+
+    fn base_set_memo_access_list(
+        did: IdentityId,
+        id: InstructionId,
+        dids: Vec<IdentityId>,
+    ) -> DispatchResult {
+        Self::ensure_memo_manager(did, id)?;
+        let dids = dids
+            .into_iter()
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect::<Vec<_>>();
+        ensure!(
+            dids.len() <= MAX_MEMO_READERS as usize,
+            Error::<T>::TooManyMemoReaders
+        );
+
+        for removed_did in Self::memo_access_list(id) {
+            if dids.binary_search(&removed_did).is_err() {
+                MemoKeyWraps::remove(id, removed_did);
+            }
+        }
+        if dids.is_empty() {
+            MemoAccessList::remove(id);
+        } else {
+            MemoAccessList::insert(id, &dids);
+        }
+        Self::deposit_event(RawEvent::MemoAccessListSet(did, id, dids));
+        Ok(())
+    }
+
+    fn base_wrap_memo_keys(
+        did: IdentityId,
+        id: InstructionId,
+        wrapped_keys: Vec<(IdentityId, WrappedMemoKey)>,
+    ) -> DispatchResult {
+        Self::ensure_memo_manager(did, id)?;
+        let access_list = Self::memo_access_list(id);
+        for (reader, wrapped_key) in &wrapped_keys {
+            ensure!(
+                access_list.binary_search(reader).is_ok(),
+                Error::<T>::NotInMemoAccessList
+            );
+            ensure_string_limited::<T>(&wrapped_key.0)?;
+        }
+
+        let readers = wrapped_keys
+            .into_iter()
+            .map(|(reader, wrapped_key)| {
+                MemoKeyWraps::insert(id, reader, wrapped_key);
+                reader
+            })
+            .collect();
+        Self::deposit_event(RawEvent::MemoKeysWrapped(did, id, readers));
+        Ok(())
+    }
+
     fn base_execute_scheduled_instruction(id: InstructionId) {
         if let Err(e) = Self::execute_instruction_retryable(id) {
             Self::deposit_event(RawEvent::FailedToExecuteInstruction(id, e));
@@ -2382,6 +6138,52 @@ pub mod migration {
         }
     }
 
+    mod v2 {
+        use super::*;
+        use scale_info::TypeInfo;
+
+        /// Old v2 Instruction information, without an expiry block.
+        #[derive(Encode, Decode, TypeInfo)]
+        #[derive(Default, Clone, PartialEq, Eq, Debug, PartialOrd, Ord)]
+        pub struct Instruction<Moment, BlockNumber> {
+            /// Unique instruction id. It is an auto incrementing number
+            pub instruction_id: InstructionId,
+            /// Id of the venue this instruction belongs to
+            pub venue_id: VenueId,
+            /// Type of settlement used for this instruction
+            pub settlement_type: SettlementType<BlockNumber>,
+            /// Date at which this instruction was created
+            pub created_at: Option<Moment>,
+            /// Date from which this instruction is valid
+            pub trade_date: Option<Moment>,
+            /// Date after which the instruction should be settled (not enforced)
+            pub value_date: Option<Moment>,
+        }
+
+        decl_storage! {
+            trait Store for Module<T: Config> as Settlement {
+                /// Details about an instruction. instruction_id -> instruction_details
+                pub InstructionDetails get(fn instruction_details):
+                map hasher(twox_64_concat) InstructionId => Instruction<T::Moment, T::BlockNumber>;
+                    }
+        }
+
+        decl_module! {
+            pub struct Module<T: Config> for enum Call where origin: T::RuntimeOrigin { }
+        }
+
+        /// Old v2 fixed-size memo.
+        #[derive(Encode, Decode, TypeInfo)]
+        #[derive(Clone, Debug, Default, PartialEq, Eq)]
+        pub struct InstructionMemo(pub [u8; 32]);
+
+        impl From<InstructionMemo> for Memo {
+            fn from(memo: InstructionMemo) -> Self {
+                memo.0.into()
+            }
+        }
+    }
+
     pub fn migrate_v1<T: Config>() {
         sp_runtime::runtime_logger::RuntimeLogger::init();
 
@@ -2393,7 +6195,7 @@ pub mod migration {
                 InstructionStatuses::<T>::insert(id, instruction_details.status);
 
                 //Migrate Instruction details.
-                let instruction = Instruction {
+                let instruction = v2::Instruction {
                     instruction_id: id,
                     venue_id: instruction_details.venue_id,
                     settlement_type: instruction_details.settlement_type,
@@ -2401,7 +6203,7 @@ pub mod migration {
                     trade_date: instruction_details.trade_date,
                     value_date: instruction_details.value_date,
                 };
-                <InstructionDetails<T>>::insert(id, instruction);
+                v2::InstructionDetails::<T>::insert(id, instruction);
 
                 total_instructions + 1
             },
@@ -2409,4 +6211,51 @@ pub mod migration {
 
         log::info!(" >>> Migrated {} Instructions.", total_instructions);
     }
+
+    pub fn migrate_v2<T: Config>() {
+        use frame_support::IterableStorageMap;
+
+        sp_runtime::runtime_logger::RuntimeLogger::init();
+
+        log::info!(" >>> Updating Settlement storage. Adding expiry to Instructions...");
+        let total_instructions = v2::InstructionDetails::<T>::drain().fold(
+            0usize,
+            |total_instructions, (id, instruction_details)| {
+                // Existing instructions never expire.
+                let instruction = Instruction {
+                    instruction_id: instruction_details.instruction_id,
+                    venue_id: instruction_details.venue_id,
+                    settlement_type: instruction_details.settlement_type,
+                    created_at: instruction_details.created_at,
+                    trade_date: instruction_details.trade_date,
+                    value_date: instruction_details.value_date,
+                    expiry_block: None,
+                };
+                <InstructionDetails<T>>::insert(id, instruction);
+
+                total_instructions + 1
+            },
+        );
+        log::info!(" >>> Migrated {} Instructions.", total_instructions);
+
+        log::info!(" >>> Migrating fixed-size memos...");
+        let mut total_memos = 0usize;
+        InstructionMemos::translate(|_, memo: v2::InstructionMemo| {
+            total_memos += 1;
+            Some(memo.into())
+        });
+        log::info!(" >>> Migrated {} memos.", total_memos);
+
+        log::info!(" >>> Counting open instructions of venues...");
+        let total_instructions =
+            VenueInstructions::iter_keys().fold(0u64, |total_instructions, (venue_id, _)| {
+                VenueOpenInstructions::mutate(venue_id, |open_instructions| {
+                    *open_instructions = open_instructions.saturating_add(1)
+                });
+
+                total_instructions.saturating_add(1)
+            });
+        OpenInstructionCount::put(total_instructions);
+        log::info!(" >>> Counted {} open Instructions.", total_instructions);
+    }
 }
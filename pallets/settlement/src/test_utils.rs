@@ -0,0 +1,73 @@
+// This file is part of the Polymesh distribution (https://github.com/PolymeshAssociation/Polymesh).
+// Copyright (c) 2020 Polymath
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, version 3.
+
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+// General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+//! Scenario builders shared by the benchmarks and the integration tests of this pallet.
+//!
+//! They create venues and portfolios by writing to storage directly, so they must
+//! never be enabled in a production runtime.
+
+use crate::*;
+
+use pallet_portfolio::PortfolioAssetBalances;
+use polymesh_primitives::{checked_inc::CheckedInc, PortfolioName, PortfolioNumber};
+use sp_runtime::SaturatedConversion;
+
+type Portfolio<T> = pallet_portfolio::Module<T>;
+
+/// The account and identity of a user.
+#[derive(Encode, Decode, Clone, Copy)]
+pub struct UserData<T: Config> {
+    pub account: T::AccountId,
+    pub did: IdentityId,
+}
+
+/// Sets the current block number.
+pub fn set_block_number<T: Config>(new_block_no: u64) {
+    frame_system::Pallet::<T>::set_block_number(new_block_no.saturated_into::<T::BlockNumber>());
+}
+
+/// Set venue related storage without any sanity checks.
+pub fn create_venue_<T: Config>(did: IdentityId, signers: Vec<T::AccountId>) -> VenueId {
+    let venue = Venue {
+        creator: did,
+        venue_type: VenueType::Distribution,
+    };
+    // NB: Venue counter starts with 1.
+    let venue_counter = Module::<T>::venue_counter();
+    VenueInfo::insert(venue_counter, venue);
+    for signer in signers {
+        <VenueSigners<T>>::insert(venue_counter, signer, true);
+    }
+    VenueCounter::put(venue_counter.checked_inc().unwrap());
+    venue_counter
+}
+
+/// Sets the balance of `ticker` in `portfolio` without any sanity checks.
+pub fn fund_portfolio<T: Config>(portfolio: &PortfolioId, ticker: &Ticker, amount: Balance) {
+    PortfolioAssetBalances::insert(portfolio, ticker, amount);
+}
+
+/// Creates a new user portfolio for `user`.
+pub fn create_user_portfolio<T: Config>(user: &UserData<T>) -> PortfolioId {
+    let portfolio_no = (Portfolio::<T>::next_portfolio_number(user.did)).0;
+    let portfolio_name =
+        PortfolioName::try_from(vec![b'P'; portfolio_no as usize].as_slice()).unwrap();
+    Portfolio::<T>::create_portfolio(
+        RawOrigin::Signed(user.account.clone()).into(),
+        portfolio_name,
+    )
+    .expect("Failed to generate portfolio");
+    PortfolioId::user_portfolio(user.did, PortfolioNumber::from(portfolio_no))
+}
@@ -0,0 +1,76 @@
+// This file is part of the Polymesh distribution (https://github.com/PolymeshAssociation/Polymesh).
+// Copyright (c) 2020 Polymath
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, version 3.
+
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+// General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+//! Helpers for runtimes and integration tests that need settled instructions.
+//!
+//! These bypass the permissions of the counterparties and the scheduler, so they must never be
+//! enabled in a production runtime.
+
+use crate::*;
+
+impl<T: Config> Module<T> {
+    /// Drives a new instruction of `did` through its whole lifecycle in one call:
+    /// it is added to `venue_id` with `legs`, affirmed by the custodians of all its portfolios,
+    /// scheduled for execution and executed right away.
+    ///
+    /// Returns the id of the settled instruction, or the error of the first step that failed.
+    /// An instruction that fails at execution is left in the `Failed` status.
+    pub fn simulate_instruction_lifecycle(
+        did: IdentityId,
+        venue_id: VenueId,
+        legs: Vec<LegV2>,
+        memo: Option<Memo>,
+    ) -> Result<InstructionId, DispatchError> {
+        let (fungible_transfers, nfts_transfers) = get_transfer_by_asset(&legs);
+        let mut custodians: BTreeMap<IdentityId, BTreeSet<PortfolioId>> = BTreeMap::new();
+        for portfolio in legs.iter().flat_map(|leg| [leg.from, leg.to]) {
+            let custodian = pallet_portfolio::Module::<T>::portfolio_custodian(portfolio)
+                .unwrap_or(portfolio.did);
+            custodians.entry(custodian).or_default().insert(portfolio);
+        }
+
+        let id = Self::base_add_instruction(
+            did,
+            venue_id,
+            SettlementType::SettleOnAffirmation,
+            None,
+            None,
+            legs,
+            memo,
+            false,
+        )?;
+        for (custodian, portfolios) in custodians {
+            Self::unsafe_affirm_instruction(
+                custodian,
+                id,
+                portfolios,
+                fungible_transfers,
+                Some(nfts_transfers),
+                None,
+            )?;
+        }
+        Self::maybe_schedule_instruction(
+            Self::instruction_affirms_pending(id),
+            id,
+            fungible_transfers,
+            nfts_transfers,
+        );
+
+        // Executes now instead of waiting for the scheduler.
+        let _ = T::Scheduler::cancel_named(id.execution_name());
+        Self::execute_instruction_retryable(id)?;
+        Ok(id)
+    }
+}
@@ -164,4 +164,36 @@ benchmarks! {
 
         let (owner, exempt_key, scope_ids) = init_exempts::<T>(i);
     }: set_entities_exempt(owner.origin, true, exempt_key, scope_ids)
+
+    start_exemption_import {
+        let (owner, exempt_key, _) = init_exempts::<T>(0);
+    }: _(owner.origin, exempt_key, limits::MAX_EXEMPTED_IDENTITIES, H256::zero())
+
+    import_exemption_batch {
+        // Number of exempt entities being imported.
+        let i in 0 .. limits::MAX_EXEMPTED_IDENTITIES.min(T::MaxExemptionsPerBatch::get());
+
+        let (owner, exempt_key, scope_ids) = init_exempts::<T>(i);
+        let mut import = BatchImport::new(i, H256::zero());
+        scope_ids.iter().for_each(|entity| import.add_entry(entity));
+        Module::<T>::start_exemption_import(owner.origin.clone().into(), exempt_key, i, import.running_hash)?;
+    }: _(owner.origin, exempt_key, scope_ids.into_iter().collect(), import.running_hash)
+
+    finalize_exemption_import {
+        let i = limits::MAX_EXEMPTED_IDENTITIES.min(T::MaxExemptionsPerBatch::get());
+        let (owner, exempt_key, scope_ids) = init_exempts::<T>(i);
+        let mut import = BatchImport::new(i, H256::zero());
+        scope_ids.iter().for_each(|entity| import.add_entry(entity));
+        Module::<T>::start_exemption_import(owner.origin.clone().into(), exempt_key, i, import.running_hash)?;
+        Module::<T>::import_exemption_batch(owner.origin.clone().into(), exempt_key, scope_ids.into_iter().collect(), import.running_hash)?;
+    }: _(owner.origin, exempt_key)
+
+    abort_exemption_import {
+        let (owner, exempt_key, _) = init_exempts::<T>(0);
+        Module::<T>::start_exemption_import(owner.origin.clone().into(), exempt_key, 1, H256::zero())?;
+    }: _(owner.origin, exempt_key)
+
+    set_transfer_volume_tracking {
+        let (owner, ticker) = init_ticker::<T>();
+    }: _(owner.origin, ticker.into(), true)
 }
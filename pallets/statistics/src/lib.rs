@@ -26,16 +26,36 @@ use frame_support::{
     traits::Get,
     BoundedBTreeSet,
 };
+use pallet_base::try_use_entry;
 pub use polymesh_common_utilities::traits::statistics::{Config, Event, WeightInfo};
 use polymesh_primitives::{
     statistics::{
         AssetScope, Percentage, Stat1stKey, Stat2ndKey, StatOpType, StatType, StatUpdate,
+        TransferVolume, TransferVolumeEra,
     },
     storage_migration_ver,
     transfer_compliance::*,
-    Balance, IdentityId, ScopeId, Ticker,
+    Balance, BatchImport, IdentityId, ScopeId, Ticker,
 };
-use sp_std::{collections::btree_set::BTreeSet, vec, vec::Vec};
+use sp_core::H256;
+use sp_runtime::traits::{One, UniqueSaturatedInto};
+use sp_std::{
+    collections::{btree_map::BTreeMap, btree_set::BTreeSet},
+    vec,
+    vec::Vec,
+};
+
+/// Number of days of transfers kept per investor, bounding the window of transfer volume limits.
+pub const MAX_INVESTOR_VOLUME_DAYS: u32 = 366;
+
+/// Number of days of settled volume kept per asset.
+pub const MAX_SETTLED_VOLUME_DAYS: u32 = 31;
+
+/// Maximum number of eras read by a single `transfer_volumes_in_range` query.
+pub const MAX_TRANSFER_VOLUME_ERAS: u32 = 366;
+
+/// Number of milliseconds in a day.
+const MILLISECONDS_PER_DAY: u64 = 24 * 60 * 60 * 1000;
 
 type Identity<T> = pallet_identity::Module<T>;
 type ExternalAgents<T> = pallet_external_agents::Module<T>;
@@ -60,6 +80,38 @@ decl_storage! {
                 hasher(blake2_128_concat) ScopeId
             =>
                 bool;
+        /// Progress of ongoing batched imports of exempt entities.
+        pub ExemptionImports get(fn exemption_imports):
+            map hasher(blake2_128_concat) TransferConditionExemptKey => Option<BatchImport>;
+        /// Assets that have transfer volume tracking enabled.
+        pub TransferVolumeTracking get(fn transfer_volume_tracking): map hasher(blake2_128_concat) AssetScope => bool;
+        /// Aggregated transfers per asset per era.
+        pub TransferVolumes get(fn transfer_volumes):
+            double_map
+                hasher(blake2_128_concat) AssetScope,
+                hasher(twox_64_concat) TransferVolumeEra
+            =>
+                TransferVolume;
+        /// Assets with a compliance condition on the transfer volume of their investors,
+        /// the only assets whose investor transfer volumes are recorded.
+        pub InvestorVolumeTracking get(fn investor_volume_tracking): map hasher(blake2_128_concat) AssetScope => bool;
+        /// Tokens transferred, sent or received, by each investor of an asset per day,
+        /// recorded while `InvestorVolumeTracking` is enabled for the asset.
+        /// Days older than `MAX_INVESTOR_VOLUME_DAYS` are pruned on the next transfer of the investor.
+        /// (asset, DID) -> day -> volume
+        pub InvestorTransferVolumes get(fn investor_transfer_volumes):
+            double_map
+                hasher(blake2_128_concat) AssetScope,
+                hasher(identity) IdentityId
+            =>
+                BTreeMap<u32, Balance>;
+        /// Tokens of each asset transferred by executed settlement legs per day, for other
+        /// pallets to tier their fees by traded volume.
+        /// This is an amount of tokens, not their value in any currency.
+        /// Days older than `MAX_SETTLED_VOLUME_DAYS` are pruned on the next settlement of the asset.
+        /// asset -> day -> settled volume
+        pub SettledVolumes get(fn settled_volumes):
+            map hasher(blake2_128_concat) AssetScope => BTreeMap<u32, Balance>;
 
         /// Storage migration version.
         StorageVersion get(fn storage_version) build(|_| Version::new(1)): Version;
@@ -75,6 +127,8 @@ decl_module! {
 
         const MaxStatsPerAsset: u32 = T::MaxStatsPerAsset::get();
         const MaxTransferConditionsPerAsset: u32 = T::MaxTransferConditionsPerAsset::get();
+        const MaxExemptionsPerBatch: u32 = T::MaxExemptionsPerBatch::get();
+        const TransferVolumeEraLength: T::BlockNumber = T::TransferVolumeEraLength::get();
 
         /// Set the active asset stat_types.
         ///
@@ -154,6 +208,119 @@ decl_module! {
         pub fn set_entities_exempt(origin, is_exempt: bool, exempt_key: TransferConditionExemptKey, entities: BTreeSet<ScopeId>) {
             Self::base_set_entities_exempt(origin, is_exempt, exempt_key, entities)?;
         }
+
+        /// Starts a batched import of entities exempt from an asset's transfer compliance rules.
+        ///
+        /// The entities are then imported, in order, with `import_exemption_batch`,
+        /// each batch being checked against the running hash of the entities imported so far.
+        /// The import is completed by `finalize_exemption_import`, which checks
+        /// that `total_entries` entities were imported and that their hash matches `expected_hash`.
+        /// See `BatchImport::add_entry` for how the hash is computed.
+        ///
+        /// # Arguments
+        /// - `origin` - a signer that has permissions to act as an agent of `exempt_key.asset`.
+        /// - `exempt_key` - the asset and stat type to exempt the entities from.
+        /// - `total_entries` - the number of entities that will be imported.
+        /// - `expected_hash` - the hash of the ordered list of entities.
+        ///
+        /// # Errors
+        /// - `ExemptionImportInProgress` if there is already an import for `exempt_key`.
+        /// - `UnauthorizedAgent` if `origin` is not agent-permissioned for `asset`.
+        ///
+        /// # Permissions
+        /// - Agent
+        /// - Asset
+        #[weight = <T as Config>::WeightInfo::start_exemption_import()]
+        pub fn start_exemption_import(origin, exempt_key: TransferConditionExemptKey, total_entries: u32, expected_hash: H256) {
+            Self::base_start_exemption_import(origin, exempt_key, total_entries, expected_hash)?;
+        }
+
+        /// Imports the next batch of exempt entities for an ongoing import.
+        ///
+        /// The batch is rejected, and none of its entities exempted, unless the running hash
+        /// of the import once the batch is folded in is `batch_hash`.
+        ///
+        /// # Arguments
+        /// - `origin` - a signer that has permissions to act as an agent of `exempt_key.asset`.
+        /// - `exempt_key` - the asset and stat type to exempt the `entities` from.
+        /// - `entities` - the next entities of the import, in order.
+        /// - `batch_hash` - the hash of all the entities imported so far, including `entities`.
+        ///
+        /// # Errors
+        /// - `ExemptionImportNotFound` if there is no import for `exempt_key`.
+        /// - `ExemptionBatchTooLarge` if `entities` is longer than `MaxExemptionsPerBatch`.
+        /// - `ExemptionImportOverflow` if more than the announced number of entities are imported.
+        /// - `ExemptionBatchHashMismatch` if the running hash after the batch isn't `batch_hash`.
+        /// - `UnauthorizedAgent` if `origin` is not agent-permissioned for `asset`.
+        ///
+        /// # Permissions
+        /// - Agent
+        /// - Asset
+        #[weight = <T as Config>::WeightInfo::import_exemption_batch(entities.len() as u32)]
+        pub fn import_exemption_batch(origin, exempt_key: TransferConditionExemptKey, entities: Vec<ScopeId>, batch_hash: H256) {
+            Self::base_import_exemption_batch(origin, exempt_key, entities, batch_hash)?;
+        }
+
+        /// Completes an import of exempt entities, after verifying its integrity.
+        ///
+        /// # Arguments
+        /// - `origin` - a signer that has permissions to act as an agent of `exempt_key.asset`.
+        /// - `exempt_key` - the key of the import to finalize.
+        ///
+        /// # Errors
+        /// - `ExemptionImportNotFound` if there is no import for `exempt_key`.
+        /// - `ExemptionImportIncomplete` if not all entities have been imported.
+        /// - `ExemptionImportHashMismatch` if the imported entities don't match the expected hash.
+        /// - `UnauthorizedAgent` if `origin` is not agent-permissioned for `asset`.
+        ///
+        /// # Permissions
+        /// - Agent
+        /// - Asset
+        #[weight = <T as Config>::WeightInfo::finalize_exemption_import()]
+        pub fn finalize_exemption_import(origin, exempt_key: TransferConditionExemptKey) {
+            Self::base_finalize_exemption_import(origin, exempt_key)?;
+        }
+
+        /// Aborts an ongoing import of exempt entities.
+        ///
+        /// Entities imported by previous batches stay exempt, they can be removed
+        /// with `set_entities_exempt`.
+        ///
+        /// # Arguments
+        /// - `origin` - a signer that has permissions to act as an agent of `exempt_key.asset`.
+        /// - `exempt_key` - the key of the import to abort.
+        ///
+        /// # Errors
+        /// - `ExemptionImportNotFound` if there is no import for `exempt_key`.
+        /// - `UnauthorizedAgent` if `origin` is not agent-permissioned for `asset`.
+        ///
+        /// # Permissions
+        /// - Agent
+        /// - Asset
+        #[weight = <T as Config>::WeightInfo::abort_exemption_import()]
+        pub fn abort_exemption_import(origin, exempt_key: TransferConditionExemptKey) {
+            Self::base_abort_exemption_import(origin, exempt_key)?;
+        }
+
+        /// Enable/disable tracking of the transfer count and volume per era for an asset.
+        ///
+        /// Disabling the tracking keeps the volumes recorded for past eras.
+        ///
+        /// # Arguments
+        /// - `origin` - a signer that has permissions to act as an agent of `asset`.
+        /// - `asset` - the asset to enable/disable transfer volume tracking on.
+        /// - `enabled` - whether transfer volumes should be tracked.
+        ///
+        /// # Errors
+        /// - `UnauthorizedAgent` if `origin` is not agent-permissioned for `asset`.
+        ///
+        /// # Permissions
+        /// - Agent
+        /// - Asset
+        #[weight = <T as Config>::WeightInfo::set_transfer_volume_tracking()]
+        pub fn set_transfer_volume_tracking(origin, asset: AssetScope, enabled: bool) {
+            Self::base_set_transfer_volume_tracking(origin, asset, enabled)?;
+        }
     }
 }
 
@@ -339,6 +506,253 @@ impl<T: Config> Module<T> {
         Ok(())
     }
 
+    fn base_start_exemption_import(
+        origin: T::RuntimeOrigin,
+        exempt_key: TransferConditionExemptKey,
+        total_entries: u32,
+        expected_hash: H256,
+    ) -> DispatchResult {
+        // Check EA permissions for asset.
+        let did = Self::ensure_asset_perms(origin, exempt_key.asset)?;
+        ensure!(
+            !ExemptionImports::contains_key(&exempt_key),
+            Error::<T>::ExemptionImportInProgress
+        );
+
+        ExemptionImports::insert(&exempt_key, BatchImport::new(total_entries, expected_hash));
+        Self::deposit_event(Event::ExemptionImportStarted(
+            did,
+            exempt_key,
+            total_entries,
+            expected_hash,
+        ));
+        Ok(())
+    }
+
+    fn base_import_exemption_batch(
+        origin: T::RuntimeOrigin,
+        exempt_key: TransferConditionExemptKey,
+        entities: Vec<ScopeId>,
+        batch_hash: H256,
+    ) -> DispatchResult {
+        // Check EA permissions for asset.
+        let did = Self::ensure_asset_perms(origin, exempt_key.asset)?;
+        ensure!(
+            entities.len() <= T::MaxExemptionsPerBatch::get() as usize,
+            Error::<T>::ExemptionBatchTooLarge
+        );
+        let mut import =
+            ExemptionImports::get(&exempt_key).ok_or(Error::<T>::ExemptionImportNotFound)?;
+        ensure!(
+            (import.imported_entries as usize).saturating_add(entities.len())
+                <= import.total_entries as usize,
+            Error::<T>::ExemptionImportOverflow
+        );
+
+        // Verify the batch before exempting any of its entities.
+        entities.iter().for_each(|entity| import.add_entry(entity));
+        ensure!(
+            import.running_hash == batch_hash,
+            Error::<T>::ExemptionBatchHashMismatch
+        );
+
+        // Commit changes to storage.
+        for entity in &entities {
+            TransferConditionExemptEntities::insert(&exempt_key, entity, true);
+        }
+        let imported_entries = import.imported_entries;
+        let total_entries = import.total_entries;
+        ExemptionImports::insert(&exempt_key, import);
+
+        Self::deposit_event(Event::ExemptionBatchImported(
+            did,
+            exempt_key,
+            imported_entries,
+            total_entries,
+        ));
+        Ok(())
+    }
+
+    fn base_finalize_exemption_import(
+        origin: T::RuntimeOrigin,
+        exempt_key: TransferConditionExemptKey,
+    ) -> DispatchResult {
+        // Check EA permissions for asset.
+        let did = Self::ensure_asset_perms(origin, exempt_key.asset)?;
+        let import =
+            ExemptionImports::get(&exempt_key).ok_or(Error::<T>::ExemptionImportNotFound)?;
+        ensure!(
+            import.imported_entries == import.total_entries,
+            Error::<T>::ExemptionImportIncomplete
+        );
+        ensure!(
+            import.is_complete(),
+            Error::<T>::ExemptionImportHashMismatch
+        );
+
+        ExemptionImports::remove(&exempt_key);
+        Self::deposit_event(Event::ExemptionImportFinalized(
+            did,
+            exempt_key,
+            import.total_entries,
+            import.running_hash,
+        ));
+        Ok(())
+    }
+
+    fn base_abort_exemption_import(
+        origin: T::RuntimeOrigin,
+        exempt_key: TransferConditionExemptKey,
+    ) -> DispatchResult {
+        // Check EA permissions for asset.
+        let did = Self::ensure_asset_perms(origin, exempt_key.asset)?;
+        let import =
+            ExemptionImports::take(&exempt_key).ok_or(Error::<T>::ExemptionImportNotFound)?;
+
+        Self::deposit_event(Event::ExemptionImportAborted(
+            did,
+            exempt_key,
+            import.imported_entries,
+        ));
+        Ok(())
+    }
+
+    fn base_set_transfer_volume_tracking(
+        origin: T::RuntimeOrigin,
+        asset: AssetScope,
+        enabled: bool,
+    ) -> DispatchResult {
+        // Check EA permissions for asset.
+        let did = Self::ensure_asset_perms(origin, asset)?;
+
+        if enabled {
+            TransferVolumeTracking::insert(&asset, true);
+        } else {
+            TransferVolumeTracking::remove(&asset);
+        }
+        Self::deposit_event(Event::TransferVolumeTrackingSet(did, asset, enabled));
+        Ok(())
+    }
+
+    /// Returns the current transfer volume era.
+    pub fn current_transfer_volume_era() -> TransferVolumeEra {
+        let era_length = T::TransferVolumeEraLength::get().max(One::one());
+        (frame_system::Pallet::<T>::block_number() / era_length).unique_saturated_into()
+    }
+
+    /// Returns the current day, in days since the Unix epoch.
+    fn current_day() -> u32 {
+        let now: u64 = <pallet_timestamp::Pallet<T>>::get().unique_saturated_into();
+        (now / MILLISECONDS_PER_DAY).unique_saturated_into()
+    }
+
+    /// Enables or disables the recording of investor transfer volumes for `asset`.
+    ///
+    /// Volumes recorded so far are kept when disabled.
+    pub fn set_investor_volume_tracking(asset: AssetScope, enabled: bool) {
+        if enabled {
+            InvestorVolumeTracking::insert(&asset, true);
+        } else {
+            InvestorVolumeTracking::remove(&asset);
+        }
+    }
+
+    /// Adds `amount` to the transfer volume of `did` in `asset` for the current day,
+    /// pruning the days no longer kept.
+    fn record_investor_transfer(asset: AssetScope, did: IdentityId, amount: Balance) {
+        let today = Self::current_day();
+        let oldest_kept = today.saturating_sub(MAX_INVESTOR_VOLUME_DAYS - 1);
+        InvestorTransferVolumes::mutate(asset, did, |volumes| {
+            volumes.retain(|day, _| *day >= oldest_kept);
+            let volume = volumes.entry(today).or_default();
+            *volume = volume.saturating_add(amount);
+        });
+    }
+
+    /// Returns the tokens transferred, sent or received, by `did` in `asset`
+    /// within the last `days` days, today included.
+    ///
+    /// Only the last `MAX_INVESTOR_VOLUME_DAYS` days are kept.
+    pub fn investor_transfer_volume(asset: AssetScope, did: IdentityId, days: u32) -> Balance {
+        if days == 0 {
+            return 0;
+        }
+        let today = Self::current_day();
+        let since = today.saturating_sub(days - 1);
+        Self::investor_transfer_volumes(asset, did)
+            .range(since..=today)
+            .fold(0, |total, (_, volume)| total.saturating_add(*volume))
+    }
+
+    /// Adds `volume` tokens to the volume of `ticker` settled in the current day,
+    /// pruning the days no longer kept.
+    pub fn record_settled_volume(ticker: &Ticker, volume: Balance) {
+        if volume == 0 {
+            return;
+        }
+        let today = Self::current_day();
+        let oldest_kept = today.saturating_sub(MAX_SETTLED_VOLUME_DAYS - 1);
+        SettledVolumes::mutate(AssetScope::Ticker(*ticker), |volumes| {
+            volumes.retain(|day, _| *day >= oldest_kept);
+            let settled = volumes.entry(today).or_default();
+            *settled = settled.saturating_add(volume);
+        });
+    }
+
+    /// Returns the tokens of `ticker` settled within the last `days` days, today included.
+    ///
+    /// Only the last `MAX_SETTLED_VOLUME_DAYS` days are kept.
+    pub fn settled_volume(ticker: &Ticker, days: u32) -> Balance {
+        if days == 0 {
+            return 0;
+        }
+        let today = Self::current_day();
+        let since = today.saturating_sub(days - 1);
+        Self::settled_volumes(AssetScope::Ticker(*ticker))
+            .range(since..=today)
+            .fold(0, |total, (_, volume)| total.saturating_add(*volume))
+    }
+
+    /// Returns the transfer volumes of `asset` for the eras in `[start_era, end_era]`.
+    ///
+    /// Eras without transfers are not included. At most `MAX_TRANSFER_VOLUME_ERAS` eras,
+    /// starting at `start_era`, are read.
+    pub fn transfer_volumes_in_range(
+        asset: AssetScope,
+        start_era: TransferVolumeEra,
+        end_era: TransferVolumeEra,
+    ) -> Vec<(TransferVolumeEra, TransferVolume)> {
+        let end_era = end_era.min(start_era.saturating_add(MAX_TRANSFER_VOLUME_ERAS - 1));
+        (start_era..=end_era)
+            .filter_map(|era| {
+                TransferVolumes::try_get(asset, era)
+                    .ok()
+                    .map(|volume| (era, volume))
+            })
+            .collect()
+    }
+
+    /// Returns up to `limit` entities exempt for `exempt_key`, starting after `start_after`.
+    ///
+    /// Used to export an asset's exempt entities in pages.
+    pub fn exempt_entities_page(
+        exempt_key: &TransferConditionExemptKey,
+        start_after: Option<ScopeId>,
+        limit: u32,
+    ) -> Vec<ScopeId> {
+        let iter = match start_after {
+            Some(entity) => {
+                let raw_key = TransferConditionExemptEntities::hashed_key_for(exempt_key, entity);
+                TransferConditionExemptEntities::iter_prefix_from(exempt_key, raw_key)
+            }
+            None => TransferConditionExemptEntities::iter_prefix(exempt_key),
+        };
+        iter.filter(|(_, exempt)| *exempt)
+            .map(|(entity, _)| entity)
+            .take(limit as usize)
+            .collect()
+    }
+
     /// Update asset stats.
     pub fn update_asset_balance_stats(
         key1: Stat1stKey,
@@ -460,10 +874,23 @@ impl<T: Config> Module<T> {
             return;
         }
 
+        // Only transfers between investors are counted towards the transfer volume.
+        let asset = AssetScope::Ticker(*ticker);
+        if let (Some(from), Some(to)) = (from_did, to_did) {
+            if Self::transfer_volume_tracking(asset) {
+                TransferVolumes::mutate(asset, Self::current_transfer_volume_era(), |volume| {
+                    volume.add_transfer(amount)
+                });
+            }
+            if Self::investor_volume_tracking(asset) {
+                Self::record_investor_transfer(asset, *from, amount);
+                Self::record_investor_transfer(asset, *to, amount);
+            }
+        }
+
         // Pre-Calculate the investor count changes.
         let count_changes = Self::investor_count_changes(from_balance, to_balance, amount);
 
-        let asset = AssetScope::Ticker(*ticker);
         // Update active asset stats.
         for stat_type in Self::active_asset_stats(asset).into_iter() {
             let key1 = Stat1stKey { asset, stat_type };
@@ -790,6 +1217,52 @@ impl<T: Config> Module<T> {
             .collect()
     }
 
+    /// Moves the statistics, transfer conditions, exemptions, transfer volumes and settled volumes of asset `old` to asset `new`.
+    /// Each statistic, exemption and transfer volume moved uses up one of the `entries`.
+    pub fn migrate_asset_scope(
+        old: AssetScope,
+        new: AssetScope,
+        entries: &mut u32,
+    ) -> DispatchResult {
+        for stat_type in Self::active_asset_stats(old) {
+            let new_key = Stat1stKey {
+                asset: new,
+                stat_type,
+            };
+            for (key2, value) in AssetStats::drain_prefix(Stat1stKey {
+                asset: old,
+                stat_type,
+            }) {
+                try_use_entry::<T>(entries)?;
+                AssetStats::insert(new_key, key2, value);
+            }
+        }
+        ActiveAssetStats::<T>::swap(old, new);
+
+        for condition in Self::asset_transfer_compliance(old).requirements {
+            let (old_key, new_key) = (condition.get_exempt_key(old), condition.get_exempt_key(new));
+            for (entity, exempt) in TransferConditionExemptEntities::drain_prefix(&old_key) {
+                try_use_entry::<T>(entries)?;
+                TransferConditionExemptEntities::insert(&new_key, entity, exempt);
+            }
+            ExemptionImports::swap(&old_key, &new_key);
+        }
+        AssetTransferCompliances::<T>::swap(old, new);
+
+        TransferVolumeTracking::swap(old, new);
+        for (era, volume) in TransferVolumes::drain_prefix(old) {
+            try_use_entry::<T>(entries)?;
+            TransferVolumes::insert(new, era, volume);
+        }
+        InvestorVolumeTracking::swap(old, new);
+        for (did, volumes) in InvestorTransferVolumes::drain_prefix(old) {
+            try_use_entry::<T>(entries)?;
+            InvestorTransferVolumes::insert(new, did, volumes);
+        }
+        SettledVolumes::swap(old, new);
+        Ok(())
+    }
+
     /// Helper function to get investor count for tests.
     pub fn investor_count(ticker: Ticker) -> u128 {
         AssetStats::get(Stat1stKey::investor_count(ticker), Stat2ndKey::NoClaimStat)
@@ -821,5 +1294,19 @@ decl_error! {
         StatTypeLimitReached,
         /// The limit of TransferConditions allowed for an asset has been reached.
         TransferConditionLimitReached,
+        /// An import of exempt entities is already in progress for this key.
+        ExemptionImportInProgress,
+        /// There is no import of exempt entities in progress for this key.
+        ExemptionImportNotFound,
+        /// The batch of exempt entities exceeds `MaxExemptionsPerBatch`.
+        ExemptionBatchTooLarge,
+        /// More entities were imported than announced when the import was started.
+        ExemptionImportOverflow,
+        /// Not all announced entities have been imported.
+        ExemptionImportIncomplete,
+        /// The hash of the imported entities doesn't match the expected hash.
+        ExemptionImportHashMismatch,
+        /// The running hash after a batch of exempt entities doesn't match the batch hash.
+        ExemptionBatchHashMismatch,
     }
 }
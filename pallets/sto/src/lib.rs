@@ -30,14 +30,14 @@ use codec::{Decode, Encode};
 use frame_support::{
     decl_error, decl_event, decl_module, decl_storage, dispatch::DispatchResult, ensure,
 };
-use pallet_base::try_next_post;
+use pallet_base::{try_next_post, try_use_entry};
 use pallet_identity::PermissionedCallOriginData;
 use pallet_settlement::{
     LegAsset, LegV2, ReceiptDetails, SettlementType, VenueId, VenueInfo, VenueType,
 };
 use polymesh_common_utilities::{
     portfolio::PortfolioSubTrait,
-    traits::{identity, portfolio},
+    traits::{asset::TickerMigrationHandler, identity, portfolio},
     with_transaction,
 };
 use polymesh_primitives::impl_checked_inc;
@@ -596,6 +596,22 @@ decl_module! {
     }
 }
 
+impl<T: Config> TickerMigrationHandler for Module<T> {
+    fn migrate_ticker(old: &Ticker, new: &Ticker, entries: &mut u32) -> DispatchResult {
+        for (id, mut fundraiser) in <Fundraisers<T>>::drain_prefix(old) {
+            try_use_entry::<T>(entries)?;
+            fundraiser.offering_asset = *new;
+            <Fundraisers<T>>::insert(new, id, fundraiser);
+        }
+        for (id, name) in FundraiserNames::drain_prefix(old) {
+            try_use_entry::<T>(entries)?;
+            FundraiserNames::insert(new, id, name);
+        }
+        FundraiserCount::swap(old, new);
+        Ok(())
+    }
+}
+
 impl<T: Config> Module<T> {
     fn set_frozen(
         origin: T::RuntimeOrigin,
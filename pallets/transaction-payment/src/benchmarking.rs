@@ -0,0 +1,68 @@
+// This file is part of the Polymesh distribution (https://github.com/PolymeshAssociation/Polymesh).
+// Copyright (c) 2020 Polymath
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, version 3.
+
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+// General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+use crate::*;
+
+use frame_benchmarking::benchmarks;
+use frame_system::RawOrigin;
+use polymesh_common_utilities::{
+    benchs::{user, AccountIdOf},
+    traits::{identity::Config as IdentityConfig, TestUtilsFn},
+};
+use polymesh_primitives::CustomClaimTypeId;
+
+fn fee_tiers(issuer: IdentityId, t: u32) -> Vec<FeeTier> {
+    (0..t)
+        .map(|i| FeeTier {
+            claim_type: ClaimType::Custom(CustomClaimTypeId(i)),
+            issuer,
+            length_fee: Permill::from_percent(50),
+            weight_fee: Permill::from_percent(50),
+        })
+        .collect()
+}
+
+benchmarks! {
+    where_clause { where T: IdentityConfig + TestUtilsFn<AccountIdOf<T>>, BalanceOf<T>: FixedPointOperand }
+
+    set_fee_tiers {
+        let t in 0..MAX_FEE_TIERS as u32;
+
+        let issuer = user::<T>("issuer", 0);
+        let tiers = fee_tiers(issuer.did(), t);
+    }: _(RawOrigin::Root, tiers)
+    verify {
+        assert_eq!(FeeTiers::decode_len().unwrap_or_default(), t as usize);
+    }
+
+    apply_fee_tier {
+        // The payer holds none of the claims, so every tier is looked up.
+        let t in 0..MAX_FEE_TIERS as u32;
+
+        let issuer = user::<T>("issuer", 0);
+        let payer = user::<T>("payer", 0);
+        FeeTiers::put(fee_tiers(issuer.did(), t));
+        let details = FeeDetails {
+            inclusion_fee: Some(InclusionFee {
+                base_fee: 1u32.into(),
+                len_fee: 1_000u32.into(),
+                adjusted_weight_fee: 1_000u32.into(),
+            }),
+            tip: 0u32.into(),
+        };
+    }: {
+        Module::<T>::apply_fee_tier(&payer.account(), details);
+    }
+}
@@ -48,26 +48,34 @@
 //!     final state of the chain at the end of the previous block. This can be configured via
 //!     [`Config::FeeMultiplierUpdate`]
 //!   - How the fees are paid via [`Config::OnChargeTransaction`].
+//!
+//! Polymesh: governance can also set fee tiers, which reduce the length and weight fees paid
+//! by identities holding a given claim, e.g. the members of a market-maker program.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+#[cfg(feature = "runtime-benchmarks")]
+pub mod benchmarking;
+
 use codec::{Decode, Encode, MaxEncodedLen};
 use frame_support::{
-    decl_event, decl_module, decl_storage,
+    decl_error, decl_event, decl_module, decl_storage,
     dispatch::{
         DispatchClass, DispatchInfo, DispatchResult, GetDispatchInfo, Pays, PostDispatchInfo,
         Weight,
     },
+    ensure,
     traits::{Currency, Get, GetCallMetadata},
     weights::{WeightToFee, WeightToFeeCoefficient, WeightToFeePolynomial},
 };
+use frame_system::ensure_root;
 use polymesh_common_utilities::traits::{
     group::GroupTrait,
     identity::IdentityFnTrait,
     relayer::SubsidiserTrait,
     transaction_payment::{CddAndFeeDetails, ChargeTxFee},
 };
-use polymesh_primitives::TransactionError;
+use polymesh_primitives::{ClaimType, IdentityId, TransactionError};
 use scale_info::TypeInfo;
 use sp_runtime::{
     traits::{
@@ -78,7 +86,7 @@ use sp_runtime::{
         InvalidTransaction, TransactionPriority, TransactionValidity, TransactionValidityError,
         ValidTransaction,
     },
-    FixedPointNumber, FixedPointOperand, FixedU128, Perquintill, RuntimeDebug,
+    FixedPointNumber, FixedPointOperand, FixedU128, Permill, Perquintill, RuntimeDebug,
 };
 use sp_std::prelude::*;
 
@@ -93,6 +101,11 @@ pub type Multiplier = FixedU128;
 
 type BalanceOf<T> = <<T as Config>::OnChargeTransaction as OnChargeTransaction<T>>::Balance;
 
+pub trait WeightInfo {
+    fn set_fee_tiers(t: u32) -> Weight;
+    fn apply_fee_tier(t: u32) -> Weight;
+}
+
 /// A struct to update the weight multiplier per block. It implements `Convert<Multiplier,
 /// Multiplier>`, meaning that it can convert the previous multiplier to the next one. This should
 /// be called on `on_finalize` of a block, prior to potentially cleaning the weight data from the
@@ -311,6 +324,22 @@ impl Default for Releases {
     }
 }
 
+/// Maximum number of fee tiers.
+pub const MAX_FEE_TIERS: usize = 16;
+
+/// Reduced fee coefficients applied to the transactions paid by identities holding a claim.
+#[derive(Encode, Decode, TypeInfo, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct FeeTier {
+    /// Type of the unscoped claim that identities must hold.
+    pub claim_type: ClaimType,
+    /// Issuer of the claim.
+    pub issuer: IdentityId,
+    /// Share of the length fee paid by the identities of this tier.
+    pub length_fee: Permill,
+    /// Share of the adjusted weight fee paid by the identities of this tier.
+    pub weight_fee: Permill,
+}
+
 pub trait Config: frame_system::Config + pallet_timestamp::Config {
     /// The overarching event type.
     type RuntimeEvent: From<Event<Self>> + Into<<Self as frame_system::Config>::RuntimeEvent>;
@@ -355,12 +384,19 @@ pub trait Config: frame_system::Config + pallet_timestamp::Config {
     // Polymesh note: This was specifically added for Polymesh
     /// Identity functionality.
     type Identity: IdentityFnTrait<Self::AccountId>;
+
+    /// Weight information for extrinsics and fee tier lookups in this pallet.
+    type WeightInfo: WeightInfo;
 }
 
 decl_storage! {
     trait Store for Module<T: Config> as TransactionPayment {
         pub NextFeeMultiplier get(fn next_fee_multiplier): Multiplier = Multiplier::saturating_from_integer(1);
 
+        /// Fee tiers set by governance. An identity holding the claims of several tiers
+        /// pays the lowest length and weight fees among them.
+        pub FeeTiers get(fn fee_tiers): Vec<FeeTier>;
+
         StorageVersion build(|_: &GenesisConfig| Releases::V2): Releases;
     }
 }
@@ -374,11 +410,22 @@ decl_event! {
         /// A transaction fee `actual_fee`, of which `tip` was added to the minimum inclusion fee,
         /// has been paid by `who`.
         TransactionFeePaid { who: AccountId, actual_fee: Balance, tip: Balance },
+        /// Governance set the fee tiers.
+        FeeTiersSet { tiers: Vec<FeeTier> },
+    }
+}
+
+decl_error! {
+    pub enum Error for Module<T: Config> {
+        /// More than `MAX_FEE_TIERS` fee tiers were given.
+        TooManyFeeTiers,
     }
 }
 
 decl_module! {
     pub struct Module<T: Config> for enum Call where origin: T::RuntimeOrigin {
+        type Error = Error<T>;
+
         fn deposit_event() = default;
 
         /// The fee to be paid for making a transaction; the per-byte portion.
@@ -436,6 +483,27 @@ decl_module! {
                 );
             })
         }
+
+        /// Sets the fee tiers, replacing the current ones.
+        ///
+        /// # Arguments
+        /// * `origin` - must be root.
+        /// * `tiers` - the new fee tiers, at most `MAX_FEE_TIERS`.
+        ///
+        /// # Errors
+        /// * `TooManyFeeTiers` if more than `MAX_FEE_TIERS` tiers are given.
+        #[weight = (
+            <T as Config>::WeightInfo::set_fee_tiers(tiers.len() as u32),
+            DispatchClass::Operational,
+            Pays::Yes
+        )]
+        pub fn set_fee_tiers(origin, tiers: Vec<FeeTier>) -> DispatchResult {
+            ensure_root(origin)?;
+            ensure!(tiers.len() <= MAX_FEE_TIERS, Error::<T>::TooManyFeeTiers);
+            FeeTiers::put(&tiers);
+            Self::deposit_event(RawEvent::FeeTiersSet { tiers });
+            Ok(())
+        }
     }
 }
 
@@ -451,9 +519,13 @@ where
     ///
     /// All dispatchables must be annotated with weight and will have some fee info. This function
     /// always returns.
+    ///
+    /// Polymesh: The fee is reduced by the fee tier of `payer`, which should be the signer
+    /// of the extrinsic.
     pub fn query_info<Extrinsic: sp_runtime::traits::Extrinsic + GetDispatchInfo>(
         unchecked_extrinsic: Extrinsic,
         len: u32,
+        payer: Option<T::AccountId>,
     ) -> RuntimeDispatchInfo<BalanceOf<T>>
     where
         T::RuntimeCall: Dispatchable<Info = DispatchInfo>,
@@ -466,7 +538,7 @@ where
         let dispatch_info = <Extrinsic as GetDispatchInfo>::get_dispatch_info(&unchecked_extrinsic);
 
         let partial_fee = if unchecked_extrinsic.is_signed().unwrap_or(false) {
-            Self::compute_fee(len, &dispatch_info, 0u32.into())
+            Self::compute_payer_fee_details(len, &dispatch_info, payer).final_fee()
         } else {
             // Unsigned extrinsics have no partial fee.
             0u32.into()
@@ -482,9 +554,12 @@ where
     }
 
     /// Query the detailed fee of a given `call`.
+    ///
+    /// Polymesh: The fee is reduced by the fee tier of `payer`, as in `query_info`.
     pub fn query_fee_details<Extrinsic: sp_runtime::traits::Extrinsic + GetDispatchInfo>(
         unchecked_extrinsic: Extrinsic,
         len: u32,
+        payer: Option<T::AccountId>,
     ) -> FeeDetails<BalanceOf<T>>
     where
         T::RuntimeCall: Dispatchable<Info = DispatchInfo>,
@@ -494,7 +569,7 @@ where
         let tip = 0u32.into();
 
         if unchecked_extrinsic.is_signed().unwrap_or(false) {
-            Self::compute_fee_details(len, &dispatch_info, tip)
+            Self::compute_payer_fee_details(len, &dispatch_info, payer)
         } else {
             // Unsigned extrinsics have no inclusion fee.
             FeeDetails {
@@ -530,6 +605,23 @@ where
         Self::compute_fee_details(len, &dispatch_info, tip)
     }
 
+    // Polymesh note: This was specifically added for Polymesh
+    /// Compute the fee details of a transaction without tip, reduced by the fee tier of `payer`.
+    fn compute_payer_fee_details(
+        len: u32,
+        info: &DispatchInfoOf<T::RuntimeCall>,
+        payer: Option<T::AccountId>,
+    ) -> FeeDetails<BalanceOf<T>>
+    where
+        T::RuntimeCall: Dispatchable<Info = DispatchInfo>,
+    {
+        let details = Self::compute_fee_details(len, info, 0u32.into());
+        match payer {
+            Some(payer) => Self::apply_fee_tier(&payer, details),
+            None => details,
+        }
+    }
+
     /// Compute the final fee value for a particular transaction.
     pub fn compute_fee(
         len: u32,
@@ -624,6 +716,44 @@ where
         }
     }
 
+    // Polymesh note: This was specifically added for Polymesh
+    /// Reduces the length and weight fees of `details` according to the best fee tier of the
+    /// identity of `payer`. The base fee and the tip are left unchanged.
+    pub fn apply_fee_tier(
+        payer: &T::AccountId,
+        mut details: FeeDetails<BalanceOf<T>>,
+    ) -> FeeDetails<BalanceOf<T>> {
+        let (fee, did) = match (
+            details.inclusion_fee.as_mut(),
+            T::Identity::get_identity(payer),
+        ) {
+            (Some(fee), Some(did)) => (fee, did),
+            _ => return details,
+        };
+        let (length_share, weight_share) = Self::fee_tiers()
+            .into_iter()
+            .filter(|tier| T::Identity::has_valid_claim(did, tier.claim_type, tier.issuer))
+            .fold(
+                (Permill::from_percent(100), Permill::from_percent(100)),
+                |(length, weight), tier| (length.min(tier.length_fee), weight.min(tier.weight_fee)),
+            );
+        fee.len_fee = Multiplier::from(length_share).saturating_mul_int(fee.len_fee);
+        fee.adjusted_weight_fee =
+            Multiplier::from(weight_share).saturating_mul_int(fee.adjusted_weight_fee);
+        details
+    }
+
+    // Polymesh note: This was specifically added for Polymesh
+    /// Registers the weight of `apply_fee_tier` in the current block, as the fee tier lookup
+    /// is done outside of the dispatch.
+    fn register_fee_tier_weight(class: DispatchClass) {
+        let tiers = FeeTiers::decode_len().unwrap_or_default() as u32;
+        <frame_system::Pallet<T>>::register_extra_weight_unchecked(
+            <T as Config>::WeightInfo::apply_fee_tier(tiers),
+            class,
+        );
+    }
+
     fn length_to_fee(length: u32) -> BalanceOf<T> {
         let len = <BalanceOf<T>>::from(length);
         let per_byte = T::TransactionByteFee::get();
@@ -691,19 +821,22 @@ where
         len: usize,
     ) -> Result<WithdrawFeeInfo<T, T::AccountId>, TransactionValidityError> {
         let tip = self.0;
-        let fee = Module::<T>::compute_fee(len as u32, info, tip);
+        let fee_details = Module::<T>::compute_fee_details(len as u32, info, tip);
 
         // Polymesh: Changed how the tx fee payer is selected.
 
         // Only mess with balances if fee is not zero.
-        if fee.is_zero() {
+        if fee_details.final_fee().is_zero() {
             let liquidity_info = Default::default();
-            return Ok((fee, liquidity_info, None));
+            return Ok((Zero::zero(), liquidity_info, None));
         }
 
         // Get the payer for this transaction.
         let payer_key =
             T::CddHandler::get_valid_payer(call, &who)?.ok_or(InvalidTransaction::Payment)?;
+        // Polymesh: The fee is reduced by the fee tier of the payer.
+        let fee = Module::<T>::apply_fee_tier(&payer_key, fee_details).final_fee();
+        Module::<T>::register_fee_tier_weight(info.class);
 
         // Check if the payer is being subsidised.
         let metadata = call.get_call_metadata();
@@ -833,12 +966,18 @@ where
             Some(pre) => pre,
             None => return Ok(()),
         };
-        let actual_fee = Module::<T>::compute_actual_fee(len as u32, info, post_info, tip);
-
         // Fee returned to original payer.
         // If payer context is empty, the fee is returned to the caller account.
         let payer = T::CddHandler::get_payer_from_context().unwrap_or(who.clone());
 
+        // Polymesh: The fee is reduced by the fee tier of the payer, as in `withdraw_fee`.
+        let actual_fee = Module::<T>::apply_fee_tier(
+            &payer,
+            Module::<T>::compute_actual_fee_details(len as u32, info, post_info, tip),
+        )
+        .final_fee();
+        Module::<T>::register_fee_tier_weight(info.class);
+
         // `fee_key` is either a subsidiser or the original payer.
         let fee_key = if let Some(subsidiser_key) = subsidiser {
             // Debit the actual fee from the subsidy.
@@ -49,6 +49,7 @@ pub mod pallet_statistics;
 pub mod pallet_sto;
 pub mod pallet_test_utils;
 pub mod pallet_timestamp;
+pub mod pallet_transaction_payment;
 pub mod pallet_treasury;
 pub mod pallet_utility;
 pub mod polymesh_contracts;
@@ -61,13 +61,17 @@ impl pallet_asset::WeightInfo for SubstrateWeight {
     // Storage: ProtocolFee Coefficient (r:1 w:0)
     // Storage: ProtocolFee BaseFees (r:1 w:0)
     // Storage: Identity CurrentPayer (r:1 w:0)
+    // Storage: Asset TickerExpiry (r:1 w:0)
+    // Storage: Asset NextExpiringTickersBucket (r:1 w:1)
     // Storage: Asset AssetOwnershipRelations (r:0 w:1)
     // Storage: Asset ClassicTickers (r:0 w:1)
+    // Storage: Asset TickerAutoRenewal (r:0 w:1)
+    // Storage: Asset ExpiringTickers (r:0 w:1)
     fn register_ticker() -> Weight {
         // Minimum execution time: 53_809 nanoseconds.
         Weight::from_ref_time(55_263_000)
-            .saturating_add(DbWeight::get().reads(8))
-            .saturating_add(DbWeight::get().writes(3))
+            .saturating_add(DbWeight::get().reads(10))
+            .saturating_add(DbWeight::get().writes(6))
     }
     // Storage: Identity KeyRecords (r:1 w:0)
     // Storage: Identity Authorizations (r:1 w:1)
@@ -115,6 +119,11 @@ impl pallet_asset::WeightInfo for SubstrateWeight {
     // Storage: Asset Identifiers (r:0 w:1)
     // Storage: ExternalAgents AgentOf (r:0 w:1)
     // Storage: ExternalAgents GroupOfAgent (r:0 w:1)
+    // Storage: ComplianceManager IssuerRequirementsTemplates (r:1 w:0)
+    // Storage: ComplianceManager DefaultRequirementsTemplates (r:1 w:0)
+    // Storage: ComplianceManager AssetCompliances (r:0 w:1)
+    // Storage: Asset TickerExpiry (r:1 w:0)
+    // Storage: Asset TickerAutoRenewal (r:0 w:1)
     /// The range of component `n` is `[1, 128]`.
     /// The range of component `i` is `[1, 512]`.
     /// The range of component `f` is `[1, 128]`.
@@ -127,8 +136,8 @@ impl pallet_asset::WeightInfo for SubstrateWeight {
             .saturating_add(Weight::from_ref_time(66_001).saturating_mul(i.into()))
             // Manually set weight for `f`
             .saturating_add(Weight::from_ref_time(100_000).saturating_mul(f.into()))
-            .saturating_add(DbWeight::get().reads(11))
-            .saturating_add(DbWeight::get().writes(12))
+            .saturating_add(DbWeight::get().reads(14))
+            .saturating_add(DbWeight::get().writes(14))
     }
     // Storage: Identity KeyRecords (r:1 w:0)
     // Storage: ExternalAgents GroupOfAgent (r:1 w:0)
@@ -470,4 +479,359 @@ impl pallet_asset::WeightInfo for SubstrateWeight {
             .saturating_add(DbWeight::get().reads(6 as u64))
             .saturating_add(DbWeight::get().writes(2 as u64))
     }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: ExternalAgents GroupOfAgent (r:1 w:0)
+    // Storage: Permissions CurrentPalletName (r:1 w:0)
+    // Storage: Permissions CurrentDispatchableName (r:1 w:0)
+    // Storage: Asset Tokens (r:1 w:0)
+    // Storage: Asset PendingAuthorizedSupply (r:0 w:1)
+    fn propose_authorized_supply() -> Weight {
+        Weight::from_ref_time(33_412_000 as u64)
+            .saturating_add(DbWeight::get().reads(5 as u64))
+            .saturating_add(DbWeight::get().writes(1 as u64))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: ExternalAgents GroupOfAgent (r:1 w:0)
+    // Storage: Permissions CurrentPalletName (r:1 w:0)
+    // Storage: Permissions CurrentDispatchableName (r:1 w:0)
+    // Storage: Asset PendingAuthorizedSupply (r:1 w:1)
+    // Storage: Asset Tokens (r:1 w:0)
+    // Storage: Asset AuthorizedSupply (r:0 w:1)
+    fn approve_authorized_supply() -> Weight {
+        Weight::from_ref_time(37_906_000 as u64)
+            .saturating_add(DbWeight::get().reads(6 as u64))
+            .saturating_add(DbWeight::get().writes(2 as u64))
+    }
+    // Storage: Asset TickerExpiry (r:0 w:1)
+    fn set_ticker_expiry_periods() -> Weight {
+        Weight::from_ref_time(14_215_000 as u64).saturating_add(DbWeight::get().writes(1 as u64))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: Permissions CurrentPalletName (r:1 w:0)
+    // Storage: Permissions CurrentDispatchableName (r:1 w:0)
+    // Storage: Asset Tickers (r:1 w:0)
+    // Storage: Timestamp Now (r:1 w:0)
+    // Storage: Asset TickerAutoRenewal (r:0 w:1)
+    fn set_ticker_auto_renewal() -> Weight {
+        Weight::from_ref_time(29_871_000 as u64)
+            .saturating_add(DbWeight::get().reads(5 as u64))
+            .saturating_add(DbWeight::get().writes(1 as u64))
+    }
+    // Storage: Asset NextExpiringTickersBucket (r:1 w:1)
+    // Storage: Timestamp Now (r:1 w:0)
+    // Storage: Asset TickerExpiry (r:1 w:0)
+    // Storage: Asset ExpiringTickers (r:2 w:2)
+    // Storage: Asset Tickers (r:1 w:1)
+    // Storage: Asset TickerAutoRenewal (r:1 w:0)
+    // Storage: Identity DidRecords (r:1 w:0)
+    // Storage: Identity CurrentPayer (r:1 w:2)
+    // Storage: ProtocolFee Coefficient (r:1 w:0)
+    // Storage: ProtocolFee BaseFees (r:1 w:0)
+    // Storage: System Account (r:1 w:1)
+    // Storage: Asset TickerConfig (r:1 w:0)
+    /// The range of component `t` is `[0, 16]`.
+    fn process_expiring_tickers(t: u32) -> Weight {
+        Weight::from_ref_time(5_876_000 as u64)
+            .saturating_add(Weight::from_ref_time(61_204_000 as u64).saturating_mul(t as u64))
+            .saturating_add(DbWeight::get().reads(3 as u64))
+            .saturating_add(DbWeight::get().reads((10 as u64).saturating_mul(t as u64)))
+            .saturating_add(DbWeight::get().writes(1 as u64))
+            .saturating_add(DbWeight::get().writes((6 as u64).saturating_mul(t as u64)))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: Permissions CurrentPalletName (r:1 w:0)
+    // Storage: Permissions CurrentDispatchableName (r:1 w:0)
+    // Storage: Identity Authorizations (r:1 w:1)
+    // Storage: Asset Tokens (r:1 w:0)
+    // Storage: Asset Tickers (r:1 w:0)
+    // Storage: Timestamp Now (r:1 w:0)
+    // Storage: Asset TickerRenewalAgents (r:0 w:1)
+    // Storage: Identity AuthorizationsGiven (r:0 w:1)
+    fn accept_ticker_renewal_agent() -> Weight {
+        Weight::from_ref_time(41_086_000 as u64)
+            .saturating_add(DbWeight::get().reads(7 as u64))
+            .saturating_add(DbWeight::get().writes(3 as u64))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: Permissions CurrentPalletName (r:1 w:0)
+    // Storage: Permissions CurrentDispatchableName (r:1 w:0)
+    // Storage: Asset TickerRenewalAgents (r:1 w:1)
+    // Storage: Asset Tickers (r:1 w:0)
+    fn remove_ticker_renewal_agent() -> Weight {
+        Weight::from_ref_time(29_754_000 as u64)
+            .saturating_add(DbWeight::get().reads(5 as u64))
+            .saturating_add(DbWeight::get().writes(1 as u64))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: Permissions CurrentPalletName (r:1 w:0)
+    // Storage: Permissions CurrentDispatchableName (r:1 w:0)
+    // Storage: Asset Tickers (r:1 w:1)
+    // Storage: Timestamp Now (r:1 w:0)
+    // Storage: Asset TickerRenewalAgents (r:1 w:0)
+    // Storage: ProtocolFee Coefficient (r:1 w:0)
+    // Storage: ProtocolFee BaseFees (r:1 w:0)
+    // Storage: Identity CurrentPayer (r:1 w:0)
+    // Storage: System Account (r:1 w:1)
+    // Storage: Asset TickerConfig (r:1 w:0)
+    // Storage: Asset NextExpiringTickersBucket (r:1 w:0)
+    // Storage: Asset ExpiringTickers (r:0 w:1)
+    fn renew_ticker_registration() -> Weight {
+        Weight::from_ref_time(64_978_000 as u64)
+            .saturating_add(DbWeight::get().reads(12 as u64))
+            .saturating_add(DbWeight::get().writes(3 as u64))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: ExternalAgents GroupOfAgent (r:1 w:0)
+    // Storage: Permissions CurrentPalletName (r:1 w:0)
+    // Storage: Permissions CurrentDispatchableName (r:1 w:0)
+    // Storage: Portfolio PortfolioCustodian (r:1 w:0)
+    // Storage: Asset Tokens (r:1 w:0)
+    // Storage: System Number (r:1 w:0)
+    // Storage: Asset LargeIssuance (r:1 w:0)
+    // Storage: Asset ScheduledIssuanceCounter (r:1 w:1)
+    // Storage: ProtocolFee Coefficient (r:1 w:0)
+    // Storage: ProtocolFee BaseFees (r:1 w:0)
+    // Storage: Identity CurrentPayer (r:1 w:0)
+    // Storage: System Account (r:1 w:1)
+    // Storage: Asset ScheduledIssuancesAt (r:16 w:1)
+    // Storage: Asset ScheduledIssuances (r:0 w:1)
+    fn schedule_issuance() -> Weight {
+        Weight::from_ref_time(109_537_000 as u64)
+            .saturating_add(DbWeight::get().reads(29 as u64))
+            .saturating_add(DbWeight::get().writes(4 as u64))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: ExternalAgents GroupOfAgent (r:1 w:0)
+    // Storage: Permissions CurrentPalletName (r:1 w:0)
+    // Storage: Permissions CurrentDispatchableName (r:1 w:0)
+    // Storage: Asset ScheduledIssuances (r:1 w:1)
+    // Storage: Asset ScheduledIssuancesAt (r:1 w:1)
+    fn cancel_scheduled_issuance() -> Weight {
+        Weight::from_ref_time(40_562_000 as u64)
+            .saturating_add(DbWeight::get().reads(6 as u64))
+            .saturating_add(DbWeight::get().writes(2 as u64))
+    }
+    // Storage: Asset ScheduledIssuancesAt (r:1 w:1)
+    // Storage: Asset ScheduledIssuances (r:1 w:1)
+    // Storage: Permissions CurrentPalletName (r:2 w:2)
+    // Storage: Permissions CurrentDispatchableName (r:2 w:2)
+    // Storage: ExternalAgents GroupOfAgent (r:1 w:0)
+    // Storage: Portfolio Portfolios (r:1 w:0)
+    // Storage: Portfolio PortfolioCustodian (r:1 w:0)
+    // Storage: Asset LargeIssuance (r:1 w:0)
+    // Storage: Asset Tokens (r:1 w:1)
+    // Storage: Asset LifecycleStates (r:1 w:0)
+    // Storage: Asset AuthorizedSupply (r:1 w:0)
+    // Storage: Asset BalanceOf (r:1 w:1)
+    // Storage: Portfolio PortfolioAssetBalances (r:2 w:2)
+    // Storage: Checkpoint Schedules (r:1 w:0)
+    // Storage: Checkpoint CheckpointIdSequence (r:1 w:0)
+    // Storage: Asset ScopeIdOf (r:1 w:0)
+    // Storage: Statistics ActiveAssetStats (r:1 w:0)
+    // Storage: Portfolio PortfolioAssetCount (r:2 w:2)
+    /// The range of component `i` is `[0, 16]`.
+    fn execute_scheduled_issuances(i: u32) -> Weight {
+        Weight::from_ref_time(4_913_000 as u64)
+            .saturating_add(Weight::from_ref_time(91_302_000 as u64).saturating_mul(i as u64))
+            .saturating_add(DbWeight::get().reads(1 as u64))
+            .saturating_add(DbWeight::get().reads((21 as u64).saturating_mul(i as u64)))
+            .saturating_add(DbWeight::get().writes(1 as u64))
+            .saturating_add(DbWeight::get().writes((12 as u64).saturating_mul(i as u64)))
+    }
+    // Storage: Asset Tokens (r:1 w:2)
+    // Storage: Asset Tickers (r:2 w:2)
+    // Storage: Asset TickerMigrations (r:1 w:1)
+    // Storage: Asset BalanceOf (r:1001 w:2000)
+    // Storage: Portfolio Portfolios (r:1000 w:0)
+    // Storage: Portfolio PortfolioLockedAssets (r:1000 w:0)
+    // Storage: Portfolio LabelledTotals (r:1000 w:0)
+    // Storage: Portfolio PortfolioAssetBalances (r:1000 w:2000)
+    // Storage: Portfolio SettlementLockLabel (r:1000 w:2000)
+    // Storage: Asset ScopeIdOf (r:1000 w:2000)
+    // Storage: Asset AcquiredAt (r:1000 w:2000)
+    // Storage: Identity Claims (r:11000 w:0)
+    // Storage: Asset AggregateBalance (r:1 w:0)
+    // Storage: Asset AssetDocuments (r:1 w:0)
+    // Storage: Asset AssetMetadataValues (r:1000 w:1998)
+    // Storage: Checkpoint CheckpointIdSequence (r:1 w:2)
+    // Storage: ExternalAgents GroupOfAgent (r:2 w:2)
+    // Storage: ExternalAgents AgentOf (r:0 w:2)
+    // Storage: ComplianceManager AssetCompliances (r:3 w:2)
+    // Storage: Statistics ActiveAssetStats (r:2 w:2)
+    // Storage: Settlement VenueFiltering (r:2 w:2)
+    // Storage: CorporateAction CorporateActions (r:1 w:0)
+    // Storage: CorporateAction CAIdSequence (r:2 w:2)
+    // Storage: Sto Fundraisers (r:1 w:0)
+    // Storage: Sto FundraiserNames (r:1 w:0)
+    // Storage: Sto FundraiserCount (r:2 w:2)
+    // Storage: Asset TickerMigrationSources (r:1 w:1)
+    /// The range of component `h` is `[1, 1000]`.
+    /// The range of component `e` is `[1, 1000]`.
+    fn migrate_ticker(h: u32, e: u32) -> Weight {
+        Weight::from_ref_time(431_586_000 as u64)
+            .saturating_add(Weight::from_ref_time(52_104_000 as u64).saturating_mul(h as u64))
+            .saturating_add(Weight::from_ref_time(14_337_000 as u64).saturating_mul(e as u64))
+            .saturating_add(DbWeight::get().reads(73 as u64))
+            .saturating_add(DbWeight::get().reads((18 as u64).saturating_mul(h as u64)))
+            .saturating_add(DbWeight::get().reads((1 as u64).saturating_mul(e as u64)))
+            .saturating_add(DbWeight::get().writes(63 as u64))
+            .saturating_add(DbWeight::get().writes((10 as u64).saturating_mul(h as u64)))
+            .saturating_add(DbWeight::get().writes((2 as u64).saturating_mul(e as u64)))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: ExternalAgents GroupOfAgent (r:1 w:0)
+    // Storage: Permissions CurrentPalletName (r:1 w:0)
+    // Storage: Permissions CurrentDispatchableName (r:1 w:0)
+    // Storage: Asset Tokens (r:1 w:0)
+    // Storage: Asset LifecycleStates (r:1 w:1)
+    // Storage: Asset Frozen (r:0 w:1)
+    fn set_lifecycle_state() -> Weight {
+        Weight::from_ref_time(48_730_000 as u64)
+            .saturating_add(DbWeight::get().reads(6 as u64))
+            .saturating_add(DbWeight::get().writes(2 as u64))
+    }
+    // Storage: Asset LargeIssuance (r:0 w:1)
+    fn set_large_issuance_threshold() -> Weight {
+        Weight::from_ref_time(14_873_000 as u64).saturating_add(DbWeight::get().writes(1 as u64))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: ExternalAgents GroupOfAgent (r:1 w:0)
+    // Storage: Permissions CurrentPalletName (r:1 w:0)
+    // Storage: Permissions CurrentDispatchableName (r:1 w:0)
+    // Storage: Portfolio PortfolioCustodian (r:1 w:0)
+    // Storage: Asset Tokens (r:1 w:0)
+    // Storage: Asset LargeIssuance (r:1 w:0)
+    // Storage: Timestamp Now (r:1 w:0)
+    // Storage: Asset PendingIssuances (r:0 w:1)
+    fn propose_issuance() -> Weight {
+        Weight::from_ref_time(41_208_000 as u64)
+            .saturating_add(DbWeight::get().reads(8 as u64))
+            .saturating_add(DbWeight::get().writes(1 as u64))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: ExternalAgents GroupOfAgent (r:1 w:0)
+    // Storage: Permissions CurrentPalletName (r:1 w:0)
+    // Storage: Permissions CurrentDispatchableName (r:1 w:0)
+    // Storage: Asset PendingIssuances (r:1 w:1)
+    // Storage: Timestamp Now (r:1 w:0)
+    // Storage: Asset Tokens (r:1 w:1)
+    // Storage: Asset LifecycleStates (r:1 w:1)
+    // Storage: Asset AuthorizedSupply (r:1 w:0)
+    // Storage: Asset BalanceOf (r:1 w:1)
+    // Storage: Portfolio PortfolioAssetBalances (r:1 w:1)
+    // Storage: ProtocolFee Coefficient (r:1 w:0)
+    // Storage: ProtocolFee BaseFees (r:1 w:0)
+    // Storage: Checkpoint Schedules (r:1 w:0)
+    // Storage: Checkpoint CheckpointIdSequence (r:1 w:0)
+    // Storage: Asset DisableInvestorUniqueness (r:1 w:0)
+    // Storage: Asset ScopeIdOf (r:1 w:0)
+    // Storage: Statistics ActiveAssetStats (r:1 w:0)
+    // Storage: Asset FundingRound (r:1 w:0)
+    // Storage: Asset IssuedInFundingRound (r:1 w:1)
+    fn approve_issuance() -> Weight {
+        Weight::from_ref_time(104_382_000 as u64)
+            .saturating_add(DbWeight::get().reads(20 as u64))
+            .saturating_add(DbWeight::get().writes(6 as u64))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: ExternalAgents GroupOfAgent (r:1 w:0)
+    // Storage: Permissions CurrentPalletName (r:1 w:0)
+    // Storage: Permissions CurrentDispatchableName (r:1 w:0)
+    // Storage: Asset PendingIssuances (r:1 w:1)
+    fn cancel_issuance() -> Weight {
+        Weight::from_ref_time(32_650_000 as u64)
+            .saturating_add(DbWeight::get().reads(5 as u64))
+            .saturating_add(DbWeight::get().writes(1 as u64))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: ExternalAgents GroupOfAgent (r:1 w:0)
+    // Storage: Permissions CurrentPalletName (r:1 w:0)
+    // Storage: Permissions CurrentDispatchableName (r:1 w:0)
+    // Storage: Portfolio PortfolioCustodian (r:1 w:0)
+    // Storage: Asset AssetLifecycleStates (r:100 w:0)
+    // Storage: Asset DisableInvestorUniqueness (r:100 w:0)
+    // Storage: Portfolio PortfolioAssetBalances (r:200 w:200)
+    // Storage: Asset BalanceOf (r:200 w:200)
+    // Storage: ComplianceManager AssetCompliances (r:100 w:0)
+    // Storage: Asset Tokens (r:100 w:0)
+    // Storage: Checkpoint CheckpointIdSequence (r:100 w:0)
+    // Storage: Statistics ActiveAssetStats (r:100 w:0)
+    /// The range of component `t` is `[0, 100]`.
+    fn issuer_batch_transfer(t: u32) -> Weight {
+        Weight::from_ref_time(41_305_000 as u64)
+            .saturating_add(Weight::from_ref_time(98_417_265 as u64).saturating_mul(t as u64))
+            .saturating_add(DbWeight::get().reads(5 as u64))
+            .saturating_add(DbWeight::get().reads((17 as u64).saturating_mul(t as u64)))
+            .saturating_add(DbWeight::get().writes((8 as u64).saturating_mul(t as u64)))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: ExternalAgents GroupOfAgent (r:1 w:0)
+    // Storage: Permissions CurrentPalletName (r:1 w:0)
+    // Storage: Permissions CurrentDispatchableName (r:1 w:0)
+    // Storage: Asset AssetDocuments (r:2 w:1)
+    // Storage: Asset AssetDocumentVersions (r:1 w:1)
+    // Storage: ProtocolFee Coefficient (r:1 w:0)
+    // Storage: ProtocolFee BaseFees (r:1 w:0)
+    // Storage: Identity CurrentPayer (r:1 w:0)
+    // Storage: System Account (r:1 w:1)
+    // Storage: Asset AssetDocumentHistory (r:0 w:1)
+    fn update_document() -> Weight {
+        Weight::from_ref_time(65_417_000 as u64)
+            .saturating_add(DbWeight::get().reads(11 as u64))
+            .saturating_add(DbWeight::get().writes(4 as u64))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: Asset AssetDocuments (r:1 w:0)
+    // Storage: Asset AssetDocumentVersions (r:1 w:0)
+    // Storage: Asset DocumentAcknowledgements (r:1 w:1)
+    // Storage: Timestamp Now (r:1 w:0)
+    fn acknowledge_document() -> Weight {
+        Weight::from_ref_time(33_952_000 as u64)
+            .saturating_add(DbWeight::get().reads(5 as u64))
+            .saturating_add(DbWeight::get().writes(1 as u64))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: ExternalAgents GroupOfAgent (r:1 w:0)
+    // Storage: Permissions CurrentPalletName (r:1 w:0)
+    // Storage: Permissions CurrentDispatchableName (r:1 w:0)
+    // Storage: Portfolio Portfolios (r:1 w:0)
+    // Storage: Asset Tokens (r:1 w:0)
+    // Storage: Asset BalanceOf (r:2 w:2)
+    // Storage: Checkpoint CheckpointIdSequence (r:1 w:0)
+    // Storage: Checkpoint BalanceUpdates (r:2 w:2)
+    // Storage: Portfolio PortfolioAssetBalances (r:2 w:2)
+    // Storage: Portfolio PortfolioLockedAssets (r:1 w:0)
+    // Storage: Portfolio LabelledTotals (r:1 w:0)
+    // Storage: Portfolio Portfolios (r:2 w:0)
+    // Storage: Asset ScopeIdOf (r:2 w:0)
+    // Storage: Asset AggregateBalance (r:2 w:2)
+    // Storage: Asset BalanceOfAtScope (r:2 w:2)
+    // Storage: Statistics AssetTransferCompliances (r:1 w:0)
+    // Storage: Statistics ActiveAssetStats (r:1 w:0)
+    // Storage: Portfolio PortfolioCustodian (r:1 w:0)
+    /// The range of component `t` is `[0, 100]`.
+    fn controller_transfer_batch(t: u32) -> Weight {
+        Weight::from_ref_time(35_208_000 as u64)
+            .saturating_add(Weight::from_ref_time(96_704_112 as u64).saturating_mul(t as u64))
+            .saturating_add(DbWeight::get().reads(4 as u64))
+            .saturating_add(DbWeight::get().reads((23 as u64).saturating_mul(t as u64)))
+            .saturating_add(DbWeight::get().writes((10 as u64).saturating_mul(t as u64)))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: ExternalAgents GroupOfAgent (r:1 w:0)
+    // Storage: Permissions CurrentPalletName (r:1 w:0)
+    // Storage: Permissions CurrentDispatchableName (r:1 w:0)
+    // Storage: Asset AssetDocumentsIdSequence (r:1 w:0)
+    // Storage: Asset AssetDocuments (r:1 w:0)
+    // Storage: Asset AssetDocumentVersions (r:1 w:1)
+    // Storage: Asset DocumentAcknowledgements (r:1001 w:1000)
+    // Storage: Asset AssetDocumentHistory (r:0 w:1)
+    /// The range of component `l` is `[0, 1000]`.
+    fn remove_document_acknowledgements(l: u32) -> Weight {
+        Weight::from_ref_time(36_000_000 as u64)
+            .saturating_add(Weight::from_ref_time(6_000_000 as u64).saturating_mul(l as u64))
+            .saturating_add(DbWeight::get().reads(8 as u64))
+            .saturating_add(DbWeight::get().reads((1 as u64).saturating_mul(l as u64)))
+            .saturating_add(DbWeight::get().writes((2 as u64).saturating_mul(l as u64)))
+    }
 }
@@ -111,4 +111,19 @@ impl pallet_balances::WeightInfo for SubstrateWeight {
             .saturating_add(DbWeight::get().reads(3))
             .saturating_add(DbWeight::get().writes(1))
     }
+    // Storage: Identity KeyRecords (r:2 w:0)
+    // Storage: Timestamp Now (r:1 w:0)
+    // Storage: Instance2Group ActiveMembers (r:1 w:0)
+    // Storage: Instance2Group InactiveMembers (r:1 w:0)
+    // Storage: Identity Claims (r:2 w:0)
+    // Storage: System Account (r:2 w:2)
+    /// The range of component `t` is `[1, 100]`.
+    fn batch_transfer_with_memos(t: u32) -> Weight {
+        Weight::from_ref_time(14_320_000)
+            .saturating_add(Weight::from_ref_time(66_857_000).saturating_mul(t.into()))
+            .saturating_add(DbWeight::get().reads(2))
+            .saturating_add(DbWeight::get().reads((7_u64).saturating_mul(t.into())))
+            .saturating_add(DbWeight::get().writes(1))
+            .saturating_add(DbWeight::get().writes((1_u64).saturating_mul(t.into())))
+    }
 }
@@ -184,4 +184,137 @@ impl pallet_compliance_manager::WeightInfo for SubstrateWeight {
             .saturating_add(DbWeight::get().reads(4))
             .saturating_add(DbWeight::get().writes(1))
     }
+    // Storage: ComplianceManager DefaultRequirementsTemplates (r:1 w:1)
+    /// The range of component `c` is `[0, 2]`.
+    fn set_default_requirements_template(c: u32) -> Weight {
+        Weight::from_ref_time(33_120_615)
+            .saturating_add(Weight::from_ref_time(6_102_284).saturating_mul(c.into()))
+            .saturating_add(DbWeight::get().reads(1))
+            .saturating_add(DbWeight::get().writes(1))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: Permissions CurrentPalletName (r:1 w:0)
+    // Storage: Permissions CurrentDispatchableName (r:1 w:0)
+    // Storage: ComplianceManager IssuerRequirementsTemplates (r:1 w:1)
+    /// The range of component `c` is `[0, 2]`.
+    fn set_issuer_requirements_template(c: u32) -> Weight {
+        Weight::from_ref_time(42_384_902)
+            .saturating_add(Weight::from_ref_time(6_211_473).saturating_mul(c.into()))
+            .saturating_add(DbWeight::get().reads(4))
+            .saturating_add(DbWeight::get().writes(1))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: ExternalAgents GroupOfAgent (r:1 w:0)
+    // Storage: Permissions CurrentPalletName (r:1 w:0)
+    // Storage: Permissions CurrentDispatchableName (r:1 w:0)
+    // Storage: ComplianceManager AssetCompliances (r:1 w:0)
+    // Storage: ComplianceManager DisabledRequirements (r:1 w:1)
+    // Storage: ComplianceManager ScheduledReenables (r:66 w:50)
+    /// The range of component `r` is `[1, 50]`.
+    fn disable_compliance_requirements(r: u32) -> Weight {
+        Weight::from_ref_time(49_862_114)
+            .saturating_add(Weight::from_ref_time(1_093_517).saturating_mul(r.into()))
+            .saturating_add(DbWeight::get().reads(22))
+            .saturating_add(DbWeight::get().reads((1_u64).saturating_mul(r.into())))
+            .saturating_add(DbWeight::get().writes(1))
+            .saturating_add(DbWeight::get().writes((1_u64).saturating_mul(r.into())))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: ExternalAgents GroupOfAgent (r:1 w:0)
+    // Storage: Permissions CurrentPalletName (r:1 w:0)
+    // Storage: Permissions CurrentDispatchableName (r:1 w:0)
+    // Storage: ComplianceManager DisabledRequirements (r:1 w:1)
+    /// The range of component `r` is `[1, 50]`.
+    fn enable_compliance_requirements(r: u32) -> Weight {
+        Weight::from_ref_time(42_017_488)
+            .saturating_add(Weight::from_ref_time(462_901).saturating_mul(r.into()))
+            .saturating_add(DbWeight::get().reads(5))
+            .saturating_add(DbWeight::get().writes(1))
+    }
+    // Storage: ComplianceManager ScheduledReenables (r:1 w:1)
+    // Storage: ComplianceManager DisabledRequirements (r:50 w:50)
+    /// The range of component `r` is `[1, 50]`.
+    fn reenable_compliance_requirements(r: u32) -> Weight {
+        Weight::from_ref_time(12_708_390)
+            .saturating_add(Weight::from_ref_time(6_384_052).saturating_mul(r.into()))
+            .saturating_add(DbWeight::get().reads(1))
+            .saturating_add(DbWeight::get().reads((1_u64).saturating_mul(r.into())))
+            .saturating_add(DbWeight::get().writes(1))
+            .saturating_add(DbWeight::get().writes((1_u64).saturating_mul(r.into())))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: ExternalAgents GroupOfAgent (r:1 w:0)
+    // Storage: Permissions CurrentPalletName (r:1 w:0)
+    // Storage: Permissions CurrentDispatchableName (r:1 w:0)
+    // Storage: System Number (r:1 w:0)
+    // Storage: ComplianceManager ScheduledRequirementChanges (r:2 w:1)
+    // Storage: ComplianceManager AssetCompliances (r:1 w:0)
+    // Storage: ComplianceManager TrustedClaimIssuer (r:1 w:0)
+    // Storage: Scheduler Lookup (r:1 w:1)
+    // Storage: Scheduler Agenda (r:1 w:1)
+    // Storage: ProtocolFee Coefficient (r:1 w:0)
+    // Storage: ProtocolFee BaseFees (r:1 w:0)
+    /// The range of component `c` is `[1, 6]`.
+    fn schedule_compliance_requirement_change(c: u32) -> Weight {
+        Weight::from_ref_time(86_472_913)
+            .saturating_add(Weight::from_ref_time(1_802_417).saturating_mul(c.into()))
+            .saturating_add(DbWeight::get().reads(13))
+            .saturating_add(DbWeight::get().writes(3))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: ExternalAgents GroupOfAgent (r:1 w:0)
+    // Storage: Permissions CurrentPalletName (r:1 w:0)
+    // Storage: Permissions CurrentDispatchableName (r:1 w:0)
+    // Storage: ComplianceManager ScheduledRequirementChanges (r:1 w:1)
+    // Storage: Scheduler Lookup (r:1 w:1)
+    // Storage: Scheduler Agenda (r:1 w:1)
+    fn cancel_compliance_requirement_change() -> Weight {
+        Weight::from_ref_time(58_316_000)
+            .saturating_add(DbWeight::get().reads(7))
+            .saturating_add(DbWeight::get().writes(3))
+    }
+    // Storage: ComplianceManager ScheduledRequirementChanges (r:1 w:1)
+    // Storage: ComplianceManager AssetCompliances (r:1 w:1)
+    // Storage: ComplianceManager TrustedClaimIssuer (r:1 w:0)
+    // Storage: ComplianceManager DisabledRequirements (r:1 w:1)
+    // Storage: Statistics InvestorVolumeTracking (r:0 w:1)
+    fn apply_compliance_requirement_change() -> Weight {
+        Weight::from_ref_time(47_905_000)
+            .saturating_add(DbWeight::get().reads(4))
+            .saturating_add(DbWeight::get().writes(4))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: Permissions CurrentPalletName (r:1 w:0)
+    // Storage: Permissions CurrentDispatchableName (r:1 w:0)
+    // Storage: ComplianceManager ComplianceTemplateCounter (r:1 w:1)
+    // Storage: ComplianceManager ComplianceTemplates (r:0 w:1)
+    /// The range of component `c` is `[0, 2]`.
+    fn register_compliance_template(c: u32) -> Weight {
+        Weight::from_ref_time(43_602_517)
+            .saturating_add(Weight::from_ref_time(6_254_190).saturating_mul(c.into()))
+            .saturating_add(DbWeight::get().reads(4))
+            .saturating_add(DbWeight::get().writes(2))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: Permissions CurrentPalletName (r:1 w:0)
+    // Storage: Permissions CurrentDispatchableName (r:1 w:0)
+    // Storage: ComplianceManager ComplianceTemplates (r:1 w:1)
+    fn remove_compliance_template() -> Weight {
+        Weight::from_ref_time(40_011_000)
+            .saturating_add(DbWeight::get().reads(4))
+            .saturating_add(DbWeight::get().writes(1))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: ExternalAgents GroupOfAgent (r:1 w:0)
+    // Storage: Permissions CurrentPalletName (r:1 w:0)
+    // Storage: Permissions CurrentDispatchableName (r:1 w:0)
+    // Storage: ComplianceManager ComplianceTemplates (r:1 w:0)
+    // Storage: ComplianceManager TrustedClaimIssuer (r:1 w:0)
+    // Storage: ComplianceManager AssetCompliances (r:1 w:1)
+    // Storage: ComplianceManager DisabledRequirements (r:0 w:1)
+    fn apply_compliance_template() -> Weight {
+        Weight::from_ref_time(99_064_000)
+            .saturating_add(DbWeight::get().reads(7))
+            .saturating_add(DbWeight::get().writes(2))
+    }
 }
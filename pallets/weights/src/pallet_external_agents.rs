@@ -176,4 +176,74 @@ impl pallet_external_agents::WeightInfo for SubstrateWeight {
             .saturating_add(DbWeight::get().reads(7))
             .saturating_add(DbWeight::get().writes(4))
     }
+    // Storage: Identity KeyRecords (r:11 w:10)
+    // Storage: ExternalAgents GroupOfAgent (r:1 w:0)
+    // Storage: Permissions CurrentPalletName (r:1 w:0)
+    // Storage: Permissions CurrentDispatchableName (r:1 w:0)
+    // Storage: Timestamp Now (r:1 w:0)
+    // Storage: Identity OffChainAuthorizationNonce (r:1 w:1)
+    // Storage: ProtocolFee Coefficient (r:1 w:0)
+    // Storage: ProtocolFee BaseFees (r:1 w:0)
+    // Storage: Identity DidKeys (r:0 w:10)
+    // Storage: Identity SecondaryKeyExpiries (r:0 w:10)
+    /// The range of component `k` is `[1, 10]`.
+    fn register_session_keys(k: u32) -> Weight {
+        Weight::from_ref_time(74_905_112)
+            .saturating_add(Weight::from_ref_time(52_836_413).saturating_mul(k.into()))
+            .saturating_add(DbWeight::get().reads(7))
+            .saturating_add(DbWeight::get().reads((1_u64).saturating_mul(k.into())))
+            .saturating_add(DbWeight::get().writes(1))
+            .saturating_add(DbWeight::get().writes((3_u64).saturating_mul(k.into())))
+    }
+    // Storage: Identity KeyRecords (r:11 w:10)
+    // Storage: Identity SecondaryKeyExpiries (r:10 w:10)
+    // Storage: MultiSig MultiSigToIdentity (r:10 w:0)
+    // Storage: Identity DidKeys (r:0 w:10)
+    /// The range of component `k` is `[1, 10]`.
+    fn expire_session_keys(k: u32) -> Weight {
+        Weight::from_ref_time(31_694_208)
+            .saturating_add(Weight::from_ref_time(11_672_954).saturating_mul(k.into()))
+            .saturating_add(DbWeight::get().reads(1))
+            .saturating_add(DbWeight::get().reads((3_u64).saturating_mul(k.into())))
+            .saturating_add(DbWeight::get().writes((3_u64).saturating_mul(k.into())))
+    }
+    // Storage: Identity DidRecords (r:1 w:0)
+    // Storage: ExternalAgents NumFullAgents (r:1 w:0)
+    // Storage: ExternalAgents AgentReplacements (r:1 w:1)
+    // Storage: Timestamp Now (r:1 w:0)
+    fn schedule_agent_replacement() -> Weight {
+        Weight::from_ref_time(28_402_000)
+            .saturating_add(DbWeight::get().reads(4))
+            .saturating_add(DbWeight::get().writes(1))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: ExternalAgents GroupOfAgent (r:1 w:0)
+    // Storage: Permissions CurrentPalletName (r:1 w:0)
+    // Storage: Permissions CurrentDispatchableName (r:1 w:0)
+    // Storage: ExternalAgents AgentReplacements (r:1 w:1)
+    fn cancel_agent_replacement() -> Weight {
+        Weight::from_ref_time(34_250_000)
+            .saturating_add(DbWeight::get().reads(5))
+            .saturating_add(DbWeight::get().writes(1))
+    }
+    // Storage: ExternalAgents AgentReplacements (r:1 w:1)
+    // Storage: Timestamp Now (r:1 w:0)
+    // Storage: ExternalAgents NumFullAgents (r:1 w:1)
+    // Storage: ExternalAgents GroupOfAgent (r:101 w:101)
+    // Storage: Identity AuthorizationsGiven (r:200 w:0)
+    // Storage: Identity Authorizations (r:100 w:0)
+    // Storage: Identity DidRecords (r:1 w:0)
+    // Storage: ExternalAgents AgentOf (r:0 w:101)
+    /// The range of component `a` is `[1, 100]`.
+    /// The range of component `g` is `[0, 100]`.
+    fn replace_agent(a: u32, g: u32) -> Weight {
+        Weight::from_ref_time(52_836_000)
+            .saturating_add(Weight::from_ref_time(9_415_000).saturating_mul(a.into()))
+            .saturating_add(Weight::from_ref_time(7_902_000).saturating_mul(g.into()))
+            .saturating_add(DbWeight::get().reads(5))
+            .saturating_add(DbWeight::get().reads((2_u64).saturating_mul(a.into())))
+            .saturating_add(DbWeight::get().reads((2_u64).saturating_mul(g.into())))
+            .saturating_add(DbWeight::get().writes(5))
+            .saturating_add(DbWeight::get().writes((2_u64).saturating_mul(a.into())))
+    }
 }
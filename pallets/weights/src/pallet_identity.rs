@@ -142,14 +142,18 @@ impl pallet_identity::WeightInfo for SubstrateWeight {
     // Storage: Identity Claims (r:2 w:0)
     // Storage: ProtocolFee Coefficient (r:1 w:0)
     // Storage: ProtocolFee BaseFees (r:1 w:0)
+    // Storage: Identity KeyProbationPeriods (r:1 w:0)
+    // Storage: Scheduler Lookup (r:1 w:1)
+    // Storage: Scheduler Agenda (r:1 w:1)
     // Storage: Identity AuthorizationsGiven (r:0 w:1)
     // Storage: Identity DidKeys (r:0 w:1)
     // Storage: Identity CurrentDid (r:0 w:1)
+    // Storage: Identity ProbationaryKeys (r:0 w:1)
     fn join_identity_as_key() -> Weight {
         // Minimum execution time: 71_953 nanoseconds.
         Weight::from_ref_time(72_363_000)
-            .saturating_add(DbWeight::get().reads(10))
-            .saturating_add(DbWeight::get().writes(5))
+            .saturating_add(DbWeight::get().reads(13))
+            .saturating_add(DbWeight::get().writes(8))
     }
     // Storage: Identity CurrentDid (r:1 w:0)
     // Storage: Identity KeyRecords (r:1 w:1)
@@ -175,6 +179,45 @@ impl pallet_identity::WeightInfo for SubstrateWeight {
             .saturating_add(DbWeight::get().writes(1))
     }
     // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: Identity DidRecords (r:100 w:0)
+    // Storage: Identity ClaimIssuanceEraLength (r:1 w:0)
+    // Storage: Identity IssuerClaimIssuanceLimits (r:1 w:0)
+    // Storage: Identity DefaultClaimIssuanceLimit (r:1 w:0)
+    // Storage: ProtocolFee Coefficient (r:1 w:0)
+    // Storage: ProtocolFee BaseFees (r:1 w:0)
+    // Storage: Timestamp Now (r:1 w:0)
+    // Storage: Identity Claims (r:100 w:100)
+    /// The range of component `c` is `[0, 100]`.
+    fn add_claims_batch(c: u32) -> Weight {
+        Weight::from_ref_time(31_402_000)
+            .saturating_add(Weight::from_ref_time(19_873_104).saturating_mul(c.into()))
+            .saturating_add(DbWeight::get().reads(7))
+            .saturating_add(DbWeight::get().reads((2_u64).saturating_mul(c.into())))
+            .saturating_add(DbWeight::get().writes((1_u64).saturating_mul(c.into())))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: Identity Claims (r:1 w:1)
+    // Storage: Timestamp Now (r:1 w:0)
+    // Storage: System Number (r:1 w:0)
+    // Storage: Scheduler Lookup (r:1 w:1)
+    // Storage: Scheduler Agenda (r:1 w:1)
+    fn schedule_claim_revocation() -> Weight {
+        Weight::from_ref_time(48_913_000)
+            .saturating_add(DbWeight::get().reads(6))
+            .saturating_add(DbWeight::get().writes(3))
+    }
+    // Storage: Identity Claims (r:1 w:1)
+    // Storage: Timestamp Now (r:1 w:0)
+    // Storage: Identity ClaimCount (r:1 w:1)
+    // Storage: System Number (r:1 w:0)
+    // Storage: Scheduler Lookup (r:1 w:1)
+    // Storage: Scheduler Agenda (r:1 w:1)
+    fn revoke_scheduled_claim() -> Weight {
+        Weight::from_ref_time(41_250_000)
+            .saturating_add(DbWeight::get().reads(6))
+            .saturating_add(DbWeight::get().writes(4))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
     // Storage: Identity Claims (r:1 w:1)
     // Storage: Asset BalanceOfAtScope (r:1 w:0)
     fn revoke_claim() -> Weight {
@@ -195,11 +238,11 @@ impl pallet_identity::WeightInfo for SubstrateWeight {
             .saturating_add(DbWeight::get().writes(1))
     }
     // Storage: Identity KeyRecords (r:2 w:1)
+    // Storage: Identity TemporaryPermissionGrants (r:0 w:1)
     fn set_secondary_key_permissions() -> Weight {
-        // Minimum execution time: 34_264 nanoseconds.
-        Weight::from_ref_time(34_925_000)
+        Weight::from_ref_time(36_012_000)
             .saturating_add(DbWeight::get().reads(2))
-            .saturating_add(DbWeight::get().writes(1))
+            .saturating_add(DbWeight::get().writes(2))
     }
     /// The range of component `a` is `[0, 1000]`.
     /// The range of component `p` is `[0, 1000]`.
@@ -233,6 +276,64 @@ impl pallet_identity::WeightInfo for SubstrateWeight {
             .saturating_add(DbWeight::get().reads(1))
             .saturating_add(DbWeight::get().writes(1))
     }
+    // Storage: Identity KeyRecords (r:2 w:0)
+    // Storage: Timestamp Now (r:1 w:0)
+    // Storage: Identity SecondaryKeyExpiries (r:0 w:1)
+    fn set_secondary_key_expiry() -> Weight {
+        Weight::from_ref_time(30_412_000)
+            .saturating_add(DbWeight::get().reads(3))
+            .saturating_add(DbWeight::get().writes(1))
+    }
+    // Storage: Identity KeyRecords (r:2 w:1)
+    // Storage: Identity TemporaryPermissionGrants (r:1 w:1)
+    // Storage: System Number (r:1 w:0)
+    // Storage: Scheduler Lookup (r:1 w:1)
+    // Storage: Scheduler Agenda (r:1 w:1)
+    fn grant_temporary_permissions() -> Weight {
+        Weight::from_ref_time(55_381_000)
+            .saturating_add(DbWeight::get().reads(6))
+            .saturating_add(DbWeight::get().writes(4))
+    }
+    // Storage: Identity TemporaryPermissionGrants (r:1 w:1)
+    // Storage: Identity KeyRecords (r:1 w:1)
+    fn revert_temporary_permissions() -> Weight {
+        Weight::from_ref_time(27_906_000)
+            .saturating_add(DbWeight::get().reads(2))
+            .saturating_add(DbWeight::get().writes(2))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: Identity KeyUsageAudit (r:0 w:1)
+    fn set_key_usage_audit() -> Weight {
+        Weight::from_ref_time(25_318_000)
+            .saturating_add(DbWeight::get().reads(1))
+            .saturating_add(DbWeight::get().writes(1))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: Identity KeyProbationPeriods (r:0 w:1)
+    fn set_key_probation_period() -> Weight {
+        Weight::from_ref_time(25_760_000)
+            .saturating_add(DbWeight::get().reads(1))
+            .saturating_add(DbWeight::get().writes(1))
+    }
+    // Storage: Identity KeyRecords (r:2 w:0)
+    // Storage: Identity ProbationaryKeys (r:1 w:1)
+    fn confirm_secondary_key() -> Weight {
+        Weight::from_ref_time(32_107_000)
+            .saturating_add(DbWeight::get().reads(3))
+            .saturating_add(DbWeight::get().writes(1))
+    }
+    // Storage: Identity ProbationaryKeys (r:1 w:1)
+    // Storage: Identity KeyRecords (r:1 w:1)
+    // Storage: Identity AccountKeyRefCount (r:1 w:0)
+    // Storage: MultiSig MultiSigToIdentity (r:1 w:0)
+    // Storage: Identity DidKeys (r:0 w:1)
+    // Storage: Identity SecondaryKeyExpiries (r:0 w:1)
+    // Storage: Identity TemporaryPermissionGrants (r:0 w:1)
+    fn end_key_probation() -> Weight {
+        Weight::from_ref_time(45_163_000)
+            .saturating_add(DbWeight::get().reads(4))
+            .saturating_add(DbWeight::get().writes(5))
+    }
     // Storage: Identity KeyRecords (r:1 w:0)
     // Storage: Identity MultiPurposeNonce (r:1 w:1)
     // Storage: Identity AuthorizationsGiven (r:0 w:1)
@@ -257,7 +358,11 @@ impl pallet_identity::WeightInfo for SubstrateWeight {
     // Storage: Identity OffChainAuthorizationNonce (r:1 w:1)
     // Storage: ProtocolFee Coefficient (r:1 w:0)
     // Storage: ProtocolFee BaseFees (r:1 w:0)
+    // Storage: Identity KeyProbationPeriods (r:2 w:0)
+    // Storage: Scheduler Lookup (r:2 w:2)
+    // Storage: Scheduler Agenda (r:2 w:2)
     // Storage: Identity DidKeys (r:0 w:2)
+    // Storage: Identity ProbationaryKeys (r:0 w:2)
     /// The range of component `i` is `[0, 200]`.
     fn add_secondary_keys_with_authorization(i: u32) -> Weight {
         // Minimum execution time: 37_910 nanoseconds.
@@ -265,9 +370,9 @@ impl pallet_identity::WeightInfo for SubstrateWeight {
             // Standard Error: 62_588
             .saturating_add(Weight::from_ref_time(53_146_463).saturating_mul(i.into()))
             .saturating_add(DbWeight::get().reads(5))
-            .saturating_add(DbWeight::get().reads((1_u64).saturating_mul(i.into())))
+            .saturating_add(DbWeight::get().reads((4_u64).saturating_mul(i.into())))
             .saturating_add(DbWeight::get().writes(1))
-            .saturating_add(DbWeight::get().writes((2_u64).saturating_mul(i.into())))
+            .saturating_add(DbWeight::get().writes((5_u64).saturating_mul(i.into())))
     }
     // Storage: Identity KeyRecords (r:1 w:0)
     // Storage: Identity DidRecords (r:1 w:0)
@@ -316,4 +421,327 @@ impl pallet_identity::WeightInfo for SubstrateWeight {
             .saturating_add(DbWeight::get().reads(3))
             .saturating_add(DbWeight::get().writes(3))
     }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: Instance2Group ActiveMembers (r:1 w:0)
+    // Storage: Identity PendingKeyRecoveries (r:1 w:1)
+    // Storage: Identity KeyRecoveryDelegates (r:1 w:1)
+    fn set_key_recovery_delegate() -> Weight {
+        Weight::from_ref_time(31_187_000)
+            .saturating_add(DbWeight::get().reads(4))
+            .saturating_add(DbWeight::get().writes(2))
+    }
+    // Storage: Identity KeyRecords (r:2 w:0)
+    // Storage: Permissions CurrentPalletName (r:1 w:0)
+    // Storage: Permissions CurrentDispatchableName (r:1 w:0)
+    // Storage: Instance2Group ActiveMembers (r:1 w:0)
+    // Storage: Identity KeyRecoveryDelegates (r:1 w:0)
+    // Storage: Identity PendingKeyRecoveries (r:1 w:1)
+    // Storage: Timestamp Now (r:1 w:0)
+    fn initiate_key_recovery() -> Weight {
+        Weight::from_ref_time(42_858_000)
+            .saturating_add(DbWeight::get().reads(8))
+            .saturating_add(DbWeight::get().writes(1))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: Permissions CurrentPalletName (r:1 w:0)
+    // Storage: Permissions CurrentDispatchableName (r:1 w:0)
+    // Storage: Identity PendingKeyRecoveries (r:1 w:1)
+    fn cancel_key_recovery() -> Weight {
+        Weight::from_ref_time(30_951_000)
+            .saturating_add(DbWeight::get().reads(4))
+            .saturating_add(DbWeight::get().writes(1))
+    }
+    // Storage: Identity KeyRecords (r:3 w:2)
+    // Storage: Permissions CurrentPalletName (r:1 w:0)
+    // Storage: Permissions CurrentDispatchableName (r:1 w:0)
+    // Storage: Identity PendingKeyRecoveries (r:1 w:1)
+    // Storage: Instance2Group ActiveMembers (r:1 w:0)
+    // Storage: Timestamp Now (r:1 w:0)
+    // Storage: Identity DidRecords (r:1 w:1)
+    // Storage: Identity AccountKeyRefCount (r:1 w:0)
+    // Storage: MultiSig MultiSigToIdentity (r:1 w:0)
+    // Storage: Identity DidKeys (r:0 w:2)
+    fn execute_key_recovery() -> Weight {
+        Weight::from_ref_time(70_124_000)
+            .saturating_add(DbWeight::get().reads(11))
+            .saturating_add(DbWeight::get().writes(6))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: Identity DidRecords (r:10 w:0)
+    // Storage: Identity PendingSocialRecoveries (r:1 w:1)
+    // Storage: Identity RecoveryGuardianSets (r:1 w:1)
+    /// The range of component `n` is `[0, 10]`.
+    fn set_recovery_guardians(n: u32) -> Weight {
+        Weight::from_ref_time(29_871_000)
+            .saturating_add(Weight::from_ref_time(3_215_000).saturating_mul(n.into()))
+            .saturating_add(DbWeight::get().reads(3))
+            .saturating_add(DbWeight::get().reads((1_u64).saturating_mul(n.into())))
+            .saturating_add(DbWeight::get().writes(2))
+    }
+    // Storage: Identity KeyRecords (r:2 w:0)
+    // Storage: Permissions CurrentPalletName (r:1 w:0)
+    // Storage: Permissions CurrentDispatchableName (r:1 w:0)
+    // Storage: Identity RecoveryGuardianSets (r:1 w:0)
+    // Storage: Identity PendingSocialRecoveries (r:1 w:1)
+    // Storage: Timestamp Now (r:1 w:0)
+    fn approve_social_recovery() -> Weight {
+        Weight::from_ref_time(44_692_000)
+            .saturating_add(DbWeight::get().reads(7))
+            .saturating_add(DbWeight::get().writes(1))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: Permissions CurrentPalletName (r:1 w:0)
+    // Storage: Permissions CurrentDispatchableName (r:1 w:0)
+    // Storage: Identity PendingSocialRecoveries (r:1 w:1)
+    // Storage: Identity RecoveryGuardianSets (r:1 w:0)
+    fn cancel_social_recovery() -> Weight {
+        Weight::from_ref_time(33_108_000)
+            .saturating_add(DbWeight::get().reads(5))
+            .saturating_add(DbWeight::get().writes(1))
+    }
+    // Storage: Identity PendingSocialRecoveries (r:1 w:1)
+    // Storage: Timestamp Now (r:1 w:0)
+    // Storage: Identity KeyRecords (r:2 w:2)
+    // Storage: Identity DidRecords (r:1 w:1)
+    // Storage: Identity AccountKeyRefCount (r:1 w:0)
+    // Storage: MultiSig MultiSigToIdentity (r:1 w:0)
+    // Storage: Identity DidKeys (r:0 w:2)
+    fn execute_social_recovery() -> Weight {
+        Weight::from_ref_time(62_754_000)
+            .saturating_add(DbWeight::get().reads(7))
+            .saturating_add(DbWeight::get().writes(6))
+    }
+    // Storage: Identity DefaultClaimIssuanceLimit (r:0 w:1)
+    // Storage: Identity ClaimIssuanceEraLength (r:0 w:1)
+    fn set_claim_issuance_limit() -> Weight {
+        Weight::from_ref_time(20_512_000).saturating_add(DbWeight::get().writes(2))
+    }
+    // Storage: Identity IssuerClaimIssuanceLimits (r:0 w:1)
+    fn set_issuer_claim_issuance_limit() -> Weight {
+        Weight::from_ref_time(19_736_000).saturating_add(DbWeight::get().writes(1))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: Identity CallFilters (r:1 w:1)
+    fn add_call_filter() -> Weight {
+        Weight::from_ref_time(25_302_000)
+            .saturating_add(DbWeight::get().reads(2))
+            .saturating_add(DbWeight::get().writes(1))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: Identity CallFilters (r:1 w:0)
+    // Storage: Identity CallFilterRemovals (r:1 w:1)
+    // Storage: Timestamp Now (r:1 w:0)
+    fn request_call_filter_removal() -> Weight {
+        Weight::from_ref_time(29_718_000)
+            .saturating_add(DbWeight::get().reads(4))
+            .saturating_add(DbWeight::get().writes(1))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: Identity CallFilterRemovals (r:1 w:1)
+    // Storage: Timestamp Now (r:1 w:0)
+    // Storage: Identity CallFilters (r:0 w:1)
+    fn remove_call_filter() -> Weight {
+        Weight::from_ref_time(30_209_000)
+            .saturating_add(DbWeight::get().reads(3))
+            .saturating_add(DbWeight::get().writes(2))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: Identity AgreementCounter (r:1 w:1)
+    // Storage: Identity Agreements (r:0 w:1)
+    fn publish_agreement() -> Weight {
+        Weight::from_ref_time(26_540_000)
+            .saturating_add(DbWeight::get().reads(2))
+            .saturating_add(DbWeight::get().writes(2))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: Identity Agreements (r:1 w:1)
+    fn update_agreement() -> Weight {
+        Weight::from_ref_time(25_617_000)
+            .saturating_add(DbWeight::get().reads(2))
+            .saturating_add(DbWeight::get().writes(1))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: Identity Agreements (r:1 w:0)
+    // Storage: Timestamp Now (r:1 w:0)
+    // Storage: Identity AgreementConsents (r:0 w:1)
+    fn give_consent() -> Weight {
+        Weight::from_ref_time(29_154_000)
+            .saturating_add(DbWeight::get().reads(3))
+            .saturating_add(DbWeight::get().writes(1))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: Identity AgreementConsents (r:1 w:1)
+    fn revoke_consent() -> Weight {
+        Weight::from_ref_time(24_789_000)
+            .saturating_add(DbWeight::get().reads(2))
+            .saturating_add(DbWeight::get().writes(1))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: Identity DidRecords (r:1 w:0)
+    // Storage: Identity ParentIdentityApprovals (r:0 w:1)
+    fn approve_parent_identity() -> Weight {
+        Weight::from_ref_time(26_513_000)
+            .saturating_add(DbWeight::get().reads(2))
+            .saturating_add(DbWeight::get().writes(1))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: Identity ParentIdentityApprovals (r:1 w:1)
+    // Storage: Identity ParentDid (r:2 w:1)
+    // Storage: Identity ChildIdentities (r:1 w:1)
+    fn link_child_identity() -> Weight {
+        Weight::from_ref_time(35_026_000)
+            .saturating_add(DbWeight::get().reads(5))
+            .saturating_add(DbWeight::get().writes(3))
+    }
+    // Storage: Identity KeyRecords (r:2 w:2)
+    // Storage: Identity ParentDid (r:1 w:1)
+    // Storage: Identity AccountKeyRefCount (r:1 w:0)
+    // Storage: MultiSig MultiSigToIdentity (r:1 w:0)
+    // Storage: Identity MultiPurposeNonce (r:1 w:1)
+    // Storage: System ParentHash (r:1 w:0)
+    // Storage: Identity DidRecords (r:1 w:1)
+    // Storage: System Account (r:1 w:1)
+    // Storage: Identity DidKeys (r:0 w:2)
+    // Storage: Identity SecondaryKeyExpiries (r:0 w:1)
+    // Storage: Identity TemporaryPermissionGrants (r:0 w:1)
+    // Storage: Identity ProbationaryKeys (r:0 w:1)
+    // Storage: Identity ChildIdentities (r:0 w:1)
+    fn create_child_identity() -> Weight {
+        Weight::from_ref_time(73_105_000)
+            .saturating_add(DbWeight::get().reads(9))
+            .saturating_add(DbWeight::get().writes(12))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: Identity ParentDid (r:1 w:1)
+    // Storage: Identity ChildIdentities (r:0 w:1)
+    fn unlink_child_identity() -> Weight {
+        Weight::from_ref_time(28_671_000)
+            .saturating_add(DbWeight::get().reads(2))
+            .saturating_add(DbWeight::get().writes(2))
+    }
+    // Storage: Identity KeyRecords (r:2 w:1)
+    // Storage: Identity ParentDid (r:1 w:0)
+    // Storage: Identity TemporaryPermissionGrants (r:0 w:1)
+    fn set_child_secondary_key_permissions() -> Weight {
+        Weight::from_ref_time(39_117_000)
+            .saturating_add(DbWeight::get().reads(3))
+            .saturating_add(DbWeight::get().writes(2))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: Identity ParentDid (r:1 w:0)
+    // Storage: Identity AccountKeyRefCount (r:2 w:0)
+    // Storage: MultiSig MultiSigToIdentity (r:2 w:0)
+    // Storage: Identity DidKeys (r:0 w:2)
+    /// The range of component `i` is `[0, 200]`.
+    fn remove_child_secondary_keys(i: u32) -> Weight {
+        Weight::from_ref_time(24_602_114)
+            .saturating_add(Weight::from_ref_time(13_801_276).saturating_mul(i.into()))
+            .saturating_add(DbWeight::get().reads(2))
+            .saturating_add(DbWeight::get().reads((4_u64).saturating_mul(i.into())))
+            .saturating_add(DbWeight::get().writes((2_u64).saturating_mul(i.into())))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: Identity ParentDid (r:1 w:0)
+    // Storage: Identity IsDidFrozen (r:0 w:1)
+    fn set_child_secondary_keys_frozen() -> Weight {
+        Weight::from_ref_time(30_207_000)
+            .saturating_add(DbWeight::get().reads(2))
+            .saturating_add(DbWeight::get().writes(1))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: Identity CustomClaimsInverse (r:1 w:1)
+    // Storage: Identity CustomClaimIdSequence (r:1 w:1)
+    // Storage: Identity CustomClaims (r:0 w:1)
+    // Storage: Identity CustomClaimSchemas (r:0 w:1)
+    /// The range of component `n` is `[1, 2048]`.
+    /// The range of component `f` is `[0, 32]`.
+    fn register_custom_claim_type_with_schema(n: u32, f: u32) -> Weight {
+        Weight::from_ref_time(40_614_902)
+            .saturating_add(Weight::from_ref_time(5_602).saturating_mul(n.into()))
+            .saturating_add(Weight::from_ref_time(1_263_718).saturating_mul(f.into()))
+            .saturating_add(DbWeight::get().reads(3))
+            .saturating_add(DbWeight::get().writes(4))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: Identity DidRecords (r:1 w:0)
+    // Storage: Identity ClaimIssuanceDelegations (r:1 w:1)
+    // Storage: Identity ClaimIssuanceDelegateCount (r:1 w:1)
+    /// The range of component `c` is `[0, 32]`.
+    fn delegate_claim_issuance(c: u32) -> Weight {
+        Weight::from_ref_time(31_262_517)
+            .saturating_add(Weight::from_ref_time(214_376).saturating_mul(c.into()))
+            .saturating_add(DbWeight::get().reads(4))
+            .saturating_add(DbWeight::get().writes(2))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: Timestamp Now (r:1 w:0)
+    // Storage: Identity Authorizations (r:1 w:50)
+    // Storage: Identity AuthorizationCount (r:1 w:1)
+    // Storage: Identity AuthorizationsGiven (r:0 w:50)
+    /// The range of component `a` is `[0, 50]`.
+    fn remove_expired_authorizations(a: u32) -> Weight {
+        Weight::from_ref_time(31_012_646)
+            .saturating_add(Weight::from_ref_time(9_782_310).saturating_mul(a.into()))
+            .saturating_add(DbWeight::get().reads(4))
+            .saturating_add(DbWeight::get().reads((1_u64).saturating_mul(a.into())))
+            .saturating_add(DbWeight::get().writes(1))
+            .saturating_add(DbWeight::get().writes((2_u64).saturating_mul(a.into())))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: Identity Authorizations (r:100 w:100)
+    // Storage: Timestamp Now (r:1 w:0)
+    // Storage: Portfolio PortfolioCustodian (r:50 w:50)
+    // Storage: Identity AuthorizationCount (r:1 w:1)
+    // Storage: Portfolio PortfoliosInCustody (r:0 w:100)
+    // Storage: Identity AuthorizationsGiven (r:0 w:50)
+    /// The range of component `a` is `[0, 50]`.
+    fn consume_authorizations_batch(a: u32) -> Weight {
+        Weight::from_ref_time(26_877_402)
+            .saturating_add(Weight::from_ref_time(38_460_229).saturating_mul(a.into()))
+            .saturating_add(DbWeight::get().reads(3))
+            .saturating_add(DbWeight::get().reads((3_u64).saturating_mul(a.into())))
+            .saturating_add(DbWeight::get().writes(1))
+            .saturating_add(DbWeight::get().writes((5_u64).saturating_mul(a.into())))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: Identity ClaimImports (r:1 w:1)
+    fn start_claim_import() -> Weight {
+        Weight::from_ref_time(34_218_000)
+            .saturating_add(DbWeight::get().reads(2))
+            .saturating_add(DbWeight::get().writes(1))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: Identity ClaimImports (r:1 w:1)
+    // Storage: Identity DidRecords (r:100 w:0)
+    // Storage: Identity ClaimIssuanceEraLength (r:1 w:0)
+    // Storage: Identity IssuerClaimIssuanceLimits (r:1 w:0)
+    // Storage: Identity DefaultClaimIssuanceLimit (r:1 w:0)
+    // Storage: ProtocolFee Coefficient (r:1 w:0)
+    // Storage: ProtocolFee BaseFees (r:1 w:0)
+    // Storage: Timestamp Now (r:1 w:0)
+    // Storage: Identity Claims (r:100 w:100)
+    // Storage: Identity ClaimCount (r:1 w:1)
+    /// The range of component `c` is `[0, 100]`.
+    fn import_claim_batch(c: u32) -> Weight {
+        Weight::from_ref_time(38_615_000)
+            .saturating_add(Weight::from_ref_time(20_412_338).saturating_mul(c.into()))
+            .saturating_add(DbWeight::get().reads(9))
+            .saturating_add(DbWeight::get().reads((2_u64).saturating_mul(c.into())))
+            .saturating_add(DbWeight::get().writes(2))
+            .saturating_add(DbWeight::get().writes((1_u64).saturating_mul(c.into())))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: Identity ClaimImports (r:1 w:1)
+    fn finalize_claim_import() -> Weight {
+        Weight::from_ref_time(33_907_000)
+            .saturating_add(DbWeight::get().reads(2))
+            .saturating_add(DbWeight::get().writes(1))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: Identity ClaimImports (r:1 w:1)
+    fn abort_claim_import() -> Weight {
+        Weight::from_ref_time(33_451_000)
+            .saturating_add(DbWeight::get().reads(2))
+            .saturating_add(DbWeight::get().writes(1))
+    }
 }
@@ -119,4 +119,145 @@ impl pallet_nft::WeightInfo for SubstrateWeight {
             .saturating_add(DbWeight::get().writes(2 as u64))
             .saturating_add(DbWeight::get().writes((1 as u64).saturating_mul(n as u64)))
     }
+    // Storage: NFT CollectionTicker (r:1 w:0)
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: ExternalAgents GroupOfAgent (r:1 w:0)
+    // Storage: Permissions CurrentPalletName (r:1 w:0)
+    // Storage: Permissions CurrentDispatchableName (r:1 w:0)
+    // Storage: NFT CredentialCollections (r:0 w:1)
+    fn set_credential_collection() -> Weight {
+        Weight::from_ref_time(38_541_000 as u64)
+            .saturating_add(DbWeight::get().reads(5 as u64))
+            .saturating_add(DbWeight::get().writes(1 as u64))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: NFT CredentialCollections (r:1 w:0)
+    // Storage: Portfolio PortfolioNFT (r:1 w:0)
+    // Storage: Identity NFTCredentials (r:0 w:1)
+    fn link_nft_credential() -> Weight {
+        Weight::from_ref_time(31_207_000 as u64)
+            .saturating_add(DbWeight::get().reads(3 as u64))
+            .saturating_add(DbWeight::get().writes(1 as u64))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: Identity NFTCredentials (r:1 w:1)
+    fn unlink_nft_credential() -> Weight {
+        Weight::from_ref_time(26_893_000 as u64)
+            .saturating_add(DbWeight::get().reads(2 as u64))
+            .saturating_add(DbWeight::get().writes(1 as u64))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: NFT NextMetadataKeySetId (r:1 w:1)
+    // Storage: NFT MetadataKeySets (r:0 w:1)
+    fn register_metadata_key_set(n: u32) -> Weight {
+        Weight::from_ref_time(34_812_000 as u64)
+            .saturating_add(Weight::from_ref_time(1_146_000 as u64).saturating_mul(n as u64))
+            .saturating_add(DbWeight::get().reads(2 as u64))
+            .saturating_add(DbWeight::get().writes(2 as u64))
+    }
+    // Storage: Asset Tokens (r:1 w:1)
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: NFT CollectionTicker (r:1 w:1)
+    // Storage: NFT MetadataKeySets (r:1 w:0)
+    // Storage: Asset TickerConfig (r:1 w:0)
+    // Storage: Asset Tickers (r:1 w:1)
+    // Storage: Identity DidRecords (r:1 w:1)
+    // Storage: Portfolio PortfolioCustodian (r:1 w:0)
+    // Storage: ProtocolFee Coefficient (r:1 w:0)
+    // Storage: ProtocolFee BaseFees (r:2 w:0)
+    // Storage: Identity CurrentPayer (r:1 w:0)
+    // Storage: ExternalAgents NumFullAgents (r:1 w:1)
+    // Storage: Asset AssetMetadataLocalNameToKey (r:1 w:1)
+    // Storage: Asset AssetMetadataNextLocalKey (r:1 w:1)
+    // Storage: NFT NextCollectionId (r:1 w:1)
+    // Storage: Asset AssetMetadataLocalKeyToName (r:0 w:1)
+    // Storage: Asset AssetMetadataLocalSpecs (r:0 w:1)
+    // Storage: NFT Collection (r:0 w:1)
+    // Storage: NFT CollectionKeys (r:0 w:1)
+    // Storage: NFT CollectionKeySet (r:0 w:1)
+    // Storage: Asset FundingRound (r:0 w:1)
+    // Storage: Asset AssetOwnershipRelations (r:0 w:1)
+    // Storage: Asset AssetNames (r:0 w:1)
+    // Storage: Asset ClassicTickers (r:0 w:1)
+    // Storage: Asset DisableInvestorUniqueness (r:0 w:1)
+    // Storage: Asset Identifiers (r:0 w:1)
+    // Storage: ExternalAgents AgentOf (r:0 w:1)
+    // Storage: ExternalAgents GroupOfAgent (r:0 w:1)
+    fn create_nft_collection_from_key_set(n: u32) -> Weight {
+        Weight::from_ref_time(127_305_000 as u64)
+            .saturating_add(Weight::from_ref_time(12_318_000 as u64).saturating_mul(n as u64))
+            .saturating_add(DbWeight::get().reads(14 as u64))
+            .saturating_add(DbWeight::get().reads((2 as u64).saturating_mul(n as u64)))
+            .saturating_add(DbWeight::get().writes(17 as u64))
+            .saturating_add(DbWeight::get().writes((4 as u64).saturating_mul(n as u64)))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: Portfolio PortfolioCustodian (r:2 w:0)
+    // Storage: Portfolio Portfolios (r:1 w:0)
+    // Storage: System Number (r:1 w:0)
+    // Storage: NFT LeasesEndingAt (r:1 w:0)
+    // Storage: Portfolio PortfolioNFT (r:1 w:0)
+    // Storage: Portfolio PortfolioLockedNFT (r:1 w:0)
+    // Storage: NFT NextLeaseId (r:1 w:1)
+    // Storage: Identity MultiPurposeNonce (r:1 w:1)
+    // Storage: Identity Authorizations (r:0 w:1)
+    // Storage: Identity AuthorizationsGiven (r:0 w:1)
+    // Storage: NFT LeaseOffers (r:0 w:1)
+    fn lease_nft() -> Weight {
+        Weight::from_ref_time(61_420_000 as u64)
+            .saturating_add(DbWeight::get().reads(10 as u64))
+            .saturating_add(DbWeight::get().writes(5 as u64))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: NFT Leases (r:1 w:1)
+    // Storage: Portfolio PortfolioCustodian (r:2 w:0)
+    // Storage: NFT LeaseEndApprovals (r:1 w:1)
+    // Storage: NFT LeasesEndingAt (r:1 w:1)
+    // Storage: Portfolio Portfolios (r:1 w:0)
+    // Storage: Portfolio PortfolioLockedNFT (r:0 w:1)
+    // Storage: NFT NumberOfNFTs (r:2 w:2)
+    // Storage: Portfolio PortfolioNFT (r:0 w:2)
+    // Storage: Identity NFTCredentials (r:1 w:0)
+    fn approve_nft_lease_end() -> Weight {
+        Weight::from_ref_time(73_206_000 as u64)
+            .saturating_add(DbWeight::get().reads(10 as u64))
+            .saturating_add(DbWeight::get().writes(8 as u64))
+    }
+    // Storage: NFT LeasesEndingAt (r:1 w:1)
+    // Storage: NFT Leases (r:16 w:16)
+    // Storage: Portfolio Portfolios (r:16 w:0)
+    // Storage: NFT NumberOfNFTs (r:32 w:32)
+    // Storage: Identity NFTCredentials (r:16 w:0)
+    // Storage: Portfolio PortfolioLockedNFT (r:0 w:16)
+    // Storage: Portfolio PortfolioNFT (r:0 w:32)
+    // Storage: NFT LeaseEndApprovals (r:0 w:16)
+    /// The range of component `n` is `[0, 16]`.
+    fn end_nft_leases(n: u32) -> Weight {
+        Weight::from_ref_time(4_268_000 as u64)
+            .saturating_add(Weight::from_ref_time(42_589_000 as u64).saturating_mul(n as u64))
+            .saturating_add(DbWeight::get().reads(1 as u64))
+            .saturating_add(DbWeight::get().reads((5 as u64).saturating_mul(n as u64)))
+            .saturating_add(DbWeight::get().writes(1 as u64))
+            .saturating_add(DbWeight::get().writes((7 as u64).saturating_mul(n as u64)))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: Identity Authorizations (r:1 w:1)
+    // Storage: NFT LeaseOffers (r:1 w:1)
+    // Storage: Portfolio PortfolioCustodian (r:1 w:0)
+    // Storage: System Number (r:1 w:0)
+    // Storage: NFT LeasesEndingAt (r:1 w:1)
+    // Storage: Portfolio PortfolioLockedNFT (r:1 w:1)
+    // Storage: NFT CollectionTicker (r:1 w:0)
+    // Storage: Asset LifecycleStates (r:1 w:0)
+    // Storage: NFT NumberOfNFTs (r:2 w:2)
+    // Storage: Portfolio PortfolioNFT (r:1 w:2)
+    // Storage: ComplianceManager AssetCompliances (r:1 w:0)
+    // Storage: Identity NFTCredentials (r:1 w:0)
+    // Storage: Identity AuthorizationsGiven (r:0 w:1)
+    // Storage: NFT Leases (r:0 w:1)
+    fn accept_nft_lease() -> Weight {
+        Weight::from_ref_time(104_730_000 as u64)
+            .saturating_add(DbWeight::get().reads(14 as u64))
+            .saturating_add(DbWeight::get().writes(11 as u64))
+    }
 }
@@ -64,6 +64,14 @@ impl pallet_portfolio::WeightInfo for SubstrateWeight {
             .saturating_add(DbWeight::get().writes(3))
     }
     // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: Portfolio NameToNumber (r:1 w:1)
+    // Storage: Portfolio Portfolios (r:1 w:1)
+    fn create_portfolio_with_salt() -> Weight {
+        Weight::from_ref_time(40_168_000)
+            .saturating_add(DbWeight::get().reads(3))
+            .saturating_add(DbWeight::get().writes(2))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
     // Storage: Portfolio PortfolioAssetCount (r:1 w:1)
     // Storage: Portfolio Portfolios (r:1 w:1)
     // Storage: Portfolio PortfolioCustodian (r:1 w:1)
@@ -147,4 +155,85 @@ impl pallet_portfolio::WeightInfo for SubstrateWeight {
             .saturating_add(DbWeight::get().writes((2 as u64).saturating_mul(f as u64)))
             .saturating_add(DbWeight::get().writes((2 as u64).saturating_mul(n as u64)))
     }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: Portfolio Portfolios (r:2 w:0)
+    // Storage: Portfolio PortfolioCustodian (r:1 w:0)
+    // Storage: Portfolio SweepRuleCounter (r:1 w:1)
+    // Storage: Scheduler Lookup (r:1 w:1)
+    // Storage: Scheduler Agenda (r:1 w:1)
+    // Storage: Portfolio SweepRules (r:0 w:1)
+    fn add_sweep_rule() -> Weight {
+        Weight::from_ref_time(42_612_000)
+            .saturating_add(DbWeight::get().reads(7))
+            .saturating_add(DbWeight::get().writes(4))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: Portfolio SweepRules (r:1 w:1)
+    // Storage: Scheduler Lookup (r:1 w:1)
+    // Storage: Scheduler Agenda (r:1 w:1)
+    fn remove_sweep_rule() -> Weight {
+        Weight::from_ref_time(31_045_000)
+            .saturating_add(DbWeight::get().reads(4))
+            .saturating_add(DbWeight::get().writes(3))
+    }
+    // Storage: Portfolio SweepRules (r:1 w:1)
+    // Storage: Portfolio PortfolioCustodian (r:1 w:0)
+    // Storage: Portfolio Portfolios (r:2 w:0)
+    // Storage: Portfolio PortfolioAssetBalances (r:2 w:2)
+    // Storage: Portfolio PortfolioLockedAssets (r:1 w:0)
+    // Storage: Portfolio LabelledTotals (r:1 w:0)
+    // Storage: Asset Tokens (r:1 w:0)
+    // Storage: Portfolio PortfolioAssetCount (r:2 w:2)
+    // Storage: Scheduler Lookup (r:1 w:1)
+    // Storage: Scheduler Agenda (r:1 w:1)
+    fn execute_sweep_rule() -> Weight {
+        Weight::from_ref_time(60_934_000)
+            .saturating_add(DbWeight::get().reads(13))
+            .saturating_add(DbWeight::get().writes(7))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: Portfolio PortfolioCustodian (r:1 w:0)
+    // Storage: Portfolio Portfolios (r:1 w:0)
+    // Storage: Portfolio LabelledBalances (r:2 w:2)
+    // Storage: Portfolio LabelledTotals (r:1 w:1)
+    // Storage: Portfolio PortfolioAssetBalances (r:1 w:0)
+    // Storage: Portfolio PortfolioLockedAssets (r:1 w:0)
+    fn move_labelled_balance() -> Weight {
+        Weight::from_ref_time(45_790_000)
+            .saturating_add(DbWeight::get().reads(8))
+            .saturating_add(DbWeight::get().writes(3))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: Portfolio PortfolioCustodian (r:1 w:0)
+    // Storage: Portfolio Portfolios (r:1 w:0)
+    // Storage: Portfolio SettlementLockLabel (r:0 w:1)
+    fn set_settlement_lock_label() -> Weight {
+        Weight::from_ref_time(30_102_000)
+            .saturating_add(DbWeight::get().reads(3))
+            .saturating_add(DbWeight::get().writes(1))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: Portfolio Portfolios (r:1 w:0)
+    // Storage: Identity DidRecords (r:1 w:0)
+    // Storage: Portfolio PortfolioObservers (r:16 w:1)
+    // Storage: Portfolio ObservedPortfolios (r:0 w:1)
+    fn add_portfolio_observer() -> Weight {
+        Weight::from_ref_time(42_515_000)
+            .saturating_add(DbWeight::get().reads(19))
+            .saturating_add(DbWeight::get().writes(2))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: Portfolio Portfolios (r:1 w:0)
+    // Storage: Portfolio PortfolioObservers (r:1 w:1)
+    // Storage: Portfolio ObservedPortfolios (r:0 w:1)
+    fn remove_portfolio_observer() -> Weight {
+        Weight::from_ref_time(28_731_000)
+            .saturating_add(DbWeight::get().reads(3))
+            .saturating_add(DbWeight::get().writes(2))
+    }
+    /// The range of component `m` is `[0, 2048]`.
+    fn fund_memos(m: u32) -> Weight {
+        Weight::from_ref_time(1_310_000)
+            .saturating_add(Weight::from_ref_time(1_000).saturating_mul(m.into()))
+    }
 }
@@ -63,4 +63,14 @@ impl pallet_protocol_fee::WeightInfo for SubstrateWeight {
         // Minimum execution time: 17_913 nanoseconds.
         Weight::from_ref_time(18_494_000).saturating_add(DbWeight::get().writes(1))
     }
+    // Storage: ProtocolFee FeeHolidays (r:0 w:1)
+    fn schedule_fee_holiday() -> Weight {
+        Weight::from_ref_time(18_867_000).saturating_add(DbWeight::get().writes(1))
+    }
+    // Storage: ProtocolFee FeeHolidays (r:1 w:1)
+    fn cancel_fee_holiday() -> Weight {
+        Weight::from_ref_time(22_041_000)
+            .saturating_add(DbWeight::get().reads(1))
+            .saturating_add(DbWeight::get().writes(1))
+    }
 }
@@ -276,6 +276,7 @@ impl pallet_settlement::WeightInfo for SubstrateWeight {
     // Storage: Settlement InstructionAffirmsPending (r:0 w:1)
     // Storage: Settlement VenueInstructions (r:0 w:1)
     // Storage: Settlement AffirmsReceived (r:0 w:1)
+    // Storage: Settlement InstructionRejections (r:0 w:1)
     /// The range of component `l` is `[1, 10]`.
     fn reject_instruction(l: u32) -> Weight {
         // Minimum execution time: 94_174 nanoseconds.
@@ -284,7 +285,7 @@ impl pallet_settlement::WeightInfo for SubstrateWeight {
             .saturating_add(Weight::from_ref_time(26_427_027).saturating_mul(l.into()))
             .saturating_add(DbWeight::get().reads(5))
             .saturating_add(DbWeight::get().reads((3_u64).saturating_mul(l.into())))
-            .saturating_add(DbWeight::get().writes(3))
+            .saturating_add(DbWeight::get().writes(4))
             .saturating_add(DbWeight::get().writes((6_u64).saturating_mul(l.into())))
     }
     // Storage: Identity KeyRecords (r:1 w:0)
@@ -362,12 +363,17 @@ impl pallet_settlement::WeightInfo for SubstrateWeight {
     // Storage: Checkpoint CheckpointIdSequence (r:1 w:0)
     // Storage: Portfolio PortfolioAssetCount (r:2 w:2)
     // Storage: Statistics ActiveAssetStats (r:1 w:0)
+    // Storage: Statistics InvestorVolumeTracking (r:1 w:0)
+    // Storage: Statistics InvestorTransferVolumes (r:2 w:2)
+    // Storage: Statistics SettledVolumes (r:1 w:1)
     // Storage: NFT CollectionTicker (r:100 w:0)
     // Storage: NFT NumberOfNFTs (r:200 w:200)
     // Storage: Portfolio PortfolioNFT (r:100 w:200)
     // Storage: Settlement UserAffirmations (r:0 w:2)
     // Storage: Settlement VenueInstructions (r:0 w:1)
-    // Storage: Settlement AffirmsReceived (r:0 w:2)
+    // Storage: Settlement AffirmsReceived (r:2 w:2)
+    // Storage: Settlement InstructionAuditRecords (r:0 w:1)
+    // Storage: Settlement InstructionAuditDigests (r:0 w:1)
     // Storage: Asset BalanceOfAtScope (r:0 w:2)
     /// The range of component `f` is `[1, 10]`.
     /// The range of component `n` is `[1, 100]`.
@@ -375,15 +381,15 @@ impl pallet_settlement::WeightInfo for SubstrateWeight {
         // Minimum execution time: 5_781_471 nanoseconds.
         Weight::from_ref_time(311_175_455)
             // Standard Error: 5_194_608
-            .saturating_add(Weight::from_ref_time(505_702_752).saturating_mul(f.into()))
+            .saturating_add(Weight::from_ref_time(512_946_104).saturating_mul(f.into()))
             // Standard Error: 488_125
-            .saturating_add(Weight::from_ref_time(399_295_432).saturating_mul(n.into()))
-            .saturating_add(DbWeight::get().reads(59))
-            .saturating_add(DbWeight::get().reads((24_u64).saturating_mul(f.into())))
-            .saturating_add(DbWeight::get().reads((10_u64).saturating_mul(n.into())))
-            .saturating_add(DbWeight::get().writes(12))
-            .saturating_add(DbWeight::get().writes((10_u64).saturating_mul(f.into())))
-            .saturating_add(DbWeight::get().writes((7_u64).saturating_mul(n.into())))
+            .saturating_add(Weight::from_ref_time(406_538_784).saturating_mul(n.into()))
+            .saturating_add(DbWeight::get().reads(61))
+            .saturating_add(DbWeight::get().reads((28_u64).saturating_mul(f.into())))
+            .saturating_add(DbWeight::get().reads((11_u64).saturating_mul(n.into())))
+            .saturating_add(DbWeight::get().writes(14))
+            .saturating_add(DbWeight::get().writes((13_u64).saturating_mul(f.into())))
+            .saturating_add(DbWeight::get().writes((8_u64).saturating_mul(n.into())))
     }
     // Storage: Identity KeyRecords (r:1 w:0)
     // Storage: Settlement InstructionDetails (r:1 w:1)
@@ -476,20 +482,25 @@ impl pallet_settlement::WeightInfo for SubstrateWeight {
     // Storage: Checkpoint CheckpointIdSequence (r:1 w:0)
     // Storage: Portfolio PortfolioAssetCount (r:2 w:2)
     // Storage: Statistics ActiveAssetStats (r:1 w:0)
+    // Storage: Statistics InvestorVolumeTracking (r:1 w:0)
+    // Storage: Statistics InvestorTransferVolumes (r:2 w:2)
+    // Storage: Statistics SettledVolumes (r:1 w:1)
     // Storage: Settlement UserAffirmations (r:0 w:2)
     // Storage: Settlement VenueInstructions (r:0 w:1)
-    // Storage: Settlement AffirmsReceived (r:0 w:2)
+    // Storage: Settlement AffirmsReceived (r:2 w:2)
+    // Storage: Settlement InstructionAuditRecords (r:0 w:1)
+    // Storage: Settlement InstructionAuditDigests (r:0 w:1)
     // Storage: Asset BalanceOfAtScope (r:0 w:2)
     /// The range of component `l` is `[1, 10]`.
     fn execute_manual_instruction(l: u32) -> Weight {
         // Minimum execution time: 579_329 nanoseconds.
         Weight::from_ref_time(131_712_065)
             // Standard Error: 447_198
-            .saturating_add(Weight::from_ref_time(436_517_218).saturating_mul(l.into()))
-            .saturating_add(DbWeight::get().reads(56))
-            .saturating_add(DbWeight::get().reads((28_u64).saturating_mul(l.into())))
-            .saturating_add(DbWeight::get().writes(5))
-            .saturating_add(DbWeight::get().writes((16_u64).saturating_mul(l.into())))
+            .saturating_add(Weight::from_ref_time(443_760_570).saturating_mul(l.into()))
+            .saturating_add(DbWeight::get().reads(58))
+            .saturating_add(DbWeight::get().reads((32_u64).saturating_mul(l.into())))
+            .saturating_add(DbWeight::get().writes(7))
+            .saturating_add(DbWeight::get().writes((19_u64).saturating_mul(l.into())))
     }
     // Storage: Identity KeyRecords (r:1 w:0)
     // Storage: Settlement VenueInfo (r:1 w:0)
@@ -612,6 +623,7 @@ impl pallet_settlement::WeightInfo for SubstrateWeight {
     // Storage: Settlement InstructionAffirmsPending (r:0 w:1)
     // Storage: Settlement VenueInstructions (r:0 w:1)
     // Storage: Settlement AffirmsReceived (r:0 w:1)
+    // Storage: Settlement InstructionRejections (r:0 w:1)
     fn reject_instruction_v2(f: u32, n: u32) -> Weight {
         Weight::from_ref_time(92_422_000 as u64)
             // Standard Error: 365_000
@@ -621,8 +633,529 @@ impl pallet_settlement::WeightInfo for SubstrateWeight {
             .saturating_add(DbWeight::get().reads(8 as u64))
             .saturating_add(DbWeight::get().reads((2 as u64).saturating_mul(f as u64)))
             .saturating_add(DbWeight::get().reads((1 as u64).saturating_mul(n as u64)))
-            .saturating_add(DbWeight::get().writes(10 as u64))
+            .saturating_add(DbWeight::get().writes(11 as u64))
             .saturating_add(DbWeight::get().writes((2 as u64).saturating_mul(f as u64)))
             .saturating_add(DbWeight::get().writes((1 as u64).saturating_mul(n as u64)))
     }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: Settlement InstructionStatuses (r:1 w:0)
+    // Storage: Settlement InstructionDetails (r:1 w:0)
+    // Storage: Settlement VenueInfo (r:1 w:0)
+    // Storage: Settlement AffirmsReceived (r:1 w:0)
+    // Storage: Settlement InstructionLegsV2 (r:11 w:20)
+    // Storage: Settlement InstructionNextLegId (r:1 w:1)
+    // Storage: Settlement InstructionRevision (r:1 w:1)
+    // Storage: Settlement InstructionLegRevisions (r:10 w:10)
+    // Storage: Settlement RemovedInstructionLegs (r:0 w:10)
+    // Storage: Settlement InstructionLegStatus (r:0 w:10)
+    // Storage: Settlement ReceiptCosigners (r:0 w:10)
+    // Storage: Settlement InstructionLegConsiderations (r:0 w:10)
+    // Storage: Settlement UserAffirmations (r:0 w:2)
+    // Storage: Settlement InstructionMediators (r:11 w:10)
+    // Storage: Settlement InstructionAffirmsPending (r:0 w:1)
+    fn edit_instruction_legs(l: u32) -> Weight {
+        Weight::from_ref_time(48_713_000 as u64)
+            .saturating_add(Weight::from_ref_time(6_412_000 as u64).saturating_mul(l as u64))
+            .saturating_add(DbWeight::get().reads(18 as u64))
+            .saturating_add(DbWeight::get().reads((2 as u64).saturating_mul(l as u64)))
+            .saturating_add(DbWeight::get().writes(13 as u64))
+            .saturating_add(DbWeight::get().writes((6 as u64).saturating_mul(l as u64)))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: Settlement InstructionMemos (r:1 w:0)
+    // Storage: Settlement InstructionDetails (r:1 w:0)
+    // Storage: Settlement VenueInfo (r:1 w:0)
+    // Storage: Settlement MemoKeyWraps (r:1 w:0)
+    // Storage: Settlement MemoAccessList (r:0 w:1)
+    fn set_memo_access_list(d: u32) -> Weight {
+        Weight::from_ref_time(31_284_000 as u64)
+            .saturating_add(Weight::from_ref_time(412_000 as u64).saturating_mul(d as u64))
+            .saturating_add(DbWeight::get().reads(5 as u64))
+            .saturating_add(DbWeight::get().writes(1 as u64))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: Settlement InstructionMemos (r:1 w:0)
+    // Storage: Settlement InstructionDetails (r:1 w:0)
+    // Storage: Settlement VenueInfo (r:1 w:0)
+    // Storage: Settlement MemoAccessList (r:1 w:0)
+    // Storage: Settlement MemoKeyWraps (r:0 w:1)
+    fn wrap_memo_keys(k: u32) -> Weight {
+        Weight::from_ref_time(30_516_000 as u64)
+            .saturating_add(Weight::from_ref_time(2_873_000 as u64).saturating_mul(k as u64))
+            .saturating_add(DbWeight::get().reads(5 as u64))
+            .saturating_add(DbWeight::get().writes((1 as u64).saturating_mul(k as u64)))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: Settlement VenueInfo (r:1 w:0)
+    // Storage: Settlement VenueDefaultExpiry (r:0 w:1)
+    fn set_venue_default_expiry() -> Weight {
+        Weight::from_ref_time(25_433_000 as u64)
+            .saturating_add(DbWeight::get().reads(2 as u64))
+            .saturating_add(DbWeight::get().writes(1 as u64))
+    }
+    // Storage: Settlement InstructionStatuses (r:1 w:1)
+    // Storage: Settlement InstructionAffirmsPending (r:1 w:1)
+    // Storage: Settlement InstructionLegsV2 (r:111 w:110)
+    // Storage: Settlement InstructionLegStatus (r:110 w:110)
+    // Storage: Portfolio PortfolioLockedAssets (r:10 w:10)
+    // Storage: Portfolio PortfolioLockedNFT (r:100 w:100)
+    // Storage: Scheduler Lookup (r:2 w:1)
+    // Storage: Scheduler Agenda (r:1 w:1)
+    // Storage: Settlement InstructionDetails (r:1 w:1)
+    // Storage: Settlement VenueInstructions (r:0 w:1)
+    // Storage: Settlement AffirmsReceived (r:0 w:1)
+    // Storage: Settlement UserAffirmations (r:0 w:2)
+    /// The range of component `f` is `[1, 10]`.
+    /// The range of component `n` is `[1, 100]`.
+    fn expire_instruction(f: u32, n: u32) -> Weight {
+        Weight::from_ref_time(96_412_000 as u64)
+            .saturating_add(Weight::from_ref_time(43_176_000 as u64).saturating_mul(f as u64))
+            .saturating_add(Weight::from_ref_time(10_287_000 as u64).saturating_mul(n as u64))
+            .saturating_add(DbWeight::get().reads(8 as u64))
+            .saturating_add(DbWeight::get().reads((3 as u64).saturating_mul(f as u64)))
+            .saturating_add(DbWeight::get().reads((1 as u64).saturating_mul(n as u64)))
+            .saturating_add(DbWeight::get().writes(9 as u64))
+            .saturating_add(DbWeight::get().writes((3 as u64).saturating_mul(f as u64)))
+            .saturating_add(DbWeight::get().writes((1 as u64).saturating_mul(n as u64)))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: Settlement VenueInfo (r:1 w:0)
+    // Storage: Settlement VenueCancellationFee (r:0 w:1)
+    fn set_venue_cancellation_fee() -> Weight {
+        Weight::from_ref_time(25_118_000 as u64)
+            .saturating_add(DbWeight::get().reads(2 as u64))
+            .saturating_add(DbWeight::get().writes(1 as u64))
+    }
+    // Storage: Settlement InstructionCancellationFees (r:1 w:0)
+    // Storage: Identity DidRecords (r:20 w:0)
+    // Storage: System Account (r:21 w:21)
+    /// The range of component `p` is `[1, 10]`.
+    fn pay_cancellation_fee(p: u32) -> Weight {
+        Weight::from_ref_time(14_882_000 as u64)
+            .saturating_add(Weight::from_ref_time(47_305_000 as u64).saturating_mul(p as u64))
+            .saturating_add(DbWeight::get().reads(2 as u64))
+            .saturating_add(DbWeight::get().reads((4 as u64).saturating_mul(p as u64)))
+            .saturating_add(DbWeight::get().writes(1 as u64))
+            .saturating_add(DbWeight::get().writes((2 as u64).saturating_mul(p as u64)))
+    }
+    // Storage: Identity DidRecords (r:10 w:0)
+    // Storage: Settlement InstructionAffirmsPending (r:1 w:1)
+    // Storage: Settlement InstructionMediators (r:0 w:10)
+    /// The range of component `m` is `[1, 10]`.
+    fn add_mediators(m: u32) -> Weight {
+        Weight::from_ref_time(12_463_000 as u64)
+            .saturating_add(Weight::from_ref_time(6_281_000 as u64).saturating_mul(m as u64))
+            .saturating_add(DbWeight::get().reads(1 as u64))
+            .saturating_add(DbWeight::get().reads((1 as u64).saturating_mul(m as u64)))
+            .saturating_add(DbWeight::get().writes(1 as u64))
+            .saturating_add(DbWeight::get().writes((1 as u64).saturating_mul(m as u64)))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: Settlement InstructionStatuses (r:1 w:0)
+    // Storage: Settlement InstructionDetails (r:1 w:0)
+    // Storage: Settlement InstructionMediators (r:1 w:1)
+    // Storage: Settlement InstructionLegsV2 (r:12 w:0)
+    // Storage: Settlement InstructionAffirmsPending (r:1 w:1)
+    // Storage: Settlement SettlementFrozen (r:10 w:0)
+    // Storage: Scheduler Lookup (r:1 w:1)
+    // Storage: Scheduler Agenda (r:1 w:1)
+    /// The range of component `f` is `[1, 10]`.
+    /// The range of component `n` is `[1, 100]`.
+    fn affirm_instruction_as_mediator(f: u32, n: u32) -> Weight {
+        Weight::from_ref_time(54_319_000 as u64)
+            .saturating_add(Weight::from_ref_time(3_157_000 as u64).saturating_mul(f as u64))
+            .saturating_add(Weight::from_ref_time(351_000 as u64).saturating_mul(n as u64))
+            .saturating_add(DbWeight::get().reads(8 as u64))
+            .saturating_add(DbWeight::get().reads((2 as u64).saturating_mul(f as u64)))
+            .saturating_add(DbWeight::get().writes(4 as u64))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: Settlement InstructionStatuses (r:1 w:0)
+    // Storage: Settlement InstructionDetails (r:1 w:0)
+    // Storage: Settlement InstructionMediators (r:1 w:1)
+    // Storage: Settlement InstructionAffirmsPending (r:1 w:1)
+    // Storage: Scheduler Lookup (r:1 w:1)
+    // Storage: Scheduler Agenda (r:1 w:1)
+    fn withdraw_affirmation_as_mediator() -> Weight {
+        Weight::from_ref_time(48_212_000 as u64)
+            .saturating_add(DbWeight::get().reads(7 as u64))
+            .saturating_add(DbWeight::get().writes(4 as u64))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: Settlement InstructionStatuses (r:1 w:0)
+    // Storage: Settlement InstructionDetails (r:1 w:0)
+    // Storage: Settlement VenueInfo (r:1 w:0)
+    // Storage: Identity DidRecords (r:1 w:0)
+    // Storage: Settlement InstructionOperators (r:0 w:1)
+    fn set_instruction_operator() -> Weight {
+        Weight::from_ref_time(34_602_000 as u64)
+            .saturating_add(DbWeight::get().reads(5 as u64))
+            .saturating_add(DbWeight::get().writes(1 as u64))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: Settlement VenueInfo (r:1 w:0)
+    // Storage: Settlement VenueFees (r:0 w:1)
+    fn set_venue_fee() -> Weight {
+        Weight::from_ref_time(25_402_000 as u64)
+            .saturating_add(DbWeight::get().reads(2 as u64))
+            .saturating_add(DbWeight::get().writes(1 as u64))
+    }
+    // Storage: Settlement VenueFeesCharged (r:10 w:10)
+    // Storage: System Account (r:11 w:11)
+    /// The range of component `p` is `[1, 10]`.
+    fn transfer_venue_fees(p: u32) -> Weight {
+        Weight::from_ref_time(9_806_000 as u64)
+            .saturating_add(Weight::from_ref_time(38_912_000 as u64).saturating_mul(p as u64))
+            .saturating_add(DbWeight::get().reads(1 as u64))
+            .saturating_add(DbWeight::get().reads((2 as u64).saturating_mul(p as u64)))
+            .saturating_add(DbWeight::get().writes(1 as u64))
+            .saturating_add(DbWeight::get().writes((2 as u64).saturating_mul(p as u64)))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: Settlement VenueInfo (r:1 w:0)
+    // Storage: Settlement VenueSigners (r:50 w:0)
+    // Storage: Settlement ReceiptSignatories (r:0 w:1)
+    /// The range of component `s` is `[1, 50]`.
+    fn set_receipt_signatories(s: u32) -> Weight {
+        Weight::from_ref_time(27_118_000 as u64)
+            .saturating_add(Weight::from_ref_time(2_347_000 as u64).saturating_mul(s as u64))
+            .saturating_add(DbWeight::get().reads(2 as u64))
+            .saturating_add(DbWeight::get().reads((1 as u64).saturating_mul(s as u64)))
+            .saturating_add(DbWeight::get().writes(1 as u64))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: Settlement VenueInfo (r:1 w:0)
+    // Storage: Settlement ReceiptMetadataSchemas (r:0 w:1)
+    fn set_receipt_metadata_schema() -> Weight {
+        Weight::from_ref_time(25_208_000 as u64)
+            .saturating_add(DbWeight::get().reads(2 as u64))
+            .saturating_add(DbWeight::get().writes(1 as u64))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: Settlement VenueInfo (r:1 w:0)
+    // Storage: Settlement VenueNetting (r:0 w:1)
+    fn set_venue_netting() -> Weight {
+        Weight::from_ref_time(24_617_000 as u64)
+            .saturating_add(DbWeight::get().reads(2 as u64))
+            .saturating_add(DbWeight::get().writes(1 as u64))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: Settlement VenueInfo (r:1 w:0)
+    // Storage: Settlement VenueFiltering (r:1 w:0)
+    // Storage: Settlement RecurringInstructionCounter (r:1 w:1)
+    // Storage: Scheduler Lookup (r:1 w:1)
+    // Storage: Scheduler Agenda (r:1 w:1)
+    // Storage: Settlement RecurringInstructions (r:0 w:1)
+    fn create_recurring_instruction(l: u32) -> Weight {
+        Weight::from_ref_time(58_932_000 as u64)
+            .saturating_add(Weight::from_ref_time(1_237_000 as u64).saturating_mul(l as u64))
+            .saturating_add(DbWeight::get().reads(6 as u64))
+            .saturating_add(DbWeight::get().writes(4 as u64))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: Settlement RecurringInstructions (r:1 w:1)
+    // Storage: Scheduler Lookup (r:1 w:1)
+    // Storage: Scheduler Agenda (r:1 w:1)
+    fn cancel_recurring_instruction() -> Weight {
+        Weight::from_ref_time(40_655_000 as u64)
+            .saturating_add(DbWeight::get().reads(4 as u64))
+            .saturating_add(DbWeight::get().writes(3 as u64))
+    }
+    // Storage: Settlement RecurringInstructions (r:1 w:1)
+    // Storage: Settlement VenueInfo (r:1 w:0)
+    // Storage: Settlement VenueFiltering (r:1 w:0)
+    // Storage: Settlement InstructionCounter (r:1 w:1)
+    // Storage: Settlement VenueDefaultExpiry (r:1 w:0)
+    // Storage: Timestamp Now (r:1 w:0)
+    // Storage: Settlement VenueCancellationFee (r:1 w:0)
+    // Storage: Settlement VenueFees (r:1 w:0)
+    // Storage: Scheduler Lookup (r:1 w:1)
+    // Storage: Scheduler Agenda (r:1 w:1)
+    // Storage: Settlement UserAffirmations (r:0 w:2)
+    // Storage: Settlement InstructionLegsV2 (r:0 w:1)
+    // Storage: Settlement InstructionStatuses (r:0 w:1)
+    // Storage: Settlement InstructionAffirmsPending (r:0 w:1)
+    // Storage: Settlement InstructionMemos (r:0 w:1)
+    // Storage: Settlement InstructionDetails (r:0 w:1)
+    // Storage: Settlement VenueInstructions (r:0 w:1)
+    fn execute_recurring_instruction(l: u32) -> Weight {
+        Weight::from_ref_time(102_740_000 as u64)
+            .saturating_add(Weight::from_ref_time(2_215_000 as u64).saturating_mul(l as u64))
+            .saturating_add(DbWeight::get().reads(10 as u64))
+            .saturating_add(DbWeight::get().writes(13 as u64))
+            .saturating_add(DbWeight::get().writes((1 as u64).saturating_mul(l as u64)))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: ExternalAgents GroupOfAgent (r:1 w:0)
+    // Storage: Permissions CurrentPalletName (r:1 w:0)
+    // Storage: Permissions CurrentDispatchableName (r:1 w:0)
+    // Storage: Settlement SettlementFrozen (r:1 w:1)
+    fn freeze_settlement() -> Weight {
+        Weight::from_ref_time(42_412_000 as u64)
+            .saturating_add(DbWeight::get().reads(5 as u64))
+            .saturating_add(DbWeight::get().writes(1 as u64))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: ExternalAgents GroupOfAgent (r:1 w:0)
+    // Storage: Permissions CurrentPalletName (r:1 w:0)
+    // Storage: Permissions CurrentDispatchableName (r:1 w:0)
+    // Storage: Settlement SettlementFrozen (r:1 w:1)
+    fn unfreeze_settlement() -> Weight {
+        Weight::from_ref_time(42_068_000 as u64)
+            .saturating_add(DbWeight::get().reads(5 as u64))
+            .saturating_add(DbWeight::get().writes(1 as u64))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: Settlement VenueInfo (r:1 w:0)
+    // Storage: Settlement VenueQuotas (r:0 w:1)
+    fn set_venue_quota() -> Weight {
+        Weight::from_ref_time(25_036_000 as u64)
+            .saturating_add(DbWeight::get().reads(2 as u64))
+            .saturating_add(DbWeight::get().writes(1 as u64))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: Settlement VenueInfo (r:1 w:0)
+    // Storage: Settlement PausedVenues (r:1 w:1)
+    fn pause_venue() -> Weight {
+        Weight::from_ref_time(25_903_000 as u64)
+            .saturating_add(DbWeight::get().reads(3 as u64))
+            .saturating_add(DbWeight::get().writes(1 as u64))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: Settlement VenueInfo (r:1 w:0)
+    // Storage: Settlement PausedVenues (r:1 w:1)
+    fn resume_venue() -> Weight {
+        Weight::from_ref_time(25_781_000 as u64)
+            .saturating_add(DbWeight::get().reads(3 as u64))
+            .saturating_add(DbWeight::get().writes(1 as u64))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: Settlement VenueInfo (r:1 w:1)
+    // Storage: Settlement VenueOpenInstructions (r:1 w:1)
+    // Storage: Settlement VenueSigners (r:51 w:50)
+    // Storage: Settlement VenueInstructions (r:1001 w:1000)
+    // Storage: Settlement UserVenues (r:1 w:1)
+    // Storage: Settlement InstructionsCreatedByKey (r:1 w:0)
+    // Storage: Settlement Details (r:0 w:1)
+    // Storage: Settlement VenueDefaultExpiry (r:0 w:1)
+    // Storage: Settlement VenueCancellationFee (r:0 w:1)
+    // Storage: Settlement VenueFees (r:0 w:1)
+    // Storage: Settlement ReceiptSignatories (r:0 w:1)
+    // Storage: Settlement ReceiptMetadataSchemas (r:0 w:1)
+    // Storage: Settlement VenueNetting (r:0 w:1)
+    // Storage: Settlement VenueQuotas (r:0 w:1)
+    // Storage: Settlement PausedVenues (r:0 w:1)
+    // Storage: Settlement VenueInstructionRequests (r:33 w:32)
+    // Storage: Settlement InstructionRequests (r:0 w:32)
+    // Storage: Settlement VenueInstructionRequestsCount (r:0 w:1)
+    /// The range of component `s` is `[0, 50]`.
+    /// The range of component `i` is `[0, 1000]`.
+    fn close_venue(s: u32, i: u32) -> Weight {
+        Weight::from_ref_time(148_305_000 as u64)
+            .saturating_add(Weight::from_ref_time(2_908_000 as u64).saturating_mul(s as u64))
+            .saturating_add(Weight::from_ref_time(1_846_000 as u64).saturating_mul(i as u64))
+            .saturating_add(DbWeight::get().reads(40 as u64))
+            .saturating_add(DbWeight::get().reads((1 as u64).saturating_mul(s as u64)))
+            .saturating_add(DbWeight::get().reads((1 as u64).saturating_mul(i as u64)))
+            .saturating_add(DbWeight::get().writes(78 as u64))
+            .saturating_add(DbWeight::get().writes((1 as u64).saturating_mul(s as u64)))
+            .saturating_add(DbWeight::get().writes((1 as u64).saturating_mul(i as u64)))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: Settlement InstructionStatuses (r:1 w:0)
+    // Storage: Settlement InstructionDetails (r:1 w:0)
+    // Storage: Settlement VenueInfo (r:1 w:0)
+    // Storage: Settlement AffirmsReceived (r:1 w:0)
+    // Storage: Settlement InstructionLegsV2 (r:11 w:20)
+    // Storage: Asset LifecycleStates (r:10 w:0)
+    // Storage: Settlement InstructionNextLegId (r:1 w:1)
+    // Storage: Settlement InstructionRevision (r:1 w:1)
+    // Storage: Settlement InstructionLegRevisions (r:1 w:2)
+    // Storage: Settlement RemovedInstructionLegs (r:0 w:1)
+    // Storage: Settlement InstructionLegStatus (r:0 w:1)
+    // Storage: Settlement ReceiptCosigners (r:0 w:1)
+    // Storage: Settlement InstructionLegConsiderations (r:0 w:1)
+    // Storage: Settlement UserAffirmations (r:0 w:2)
+    // Storage: Settlement InstructionMediators (r:11 w:10)
+    // Storage: Settlement InstructionAffirmsPending (r:0 w:1)
+    /// The range of component `l` is `[1, 10]`.
+    fn amend_instruction_leg(l: u32) -> Weight {
+        Weight::from_ref_time(49_870_000 as u64)
+            .saturating_add(Weight::from_ref_time(5_936_000 as u64).saturating_mul(l as u64))
+            .saturating_add(DbWeight::get().reads(20 as u64))
+            .saturating_add(DbWeight::get().reads((2 as u64).saturating_mul(l as u64)))
+            .saturating_add(DbWeight::get().writes(21 as u64))
+            .saturating_add(DbWeight::get().writes((2 as u64).saturating_mul(l as u64)))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: Settlement InstructionStatuses (r:1 w:0)
+    // Storage: Settlement InstructionDetails (r:1 w:0)
+    // Storage: Settlement VenueInfo (r:1 w:0)
+    // Storage: Settlement AffirmsReceived (r:1 w:0)
+    // Storage: Settlement InstructionLegsV2 (r:1 w:0)
+    // Storage: Settlement InstructionLegConsiderations (r:0 w:1)
+    fn set_leg_consideration() -> Weight {
+        Weight::from_ref_time(35_012_000 as u64)
+            .saturating_add(DbWeight::get().reads(6 as u64))
+            .saturating_add(DbWeight::get().writes(1 as u64))
+    }
+    // Storage: Settlement MarketHoursConfig (r:0 w:1)
+    fn set_market_hours() -> Weight {
+        Weight::from_ref_time(12_934_000 as u64).saturating_add(DbWeight::get().writes(1 as u64))
+    }
+    // Storage: Settlement ExecutionRounds (r:0 w:50)
+    /// The range of component `v` is `[0, 50]`.
+    fn clear_execution_rounds(v: u32) -> Weight {
+        Weight::from_ref_time(4_537_000 as u64)
+            .saturating_add(Weight::from_ref_time(1_103_000 as u64).saturating_mul(v as u64))
+            .saturating_add(DbWeight::get().writes((1 as u64).saturating_mul(v as u64)))
+    }
+    // Storage: Settlement TaggedInstructions (r:0 w:4)
+    // Storage: Settlement InstructionTags (r:0 w:1)
+    /// The range of component `t` is `[1, 4]`.
+    fn add_instruction_tags(t: u32) -> Weight {
+        Weight::from_ref_time(10_215_000 as u64)
+            .saturating_add(Weight::from_ref_time(1_492_000 as u64).saturating_mul(t as u64))
+            .saturating_add(DbWeight::get().writes(1 as u64))
+            .saturating_add(DbWeight::get().writes((1 as u64).saturating_mul(t as u64)))
+    }
+    // Storage: Settlement InstructionLegs (r:1000 w:1000)
+    // Storage: Settlement InstructionLegsV2 (r:0 w:1000)
+    /// The range of component `i` is `[1, 100]`.
+    /// The range of component `l` is `[0, 1000]`.
+    fn migrate_instruction_legs(i: u32, l: u32) -> Weight {
+        Weight::from_ref_time(8_905_000 as u64)
+            .saturating_add(Weight::from_ref_time(2_174_000 as u64).saturating_mul(i as u64))
+            .saturating_add(Weight::from_ref_time(6_013_000 as u64).saturating_mul(l as u64))
+            .saturating_add(DbWeight::get().reads((1 as u64).saturating_mul(i as u64)))
+            .saturating_add(DbWeight::get().reads((2 as u64).saturating_mul(l as u64)))
+            .saturating_add(DbWeight::get().writes((2 as u64).saturating_mul(l as u64)))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: Settlement InstructionDetails (r:1 w:1)
+    // Storage: Settlement InstructionStatuses (r:1 w:1)
+    // Storage: Portfolio PortfolioCustodian (r:2 w:0)
+    // Storage: Settlement UserAffirmations (r:2 w:4)
+    // Storage: Settlement InstructionAffirmsPending (r:1 w:1)
+    // Storage: Settlement InstructionLegsV2 (r:10 w:20)
+    // Storage: Settlement InstructionLegStatus (r:10 w:20)
+    // Storage: Portfolio PortfolioLockedAssets (r:10 w:20)
+    // Storage: Portfolio PortfolioAssetBalances (r:20 w:20)
+    // Storage: Asset BalanceOf (r:20 w:20)
+    // Storage: Asset Tokens (r:10 w:0)
+    // Storage: Asset Frozen (r:10 w:0)
+    // Storage: ComplianceManager AssetCompliances (r:10 w:0)
+    // Storage: Statistics ActiveAssetStats (r:10 w:0)
+    // Storage: Settlement SettlementFrozen (r:10 w:0)
+    // Storage: Settlement AffirmsReceived (r:0 w:2)
+    // Storage: Settlement SettledInstructions (r:0 w:1)
+    /// The range of component `l` is `[1, 10]`.
+    fn reveal_portfolio_alias(l: u32) -> Weight {
+        Weight::from_ref_time(212_473_000 as u64)
+            .saturating_add(Weight::from_ref_time(58_902_000 as u64).saturating_mul(l as u64))
+            .saturating_add(DbWeight::get().reads(8 as u64))
+            .saturating_add(DbWeight::get().reads((12 as u64).saturating_mul(l as u64)))
+            .saturating_add(DbWeight::get().writes(11 as u64))
+            .saturating_add(DbWeight::get().writes((10 as u64).saturating_mul(l as u64)))
+    }
+    // Storage: Settlement SettledInstructions (r:1 w:1)
+    // Storage: Settlement InstructionStatuses (r:0 w:1)
+    // Storage: Settlement InstructionAuditRecords (r:0 w:1)
+    // Storage: Settlement InstructionAuditDigests (r:0 w:1)
+    fn prune_instruction_status() -> Weight {
+        Weight::from_ref_time(14_971_000 as u64)
+            .saturating_add(DbWeight::get().reads(1 as u64))
+            .saturating_add(DbWeight::get().writes(4 as u64))
+    }
+    // Storage: Settlement RejectedInstructions (r:1 w:1)
+    // Storage: Settlement InstructionLegsV2 (r:1 w:1)
+    // Storage: Settlement InstructionLegs (r:1 w:0)
+    // Storage: Settlement InstructionDetails (r:0 w:1)
+    // Storage: Settlement InstructionNextLegId (r:0 w:1)
+    // Storage: Settlement InstructionLegStatus (r:0 w:1)
+    // Storage: Settlement InstructionAffirmsPending (r:0 w:1)
+    // Storage: Settlement AffirmsReceived (r:0 w:2)
+    // Storage: Settlement MemoAccessList (r:1 w:1)
+    // Storage: Settlement MemoKeyWraps (r:0 w:100)
+    /// The range of component `l` is `[1, 10]`.
+    fn prune_rejected_instruction(l: u32) -> Weight {
+        Weight::from_ref_time(36_913_000 as u64)
+            .saturating_add(Weight::from_ref_time(4_282_000 as u64).saturating_mul(l as u64))
+            .saturating_add(DbWeight::get().reads(4 as u64))
+            .saturating_add(DbWeight::get().writes(106 as u64))
+            .saturating_add(DbWeight::get().writes((3 as u64).saturating_mul(l as u64)))
+    }
+    /// The range of component `m` is `[0, 2048]`.
+    fn instruction_memo(m: u32) -> Weight {
+        Weight::from_ref_time(1_206_000 as u64)
+            .saturating_add(Weight::from_ref_time(1_000 as u64).saturating_mul(m as u64))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: Settlement VenueInfo (r:1 w:0)
+    // Storage: Settlement PausedVenues (r:1 w:0)
+    // Storage: Settlement VenueFiltering (r:1 w:0)
+    // Storage: Asset LifecycleStates (r:1 w:0)
+    // Storage: Settlement VenueInstructionRequestsCount (r:1 w:1)
+    // Storage: Settlement InstructionRequestCounter (r:1 w:1)
+    // Storage: Settlement InstructionRequests (r:0 w:1)
+    // Storage: Settlement VenueInstructionRequests (r:0 w:1)
+    /// The range of component `l` is `[1, 10]`.
+    fn request_instruction(l: u32) -> Weight {
+        Weight::from_ref_time(43_207_000 as u64)
+            .saturating_add(Weight::from_ref_time(3_082_000 as u64).saturating_mul(l as u64))
+            .saturating_add(DbWeight::get().reads(7 as u64))
+            .saturating_add(DbWeight::get().writes(4 as u64))
+    }
+    // Storage: Settlement InstructionRequests (r:1 w:1)
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: Settlement VenueQuotas (r:2 w:0)
+    // Storage: Settlement VenueInfo (r:1 w:0)
+    // Storage: Settlement PausedVenues (r:1 w:0)
+    // Storage: Settlement VenueOpenInstructions (r:1 w:1)
+    // Storage: Settlement VenueFiltering (r:2 w:0)
+    // Storage: Nft NumberOfNFTs (r:1 w:0)
+    // Storage: Asset LifecycleStates (r:2 w:0)
+    // Storage: Settlement InstructionCounter (r:1 w:1)
+    // Storage: Settlement VenueDefaultExpiry (r:1 w:0)
+    // Storage: Timestamp Now (r:1 w:0)
+    // Storage: Scheduler Lookup (r:1 w:1)
+    // Storage: Scheduler Agenda (r:1 w:1)
+    // Storage: Settlement InstructionStatuses (r:0 w:1)
+    // Storage: Settlement UserAffirmations (r:0 w:2)
+    // Storage: Settlement VenueInstructions (r:0 w:1)
+    // Storage: Settlement InstructionDetails (r:0 w:1)
+    // Storage: Settlement InstructionLegsV2 (r:0 w:11)
+    // Storage: Settlement InstructionAffirmsPending (r:0 w:1)
+    // Storage: Settlement InstructionMemos (r:0 w:1)
+    // Storage: Settlement VenueInstructionRequests (r:0 w:1)
+    // Storage: Settlement VenueInstructionRequestsCount (r:1 w:1)
+    /// The range of component `f` is `[1, 10]`.
+    /// The range of component `n` is `[1, 100]`.
+    fn approve_requested_instruction(f: u32, n: u32) -> Weight {
+        Weight::from_ref_time(74_916_000 as u64)
+            .saturating_add(Weight::from_ref_time(9_761_000 as u64).saturating_mul(f as u64))
+            .saturating_add(Weight::from_ref_time(1_212_000 as u64).saturating_mul(n as u64))
+            .saturating_add(DbWeight::get().reads(17 as u64))
+            .saturating_add(DbWeight::get().writes(14 as u64))
+            .saturating_add(DbWeight::get().writes((1 as u64).saturating_mul(f as u64)))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: Settlement InstructionRequests (r:1 w:1)
+    // Storage: Settlement VenueInfo (r:1 w:0)
+    // Storage: Settlement VenueInstructionRequests (r:0 w:1)
+    // Storage: Settlement VenueInstructionRequestsCount (r:1 w:1)
+    fn remove_instruction_request() -> Weight {
+        Weight::from_ref_time(29_436_000 as u64)
+            .saturating_add(DbWeight::get().reads(4 as u64))
+            .saturating_add(DbWeight::get().writes(3 as u64))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: Portfolio PortfolioCustodian (r:1 w:0)
+    // Storage: Identity DidRecords (r:1 w:0)
+    // Storage: Settlement PortfolioSettlementAgents (r:1 w:1)
+    fn set_portfolio_settlement_agent() -> Weight {
+        Weight::from_ref_time(27_318_000 as u64)
+            .saturating_add(DbWeight::get().reads(4 as u64))
+            .saturating_add(DbWeight::get().writes(1 as u64))
+    }
 }
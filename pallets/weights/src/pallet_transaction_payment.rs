@@ -0,0 +1,73 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Autogenerated weights for pallet_transaction_payment
+//!
+//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
+//! DATE: 2023-01-25, STEPS: `100`, REPEAT: 5, LOW RANGE: `[]`, HIGH RANGE: `[]`
+//! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: None, DB CACHE: 512
+//! HOSTNAME: `dev-fsn001`, CPU: `AMD Ryzen 9 5950X 16-Core Processor`
+
+// Executed Command:
+// ./target/release/polymesh
+// benchmark
+// pallet
+// -s
+// 100
+// -r
+// 5
+// -p=pallet_transaction_payment
+// -e=*
+// --heap-pages
+// 4096
+// --db-cache
+// 512
+// --execution
+// wasm
+// --wasm-execution
+// compiled
+// --output
+// ./pallets/weights/src/
+// --template
+// ./.maintain/frame-weight-template.hbs
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use polymesh_runtime_common::{RocksDbWeight as DbWeight, Weight};
+
+/// Weights for pallet_transaction_payment using the Substrate node and recommended hardware.
+pub struct SubstrateWeight;
+impl pallet_transaction_payment::WeightInfo for SubstrateWeight {
+    // Storage: TransactionPayment FeeTiers (r:0 w:1)
+    /// The range of component `t` is `[0, 16]`.
+    fn set_fee_tiers(t: u32) -> Weight {
+        Weight::from_ref_time(17_986_000)
+            .saturating_add(Weight::from_ref_time(742_311).saturating_mul(t.into()))
+            .saturating_add(DbWeight::get().writes(1))
+    }
+    // Storage: Identity KeyRecords (r:1 w:0)
+    // Storage: TransactionPayment FeeTiers (r:1 w:0)
+    // Storage: Identity Claims (r:16 w:0)
+    /// The range of component `t` is `[0, 16]`.
+    fn apply_fee_tier(t: u32) -> Weight {
+        Weight::from_ref_time(9_412_000)
+            .saturating_add(Weight::from_ref_time(6_118_560).saturating_mul(t.into()))
+            .saturating_add(DbWeight::get().reads(2))
+            .saturating_add(DbWeight::get().reads((1_u64).saturating_mul(t.into())))
+    }
+}
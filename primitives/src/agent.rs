@@ -1,6 +1,7 @@
-use crate::impl_checked_inc;
+use crate::{impl_checked_inc, IdentityId, Ticker};
 use codec::{Decode, Encode};
 use scale_info::TypeInfo;
+use sp_core::H512;
 #[cfg(feature = "std")]
 use sp_runtime::{Deserialize, Serialize};
 
@@ -30,3 +31,24 @@ pub enum AgentGroup {
     /// Agent group corresponding to a Primary Issuance Agent (PIA) on Polymesh Mainnet v1.
     PolymeshV1PIA,
 }
+
+/// A replacement of the sole full agent of an asset, scheduled by governance.
+#[derive(Encode, Decode, TypeInfo)]
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct AgentReplacement<Moment> {
+    /// The identity that becomes the only agent of the asset.
+    pub new_agent: IdentityId,
+    /// The moment from which the replacement can be carried out.
+    pub effective_at: Moment,
+}
+
+/// A session key along with its off-chain authorization to join the agent's identity.
+/// `auth_signature` is the signature, generated by `key`, of a `TargetIdAuthorization`.
+#[derive(Encode, Decode, TypeInfo)]
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct SessionKeyWithAuth<AccountId> {
+    /// The key to register.
+    pub key: AccountId,
+    /// Off-chain authorization signature.
+    pub auth_signature: H512,
+}
@@ -13,10 +13,11 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <http://www.gnu.org/licenses/>.
 
-use crate::compliance_manager::AssetComplianceResult;
+use crate::compliance_manager::{AssetComplianceResult, ComplianceSummary};
 use crate::identity_id::PortfolioValidityResult;
 use crate::impl_checked_inc;
 use crate::transfer_compliance::TransferConditionResult;
+use crate::{Balance, Document, DocumentId, IdentityId, Ticker};
 use codec::{Decode, Encode};
 use polymesh_primitives_derive::VecU8StrongTyped;
 use scale_info::TypeInfo;
@@ -76,6 +77,61 @@ pub enum AssetType {
     NonFungible(NonFungibleType),
 }
 
+/// The lifecycle state of an asset, which defines the operations allowed on it.
+#[derive(Encode, Decode, TypeInfo, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum AssetLifecycleState {
+    /// The asset has been created but no token has been issued yet.
+    PreIssuance,
+    /// Tokens can be issued, transferred and redeemed.
+    Active,
+    /// Transfers and issuance are halted, e.g. while the asset is frozen.
+    Suspended,
+    /// The asset has reached maturity, its tokens can only be redeemed.
+    Matured,
+    /// The asset has been retired, no operation is allowed on its tokens anymore.
+    Retired,
+}
+
+impl Default for AssetLifecycleState {
+    fn default() -> Self {
+        Self::Active
+    }
+}
+
+impl AssetLifecycleState {
+    /// Returns `true` if an asset in this state can be moved to the `next` state.
+    pub fn can_transition_to(&self, next: &Self) -> bool {
+        use AssetLifecycleState::*;
+        matches!(
+            (self, next),
+            (PreIssuance, Active | Suspended | Retired)
+                | (Active, Suspended | Matured)
+                | (Suspended, Active | Matured | Retired)
+                | (Matured, Retired)
+        )
+    }
+
+    /// Returns `true` if tokens of an asset in this state can be issued.
+    pub fn allows_issuance(&self) -> bool {
+        matches!(self, Self::PreIssuance | Self::Active)
+    }
+
+    /// Returns `true` if tokens of an asset in this state can be transferred.
+    pub fn allows_transfers(&self) -> bool {
+        matches!(self, Self::Active)
+    }
+
+    /// Returns `true` if tokens of an asset in this state can be redeemed.
+    pub fn allows_redemption(&self) -> bool {
+        matches!(self, Self::Active | Self::Matured)
+    }
+
+    /// Returns `true` if tokens of an asset in this state can never be transferred again.
+    pub fn transfers_ended(&self) -> bool {
+        matches!(self, Self::Matured | Self::Retired)
+    }
+}
+
 /// Defines all non-fungible variants.
 #[derive(Encode, Decode, TypeInfo, Copy, Clone, Debug, PartialEq, Eq)]
 pub enum NonFungibleType {
@@ -125,6 +181,28 @@ impl AssetType {
             AssetType::NonFungible(_) => false,
         }
     }
+
+    /// Returns the name of the asset type, or `None` for custom types, whose names are stored on-chain.
+    pub fn label(&self) -> Option<&'static [u8]> {
+        Some(match self {
+            AssetType::EquityCommon => b"EquityCommon",
+            AssetType::EquityPreferred => b"EquityPreferred",
+            AssetType::Commodity => b"Commodity",
+            AssetType::FixedIncome => b"FixedIncome",
+            AssetType::REIT => b"REIT",
+            AssetType::Fund => b"Fund",
+            AssetType::RevenueShareAgreement => b"RevenueShareAgreement",
+            AssetType::StructuredProduct => b"StructuredProduct",
+            AssetType::Derivative => b"Derivative",
+            AssetType::StableCoin => b"StableCoin",
+            AssetType::NonFungible(NonFungibleType::Derivative) => b"NonFungibleDerivative",
+            AssetType::NonFungible(NonFungibleType::FixedIncome) => b"NonFungibleFixedIncome",
+            AssetType::NonFungible(NonFungibleType::Invoice) => b"NonFungibleInvoice",
+            AssetType::Custom(_) | AssetType::NonFungible(NonFungibleType::Custom(_)) => {
+                return None
+            }
+        })
+    }
 }
 
 /// A wrapper for a funding round name.
@@ -132,6 +210,55 @@ impl AssetType {
 #[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Default)]
 pub struct FundingRoundName(pub Vec<u8>);
 
+/// The authorized share capital of an asset compared to its issued supply.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize, Debug))]
+#[derive(Decode, Encode, Clone, PartialEq, Eq)]
+pub struct AuthorizedCapital {
+    /// Maximum supply authorized by the agents of the asset, if any.
+    pub authorized_supply: Option<Balance>,
+    /// Supply issued so far.
+    pub issued_supply: Balance,
+}
+
+/// The descriptive metadata of an asset gathered in a single structure, for bridges and aggregators.
+/// Types, identifier schemes and metadata names are given by their ASCII labels.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize, Debug))]
+#[derive(Decode, Encode, Clone, PartialEq, Eq)]
+pub struct AssetDescription {
+    /// Ticker of the asset.
+    pub ticker: Ticker,
+    /// Name of the asset.
+    pub name: Vec<u8>,
+    /// Type of the asset, e.g. `EquityCommon`, or the name of its custom type.
+    pub asset_type: Vec<u8>,
+    /// Whether the asset is divisible.
+    pub divisible: bool,
+    /// Identity owning the asset.
+    pub owner: IdentityId,
+    /// Total supply of the asset.
+    pub total_supply: Balance,
+    /// Name of the current funding round.
+    pub funding_round: Vec<u8>,
+    /// Identifiers of the asset, as (scheme, code), e.g. (`ISIN`, `US0378331005`).
+    pub identifiers: Vec<(Vec<u8>, Vec<u8>)>,
+    /// Documents of the asset.
+    pub documents: Vec<(DocumentId, Document)>,
+    /// Metadata values of the asset, as (name, value).
+    pub metadata: Vec<(Vec<u8>, Vec<u8>)>,
+    /// Overview of the compliance rules of the asset.
+    pub compliance: ComplianceSummary,
+}
+
+/// A page of the holders of an asset with their balances, for building cap tables.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize, Debug))]
+#[derive(Decode, Encode, Clone, PartialEq, Eq)]
+pub struct AssetHolders {
+    /// Holders with a non-zero balance, in increasing order of DID, with their balances.
+    pub holders: Vec<(IdentityId, Balance)>,
+    /// The last holder of the page if there are more holders, to start the next page after.
+    pub next_holder: Option<IdentityId>,
+}
+
 /// Result of a granular can transfer.
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize, Debug))]
 #[derive(Decode, Encode, Clone, PartialEq, Eq)]
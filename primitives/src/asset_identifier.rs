@@ -68,6 +68,26 @@ impl AssetIdentifier {
             AssetIdentifier::FIGI(bs) => validate_figi(bs),
         }
     }
+
+    /// Returns the name of the identifier scheme, e.g. `ISIN`.
+    pub fn scheme(&self) -> &'static [u8] {
+        match self {
+            AssetIdentifier::CUSIP(_) => b"CUSIP",
+            AssetIdentifier::CINS(_) => b"CINS",
+            AssetIdentifier::ISIN(_) => b"ISIN",
+            AssetIdentifier::LEI(_) => b"LEI",
+            AssetIdentifier::FIGI(_) => b"FIGI",
+        }
+    }
+
+    /// Returns the identifier code, as ASCII characters.
+    pub fn code(&self) -> &[u8] {
+        match self {
+            AssetIdentifier::CUSIP(bs) | AssetIdentifier::CINS(bs) => bs,
+            AssetIdentifier::ISIN(bs) | AssetIdentifier::FIGI(bs) => bs,
+            AssetIdentifier::LEI(bs) => bs,
+        }
+    }
 }
 
 fn validate_cusip(bytes: &[u8; 9]) -> bool {
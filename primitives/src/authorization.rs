@@ -14,8 +14,8 @@
 // along with this program. If not, see <http://www.gnu.org/licenses/>.
 
 use crate::{
-    agent::AgentGroup, identity_id::IdentityId, secondary_key::Permissions, Balance, PortfolioId,
-    Ticker,
+    agent::AgentGroup, identity_id::IdentityId, nft::NFTLeaseId, secondary_key::Permissions,
+    Balance, PortfolioId, Ticker,
 };
 use codec::{Decode, Encode};
 use frame_support::dispatch::DispatchError;
@@ -55,6 +55,12 @@ pub enum AuthorizationData<AccountId> {
     /// Authorization to change primary key and leave it as a secondary key
     /// with the given permissions.
     RotatePrimaryKeyToSecondary(Permissions),
+    /// Authorization to take an NFT on lease.
+    /// Must be issued by the lessor to the custodian of the lessee's portfolio.
+    LeaseNFT(NFTLeaseId),
+    /// Authorization to renew the registration of a ticker, paying its fees.
+    /// Must be issued by the owner of the ticker.
+    BecomeTickerRenewalAgent(Ticker),
 }
 
 impl<AccountId> AuthorizationData<AccountId> {
@@ -71,6 +77,8 @@ impl<AccountId> AuthorizationData<AccountId> {
             Self::PortfolioCustody(..) => AuthorizationType::PortfolioCustody,
             Self::AddRelayerPayingKey(..) => AuthorizationType::AddRelayerPayingKey,
             Self::RotatePrimaryKeyToSecondary(..) => AuthorizationType::RotatePrimaryKeyToSecondary,
+            Self::LeaseNFT(..) => AuthorizationType::LeaseNFT,
+            Self::BecomeTickerRenewalAgent(..) => AuthorizationType::BecomeTickerRenewalAgent,
         }
     }
 }
@@ -99,6 +107,10 @@ pub enum AuthorizationType {
     AddRelayerPayingKey,
     /// Authorization to change primary key with an existing secondary key
     RotatePrimaryKeyToSecondary,
+    /// Authorization to take an NFT on lease.
+    LeaseNFT,
+    /// Authorization to become the renewal agent of a ticker.
+    BecomeTickerRenewalAgent,
 }
 
 /// Status of an Authorization after consume is called on it.
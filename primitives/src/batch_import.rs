@@ -0,0 +1,59 @@
+// This file is part of the Polymesh distribution (https://github.com/PolymeshAssociation/Polymesh).
+// Copyright (c) 2020 Polymath
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, version 3.
+
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+// General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+use codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use sp_core::H256;
+use sp_io::hashing::blake2_256;
+
+/// Progress of a batched import of entries, e.g. exempt entities or claim targets.
+#[derive(Decode, Encode, TypeInfo)]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BatchImport {
+    /// Total number of entries expected to be imported.
+    pub total_entries: u32,
+    /// Number of entries imported so far.
+    pub imported_entries: u32,
+    /// The hash expected once all entries have been imported.
+    pub expected_hash: H256,
+    /// Running hash over the entries imported so far.
+    pub running_hash: H256,
+}
+
+impl BatchImport {
+    /// Creates a new import expecting `total_entries` entries, hashing to `expected_hash`.
+    pub fn new(total_entries: u32, expected_hash: H256) -> Self {
+        Self {
+            total_entries,
+            imported_entries: 0,
+            expected_hash,
+            running_hash: H256::zero(),
+        }
+    }
+
+    /// Folds `entry` into the running hash and increments the imported entries.
+    ///
+    /// The running hash is `blake2_256(running_hash ++ entry)`, starting from the zero hash,
+    /// so that off-chain tooling can compute the expected hash of an ordered list of entries.
+    pub fn add_entry<E: Encode>(&mut self, entry: &E) {
+        self.running_hash = H256(blake2_256(&(self.running_hash, entry).encode()));
+        self.imported_entries = self.imported_entries.saturating_add(1);
+    }
+
+    /// Returns `true` if all entries have been imported and the running hash matches.
+    pub fn is_complete(&self) -> bool {
+        self.imported_entries == self.total_entries && self.running_hash == self.expected_hash
+    }
+}
@@ -13,8 +13,10 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <http://www.gnu.org/licenses/>.
 
-use crate::condition::{conditions_total_counts, Condition};
+use crate::condition::{conditions_total_counts, Condition, TrustedIssuer};
+use crate::{impl_checked_inc, Claim, IdentityId};
 use codec::{Decode, Encode};
+use polymesh_primitives_derive::VecU8StrongTyped;
 use scale_info::TypeInfo;
 #[cfg(feature = "std")]
 use sp_runtime::{Deserialize, Serialize};
@@ -61,6 +63,28 @@ impl ComplianceRequirement {
     }
 }
 
+/// A change to the compliance requirements of an asset, scheduled for a later block.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, TypeInfo, Clone, PartialEq, Eq, Debug)]
+pub enum ComplianceRequirementChange {
+    /// Adds the requirement. Its id is assigned when the change is scheduled.
+    Add(ComplianceRequirement),
+    /// Replaces the requirement with the same id.
+    Replace(ComplianceRequirement),
+    /// Removes the requirement with the given id.
+    Remove(u32),
+}
+
+impl ComplianceRequirementChange {
+    /// Returns the id of the requirement affected by the change.
+    pub fn requirement_id(&self) -> u32 {
+        match self {
+            Self::Add(req) | Self::Replace(req) => req.id,
+            Self::Remove(id) => *id,
+        }
+    }
+}
+
 /// A compliance requirement along with its evaluation result
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize, Debug))]
 #[derive(Encode, Decode, Clone, PartialEq, Eq, Hash)]
@@ -141,3 +165,98 @@ impl From<AssetCompliance> for AssetComplianceResult {
         }
     }
 }
+
+/// A condition evaluated for a hypothetical transfer, along with how it could be satisfied.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize, Debug))]
+#[derive(Encode, Decode, Clone, PartialEq, Eq)]
+pub struct ConditionReport {
+    /// Condition being evaluated.
+    pub condition: Condition,
+    /// Result of evaluation.
+    pub result: bool,
+    /// Claims lacked by the identity that would satisfy the condition,
+    /// each with the issuers trusted to issue it.
+    /// Empty if the condition holds, or doesn't require any claim to hold.
+    pub missing_claims: Vec<(Claim, Vec<TrustedIssuer>)>,
+}
+
+/// A compliance requirement evaluated for a hypothetical transfer.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize, Debug))]
+#[derive(Encode, Decode, Clone, PartialEq, Eq)]
+pub struct ComplianceRequirementReport {
+    /// Unique identifier of the compliance requirement.
+    pub id: u32,
+    /// Whether the requirement is disabled, in which case it always fails.
+    pub disabled: bool,
+    /// Reports of the sender conditions.
+    pub sender_conditions: Vec<ConditionReport>,
+    /// Reports of the receiver conditions.
+    pub receiver_conditions: Vec<ConditionReport>,
+    /// Result of the requirement's evaluation.
+    pub result: bool,
+}
+
+/// The compliance rules of an asset evaluated for a hypothetical transfer.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize, Debug))]
+#[derive(Encode, Decode, Clone, PartialEq, Eq)]
+pub struct AssetComplianceReport {
+    /// Whether the compliance rules are paused, in which case any transfer is compliant.
+    pub paused: bool,
+    /// Reports of the compliance requirements.
+    pub requirements: Vec<ComplianceRequirementReport>,
+    /// Whether the transfer would be compliant.
+    pub result: bool,
+}
+
+/// The impact of a proposed set of compliance requirements on a sample of an asset's holders.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize, Debug))]
+#[derive(Encode, Decode, Clone, PartialEq, Eq)]
+pub struct RequirementsImpact {
+    /// Number of holders evaluated.
+    pub evaluated: u32,
+    /// Holders that would fail the sender conditions of every requirement.
+    pub cannot_send: Vec<IdentityId>,
+    /// Holders that would fail the receiver conditions of every requirement.
+    pub cannot_receive: Vec<IdentityId>,
+    /// The holder to continue the evaluation after, if there are holders left.
+    pub next_holder: Option<IdentityId>,
+}
+
+/// A global and unique compliance template ID.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, TypeInfo)]
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Default, Debug)]
+pub struct ComplianceTemplateId(pub u64);
+impl_checked_inc!(ComplianceTemplateId);
+
+/// The name of a compliance template.
+#[derive(Decode, Encode, TypeInfo, VecU8StrongTyped)]
+#[derive(Clone, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct ComplianceTemplateName(pub Vec<u8>);
+
+/// A named set of compliance requirements that can be applied to assets.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, TypeInfo, Default, Clone, PartialEq, Eq, Debug)]
+pub struct ComplianceTemplate {
+    /// The identity that registered the template, or the GC identity for chain-level templates.
+    pub owner: IdentityId,
+    /// The name of the template.
+    pub name: ComplianceTemplateName,
+    /// The compliance requirements of the template.
+    pub requirements: Vec<ComplianceRequirement>,
+}
+
+/// An overview of the compliance rules of an asset.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, TypeInfo, Default, Clone, PartialEq, Eq, Debug)]
+pub struct ComplianceSummary {
+    /// Whether the compliance rules are paused.
+    pub paused: bool,
+    /// Number of compliance requirements.
+    pub requirements: u32,
+    /// Number of compliance requirements that are currently disabled.
+    pub disabled_requirements: u32,
+    /// Claim issuers trusted by default for the asset.
+    pub trusted_issuers: Vec<IdentityId>,
+}
@@ -13,7 +13,7 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <http://www.gnu.org/licenses/>.
 
-use crate::{Claim, ClaimType, IdentityId};
+use crate::{Balance, Claim, ClaimType, IdentityId, Moment, Scope, Ticker};
 use codec::{Decode, Encode};
 use core::iter;
 use either::Either;
@@ -48,6 +48,28 @@ pub enum ConditionType {
     IsNoneOf(Vec<Claim>),
     /// Condition to ensure that the sender/receiver is a particular identity or an external agent.
     IsIdentity(TargetIdentity),
+    /// Condition to ensure that the sender/receiver has linked an NFT of the collection
+    /// of the given ticker to its identity, as a credential.
+    HoldsNFTCredential(Ticker),
+    /// Condition to ensure that claim filter produces one claim issued, or re-affirmed,
+    /// within the given number of days.
+    IsPresentWithin(Claim, u32),
+    /// Condition to ensure that the sender/receiver opened their position in the asset,
+    /// i.e. acquired tokens while holding none, at least the given number of milliseconds ago.
+    /// Receiving more tokens doesn't restart the period. Positions opened before acquisitions
+    /// were recorded count from when recording began.
+    MinimumHoldingPeriod(Moment),
+    /// Condition to ensure that the amount of the transfer is at most the given amount.
+    MaxTransferAmount(Balance),
+    /// Condition to ensure that the tokens transferred, sent or received, by the sender/receiver
+    /// within the given number of days, including the transfer, are at most the given amount.
+    MaxTransferVolume(Balance, u32),
+    /// Condition to ensure that the sender/receiver has a `RiskScore` claim with the given scope
+    /// and that none of its scores is above the given score.
+    RiskScoreAtMost(Scope, u8),
+    /// Condition to ensure that the sender/receiver has a `RiskScore` claim with the given scope
+    /// whose score is at least the given score.
+    RiskScoreAtLeast(Scope, u8),
 }
 
 impl ConditionType {
@@ -55,7 +77,14 @@ impl ConditionType {
     fn count(&self) -> usize {
         match self {
             ConditionType::IsIdentity(..)
+            | ConditionType::HoldsNFTCredential(..)
             | ConditionType::IsPresent(..)
+            | ConditionType::IsPresentWithin(..)
+            | ConditionType::MinimumHoldingPeriod(..)
+            | ConditionType::MaxTransferAmount(..)
+            | ConditionType::MaxTransferVolume(..)
+            | ConditionType::RiskScoreAtMost(..)
+            | ConditionType::RiskScoreAtLeast(..)
             | ConditionType::IsAbsent(..) => 1,
             ConditionType::IsNoneOf(claims) | ConditionType::IsAnyOf(claims) => claims.len(),
         }
@@ -189,11 +218,50 @@ impl Condition {
     /// Returns all the claims in the condition.
     pub fn claims(&self) -> impl Iterator<Item = &Claim> {
         match &self.condition_type {
-            ConditionType::IsPresent(c) | ConditionType::IsAbsent(c) => Either::Left(iter::once(c)),
+            ConditionType::IsPresent(c)
+            | ConditionType::IsPresentWithin(c, _)
+            | ConditionType::IsAbsent(c) => Either::Left(iter::once(c)),
             ConditionType::IsAnyOf(cs) | ConditionType::IsNoneOf(cs) => Either::Right(cs.iter()),
-            ConditionType::IsIdentity(_) => Either::Right([].iter()),
+            ConditionType::IsIdentity(_)
+            | ConditionType::HoldsNFTCredential(_)
+            | ConditionType::MinimumHoldingPeriod(_)
+            | ConditionType::MaxTransferAmount(_)
+            | ConditionType::MaxTransferVolume(..)
+            | ConditionType::RiskScoreAtMost(..)
+            | ConditionType::RiskScoreAtLeast(..) => Either::Right([].iter()),
         }
     }
+
+    /// Returns the scope of the `RiskScore` claims compared by the condition, if any.
+    pub fn risk_score_scope(&self) -> Option<&Scope> {
+        match &self.condition_type {
+            ConditionType::RiskScoreAtMost(scope, _)
+            | ConditionType::RiskScoreAtLeast(scope, _) => Some(scope),
+            _ => None,
+        }
+    }
+
+    /// Returns the scopes of the claims in the condition,
+    /// including the scope of the `RiskScore` claims it compares.
+    pub fn scopes_mut(&mut self) -> impl Iterator<Item = &mut Scope> {
+        let (claims, risk_score_scope): (&mut [Claim], _) = match &mut self.condition_type {
+            ConditionType::IsPresent(c)
+            | ConditionType::IsPresentWithin(c, _)
+            | ConditionType::IsAbsent(c) => (core::slice::from_mut(c), None),
+            ConditionType::IsAnyOf(cs) | ConditionType::IsNoneOf(cs) => (cs, None),
+            ConditionType::RiskScoreAtMost(scope, _)
+            | ConditionType::RiskScoreAtLeast(scope, _) => (&mut [], Some(scope)),
+            ConditionType::IsIdentity(_)
+            | ConditionType::HoldsNFTCredential(_)
+            | ConditionType::MinimumHoldingPeriod(_)
+            | ConditionType::MaxTransferAmount(_)
+            | ConditionType::MaxTransferVolume(..) => (&mut [], None),
+        };
+        claims
+            .iter_mut()
+            .filter_map(Claim::as_scope_mut)
+            .chain(risk_score_scope)
+    }
 }
 
 /// Return the total number of condtions, claims, issuers, and claim_types.
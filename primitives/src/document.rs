@@ -66,3 +66,15 @@ pub struct Document {
     /// Need not be when added to chain.
     pub filing_date: Option<Moment>,
 }
+
+/// The result of checking a document hash against the on-chain record of the document.
+#[derive(Decode, Encode, TypeInfo, Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum DocumentHashCheck {
+    /// The hash matches the hash of the on-chain document.
+    Matches,
+    /// The hash differs from the hash of the on-chain document.
+    Differs,
+    /// The asset has no document with the given id.
+    NotFound,
+}
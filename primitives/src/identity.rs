@@ -13,10 +13,13 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <http://www.gnu.org/licenses/>.
 
+use crate::{impl_checked_inc, DispatchableName, IdentityId, PalletName};
 use codec::{Decode, Encode};
 use scale_info::TypeInfo;
+use sp_core::H256;
 #[cfg(feature = "std")]
 use sp_runtime::{Deserialize, Serialize};
+use sp_std::vec::Vec;
 
 /// Identity record.
 ///
@@ -39,3 +42,63 @@ impl<AccountId> DidRecord<AccountId> {
         }
     }
 }
+
+/// A call, or all the calls of a pallet, that an identity has restricted itself from making.
+#[derive(Encode, Decode, TypeInfo)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct CallFilter {
+    /// The pallet of the filtered calls.
+    pub pallet_name: PalletName,
+    /// The filtered call, or `None` to filter all the calls of the pallet.
+    pub dispatchable_name: Option<DispatchableName>,
+}
+
+/// An agreement id, unique among the agreements published by an identity.
+#[derive(Encode, Decode, TypeInfo)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct AgreementId(pub u64);
+impl_checked_inc!(AgreementId);
+
+/// The current version of an agreement published by an issuer or a provider.
+#[derive(Encode, Decode, TypeInfo)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Agreement {
+    /// The hash of the agreement's document.
+    pub hash: H256,
+    /// The version of the agreement, starting at `1` and increased by every update.
+    pub version: u32,
+}
+
+/// The consent of an identity to a version of an agreement.
+#[derive(Encode, Decode, TypeInfo)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct AgreementConsent<Moment> {
+    /// The hash of the agreement's document the identity consented to.
+    pub hash: H256,
+    /// The version of the agreement the identity consented to.
+    pub version: u32,
+    /// When the consent was given.
+    pub given_at: Moment,
+}
+
+impl<Moment> AgreementConsent<Moment> {
+    /// Returns `true` if the consent was given to the current version of `agreement`.
+    pub fn covers(&self, agreement: &Agreement) -> bool {
+        self.version == agreement.version && self.hash == agreement.hash
+    }
+}
+
+/// The identities that can jointly recover the primary key of an identity.
+#[derive(Encode, Decode, TypeInfo)]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct RecoveryGuardians {
+    /// The guardian identities.
+    pub guardians: Vec<IdentityId>,
+    /// The number of guardians that must approve a recovery.
+    pub threshold: u32,
+}
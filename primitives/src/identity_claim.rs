@@ -33,6 +33,87 @@ pub type ScopeId = IdentityId;
 pub struct CustomClaimTypeId(pub u32);
 impl_checked_inc!(CustomClaimTypeId);
 
+/// Maximum number of fields of a `CustomClaimSchema`.
+pub const MAX_CUSTOM_CLAIM_SCHEMA_FIELDS: usize = 32;
+
+/// The type of a field of a custom claim payload.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, TypeInfo)]
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+pub enum CustomClaimFieldType {
+    /// A `bool`.
+    Bool,
+    /// A `u32`.
+    U32,
+    /// A `u64`.
+    U64,
+    /// A `u128`.
+    U128,
+    /// A UTF-8 string of at most the given number of bytes.
+    Text(u32),
+    /// Raw bytes of at most the given length.
+    Bytes(u32),
+}
+
+impl CustomClaimFieldType {
+    /// Decodes a value of this type from `input`, returning `true` if it is valid.
+    fn decode_value(&self, input: &mut &[u8]) -> bool {
+        let bytes_limited = |input: &mut &[u8], max_len: u32| {
+            Vec::<u8>::decode(input)
+                .ok()
+                .filter(|bytes| bytes.len() <= max_len as usize)
+        };
+        match self {
+            Self::Bool => bool::decode(input).is_ok(),
+            Self::U32 => u32::decode(input).is_ok(),
+            Self::U64 => u64::decode(input).is_ok(),
+            Self::U128 => u128::decode(input).is_ok(),
+            Self::Text(max_len) => bytes_limited(input, *max_len)
+                .map_or(false, |text| core::str::from_utf8(&text).is_ok()),
+            Self::Bytes(max_len) => bytes_limited(input, *max_len).is_some(),
+        }
+    }
+
+    /// Returns the maximum length of the values of this type, if they have a variable length.
+    pub fn max_len(&self) -> Option<u32> {
+        match self {
+            Self::Text(max_len) | Self::Bytes(max_len) => Some(*max_len),
+            Self::Bool | Self::U32 | Self::U64 | Self::U128 => None,
+        }
+    }
+}
+
+/// A named field of a custom claim payload.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, TypeInfo)]
+#[derive(Clone, PartialEq, Eq, Debug, Hash)]
+pub struct CustomClaimField {
+    /// The name of the field.
+    pub name: Vec<u8>,
+    /// The type of the field.
+    pub field_type: CustomClaimFieldType,
+}
+
+/// The fields of the payloads of a custom claim type.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, TypeInfo)]
+#[derive(Clone, Default, PartialEq, Eq, Debug, Hash)]
+pub struct CustomClaimSchema {
+    /// The fields of the payloads, in order.
+    pub fields: Vec<CustomClaimField>,
+}
+
+impl CustomClaimSchema {
+    /// Returns `true` if `payload` is the SCALE encoding of valid values of the fields, in order.
+    pub fn accepts(&self, mut payload: &[u8]) -> bool {
+        let input = &mut payload;
+        self.fields
+            .iter()
+            .all(|field| field.field_type.decode_value(input))
+            && input.is_empty()
+    }
+}
+
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 #[derive(Encode, Decode, TypeInfo)]
 #[derive(Clone, PartialEq, Eq, Debug, PartialOrd, Ord, Hash)]
@@ -75,6 +156,28 @@ impl Scope {
     }
 }
 
+/// Highest (riskiest) value of a `RiskScore`.
+pub const MAX_RISK_SCORE: u8 = 100;
+
+/// A risk score assessed by a scoring provider, the issuer of the claim holding it.
+/// The expiry of the score is the expiry of that claim.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, TypeInfo)]
+#[derive(Copy, Default, Clone, PartialEq, Eq, Debug, PartialOrd, Ord, Hash)]
+pub struct RiskScore {
+    /// Score from `0` (lowest risk) to `MAX_RISK_SCORE` (highest risk).
+    pub score: u8,
+    /// Version of the provider's scoring model which produced the score.
+    pub model_version: u32,
+}
+
+impl RiskScore {
+    /// Returns `true` if the score is within `0..=MAX_RISK_SCORE`.
+    pub fn is_valid(&self) -> bool {
+        self.score <= MAX_RISK_SCORE
+    }
+}
+
 /// All possible claims in polymesh
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 #[derive(Encode, Decode, TypeInfo, Clone, PartialEq, Eq, Debug, Hash)]
@@ -114,6 +217,10 @@ pub enum Claim {
     InvestorUniquenessV2(CddId),
     /// Custom claim with an optional scope.
     Custom(CustomClaimTypeId, Option<Scope>),
+    /// Risk score of the user, assessed by the claim issuer.
+    RiskScore(RiskScore, Scope),
+    /// Custom claim with an optional scope and a payload that matches the schema of its type.
+    CustomWithPayload(CustomClaimTypeId, Option<Scope>, Vec<u8>),
 }
 
 impl Default for Claim {
@@ -137,7 +244,10 @@ impl Claim {
             Claim::Blocked(..) => ClaimType::Blocked,
             Claim::InvestorUniqueness(..) => ClaimType::InvestorUniqueness,
             Claim::InvestorUniquenessV2(..) => ClaimType::InvestorUniquenessV2,
-            Claim::Custom(cc_id, _) => ClaimType::Custom(*cc_id),
+            Claim::Custom(cc_id, _) | Claim::CustomWithPayload(cc_id, ..) => {
+                ClaimType::Custom(*cc_id)
+            }
+            Claim::RiskScore(..) => ClaimType::RiskScore,
             Claim::NoData => ClaimType::NoType,
         }
     }
@@ -153,8 +263,29 @@ impl Claim {
             | Claim::Jurisdiction(.., scope)
             | Claim::Exempted(scope)
             | Claim::Blocked(scope)
-            | Claim::InvestorUniqueness(scope, ..) => Some(scope),
-            Claim::Custom(_, scope) => scope.as_ref(),
+            | Claim::InvestorUniqueness(scope, ..)
+            | Claim::RiskScore(.., scope) => Some(scope),
+            Claim::Custom(_, scope) | Claim::CustomWithPayload(_, scope, _) => scope.as_ref(),
+            Claim::CustomerDueDiligence(..) | Claim::InvestorUniquenessV2(..) | Claim::NoData => {
+                None
+            }
+        }
+    }
+
+    /// The mutable scope of this claim.
+    pub fn as_scope_mut(&mut self) -> Option<&mut Scope> {
+        match self {
+            Claim::Accredited(scope)
+            | Claim::Affiliate(scope)
+            | Claim::BuyLockup(scope)
+            | Claim::SellLockup(scope)
+            | Claim::KnowYourCustomer(scope)
+            | Claim::Jurisdiction(.., scope)
+            | Claim::Exempted(scope)
+            | Claim::Blocked(scope)
+            | Claim::InvestorUniqueness(scope, ..)
+            | Claim::RiskScore(.., scope) => Some(scope),
+            Claim::Custom(_, scope) | Claim::CustomWithPayload(_, scope, _) => scope.as_mut(),
             Claim::CustomerDueDiligence(..) | Claim::InvestorUniquenessV2(..) | Claim::NoData => {
                 None
             }
@@ -198,6 +329,8 @@ pub enum ClaimType {
     InvestorUniquenessV2,
     /// Custom claim referenced by Id.
     Custom(CustomClaimTypeId),
+    /// Risk score assessed by a scoring provider.
+    RiskScore,
 }
 
 impl Default for ClaimType {
@@ -220,6 +220,12 @@ impl Printable for IdentityId {
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct PortfolioName(pub Vec<u8>);
 
+/// A label of a sub-balance within a portfolio, e.g. "margin" or "available".
+#[derive(Decode, Encode, TypeInfo, VecU8StrongTyped)]
+#[derive(Clone, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct PortfolioBalanceLabel(pub Vec<u8>);
+
 /// The unique ID of a non-default portfolio.
 #[derive(Encode, Decode, TypeInfo)]
 #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
@@ -125,13 +125,16 @@ pub use event_only::EventOnly;
 /// Polymesh Distributed Identity.
 pub mod identity_id;
 pub use identity_id::{
-    EventDid, IdentityId, PortfolioId, PortfolioKind, PortfolioName, PortfolioNumber,
+    EventDid, IdentityId, PortfolioBalanceLabel, PortfolioId, PortfolioKind, PortfolioName,
+    PortfolioNumber,
 };
 
 /// Identity information.
 /// Each DID is associated with this kind of record.
 pub mod identity;
-pub use identity::DidRecord;
+pub use identity::{
+    Agreement, AgreementConsent, AgreementId, CallFilter, DidRecord, RecoveryGuardians,
+};
 
 /// Provides the `CheckedInc` trait.
 pub mod checked_inc;
@@ -149,7 +152,10 @@ pub use investor_zkproof_data::InvestorZKProofData;
 /// Claim information.
 /// Each claim is associated with this kind of record.
 pub mod identity_claim;
-pub use identity_claim::{Claim, ClaimType, CustomClaimTypeId, IdentityClaim, Scope, ScopeId};
+pub use identity_claim::{
+    Claim, ClaimType, CustomClaimField, CustomClaimFieldType, CustomClaimSchema, CustomClaimTypeId,
+    IdentityClaim, RiskScore, Scope, ScopeId,
+};
 
 // Defining and enumerating jurisdictions.
 pub mod jurisdiction;
@@ -185,7 +191,7 @@ pub use document_hash::DocumentHash;
 
 /// Document types
 pub mod document;
-pub use document::{Document, DocumentId, DocumentName, DocumentUri};
+pub use document::{Document, DocumentHashCheck, DocumentId, DocumentName, DocumentUri};
 
 /// Rules for claims.
 pub mod condition;
@@ -229,11 +235,22 @@ pub mod ethereum;
 
 /// NFT type definitions.
 pub mod nft;
-pub use nft::{NFTCollectionId, NFTCollectionKeys, NFTId, NFTMetadataAttribute, NFTs};
+pub use nft::{
+    NFTCollectionId, NFTCollectionKeys, NFTId, NFTLease, NFTLeaseId, NFTMetadataAttribute,
+    NFTMetadataKeySet, NFTMetadataKeySetId, NFTMetadataKeySpec, NFTs,
+};
 
 /// Portfolio type definitions.
 pub mod portfolio;
-pub use portfolio::{Fund, FundDescription, Memo};
+pub use portfolio::{Fund, FundDescription, Memo, MAX_MEMO_LEN};
+
+/// Chain-state health type definitions.
+pub mod state_health;
+pub use state_health::StateCounters;
+
+/// Batched import type definitions.
+pub mod batch_import;
+pub use batch_import::BatchImport;
 
 /// Represents custom transaction errors.
 #[repr(u8)]
@@ -7,8 +7,10 @@ use sp_std::collections::btree_set::BTreeSet;
 use sp_std::vec::IntoIter;
 use sp_std::vec::Vec;
 
-use crate::asset_metadata::{AssetMetadataKey, AssetMetadataValue};
-use crate::{impl_checked_inc, Ticker};
+use crate::asset_metadata::{
+    AssetMetadataKey, AssetMetadataName, AssetMetadataSpec, AssetMetadataValue,
+};
+use crate::{impl_checked_inc, IdentityId, PortfolioId, Ticker};
 
 /// Controls the total number of NFTs per identity.
 pub type NFTCount = u64;
@@ -124,3 +126,49 @@ pub struct NFTMetadataAttribute {
     /// The metadata value.
     pub value: AssetMetadataValue,
 }
+
+/// Controls the next available id for an NFT metadata key-set.
+#[derive(Clone, Copy, Debug, Decode, Default, Encode, Eq, PartialEq, TypeInfo)]
+pub struct NFTMetadataKeySetId(pub u64);
+impl_checked_inc!(NFTMetadataKeySetId);
+
+/// Defines a metadata key of a key-set, which is composed of a name and its specs.
+#[derive(Clone, Debug, Decode, Default, Encode, Eq, PartialEq, TypeInfo)]
+pub struct NFTMetadataKeySpec {
+    /// The name of the metadata key.
+    pub name: AssetMetadataName,
+    /// The specs of the metadata key.
+    pub spec: AssetMetadataSpec,
+}
+
+/// A reusable set of metadata keys that the NFT collections of its owner can reference.
+#[derive(Clone, Debug, Decode, Default, Encode, Eq, PartialEq, TypeInfo)]
+pub struct NFTMetadataKeySet {
+    /// The identity that registered the key-set.
+    pub owner: IdentityId,
+    /// The metadata keys of the key-set.
+    pub keys: Vec<NFTMetadataKeySpec>,
+}
+
+/// Controls the next available id for an NFT lease.
+#[derive(
+    Clone, Copy, Debug, Decode, Default, Encode, Eq, Ord, PartialOrd, PartialEq, TypeInfo
+)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct NFTLeaseId(pub u64);
+impl_checked_inc!(NFTLeaseId);
+
+/// An NFT held by a lessee for a limited time, and returned to the lessor at the end of the lease.
+#[derive(Clone, Debug, Decode, Encode, Eq, PartialEq, TypeInfo)]
+pub struct NFTLease<BlockNumber> {
+    /// The portfolio the NFT is returned to.
+    pub lessor: PortfolioId,
+    /// The portfolio holding the NFT during the lease.
+    pub lessee: PortfolioId,
+    /// The ticker of the NFT collection.
+    pub ticker: Ticker,
+    /// The id of the leased NFT.
+    pub nft_id: NFTId,
+    /// Block in which the NFT is returned to the lessor.
+    pub end: BlockNumber,
+}
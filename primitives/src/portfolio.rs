@@ -15,6 +15,7 @@
 
 use codec::{Decode, Encode};
 use scale_info::TypeInfo;
+use sp_std::vec::Vec;
 
 use crate::{Balance, NFTs, Ticker};
 
@@ -41,6 +42,37 @@ pub enum FundDescription {
     NonFungible(NFTs),
 }
 
-/// A memo describing the transfer.
-#[derive(Clone, Debug, Decode, Encode, Eq, PartialEq, TypeInfo)]
-pub struct Memo(pub [u8; 32]);
+/// The maximum length, in bytes, of a [`Memo`].
+pub const MAX_MEMO_LEN: usize = 2048;
+
+/// A memo describing the transfer, of at most [`MAX_MEMO_LEN`] bytes.
+///
+/// It is shared by portfolio moves and settlement instructions, so the same reference
+/// (e.g. ISIN, trade and counterparty references) can follow the assets through both.
+#[derive(
+    Clone, Debug, Decode, Default, Encode, Eq, PartialEq, PartialOrd, Ord, TypeInfo
+)]
+pub struct Memo(pub Vec<u8>);
+
+impl Memo {
+    /// Returns the length, in bytes, of the memo.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if the memo is empty.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns `true` if the memo is not longer than [`MAX_MEMO_LEN`].
+    pub fn is_valid_len(&self) -> bool {
+        self.len() <= MAX_MEMO_LEN
+    }
+}
+
+impl From<[u8; 32]> for Memo {
+    fn from(memo: [u8; 32]) -> Self {
+        Self(memo.to_vec())
+    }
+}
@@ -167,8 +167,8 @@ impl<C: Iterator<Item = Claim>> Proposition<C> for AnyProposition<'_> {
 mod tests {
     use crate::{
         proposition::{self, Context, Proposition},
-        CddId, Claim, Condition, ConditionType, CountryCode, IdentityId, InvestorUid, Scope,
-        TargetIdentity,
+        CddId, Claim, Condition, ConditionType, CountryCode, IdentityId, InvestorUid, RiskScore,
+        Scope, TargetIdentity,
     };
     use std::convert::From;
     use std::vec::IntoIter;
@@ -249,9 +249,16 @@ mod tests {
         ];
 
         let check = |expected, context: &Context<Iter>| {
-            let out = !conditions
-                .iter()
-                .any(|condition| !proposition::run(&condition, context.clone(), |_| false));
+            let out = !conditions.iter().any(|condition| {
+                !proposition::run(
+                    &condition,
+                    context.clone(),
+                    |_| false,
+                    |_, _| false,
+                    |_, _| false,
+                    |_, _, _| false,
+                )
+            });
             assert_eq!(out, expected);
         };
 
@@ -300,6 +307,9 @@ mod tests {
                 claims: vec![].into_iter(),
             },
             |context: Context<_>| context.id == identity1,
+            |_, _| false,
+            |_, _| false,
+            |_, _, _| false,
         ));
         assert!(proposition::run(
             &ConditionType::IsIdentity(TargetIdentity::Specific(identity1)).into(),
@@ -308,6 +318,93 @@ mod tests {
                 claims: vec![].into_iter(),
             },
             |_| false,
+            |_, _| false,
+            |_, _| false,
+            |_, _, _| false,
+        ));
+
+        let ticker = Ticker::from_slice_truncated(b"PASS".as_ref());
+        assert!(proposition::run(
+            &ConditionType::HoldsNFTCredential(ticker).into(),
+            Context {
+                id: identity1,
+                claims: vec![].into_iter(),
+            },
+            |_| false,
+            |t: &Ticker, context: Context<_>| *t == ticker && context.id == identity1,
+            |_, _| false,
+            |_, _, _| false,
+        ));
+        assert!(proposition::run(
+            &ConditionType::MinimumHoldingPeriod(1_000).into(),
+            Context {
+                id: identity1,
+                claims: vec![].into_iter(),
+            },
+            |_| false,
+            |_, _| false,
+            |period, context: Context<_>| period == 1_000 && context.id == identity1,
+            |_, _, _| false,
+        ));
+        assert!(proposition::run(
+            &ConditionType::MaxTransferVolume(100, 30).into(),
+            Context {
+                id: identity1,
+                claims: vec![].into_iter(),
+            },
+            |_| false,
+            |_, _| false,
+            |_, _| false,
+            |max, days, context: Context<_>| {
+                max == 100 && days == Some(30) && context.id == identity1
+            },
+        ));
+
+        let risk_score = |score| {
+            Claim::RiskScore(
+                RiskScore {
+                    score,
+                    model_version: 1,
+                },
+                scope.clone(),
+            )
+        };
+        let check_risk = |condition: ConditionType, scores: &[u8]| {
+            proposition::run(
+                &condition.into(),
+                Context {
+                    id: identity1,
+                    claims: scores
+                        .iter()
+                        .map(|s| risk_score(*s))
+                        .collect::<Vec<_>>()
+                        .into_iter(),
+                },
+                |_| false,
+                |_, _| false,
+                |_, _| false,
+                |_, _, _| false,
+            )
+        };
+        assert!(check_risk(
+            ConditionType::RiskScoreAtMost(scope.clone(), 50),
+            &[10, 50]
+        ));
+        assert!(!check_risk(
+            ConditionType::RiskScoreAtMost(scope.clone(), 50),
+            &[10, 51]
+        ));
+        assert!(!check_risk(
+            ConditionType::RiskScoreAtMost(scope.clone(), 50),
+            &[]
+        ));
+        assert!(check_risk(
+            ConditionType::RiskScoreAtLeast(scope.clone(), 50),
+            &[10, 50]
+        ));
+        assert!(!check_risk(
+            ConditionType::RiskScoreAtLeast(scope.clone(), 50),
+            &[10, 49]
         ));
     }
 }
@@ -13,7 +13,7 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <http://www.gnu.org/licenses/>.
 
-use crate::{Claim, Condition, ConditionType, IdentityId, TargetIdentity};
+use crate::{Balance, Claim, Condition, ConditionType, IdentityId, Moment, TargetIdentity, Ticker};
 use codec::{Decode, Encode};
 
 use sp_std::prelude::*;
@@ -104,13 +104,32 @@ pub fn not<P: Proposition<C>, C>(proposition: P) -> NotProposition<P> {
 }
 
 /// Helper function to run propositions from a context.
-pub fn run<C: Iterator<Item = Claim>, E: Proposition<C>>(
+///
+/// `ea_prop` resolves whether the target is an external agent of the asset,
+/// `nft_credential_prop` whether it has linked an NFT of a collection as a credential,
+/// `holding_period_prop` whether it has held its tokens for at least a period,
+/// and `transfer_limit_prop` whether the transfer, or with `Some(days)` all of its transfers
+/// within that number of days, amount to at most a maximum.
+pub fn run<C, E, N, H, L>(
     condition: &Condition,
     context: Context<C>,
     ea_prop: E,
-) -> bool {
+    nft_credential_prop: N,
+    holding_period_prop: H,
+    transfer_limit_prop: L,
+) -> bool
+where
+    C: Iterator<Item = Claim>,
+    E: Proposition<C>,
+    N: Fn(&Ticker, Context<C>) -> bool,
+    H: Fn(Moment, Context<C>) -> bool,
+    L: Fn(Balance, Option<u32>, Context<C>) -> bool,
+{
     match &condition.condition_type {
-        ConditionType::IsPresent(claim) => exists(claim).evaluate(context),
+        // Claims that aren't fresh enough are filtered out of the context.
+        ConditionType::IsPresent(claim) | ConditionType::IsPresentWithin(claim, _) => {
+            exists(claim).evaluate(context)
+        }
         ConditionType::IsAbsent(claim) => not::<_, C>(exists(claim)).evaluate(context),
         ConditionType::IsAnyOf(claims) => any(claims).evaluate(context),
         ConditionType::IsNoneOf(claims) => not::<_, C>(any(claims)).evaluate(context),
@@ -118,5 +137,25 @@ pub fn run<C: Iterator<Item = Claim>, E: Proposition<C>>(
             IsIdentityProposition { identity: *id }.evaluate(context)
         }
         ConditionType::IsIdentity(TargetIdentity::ExternalAgent) => ea_prop.evaluate(context),
+        ConditionType::HoldsNFTCredential(ticker) => nft_credential_prop(ticker, context),
+        ConditionType::MinimumHoldingPeriod(period) => holding_period_prop(*period, context),
+        ConditionType::MaxTransferAmount(max) => transfer_limit_prop(*max, None, context),
+        ConditionType::MaxTransferVolume(max, days) => {
+            transfer_limit_prop(*max, Some(*days), context)
+        }
+        // The context holds the `RiskScore` claims of the condition's scope.
+        ConditionType::RiskScoreAtMost(_, max) => {
+            let mut scores = risk_scores(context).peekable();
+            scores.peek().is_some() && scores.all(|score| score <= *max)
+        }
+        ConditionType::RiskScoreAtLeast(_, min) => risk_scores(context).any(|score| score >= *min),
     }
 }
+
+/// Returns the scores of the `RiskScore` claims in `context`.
+fn risk_scores<C: Iterator<Item = Claim>>(context: Context<C>) -> impl Iterator<Item = u8> {
+    context.claims.filter_map(|claim| match claim {
+        Claim::RiskScore(risk_score, _) => Some(risk_score.score),
+        _ => None,
+    })
+}
@@ -0,0 +1,32 @@
+// This file is part of the Polymesh distribution (https://github.com/PolymeshAssociation/Polymesh).
+// Copyright (c) 2020 Polymath
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, version 3.
+
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+// General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+use codec::{Decode, Encode};
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use sp_runtime::{Deserialize, Serialize};
+
+/// Counters of the chain-state entries that grow with usage.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Decode, Encode, TypeInfo)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct StateCounters {
+    /// Number of claims stored in the identity pallet.
+    pub claims: u64,
+    /// Number of pending authorizations.
+    pub authorizations: u64,
+    /// Number of settlement instructions that haven't been executed, rejected or pruned yet.
+    pub open_instructions: u64,
+}
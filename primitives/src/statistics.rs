@@ -13,7 +13,7 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <http://www.gnu.org/licenses/>.
 
-use crate::{Claim, ClaimType, CountryCode, IdentityId, Scope, Ticker};
+use crate::{Balance, Claim, ClaimType, CountryCode, IdentityId, Scope, Ticker};
 use codec::{Decode, Encode};
 use scale_info::TypeInfo;
 #[cfg(feature = "std")]
@@ -227,6 +227,28 @@ pub struct StatUpdate {
     pub value: Option<u128>,
 }
 
+/// Index of a transfer volume stats era.
+pub type TransferVolumeEra = u32;
+
+/// Aggregated transfers of an asset during an era.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, TypeInfo)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct TransferVolume {
+    /// Number of transfers.
+    pub count: u64,
+    /// Total amount transferred.
+    pub volume: Balance,
+}
+
+impl TransferVolume {
+    /// Adds a transfer of `amount` to the aggregate.
+    pub fn add_transfer(&mut self, amount: Balance) {
+        self.count = self.count.saturating_add(1);
+        self.volume = self.volume.saturating_add(amount);
+    }
+}
+
 /// Older v1 Transfer Managers.
 pub mod v1 {
     use super::*;
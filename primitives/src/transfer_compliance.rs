@@ -110,6 +110,7 @@ impl From<v1::TransferManagerResult> for TransferConditionResult {
 }
 
 /// Transfer Condition Exempt key.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 #[derive(Decode, Encode, TypeInfo)]
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub struct TransferConditionExemptKey {
@@ -16,19 +16,32 @@
 //! Runtime API definition for Asset module.
 
 use codec::Codec;
-use polymesh_primitives::{Balance, IdentityId, PortfolioId, Ticker};
+use polymesh_primitives::{
+    asset::{AssetDescription, AssetHolders, AuthorizedCapital},
+    calendar::CheckpointId,
+    Balance, DocumentHash, DocumentHashCheck, DocumentId, IdentityId, Moment, PortfolioId, Ticker,
+};
 use sp_std::vec::Vec;
 
 /// The maximum number of DIDs allowed in a `balance_at` RPC query.
 pub const MAX_BALANCE_AT_QUERY_SIZE: usize = 100;
 
+/// The maximum number of documents allowed in a `verify_document_hashes` RPC query.
+pub const MAX_DOCUMENT_HASHES_QUERY_SIZE: usize = 1000;
+
+/// The maximum number of holders returned by a `get_holders` RPC query.
+pub const MAX_HOLDERS_QUERY_SIZE: u32 = 1000;
+
+/// The maximum number of acknowledgements returned by a `document_acknowledgements` RPC query.
+pub const MAX_ACKNOWLEDGEMENTS_QUERY_SIZE: u32 = 1000;
+
 pub type Error = Vec<u8>;
 pub type CanTransferResult = Result<u8, Error>;
 
 sp_api::decl_runtime_apis! {
 
     /// The API to interact with Asset.
-    #[api_version(2)]
+    #[api_version(7)]
     pub trait AssetApi<AccountId>
     where
         AccountId: Codec,
@@ -77,6 +90,72 @@ sp_api::decl_runtime_apis! {
             value: Balance
         ) -> polymesh_primitives::asset::GranularCanTransferResult;
 
+        /// Returns the authorized supply of an asset, if any, and its issued supply.
+        #[api_version(3)]
+        fn authorized_capital(ticker: Ticker) -> AuthorizedCapital;
+
+        /// Checks each document hash against the hash of the document of `ticker` with the same id.
+        /// The results are in the same order as `documents`.
+        #[api_version(4)]
+        fn verify_document_hashes(
+            ticker: Ticker,
+            documents: Vec<(DocumentId, DocumentHash)>
+        ) -> Vec<DocumentHashCheck>;
+
+        /// Returns the descriptive metadata of an asset (type, identifiers, documents, metadata values
+        /// and compliance overview), or `None` if the asset doesn't exist.
+        ///
+        /// ```ignore
+        /// curl http://localhost:9933 -H "Content-Type: application/json" -d '{
+        ///     "id":1,
+        ///     "jsonrpc":"2.0",
+        ///     "method": "asset_describeAsset",
+        ///     "params": ["0x414243000000000000000000"]
+        ///   }'
+        /// ```
+        #[api_version(5)]
+        fn describe_asset(ticker: Ticker) -> Option<AssetDescription>;
+
+        /// Returns at most `limit` holders of an asset after `start_after`, in increasing order of DID,
+        /// with their balances at `checkpoint`, or at the current block if `checkpoint` is `None`.
+        /// Returns `None` if `checkpoint` doesn't exist.
+        ///
+        /// ```ignore
+        /// curl http://localhost:9933 -H "Content-Type: application/json" -d '{
+        ///     "id":1,
+        ///     "jsonrpc":"2.0",
+        ///     "method": "asset_getHolders",
+        ///     "params": ["0x414243000000000000000000", null, null, 100]
+        ///   }'
+        /// ```
+        #[api_version(6)]
+        fn get_holders(
+            ticker: Ticker,
+            checkpoint: Option<CheckpointId>,
+            start_after: Option<IdentityId>,
+            limit: u32
+        ) -> Option<AssetHolders>;
+
+        /// Returns at most `limit` identities, after `start_after` in increasing order of DID,
+        /// that acknowledged the `version` of the document `id` of `ticker`, with the time of their acknowledgement.
+        ///
+        /// ```ignore
+        /// curl http://localhost:9933 -H "Content-Type: application/json" -d '{
+        ///     "id":1,
+        ///     "jsonrpc":"2.0",
+        ///     "method": "asset_documentAcknowledgements",
+        ///     "params": ["0x414243000000000000000000", 0, 1, null, 100]
+        ///   }'
+        /// ```
+        #[api_version(7)]
+        fn document_acknowledgements(
+            ticker: Ticker,
+            id: DocumentId,
+            version: u32,
+            start_after: Option<IdentityId>,
+            limit: u32
+        ) -> Vec<(IdentityId, Moment)>;
+
         /// Checks whether a transaction with given parameters can take place or not.
         /// The result is "granular" meaning each check is run and returned regardless of outcome.
         ///
@@ -0,0 +1,53 @@
+// This file is part of the Polymesh distribution (https://github.com/PolymeshAssociation/Polymesh).
+// Copyright (c) 2020 Polymath
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, version 3.
+
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+// General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+//! Runtime API definition for Bridge module.
+
+use codec::Codec;
+use pallet_bridge::{Withdrawal, WithdrawalId};
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+    /// The API to query the bridge outbound withdrawal queue.
+    pub trait BridgeApi<AccountId, BlockNumber>
+    where
+        AccountId: Codec,
+        BlockNumber: Codec,
+    {
+        /// Returns all withdrawals that are queued or halted.
+        ///
+        /// ```ignore
+        /// curl http://localhost:9933 -H "Content-Type: application/json" -d '{
+        ///     "id":1,
+        ///     "jsonrpc":"2.0",
+        ///     "method": "bridge_getWithdrawalQueue",
+        ///     "params":[]
+        ///   }'
+        /// ```
+        fn get_withdrawal_queue() -> Vec<(WithdrawalId, Withdrawal<AccountId, BlockNumber>)>;
+
+        /// Returns the withdrawal identified by `id`, if any.
+        ///
+        /// ```ignore
+        /// curl http://localhost:9933 -H "Content-Type: application/json" -d '{
+        ///     "id":1,
+        ///     "jsonrpc":"2.0",
+        ///     "method": "bridge_getWithdrawal",
+        ///     "params":[1]
+        ///   }'
+        /// ```
+        fn get_withdrawal(id: WithdrawalId) -> Option<Withdrawal<AccountId, BlockNumber>>;
+    }
+}
@@ -17,7 +17,13 @@
 
 use codec::Codec;
 use frame_support::traits::Currency;
-use polymesh_primitives::{compliance_manager::AssetComplianceResult, IdentityId, Ticker};
+use polymesh_primitives::{
+    compliance_manager::{
+        AssetComplianceReport, AssetComplianceResult, ComplianceRequirement, RequirementsImpact,
+    },
+    Balance, IdentityId, Ticker,
+};
+use sp_std::vec::Vec;
 
 pub trait Trait: frame_system::Config {
     type Currency: Currency<Self::AccountId>;
@@ -26,6 +32,7 @@ pub trait Trait: frame_system::Config {
 sp_api::decl_runtime_apis! {
 
     /// The API to interact with Compliance manager.
+    #[api_version(3)]
     pub trait ComplianceManagerApi<AccountId>
     where
         AccountId: Codec,
@@ -56,5 +63,57 @@ sp_api::decl_runtime_apis! {
             from_did: Option<IdentityId>,
             to_did: Option<IdentityId>,
         ) -> AssetComplianceResult;
+
+         /// Evaluates the compliance rules for a transfer of `amount`, reporting which requirements
+         /// and conditions pass, and for failing ones the claims lacked along with the issuers trusted for them.
+         ///
+         /// ```ignore
+         ///  curl
+         ///    -H "Content-Type: application/json"
+         ///    -d {
+         ///        "id":1, "jsonrpc":"2.0",
+         ///        "method": "compliance_complianceReport",
+         ///        "params":[
+         ///            "0x010000000000000000000000",
+         ///            "0x2a00000000000000000000000000000000000000000000000000000000000000",
+         ///            "0x3905000000000000000000000000000000000000000000000000000000000000",
+         ///            500
+         ///            ]
+         ///       }
+         ///    http://localhost:9933 | python3 -m json.tool
+         /// ```
+        #[api_version(2)]
+        fn compliance_report(
+            ticker: Ticker,
+            from_did: Option<IdentityId>,
+            to_did: Option<IdentityId>,
+            amount: Balance,
+        ) -> AssetComplianceReport;
+
+         /// Evaluates the proposed `requirements` against up to `limit` holders of `ticker`,
+         /// starting after `start_after`, reporting those that could no longer send or receive it.
+         ///
+         /// ```ignore
+         ///  curl
+         ///    -H "Content-Type: application/json"
+         ///    -d {
+         ///        "id":1, "jsonrpc":"2.0",
+         ///        "method": "compliance_simulateRequirements",
+         ///        "params":[
+         ///            "0x010000000000000000000000",
+         ///            [{"sender_conditions": [], "receiver_conditions": [], "id": 1}],
+         ///            null,
+         ///            100
+         ///            ]
+         ///       }
+         ///    http://localhost:9933 | python3 -m json.tool
+         /// ```
+        #[api_version(3)]
+        fn simulate_requirements(
+            ticker: Ticker,
+            requirements: Vec<ComplianceRequirement>,
+            start_after: Option<IdentityId>,
+            limit: u32,
+        ) -> RequirementsImpact;
     }
 }
@@ -1,13 +1,17 @@
 use codec::Codec;
 use pallet_identity::types::{
-    AssetDidResult, CddStatus, DidStatus, KeyIdentityData, RpcDidRecords,
+    AccountOverview, AssetDidResult, CddStatus, DidStatus, DidStatusDetails, KeyIdentityData,
+    KeyPermissionsData, RpcDidRecords,
+};
+use polymesh_primitives::{
+    AgreementId, Authorization, AuthorizationType, DispatchableName, IdentityClaim, PalletName,
+    Signatory,
 };
-use polymesh_primitives::{Authorization, AuthorizationType, Signatory};
 use sp_std::prelude::*;
 
 sp_api::decl_runtime_apis! {
     /// Identity runtime API.
-    #[api_version(2)]
+    #[api_version(7)]
     pub trait IdentityApi<IdentityId, Ticker, AccountId, Moment> where
         IdentityId: Codec,
         Ticker: Codec,
@@ -47,5 +51,39 @@ sp_api::decl_runtime_apis! {
         ///
         /// This is an aggregate call provided for UX convenience.
         fn get_key_identity_data(acc: AccountId) -> Option<KeyIdentityData<IdentityId>>;
+
+        /// Provide the `AccountOverview` of a given `AccountId`, including:
+        /// - the corresponding DID, key type and permissions,
+        /// - the components of its POLYX balance,
+        /// - its subsidy, if any.
+        ///
+        /// This is an aggregate call provided for UX convenience.
+        #[api_version(3)]
+        fn get_account_overview(acc: AccountId) -> AccountOverview<IdentityId, AccountId>;
+
+        /// Returns the latest non-expired risk score claim of `did` issued by each scoring provider.
+        #[api_version(4)]
+        fn get_risk_scores(did: IdentityId) -> Vec<IdentityClaim>;
+
+        /// Provide the `KeyPermissionsData` of a given `AccountId`, including:
+        /// - the corresponding DID and the permissions of the key,
+        /// - whether the key is frozen or expired,
+        /// - the call filters of the DID.
+        #[api_version(5)]
+        fn get_key_permissions(acc: AccountId) -> Option<KeyPermissionsData<IdentityId>>;
+
+        /// Returns `true` if `acc` is currently allowed to call `extrinsic` of `pallet`.
+        #[api_version(5)]
+        fn can_call(acc: AccountId, pallet: PalletName, extrinsic: DispatchableName) -> bool;
+
+        /// Retrieve the status of the DIDs, with the issuer, issuance and expiry dates
+        /// of their valid CDD claim expiring last.
+        #[api_version(6)]
+        fn get_did_status_details(dids: Vec<IdentityId>) -> Vec<DidStatusDetails>;
+
+        /// Returns `true` if `did` has consented to the current version of the agreement `id`
+        /// published by `publisher`.
+        #[api_version(7)]
+        fn has_consented(did: IdentityId, publisher: IdentityId, id: AgreementId) -> bool;
     }
 }
@@ -17,8 +17,12 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 pub mod asset;
+pub mod bridge;
 pub mod compliance_manager;
 pub mod identity;
 pub mod nft;
 pub mod pips;
+pub mod settlement;
+pub mod state_health;
+pub mod statistics;
 pub mod transaction_payment;
@@ -0,0 +1,198 @@
+// This file is part of the Polymesh distribution (https://github.com/PolymeshAssociation/Polymesh).
+// Copyright (c) 2020 Polymath
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, version 3.
+
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+// General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+//! Runtime API definition for Settlement module.
+
+use frame_support::dispatch::DispatchError;
+use pallet_settlement::{
+    AffirmationStatus, InstructionAuditExport, InstructionId, InstructionTag, LegId, LegRevision,
+    LegV2, LockedAsset, NetObligation, ReceiptUsageDetails, VenueId, VenueSignerUsage,
+};
+use polymesh_primitives::{AccountId, BlockNumber, IdentityId, PortfolioId, Ticker};
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+
+    pub trait SettlementApi {
+        /// Returns the tickers, out of `tickers`, that the venue `venue_id` is not allowed to settle.
+        /// An empty result means that instructions involving all `tickers` can be created under `venue_id`.
+        /// Fails with `InvalidVenue` if `venue_id` doesn't exist.
+        ///
+        /// ```ignore
+        /// curl http://localhost:9933 -H "Content-Type: application/json" -d '{
+        ///     "id":1,
+        ///     "jsonrpc":"2.0",
+        ///     "method": "settlement_getUnauthorizedVenueTickers",
+        ///     "params":[1, ["0x5449434B4552303030303031", "0x5449434B4552303030303032"]]
+        ///   }'
+        /// ```
+        fn get_unauthorized_venue_tickers(venue_id: VenueId, tickers: Vec<Ticker>) -> Result<Vec<Ticker>, DispatchError>;
+
+        /// Returns the revision history of every leg that has been part of `instruction_id`, ordered by leg id.
+        /// Legs created with the instruction have `added_in == 0`, and legs removed by an edit have `removed_in` set.
+        ///
+        /// ```ignore
+        /// curl http://localhost:9933 -H "Content-Type: application/json" -d '{
+        ///     "id":1,
+        ///     "jsonrpc":"2.0",
+        ///     "method": "settlement_getInstructionLegHistory",
+        ///     "params":[1]
+        ///   }'
+        /// ```
+        fn get_instruction_leg_history(instruction_id: InstructionId) -> Vec<(LegId, LegRevision)>;
+
+        /// Returns the instructions awaiting an affirmation from any of the portfolios owned by `did`.
+        ///
+        /// ```ignore
+        /// curl http://localhost:9933 -H "Content-Type: application/json" -d '{
+        ///     "id":1,
+        ///     "jsonrpc":"2.0",
+        ///     "method": "settlement_getPendingInstructions",
+        ///     "params":["0x0100000000000000000000000000000000000000000000000000000000000000"]
+        ///   }'
+        /// ```
+        fn get_pending_instructions(did: IdentityId) -> Vec<InstructionId>;
+
+        /// Returns all legs of `instruction_id`, with their leg id.
+        ///
+        /// ```ignore
+        /// curl http://localhost:9933 -H "Content-Type: application/json" -d '{
+        ///     "id":1,
+        ///     "jsonrpc":"2.0",
+        ///     "method": "settlement_getInstructionLegs",
+        ///     "params":[1]
+        ///   }'
+        /// ```
+        fn get_instruction_legs(instruction_id: InstructionId) -> Vec<(LegId, LegV2)>;
+
+        /// Returns the affirmation status of `portfolio` for `instruction_id`.
+        ///
+        /// ```ignore
+        /// curl http://localhost:9933 -H "Content-Type: application/json" -d '{
+        ///     "id":1,
+        ///     "jsonrpc":"2.0",
+        ///     "method": "settlement_getAffirmationStatus",
+        ///     "params":[1, { "did": "0x0100000000000000000000000000000000000000000000000000000000000000", "kind": "Default"}]
+        ///   }'
+        /// ```
+        fn get_affirmation_status(instruction_id: InstructionId, portfolio: PortfolioId) -> AffirmationStatus;
+
+        /// Dry-runs the execution of `instruction_id`, returning the legs that would fail and the reason of each failure.
+        /// Checks venue filtering, portfolio balances and locks, and compliance, without changing any state.
+        ///
+        /// ```ignore
+        /// curl http://localhost:9933 -H "Content-Type: application/json" -d '{
+        ///     "id":1,
+        ///     "jsonrpc":"2.0",
+        ///     "method": "settlement_canExecuteInstruction",
+        ///     "params":[1]
+        ///   }'
+        /// ```
+        fn can_execute_instruction(instruction_id: InstructionId) -> Result<Vec<(LegId, DispatchError)>, DispatchError>;
+
+        /// Returns the receipts currently claimed by `signer`, ordered by receipt uid, along with the
+        /// instruction and leg each receipt settled and the metadata it was claimed with.
+        /// Receipts that were unclaimed, or only invalidated by the signer, are not included.
+        ///
+        /// ```ignore
+        /// curl http://localhost:9933 -H "Content-Type: application/json" -d '{
+        ///     "id":1,
+        ///     "jsonrpc":"2.0",
+        ///     "method": "settlement_getReceiptsUsed",
+        ///     "params":["5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"]
+        ///   }'
+        /// ```
+        fn get_receipts_used(signer: AccountId) -> Vec<(u64, ReceiptUsageDetails)>;
+
+        /// Returns the instructions carrying `tag`, ordered by instruction id.
+        ///
+        /// ```ignore
+        /// curl http://localhost:9933 -H "Content-Type: application/json" -d '{
+        ///     "id":1,
+        ///     "jsonrpc":"2.0",
+        ///     "method": "settlement_getTaggedInstructions",
+        ///     "params":["0x54524144452d30303030303030303031"]
+        ///   }'
+        /// ```
+        fn get_tagged_instructions(tag: InstructionTag) -> Vec<InstructionId>;
+
+        /// Returns the assets of `portfolio` locked by affirmed instructions awaiting execution,
+        /// with the instruction and leg holding each lock. Locked assets can't be moved out of the portfolio.
+        ///
+        /// ```ignore
+        /// curl http://localhost:9933 -H "Content-Type: application/json" -d '{
+        ///     "id":1,
+        ///     "jsonrpc":"2.0",
+        ///     "method": "settlement_getLockedAssets",
+        ///     "params":[{ "did": "0x0100000000000000000000000000000000000000000000000000000000000000", "kind": "Default"}]
+        ///   }'
+        /// ```
+        fn get_locked_assets(portfolio: PortfolioId) -> Vec<(InstructionId, LegId, LockedAsset)>;
+
+        /// Returns what `did` is due to receive from, and deliver to, each counterparty in each ticker
+        /// through the pending instructions that can settle between the blocks `start` and `end`.
+        /// Instructions settling on affirmation are always included.
+        ///
+        /// ```ignore
+        /// curl http://localhost:9933 -H "Content-Type: application/json" -d '{
+        ///     "id":1,
+        ///     "jsonrpc":"2.0",
+        ///     "method": "settlement_getNetObligations",
+        ///     "params":["0x0100000000000000000000000000000000000000000000000000000000000000", 100, 200]
+        ///   }'
+        /// ```
+        fn get_net_obligations(did: IdentityId, start: BlockNumber, end: BlockNumber) -> Vec<NetObligation>;
+
+        /// Returns the signers of `venue_id`, with the number of receipts each signed that were claimed
+        /// and the block in which one was last claimed. Usage is reset when a signer is removed.
+        ///
+        /// ```ignore
+        /// curl http://localhost:9933 -H "Content-Type: application/json" -d '{
+        ///     "id":1,
+        ///     "jsonrpc":"2.0",
+        ///     "method": "settlement_getVenueSignersUsage",
+        ///     "params":[1]
+        ///   }'
+        /// ```
+        fn get_venue_signers_usage(venue_id: VenueId) -> Vec<(AccountId, VenueSignerUsage<BlockNumber>)>;
+
+        /// Returns the SCALE-encoded audit record of the executed instruction `instruction_id`
+        /// (legs, affirming portfolios, claimed receipts and dates), with its `blake2_256` digest.
+        /// Returns `None` if the instruction wasn't executed, or its record was pruned.
+        ///
+        /// ```ignore
+        /// curl http://localhost:9933 -H "Content-Type: application/json" -d '{
+        ///     "id":1,
+        ///     "jsonrpc":"2.0",
+        ///     "method": "settlement_getInstructionAuditExport",
+        ///     "params":[1]
+        ///   }'
+        /// ```
+        fn get_instruction_audit_export(instruction_id: InstructionId) -> Option<InstructionAuditExport>;
+
+        /// Returns whether `record` is the SCALE-encoded audit record of the executed instruction `instruction_id`,
+        /// by comparing its digest with the one stored on execution.
+        ///
+        /// ```ignore
+        /// curl http://localhost:9933 -H "Content-Type: application/json" -d '{
+        ///     "id":1,
+        ///     "jsonrpc":"2.0",
+        ///     "method": "settlement_verifyInstructionAudit",
+        ///     "params":[1, "0x0100000000000000"]
+        ///   }'
+        /// ```
+        fn verify_instruction_audit(instruction_id: InstructionId, record: Vec<u8>) -> bool;
+    }
+}
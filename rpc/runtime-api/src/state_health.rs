@@ -0,0 +1,35 @@
+// This file is part of the Polymesh distribution (https://github.com/PolymeshAssociation/Polymesh).
+// Copyright (c) 2020 Polymath
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, version 3.
+
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+// General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+//! Runtime API definition for chain-state health.
+
+use polymesh_primitives::StateCounters;
+
+sp_api::decl_runtime_apis! {
+
+    pub trait StateHealthApi {
+        /// Returns the counters of the chain-state entries that grow with usage.
+        ///
+        /// ```ignore
+        /// curl http://localhost:9933 -H "Content-Type: application/json" -d '{
+        ///     "id":1,
+        ///     "jsonrpc":"2.0",
+        ///     "method": "stateHealth_getStateCounters",
+        ///     "params":[]
+        ///   }'
+        /// ```
+        fn get_state_counters() -> StateCounters;
+    }
+}
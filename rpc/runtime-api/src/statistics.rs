@@ -0,0 +1,73 @@
+// This file is part of the Polymesh distribution (https://github.com/PolymeshAssociation/Polymesh).
+// Copyright (c) 2020 Polymath
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, version 3.
+
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+// General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+//! Runtime API definition for Statistics module.
+
+use polymesh_primitives::statistics::{TransferVolume, TransferVolumeEra};
+use polymesh_primitives::transfer_compliance::TransferConditionExemptKey;
+use polymesh_primitives::{ScopeId, Ticker};
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+
+    pub trait StatisticsApi {
+        /// Returns the current transfer volume era.
+        ///
+        /// ```ignore
+        /// curl http://localhost:9933 -H "Content-Type: application/json" -d '{
+        ///     "id":1,
+        ///     "jsonrpc":"2.0",
+        ///     "method": "statistics_getCurrentTransferVolumeEra",
+        ///     "params":[]
+        ///   }'
+        /// ```
+        fn get_current_transfer_volume_era() -> TransferVolumeEra;
+
+        /// Returns the transfer count and volume of `ticker` for each era in `[start_era, end_era]`
+        /// that has transfers. Only assets with transfer volume tracking enabled have volumes.
+        /// At most `MAX_TRANSFER_VOLUME_ERAS` eras, starting at `start_era`, are returned.
+        ///
+        /// ```ignore
+        /// curl http://localhost:9933 -H "Content-Type: application/json" -d '{
+        ///     "id":1,
+        ///     "jsonrpc":"2.0",
+        ///     "method": "statistics_getTransferVolumes",
+        ///     "params":["0x5449434B4552303030303031", 0, 10]
+        ///   }'
+        /// ```
+        fn get_transfer_volumes(
+            ticker: Ticker,
+            start_era: TransferVolumeEra,
+            end_era: TransferVolumeEra
+        ) -> Vec<(TransferVolumeEra, TransferVolume)>;
+
+        /// Returns up to `limit` entities exempt for `exempt_key`, starting after `start_after`.
+        /// Used to export the exempt entities of an asset in pages.
+        ///
+        /// ```ignore
+        /// curl http://localhost:9933 -H "Content-Type: application/json" -d '{
+        ///     "id":1,
+        ///     "jsonrpc":"2.0",
+        ///     "method": "statistics_getExemptEntities",
+        ///     "params":[{ "asset": { "Ticker": "0x5449434B4552303030303031" }, "op": "Count", "claim_type": null }, null, 100]
+        ///   }'
+        /// ```
+        fn get_exempt_entities(
+            exempt_key: TransferConditionExemptKey,
+            start_after: Option<ScopeId>,
+            limit: u32
+        ) -> Vec<ScopeId>;
+    }
+}
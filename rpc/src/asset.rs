@@ -13,7 +13,10 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <http://www.gnu.org/licenses/>.
 
-pub use node_rpc_runtime_api::asset::{AssetApi as AssetRuntimeApi, CanTransferResult};
+pub use node_rpc_runtime_api::asset::{
+    AssetApi as AssetRuntimeApi, CanTransferResult, MAX_ACKNOWLEDGEMENTS_QUERY_SIZE,
+    MAX_DOCUMENT_HASHES_QUERY_SIZE, MAX_HOLDERS_QUERY_SIZE,
+};
 
 use std::{convert::TryInto, sync::Arc};
 
@@ -24,8 +27,13 @@ use jsonrpsee::{
     proc_macros::rpc,
     types::error::{CallError, ErrorCode, ErrorObject},
 };
-use polymesh_primitives::asset::GranularCanTransferResult;
-use polymesh_primitives::{IdentityId, PortfolioId, Ticker};
+use polymesh_primitives::asset::{
+    AssetDescription, AssetHolders, AuthorizedCapital, GranularCanTransferResult,
+};
+use polymesh_primitives::calendar::CheckpointId;
+use polymesh_primitives::{
+    DocumentHash, DocumentHashCheck, DocumentId, IdentityId, Moment, PortfolioId, Ticker,
+};
 use sp_api::{ApiExt, ApiRef, ProvideRuntimeApi};
 use sp_blockchain::HeaderBackend;
 use sp_rpc::number;
@@ -57,6 +65,49 @@ pub trait AssetApi<BlockHash, AccountId> {
         value: number::NumberOrHex,
         at: Option<BlockHash>,
     ) -> RpcResult<GranularCanTransferResult>;
+
+    #[method(name = "asset_authorizedCapital")]
+    fn authorized_capital(
+        &self,
+        ticker: Ticker,
+        at: Option<BlockHash>,
+    ) -> RpcResult<AuthorizedCapital>;
+
+    #[method(name = "asset_verifyDocumentHashes")]
+    fn verify_document_hashes(
+        &self,
+        ticker: Ticker,
+        documents: Vec<(DocumentId, DocumentHash)>,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<DocumentHashCheck>>;
+
+    #[method(name = "asset_describeAsset")]
+    fn describe_asset(
+        &self,
+        ticker: Ticker,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Option<AssetDescription>>;
+
+    #[method(name = "asset_getHolders")]
+    fn get_holders(
+        &self,
+        ticker: Ticker,
+        checkpoint: Option<CheckpointId>,
+        start_after: Option<IdentityId>,
+        limit: u32,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Option<AssetHolders>>;
+
+    #[method(name = "asset_documentAcknowledgements")]
+    fn document_acknowledgements(
+        &self,
+        ticker: Ticker,
+        id: DocumentId,
+        version: u32,
+        start_after: Option<IdentityId>,
+        limit: u32,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<(IdentityId, Moment)>>;
 }
 
 /// An implementation of asset specific RPC methods.
@@ -190,4 +241,125 @@ where
             .into()
         })
     }
+
+    fn authorized_capital(
+        &self,
+        ticker: Ticker,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<AuthorizedCapital> {
+        rpc_forward_call!(
+            self,
+            at,
+            |api: ApiRef<<C as ProvideRuntimeApi<Block>>::Api>, at| api
+                .authorized_capital(at, ticker),
+            "Unable to fetch authorized capital"
+        )
+    }
+
+    fn verify_document_hashes(
+        &self,
+        ticker: Ticker,
+        documents: Vec<(DocumentId, DocumentHash)>,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<DocumentHashCheck>> {
+        if documents.len() > MAX_DOCUMENT_HASHES_QUERY_SIZE {
+            return Err(CallError::Custom(ErrorObject::owned(
+                ErrorCode::InvalidParams.code(),
+                format!(
+                    "At most {} documents can be verified at once",
+                    MAX_DOCUMENT_HASHES_QUERY_SIZE
+                ),
+                None::<()>,
+            ))
+            .into());
+        }
+        rpc_forward_call!(
+            self,
+            at,
+            |api: ApiRef<<C as ProvideRuntimeApi<Block>>::Api>, at| api
+                .verify_document_hashes(at, ticker, documents),
+            "Unable to verify document hashes"
+        )
+    }
+
+    fn describe_asset(
+        &self,
+        ticker: Ticker,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Option<AssetDescription>> {
+        rpc_forward_call!(
+            self,
+            at,
+            |api: ApiRef<<C as ProvideRuntimeApi<Block>>::Api>, at| api.describe_asset(at, ticker),
+            "Unable to describe asset"
+        )
+    }
+
+    fn get_holders(
+        &self,
+        ticker: Ticker,
+        checkpoint: Option<CheckpointId>,
+        start_after: Option<IdentityId>,
+        limit: u32,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Option<AssetHolders>> {
+        if limit > MAX_HOLDERS_QUERY_SIZE {
+            return Err(CallError::Custom(ErrorObject::owned(
+                ErrorCode::InvalidParams.code(),
+                format!(
+                    "At most {} holders can be fetched at once",
+                    MAX_HOLDERS_QUERY_SIZE
+                ),
+                None::<()>,
+            ))
+            .into());
+        }
+        rpc_forward_call!(
+            self,
+            at,
+            |api: ApiRef<<C as ProvideRuntimeApi<Block>>::Api>, at| api.get_holders(
+                at,
+                ticker,
+                checkpoint,
+                start_after,
+                limit
+            ),
+            "Unable to fetch asset holders"
+        )
+    }
+
+    fn document_acknowledgements(
+        &self,
+        ticker: Ticker,
+        id: DocumentId,
+        version: u32,
+        start_after: Option<IdentityId>,
+        limit: u32,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<(IdentityId, Moment)>> {
+        if limit > MAX_ACKNOWLEDGEMENTS_QUERY_SIZE {
+            return Err(CallError::Custom(ErrorObject::owned(
+                ErrorCode::InvalidParams.code(),
+                format!(
+                    "At most {} acknowledgements can be fetched at once",
+                    MAX_ACKNOWLEDGEMENTS_QUERY_SIZE
+                ),
+                None::<()>,
+            ))
+            .into());
+        }
+        rpc_forward_call!(
+            self,
+            at,
+            |api: ApiRef<<C as ProvideRuntimeApi<Block>>::Api>, at| api.document_acknowledgements(
+                at,
+                ticker,
+                id,
+                version,
+                start_after,
+                limit
+            ),
+            "Unable to fetch document acknowledgements"
+        )
+    }
 }
@@ -0,0 +1,100 @@
+// This file is part of the Polymesh distribution (https://github.com/PolymeshAssociation/Polymesh).
+// Copyright (c) 2020 Polymath
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, version 3.
+
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+// General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+use std::sync::Arc;
+
+use codec::Codec;
+use jsonrpsee::{
+    core::RpcResult,
+    proc_macros::rpc,
+    types::error::{CallError, ErrorObject},
+};
+
+use sp_api::{ApiRef, ProvideRuntimeApi};
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+pub use node_rpc_runtime_api::bridge::BridgeApi as BridgeRuntimeApi;
+use pallet_bridge::{Withdrawal, WithdrawalId};
+
+/// Bridge RPC methods.
+#[rpc(client, server)]
+pub trait BridgeApi<BlockHash, AccountId, BlockNumber> {
+    /// Returns all withdrawals that are queued or halted.
+    #[method(name = "bridge_getWithdrawalQueue")]
+    fn get_withdrawal_queue(
+        &self,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<(WithdrawalId, Withdrawal<AccountId, BlockNumber>)>>;
+
+    /// Returns the withdrawal identified by `id`, if any.
+    #[method(name = "bridge_getWithdrawal")]
+    fn get_withdrawal(
+        &self,
+        id: WithdrawalId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Option<Withdrawal<AccountId, BlockNumber>>>;
+}
+
+/// An implementation of Bridge specific RPC methods.
+pub struct Bridge<T, U> {
+    client: Arc<T>,
+    _marker: std::marker::PhantomData<U>,
+}
+
+impl<T, U> Bridge<T, U> {
+    /// Creates a new `Bridge` with the given reference to the client.
+    pub fn new(client: Arc<T>) -> Self {
+        Self {
+            client,
+            _marker: Default::default(),
+        }
+    }
+}
+
+impl<T, Block, AccountId, BlockNumber>
+    BridgeApiServer<<Block as BlockT>::Hash, AccountId, BlockNumber> for Bridge<T, Block>
+where
+    Block: BlockT,
+    T: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+    T::Api: BridgeRuntimeApi<Block, AccountId, BlockNumber>,
+    AccountId: Codec,
+    BlockNumber: Codec,
+{
+    fn get_withdrawal_queue(
+        &self,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<(WithdrawalId, Withdrawal<AccountId, BlockNumber>)>> {
+        rpc_forward_call!(
+            self,
+            at,
+            |api: ApiRef<<T as ProvideRuntimeApi<Block>>::Api>, at| api.get_withdrawal_queue(at),
+            "Unable to fetch the withdrawal queue"
+        )
+    }
+
+    fn get_withdrawal(
+        &self,
+        id: WithdrawalId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Option<Withdrawal<AccountId, BlockNumber>>> {
+        rpc_forward_call!(
+            self,
+            at,
+            |api: ApiRef<<T as ProvideRuntimeApi<Block>>::Api>, at| api.get_withdrawal(at, id),
+            "Unable to fetch the withdrawal"
+        )
+    }
+}
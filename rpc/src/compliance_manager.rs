@@ -25,7 +25,12 @@ use jsonrpsee::{
     proc_macros::rpc,
     types::error::{CallError, ErrorObject},
 };
-use polymesh_primitives::{compliance_manager::AssetComplianceResult, IdentityId, Ticker};
+use polymesh_primitives::{
+    compliance_manager::{
+        AssetComplianceReport, AssetComplianceResult, ComplianceRequirement, RequirementsImpact,
+    },
+    Balance, IdentityId, Ticker,
+};
 use sp_api::ProvideRuntimeApi;
 use sp_blockchain::HeaderBackend;
 use sp_runtime::{generic::BlockId, traits::Block as BlockT};
@@ -47,6 +52,26 @@ pub trait ComplianceManagerApi<BlockHash, AccountId> {
         to_did: Option<IdentityId>,
         at: Option<BlockHash>,
     ) -> RpcResult<AssetComplianceResult>;
+
+    #[method(name = "compliance_complianceReport")]
+    fn compliance_report(
+        &self,
+        ticker: Ticker,
+        from_did: Option<IdentityId>,
+        to_did: Option<IdentityId>,
+        amount: Balance,
+        at: Option<BlockHash>,
+    ) -> RpcResult<AssetComplianceReport>;
+
+    #[method(name = "compliance_simulateRequirements")]
+    fn simulate_requirements(
+        &self,
+        ticker: Ticker,
+        requirements: Vec<ComplianceRequirement>,
+        start_after: Option<IdentityId>,
+        limit: u32,
+        at: Option<BlockHash>,
+    ) -> RpcResult<RequirementsImpact>;
 }
 
 /// An implementation of Compliance manager specific RPC methods.
@@ -95,4 +120,50 @@ where
                 .into()
             })
     }
+
+    fn compliance_report(
+        &self,
+        ticker: Ticker,
+        from_did: Option<IdentityId>,
+        to_did: Option<IdentityId>,
+        amount: Balance,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<AssetComplianceReport> {
+        let api = self.client.runtime_api();
+        // If the block hash is not supplied assume the best block.
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        api.compliance_report(&at, ticker, from_did, to_did, amount)
+            .map_err(|e| {
+                CallError::Custom(ErrorObject::owned(
+                    Error::RuntimeError.into(),
+                    "Unable to fetch compliance report from compliance manager.",
+                    Some(e.to_string()),
+                ))
+                .into()
+            })
+    }
+
+    fn simulate_requirements(
+        &self,
+        ticker: Ticker,
+        requirements: Vec<ComplianceRequirement>,
+        start_after: Option<IdentityId>,
+        limit: u32,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<RequirementsImpact> {
+        let api = self.client.runtime_api();
+        // If the block hash is not supplied assume the best block.
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        api.simulate_requirements(&at, ticker, requirements, start_after, limit)
+            .map_err(|e| {
+                CallError::Custom(ErrorObject::owned(
+                    Error::RuntimeError.into(),
+                    "Unable to simulate compliance requirements.",
+                    Some(e.to_string()),
+                ))
+                .into()
+            })
+    }
 }
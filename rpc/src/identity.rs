@@ -1,7 +1,11 @@
 pub use pallet_identity::types::{
-    AssetDidResult, CddStatus, DidStatus, KeyIdentityData, RpcDidRecords,
+    AccountOverview, AssetDidResult, CddStatus, DidStatus, DidStatusDetails, KeyIdentityData,
+    KeyPermissionsData, RpcDidRecords,
+};
+use polymesh_primitives::{
+    AgreementId, Authorization, AuthorizationType, DispatchableName, IdentityClaim, PalletName,
+    Signatory,
 };
-use polymesh_primitives::{Authorization, AuthorizationType, Signatory};
 
 pub use node_rpc_runtime_api::identity::IdentityApi as IdentityRuntimeApi;
 
@@ -77,6 +81,63 @@ pub trait IdentityApi<BlockHash, IdentityId, Ticker, AccountId, Moment> {
         acc: AccountId,
         at: Option<BlockHash>,
     ) -> RpcResult<Option<KeyIdentityData<IdentityId>>>;
+
+    /// Provide the `AccountOverview` of a given `AccountId`, including:
+    /// - the corresponding DID, key type and permissions,
+    /// - the components of its POLYX balance,
+    /// - its subsidy, if any.
+    ///
+    /// This is an aggregate call provided for UX convenience.
+    #[method(name = "identity_getAccountOverview")]
+    fn get_account_overview(
+        &self,
+        acc: AccountId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<AccountOverview<IdentityId, AccountId>>;
+
+    /// Returns the latest non-expired risk score claim of `did` issued by each scoring provider.
+    #[method(name = "identity_getRiskScores")]
+    fn get_risk_scores(
+        &self,
+        did: IdentityId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<IdentityClaim>>;
+
+    /// Provides the permissions of `key` and the restrictions applying to them.
+    #[method(name = "identity_getKeyPermissions")]
+    fn get_key_permissions(
+        &self,
+        key: AccountId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Option<KeyPermissionsData<IdentityId>>>;
+
+    /// Returns `true` if `key` is currently allowed to call `extrinsic` of `pallet`.
+    #[method(name = "identity_canCall")]
+    fn can_call(
+        &self,
+        key: AccountId,
+        pallet: PalletName,
+        extrinsic: DispatchableName,
+        at: Option<BlockHash>,
+    ) -> RpcResult<bool>;
+
+    /// Provide the status of the given DIDs, with the details of their CDD claims.
+    #[method(name = "identity_getDidStatusDetails")]
+    fn get_did_status_details(
+        &self,
+        dids: Vec<IdentityId>,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<DidStatusDetails>>;
+
+    /// Returns `true` if `did` has consented to the current version of the agreement `id` of `publisher`.
+    #[method(name = "identity_hasConsented")]
+    fn has_consented(
+        &self,
+        did: IdentityId,
+        publisher: IdentityId,
+        id: AgreementId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<bool>;
 }
 
 /// A struct that implements the [`IdentityApi`].
@@ -265,4 +326,109 @@ where
             "Unable to query `get_key_identity_data`."
         )
     }
+
+    fn get_account_overview(
+        &self,
+        acc: AccountId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<AccountOverview<IdentityId, AccountId>> {
+        rpc_forward_call!(
+            self,
+            at,
+            |api: ApiRef<<C as ProvideRuntimeApi<Block>>::Api>, at| {
+                api.get_account_overview(at, acc)
+            },
+            "Unable to query `get_account_overview`."
+        )
+    }
+
+    fn get_risk_scores(
+        &self,
+        did: IdentityId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<IdentityClaim>> {
+        rpc_forward_call!(
+            self,
+            at,
+            |api: ApiRef<<C as ProvideRuntimeApi<Block>>::Api>, at| {
+                api.get_risk_scores(at, did)
+            },
+            "Unable to query `get_risk_scores`."
+        )
+    }
+
+    fn get_key_permissions(
+        &self,
+        key: AccountId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Option<KeyPermissionsData<IdentityId>>> {
+        rpc_forward_call!(
+            self,
+            at,
+            |api: ApiRef<<C as ProvideRuntimeApi<Block>>::Api>, at| {
+                api.get_key_permissions(at, key)
+            },
+            "Unable to query `get_key_permissions`."
+        )
+    }
+
+    fn can_call(
+        &self,
+        key: AccountId,
+        pallet: PalletName,
+        extrinsic: DispatchableName,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<bool> {
+        rpc_forward_call!(
+            self,
+            at,
+            |api: ApiRef<<C as ProvideRuntimeApi<Block>>::Api>, at| {
+                api.can_call(at, key, pallet, extrinsic)
+            },
+            "Unable to query `can_call`."
+        )
+    }
+
+    fn get_did_status_details(
+        &self,
+        dids: Vec<IdentityId>,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<DidStatusDetails>> {
+        if dids.len() > MAX_IDENTITIES_ALLOWED_TO_QUERY as usize {
+            return Err(CallError::Custom(ErrorObject::owned(
+                Error::RuntimeError.into(),
+                "Unable to fetch dids status",
+                Some(format!(
+                    "Provided vector length is more than the maximum allowed length i.e {:?}",
+                    MAX_IDENTITIES_ALLOWED_TO_QUERY
+                )),
+            ))
+            .into());
+        }
+        rpc_forward_call!(
+            self,
+            at,
+            |api: ApiRef<<C as ProvideRuntimeApi<Block>>::Api>, at| {
+                api.get_did_status_details(at, dids)
+            },
+            "Unable to query `get_did_status_details`."
+        )
+    }
+
+    fn has_consented(
+        &self,
+        did: IdentityId,
+        publisher: IdentityId,
+        id: AgreementId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<bool> {
+        rpc_forward_call!(
+            self,
+            at,
+            |api: ApiRef<<C as ProvideRuntimeApi<Block>>::Api>, at| {
+                api.has_consented(at, did, publisher, id)
+            },
+            "Unable to query `has_consented`."
+        )
+    }
 }
@@ -50,8 +50,12 @@ macro_rules! rpc_forward_call {
 }
 
 pub mod asset;
+pub mod bridge;
 pub mod compliance_manager;
 pub mod identity;
 pub mod nft;
 pub mod pips;
+pub mod settlement;
+pub mod state_health;
+pub mod statistics;
 pub mod transaction_payment;
@@ -0,0 +1,417 @@
+// This file is part of the Polymesh distribution (https://github.com/PolymeshAssociation/Polymesh).
+// Copyright (c) 2020 Polymath
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, version 3.
+
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+// General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+use std::sync::Arc;
+
+use crate::Error;
+use jsonrpsee::{
+    core::RpcResult,
+    proc_macros::rpc,
+    types::error::{CallError, ErrorObject},
+};
+
+use frame_support::dispatch::DispatchError;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::Bytes;
+use sp_runtime::generic::BlockId;
+use sp_runtime::traits::Block as BlockT;
+
+pub use node_rpc_runtime_api::settlement::SettlementApi as SettlementRuntimeApi;
+use pallet_settlement::{
+    AffirmationStatus, InstructionAuditExport, InstructionId, InstructionTag, LegId, LegRevision,
+    LegV2, LockedAsset, NetObligation, ReceiptUsageDetails, VenueId, VenueSignerUsage,
+};
+use polymesh_primitives::{AccountId, BlockNumber, IdentityId, PortfolioId, Ticker};
+
+#[rpc(client, server)]
+pub trait SettlementApi<BlockHash> {
+    #[method(name = "settlement_getUnauthorizedVenueTickers")]
+    fn get_unauthorized_venue_tickers(
+        &self,
+        venue_id: VenueId,
+        tickers: Vec<Ticker>,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Result<Vec<Ticker>, DispatchError>>;
+
+    #[method(name = "settlement_getInstructionLegHistory")]
+    fn get_instruction_leg_history(
+        &self,
+        instruction_id: InstructionId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<(LegId, LegRevision)>>;
+
+    #[method(name = "settlement_getPendingInstructions")]
+    fn get_pending_instructions(
+        &self,
+        did: IdentityId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<InstructionId>>;
+
+    #[method(name = "settlement_getInstructionLegs")]
+    fn get_instruction_legs(
+        &self,
+        instruction_id: InstructionId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<(LegId, LegV2)>>;
+
+    #[method(name = "settlement_getAffirmationStatus")]
+    fn get_affirmation_status(
+        &self,
+        instruction_id: InstructionId,
+        portfolio: PortfolioId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<AffirmationStatus>;
+
+    #[method(name = "settlement_canExecuteInstruction")]
+    fn can_execute_instruction(
+        &self,
+        instruction_id: InstructionId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Result<Vec<(LegId, DispatchError)>, DispatchError>>;
+
+    #[method(name = "settlement_getReceiptsUsed")]
+    fn get_receipts_used(
+        &self,
+        signer: AccountId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<(u64, ReceiptUsageDetails)>>;
+
+    #[method(name = "settlement_getTaggedInstructions")]
+    fn get_tagged_instructions(
+        &self,
+        tag: InstructionTag,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<InstructionId>>;
+
+    #[method(name = "settlement_getLockedAssets")]
+    fn get_locked_assets(
+        &self,
+        portfolio: PortfolioId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<(InstructionId, LegId, LockedAsset)>>;
+
+    #[method(name = "settlement_getNetObligations")]
+    fn get_net_obligations(
+        &self,
+        did: IdentityId,
+        start: BlockNumber,
+        end: BlockNumber,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<NetObligation>>;
+
+    #[method(name = "settlement_getVenueSignersUsage")]
+    fn get_venue_signers_usage(
+        &self,
+        venue_id: VenueId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<(AccountId, VenueSignerUsage<BlockNumber>)>>;
+
+    #[method(name = "settlement_getInstructionAuditExport")]
+    fn get_instruction_audit_export(
+        &self,
+        instruction_id: InstructionId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Option<InstructionAuditExport>>;
+
+    #[method(name = "settlement_verifyInstructionAudit")]
+    fn verify_instruction_audit(
+        &self,
+        instruction_id: InstructionId,
+        record: Bytes,
+        at: Option<BlockHash>,
+    ) -> RpcResult<bool>;
+}
+
+/// An implementation of Settlement specific RPC methods.
+pub struct Settlement<T, U> {
+    client: Arc<T>,
+    _marker: std::marker::PhantomData<U>,
+}
+
+impl<T, U> Settlement<T, U> {
+    /// Creates a new `Settlement` with the given reference to the client.
+    pub fn new(client: Arc<T>) -> Self {
+        Self {
+            client,
+            _marker: Default::default(),
+        }
+    }
+}
+
+impl<T, Block> SettlementApiServer<<Block as BlockT>::Hash> for Settlement<T, Block>
+where
+    Block: BlockT,
+    T: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+    T::Api: SettlementRuntimeApi<Block>,
+{
+    fn get_unauthorized_venue_tickers(
+        &self,
+        venue_id: VenueId,
+        tickers: Vec<Ticker>,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Result<Vec<Ticker>, DispatchError>> {
+        let api = self.client.runtime_api();
+        // If the block hash is not supplied assume the best block.
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        api.get_unauthorized_venue_tickers(&at, venue_id, tickers)
+            .map_err(|e| {
+                CallError::Custom(ErrorObject::owned(
+                    Error::RuntimeError.into(),
+                    "Unable to call get_unauthorized_venue_tickers runtime",
+                    Some(e.to_string()),
+                ))
+                .into()
+            })
+    }
+
+    fn get_instruction_leg_history(
+        &self,
+        instruction_id: InstructionId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<(LegId, LegRevision)>> {
+        let api = self.client.runtime_api();
+        // If the block hash is not supplied assume the best block.
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        api.get_instruction_leg_history(&at, instruction_id)
+            .map_err(|e| {
+                CallError::Custom(ErrorObject::owned(
+                    Error::RuntimeError.into(),
+                    "Unable to call get_instruction_leg_history runtime",
+                    Some(e.to_string()),
+                ))
+                .into()
+            })
+    }
+
+    fn get_pending_instructions(
+        &self,
+        did: IdentityId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<InstructionId>> {
+        let api = self.client.runtime_api();
+        // If the block hash is not supplied assume the best block.
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        api.get_pending_instructions(&at, did).map_err(|e| {
+            CallError::Custom(ErrorObject::owned(
+                Error::RuntimeError.into(),
+                "Unable to call get_pending_instructions runtime",
+                Some(e.to_string()),
+            ))
+            .into()
+        })
+    }
+
+    fn get_instruction_legs(
+        &self,
+        instruction_id: InstructionId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<(LegId, LegV2)>> {
+        let api = self.client.runtime_api();
+        // If the block hash is not supplied assume the best block.
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        api.get_instruction_legs(&at, instruction_id).map_err(|e| {
+            CallError::Custom(ErrorObject::owned(
+                Error::RuntimeError.into(),
+                "Unable to call get_instruction_legs runtime",
+                Some(e.to_string()),
+            ))
+            .into()
+        })
+    }
+
+    fn get_affirmation_status(
+        &self,
+        instruction_id: InstructionId,
+        portfolio: PortfolioId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<AffirmationStatus> {
+        let api = self.client.runtime_api();
+        // If the block hash is not supplied assume the best block.
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        api.get_affirmation_status(&at, instruction_id, portfolio)
+            .map_err(|e| {
+                CallError::Custom(ErrorObject::owned(
+                    Error::RuntimeError.into(),
+                    "Unable to call get_affirmation_status runtime",
+                    Some(e.to_string()),
+                ))
+                .into()
+            })
+    }
+
+    fn can_execute_instruction(
+        &self,
+        instruction_id: InstructionId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Result<Vec<(LegId, DispatchError)>, DispatchError>> {
+        let api = self.client.runtime_api();
+        // If the block hash is not supplied assume the best block.
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        api.can_execute_instruction(&at, instruction_id)
+            .map_err(|e| {
+                CallError::Custom(ErrorObject::owned(
+                    Error::RuntimeError.into(),
+                    "Unable to call can_execute_instruction runtime",
+                    Some(e.to_string()),
+                ))
+                .into()
+            })
+    }
+
+    fn get_receipts_used(
+        &self,
+        signer: AccountId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<(u64, ReceiptUsageDetails)>> {
+        let api = self.client.runtime_api();
+        // If the block hash is not supplied assume the best block.
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        api.get_receipts_used(&at, signer).map_err(|e| {
+            CallError::Custom(ErrorObject::owned(
+                Error::RuntimeError.into(),
+                "Unable to call get_receipts_used runtime",
+                Some(e.to_string()),
+            ))
+            .into()
+        })
+    }
+
+    fn get_tagged_instructions(
+        &self,
+        tag: InstructionTag,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<InstructionId>> {
+        let api = self.client.runtime_api();
+        // If the block hash is not supplied assume the best block.
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        api.get_tagged_instructions(&at, tag).map_err(|e| {
+            CallError::Custom(ErrorObject::owned(
+                Error::RuntimeError.into(),
+                "Unable to call get_tagged_instructions runtime",
+                Some(e.to_string()),
+            ))
+            .into()
+        })
+    }
+
+    fn get_locked_assets(
+        &self,
+        portfolio: PortfolioId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<(InstructionId, LegId, LockedAsset)>> {
+        let api = self.client.runtime_api();
+        // If the block hash is not supplied assume the best block.
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        api.get_locked_assets(&at, portfolio).map_err(|e| {
+            CallError::Custom(ErrorObject::owned(
+                Error::RuntimeError.into(),
+                "Unable to call get_locked_assets runtime",
+                Some(e.to_string()),
+            ))
+            .into()
+        })
+    }
+
+    fn get_net_obligations(
+        &self,
+        did: IdentityId,
+        start: BlockNumber,
+        end: BlockNumber,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<NetObligation>> {
+        let api = self.client.runtime_api();
+        // If the block hash is not supplied assume the best block.
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        api.get_net_obligations(&at, did, start, end).map_err(|e| {
+            CallError::Custom(ErrorObject::owned(
+                Error::RuntimeError.into(),
+                "Unable to call get_net_obligations runtime",
+                Some(e.to_string()),
+            ))
+            .into()
+        })
+    }
+
+    fn get_venue_signers_usage(
+        &self,
+        venue_id: VenueId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<(AccountId, VenueSignerUsage<BlockNumber>)>> {
+        let api = self.client.runtime_api();
+        // If the block hash is not supplied assume the best block.
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        api.get_venue_signers_usage(&at, venue_id).map_err(|e| {
+            CallError::Custom(ErrorObject::owned(
+                Error::RuntimeError.into(),
+                "Unable to call get_venue_signers_usage runtime",
+                Some(e.to_string()),
+            ))
+            .into()
+        })
+    }
+
+    fn get_instruction_audit_export(
+        &self,
+        instruction_id: InstructionId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Option<InstructionAuditExport>> {
+        let api = self.client.runtime_api();
+        // If the block hash is not supplied assume the best block.
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        api.get_instruction_audit_export(&at, instruction_id)
+            .map_err(|e| {
+                CallError::Custom(ErrorObject::owned(
+                    Error::RuntimeError.into(),
+                    "Unable to call get_instruction_audit_export runtime",
+                    Some(e.to_string()),
+                ))
+                .into()
+            })
+    }
+
+    fn verify_instruction_audit(
+        &self,
+        instruction_id: InstructionId,
+        record: Bytes,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<bool> {
+        let api = self.client.runtime_api();
+        // If the block hash is not supplied assume the best block.
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        api.verify_instruction_audit(&at, instruction_id, record.to_vec())
+            .map_err(|e| {
+                CallError::Custom(ErrorObject::owned(
+                    Error::RuntimeError.into(),
+                    "Unable to call verify_instruction_audit runtime",
+                    Some(e.to_string()),
+                ))
+                .into()
+            })
+    }
+}
@@ -0,0 +1,67 @@
+// This file is part of the Polymesh distribution (https://github.com/PolymeshAssociation/Polymesh).
+// Copyright (c) 2020 Polymath
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, version 3.
+
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+// General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+use std::sync::Arc;
+
+use jsonrpsee::{
+    core::RpcResult,
+    proc_macros::rpc,
+    types::error::{CallError, ErrorObject},
+};
+
+use sp_api::{ApiRef, ProvideRuntimeApi};
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+pub use node_rpc_runtime_api::state_health::StateHealthApi as StateHealthRuntimeApi;
+use polymesh_primitives::StateCounters;
+
+#[rpc(client, server)]
+pub trait StateHealthApi<BlockHash> {
+    #[method(name = "stateHealth_getStateCounters")]
+    fn get_state_counters(&self, at: Option<BlockHash>) -> RpcResult<StateCounters>;
+}
+
+/// An implementation of chain-state health RPC methods.
+pub struct StateHealth<T, U> {
+    client: Arc<T>,
+    _marker: std::marker::PhantomData<U>,
+}
+
+impl<T, U> StateHealth<T, U> {
+    /// Creates a new `StateHealth` with the given reference to the client.
+    pub fn new(client: Arc<T>) -> Self {
+        Self {
+            client,
+            _marker: Default::default(),
+        }
+    }
+}
+
+impl<T, Block> StateHealthApiServer<<Block as BlockT>::Hash> for StateHealth<T, Block>
+where
+    Block: BlockT,
+    T: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+    T::Api: StateHealthRuntimeApi<Block>,
+{
+    fn get_state_counters(&self, at: Option<<Block as BlockT>::Hash>) -> RpcResult<StateCounters> {
+        rpc_forward_call!(
+            self,
+            at,
+            |api: ApiRef<<T as ProvideRuntimeApi<Block>>::Api>, at| api.get_state_counters(at),
+            "Unable to fetch the state counters"
+        )
+    }
+}
@@ -0,0 +1,130 @@
+// This file is part of the Polymesh distribution (https://github.com/PolymeshAssociation/Polymesh).
+// Copyright (c) 2020 Polymath
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, version 3.
+
+// This program is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the GNU
+// General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <http://www.gnu.org/licenses/>.
+
+use std::sync::Arc;
+
+use jsonrpsee::{
+    core::RpcResult,
+    proc_macros::rpc,
+    types::error::{CallError, ErrorObject},
+};
+
+use sp_api::{ApiRef, ProvideRuntimeApi};
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+pub use node_rpc_runtime_api::statistics::StatisticsApi as StatisticsRuntimeApi;
+use polymesh_primitives::statistics::{TransferVolume, TransferVolumeEra};
+use polymesh_primitives::transfer_compliance::TransferConditionExemptKey;
+use polymesh_primitives::{ScopeId, Ticker};
+
+#[rpc(client, server)]
+pub trait StatisticsApi<BlockHash> {
+    #[method(name = "statistics_getCurrentTransferVolumeEra")]
+    fn get_current_transfer_volume_era(
+        &self,
+        at: Option<BlockHash>,
+    ) -> RpcResult<TransferVolumeEra>;
+
+    #[method(name = "statistics_getTransferVolumes")]
+    fn get_transfer_volumes(
+        &self,
+        ticker: Ticker,
+        start_era: TransferVolumeEra,
+        end_era: TransferVolumeEra,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<(TransferVolumeEra, TransferVolume)>>;
+
+    #[method(name = "statistics_getExemptEntities")]
+    fn get_exempt_entities(
+        &self,
+        exempt_key: TransferConditionExemptKey,
+        start_after: Option<ScopeId>,
+        limit: u32,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<ScopeId>>;
+}
+
+/// An implementation of Statistics specific RPC methods.
+pub struct Statistics<T, U> {
+    client: Arc<T>,
+    _marker: std::marker::PhantomData<U>,
+}
+
+impl<T, U> Statistics<T, U> {
+    /// Creates a new `Statistics` with the given reference to the client.
+    pub fn new(client: Arc<T>) -> Self {
+        Self {
+            client,
+            _marker: Default::default(),
+        }
+    }
+}
+
+impl<T, Block> StatisticsApiServer<<Block as BlockT>::Hash> for Statistics<T, Block>
+where
+    Block: BlockT,
+    T: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+    T::Api: StatisticsRuntimeApi<Block>,
+{
+    fn get_current_transfer_volume_era(
+        &self,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<TransferVolumeEra> {
+        rpc_forward_call!(
+            self,
+            at,
+            |api: ApiRef<<T as ProvideRuntimeApi<Block>>::Api>, at| api
+                .get_current_transfer_volume_era(at),
+            "Unable to fetch the current transfer volume era"
+        )
+    }
+
+    fn get_transfer_volumes(
+        &self,
+        ticker: Ticker,
+        start_era: TransferVolumeEra,
+        end_era: TransferVolumeEra,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<(TransferVolumeEra, TransferVolume)>> {
+        rpc_forward_call!(
+            self,
+            at,
+            |api: ApiRef<<T as ProvideRuntimeApi<Block>>::Api>, at| api
+                .get_transfer_volumes(at, ticker, start_era, end_era),
+            "Unable to fetch transfer volumes"
+        )
+    }
+
+    fn get_exempt_entities(
+        &self,
+        exempt_key: TransferConditionExemptKey,
+        start_after: Option<ScopeId>,
+        limit: u32,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<ScopeId>> {
+        rpc_forward_call!(
+            self,
+            at,
+            |api: ApiRef<<T as ProvideRuntimeApi<Block>>::Api>, at| api.get_exempt_entities(
+                at,
+                exempt_key,
+                start_after,
+                limit
+            ),
+            "Unable to fetch exempt entities"
+        )
+    }
+}
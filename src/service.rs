@@ -110,6 +110,10 @@ pub trait RuntimeApiCollection:
     + pallet_group_rpc_runtime_api::GroupApi<Block>
     + node_rpc_runtime_api::compliance_manager::ComplianceManagerApi<Block, AccountId>
     + node_rpc_runtime_api::nft::NFTApi<Block>
+    + node_rpc_runtime_api::settlement::SettlementApi<Block>
+    + node_rpc_runtime_api::statistics::StatisticsApi<Block>
+    + node_rpc_runtime_api::state_health::StateHealthApi<Block>
+    + node_rpc_runtime_api::bridge::BridgeApi<Block, AccountId, BlockNumber>
 where
     <Self as sp_api::ApiExt<Block>>::StateBackend: sp_api::StateBackend<BlakeTwo256>,
 {
@@ -135,7 +139,11 @@ where
         + node_rpc_runtime_api::asset::AssetApi<Block, AccountId>
         + pallet_group_rpc_runtime_api::GroupApi<Block>
         + node_rpc_runtime_api::compliance_manager::ComplianceManagerApi<Block, AccountId>
-        + node_rpc_runtime_api::nft::NFTApi<Block>,
+        + node_rpc_runtime_api::nft::NFTApi<Block>
+        + node_rpc_runtime_api::settlement::SettlementApi<Block>
+        + node_rpc_runtime_api::statistics::StatisticsApi<Block>
+        + node_rpc_runtime_api::state_health::StateHealthApi<Block>
+        + node_rpc_runtime_api::bridge::BridgeApi<Block, AccountId, BlockNumber>,
     <Self as sp_api::ApiExt<Block>>::StateBackend: sp_api::StateBackend<BlakeTwo256>,
 {
 }